@@ -0,0 +1,244 @@
+//! Headless compositor benchmark: frame cost of `term_wm::render_app` over
+//! a grid of idle panes, with window damage tracking off and on.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use term_wm::actions::TermWmAction;
+use term_wm::component_context::ComponentContext;
+use term_wm::engine::CoreEngine;
+use term_wm::hitbox_registry::HitboxRegistry;
+use term_wm::layout::Direction;
+use term_wm::layout::tiling::{LayoutNode, TilingLayout};
+use term_wm::window::{LayerManager, WindowKey, WindowManager};
+use term_wm::wm_config::WmConfig;
+use term_wm::{AppContext, Component, RenderBackend};
+use term_wm_console::RatatuiBackend;
+use term_wm_console::draw_plan_renderer::DrawPlanRenderer;
+use term_wm_layout_engine::LayoutRect;
+
+use crate::NoiseField;
+
+/// Screen size the panes are tiled into.
+const SCREEN: Rect = Rect {
+    x: 0,
+    y: 0,
+    width: 240,
+    height: 64,
+};
+
+/// Frames timed per mode, after one warm-up frame.
+const FRAMES: u32 = 200;
+
+/// A pane whose content never changes, like a shell waiting at its prompt.
+/// Drawing it costs a full noise fill, about what a terminal pane spends
+/// converting its grid.
+struct IdlePane {
+    seed: u64,
+}
+
+impl Component<TermWmAction> for IdlePane {
+    fn render(
+        &mut self,
+        backend: &mut dyn RenderBackend,
+        area: LayoutRect,
+        _ctx: &ComponentContext,
+        _registry: &mut HitboxRegistry,
+    ) {
+        let Some(rb) = backend.as_any_mut().downcast_mut::<RatatuiBackend>() else {
+            return;
+        };
+        let area = Rect {
+            x: area.x.max(0) as u16,
+            y: area.y.max(0) as u16,
+            width: area.width,
+            height: area.height,
+        }
+        .intersection(rb.buffer.area);
+        NoiseField { state: self.seed }.fill(&mut rb.buffer, area, 0);
+    }
+
+    fn needs_render(&self) -> bool {
+        false
+    }
+}
+
+/// Frame timings of one compositor mode.
+#[derive(Debug, Clone, Copy)]
+pub struct ModeTiming {
+    pub total: Duration,
+    pub best: Duration,
+    pub worst: Duration,
+    pub frames: u32,
+}
+
+impl ModeTiming {
+    fn average_ms(&self) -> f64 {
+        if self.frames == 0 {
+            return 0.0;
+        }
+        self.total.as_secs_f64() * 1_000.0 / f64::from(self.frames)
+    }
+}
+
+/// Before/after frame cost for `panes` idle panes.
+#[derive(Debug, Clone, Copy)]
+pub struct CompositorReport {
+    pub panes: usize,
+    pub full_redraw: ModeTiming,
+    pub damage_tracked: ModeTiming,
+}
+
+impl fmt::Display for CompositorReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let line = |f: &mut fmt::Formatter<'_>, label: &str, t: &ModeTiming| {
+            writeln!(
+                f,
+                "{label}: avg {:.3} ms | best {:.3} ms | worst {:.3} ms",
+                t.average_ms(),
+                t.best.as_secs_f64() * 1_000.0,
+                t.worst.as_secs_f64() * 1_000.0,
+            )
+        };
+        writeln!(
+            f,
+            "Compositor bench: {} idle panes, {}x{}, {} frames per mode.",
+            self.panes, SCREEN.width, SCREEN.height, self.full_redraw.frames
+        )?;
+        line(f, "Damage tracking off", &self.full_redraw)?;
+        line(f, "Damage tracking on ", &self.damage_tracked)?;
+        let after = self.damage_tracked.average_ms();
+        if after > 0.0 {
+            writeln!(f, "Speedup: {:.1}x", self.full_redraw.average_ms() / after)?;
+        }
+        Ok(())
+    }
+}
+
+/// Time `render_app` over `panes` idle panes without and with damage
+/// tracking.
+pub fn run_idle_panes(panes: usize) -> CompositorReport {
+    CompositorReport {
+        panes,
+        full_redraw: time_frames(panes, false, FRAMES),
+        damage_tracked: time_frames(panes, true, FRAMES),
+    }
+}
+
+fn time_frames(panes: usize, damage_tracking: bool, frames: u32) -> ModeTiming {
+    let mut wm = idle_wm(panes);
+    let mut engine = CoreEngine::new();
+    let mut renderer = DrawPlanRenderer::new();
+    renderer.window_damage_mut().set_enabled(damage_tracking);
+    let mut backend = RatatuiBackend::new_simple(Buffer::empty(SCREEN), SCREEN);
+
+    // Warm-up: fills every window cache and sizes the buffers.
+    term_wm::render_app(&mut backend, &mut wm, &mut engine, &mut renderer);
+
+    let mut timing = ModeTiming {
+        total: Duration::ZERO,
+        best: Duration::MAX,
+        worst: Duration::ZERO,
+        frames,
+    };
+    for _ in 0..frames {
+        // Like `Terminal::draw`, every frame starts from a blank buffer.
+        backend.buffer.reset();
+        let start = Instant::now();
+        term_wm::render_app(&mut backend, &mut wm, &mut engine, &mut renderer);
+        let elapsed = start.elapsed();
+        timing.total += elapsed;
+        timing.best = timing.best.min(elapsed);
+        timing.worst = timing.worst.max(elapsed);
+    }
+    timing
+}
+
+/// A window manager with `panes` idle panes tiled in a near-square grid.
+fn idle_wm(panes: usize) -> WindowManager<IdlePane> {
+    let mut wm = WindowManager::<IdlePane>::with_config(
+        WmConfig::default(),
+        Arc::new(AppContext::new(
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION"),
+        )),
+        None,
+        LayerManager::new(),
+        HashMap::new(),
+    );
+    wm.set_panel_visible(false);
+    let columns = (panes as f64).sqrt().ceil().max(1.0) as usize;
+    let keys: Vec<_> = (0..panes as u64)
+        .map(|i| wm.create_window(IdlePane { seed: i + 1 }))
+        .collect();
+    let rows = keys
+        .chunks(columns)
+        .map(|row| {
+            split(
+                Direction::Horizontal,
+                row.iter().copied().map(LayoutNode::Leaf),
+            )
+        })
+        .collect::<Vec<_>>();
+    wm.set_managed_layout(TilingLayout::new(split(Direction::Vertical, rows)));
+    wm
+}
+
+fn split(
+    direction: Direction,
+    children: impl IntoIterator<Item = LayoutNode<WindowKey>>,
+) -> LayoutNode<WindowKey> {
+    let children: Vec<_> = children.into_iter().collect();
+    LayoutNode::Split {
+        direction,
+        weights: vec![1; children.len()],
+        children,
+        resizable: true,
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn damage_tracking_reuses_idle_panes() {
+        let mut wm = idle_wm(6);
+        let mut engine = CoreEngine::new();
+        let mut renderer = DrawPlanRenderer::new();
+        let mut backend = RatatuiBackend::new_simple(Buffer::empty(SCREEN), SCREEN);
+        term_wm::render_app(&mut backend, &mut wm, &mut engine, &mut renderer);
+        let first = backend.buffer.clone();
+        assert_eq!(renderer.window_damage().stats().redrawn, 6);
+
+        backend.buffer.reset();
+        term_wm::render_app(&mut backend, &mut wm, &mut engine, &mut renderer);
+        let stats = renderer.window_damage().stats();
+        assert_eq!((stats.redrawn, stats.reused), (0, 6));
+        assert_eq!(backend.buffer, first);
+    }
+
+    #[test]
+    fn report_lists_both_modes() {
+        let timing = |ms| ModeTiming {
+            total: Duration::from_millis(ms * 10),
+            best: Duration::from_millis(ms),
+            worst: Duration::from_millis(ms),
+            frames: 10,
+        };
+        let report = CompositorReport {
+            panes: 4,
+            full_redraw: timing(4),
+            damage_tracked: timing(1),
+        }
+        .to_string();
+        assert!(report.contains("4 idle panes"));
+        assert!(report.contains("Damage tracking off: avg 4.000 ms"));
+        assert!(report.contains("Speedup: 4.0x"));
+    }
+}
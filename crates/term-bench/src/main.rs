@@ -19,6 +19,9 @@ use ratatui::{
     widgets::Paragraph,
 };
 
+mod compositor;
+mod output_stream;
+
 const GLYPHS: [&str; 10] = [".", ",", ":", "-", ";", "+", "*", "x", "#", "@"];
 
 #[derive(Parser, Debug)]
@@ -40,6 +43,16 @@ struct BenchCli {
     /// Target frames per second to pace rendering so comparisons are repeatable.
     #[arg(short = 'f', long = "fps", value_name = "FPS", default_value_t = 60.0)]
     target_fps: f64,
+
+    /// Skip the terminal run and time the compositor over this many idle
+    /// panes, with window damage tracking off and on.
+    #[arg(long = "idle-panes", value_name = "COUNT")]
+    idle_panes: Option<usize>,
+
+    /// Skip the terminal run and replay this many MiB of synthetic PTY
+    /// output through the gateway's output batching and codecs.
+    #[arg(long = "output-stream", value_name = "MIB")]
+    output_stream_mib: Option<usize>,
 }
 
 impl BenchCli {
@@ -56,6 +69,8 @@ struct BenchConfig {
     duration: Duration,
     target_fps: f64,
     frame_budget: Duration,
+    idle_panes: Option<usize>,
+    output_stream_mib: Option<usize>,
 }
 
 impl TryFrom<&BenchCli> for BenchConfig {
@@ -68,10 +83,21 @@ impl TryFrom<&BenchCli> for BenchConfig {
         if !(1.0..=240.0).contains(&cli.target_fps) {
             return Err("fps must be between 1 and 240".to_string());
         }
+        if cli.idle_panes.is_some_and(|n| !(1..=256).contains(&n)) {
+            return Err("idle panes must be between 1 and 256".to_string());
+        }
+        if cli
+            .output_stream_mib
+            .is_some_and(|n| !(1..=1024).contains(&n))
+        {
+            return Err("output stream size must be between 1 and 1024 MiB".to_string());
+        }
         Ok(Self {
             duration: cli.duration(),
             target_fps: cli.target_fps,
             frame_budget: cli.frame_budget(),
+            idle_panes: cli.idle_panes,
+            output_stream_mib: cli.output_stream_mib,
         })
     }
 }
@@ -82,6 +108,15 @@ fn main() -> io::Result<()> {
     let config = BenchConfig::try_from(&args)
         .map_err(|msg| io::Error::new(io::ErrorKind::InvalidInput, msg))?;
 
+    if let Some(panes) = config.idle_panes {
+        println!("{}", compositor::run_idle_panes(panes));
+        return Ok(());
+    }
+    if let Some(mib) = config.output_stream_mib {
+        println!("{}", output_stream::run_output_stream(mib * 1024 * 1024));
+        return Ok(());
+    }
+
     terminal::enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(
@@ -471,6 +506,8 @@ mod tests {
         let cli = BenchCli {
             duration_seconds: 5.0,
             target_fps: 30.0,
+            idle_panes: None,
+            output_stream_mib: None,
         };
         let cfg = BenchConfig::try_from(&cli).expect("valid config");
         assert_eq!(cfg.target_fps, 30.0);
@@ -480,6 +517,8 @@ mod tests {
         let bad = BenchCli {
             duration_seconds: 0.1,
             target_fps: 60.0,
+            idle_panes: None,
+            output_stream_mib: None,
         };
         assert!(BenchConfig::try_from(&bad).is_err());
 
@@ -487,8 +526,28 @@ mod tests {
         let bad2 = BenchCli {
             duration_seconds: 2.0,
             target_fps: 1000.0,
+            idle_panes: None,
+            output_stream_mib: None,
         };
         assert!(BenchConfig::try_from(&bad2).is_err());
+
+        // invalid idle pane count
+        let bad3 = BenchCli {
+            duration_seconds: 2.0,
+            target_fps: 60.0,
+            idle_panes: Some(0),
+            output_stream_mib: None,
+        };
+        assert!(BenchConfig::try_from(&bad3).is_err());
+
+        // invalid output stream size
+        let bad4 = BenchCli {
+            duration_seconds: 2.0,
+            target_fps: 60.0,
+            idle_panes: None,
+            output_stream_mib: Some(0),
+        };
+        assert!(BenchConfig::try_from(&bad4).is_err());
     }
 
     #[test]
@@ -512,6 +571,8 @@ mod tests {
         let cli = BenchCli {
             duration_seconds: 1.0,
             target_fps: 60.0,
+            idle_panes: None,
+            output_stream_mib: None,
         };
         let cfg = BenchConfig::try_from(&cli).unwrap();
         let report = stats.final_report(&cfg);
@@ -606,6 +667,8 @@ mod tests {
         let cli = BenchCli {
             duration_seconds: 2.0,
             target_fps: 60.0,
+            idle_panes: None,
+            output_stream_mib: None,
         };
         let cfg = BenchConfig::try_from(&cli).unwrap();
         let v = build_overlay_lines(&stats, &cfg);
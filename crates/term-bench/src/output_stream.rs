@@ -0,0 +1,237 @@
+//! Headless gateway output benchmark: replays a synthetic PTY stream through
+//! the gateway's output batcher and each output codec, reporting stream
+//! frames, bytes on the wire, codec throughput and the latency batching
+//! adds.
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use term_session_muxio_service_definitions::{OutputCodec, OutputDecoder, OutputEncoder};
+use term_session_server::output_batch::{MAX_BATCH_BYTES, OutputBatcher};
+
+use crate::NoiseField;
+
+/// Gap between PTY reads while a command floods the terminal.
+const BURST_READ_GAP: Duration = Duration::from_micros(50);
+
+/// Largest single PTY read.
+const MAX_READ_BYTES: u32 = 4096;
+
+/// Keystrokes typed after each burst, and the pause between them.
+const ECHOES_PER_BURST: usize = 20;
+const ECHO_GAP: Duration = Duration::from_millis(120);
+
+/// Bytes of build log per burst.
+const BURST_BYTES: usize = 256 * 1024;
+
+/// One PTY read, at a virtual time offset from the start of the stream.
+struct Read {
+    at: Duration,
+    data: Vec<u8>,
+    echo: bool,
+}
+
+/// Bursts of build-log output, each followed by a stretch of typing.
+fn workload(bytes: usize) -> Vec<Read> {
+    let mut rng = NoiseField { state: 0x5eed };
+    let mut reads = Vec::new();
+    let mut at = Duration::ZERO;
+    let mut line_no = 0u32;
+    let mut produced = 0;
+    while produced < bytes {
+        let mut log = Vec::with_capacity(BURST_BYTES);
+        while log.len() < BURST_BYTES.min(bytes - produced) {
+            line_no += 1;
+            let krate = rng.next() % 300;
+            log.extend_from_slice(
+                format!(
+                    "\x1b[1m\x1b[32m   Compiling\x1b[0m crate-{krate} v0.{}.{} (/src/crates/crate-{krate}) [{line_no}]\r\n",
+                    rng.next() % 20,
+                    rng.next() % 10,
+                )
+                .as_bytes(),
+            );
+        }
+        produced += log.len();
+        let mut rest = log.as_slice();
+        while !rest.is_empty() {
+            let n = (1 + rng.next() % MAX_READ_BYTES) as usize;
+            let (data, tail) = rest.split_at(n.min(rest.len()));
+            reads.push(Read {
+                at,
+                data: data.to_vec(),
+                echo: false,
+            });
+            rest = tail;
+            at += BURST_READ_GAP;
+        }
+        for _ in 0..ECHOES_PER_BURST {
+            at += ECHO_GAP;
+            let key = b'a' + (rng.next() % 26) as u8;
+            reads.push(Read {
+                at,
+                data: vec![key],
+                echo: true,
+            });
+        }
+        at += ECHO_GAP;
+    }
+    reads
+}
+
+/// Results of one batching/codec combination.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StreamMode {
+    pub frames: usize,
+    pub wire_bytes: usize,
+    /// Wall time spent encoding and decoding.
+    pub codec_time: Duration,
+    /// Largest delay the batcher added to a keystroke echo.
+    pub worst_echo: Duration,
+    /// Largest delay the batcher added to any read.
+    pub worst_delay: Duration,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct OutputStreamReport {
+    pub input_bytes: usize,
+    pub reads: usize,
+    /// `[codec][batched]` for raw and lz4, unbatched then batched.
+    pub modes: [[StreamMode; 2]; 2],
+}
+
+const CODECS: [OutputCodec; 2] = [OutputCodec::Raw, OutputCodec::Lz4];
+
+impl fmt::Display for OutputStreamReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mib = self.input_bytes as f64 / (1024.0 * 1024.0);
+        writeln!(
+            f,
+            "Output stream bench: {mib:.1} MiB in {} PTY reads.",
+            self.reads
+        )?;
+        for (codec, modes) in CODECS.iter().zip(&self.modes) {
+            for (batched, mode) in modes.iter().enumerate() {
+                let label = format!(
+                    "{:<3} {}",
+                    codec.name(),
+                    if batched == 1 {
+                        "batched  "
+                    } else {
+                        "unbatched"
+                    }
+                );
+                let throughput = if mode.codec_time.is_zero() {
+                    String::from("-")
+                } else {
+                    format!("{:.0} MiB/s", mib / mode.codec_time.as_secs_f64())
+                };
+                writeln!(
+                    f,
+                    "{label}: {} frames | {} wire bytes ({:.1}%) | codec {throughput} | worst echo delay {:.2} ms | worst delay {:.2} ms",
+                    mode.frames,
+                    mode.wire_bytes,
+                    mode.wire_bytes as f64 * 100.0 / self.input_bytes.max(1) as f64,
+                    mode.worst_echo.as_secs_f64() * 1_000.0,
+                    mode.worst_delay.as_secs_f64() * 1_000.0,
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Replay `bytes` of synthetic PTY output through every combination.
+pub fn run_output_stream(bytes: usize) -> OutputStreamReport {
+    let reads = workload(bytes);
+    let modes = CODECS.map(|codec| {
+        [
+            Duration::ZERO,
+            term_session_server::output_batch::BATCH_LATENCY,
+        ]
+        .map(|latency| replay(&reads, codec, OutputBatcher::new(latency, MAX_BATCH_BYTES)))
+    });
+    OutputStreamReport {
+        input_bytes: reads.iter().map(|r| r.data.len()).sum(),
+        reads: reads.len(),
+        modes,
+    }
+}
+
+/// Drive the batcher on the workload's virtual clock, timing the codec on
+/// the real one.
+fn replay(reads: &[Read], codec: OutputCodec, mut batcher: OutputBatcher) -> StreamMode {
+    let start = Instant::now();
+    let mut encoder = OutputEncoder::new(codec);
+    let mut decoder = OutputDecoder::new(codec);
+    let mut mode = StreamMode::default();
+    // Arrival time of the oldest pending read, and of the oldest pending echo.
+    let mut oldest: Option<Instant> = None;
+    let mut oldest_echo: Option<Instant> = None;
+
+    let mut send = |batch: Vec<u8>,
+                    now: Instant,
+                    oldest: &mut Option<Instant>,
+                    oldest_echo: &mut Option<Instant>| {
+        if let Some(at) = oldest.take() {
+            mode.worst_delay = mode.worst_delay.max(now - at);
+        }
+        if let Some(at) = oldest_echo.take() {
+            mode.worst_echo = mode.worst_echo.max(now - at);
+        }
+        let timer = Instant::now();
+        let frame = encoder.encode(&batch);
+        let decoded = decoder.decode(&frame);
+        mode.codec_time += timer.elapsed();
+        debug_assert_eq!(decoded.ok().as_deref(), Some(batch.as_slice()));
+        mode.frames += 1;
+        mode.wire_bytes += frame.len();
+    };
+
+    for read in reads {
+        let now = start + read.at;
+        // The output task's timer fires at the deadline, before this read.
+        if let Some(due) = batcher.deadline().filter(|&due| due <= now)
+            && let Some(batch) = batcher.poll(due)
+        {
+            send(batch, due, &mut oldest, &mut oldest_echo);
+        }
+        batcher.push(&read.data, now);
+        oldest.get_or_insert(now);
+        if read.echo {
+            oldest_echo.get_or_insert(now);
+        }
+        if let Some(batch) = batcher.poll(now) {
+            send(batch, now, &mut oldest, &mut oldest_echo);
+        }
+    }
+    if let Some(due) = batcher.deadline()
+        && let Some(batch) = batcher.flush(due)
+    {
+        send(batch, due, &mut oldest, &mut oldest_echo);
+    }
+    mode
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batching_cuts_frames_and_lz4_cuts_bytes_without_delaying_echo() {
+        let report = run_output_stream(512 * 1024);
+        let [[raw, raw_batched], [lz4, lz4_batched]] = report.modes;
+        assert_eq!(raw.frames, report.reads);
+        assert_eq!(raw.wire_bytes, report.input_bytes);
+        assert!(raw_batched.frames * 4 < raw.frames, "{raw_batched:?}");
+        assert_eq!(raw_batched.wire_bytes, report.input_bytes);
+        assert!(lz4.wire_bytes * 3 < report.input_bytes, "{lz4:?}");
+        assert!(lz4_batched.wire_bytes < lz4.wire_bytes, "{lz4_batched:?}");
+        assert_eq!(raw_batched.worst_echo, Duration::ZERO);
+        assert!(raw_batched.worst_delay <= term_session_server::output_batch::BATCH_LATENCY);
+
+        let text = report.to_string();
+        assert!(text.contains("lz4 batched  :"), "{text}");
+    }
+}
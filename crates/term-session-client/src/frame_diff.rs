@@ -0,0 +1,611 @@
+//! Differential frame encoder for the headless client.
+//!
+//! [`render_frame`] repaints every cell of every row, which is wasteful over
+//! slow links where most frames change a handful of cells. [`FrameDiffer`]
+//! remembers the screen it last emitted and writes only what changed:
+//!
+//! * whole-screen scrolls become `CSI n S` / `CSI n T` before the cell diff,
+//! * changed cells are grouped into runs (short unchanged gaps are reprinted
+//!   rather than skipped with a cursor move),
+//! * SGR state carries across runs and is changed with one combined sequence,
+//! * cursor moves use `CR`, `CR LF` or relative moves when shorter than `CUP`,
+//! * blank row tails are erased with `EL` instead of written out.
+//!
+//! A full repaint happens on the first frame, on resize and when the caller
+//! asks for one (attach, screen clear).
+
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::{self, Write};
+
+use term_wm_vt100::{Color, Screen};
+
+use crate::{CellStyle, render_frame};
+
+/// Unchanged cells between two changed runs that are reprinted instead of
+/// jumped over — a relative cursor move costs about as much.
+const MAX_RUN_GAP: usize = 3;
+
+/// One emitted cell: its text, display width (`0` for the second half of a
+/// wide character) and style.
+#[derive(Clone, PartialEq)]
+struct SnapCell {
+    text: String,
+    width: u8,
+    style: CellStyle,
+}
+
+impl Default for SnapCell {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            width: 1,
+            style: CellStyle::default(),
+        }
+    }
+}
+
+impl SnapCell {
+    /// A cell that `EL` produces: no text, default colors and attributes.
+    fn is_blank(&self) -> bool {
+        (self.text.is_empty() || self.text == " ") && self.style == CellStyle::default()
+    }
+
+    fn fill(&mut self, cell: Option<&term_wm_vt100::Cell>) {
+        self.text.clear();
+        self.width = 1;
+        self.style = CellStyle::default();
+        if let Some(cell) = cell {
+            let contents = cell.contents();
+            self.text.push_str(contents);
+            self.style = CellStyle::from_cell(cell);
+            if cell.is_wide_continuation() {
+                self.width = 0;
+            } else if !contents.is_empty() {
+                self.width = unicode_width::UnicodeWidthStr::width(contents).clamp(1, 2) as u8;
+            }
+        }
+    }
+}
+
+/// Keeps the last emitted screen and encodes each new frame as the
+/// difference from it.
+#[derive(Default)]
+pub struct FrameDiffer {
+    rows: u16,
+    cols: u16,
+    /// Row-major cells as the host terminal currently shows them; empty
+    /// until the first (full) frame.
+    shown: Vec<SnapCell>,
+    /// Scratch snapshot of the incoming screen, swapped with `shown`.
+    next: Vec<SnapCell>,
+    cursor: (u16, u16),
+    cursor_hidden: bool,
+}
+
+impl FrameDiffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bring the host terminal from the last emitted frame to `screen`.
+    /// `full` forces a repaint with a screen clear (resize, attach);
+    /// otherwise only changes are written, and nothing at all when the
+    /// screen is unchanged.
+    pub fn render(&mut self, out: &mut dyn Write, screen: &Screen, full: bool) -> io::Result<()> {
+        let (rows, cols) = screen.size();
+        snapshot(screen, &mut self.next);
+        let cursor = screen.cursor_position();
+        let cursor_hidden = screen.hide_cursor();
+        let resized = (rows, cols) != (self.rows, self.cols);
+        if full || resized || self.shown.len() != self.next.len() {
+            render_frame(out, screen, rows, cols, full)?;
+        } else {
+            let mut enc = Encoder::new(rows, cols, self.cursor);
+            enc.diff(&mut self.shown, &self.next);
+            let buf = enc.finish(
+                cursor,
+                cursor_hidden,
+                cursor != self.cursor || cursor_hidden != self.cursor_hidden,
+            );
+            if !buf.is_empty() {
+                out.write_all(&buf)?;
+                out.flush()?;
+            }
+        }
+        std::mem::swap(&mut self.shown, &mut self.next);
+        self.rows = rows;
+        self.cols = cols;
+        self.cursor = cursor;
+        self.cursor_hidden = cursor_hidden;
+        Ok(())
+    }
+}
+
+fn snapshot(screen: &Screen, cells: &mut Vec<SnapCell>) {
+    let (rows, cols) = screen.size();
+    cells.resize_with(rows as usize * cols as usize, SnapCell::default);
+    for row in 0..rows {
+        for col in 0..cols {
+            cells[row as usize * cols as usize + col as usize].fill(screen.cell(row, col));
+        }
+    }
+}
+
+/// Byte encoder for one differential frame.
+struct Encoder {
+    buf: Vec<u8>,
+    rows: usize,
+    cols: usize,
+    /// Host cursor position; `None` after writing into the last column,
+    /// where terminals disagree about the pending-wrap state.
+    cursor: Option<(usize, usize)>,
+    style: CellStyle,
+}
+
+impl Encoder {
+    fn new(rows: u16, cols: u16, cursor: (u16, u16)) -> Self {
+        Self {
+            buf: Vec::new(),
+            rows: rows as usize,
+            cols: cols as usize,
+            // A cursor parked past the last column (pending wrap) is
+            // clamped differently by each host; treat it as unknown.
+            cursor: (cursor.1 < cols).then_some((cursor.0 as usize, cursor.1 as usize)),
+            style: CellStyle::default(),
+        }
+    }
+
+    /// Encode `next` against `shown`, updating `shown` to match as it goes.
+    fn diff(&mut self, shown: &mut [SnapCell], next: &[SnapCell]) {
+        if shown == next {
+            return;
+        }
+        if let Some(shift) = detect_scroll(shown, next, self.rows, self.cols) {
+            self.scroll(shown, shift);
+        }
+        for row in 0..self.rows {
+            let range = row * self.cols..(row + 1) * self.cols;
+            self.diff_row(row, &shown[range.clone()], &next[range]);
+        }
+    }
+
+    /// Scroll the whole screen by `shift` rows (positive: content moves up)
+    /// and mirror the effect on `shown`.
+    fn scroll(&mut self, shown: &mut [SnapCell], shift: isize) {
+        self.begin();
+        // New lines take the current background (BCE); keep them default.
+        self.set_style(CellStyle::default());
+        let n = shift.unsigned_abs();
+        let _ = write!(self.buf, "\x1b[{n}{}", if shift > 0 { 'S' } else { 'T' });
+        let cells = n * self.cols;
+        if shift > 0 {
+            shown.rotate_left(cells);
+            let len = shown.len();
+            shown[len - cells..].fill(SnapCell::default());
+        } else {
+            shown.rotate_right(cells);
+            shown[..cells].fill(SnapCell::default());
+        }
+    }
+
+    fn diff_row(&mut self, row: usize, shown: &[SnapCell], next: &[SnapCell]) {
+        let cols = self.cols;
+        let mut col = 0;
+        while let Some(offset) = (col..cols).position(|c| shown[c] != next[c]) {
+            let mut start = col + offset;
+            // Redraw a wide character from its first half.
+            while start > 0 && (next[start].width == 0 || shown[start].width == 0) {
+                start -= 1;
+            }
+            // Extend through changes separated by short unchanged gaps.
+            let mut end = start + 1;
+            let mut probe = end;
+            while probe < cols && probe - end <= MAX_RUN_GAP {
+                if shown[probe] != next[probe] {
+                    end = probe + 1;
+                }
+                probe += 1;
+            }
+            while end < cols && next[end].width == 0 {
+                end += 1;
+            }
+            col = self.write_run(row, (start, end), shown, next);
+        }
+    }
+
+    /// Write `next[start..end]` of `row`, erasing the rest of the row with
+    /// `EL` when nothing but blanks follows. Returns the column written up to.
+    fn write_run(
+        &mut self,
+        row: usize,
+        (start, end): (usize, usize),
+        shown: &[SnapCell],
+        next: &[SnapCell],
+    ) -> usize {
+        self.begin();
+        let cols = self.cols;
+        let blank_from = next
+            .iter()
+            .rposition(|c| !c.is_blank())
+            .map_or(0, |c| c + 1);
+        let erase = end >= blank_from;
+        let mut col = start;
+        while col < if erase { blank_from } else { end } {
+            let cell = &next[col];
+            let width = usize::from(cell.width.max(1));
+            self.move_to(row, col);
+            // Margin sanitation as in `render_frame`: clear the right margin
+            // before the cell that touches the right edge.
+            if col + width >= cols {
+                self.set_style(CellStyle::default());
+                self.buf.extend_from_slice(b"\x1b[K");
+            }
+            self.set_style(cell.style);
+            if cell.width == 0 || cell.text.is_empty() {
+                self.buf.push(b' ');
+            } else {
+                self.buf.extend_from_slice(cell.text.as_bytes());
+            }
+            col += width;
+            self.cursor = (col < cols).then_some((row, col));
+        }
+        if erase && col < cols {
+            if shown[col..].iter().all(SnapCell::is_blank) {
+                return cols;
+            }
+            self.move_to(row, col);
+            self.set_style(CellStyle::default());
+            self.buf.extend_from_slice(b"\x1b[K");
+            return cols;
+        }
+        col.max(end)
+    }
+
+    fn move_to(&mut self, row: usize, col: usize) {
+        if self.cursor == Some((row, col)) {
+            return;
+        }
+        let cup = if col == 0 {
+            format!("\x1b[{}H", row + 1)
+        } else {
+            format!("\x1b[{};{}H", row + 1, col + 1)
+        };
+        let relative = match self.cursor {
+            Some((r, _)) if r == row && col == 0 => Some("\r".to_string()),
+            Some((r, c)) if r == row && col > c => Some(match col - c {
+                1 => "\x1b[C".to_string(),
+                n => format!("\x1b[{n}C"),
+            }),
+            Some((r, c)) if r == row => Some(match c - col {
+                1 => "\x1b[D".to_string(),
+                n => format!("\x1b[{n}D"),
+            }),
+            Some((r, _)) if r + 1 == row && col == 0 => Some("\r\n".to_string()),
+            _ => None,
+        };
+        let seq = match relative {
+            Some(rel) if rel.len() < cup.len() => rel,
+            _ => cup,
+        };
+        self.buf.extend_from_slice(seq.as_bytes());
+        self.cursor = Some((row, col));
+    }
+
+    /// Switch the SGR state to `style` with a single sequence.
+    fn set_style(&mut self, style: CellStyle) {
+        let from = self.style;
+        if from == style {
+            return;
+        }
+        let dropped = (from.bold && !style.bold)
+            || (from.dim && !style.dim)
+            || (from.italic && !style.italic)
+            || (from.underline && !style.underline)
+            || (from.inverse && !style.inverse);
+        let base = if dropped { CellStyle::default() } else { from };
+        let mut params: Vec<String> = Vec::new();
+        if dropped {
+            params.push("0".into());
+        }
+        for (on, was, code) in [
+            (style.bold, base.bold, "1"),
+            (style.dim, base.dim, "2"),
+            (style.italic, base.italic, "3"),
+            (style.underline, base.underline, "4"),
+            (style.inverse, base.inverse, "7"),
+        ] {
+            if on && !was {
+                params.push(code.into());
+            }
+        }
+        if style.fg != base.fg {
+            params.push(color_param(style.fg, 30));
+        }
+        if style.bg != base.bg {
+            params.push(color_param(style.bg, 40));
+        }
+        let _ = write!(self.buf, "\x1b[{}m", params.join(";"));
+        self.style = style;
+    }
+
+    /// Open the frame: synchronized update, hidden cursor, no autowrap.
+    fn begin(&mut self) {
+        if self.buf.is_empty() {
+            self.buf.extend_from_slice(b"\x1b[?2026h\x1b[?25l\x1b[?7l");
+        }
+    }
+
+    /// Close the frame and place the cursor. Returns the bytes to write,
+    /// empty when neither cells nor cursor changed.
+    fn finish(mut self, cursor: (u16, u16), cursor_hidden: bool, cursor_changed: bool) -> Vec<u8> {
+        let drew = !self.buf.is_empty();
+        if !drew && !cursor_changed {
+            return self.buf;
+        }
+        if drew {
+            self.buf.extend_from_slice(b"\x1b[?7h\x1b[0m");
+        }
+        self.move_to(cursor.0 as usize, cursor.1 as usize);
+        self.buf.extend_from_slice(if cursor_hidden {
+            b"\x1b[?25l"
+        } else {
+            b"\x1b[?25h"
+        });
+        if drew {
+            self.buf.extend_from_slice(b"\x1b[?2026l");
+        }
+        self.buf
+    }
+}
+
+/// SGR parameter selecting `color`; `base` is 30 for foreground, 40 for
+/// background.
+fn color_param(color: Color, base: u8) -> String {
+    match color {
+        Color::Default => format!("{}", base + 9),
+        Color::Idx(i) => format!("{};5;{i}", base + 8),
+        Color::Rgb(r, g, b) => format!("{};2;{r};{g};{b}", base + 8),
+    }
+}
+
+/// The whole-screen scroll that best explains the change from `shown` to
+/// `next`: `Some(n)` when content moved up `n` rows (`-n` for down) and more
+/// non-blank rows line up after scrolling than without it.
+///
+/// Rows are hashed once and compared by hash, and offsets are only tried
+/// when both the first and last rows changed — edits that leave either edge
+/// in place (typing, a status line update) are not scrolls.
+fn detect_scroll(shown: &[SnapCell], next: &[SnapCell], rows: usize, cols: usize) -> Option<isize> {
+    if rows < 2 || cols == 0 {
+        return None;
+    }
+    let shown_rows: Vec<u64> = shown.chunks(cols).map(row_hash).collect();
+    let next_rows: Vec<u64> = next.chunks(cols).map(row_hash).collect();
+    if shown_rows[0] == next_rows[0] || shown_rows[rows - 1] == next_rows[rows - 1] {
+        return None;
+    }
+    let content: Vec<bool> = next
+        .chunks(cols)
+        .map(|row| !row.iter().all(SnapCell::is_blank))
+        .collect();
+    // Non-blank rows of `next` that match `shown` moved by `shift`.
+    let aligned = |shift: isize| {
+        (0..rows)
+            .filter(|&r| {
+                let from = r as isize + shift;
+                content[r]
+                    && from >= 0
+                    && (from as usize) < rows
+                    && next_rows[r] == shown_rows[from as usize]
+            })
+            .count()
+    };
+    let unmoved = aligned(0);
+    let limit = rows as isize - 1;
+    (1..=limit)
+        .flat_map(|n| [n, -n])
+        .map(|shift| (shift, aligned(shift)))
+        .filter(|&(_, count)| count > unmoved && count >= 2)
+        .max_by_key(|&(shift, count)| (count, -shift.abs()))
+        .map(|(shift, _)| shift)
+}
+
+/// Hash of one row's text, widths and styles, for cheap row comparison.
+fn row_hash(row: &[SnapCell]) -> u64 {
+    fn color_key(color: Color) -> (u8, u8, u8, u8) {
+        match color {
+            Color::Default => (0, 0, 0, 0),
+            Color::Idx(i) => (1, i, 0, 0),
+            Color::Rgb(r, g, b) => (2, r, g, b),
+        }
+    }
+    let mut hasher = DefaultHasher::new();
+    for cell in row {
+        cell.text.hash(&mut hasher);
+        cell.width.hash(&mut hasher);
+        let style = &cell.style;
+        color_key(style.fg).hash(&mut hasher);
+        color_key(style.bg).hash(&mut hasher);
+        [
+            style.bold,
+            style.dim,
+            style.italic,
+            style.underline,
+            style.inverse,
+        ]
+        .hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use term_wm_vt100::Parser;
+
+    /// Render `before` in full, then feed `after` and return the diff bytes,
+    /// escaped for readable snapshots.
+    fn diff_after(rows: u16, cols: u16, before: &[u8], after: &[u8]) -> String {
+        let mut parser = Parser::new(rows, cols, 0);
+        let mut differ = FrameDiffer::new();
+        parser.process(before);
+        differ
+            .render(&mut Vec::new(), parser.screen(), false)
+            .unwrap();
+        parser.process(after);
+        let mut out = Vec::new();
+        differ.render(&mut out, parser.screen(), false).unwrap();
+        String::from_utf8(out)
+            .unwrap()
+            .replace('\x1b', "\\x1b")
+            .replace('\r', "\\r")
+            .replace('\n', "\\n")
+    }
+
+    #[test]
+    fn unchanged_screen_writes_nothing() {
+        assert_eq!(diff_after(4, 8, b"Hello", b""), "");
+    }
+
+    #[test]
+    fn snapshot_diff_single_cell() {
+        insta::assert_snapshot!(
+            "diff_single_cell",
+            diff_after(4, 8, b"Hello", b"\x1b[1;2Ha")
+        );
+    }
+
+    #[test]
+    fn snapshot_diff_carries_sgr_across_runs() {
+        insta::assert_snapshot!(
+            "diff_sgr_across_runs",
+            diff_after(
+                2,
+                16,
+                b"aaaaaaaaaaaaaaaa",
+                b"\x1b[1;1H\x1b[1;31mb\x1b[1;10Hc\x1b[22md"
+            )
+        );
+    }
+
+    #[test]
+    fn snapshot_diff_erases_blank_tail() {
+        insta::assert_snapshot!(
+            "diff_erases_blank_tail",
+            diff_after(2, 10, b"long line", b"\x1b[1;5H\x1b[K")
+        );
+    }
+
+    #[test]
+    fn snapshot_diff_scrolls_up() {
+        insta::assert_snapshot!(
+            "diff_scrolls_up",
+            diff_after(4, 6, b"one\r\ntwo\r\nthree\r\nfour", b"\r\nfive\r\nsix")
+        );
+    }
+
+    #[test]
+    fn snapshot_diff_scrolls_down() {
+        insta::assert_snapshot!(
+            "diff_scrolls_down",
+            diff_after(4, 6, b"one\r\ntwo\r\nthree", b"\x1b[1;1H\x1bMzero")
+        );
+    }
+
+    #[test]
+    fn scroll_is_not_tried_when_an_edge_row_is_unchanged() {
+        let snap = |bytes: &[u8]| {
+            let mut parser = Parser::new(4, 6, 0);
+            parser.process(bytes);
+            let mut cells = Vec::new();
+            snapshot(parser.screen(), &mut cells);
+            cells
+        };
+        let shown = snap(b"aa\r\nbb\r\naa\r\nbb");
+        // Rows 1..3 line up one row up, but the first row stayed put.
+        let next = snap(b"aa\r\naa\r\nbb\r\nbb");
+        assert_eq!(detect_scroll(&shown, &next, 4, 6), None);
+        let scrolled = snap(b"bb\r\naa\r\nbb\r\ncc");
+        assert_eq!(detect_scroll(&shown, &scrolled, 4, 6), Some(1));
+    }
+
+    #[test]
+    fn snapshot_diff_wide_char() {
+        insta::assert_snapshot!(
+            "diff_wide_char",
+            diff_after(1, 6, b"ab\xe3\x81\x82c", b"\x1b[1;4Hx")
+        );
+    }
+
+    #[test]
+    fn cursor_only_change_moves_cursor() {
+        assert_eq!(
+            diff_after(4, 8, b"Hello", b"\x1b[3;2H"),
+            "\\x1b[3;2H\\x1b[?25h"
+        );
+    }
+
+    #[test]
+    fn resize_and_forced_frames_repaint_everything() {
+        let mut parser = Parser::new(2, 4, 0);
+        let mut differ = FrameDiffer::new();
+        let mut full = Vec::new();
+        render_frame(&mut full, parser.screen(), 2, 4, true).unwrap();
+
+        differ
+            .render(&mut Vec::new(), parser.screen(), false)
+            .unwrap();
+        let mut out = Vec::new();
+        differ.render(&mut out, parser.screen(), true).unwrap();
+        assert_eq!(out, full, "forced frame is a full repaint");
+
+        parser.screen_mut().set_size(3, 4);
+        let mut out = Vec::new();
+        differ.render(&mut out, parser.screen(), false).unwrap();
+        assert!(
+            String::from_utf8_lossy(&out).contains("\x1b[3;1H"),
+            "resize repaints every row"
+        );
+    }
+
+    #[test]
+    fn diffs_reproduce_the_screen() {
+        // Replaying the full frame and every diff into a fresh emulator must
+        // land on the same contents as the source screen.
+        let steps: [&[u8]; 5] = [
+            b"\x1b[32mgreen\x1b[0m text\r\nsecond",
+            b"\r\nthird\r\nfourth\r\nfifth",
+            b"\x1b[2;3H\x1b[7mXY\x1b[0m\xe3\x81\x82",
+            b"\x1b[H\x1bM\x1bMtop",
+            b"\x1b[3;1H\x1b[2K\x1b[44m  \x1b[0m",
+        ];
+        let mut source = Parser::new(4, 12, 0);
+        let mut host = Parser::new(4, 12, 0);
+        let mut differ = FrameDiffer::new();
+        for step in steps {
+            source.process(step);
+            let mut out = Vec::new();
+            differ.render(&mut out, source.screen(), false).unwrap();
+            host.process(&out);
+            for row in 0..4 {
+                for col in 0..12 {
+                    let cells = [&host, &source].map(|parser| {
+                        let mut cell = SnapCell::default();
+                        cell.fill(parser.screen().cell(row, col));
+                        if cell.text == " " {
+                            cell.text.clear();
+                        }
+                        cell
+                    });
+                    let [a, b] = &cells;
+                    assert!(a == b, "cell {row},{col}: {:?} vs {:?}", a.text, b.text);
+                }
+            }
+            assert_eq!(
+                host.screen().cursor_position(),
+                source.screen().cursor_position()
+            );
+        }
+    }
+}
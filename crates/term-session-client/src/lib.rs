@@ -1,6 +1,10 @@
+mod frame_diff;
 mod remote_pane;
+pub mod transport;
 
+pub use frame_diff::FrameDiffer;
 pub use remote_pane::RemotePane;
+pub use transport::GatewayClient;
 
 use std::io::{self, IsTerminal, Write, stdout};
 use std::sync::atomic::{AtomicBool, AtomicU16, Ordering};
@@ -9,23 +13,26 @@ use std::time::Duration;
 
 use crossterm::QueueableCommand;
 use crossterm::cursor::{Hide, Show};
-use crossterm::event::{DisableBracketedPaste, EnableBracketedPaste};
+use crossterm::event::{
+    DisableBracketedPaste, DisableFocusChange, EnableBracketedPaste, EnableFocusChange,
+};
 use crossterm::terminal::{
     EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
 };
+use muxio_rpc_service_caller::RpcServiceCallerInterface;
+use muxio_rpc_service_caller::prebuffered::RpcCallPrebuffered;
 use muxio_rpc_service_endpoint::RpcServiceEndpointInterface;
 use muxio_tokio_mpsc_adapter::ChannelCallerExt;
-use muxio_tokio_rpc_ipc_client::{RpcCallPrebuffered, RpcIpcClient, RpcServiceCallerInterface};
 use portable_pty::PtySize;
 use term_clipboard::{Clipboard, Osc52Extractor};
 use term_session_muxio_service_definitions::{
-    Attach, AttachRequest, OnPtyResized, OnWorkspaceRebind, RpcMethodPrebuffered,
-    STREAM_INPUT_METHOD_ID, SUBSCRIBE_OUTPUT_METHOD_ID, Spawn, SpawnRequest, SpawnResponse,
-    path_wire,
+    Attach, AttachRequest, NegotiateOutput, OnGatewayUpgrade, OnPtyResized, OnWorkspaceRebind,
+    OutputCodec, OutputDecoder, RpcMethodPrebuffered, STREAM_INPUT_METHOD_ID,
+    SUBSCRIBE_OUTPUT_METHOD_ID, Spawn, SpawnRequest, SpawnResponse, output_codec, path_wire,
 };
 #[cfg(unix)]
 use term_sys_io::redirect_fd_to_tracing;
-use term_wm_events::{Event, KeyKind, KeyModifiers, MouseEventKind};
+use term_wm_events::{Event, HostDisplay, KeyKind, KeyModifiers, MouseEventKind};
 use term_wm_pty_engine::Pane;
 use term_wm_pty_engine::input_encoding::{key_to_bytes, mouse_event_to_bytes};
 use term_wm_pty_engine::signal::install_sigint_handler;
@@ -117,6 +124,21 @@ pub fn is_nested_session_fatal(err: &io::Error) -> bool {
     err.to_string().contains("FATAL: Attempted to run")
 }
 
+/// Message of the error [`run_session`] returns when the gateway was upgraded
+/// underneath the viewer (see [`is_gateway_upgrade`]).
+const GATEWAY_UPGRADED_MESSAGE: &str = "gateway upgraded; reconnect to the replacement daemon";
+
+fn gateway_upgraded_error() -> io::Error {
+    io::Error::new(io::ErrorKind::ConnectionReset, GATEWAY_UPGRADED_MESSAGE)
+}
+
+/// Returns `true` if [`run_session`] ended because the gateway daemon was
+/// replaced by `term-session upgrade`. The session is still running; callers
+/// wait for the new gateway and call [`run_session`] again.
+pub fn is_gateway_upgrade(err: &io::Error) -> bool {
+    err.kind() == io::ErrorKind::ConnectionReset && err.to_string() == GATEWAY_UPGRADED_MESSAGE
+}
+
 /// Whether an attach should be refused because the caller is already inside an
 /// active term-session targeting the same gateway and nesting was not explicitly
 /// allowed.
@@ -135,9 +157,9 @@ fn should_block_nesting(
 }
 
 /// Initialize terminal for TUI mode: write startup escape sequences
-/// (alternate screen, hide cursor, bracketed paste, mouse capture) to
-/// the given writer, enable raw mode on stdin, and return a guard that
-/// restores the terminal on drop.
+/// (alternate screen, hide cursor, bracketed paste, focus reporting, mouse
+/// capture) to the given writer, enable raw mode on stdin, and return a
+/// guard that restores the terminal on drop.
 ///
 /// The writer parameter allows tests to capture the ANSI sequences
 /// without writing to a real terminal.
@@ -148,6 +170,7 @@ pub fn init_terminal<W: Write>(mut writer: W) -> io::Result<TerminalGuard<W>> {
     writer.queue(EnterAlternateScreen)?;
     writer.queue(Hide)?;
     writer.queue(EnableBracketedPaste)?;
+    writer.queue(EnableFocusChange)?;
     writer.queue(crossterm::event::EnableMouseCapture)?;
     // crossterm's `EnableMouseCapture` on Windows only calls `SetConsoleMode`
     // (`is_ansi_code_supported()` returns false), so it emits no ANSI. When
@@ -161,6 +184,7 @@ pub fn init_terminal<W: Write>(mut writer: W) -> io::Result<TerminalGuard<W>> {
     writer.flush()?;
     Ok(TerminalGuard {
         writer: Some(writer),
+        reset_cursor_style: false,
     })
 }
 
@@ -169,6 +193,15 @@ pub fn init_terminal<W: Write>(mut writer: W) -> io::Result<TerminalGuard<W>> {
 /// inject a `Vec<u8>` writer and verify the teardown sequences.
 pub struct TerminalGuard<W: Write = std::io::Stdout> {
     writer: Option<W>,
+    reset_cursor_style: bool,
+}
+
+impl<W: Write> TerminalGuard<W> {
+    /// Also reset the cursor shape and color on drop, once a session has
+    /// passed a style through to the terminal.
+    pub fn reset_cursor_style_on_drop(&mut self) {
+        self.reset_cursor_style = true;
+    }
 }
 
 impl<W: Write> Drop for TerminalGuard<W> {
@@ -180,6 +213,11 @@ impl<W: Write> Drop for TerminalGuard<W> {
             #[cfg(windows)]
             let _ = term_wm_crossterm_adapter::set_mouse_capture_with(writer, false);
             let _ = writer.queue(DisableBracketedPaste);
+            let _ = writer.queue(DisableFocusChange);
+            if self.reset_cursor_style {
+                let reset = term_wm_pty_engine::CursorStyle::default().escape_sequence();
+                let _ = writer.write_all(reset.as_bytes());
+            }
             let _ = writer.queue(Show);
             let _ = writer.queue(LeaveAlternateScreen);
             if std::io::stdin().is_terminal() {
@@ -190,9 +228,17 @@ impl<W: Write> Drop for TerminalGuard<W> {
     }
 }
 
-/// Convert a crossterm event into a core Event for use in the event-driven loop.
-fn convert_crossterm_event(evt: crossterm::event::Event) -> Option<Event> {
-    term_wm_crossterm_adapter::try_translate_event(evt)
+/// Ask the host terminal for its default colors (OSC 10/11). The replies
+/// come back through the input thread as [`Event::HostColor`] and travel to
+/// the gateway as attributed input: it answers the channel's own OSC 10/11
+/// queries with them and hands them to an inner window manager.
+fn query_host_colors(out: &mut dyn Write) -> io::Result<()> {
+    if !std::io::stdin().is_terminal() {
+        return Ok(());
+    }
+    term_wm_crossterm_adapter::expect_host_color_replies();
+    out.write_all(term_wm_crossterm_adapter::HOST_COLOR_QUERY.as_bytes())?;
+    out.flush()
 }
 
 /// Two motion mouse events may be coalesced (keep only the latest position)
@@ -316,22 +362,26 @@ fn client_ssh_ip() -> Option<String> {
 /// `socket_path` is the gateway channel name (the muxio socket identity);
 /// `channel` is the logical channel to attach to; `cmd` is the command to run
 /// (empty = the gateway's default shell). PTY geometry is read from the real
-/// terminal.
+/// terminal. `host_display` is what the caller probed on that terminal; it is
+/// reported to a window manager running inside the channel on every attach.
 pub fn run_session(
     socket_path: &str,
     channel: &str,
     cmd: &[String],
     allow_nested: bool,
     app_name: &str,
+    host_display: Option<HostDisplay>,
 ) -> io::Result<Option<String>> {
     // Reject "session inception": a client started inside an already-active
     // term-session environment (detected via the marker the daemon injects into
     // every spawned PTY child). Inception is blocked only when the target socket
-    // matches the host gateway — different gateways are isolated and safe.
-    // `--allow-nested` opts out.
+    // matches the host gateway — different gateways are isolated and safe, and
+    // so is a remote one. `--allow-nested` opts out.
     let host_gateway =
         std::env::var(term_session_muxio_service_definitions::SESSION_GATEWAY_ENV_VAR).ok();
-    if should_block_nesting(host_gateway.as_deref(), socket_path, allow_nested) {
+    if transport::remote_gateway().is_none()
+        && should_block_nesting(host_gateway.as_deref(), socket_path, allow_nested)
+    {
         return Err(nested_session_fatal_error(app_name));
     }
 
@@ -345,9 +395,9 @@ pub fn run_session(
     let rt =
         tokio::runtime::Runtime::new().map_err(|e| io::Error::other(format!("runtime: {e}")))?;
 
-    // Connect via muxio IPC
-    let client: Arc<RpcIpcClient> = rt
-        .block_on(RpcIpcClient::new(socket_path))
+    // Connect via muxio, over IPC or to the `--connect` remote gateway
+    let client: Arc<GatewayClient> = rt
+        .block_on(GatewayClient::connect(socket_path))
         .map_err(|e| io::Error::new(io::ErrorKind::ConnectionRefused, format!("{e:?}")))?;
 
     // ABI/transport fault interception: a decode/parse fault during the
@@ -412,6 +462,26 @@ pub fn run_session(
         .map_err(|e| io::Error::other(format!("register OnWorkspaceRebind: {e:?}")))?;
     }
 
+    // Upgrade notice: set by the OnGatewayUpgrade handler just before the
+    // daemon hands its sessions to a replacement and drops this connection.
+    let gateway_upgraded = Arc::new(AtomicBool::new(false));
+
+    {
+        let upgraded_ref = Arc::clone(&gateway_upgraded);
+        rt.block_on(client.get_endpoint().register_prebuffered(
+            OnGatewayUpgrade::METHOD_ID,
+            move |_payload, _ctx| {
+                let upgraded_ref = Arc::clone(&upgraded_ref);
+                async move {
+                    upgraded_ref.store(true, Ordering::Relaxed);
+                    OnGatewayUpgrade::encode_response(())
+                        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+                }
+            },
+        ))
+        .map_err(|e| io::Error::other(format!("register OnGatewayUpgrade: {e:?}")))?;
+    }
+
     // Channels for raw PTY output bytes and clipboard text from the subscription stream.
     // Using crossbeam so the main loop can block on both input and PTY output.
     // Bounded to cap head-of-line queuing under burst load.
@@ -432,7 +502,7 @@ pub fn run_session(
         .map(|h| h.to_string_lossy().into_owned())
         .unwrap_or_else(|_| "unknown".to_string());
 
-    let (actual_cols, actual_rows) = rt.block_on(async {
+    let (actual_cols, actual_rows, output_codec) = rt.block_on(async {
         // 1) Attach: bind this connection to the channel (server-assigned
         // conn_id); report our OS PID so `list` can show which client is which.
         let conn_id = Attach::call(
@@ -448,6 +518,11 @@ pub fn run_session(
         )
         .await
         .map_err(|e| abi_fault(&e))?;
+        // Pick the output stream's encoding. A gateway that predates the
+        // negotiation rejects the call and keeps sending raw chunks.
+        let output_codec = NegotiateOutput::call(&*client, output_codec::client_offer())
+            .await
+            .unwrap_or(OutputCodec::Raw);
         // 2) Spawn: join/respawn the session (cmd travels via Spawn).
         let cmd = if cmd.is_empty() {
             None
@@ -477,7 +552,7 @@ pub fn run_session(
         .await
         .map_err(|e| abi_fault(&e))?;
         let _ = conn_id;
-        Ok::<(u16, u16), io::Error>((actual_cols, actual_rows))
+        Ok::<_, io::Error>((actual_cols, actual_rows, output_codec))
     })?;
 
     // Open streaming channels for output subscription and input
@@ -490,14 +565,19 @@ pub fn run_session(
             .map_err(|e| io::Error::other(format!("subscribe: {e:?}")))?;
 
         // Forward raw PTY output chunks to push_tx.  Each chunk from the
-        // muxio stream is a complete message — no custom framing needed.
+        // muxio stream is a complete message — no custom framing needed
+        // beyond the negotiated codec's.
         // Intercept OSC 52 clipboard sequences before the parser consumes them.
         rt.spawn(async move {
+            let mut decoder = OutputDecoder::new(output_codec);
             let mut osc52 = Osc52Extractor::new();
             let mut prev_tail: [u8; PREV_TAIL_LEN] = [0; PREV_TAIL_LEN];
 
             while let Some(chunk) = reader.recv().await {
-                if let Ok(mut data) = chunk {
+                let decoded = chunk
+                    .map_err(|e| io::Error::other(format!("{e:?}")))
+                    .and_then(|frame| decoder.decode(&frame));
+                if let Ok(mut data) = decoded {
                     if let Some(text) = osc52.push(&data, &prev_tail) {
                         let _ = clip_tx.try_send(text);
                     }
@@ -600,6 +680,15 @@ pub fn run_session(
         });
     }
 
+    if let Some(display) = host_display {
+        let _ = attributed_tx.try_send(
+            term_session_muxio_service_definitions::SendAttributedInputRequest {
+                channel: channel.to_string(),
+                event: Event::HostDisplay(display),
+            },
+        );
+    }
+
     // Wait for initial output
     for _ in 0..INITIAL_WAIT_ITERS {
         pane.drain_pushes();
@@ -635,7 +724,7 @@ pub fn run_session(
     // noise suppression.
     #[cfg(unix)]
     let _ = redirect_fd_to_tracing(libc::STDERR_FILENO, true);
-    let _guard = init_terminal(stdout())?;
+    let mut guard = init_terminal(stdout())?;
     let mut out = stdout();
 
     let mut clipboard = Clipboard::new();
@@ -650,11 +739,12 @@ pub fn run_session(
     std::thread::Builder::new()
         .name("crossterm-input".into())
         .spawn(move || {
+            let mut translator = term_wm_crossterm_adapter::OscReplyAssembler::new();
             loop {
                 match crossterm::event::poll(Duration::from_millis(INPUT_POLL_MS)) {
                     Ok(true) => {
                         if let Ok(crossterm_evt) = crossterm::event::read()
-                            && let Some(e) = convert_crossterm_event(crossterm_evt)
+                            && let Some(e) = translator.translate(crossterm_evt)
                             && input_tx.send(e).is_err()
                         {
                             break;
@@ -667,15 +757,19 @@ pub fn run_session(
         })
         .map_err(|e| io::Error::other(format!("spawn input thread: {e}")))?;
 
-    // Initial full-frame render
+    // The host's colors, now and whenever it regains focus (the user may have
+    // switched its color scheme meanwhile).
+    query_host_colors(&mut out)?;
+
+    // Initial full-frame render; later frames only send what changed.
+    let mut frames = FrameDiffer::new();
     {
         let parser = pane.shared_parser();
         let parser = parser.lock().unwrap_or_else(|e| e.into_inner());
-        let screen = parser.screen();
-        let (rows, cols) = screen.size();
-        render_frame(&mut out, screen, rows, cols, false)?;
+        frames.render(&mut out, parser.screen(), false)?;
     }
 
+    let mut cursor_style = term_wm_pty_engine::CursorStyle::default();
     let mut pending_input: Option<Event> = None;
     loop {
         let mut force_render = false;
@@ -739,9 +833,7 @@ pub fn run_session(
                             clear_display |= resized;
 
                             // PTY output — process directly into parser
-                            let parser = pane.shared_parser();
-                            let mut parser = parser.lock().unwrap_or_else(|e| e.into_inner());
-                            parser.process(&data);
+                            pane.process(&data);
                             None
                         }
                         Err(_) => {
@@ -849,6 +941,7 @@ pub fn run_session(
                     force_render = true;
                     clear_display = true;
                 }
+                Event::FocusGained => query_host_colors(&mut out)?,
                 Event::Paste(text) => {
                     let mut wrapped = Vec::with_capacity(text.len() + BRACKETED_PASTE_OVERHEAD);
                     wrapped.extend_from_slice(b"\x1b[200~");
@@ -860,18 +953,30 @@ pub fn run_session(
             }
         }
 
-        // Connection health — check after wakeup
+        // Connection health — check after wakeup. After an upgrade notice the
+        // drop (or the output stream ending with it) is expected.
+        if gateway_upgraded.load(Ordering::Relaxed) && (!client.is_connected() || pane.has_exited())
+        {
+            return Err(gateway_upgraded_error());
+        }
         if !client.is_connected() {
             return Err(io::Error::other("connection to session server lost"));
         }
 
-        // Full-frame explicit row-by-row render
+        // Differential render; resizes repaint the whole frame.
         if has_new_data || force_render {
             let parser = pane.shared_parser();
             let parser = parser.lock().unwrap_or_else(|e| e.into_inner());
-            let screen = parser.screen();
-            let (rows, cols) = screen.size();
-            render_frame(&mut out, screen, rows, cols, clear_display)?;
+            frames.render(&mut out, parser.screen(), clear_display)?;
+
+            // The remote WM's cursor shape and color for its focused pane.
+            let style = pane.cursor_style();
+            if style != cursor_style {
+                out.write_all(style.escape_sequence().as_bytes())?;
+                out.flush()?;
+                cursor_style = style;
+                guard.reset_cursor_style_on_drop();
+            }
         }
 
         // Exit on session exit
@@ -1059,6 +1164,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn gateway_upgrade_error_is_recognized() {
+        assert!(is_gateway_upgrade(&gateway_upgraded_error()));
+        assert!(!is_gateway_upgrade(&io::Error::other(
+            "connection to session server lost"
+        )));
+        assert!(!is_gateway_upgrade(&nested_session_fatal_error("x")));
+    }
+
     #[test]
     fn nested_session_fatal_error_brands_with_app_name() {
         let err = nested_session_fatal_error("my-app");
@@ -1252,6 +1366,7 @@ mod tests {
         {
             let _guard = TerminalGuard {
                 writer: Some(writer),
+                reset_cursor_style: false,
             };
         }
         let bytes = buf.lock().unwrap();
@@ -1270,6 +1385,7 @@ mod tests {
         {
             let _guard = TerminalGuard {
                 writer: Some(writer),
+                reset_cursor_style: false,
             };
         }
         let bytes = buf.lock().unwrap();
@@ -1281,6 +1397,23 @@ mod tests {
         );
     }
 
+    /// A session that passed a cursor style through resets it on teardown;
+    /// one that didn't leaves the user's cursor alone.
+    #[test]
+    fn terminal_guard_resets_cursor_style_only_when_passed_through() {
+        let reset = term_wm_pty_engine::CursorStyle::default().escape_sequence();
+        for passed_through in [false, true] {
+            let (writer, buf) = TestWriter::new();
+            let mut guard = init_terminal(writer).expect("init_terminal");
+            if passed_through {
+                guard.reset_cursor_style_on_drop();
+            }
+            drop(guard);
+            let text = String::from_utf8_lossy(&buf.lock().unwrap()).to_string();
+            assert_eq!(text.contains(&reset), passed_through, "{text:?}");
+        }
+    }
+
     /// Full lifecycle: init_terminal followed by TerminalGuard teardown
     /// writes both the enable and disable sequences.
     #[test]
@@ -3,19 +3,23 @@ use std::io;
 use std::sync::{Arc, Mutex};
 
 use crossbeam_channel::{Receiver, TryRecvError};
-use muxio_tokio_rpc_ipc_client::RpcIpcClient;
 use portable_pty::{ExitStatus, PtySize};
 use term_session_muxio_service_definitions::{CloseSession, ResizePty};
-use term_wm_pty_engine::{Pane, PtyResult};
+use term_wm_pty_engine::{CursorStyle, Pane, PtyResult, PtyStateObserver, PtyStateTracker};
 use tokio::runtime::Handle;
 
+use crate::GatewayClient;
+
 type InputWriter = Box<dyn FnMut(&[u8]) -> io::Result<()> + Send>;
 
 pub struct RemotePane {
     pub id: u64,
-    client: Option<std::sync::Arc<RpcIpcClient>>,
+    client: Option<std::sync::Arc<GatewayClient>>,
     rt: Handle,
     parser: Arc<Mutex<term_wm_vt100::Parser>>,
+    /// Follows the output for state the parser doesn't keep (cursor style).
+    tracker: Arc<PtyStateTracker>,
+    observer: PtyStateObserver,
     exited: Cell<bool>,
     push_rx: Receiver<Vec<u8>>,
     input_writer: InputWriter,
@@ -24,18 +28,21 @@ pub struct RemotePane {
 impl RemotePane {
     pub fn new(
         id: u64,
-        client: Option<std::sync::Arc<RpcIpcClient>>,
+        client: Option<std::sync::Arc<GatewayClient>>,
         rt: Handle,
         cols: u16,
         rows: u16,
         push_rx: Receiver<Vec<u8>>,
         input_writer: InputWriter,
     ) -> Self {
+        let tracker = Arc::new(PtyStateTracker::new(rows));
         Self {
             id,
             client,
             rt,
             parser: Arc::new(Mutex::new(term_wm_vt100::Parser::new(rows, cols, 0))),
+            observer: PtyStateObserver::new(Arc::clone(&tracker)),
+            tracker,
             exited: Cell::new(false),
             push_rx,
             input_writer,
@@ -49,8 +56,7 @@ impl RemotePane {
         loop {
             match self.push_rx.try_recv() {
                 Ok(data) => {
-                    let mut parser = self.parser.lock().unwrap_or_else(|err| err.into_inner());
-                    parser.process(&data);
+                    self.process(&data);
                     updated = true;
                 }
                 Err(TryRecvError::Disconnected) => {
@@ -63,6 +69,13 @@ impl RemotePane {
         updated
     }
 
+    /// Feed server output to the parser and the state tracker.
+    pub fn process(&mut self, data: &[u8]) {
+        let mut parser = self.parser.lock().unwrap_or_else(|err| err.into_inner());
+        parser.process(data);
+        self.observer.observe(data);
+    }
+
     fn rpc_to_pty<E: std::fmt::Display>(e: E) -> Box<dyn std::error::Error + Send + Sync> {
         Box::new(io::Error::other(format!("{e}")))
     }
@@ -76,7 +89,7 @@ impl Pane for RemotePane {
     fn resize(&mut self, size: PtySize) -> PtyResult<()> {
         let (actual_cols, actual_rows) = if let Some(ref client) = self.client {
             let result = self.rt.block_on(async {
-                use muxio_tokio_rpc_ipc_client::RpcCallPrebuffered;
+                use muxio_rpc_service_caller::prebuffered::RpcCallPrebuffered;
                 ResizePty::call(&**client, (self.id, size.cols, size.rows)).await
             });
             result.map_err(Self::rpc_to_pty)?
@@ -99,6 +112,10 @@ impl Pane for RemotePane {
         parser.screen().alternate_screen()
     }
 
+    fn cursor_style(&self) -> CursorStyle {
+        self.tracker.cursor_style()
+    }
+
     fn scrollback(&mut self) -> usize {
         0
     }
@@ -137,7 +154,7 @@ impl Pane for RemotePane {
         if let Some(ref client) = self.client {
             self.rt
                 .block_on(async {
-                    use muxio_tokio_rpc_ipc_client::RpcCallPrebuffered;
+                    use muxio_rpc_service_caller::prebuffered::RpcCallPrebuffered;
                     CloseSession::call(&**client, self.id).await
                 })
                 .map_err(Self::rpc_to_pty)?;
@@ -177,4 +194,24 @@ mod tests {
         // 3. Subsequent call on drained buffer must return false
         assert!(!pane.drain_pushes());
     }
+
+    #[test]
+    fn drain_pushes_follows_cursor_style() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let (push_tx, push_rx) = crossbeam_channel::unbounded();
+        let input_writer: InputWriter = Box::new(|_| Ok(()));
+        let mut pane = RemotePane::new(1, None, rt.handle().clone(), 80, 24, push_rx, input_writer);
+
+        push_tx.send(b"\x1b[5".to_vec()).unwrap();
+        push_tx.send(b" q\x1b]12;#00ff00\x07".to_vec()).unwrap();
+        pane.drain_pushes();
+        let style = pane.cursor_style();
+        assert_eq!(style.shape, term_wm_pty_engine::CursorShape::BlinkingBar);
+        assert_eq!(style.color, Some((0, 255, 0)));
+
+        pane.process(b"\x1b[0 q\x1b]112\x07");
+        assert!(pane.cursor_style().is_default());
+    }
 }
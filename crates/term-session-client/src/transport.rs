@@ -0,0 +1,256 @@
+//! muxio RPC client over any tokio byte stream.
+//!
+//! [`GatewayClient`] speaks the same framing as `muxio-tokio-rpc-ipc-client`,
+//! but over a stream it is handed rather than one it opens itself. That lets
+//! one client type reach the gateway through its local IPC socket or, after
+//! `term-session --connect` registers a [`RemoteGateway`], over an
+//! authenticated TCP connection (see
+//! [`term_session_muxio_service_definitions::remote`]).
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex as StdMutex, OnceLock, Weak};
+use std::time::Duration;
+use std::{fmt, io};
+
+use interprocess::local_socket::{GenericNamespaced, ToNsName, tokio::prelude::*};
+use muxio_core::frame::FrameDecodeError;
+use muxio_core::rpc::RpcDispatcher;
+use muxio_rpc_service_caller::{RpcServiceCallerInterface, RpcTransportState};
+use muxio_rpc_service_endpoint::RpcServiceEndpoint;
+use muxio_rpc_service_endpoint::client_read_channel::{ClientReadTarget, spawn_client_read_loop};
+use term_session_muxio_service_definitions::remote::{REMOTE_AUTH_OK, RemoteToken, hello_frame};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::sync::{Mutex as TokioMutex, mpsc};
+use tokio::task::JoinHandle;
+
+/// How long connecting to and authenticating with a remote gateway may take.
+const REMOTE_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Read buffer per connection, matching the IPC transports.
+const READ_BUF_BYTES: usize = 64 * 1024;
+
+type StateChangeHandler = Arc<StdMutex<Option<Box<dyn Fn(RpcTransportState) + Send + Sync>>>>;
+
+/// A gateway on another machine, reached over TCP with a pre-shared token.
+#[derive(Clone, Debug)]
+pub struct RemoteGateway {
+    pub addr: String,
+    pub token: RemoteToken,
+}
+
+static REMOTE_GATEWAY: OnceLock<RemoteGateway> = OnceLock::new();
+
+/// Send every [`GatewayClient::connect`] in this process to `remote` instead
+/// of the local gateway socket. Set once, by `term-session --connect`.
+pub fn set_remote_gateway(remote: RemoteGateway) -> io::Result<()> {
+    REMOTE_GATEWAY
+        .set(remote)
+        .map_err(|_| io::Error::other("remote gateway already set"))
+}
+
+/// The remote gateway this process talks to, if any.
+pub fn remote_gateway() -> Option<&'static RemoteGateway> {
+    REMOTE_GATEWAY.get()
+}
+
+/// Whether the remote gateway's listener accepts connections (no handshake).
+pub fn probe_remote(remote: &RemoteGateway) -> bool {
+    std::net::TcpStream::connect(&remote.addr).is_ok()
+}
+
+/// An RPC client for the gateway, independent of the byte stream under it.
+pub struct GatewayClient {
+    dispatcher: Arc<TokioMutex<RpcDispatcher<'static>>>,
+    endpoint: Arc<RpcServiceEndpoint<()>>,
+    tx: mpsc::UnboundedSender<Vec<u8>>,
+    state_change_handler: StateChangeHandler,
+    is_connected: Arc<AtomicBool>,
+    task_handles: Vec<JoinHandle<()>>,
+}
+
+impl fmt::Debug for GatewayClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GatewayClient")
+            .field("is_connected", &self.is_connected.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+impl Drop for GatewayClient {
+    fn drop(&mut self) {
+        for handle in &self.task_handles {
+            handle.abort();
+        }
+        if self.is_connected.swap(false, Ordering::SeqCst) {
+            self.notify_state(RpcTransportState::Disconnected);
+        }
+    }
+}
+
+impl GatewayClient {
+    /// Connect to the gateway named `gateway`, or to the registered
+    /// [`RemoteGateway`] when there is one.
+    pub async fn connect(gateway: &str) -> io::Result<Arc<Self>> {
+        match remote_gateway() {
+            Some(remote) => Self::connect_remote(remote).await,
+            None => Self::connect_local(gateway).await,
+        }
+    }
+
+    /// Connect to a gateway's local IPC socket.
+    pub async fn connect_local(gateway: &str) -> io::Result<Arc<Self>> {
+        let name = gateway
+            .to_ns_name::<GenericNamespaced>()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        let stream = LocalSocketStream::connect(name)
+            .await
+            .map_err(|e| io::Error::new(io::ErrorKind::ConnectionRefused, e))?;
+        Ok(Self::from_stream(stream))
+    }
+
+    /// Open an authenticated TCP connection to `remote` and run RPC over it.
+    pub async fn connect_remote(remote: &RemoteGateway) -> io::Result<Arc<Self>> {
+        let stream = open_remote(&remote.addr, &remote.token).await?;
+        Ok(Self::from_stream(stream))
+    }
+
+    /// Run the RPC client over an already connected stream.
+    pub fn from_stream<S>(stream: S) -> Arc<Self>
+    where
+        S: AsyncRead + AsyncWrite + Send + 'static,
+    {
+        let (read_half, write_half) = tokio::io::split(stream);
+        let write_half = Arc::new(TokioMutex::new(write_half));
+        let (tx, send_handle) =
+            muxio_rpc_service_caller::write_channel::spawn_write_loop(move |msg: Vec<u8>| {
+                let w = Arc::clone(&write_half);
+                async move { w.lock().await.write_all(&msg).await.map_err(|_| ()) }
+            });
+
+        Arc::new_cyclic(|weak: &Weak<GatewayClient>| {
+            let read_stream = futures_util::stream::unfold(
+                (read_half, vec![0u8; READ_BUF_BYTES]),
+                |(mut r, mut buf)| async {
+                    let n = r.read(&mut buf).await.ok()?;
+                    (n > 0).then(|| (bytes::Bytes::copy_from_slice(&buf[..n]), (r, buf)))
+                },
+            );
+            let emit_tx = tx.clone();
+            let recv_handle =
+                spawn_client_read_loop(weak.clone(), Box::pin(read_stream), move |bytes| {
+                    let _ = emit_tx.send(bytes);
+                });
+            Self {
+                dispatcher: Arc::new(TokioMutex::new(RpcDispatcher::new())),
+                endpoint: Arc::new(RpcServiceEndpoint::new()),
+                tx,
+                state_change_handler: Arc::new(StdMutex::new(None)),
+                is_connected: Arc::new(AtomicBool::new(true)),
+                task_handles: vec![send_handle, recv_handle],
+            }
+        })
+    }
+
+    /// Endpoint for methods the gateway calls on this client (pushes).
+    pub fn get_endpoint(&self) -> Arc<RpcServiceEndpoint<()>> {
+        Arc::clone(&self.endpoint)
+    }
+
+    fn notify_state(&self, state: RpcTransportState) {
+        if let Ok(guard) = self.state_change_handler.lock()
+            && let Some(handler) = guard.as_ref()
+        {
+            handler(state);
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ClientReadTarget for GatewayClient {
+    fn dispatcher(&self) -> Arc<TokioMutex<RpcDispatcher<'static>>> {
+        Arc::clone(&self.dispatcher)
+    }
+
+    fn endpoint(&self) -> Arc<RpcServiceEndpoint<()>> {
+        Arc::clone(&self.endpoint)
+    }
+
+    async fn shutdown(&self) {
+        // Fail every pending call even if the flag already flipped, so no
+        // caller waits forever on a dead stream.
+        self.is_connected.store(false, Ordering::SeqCst);
+        self.notify_state(RpcTransportState::Disconnected);
+        self.dispatcher
+            .lock()
+            .await
+            .fail_all_pending_requests(FrameDecodeError::ReadAfterCancel);
+    }
+}
+
+#[async_trait::async_trait]
+impl RpcServiceCallerInterface for GatewayClient {
+    fn get_dispatcher(&self) -> Arc<TokioMutex<RpcDispatcher<'static>>> {
+        Arc::clone(&self.dispatcher)
+    }
+
+    fn get_emit_fn(&self) -> Arc<dyn Fn(Vec<u8>) + Send + Sync> {
+        let tx = self.tx.clone();
+        let is_connected = Arc::clone(&self.is_connected);
+        Arc::new(move |chunk: Vec<u8>| {
+            if is_connected.load(Ordering::Relaxed) {
+                let _ = tx.send(chunk);
+            }
+        })
+    }
+
+    fn is_connected(&self) -> bool {
+        self.is_connected.load(Ordering::Relaxed)
+    }
+
+    async fn set_state_change_handler(
+        &self,
+        handler: impl Fn(RpcTransportState) + Send + Sync + 'static,
+    ) {
+        if let Ok(mut guard) = self.state_change_handler.lock() {
+            if self.is_connected.load(Ordering::Relaxed) {
+                handler(RpcTransportState::Connected);
+            }
+            *guard = Some(Box::new(handler));
+        }
+    }
+}
+
+/// Connect to `addr` and complete the token handshake.
+async fn open_remote(addr: &str, token: &RemoteToken) -> io::Result<TcpStream> {
+    let handshake = async {
+        let mut stream = TcpStream::connect(addr).await?;
+        // Interactive traffic: small frames must not wait for Nagle.
+        stream.set_nodelay(true)?;
+        stream.write_all(&hello_frame(token)).await?;
+        let mut verdict = [0u8; 1];
+        let n = stream.read(&mut verdict).await?;
+        if n == 1 && verdict[0] == REMOTE_AUTH_OK {
+            Ok(stream)
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!(
+                    "remote gateway {addr} rejected the token (repeated failures lock an address out for a minute)"
+                ),
+            ))
+        }
+    };
+    tokio::time::timeout(REMOTE_CONNECT_TIMEOUT, handshake)
+        .await
+        .map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("remote gateway {addr} did not answer"),
+            )
+        })?
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::PermissionDenied => e,
+            kind => io::Error::new(kind, format!("remote gateway {addr}: {e}")),
+        })
+}
@@ -0,0 +1,189 @@
+//! Control mode: structured window-manager events and commands.
+//!
+//! A control client (`term-session control <channel>`, `term-wm --control`)
+//! subscribes to a channel on the gateway and receives a stream of
+//! [`ControlEvent`]s published by the window manager running in that
+//! channel; it drives the WM by sending [`ControlCommand`]s. The gateway only
+//! relays: it never interprets either type.
+//!
+//! Both types travel as bitcode over RPC and are printed/parsed as one JSON
+//! object per line by the clients, tagged with `"event"` or `"command"`:
+//!
+//! ```text
+//! {"event":"window_opened","window":4294967297,"title":"Shell 1"}
+//! {"command":"send_input","window":4294967297,"data":"ls\n"}
+//! ```
+
+use bitcode::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+
+/// Stable id of a window for the lifetime of the WM process. Ids of closed
+/// windows are never handed out again.
+pub type ControlWindowId = u64;
+
+/// One window in a [`ControlEvent::Windows`] listing. Geometry is the last
+/// drawn region in host terminal cells.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, Serialize, Deserialize)]
+pub struct ControlWindow {
+    pub window: ControlWindowId,
+    pub title: String,
+    pub focused: bool,
+    pub floating: bool,
+    pub x: i32,
+    pub y: i32,
+    pub width: u16,
+    pub height: u16,
+}
+
+/// Something that happened in the window manager.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ControlEvent {
+    WindowOpened {
+        window: ControlWindowId,
+        title: String,
+    },
+    WindowClosed {
+        window: ControlWindowId,
+    },
+    /// `None` when no window has focus (e.g. the last one closed).
+    FocusChanged {
+        window: Option<ControlWindowId>,
+    },
+    TitleChanged {
+        window: ControlWindowId,
+        title: String,
+    },
+    /// The app in `window` switched direct-input routing (alternate screen,
+    /// mouse tracking, ...).
+    DirectInputChanged {
+        window: ControlWindowId,
+        keyboard: bool,
+        mouse: bool,
+    },
+    /// `window` switched between floating and tiled, by the user or as the
+    /// side effect of a `resize` / `move` command on a tiled window.
+    FloatingChanged {
+        window: ControlWindowId,
+        floating: bool,
+    },
+    /// A toast was shown.
+    Notification {
+        id: u64,
+        message: String,
+    },
+    /// The process in `window` exited; a `window_closed` follows.
+    ProcessExited {
+        window: ControlWindowId,
+    },
+    /// Reply to [`ControlCommand::ListWindows`], in display order. The
+    /// gateway also requests one whenever a client subscribes, so a new
+    /// subscriber starts from a known state.
+    Windows {
+        windows: Vec<ControlWindow>,
+    },
+    /// A command could not be applied.
+    Error {
+        message: String,
+    },
+}
+
+/// Something a control client asks the window manager to do.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum ControlCommand {
+    /// Open a terminal window running the default shell; `run` is typed into
+    /// it like `term-wm --run`.
+    Open {
+        #[serde(default)]
+        run: Option<String>,
+        #[serde(default)]
+        title: Option<String>,
+    },
+    Close {
+        window: ControlWindowId,
+    },
+    Focus {
+        window: ControlWindowId,
+    },
+    /// Write `data` to the window's PTY as if typed.
+    SendInput {
+        window: ControlWindowId,
+        data: String,
+    },
+    /// Resize the window, floating it if it was tiled.
+    Resize {
+        window: ControlWindowId,
+        width: u16,
+        height: u16,
+    },
+    /// Move the window's top-left corner, floating it if it was tiled.
+    Move {
+        window: ControlWindowId,
+        x: i32,
+        y: i32,
+    },
+    ListWindows,
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_serialize_as_tagged_json_objects() {
+        let json = serde_json::to_string(&ControlEvent::TitleChanged {
+            window: 7,
+            title: "htop".into(),
+        })
+        .unwrap();
+        assert_eq!(
+            json,
+            r#"{"event":"title_changed","window":7,"title":"htop"}"#
+        );
+        let json = serde_json::to_string(&ControlEvent::FocusChanged { window: None }).unwrap();
+        assert_eq!(json, r#"{"event":"focus_changed","window":null}"#);
+    }
+
+    #[test]
+    fn commands_parse_from_json_with_optional_fields() {
+        let cmd: ControlCommand = serde_json::from_str(r#"{"command":"open"}"#).unwrap();
+        assert_eq!(
+            cmd,
+            ControlCommand::Open {
+                run: None,
+                title: None
+            }
+        );
+        let cmd: ControlCommand =
+            serde_json::from_str(r#"{"command":"move","window":3,"x":-2,"y":5}"#).unwrap();
+        assert_eq!(
+            cmd,
+            ControlCommand::Move {
+                window: 3,
+                x: -2,
+                y: 5
+            }
+        );
+        assert!(serde_json::from_str::<ControlCommand>(r#"{"command":"explode"}"#).is_err());
+    }
+
+    #[test]
+    fn events_round_trip_through_bitcode() {
+        let event = ControlEvent::Windows {
+            windows: vec![ControlWindow {
+                window: 1,
+                title: "Shell 1".into(),
+                focused: true,
+                floating: false,
+                x: 0,
+                y: 1,
+                width: 80,
+                height: 24,
+            }],
+        };
+        let decoded: ControlEvent = bitcode::decode(&bitcode::encode(&event)).unwrap();
+        assert_eq!(decoded, event);
+    }
+}
@@ -0,0 +1,136 @@
+//! Wire format for the gateway upgrade handoff.
+//!
+//! During `term-session upgrade` the running daemon streams every channel to
+//! its replacement over a private Unix socket. Each [`HandoffFrame::Channel`]
+//! travels with the session's PTY master descriptor attached (`SCM_RIGHTS`);
+//! [`HandoffFrame::Done`] closes the stream and the replacement acknowledges
+//! with a single byte once every channel is live. Frames are length-prefixed
+//! (`u32` little-endian) so the descriptor rides on the prefix and the body
+//! can be read with plain `read_exact`.
+//!
+//! Like [`crate::path_wire::PathWire`], this is same-host only: the old and
+//! new daemon always share a machine and a binary family.
+
+use std::io;
+
+use bitcode::{Decode, Encode};
+
+use crate::methods::RestartPolicy;
+use crate::path_wire::PathWire;
+
+/// Byte the replacement daemon writes once it owns every handed-off channel.
+pub const HANDOFF_ACK: u8 = 0x06;
+
+/// Upper bound on a single frame body. A channel carries at most one screen
+/// snapshot and the retained-output tail, so anything larger is corruption.
+pub const MAX_HANDOFF_FRAME_BYTES: usize = 64 * 1024 * 1024;
+
+/// A live PTY session. The master descriptor is not part of the body; it is
+/// attached to the frame's length prefix.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct HandoffSession {
+    pub id: u64,
+    pub child_pid: u32,
+    pub cols: u16,
+    pub rows: u16,
+    pub title: Option<String>,
+    /// The emulator snapshot (`Pty::generate_snapshot`) taken after the old
+    /// daemon's reader stopped, replayed into the new emulator.
+    pub screen_state: Vec<u8>,
+}
+
+/// One channel's persistent state. Connected clients are not transferred;
+/// they reconnect to the replacement daemon on their own.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct HandoffChannel {
+    pub name: String,
+    pub cmd: Vec<String>,
+    /// Working directory of the last spawn, reused by `CloneChannel`.
+    pub cwd: Option<PathWire>,
+    pub env: Vec<(String, String)>,
+    pub created_at_unix: u64,
+    pub created_seq: u64,
+    pub output_cache: Vec<u8>,
+    /// `None` for a channel whose session already exited (only the retained
+    /// output survives).
+    pub session: Option<HandoffSession>,
+    pub restart_policy: RestartPolicy,
+    pub restart_count: u32,
+    /// Consecutive quick exits, which drive the restart backoff.
+    pub restart_streak: u32,
+    pub last_exit_code: Option<i32>,
+    pub last_exit_unix: Option<u64>,
+    /// An automatic restart was scheduled; the replacement runs it at once.
+    pub restart_pending: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub enum HandoffFrame {
+    Channel(Box<HandoffChannel>),
+    /// End of the stream. `gateway` is the socket name the replacement must
+    /// bind (the old daemon's, whatever the new binary would resolve), and
+    /// `next_channel_seq` keeps `ListChannels` ordering across the upgrade.
+    Done {
+        gateway: String,
+        next_channel_seq: u64,
+    },
+}
+
+impl HandoffFrame {
+    pub fn encode(&self) -> Vec<u8> {
+        bitcode::encode(self)
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, io::Error> {
+        bitcode::decode::<HandoffFrame>(bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn channel_frame_round_trips() {
+        let frame = HandoffFrame::Channel(Box::new(HandoffChannel {
+            name: "dev/main".into(),
+            cmd: vec!["bash".into(), "-l".into()],
+            cwd: Some(PathWire::encode(std::path::Path::new("/srv/app"))),
+            env: vec![("EDITOR".into(), "vim".into())],
+            created_at_unix: 1_700_000_000,
+            created_seq: 7,
+            output_cache: b"tail".to_vec(),
+            session: Some(HandoffSession {
+                id: 1,
+                child_pid: 4242,
+                cols: 120,
+                rows: 40,
+                title: Some("vim".into()),
+                screen_state: b"\x1b[H\x1b[2Jhello".to_vec(),
+            }),
+            restart_policy: RestartPolicy::OnFailure { max_retries: 3 },
+            restart_count: 2,
+            restart_streak: 1,
+            last_exit_code: Some(1),
+            last_exit_unix: Some(1_700_000_100),
+            restart_pending: false,
+        }));
+        assert_eq!(HandoffFrame::decode(&frame.encode()).unwrap(), frame);
+    }
+
+    #[test]
+    fn done_frame_round_trips() {
+        let frame = HandoffFrame::Done {
+            gateway: "term-wm/dev/user/gateway".into(),
+            next_channel_seq: 12,
+        };
+        assert_eq!(HandoffFrame::decode(&frame.encode()).unwrap(), frame);
+    }
+
+    #[test]
+    fn malformed_frame_is_rejected() {
+        assert!(HandoffFrame::decode(b"not-bitcode").is_err());
+    }
+}
@@ -1,20 +1,36 @@
 pub mod channel;
+pub mod control;
+pub mod handoff;
 pub mod methods;
+pub mod output_codec;
 pub mod path_wire;
+pub mod remote;
 
 pub use channel::{
     ChannelName, DEFAULT_WORKSPACE, GATEWAY_CHANNEL_ENV_VAR, GATEWAY_NAMESPACE,
     SESSION_ACTIVE_ENV_VAR, SESSION_CHANNEL_NAME, SESSION_GATEWAY_ENV_VAR, gateway_channel_name,
     gateway_help_line, probe_ipc_endpoint,
 };
+pub use control::{ControlCommand, ControlEvent, ControlWindow, ControlWindowId};
+pub use handoff::{HandoffChannel, HandoffFrame, HandoffSession};
 pub use methods::{
-    Attach, AttachRequest, ChannelInfo, ClientInfo, CloseSession, KillChannel, KillClient,
-    ListChannels, ListChannelsResponse, OnAttributedInput, OnAttributedInputRequest, OnPtyResized,
-    OnWorkspaceRebind, OnWorkspaceRebindRequest, PushOutput, RPC_ERROR_LIVE_PARTICIPANTS,
-    RPC_ERROR_LIVE_SESSIONS, RPC_ERROR_SHUTTING_DOWN, RPC_ERROR_UNATTACHED, RebindWorkspace,
-    RebindWorkspaceRequest, ResizePty, STREAM_INPUT_METHOD_ID, SUBSCRIBE_OUTPUT_METHOD_ID,
-    SendAttributedInput, SendAttributedInputRequest, SessionInfo, ShutdownGateway, Spawn,
-    SpawnRequest, SpawnResponse, SubscribeInternalInput, SubscribeInternalInputRequest, WriteInput,
+    Attach, AttachRequest, ChannelInfo, ClientInfo, CloneChannel, CloneChannelRequest,
+    CloseSession, KillChannel, KillClient, ListChannels, ListChannelsResponse, NegotiateOutput,
+    OnAttributedInput, OnAttributedInputRequest, OnChannelRenamed, OnChannelRenamedRequest,
+    OnControlCommand, OnControlEvents, OnGatewayUpgrade, OnPtyResized, OnWorkspaceRebind,
+    OnWorkspaceRebindRequest, PublishControlEvents, PublishControlEventsRequest, PushOutput,
+    RPC_ERROR_CHANNEL_EXISTS, RPC_ERROR_LIVE_PARTICIPANTS, RPC_ERROR_LIVE_SESSIONS,
+    RPC_ERROR_NO_CONTROL_TARGET, RPC_ERROR_SHUTTING_DOWN, RPC_ERROR_UNATTACHED,
+    RPC_ERROR_UPGRADE_UNSUPPORTED, RebindWorkspace, RebindWorkspaceRequest, RenameChannel,
+    RenameChannelRequest, ResizePty, RestartPolicy, STREAM_INPUT_METHOD_ID,
+    SUBSCRIBE_OUTPUT_METHOD_ID, SendAttributedInput, SendAttributedInputRequest,
+    SendControlCommand, SendControlCommandRequest, SessionInfo, SetChannelEnv,
+    SetChannelEnvRequest, SetRestartPolicy, SetRestartPolicyRequest, ShutdownGateway, Spawn,
+    SpawnRequest, SpawnResponse, SubscribeControl, SubscribeControlRequest, SubscribeInternalInput,
+    SubscribeInternalInputRequest, UpgradeGateway, UpgradeGatewayRequest, UpgradeGatewayResponse,
+    WriteInput,
 };
 pub use muxio_rpc_service::prebuffered::RpcMethodPrebuffered;
+pub use output_codec::{OUTPUT_CODEC_ENV_VAR, OutputCodec, OutputDecoder, OutputEncoder};
 pub use path_wire::PathWire;
+pub use remote::RemoteToken;
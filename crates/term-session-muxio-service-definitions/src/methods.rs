@@ -1,8 +1,11 @@
+use std::fmt;
 use std::io;
 
 use bitcode::{Decode, Encode};
 use muxio_rpc_service::{prebuffered::RpcMethodPrebuffered, rpc_method_id};
 
+use crate::control::{ControlCommand, ControlEvent};
+use crate::output_codec::OutputCodec;
 use crate::path_wire::PathWire;
 use term_wm_events::Event;
 
@@ -15,6 +18,11 @@ pub const RPC_ERROR_UNATTACHED: &str =
 pub const RPC_ERROR_SHUTTING_DOWN: &str = "gateway: shutting down";
 pub const RPC_ERROR_LIVE_SESSIONS: &str =
     "gateway: live session(s) running; use `--force` to stop anyway";
+pub const RPC_ERROR_UPGRADE_UNSUPPORTED: &str =
+    "gateway: live upgrade is not supported on this platform";
+pub const RPC_ERROR_NO_CONTROL_TARGET: &str =
+    "gateway: no window manager is running on this channel; control mode needs `term-wm`";
+pub const RPC_ERROR_CHANNEL_EXISTS: &str = "gateway: a channel with that name already exists";
 pub const RPC_ERROR_LIVE_PARTICIPANTS: &str = "gateway: live participant(s) attached to channel; use `term-session kill <channel> --force` to kill anyway";
 
 // ── Attach ──────────────────────────────────────────────────────────
@@ -276,6 +284,65 @@ pub const STREAM_INPUT_METHOD_ID: u64 = rpc_method_id!("session.stream_input");
 // ── SubscribeOutput (streaming handler for PTY output pushes) ──────
 pub const SUBSCRIBE_OUTPUT_METHOD_ID: u64 = rpc_method_id!("session.subscribe_output");
 
+// ── NegotiateOutput ──────────────────────────────────────────────────
+
+#[derive(Encode, Decode)]
+struct NegotiateOutputRequest {
+    /// [`OutputCodec::wire_id`]s, most preferred first.
+    pub codecs: Vec<u8>,
+}
+
+#[derive(Encode, Decode)]
+struct NegotiateOutputResponse {
+    pub codec: u8,
+}
+
+/// Picks the encoding of this connection's later `SubscribeOutput` streams
+/// from the client's offer. Connections that never call it stay on
+/// [`OutputCodec::Raw`].
+pub struct NegotiateOutput;
+
+impl RpcMethodPrebuffered for NegotiateOutput {
+    const METHOD_ID: u64 = rpc_method_id!("session.negotiate_output");
+
+    type Input = Vec<OutputCodec>;
+    type Output = OutputCodec;
+
+    fn encode_request(input: Self::Input) -> Result<Vec<u8>, io::Error> {
+        Ok(bitcode::encode(&NegotiateOutputRequest {
+            codecs: input.into_iter().map(OutputCodec::wire_id).collect(),
+        }))
+    }
+
+    /// Unknown codec ids are dropped, so a newer client's offer still
+    /// decodes here.
+    fn decode_request(bytes: &[u8]) -> Result<Self::Input, io::Error> {
+        let r = bitcode::decode::<NegotiateOutputRequest>(bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(r.codecs
+            .into_iter()
+            .filter_map(OutputCodec::from_wire_id)
+            .collect())
+    }
+
+    fn encode_response(output: Self::Output) -> Result<Vec<u8>, io::Error> {
+        Ok(bitcode::encode(&NegotiateOutputResponse {
+            codec: output.wire_id(),
+        }))
+    }
+
+    fn decode_response(bytes: &[u8]) -> Result<Self::Output, io::Error> {
+        let r = bitcode::decode::<NegotiateOutputResponse>(bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        OutputCodec::from_wire_id(r.codec).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("gateway chose unknown output codec {}", r.codec),
+            )
+        })
+    }
+}
+
 // ── ListChannels ─────────────────────────────────────────────────────
 
 /// Public wire info for one session on a channel.
@@ -287,6 +354,12 @@ pub struct SessionInfo {
     pub exited: bool,
     pub exit_code: Option<i32>,
     pub title: String,
+    /// Automatic restarts of the channel's session so far.
+    pub restart_count: u32,
+    /// Exit code of the previous session, if one has exited.
+    pub last_exit_code: Option<i32>,
+    /// Unix seconds when the previous session exited.
+    pub last_exit_unix: Option<u64>,
 }
 
 /// Public wire info for one attached client socket on a channel.
@@ -314,6 +387,11 @@ pub struct ChannelInfo {
     pub created_at_unix: u64,
     pub session: Option<SessionInfo>,
     pub clients: Vec<ClientInfo>,
+    /// Per-channel environment applied to every spawn, sorted by name.
+    pub env: Vec<(String, String)>,
+    pub restart_policy: RestartPolicy,
+    /// True while the session has exited and an automatic restart is due.
+    pub restart_pending: bool,
 }
 
 /// Response for `ListChannels`: the gateway's PID + bound socket name plus the
@@ -478,6 +556,82 @@ impl RpcMethodPrebuffered for ShutdownGateway {
     }
 }
 
+// ── UpgradeGateway ───────────────────────────────────────────────────
+
+/// Request for `UpgradeGateway`: replace the running daemon with `binary`
+/// (the daemon's own executable when `None`) without ending any session.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct UpgradeGatewayRequest {
+    pub binary: Option<PathWire>,
+}
+
+/// Response for `UpgradeGateway`, sent once the replacement daemon has taken
+/// ownership of every channel.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct UpgradeGatewayResponse {
+    /// OS PID of the replacement daemon.
+    pub new_pid: u64,
+    /// Number of channels handed over.
+    pub channels: u32,
+}
+
+pub struct UpgradeGateway;
+
+impl RpcMethodPrebuffered for UpgradeGateway {
+    const METHOD_ID: u64 = rpc_method_id!("session.upgrade_gateway");
+
+    type Input = UpgradeGatewayRequest;
+    type Output = UpgradeGatewayResponse;
+
+    fn encode_request(input: Self::Input) -> Result<Vec<u8>, io::Error> {
+        Ok(bitcode::encode(&input))
+    }
+
+    fn decode_request(bytes: &[u8]) -> Result<Self::Input, io::Error> {
+        bitcode::decode::<UpgradeGatewayRequest>(bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn encode_response(output: Self::Output) -> Result<Vec<u8>, io::Error> {
+        Ok(bitcode::encode(&output))
+    }
+
+    fn decode_response(bytes: &[u8]) -> Result<Self::Output, io::Error> {
+        bitcode::decode::<UpgradeGatewayResponse>(bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+// ── OnGatewayUpgrade (server pushes before handing off to a new daemon) ──
+
+/// Sent to every connected client once `UpgradeGateway` has committed. The
+/// connection drops shortly after; a client that saw this reconnects to the
+/// replacement daemon instead of treating the drop as the session ending.
+pub struct OnGatewayUpgrade;
+
+impl RpcMethodPrebuffered for OnGatewayUpgrade {
+    const METHOD_ID: u64 = rpc_method_id!("session.on_gateway_upgrade");
+
+    type Input = ();
+    type Output = ();
+
+    fn encode_request(_input: Self::Input) -> Result<Vec<u8>, io::Error> {
+        Ok(Vec::new())
+    }
+
+    fn decode_request(_bytes: &[u8]) -> Result<Self::Input, io::Error> {
+        Ok(())
+    }
+
+    fn encode_response(_output: Self::Output) -> Result<Vec<u8>, io::Error> {
+        Ok(Vec::new())
+    }
+
+    fn decode_response(_bytes: &[u8]) -> Result<Self::Output, io::Error> {
+        Ok(())
+    }
+}
+
 // ── RebindWorkspace (client asks server to rebind viewers) ───────────
 
 /// Client request to rebind all viewers on `source_channel` to `target`.
@@ -651,6 +805,371 @@ impl RpcMethodPrebuffered for SubscribeInternalInput {
     }
 }
 
+// ── SubscribeControl (control client -> server: receive WM events) ───
+
+/// A control client asks for the channel's WM events, pushed back to it via
+/// [`OnControlEvents`] until it disconnects. Does not attach to the channel.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct SubscribeControlRequest {
+    pub channel: String,
+}
+
+pub struct SubscribeControl;
+
+impl RpcMethodPrebuffered for SubscribeControl {
+    const METHOD_ID: u64 = rpc_method_id!("session.subscribe_control");
+
+    type Input = SubscribeControlRequest;
+    type Output = ();
+
+    fn encode_request(input: Self::Input) -> Result<Vec<u8>, io::Error> {
+        Ok(bitcode::encode(&input))
+    }
+
+    fn decode_request(bytes: &[u8]) -> Result<Self::Input, io::Error> {
+        bitcode::decode::<SubscribeControlRequest>(bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn encode_response(_output: Self::Output) -> Result<Vec<u8>, io::Error> {
+        Ok(Vec::new())
+    }
+
+    fn decode_response(_bytes: &[u8]) -> Result<Self::Output, io::Error> {
+        Ok(())
+    }
+}
+
+// ── PublishControlEvents (inner WM -> server: fan out to subscribers) ─
+
+/// The inner WM publishes a batch of events (one frame's worth) for its
+/// channel's control subscribers.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct PublishControlEventsRequest {
+    pub channel: String,
+    pub events: Vec<ControlEvent>,
+}
+
+pub struct PublishControlEvents;
+
+impl RpcMethodPrebuffered for PublishControlEvents {
+    const METHOD_ID: u64 = rpc_method_id!("session.publish_control_events");
+
+    type Input = PublishControlEventsRequest;
+    type Output = ();
+
+    fn encode_request(input: Self::Input) -> Result<Vec<u8>, io::Error> {
+        Ok(bitcode::encode(&input))
+    }
+
+    fn decode_request(bytes: &[u8]) -> Result<Self::Input, io::Error> {
+        bitcode::decode::<PublishControlEventsRequest>(bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn encode_response(_output: Self::Output) -> Result<Vec<u8>, io::Error> {
+        Ok(Vec::new())
+    }
+
+    fn decode_response(_bytes: &[u8]) -> Result<Self::Output, io::Error> {
+        Ok(())
+    }
+}
+
+// ── OnControlEvents (server -> control client: event batch) ──────────
+
+/// Server pushes a batch of WM events to a control subscriber, in order.
+pub struct OnControlEvents;
+
+impl RpcMethodPrebuffered for OnControlEvents {
+    const METHOD_ID: u64 = rpc_method_id!("session.on_control_events");
+
+    type Input = Vec<ControlEvent>;
+    type Output = ();
+
+    fn encode_request(input: Self::Input) -> Result<Vec<u8>, io::Error> {
+        Ok(bitcode::encode(&input))
+    }
+
+    fn decode_request(bytes: &[u8]) -> Result<Self::Input, io::Error> {
+        bitcode::decode::<Vec<ControlEvent>>(bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn encode_response(_output: Self::Output) -> Result<Vec<u8>, io::Error> {
+        Ok(Vec::new())
+    }
+
+    fn decode_response(_bytes: &[u8]) -> Result<Self::Output, io::Error> {
+        Ok(())
+    }
+}
+
+// ── SendControlCommand (control client -> server: WM command) ────────
+
+/// A control client sends a command to the WM on `channel`. Fails with
+/// [`RPC_ERROR_NO_CONTROL_TARGET`] when no WM has subscribed there.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct SendControlCommandRequest {
+    pub channel: String,
+    pub command: ControlCommand,
+}
+
+pub struct SendControlCommand;
+
+impl RpcMethodPrebuffered for SendControlCommand {
+    const METHOD_ID: u64 = rpc_method_id!("session.send_control_command");
+
+    type Input = SendControlCommandRequest;
+    type Output = ();
+
+    fn encode_request(input: Self::Input) -> Result<Vec<u8>, io::Error> {
+        Ok(bitcode::encode(&input))
+    }
+
+    fn decode_request(bytes: &[u8]) -> Result<Self::Input, io::Error> {
+        bitcode::decode::<SendControlCommandRequest>(bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn encode_response(_output: Self::Output) -> Result<Vec<u8>, io::Error> {
+        Ok(Vec::new())
+    }
+
+    fn decode_response(_bytes: &[u8]) -> Result<Self::Output, io::Error> {
+        Ok(())
+    }
+}
+
+// ── OnControlCommand (server -> inner WM: command) ───────────────────
+
+/// Server forwards a control command to the channel's inner WM.
+pub struct OnControlCommand;
+
+impl RpcMethodPrebuffered for OnControlCommand {
+    const METHOD_ID: u64 = rpc_method_id!("session.on_control_command");
+
+    type Input = ControlCommand;
+    type Output = ();
+
+    fn encode_request(input: Self::Input) -> Result<Vec<u8>, io::Error> {
+        Ok(bitcode::encode(&input))
+    }
+
+    fn decode_request(bytes: &[u8]) -> Result<Self::Input, io::Error> {
+        bitcode::decode::<ControlCommand>(bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn encode_response(_output: Self::Output) -> Result<Vec<u8>, io::Error> {
+        Ok(Vec::new())
+    }
+
+    fn decode_response(_bytes: &[u8]) -> Result<Self::Output, io::Error> {
+        Ok(())
+    }
+}
+
+// ── RenameChannel ────────────────────────────────────────────────────
+
+/// Rename `from` to `to`, keeping its session, clients and subscriptions.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct RenameChannelRequest {
+    pub from: String,
+    pub to: String,
+}
+
+pub struct RenameChannel;
+
+impl RpcMethodPrebuffered for RenameChannel {
+    const METHOD_ID: u64 = rpc_method_id!("session.rename_channel");
+
+    type Input = RenameChannelRequest;
+    type Output = ();
+
+    fn encode_request(input: Self::Input) -> Result<Vec<u8>, io::Error> {
+        Ok(bitcode::encode(&input))
+    }
+
+    fn decode_request(bytes: &[u8]) -> Result<Self::Input, io::Error> {
+        bitcode::decode::<RenameChannelRequest>(bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn encode_response(_output: Self::Output) -> Result<Vec<u8>, io::Error> {
+        Ok(Vec::new())
+    }
+
+    fn decode_response(_bytes: &[u8]) -> Result<Self::Output, io::Error> {
+        Ok(())
+    }
+}
+
+// ── OnChannelRenamed (server -> inner WM: its channel has a new name) ──
+
+/// Server push telling the inner WM that its channel is now `channel`.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct OnChannelRenamedRequest {
+    pub channel: String,
+}
+
+pub struct OnChannelRenamed;
+
+impl RpcMethodPrebuffered for OnChannelRenamed {
+    const METHOD_ID: u64 = rpc_method_id!("session.on_channel_renamed");
+
+    type Input = OnChannelRenamedRequest;
+    type Output = ();
+
+    fn encode_request(input: Self::Input) -> Result<Vec<u8>, io::Error> {
+        Ok(bitcode::encode(&input))
+    }
+
+    fn decode_request(bytes: &[u8]) -> Result<Self::Input, io::Error> {
+        bitcode::decode::<OnChannelRenamedRequest>(bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn encode_response(_output: Self::Output) -> Result<Vec<u8>, io::Error> {
+        Ok(Vec::new())
+    }
+
+    fn decode_response(_bytes: &[u8]) -> Result<Self::Output, io::Error> {
+        Ok(())
+    }
+}
+
+// ── CloneChannel ─────────────────────────────────────────────────────
+
+/// Create `target` running a new session with `source`'s command, working
+/// directory and environment.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct CloneChannelRequest {
+    pub source: String,
+    pub target: String,
+}
+
+pub struct CloneChannel;
+
+impl RpcMethodPrebuffered for CloneChannel {
+    const METHOD_ID: u64 = rpc_method_id!("session.clone_channel");
+
+    type Input = CloneChannelRequest;
+    type Output = ();
+
+    fn encode_request(input: Self::Input) -> Result<Vec<u8>, io::Error> {
+        Ok(bitcode::encode(&input))
+    }
+
+    fn decode_request(bytes: &[u8]) -> Result<Self::Input, io::Error> {
+        bitcode::decode::<CloneChannelRequest>(bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn encode_response(_output: Self::Output) -> Result<Vec<u8>, io::Error> {
+        Ok(Vec::new())
+    }
+
+    fn decode_response(_bytes: &[u8]) -> Result<Self::Output, io::Error> {
+        Ok(())
+    }
+}
+
+// ── SetChannelEnv ────────────────────────────────────────────────────
+
+/// Change a channel's environment. Takes effect on the next spawn; the
+/// running session keeps the environment it started with.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct SetChannelEnvRequest {
+    pub channel: String,
+    pub set: Vec<(String, String)>,
+    pub unset: Vec<String>,
+}
+
+pub struct SetChannelEnv;
+
+impl RpcMethodPrebuffered for SetChannelEnv {
+    const METHOD_ID: u64 = rpc_method_id!("session.set_channel_env");
+
+    type Input = SetChannelEnvRequest;
+    type Output = ();
+
+    fn encode_request(input: Self::Input) -> Result<Vec<u8>, io::Error> {
+        Ok(bitcode::encode(&input))
+    }
+
+    fn decode_request(bytes: &[u8]) -> Result<Self::Input, io::Error> {
+        bitcode::decode::<SetChannelEnvRequest>(bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn encode_response(_output: Self::Output) -> Result<Vec<u8>, io::Error> {
+        Ok(Vec::new())
+    }
+
+    fn decode_response(_bytes: &[u8]) -> Result<Self::Output, io::Error> {
+        Ok(())
+    }
+}
+
+// ── SetRestartPolicy ─────────────────────────────────────────────────
+
+/// What the gateway does when a channel's session exits on its own. An
+/// explicit kill never triggers a restart.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Encode, Decode)]
+pub enum RestartPolicy {
+    /// Leave the channel without a session until the next attach.
+    #[default]
+    Never,
+    /// Restart after a non-zero exit, with exponential backoff, giving up
+    /// after `max_retries` consecutive quick failures.
+    OnFailure { max_retries: u32 },
+    /// Restart after every exit, with the same backoff.
+    Always,
+}
+
+impl fmt::Display for RestartPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Never => f.write_str("never"),
+            Self::OnFailure { max_retries } => write!(f, "on-failure (max {max_retries} retries)"),
+            Self::Always => f.write_str("always"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct SetRestartPolicyRequest {
+    pub channel: String,
+    pub policy: RestartPolicy,
+}
+
+pub struct SetRestartPolicy;
+
+impl RpcMethodPrebuffered for SetRestartPolicy {
+    const METHOD_ID: u64 = rpc_method_id!("session.set_restart_policy");
+
+    type Input = SetRestartPolicyRequest;
+    type Output = ();
+
+    fn encode_request(input: Self::Input) -> Result<Vec<u8>, io::Error> {
+        Ok(bitcode::encode(&input))
+    }
+
+    fn decode_request(bytes: &[u8]) -> Result<Self::Input, io::Error> {
+        bitcode::decode::<SetRestartPolicyRequest>(bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn encode_response(_output: Self::Output) -> Result<Vec<u8>, io::Error> {
+        Ok(Vec::new())
+    }
+
+    fn decode_response(_bytes: &[u8]) -> Result<Self::Output, io::Error> {
+        Ok(())
+    }
+}
+
 #[allow(clippy::unwrap_used)]
 #[cfg(test)]
 mod tests {
@@ -727,6 +1246,98 @@ mod tests {
         assert_eq!(OnPtyResized::decode_request(&bytes).unwrap(), (200, 60));
     }
 
+    #[test]
+    fn upgrade_gateway_round_trips() {
+        let req = roundtrip_request::<UpgradeGateway>(UpgradeGatewayRequest {
+            binary: Some(PathWire::encode("/usr/bin/term-wm")),
+        });
+        assert_eq!(
+            req.binary.map(|b| b.decode()),
+            Some(std::path::PathBuf::from("/usr/bin/term-wm"))
+        );
+        let bytes = UpgradeGateway::encode_response(UpgradeGatewayResponse {
+            new_pid: 4242,
+            channels: 3,
+        })
+        .unwrap();
+        let resp = UpgradeGateway::decode_response(&bytes).unwrap();
+        assert_eq!((resp.new_pid, resp.channels), (4242, 3));
+    }
+
+    #[test]
+    fn control_methods_round_trip() {
+        let req = roundtrip_request::<SendControlCommand>(SendControlCommandRequest {
+            channel: "dev/main".into(),
+            command: ControlCommand::Focus { window: 9 },
+        });
+        assert_eq!(req.channel, "dev/main");
+        assert_eq!(req.command, ControlCommand::Focus { window: 9 });
+
+        let req = roundtrip_request::<PublishControlEvents>(PublishControlEventsRequest {
+            channel: "dev/main".into(),
+            events: vec![ControlEvent::WindowClosed { window: 9 }],
+        });
+        assert_eq!(req.events, vec![ControlEvent::WindowClosed { window: 9 }]);
+
+        let events = vec![ControlEvent::ProcessExited { window: 9 }];
+        let bytes = OnControlEvents::encode_request(events.clone()).unwrap();
+        assert_eq!(OnControlEvents::decode_request(&bytes).unwrap(), events);
+
+        let bytes = OnControlCommand::encode_request(ControlCommand::ListWindows).unwrap();
+        assert_eq!(
+            OnControlCommand::decode_request(&bytes).unwrap(),
+            ControlCommand::ListWindows
+        );
+
+        let req = roundtrip_request::<SubscribeControl>(SubscribeControlRequest {
+            channel: "dev/main".into(),
+        });
+        assert_eq!(req.channel, "dev/main");
+    }
+
+    #[test]
+    fn channel_management_methods_round_trip() {
+        let req = roundtrip_request::<RenameChannel>(RenameChannelRequest {
+            from: "dev/main".into(),
+            to: "prod/main".into(),
+        });
+        assert_eq!(
+            (req.from.as_str(), req.to.as_str()),
+            ("dev/main", "prod/main")
+        );
+
+        let req = roundtrip_request::<CloneChannel>(CloneChannelRequest {
+            source: "dev/main".into(),
+            target: "dev/copy".into(),
+        });
+        assert_eq!(req.target, "dev/copy");
+
+        let req = roundtrip_request::<SetChannelEnv>(SetChannelEnvRequest {
+            channel: "dev/main".into(),
+            set: vec![("EDITOR".into(), "vim".into())],
+            unset: vec!["PAGER".into()],
+        });
+        assert_eq!(req.set, vec![("EDITOR".to_string(), "vim".to_string())]);
+        assert_eq!(req.unset, vec!["PAGER".to_string()]);
+
+        let req = roundtrip_request::<OnChannelRenamed>(OnChannelRenamedRequest {
+            channel: "prod/main".into(),
+        });
+        assert_eq!(req.channel, "prod/main");
+    }
+
+    #[test]
+    fn set_restart_policy_round_trips() {
+        let req = roundtrip_request::<SetRestartPolicy>(SetRestartPolicyRequest {
+            channel: "dev/server".into(),
+            policy: RestartPolicy::OnFailure { max_retries: 5 },
+        });
+        assert_eq!(req.channel, "dev/server");
+        assert_eq!(req.policy, RestartPolicy::OnFailure { max_retries: 5 });
+        assert_eq!(req.policy.to_string(), "on-failure (max 5 retries)");
+        assert_eq!(RestartPolicy::default(), RestartPolicy::Never);
+    }
+
     #[test]
     fn malformed_wire_bytes_are_rejected() {
         let bad = b"not-bitcode".to_vec();
@@ -736,6 +1347,13 @@ mod tests {
         assert!(OnAttributedInput::decode_request(&bad).is_err());
         assert!(SubscribeInternalInput::decode_request(&bad).is_err());
         assert!(OnPtyResized::decode_request(&bad).is_err());
+        assert!(UpgradeGateway::decode_request(&bad).is_err());
+        assert!(SendControlCommand::decode_request(&bad).is_err());
+        assert!(PublishControlEvents::decode_request(&bad).is_err());
+        assert!(RenameChannel::decode_request(&bad).is_err());
+        assert!(CloneChannel::decode_request(&bad).is_err());
+        assert!(SetChannelEnv::decode_request(&bad).is_err());
+        assert!(SetRestartPolicy::decode_request(&bad).is_err());
     }
 
     #[test]
@@ -744,6 +1362,9 @@ mod tests {
         assert!(RPC_ERROR_SHUTTING_DOWN.starts_with("gateway:"));
         assert!(RPC_ERROR_LIVE_SESSIONS.starts_with("gateway:"));
         assert!(RPC_ERROR_LIVE_PARTICIPANTS.starts_with("gateway:"));
+        assert!(RPC_ERROR_UPGRADE_UNSUPPORTED.starts_with("gateway:"));
+        assert!(RPC_ERROR_NO_CONTROL_TARGET.starts_with("gateway:"));
+        assert!(RPC_ERROR_CHANNEL_EXISTS.starts_with("gateway:"));
     }
 }
 
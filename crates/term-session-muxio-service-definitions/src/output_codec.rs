@@ -0,0 +1,341 @@
+//! Negotiated compression for the `SubscribeOutput` stream.
+//!
+//! A client that calls [`NegotiateOutput`](crate::NegotiateOutput) before
+//! opening its output subscription may receive every PTY chunk compressed.
+//! Clients that never negotiate (and gateways that predate it) keep the raw
+//! stream, so the negotiation is the only compatibility switch.
+//!
+//! With [`OutputCodec::Lz4`] each non-empty frame starts with a tag byte:
+//!
+//! ```text
+//! 0x00 <bytes>                       stored verbatim
+//! 0x01 <u32 LE length> <lz4 block>   compressed
+//! ```
+//!
+//! Both ends keep the last [`DICTIONARY_BYTES`] of decompressed output as the
+//! block's external dictionary, so a redraw that repeats what scrolled by a
+//! moment ago compresses to a handful of back-references. The dictionary is
+//! per stream: it starts empty when the subscription opens and only ever
+//! advances in frame order. Empty frames carry no tag and pass through
+//! unchanged.
+
+use std::io;
+
+pub use term_wm_config::env::OUTPUT_CODEC_ENV_VAR;
+
+/// Trailing decompressed output kept as the lz4 dictionary (the format's
+/// 64 KiB match window).
+pub const DICTIONARY_BYTES: usize = 64 * 1024;
+
+/// Chunks shorter than this are stored: keystroke echo gains nothing from
+/// compression and should not wait on it.
+pub const MIN_COMPRESS_BYTES: usize = 64;
+
+/// Upper bound on one decompressed frame; a larger length is corruption.
+pub const MAX_FRAME_BYTES: usize = 16 * 1024 * 1024;
+
+const TAG_STORED: u8 = 0x00;
+const TAG_LZ4: u8 = 0x01;
+
+/// Encoding of the `SubscribeOutput` stream.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum OutputCodec {
+    /// Chunks travel untouched, without a tag byte.
+    #[default]
+    Raw,
+    /// Tagged frames, lz4 blocks against the per-stream dictionary.
+    Lz4,
+}
+
+impl OutputCodec {
+    /// Every codec this build can decode, most preferred first.
+    pub const SUPPORTED: &[OutputCodec] = &[OutputCodec::Lz4, OutputCodec::Raw];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            OutputCodec::Raw => "raw",
+            OutputCodec::Lz4 => "lz4",
+        }
+    }
+
+    /// Parses [`name`](Self::name) (case-insensitive); `none` is accepted
+    /// for `raw`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "raw" | "none" => Some(OutputCodec::Raw),
+            "lz4" => Some(OutputCodec::Lz4),
+            _ => None,
+        }
+    }
+
+    /// Wire id used during negotiation. Ids are stable; a peer skips the ids
+    /// it does not know instead of failing to decode the whole offer.
+    pub fn wire_id(self) -> u8 {
+        match self {
+            OutputCodec::Raw => 0,
+            OutputCodec::Lz4 => 1,
+        }
+    }
+
+    pub fn from_wire_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(OutputCodec::Raw),
+            1 => Some(OutputCodec::Lz4),
+            _ => None,
+        }
+    }
+
+    /// The first codec in the client's preference-ordered `offer` that this
+    /// build supports, or `Raw`.
+    pub fn choose(offer: &[OutputCodec]) -> Self {
+        offer
+            .iter()
+            .copied()
+            .find(|codec| Self::SUPPORTED.contains(codec))
+            .unwrap_or_default()
+    }
+}
+
+/// What a client offers in `NegotiateOutput`: the codec named by
+/// `TERM_SESSION_OUTPUT_CODEC` alone, otherwise [`OutputCodec::SUPPORTED`].
+pub fn client_offer() -> Vec<OutputCodec> {
+    std::env::var(OUTPUT_CODEC_ENV_VAR)
+        .ok()
+        .and_then(|name| OutputCodec::from_name(&name))
+        .map_or_else(|| OutputCodec::SUPPORTED.to_vec(), |codec| vec![codec])
+}
+
+impl std::fmt::Display for OutputCodec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Sliding window over the decompressed stream, shared by both directions.
+#[derive(Debug, Default)]
+struct History(Vec<u8>);
+
+impl History {
+    fn push(&mut self, data: &[u8]) {
+        if data.len() >= DICTIONARY_BYTES {
+            self.0.clear();
+            self.0
+                .extend_from_slice(&data[data.len() - DICTIONARY_BYTES..]);
+            return;
+        }
+        let overflow = (self.0.len() + data.len()).saturating_sub(DICTIONARY_BYTES);
+        self.0.drain(..overflow);
+        self.0.extend_from_slice(data);
+    }
+}
+
+/// Server half of one output stream.
+#[derive(Debug, Default)]
+pub struct OutputEncoder {
+    codec: OutputCodec,
+    history: History,
+}
+
+impl OutputEncoder {
+    pub fn new(codec: OutputCodec) -> Self {
+        Self {
+            codec,
+            history: History::default(),
+        }
+    }
+
+    pub fn codec(&self) -> OutputCodec {
+        self.codec
+    }
+
+    /// Frame `chunk` for the wire. Falls back to a stored frame when the
+    /// chunk is small or does not shrink.
+    pub fn encode(&mut self, chunk: &[u8]) -> Vec<u8> {
+        if self.codec == OutputCodec::Raw || chunk.is_empty() {
+            return chunk.to_vec();
+        }
+        let mut frame = Vec::with_capacity(chunk.len() + 5);
+        if chunk.len() >= MIN_COMPRESS_BYTES {
+            let block = lz4_flex::block::compress_with_dict(chunk, &self.history.0);
+            if block.len() + 4 < chunk.len() {
+                frame.push(TAG_LZ4);
+                frame.extend_from_slice(&(chunk.len() as u32).to_le_bytes());
+                frame.extend_from_slice(&block);
+            }
+        }
+        if frame.is_empty() {
+            frame.push(TAG_STORED);
+            frame.extend_from_slice(chunk);
+        }
+        self.history.push(chunk);
+        frame
+    }
+}
+
+/// Client half of one output stream.
+#[derive(Debug, Default)]
+pub struct OutputDecoder {
+    codec: OutputCodec,
+    history: History,
+}
+
+impl OutputDecoder {
+    pub fn new(codec: OutputCodec) -> Self {
+        Self {
+            codec,
+            history: History::default(),
+        }
+    }
+
+    pub fn codec(&self) -> OutputCodec {
+        self.codec
+    }
+
+    /// Undo [`OutputEncoder::encode`]. Frames must arrive in the order they
+    /// were encoded; an error leaves the dictionary out of step, so the
+    /// stream is unusable afterwards.
+    pub fn decode(&mut self, frame: &[u8]) -> io::Result<Vec<u8>> {
+        if self.codec == OutputCodec::Raw || frame.is_empty() {
+            return Ok(frame.to_vec());
+        }
+        let data = match frame[0] {
+            TAG_STORED => frame[1..].to_vec(),
+            TAG_LZ4 => {
+                let (len, block) = frame[1..]
+                    .split_first_chunk::<4>()
+                    .ok_or_else(|| invalid("truncated lz4 output frame"))?;
+                let len = u32::from_le_bytes(*len) as usize;
+                if len > MAX_FRAME_BYTES {
+                    return Err(invalid("lz4 output frame exceeds the size limit"));
+                }
+                let data = lz4_flex::block::decompress_with_dict(block, len, &self.history.0)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                if data.len() != len {
+                    return Err(invalid("lz4 output frame length mismatch"));
+                }
+                data
+            }
+            tag => return Err(invalid(&format!("unknown output frame tag {tag:#04x}"))),
+        };
+        self.history.push(&data);
+        Ok(data)
+    }
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_log(lines: usize) -> Vec<u8> {
+        (0..lines)
+            .flat_map(|i| {
+                format!(
+                    "   Compiling crate-{} v0.{}.0 (/src/crate-{})\r\n",
+                    i % 40,
+                    i % 7,
+                    i % 40
+                )
+                .into_bytes()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn lz4_round_trips_and_shrinks_repetitive_output() {
+        let mut enc = OutputEncoder::new(OutputCodec::Lz4);
+        let mut dec = OutputDecoder::new(OutputCodec::Lz4);
+        let log = build_log(4000);
+        let mut wire = 0;
+        for chunk in log.chunks(4096) {
+            let frame = enc.encode(chunk);
+            wire += frame.len();
+            assert_eq!(dec.decode(&frame).unwrap(), chunk);
+        }
+        assert!(wire * 4 < log.len(), "{wire} of {} bytes", log.len());
+    }
+
+    #[test]
+    fn dictionary_carries_across_frames() {
+        let mut enc = OutputEncoder::new(OutputCodec::Lz4);
+        let mut dec = OutputDecoder::new(OutputCodec::Lz4);
+        // Text with no internal repetition, so only the dictionary helps.
+        let mut seed = 0x2545_f491_u32;
+        let screen: Vec<u8> = (0..2000)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                b'a' + (seed >> 16) as u8 % 26
+            })
+            .collect();
+        let first = enc.encode(&screen);
+        let repeat = enc.encode(&screen);
+        assert!(
+            repeat.len() * 10 < first.len(),
+            "{} vs {}",
+            repeat.len(),
+            first.len()
+        );
+        assert_eq!(dec.decode(&first).unwrap(), screen);
+        assert_eq!(dec.decode(&repeat).unwrap(), screen);
+    }
+
+    #[test]
+    fn small_and_empty_chunks_are_not_compressed() {
+        let mut enc = OutputEncoder::new(OutputCodec::Lz4);
+        let mut dec = OutputDecoder::new(OutputCodec::Lz4);
+        assert_eq!(enc.encode(b"a"), [TAG_STORED, b'a']);
+        assert!(enc.encode(b"").is_empty());
+        assert!(dec.decode(b"").unwrap().is_empty());
+        assert_eq!(dec.decode(&[TAG_STORED, b'a']).unwrap(), b"a");
+    }
+
+    #[test]
+    fn raw_passes_frames_through() {
+        let mut enc = OutputEncoder::new(OutputCodec::Raw);
+        let mut dec = OutputDecoder::new(OutputCodec::Raw);
+        let log = build_log(100);
+        assert_eq!(enc.encode(&log), log);
+        assert_eq!(dec.decode(&log).unwrap(), log);
+    }
+
+    #[test]
+    fn corrupt_frames_are_errors() {
+        let mut dec = OutputDecoder::new(OutputCodec::Lz4);
+        assert!(dec.decode(&[0x07, 1, 2]).is_err());
+        assert!(dec.decode(&[TAG_LZ4, 1]).is_err());
+        assert!(dec.decode(&[TAG_LZ4, 0xff, 0xff, 0xff, 0xff, 0]).is_err());
+        assert!(dec.decode(&[TAG_LZ4, 10, 0, 0, 0, 0xf0]).is_err());
+    }
+
+    #[test]
+    fn history_keeps_only_the_window() {
+        let mut h = History::default();
+        h.push(&vec![1; DICTIONARY_BYTES - 1]);
+        h.push(&[2, 3]);
+        assert_eq!(h.0.len(), DICTIONARY_BYTES);
+        assert_eq!(&h.0[DICTIONARY_BYTES - 2..], [2, 3]);
+        h.push(&vec![4; DICTIONARY_BYTES + 5]);
+        assert_eq!(h.0, vec![4; DICTIONARY_BYTES]);
+    }
+
+    #[test]
+    fn choose_takes_the_first_known_codec() {
+        use OutputCodec::{Lz4, Raw};
+        assert_eq!(OutputCodec::choose(&[Lz4, Raw]), Lz4);
+        assert_eq!(OutputCodec::choose(&[Raw, Lz4]), Raw);
+        assert_eq!(OutputCodec::choose(&[]), Raw);
+        let ids = [9, Lz4.wire_id(), Raw.wire_id()];
+        let known: Vec<_> = ids
+            .into_iter()
+            .filter_map(OutputCodec::from_wire_id)
+            .collect();
+        assert_eq!(known, [Lz4, Raw]);
+        assert_eq!(OutputCodec::from_name(" LZ4 "), Some(OutputCodec::Lz4));
+        assert_eq!(OutputCodec::from_name("none"), Some(OutputCodec::Raw));
+        assert_eq!(OutputCodec::from_name("zstd"), None);
+    }
+}
@@ -0,0 +1,308 @@
+//! Handshake and credentials for remote (TCP) gateway access.
+//!
+//! Locally the gateway speaks muxio RPC over an IPC socket. Remote access
+//! runs the same RPC over TCP: the client opens a connection, sends a
+//! [`hello_frame`] carrying the pre-shared token, and waits for a one-byte
+//! verdict. After [`REMOTE_AUTH_OK`] both ends run muxio framing directly on
+//! the TCP stream, against the same endpoint, so every method works unchanged.
+//!
+//! There is no TLS: the token authenticates the client but the stream is
+//! cleartext. Use it on a trusted LAN, or tunnel it.
+
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use term_wm_config::env::{GATEWAY_NAMESPACE, REMOTE_TOKEN_ENV_VAR, active_environment};
+
+/// First bytes of every hello; anything else is not a term-wm client.
+pub const REMOTE_HELLO_MAGIC: &[u8; 8] = b"term-wm\x01";
+
+/// Magic plus the `u16` little-endian token length.
+pub const REMOTE_HELLO_HEADER_LEN: usize = REMOTE_HELLO_MAGIC.len() + 2;
+
+/// Upper bound on a token, so a bogus length never makes the gateway buffer
+/// more than this before rejecting.
+pub const MAX_TOKEN_BYTES: usize = 256;
+
+/// Verdict byte: the token matched and the stream is now an RPC connection.
+pub const REMOTE_AUTH_OK: u8 = 0x06;
+
+/// Verdict byte: rejected (wrong token, or the peer is rate limited). The
+/// gateway closes the connection right after sending it.
+pub const REMOTE_AUTH_DENIED: u8 = 0x15;
+
+/// Key file name under `$XDG_RUNTIME_DIR/term-wm/<env>/`.
+pub const REMOTE_TOKEN_FILE_NAME: &str = "remote.key";
+
+/// Random bytes in a generated token (hex encoded, so twice as many chars).
+const GENERATED_TOKEN_BYTES: usize = 32;
+
+/// A pre-shared remote access token. `Debug` never prints the secret.
+#[derive(Clone, PartialEq, Eq)]
+pub struct RemoteToken(String);
+
+impl RemoteToken {
+    /// Validate a token: surrounding whitespace is ignored (key files end
+    /// with a newline); it must then be non-empty printable ASCII of at most
+    /// [`MAX_TOKEN_BYTES`].
+    pub fn new(raw: &str) -> io::Result<Self> {
+        let token = raw.trim();
+        if token.is_empty() || token.len() > MAX_TOKEN_BYTES {
+            return Err(invalid_token(format!(
+                "must be 1..={MAX_TOKEN_BYTES} bytes"
+            )));
+        }
+        if !token.bytes().all(|b| b.is_ascii_graphic()) {
+            return Err(invalid_token("must be printable ASCII without spaces"));
+        }
+        Ok(Self(token.to_string()))
+    }
+
+    /// A fresh random token.
+    pub fn generate() -> io::Result<Self> {
+        let mut bytes = [0u8; GENERATED_TOKEN_BYTES];
+        getrandom::fill(&mut bytes).map_err(|e| io::Error::other(format!("token: {e}")))?;
+        let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+        Ok(Self(hex))
+    }
+
+    /// [`REMOTE_TOKEN_ENV_VAR`], if set.
+    pub fn from_env() -> io::Result<Option<Self>> {
+        match std::env::var(REMOTE_TOKEN_ENV_VAR) {
+            Ok(raw) => Self::new(&raw).map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Read a key file. On Unix the file must not be readable by group or
+    /// others, like an SSH private key.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(path)?.permissions().mode();
+            if mode & 0o077 != 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    format!(
+                        "key file {} is accessible by other users (mode {:o}); run `chmod 600` on it",
+                        path.display(),
+                        mode & 0o777
+                    ),
+                ));
+            }
+        }
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display())))?;
+        Self::new(&raw)
+    }
+
+    /// Read the key file at `path`, creating it (owner-only) with a generated
+    /// token if it does not exist yet.
+    pub fn load_or_create(path: &Path) -> io::Result<Self> {
+        if let Some(dir) = path.parent() {
+            create_private_dir(dir)?;
+        }
+        let token = Self::generate()?;
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        match options.open(path) {
+            Ok(mut file) => {
+                use std::io::Write;
+                writeln!(file, "{}", token.0)?;
+                Ok(token)
+            }
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => Self::load(path),
+            Err(e) => Err(io::Error::new(e.kind(), format!("{}: {e}", path.display()))),
+        }
+    }
+
+    /// The secret itself, for writing it out or handing it to a user.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Compare a presented token without an early exit, so response timing
+    /// does not reveal how much of a guess was right.
+    pub fn verify(&self, presented: &[u8]) -> bool {
+        let expected = self.0.as_bytes();
+        let mut diff = expected.len() ^ presented.len();
+        for (i, &b) in expected.iter().enumerate() {
+            diff |= usize::from(b ^ presented.get(i).copied().unwrap_or(0));
+        }
+        diff == 0
+    }
+}
+
+impl fmt::Debug for RemoteToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RemoteToken(<redacted>)")
+    }
+}
+
+fn invalid_token(reason: impl fmt::Display) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("invalid remote token: {reason}"),
+    )
+}
+
+fn create_private_dir(dir: &Path) -> io::Result<()> {
+    let mut builder = std::fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+    builder.create(dir)
+}
+
+/// The default key file: `$XDG_RUNTIME_DIR/term-wm/<env>/remote.key`. `None`
+/// when `XDG_RUNTIME_DIR` is unset (e.g. Windows); the token must then come
+/// from [`REMOTE_TOKEN_ENV_VAR`] or an explicit file.
+pub fn default_token_file() -> Option<PathBuf> {
+    let runtime_dir = std::env::var_os("XDG_RUNTIME_DIR").filter(|d| !d.is_empty())?;
+    Some(
+        PathBuf::from(runtime_dir)
+            .join(GATEWAY_NAMESPACE)
+            .join(active_environment().as_str())
+            .join(REMOTE_TOKEN_FILE_NAME),
+    )
+}
+
+/// The token a connecting client presents: an explicit key file wins, then
+/// [`REMOTE_TOKEN_ENV_VAR`], then [`default_token_file`].
+pub fn client_token(token_file: Option<&Path>) -> io::Result<RemoteToken> {
+    if let Some(path) = token_file {
+        return RemoteToken::load(path);
+    }
+    if let Some(token) = RemoteToken::from_env()? {
+        return Ok(token);
+    }
+    match default_token_file() {
+        Some(path) => RemoteToken::load(&path),
+        None => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no remote token: set {REMOTE_TOKEN_ENV_VAR} or pass --token-file"),
+        )),
+    }
+}
+
+/// The client's opening bytes: magic, token length (`u16` LE), token.
+pub fn hello_frame(token: &RemoteToken) -> Vec<u8> {
+    let token = token.as_str().as_bytes();
+    let mut frame = Vec::with_capacity(REMOTE_HELLO_HEADER_LEN + token.len());
+    frame.extend_from_slice(REMOTE_HELLO_MAGIC);
+    // `RemoteToken::new` caps the length well below u16::MAX.
+    frame.extend_from_slice(&(token.len() as u16).to_le_bytes());
+    frame.extend_from_slice(token);
+    frame
+}
+
+/// Validate a hello header and return the token length that follows it.
+pub fn parse_hello_header(header: &[u8; REMOTE_HELLO_HEADER_LEN]) -> io::Result<usize> {
+    let (magic, len) = header.split_at(REMOTE_HELLO_MAGIC.len());
+    if magic != REMOTE_HELLO_MAGIC {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "not a term-wm remote client",
+        ));
+    }
+    let len = usize::from(u16::from_le_bytes([len[0], len[1]]));
+    if len == 0 || len > MAX_TOKEN_BYTES {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "remote hello token length out of range",
+        ));
+    }
+    Ok(len)
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hello_frame_round_trips_through_the_header() {
+        let token = RemoteToken::new("s3cret-token\n").unwrap();
+        let frame = hello_frame(&token);
+        let header: [u8; REMOTE_HELLO_HEADER_LEN] =
+            frame[..REMOTE_HELLO_HEADER_LEN].try_into().unwrap();
+        let len = parse_hello_header(&header).unwrap();
+        assert_eq!(len, "s3cret-token".len());
+        assert!(token.verify(&frame[REMOTE_HELLO_HEADER_LEN..]));
+    }
+
+    #[test]
+    fn foreign_or_oversized_hello_is_rejected() {
+        let mut header = [0u8; REMOTE_HELLO_HEADER_LEN];
+        header[..8].copy_from_slice(b"GET / HT");
+        assert!(parse_hello_header(&header).is_err());
+        header[..8].copy_from_slice(REMOTE_HELLO_MAGIC);
+        header[8..].copy_from_slice(&((MAX_TOKEN_BYTES + 1) as u16).to_le_bytes());
+        assert!(parse_hello_header(&header).is_err());
+    }
+
+    #[test]
+    fn verify_rejects_prefixes_and_extensions() {
+        let token = RemoteToken::new("abcdef").unwrap();
+        assert!(token.verify(b"abcdef"));
+        assert!(!token.verify(b"abcde"));
+        assert!(!token.verify(b"abcdefg"));
+        assert!(!token.verify(b"abcdeg"));
+        assert!(!token.verify(b""));
+    }
+
+    #[test]
+    fn invalid_tokens_are_rejected() {
+        assert!(RemoteToken::new("  \n").is_err());
+        assert!(RemoteToken::new("has space").is_err());
+        assert!(RemoteToken::new(&"x".repeat(MAX_TOKEN_BYTES + 1)).is_err());
+    }
+
+    #[test]
+    fn debug_redacts_the_secret() {
+        let token = RemoteToken::new("hunter2").unwrap();
+        assert!(!format!("{token:?}").contains("hunter2"));
+    }
+
+    #[test]
+    fn load_or_create_generates_once_and_is_owner_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir
+            .path()
+            .join("term-wm")
+            .join("dev")
+            .join(REMOTE_TOKEN_FILE_NAME);
+        let first = RemoteToken::load_or_create(&path).unwrap();
+        assert_eq!(first.as_str().len(), GENERATED_TOKEN_BYTES * 2);
+        let again = RemoteToken::load_or_create(&path).unwrap();
+        assert_eq!(first, again);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn load_refuses_a_shared_key_file() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(REMOTE_TOKEN_FILE_NAME);
+        std::fs::write(&path, "token\n").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        let err = RemoteToken::load(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+    }
+}
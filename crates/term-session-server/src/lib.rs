@@ -1,5 +1,9 @@
+pub mod output_batch;
+mod remote;
 pub mod session;
 pub mod session_server;
+#[cfg(target_os = "linux")]
+mod upgrade;
 
 pub use session::Session;
 pub use session_server::run_gateway;
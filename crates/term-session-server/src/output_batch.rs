@@ -0,0 +1,162 @@
+//! Adaptive coalescing of PTY output before it is sent to subscribers.
+//!
+//! The output task wakes for every PTY read, so a build log or a `cat` of a
+//! large file turns into thousands of small stream frames. [`OutputBatcher`]
+//! sends the first chunk after a quiet period immediately — keystroke echo
+//! and a single redraw never wait — and coalesces whatever follows within
+//! the latency budget into one frame.
+
+use std::time::{Duration, Instant};
+
+/// Longest a chunk is held back while a burst is being coalesced.
+pub const BATCH_LATENCY: Duration = Duration::from_millis(4);
+
+/// A batch this large is sent without waiting for the budget to run out.
+pub const MAX_BATCH_BYTES: usize = 64 * 1024;
+
+#[derive(Debug)]
+pub struct OutputBatcher {
+    latency: Duration,
+    max_bytes: usize,
+    pending: Vec<u8>,
+    /// When `pending` is due; `None` while it is empty.
+    deadline: Option<Instant>,
+    last_flush: Option<Instant>,
+}
+
+impl Default for OutputBatcher {
+    fn default() -> Self {
+        Self::new(BATCH_LATENCY, MAX_BATCH_BYTES)
+    }
+}
+
+impl OutputBatcher {
+    /// A zero `latency` disables coalescing: every chunk is due at once.
+    pub fn new(latency: Duration, max_bytes: usize) -> Self {
+        Self {
+            latency,
+            max_bytes,
+            pending: Vec::new(),
+            deadline: None,
+            last_flush: None,
+        }
+    }
+
+    /// Queue a chunk read from the PTY at `now`.
+    pub fn push(&mut self, chunk: &[u8], now: Instant) {
+        if chunk.is_empty() {
+            return;
+        }
+        let quiet = self
+            .last_flush
+            .is_none_or(|at| now.saturating_duration_since(at) >= self.latency);
+        self.pending.extend_from_slice(chunk);
+        let due = if quiet || self.pending.len() >= self.max_bytes {
+            now
+        } else {
+            now + self.latency
+        };
+        self.deadline = Some(self.deadline.map_or(due, |d| d.min(due)));
+    }
+
+    /// When the pending batch is due, for the output task's timer.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// The pending batch, once it is due at `now`.
+    pub fn poll(&mut self, now: Instant) -> Option<Vec<u8>> {
+        if self.deadline.is_some_and(|d| d <= now) {
+            self.flush(now)
+        } else {
+            None
+        }
+    }
+
+    /// The pending batch regardless of its deadline, e.g. before the stream
+    /// ends or a new subscriber's snapshot is taken.
+    pub fn flush(&mut self, now: Instant) -> Option<Vec<u8>> {
+        self.deadline = None;
+        if self.pending.is_empty() {
+            return None;
+        }
+        self.last_flush = Some(now);
+        Some(std::mem::take(&mut self.pending))
+    }
+
+    /// Drop the pending batch (nobody is left to receive it).
+    pub fn clear(&mut self) {
+        self.pending.clear();
+        self.deadline = None;
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: Duration = Duration::from_millis(1);
+
+    #[test]
+    fn echo_after_a_quiet_period_is_sent_at_once() {
+        let mut b = OutputBatcher::default();
+        let t0 = Instant::now();
+        b.push(b"a", t0);
+        assert_eq!(b.poll(t0).unwrap(), b"a");
+        // Typing slower than the budget never waits.
+        b.push(b"b", t0 + 100 * MS);
+        assert_eq!(b.poll(t0 + 100 * MS).unwrap(), b"b");
+        assert_eq!(b.deadline(), None);
+    }
+
+    #[test]
+    fn a_burst_is_coalesced_within_the_budget() {
+        let mut b = OutputBatcher::default();
+        let t0 = Instant::now();
+        b.push(b"first", t0);
+        assert_eq!(b.poll(t0).unwrap(), b"first");
+        b.push(b"-2", t0 + MS);
+        b.push(b"-3", t0 + 2 * MS);
+        assert_eq!(b.poll(t0 + 2 * MS), None);
+        assert_eq!(b.deadline(), Some(t0 + MS + BATCH_LATENCY));
+        assert_eq!(b.poll(t0 + MS + BATCH_LATENCY).unwrap(), b"-2-3");
+    }
+
+    #[test]
+    fn a_full_batch_is_due_immediately() {
+        let mut b = OutputBatcher::new(BATCH_LATENCY, 8);
+        let t0 = Instant::now();
+        b.push(b"x", t0);
+        b.poll(t0).unwrap();
+        b.push(b"1234", t0 + MS);
+        assert_eq!(b.poll(t0 + MS), None);
+        b.push(b"5678", t0 + MS);
+        assert_eq!(b.poll(t0 + MS).unwrap(), b"12345678");
+    }
+
+    #[test]
+    fn flush_and_clear_empty_the_batch() {
+        let mut b = OutputBatcher::default();
+        let t0 = Instant::now();
+        b.push(b"x", t0);
+        b.poll(t0).unwrap();
+        b.push(b"y", t0 + MS);
+        assert_eq!(b.flush(t0 + MS).unwrap(), b"y");
+        assert_eq!(b.flush(t0 + MS), None);
+        b.push(b"z", t0 + 2 * MS);
+        b.clear();
+        assert_eq!(b.deadline(), None);
+        assert_eq!(b.poll(t0 + 100 * MS), None);
+    }
+
+    #[test]
+    fn zero_latency_never_holds_back() {
+        let mut b = OutputBatcher::new(Duration::ZERO, MAX_BATCH_BYTES);
+        let t0 = Instant::now();
+        for chunk in [b"a", b"b"] {
+            b.push(chunk, t0);
+            assert_eq!(b.poll(t0).unwrap(), chunk);
+        }
+    }
+}
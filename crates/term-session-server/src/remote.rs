@@ -0,0 +1,407 @@
+//! Remote (TCP) access to the gateway.
+//!
+//! When [`REMOTE_LISTEN_ENV_VAR`] is set the daemon also accepts TCP
+//! connections. Each one must open with a token hello (see
+//! [`term_session_muxio_service_definitions::remote`]); once it passes, muxio
+//! RPC runs directly on the TCP stream against the gateway's own endpoint and
+//! connection events, so remote clients get exactly the handlers local ones do.
+//!
+//! Failed authentication is rate limited per source address: after
+//! [`MAX_AUTH_FAILURES`] failures inside [`AUTH_FAILURE_WINDOW`] the address is
+//! refused outright until the window expires, and every failure is answered
+//! only after [`AUTH_FAILURE_DELAY`].
+
+use std::collections::HashMap;
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use muxio_core::frame::FrameDecodeError;
+use muxio_core::rpc::RpcDispatcher;
+use muxio_core::utils::IdSpace;
+use muxio_rpc_service_endpoint::{RpcServiceEndpoint, RpcServiceEndpointInterface};
+use muxio_tokio_rpc_ipc_server::{
+    RpcIpcConnectionContext, RpcIpcConnectionContextHandle, RpcIpcServerEvent,
+};
+use term_session_muxio_service_definitions::remote::{
+    REMOTE_AUTH_DENIED, REMOTE_AUTH_OK, REMOTE_HELLO_HEADER_LEN, RemoteToken, default_token_file,
+    parse_hello_header,
+};
+use term_wm_config::env::{REMOTE_LISTEN_ENV_VAR, REMOTE_TOKEN_ENV_VAR};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+
+/// Failed attempts from one address before it is locked out.
+const MAX_AUTH_FAILURES: u32 = 5;
+
+/// How long failures are remembered (and a lockout lasts).
+const AUTH_FAILURE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Addresses with failures remembered at once; beyond this the oldest are
+/// forgotten so a scan from many addresses cannot grow the map without bound.
+const MAX_TRACKED_ADDRESSES: usize = 4096;
+
+/// Pause before answering a failed hello, so guessing is slow even below the
+/// lockout threshold.
+const AUTH_FAILURE_DELAY: Duration = Duration::from_millis(250);
+
+/// How long a fresh connection may take to send its hello.
+const HELLO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Remote connection ids count down from the top of the range so they never
+/// meet the IPC server's, which count up from 1.
+static NEXT_REMOTE_CONN_ID: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+/// Read buffer per connection, matching the IPC transport.
+const READ_BUF_BYTES: usize = 64 * 1024;
+
+/// The gateway's RPC endpoint, shared by local and remote connections.
+pub(crate) type GatewayEndpoint = Arc<RpcServiceEndpoint<Arc<RpcIpcConnectionContext>>>;
+
+/// Remote listener settings resolved at daemon startup.
+pub(crate) struct RemoteListenConfig {
+    pub(crate) addr: String,
+    pub(crate) token: RemoteToken,
+}
+
+impl RemoteListenConfig {
+    /// `None` unless [`REMOTE_LISTEN_ENV_VAR`] is set. The token comes from
+    /// [`REMOTE_TOKEN_ENV_VAR`], else the default key file, which is created
+    /// on first use.
+    pub(crate) fn from_env() -> io::Result<Option<Self>> {
+        let Ok(addr) = std::env::var(REMOTE_LISTEN_ENV_VAR) else {
+            return Ok(None);
+        };
+        let token = match RemoteToken::from_env()? {
+            Some(token) => token,
+            None => {
+                let path = default_token_file().ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!(
+                            "{REMOTE_LISTEN_ENV_VAR} needs a token: set {REMOTE_TOKEN_ENV_VAR} or XDG_RUNTIME_DIR"
+                        ),
+                    )
+                })?;
+                let token = RemoteToken::load_or_create(&path)?;
+                tracing::info!(path = %path.display(), "Remote access key file");
+                token
+            }
+        };
+        Ok(Some(Self { addr, token }))
+    }
+}
+
+/// Per-address failure bookkeeping.
+#[derive(Default)]
+pub(crate) struct AuthLimiter {
+    failures: HashMap<IpAddr, (u32, Instant)>,
+}
+
+impl AuthLimiter {
+    /// Whether `ip` is locked out at `now`. Expired records are dropped.
+    pub(crate) fn is_blocked(&mut self, ip: IpAddr, now: Instant) -> bool {
+        match self.failures.get(&ip) {
+            Some(&(_, since)) if now.duration_since(since) >= AUTH_FAILURE_WINDOW => {
+                self.failures.remove(&ip);
+                false
+            }
+            Some(&(count, _)) => count >= MAX_AUTH_FAILURES,
+            None => false,
+        }
+    }
+
+    /// Count a failure. The window starts at the first failure, so a steady
+    /// trickle of guesses cannot keep an address just under the limit.
+    pub(crate) fn record_failure(&mut self, ip: IpAddr, now: Instant) {
+        if self.failures.len() >= MAX_TRACKED_ADDRESSES && !self.failures.contains_key(&ip) {
+            self.prune(now);
+        }
+        let entry = self.failures.entry(ip).or_insert((0, now));
+        if now.duration_since(entry.1) >= AUTH_FAILURE_WINDOW {
+            *entry = (0, now);
+        }
+        entry.0 += 1;
+    }
+
+    pub(crate) fn record_success(&mut self, ip: IpAddr) {
+        self.failures.remove(&ip);
+    }
+
+    /// Make room for one more address: drop expired records, then the oldest
+    /// windows if a flood of distinct addresses still fills the map.
+    fn prune(&mut self, now: Instant) {
+        self.failures
+            .retain(|_, &mut (_, since)| now.duration_since(since) < AUTH_FAILURE_WINDOW);
+        if self.failures.len() < MAX_TRACKED_ADDRESSES {
+            return;
+        }
+        let mut starts: Vec<Instant> = self.failures.values().map(|&(_, since)| since).collect();
+        let excess = self.failures.len() + 1 - MAX_TRACKED_ADDRESSES / 2;
+        let (_, &mut cutoff, _) = starts.select_nth_unstable(excess - 1);
+        self.failures.retain(|_, &mut (_, since)| since > cutoff);
+    }
+}
+
+/// Bind the remote listener. When `retry_for` is set (a replacement daemon
+/// after an upgrade) an `AddrInUse` is retried until the old daemon lets go
+/// of the port.
+pub(crate) async fn bind(addr: &str, retry_for: Option<Duration>) -> io::Result<TcpListener> {
+    let deadline = retry_for.map(|d| Instant::now() + d);
+    loop {
+        match TcpListener::bind(addr).await {
+            Err(e)
+                if e.kind() == io::ErrorKind::AddrInUse
+                    && deadline.is_some_and(|d| Instant::now() < d) =>
+            {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+            result => {
+                return result.map_err(|e| io::Error::new(e.kind(), format!("listen {addr}: {e}")));
+            }
+        }
+    }
+}
+
+/// Accept remote clients forever, serving each authenticated one from
+/// `endpoint` and reporting it on `events` like a local connection.
+pub(crate) async fn serve(
+    listener: TcpListener,
+    token: RemoteToken,
+    endpoint: GatewayEndpoint,
+    events: mpsc::UnboundedSender<RpcIpcServerEvent>,
+) {
+    let token = Arc::new(token);
+    let limiter = Arc::new(Mutex::new(AuthLimiter::default()));
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                tracing::warn!(error = %e, "Remote accept failed");
+                continue;
+            }
+        };
+        let token = Arc::clone(&token);
+        let limiter = Arc::clone(&limiter);
+        let endpoint = Arc::clone(&endpoint);
+        let events = events.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_client(stream, peer, &token, &limiter, endpoint, &events).await {
+                tracing::debug!(%peer, error = %e, "Remote client ended");
+            }
+        });
+    }
+}
+
+async fn handle_client(
+    mut stream: TcpStream,
+    peer: SocketAddr,
+    token: &RemoteToken,
+    limiter: &Mutex<AuthLimiter>,
+    endpoint: GatewayEndpoint,
+    events: &mpsc::UnboundedSender<RpcIpcServerEvent>,
+) -> io::Result<()> {
+    // The hello is read even from a locked-out address: closing with it
+    // unread would reset the connection and lose the verdict byte.
+    let authenticated = tokio::time::timeout(HELLO_TIMEOUT, read_hello(&mut stream, token))
+        .await
+        .unwrap_or(Ok(false))?;
+    let blocked = limiter
+        .lock()
+        .map(|mut l| l.is_blocked(peer.ip(), Instant::now()))
+        .unwrap_or(true);
+    if blocked || !authenticated {
+        if blocked {
+            tracing::warn!(%peer, "Remote client locked out after repeated auth failures");
+        } else {
+            if let Ok(mut l) = limiter.lock() {
+                l.record_failure(peer.ip(), Instant::now());
+            }
+            tracing::warn!(%peer, "Remote client failed authentication");
+        }
+        tokio::time::sleep(AUTH_FAILURE_DELAY).await;
+        let _ = stream.write_all(&[REMOTE_AUTH_DENIED]).await;
+        return Ok(());
+    }
+    if let Ok(mut l) = limiter.lock() {
+        l.record_success(peer.ip());
+    }
+
+    stream.write_all(&[REMOTE_AUTH_OK]).await?;
+    // Interactive traffic: small frames must not wait for Nagle.
+    stream.set_nodelay(true)?;
+    tracing::info!(%peer, "Remote client authenticated");
+    serve_connection(stream, endpoint, events).await;
+    Ok(())
+}
+
+/// Run muxio RPC on `stream` until it closes: the stream counterpart of the
+/// IPC server's per-connection loop, with the same context and events.
+async fn serve_connection<S>(
+    stream: S,
+    endpoint: GatewayEndpoint,
+    events: &mpsc::UnboundedSender<RpcIpcServerEvent>,
+) where
+    S: AsyncRead + AsyncWrite + Send + 'static,
+{
+    let (mut read_half, write_half) = tokio::io::split(stream);
+    let write_half = Arc::new(tokio::sync::Mutex::new(write_half));
+    let (write_tx, writer) =
+        muxio_rpc_service_caller::write_channel::spawn_write_loop(move |msg: Vec<u8>| {
+            let w = Arc::clone(&write_half);
+            async move { w.lock().await.write_all(&msg).await.map_err(|_| ()) }
+        });
+    let is_connected = Arc::new(AtomicBool::new(true));
+    let context = Arc::new(RpcIpcConnectionContext {
+        write_tx: write_tx.clone(),
+        conn_id: NEXT_REMOTE_CONN_ID.fetch_sub(1, Ordering::Relaxed),
+        is_connected: Arc::clone(&is_connected),
+        dispatcher: Arc::new(tokio::sync::Mutex::new(RpcDispatcher::new_with_id_space(
+            IdSpace::Server,
+        ))),
+    });
+    let conn_id = context.conn_id;
+    let _ = events.send(RpcIpcServerEvent::ClientConnected(
+        RpcIpcConnectionContextHandle(Arc::clone(&context)),
+    ));
+
+    let reader_context = Arc::clone(&context);
+    let reader = tokio::spawn(async move {
+        let mut buf = vec![0u8; READ_BUF_BYTES];
+        loop {
+            let n = match read_half.read(&mut buf).await {
+                Ok(0) | Err(_) => break,
+                Ok(n) => n,
+            };
+            let tx = write_tx.clone();
+            let on_emit = move |chunk: &[u8]| {
+                let _ = tx.send(chunk.to_vec());
+            };
+            // Decode under the dispatcher lock, run handlers without it (a
+            // handler may need the dispatcher), then respond under it again.
+            let requests = {
+                let mut dispatcher = reader_context.dispatcher.lock().await;
+                match endpoint
+                    .decode_bytes(
+                        &mut dispatcher,
+                        Arc::clone(&reader_context),
+                        &buf[..n],
+                        on_emit.clone(),
+                    )
+                    .await
+                {
+                    Ok(requests) => requests,
+                    Err(e) => {
+                        tracing::warn!(conn_id, error = ?e, "Remote client sent a bad frame");
+                        continue;
+                    }
+                }
+            };
+            let responses = endpoint
+                .run_handlers(Arc::clone(&reader_context), requests)
+                .await;
+            if !responses.is_empty() {
+                let mut dispatcher = reader_context.dispatcher.lock().await;
+                let _ = endpoint.send_responses(&mut dispatcher, responses, on_emit);
+            }
+        }
+    });
+
+    tokio::select! {
+        _ = writer => {},
+        _ = reader => {},
+    }
+    is_connected.store(false, Ordering::SeqCst);
+    context
+        .dispatcher
+        .lock()
+        .await
+        .fail_all_pending_requests(FrameDecodeError::ReadAfterCancel);
+    let _ = events.send(RpcIpcServerEvent::ClientDisconnected(conn_id));
+}
+
+/// Read one hello and check its token. A malformed hello counts as a failed
+/// attempt rather than an I/O error.
+async fn read_hello(stream: &mut TcpStream, token: &RemoteToken) -> io::Result<bool> {
+    let mut header = [0u8; REMOTE_HELLO_HEADER_LEN];
+    stream.read_exact(&mut header).await?;
+    let Ok(len) = parse_hello_header(&header) else {
+        return Ok(false);
+    };
+    let mut presented = vec![0u8; len];
+    stream.read_exact(&mut presented).await?;
+    Ok(token.verify(&presented))
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip() -> IpAddr {
+        IpAddr::from([192, 0, 2, 7])
+    }
+
+    #[test]
+    fn limiter_locks_out_after_repeated_failures() {
+        let mut limiter = AuthLimiter::default();
+        let now = Instant::now();
+        for _ in 0..MAX_AUTH_FAILURES - 1 {
+            limiter.record_failure(ip(), now);
+        }
+        assert!(!limiter.is_blocked(ip(), now));
+        limiter.record_failure(ip(), now);
+        assert!(limiter.is_blocked(ip(), now));
+        assert!(!limiter.is_blocked(IpAddr::from([192, 0, 2, 8]), now));
+    }
+
+    #[test]
+    fn lockout_expires_with_the_window() {
+        let mut limiter = AuthLimiter::default();
+        let now = Instant::now();
+        for _ in 0..MAX_AUTH_FAILURES {
+            limiter.record_failure(ip(), now);
+        }
+        assert!(limiter.is_blocked(ip(), now + AUTH_FAILURE_WINDOW / 2));
+        assert!(!limiter.is_blocked(ip(), now + AUTH_FAILURE_WINDOW));
+    }
+
+    #[test]
+    fn failure_map_stays_bounded() {
+        let mut limiter = AuthLimiter::default();
+        let now = Instant::now();
+        for i in 0..MAX_TRACKED_ADDRESSES as u32 {
+            limiter.record_failure(IpAddr::from(i.to_be_bytes()), now);
+        }
+        // Expired records make room first.
+        let later = now + AUTH_FAILURE_WINDOW;
+        limiter.record_failure(ip(), later);
+        assert_eq!(limiter.failures.len(), 1);
+
+        // A flood inside one window evicts the oldest records.
+        for i in 0..MAX_TRACKED_ADDRESSES as u32 {
+            let at = later + Duration::from_millis(u64::from(i));
+            limiter.record_failure(IpAddr::from((i + 1).to_be_bytes()), at);
+        }
+        assert!(limiter.failures.len() <= MAX_TRACKED_ADDRESSES);
+        assert!(!limiter.failures.contains_key(&ip()));
+        let newest = IpAddr::from((MAX_TRACKED_ADDRESSES as u32).to_be_bytes());
+        assert!(limiter.failures.contains_key(&newest));
+    }
+
+    #[test]
+    fn success_clears_earlier_failures() {
+        let mut limiter = AuthLimiter::default();
+        let now = Instant::now();
+        for _ in 0..MAX_AUTH_FAILURES - 1 {
+            limiter.record_failure(ip(), now);
+        }
+        limiter.record_success(ip());
+        limiter.record_failure(ip(), now);
+        assert!(!limiter.is_blocked(ip(), now));
+    }
+}
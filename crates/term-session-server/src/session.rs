@@ -34,6 +34,9 @@ fn resolve_cwd(cwd: Option<&PathWire>) -> Option<std::path::PathBuf> {
 }
 
 impl Session {
+    /// Spawn `cmd` (the default shell when `None`) in a new PTY. `env` is the
+    /// channel's own environment; it cannot override the variables the
+    /// gateway sets for itself.
     pub fn spawn(
         id: u64,
         cmd: Option<Vec<String>>,
@@ -41,6 +44,7 @@ impl Session {
         rows: u16,
         channel: Option<&ChannelName>,
         cwd: Option<&PathWire>,
+        env: &[(String, String)],
     ) -> PtyResult<Self> {
         let size = PtySize {
             rows,
@@ -60,6 +64,9 @@ impl Session {
         } else {
             default_shell_command()
         };
+        for (key, value) in env {
+            builder.env(key, value);
+        }
         if let Some(ch) = channel {
             builder.env(term_wm_config::env::CHANNEL_ENV_VAR, ch.to_string());
         }
@@ -71,6 +78,13 @@ impl Session {
             term_wm_config::env::SESSION_GATEWAY_ENV_VAR,
             &active_gateway,
         );
+        // A daemon started by a gateway upgrade inherits the handoff socket
+        // name; it is meaningless to the sessions it spawns.
+        builder.env_remove(term_wm_config::env::HANDOFF_ENV_VAR);
+        // Remote access settings belong to this daemon: a shell must never
+        // see the token, nor make a nested gateway grab the same port.
+        builder.env_remove(term_wm_config::env::REMOTE_LISTEN_ENV_VAR);
+        builder.env_remove(term_wm_config::env::REMOTE_TOKEN_ENV_VAR);
         if let Some(c) = resolved_cwd {
             builder.cwd(c);
         }
@@ -86,6 +100,37 @@ impl Session {
         })
     }
 
+    /// Rebuild a session handed over by the previous gateway daemon: `master`
+    /// is its PTY master descriptor and `screen_state` the emulator snapshot
+    /// taken after the old daemon stopped reading.
+    #[cfg(unix)]
+    pub fn adopt(
+        id: u64,
+        master: std::os::unix::io::OwnedFd,
+        child_pid: u32,
+        cols: u16,
+        rows: u16,
+        title: Option<String>,
+        screen_state: &[u8],
+    ) -> PtyResult<Self> {
+        let size = PtySize {
+            rows,
+            cols,
+            pixel_width: 0,
+            pixel_height: 0,
+        };
+        let pty = Pty::adopt(master, child_pid, size, 0, screen_state)?;
+        Ok(Self {
+            id,
+            pty,
+            title,
+            exited: false,
+            exit_code: None,
+            cols,
+            rows,
+        })
+    }
+
     pub fn read_output(&mut self) -> Vec<u8> {
         // Clear dirty flag and wake the PTY reader thread from I/O burst budget parking
         self.pty.screen();
@@ -241,8 +286,8 @@ mod tests {
             "pwd".to_string(),
             report.to_string_lossy().into_owned(),
         ];
-        let mut session =
-            Session::spawn(1, Some(cmd), TEST_COLS, TEST_ROWS, None, cwd).expect("spawn session");
+        let mut session = Session::spawn(1, Some(cmd), TEST_COLS, TEST_ROWS, None, cwd, &[])
+            .expect("spawn session");
         let bytes = read_report(&mut session, &report);
         session.pty.kill_child().ok();
         PathWire::from(bytes)
@@ -295,6 +340,10 @@ mod tests {
     /// Spawn a session running `mock envvar <NAME> <report>` and return the
     /// report bytes (`<NAME>=<value>`), mirroring `spawn_pwd_report`.
     fn spawn_envvar_report(name: &str) -> Vec<u8> {
+        spawn_envvar_report_with(name, &[])
+    }
+
+    fn spawn_envvar_report_with(name: &str, env: &[(String, String)]) -> Vec<u8> {
         let dir = tempfile::tempdir().expect("report tempdir");
         let report = dir.path().join("envvar.txt");
         let mock = term_session_mock::get_mock_bin();
@@ -304,8 +353,8 @@ mod tests {
             name.to_string(),
             report.to_string_lossy().into_owned(),
         ];
-        let mut session =
-            Session::spawn(1, Some(cmd), TEST_COLS, TEST_ROWS, None, None).expect("spawn session");
+        let mut session = Session::spawn(1, Some(cmd), TEST_COLS, TEST_ROWS, None, None, env)
+            .expect("spawn session");
         let bytes = read_report(&mut session, &report);
         session.pty.kill_child().ok();
         bytes
@@ -325,6 +374,21 @@ mod tests {
         assert!(!value.is_empty(), "gateway env var must be non-empty");
     }
 
+    #[test]
+    fn spawn_applies_channel_env() {
+        let env = [("TERM_SESSION_TEST_EDITOR".to_string(), "vim".to_string())];
+        let report = spawn_envvar_report_with("TERM_SESSION_TEST_EDITOR", &env);
+        assert_eq!(report, b"TERM_SESSION_TEST_EDITOR=vim");
+    }
+
+    #[test]
+    fn channel_env_cannot_override_gateway_vars() {
+        let var = term_wm_config::env::SESSION_GATEWAY_ENV_VAR;
+        let env = [(var.to_string(), "spoofed".to_string())];
+        let report = spawn_envvar_report_with(var, &env);
+        assert_ne!(report, format!("{var}=spoofed").into_bytes());
+    }
+
     /// With `channel = None` the daemon sets no channel env var; this both
     /// confirms the mock reports a genuinely-unset var as `NAME=` (no
     /// fabricated `1`) and that the `TERM_SESSION_CHANNEL` injection stays
@@ -1,4 +1,4 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 
@@ -10,17 +10,29 @@ use muxio_tokio_rpc_ipc_server::{RpcIpcConnectionContextHandle, RpcIpcServer, Rp
 use portable_pty::PtySize;
 use tokio::sync::{Mutex, Notify, RwLock, mpsc, oneshot};
 
+#[cfg(not(target_os = "linux"))]
+use term_session_muxio_service_definitions::RPC_ERROR_UPGRADE_UNSUPPORTED;
 use term_session_muxio_service_definitions::{
-    Attach, ChannelInfo, ChannelName, ClientInfo, CloseSession, KillChannel, KillClient,
-    ListChannels, ListChannelsResponse, OnAttributedInput, OnAttributedInputRequest, OnPtyResized,
-    OnWorkspaceRebind, OnWorkspaceRebindRequest, RPC_ERROR_LIVE_PARTICIPANTS,
-    RPC_ERROR_LIVE_SESSIONS, RPC_ERROR_SHUTTING_DOWN, RPC_ERROR_UNATTACHED, RebindWorkspace,
-    ResizePty, STREAM_INPUT_METHOD_ID, SUBSCRIBE_OUTPUT_METHOD_ID, SendAttributedInput,
-    SessionInfo, ShutdownGateway, Spawn, SpawnRequest, SpawnResponse, SubscribeInternalInput,
-    WriteInput,
+    Attach, ChannelInfo, ChannelName, ClientInfo, CloneChannel, CloseSession, ControlCommand,
+    ControlEvent, KillChannel, KillClient, ListChannels, ListChannelsResponse, NegotiateOutput,
+    OnAttributedInput, OnAttributedInputRequest, OnChannelRenamed, OnChannelRenamedRequest,
+    OnControlCommand, OnControlEvents, OnPtyResized, OnWorkspaceRebind, OnWorkspaceRebindRequest,
+    PathWire, PublishControlEvents, RPC_ERROR_CHANNEL_EXISTS, RPC_ERROR_LIVE_PARTICIPANTS,
+    RPC_ERROR_LIVE_SESSIONS, RPC_ERROR_NO_CONTROL_TARGET, RPC_ERROR_SHUTTING_DOWN,
+    RPC_ERROR_UNATTACHED, RebindWorkspace, RenameChannel, ResizePty, RestartPolicy,
+    STREAM_INPUT_METHOD_ID, SUBSCRIBE_OUTPUT_METHOD_ID, SendAttributedInput, SendControlCommand,
+    SessionInfo, SetChannelEnv, SetRestartPolicy, ShutdownGateway, Spawn, SpawnRequest,
+    SpawnResponse, SubscribeControl, SubscribeInternalInput, UpgradeGateway, WriteInput,
 };
-use term_wm_pty_engine::PtyStatus;
+#[cfg(target_os = "linux")]
+use term_session_muxio_service_definitions::{
+    HandoffChannel, HandoffSession, OnGatewayUpgrade, UpgradeGatewayResponse,
+};
+use term_session_muxio_service_definitions::{OutputCodec, OutputEncoder};
+use term_wm_events::{Event, HostColorKind, HostDisplay};
+use term_wm_pty_engine::{PtyResult, PtyStatus, ReportedColors};
 
+use crate::output_batch::OutputBatcher;
 use crate::session::Session;
 
 /// Session id per channel (each channel hosts a single PTY at a time).
@@ -28,6 +40,19 @@ const SESSION_ID: u64 = 1;
 /// Bounded input channel capacity — memory safety against extreme input bursts.
 const INPUT_CHANNEL_CAPACITY: usize = 128;
 
+/// PTY size for a session spawned by `CloneChannel` when the source has no
+/// live session to copy it from. The first attaching client resizes it.
+const CLONE_FALLBACK_SIZE: (u16, u16) = (80, 24);
+
+/// Delay before the first automatic restart; doubles with each consecutive
+/// quick exit up to `RESTART_BACKOFF_MAX`.
+const RESTART_BACKOFF_BASE: std::time::Duration = std::time::Duration::from_millis(500);
+const RESTART_BACKOFF_MAX: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// A session that ran at least this long before exiting resets the restart
+/// backoff (and the `OnFailure` retry budget).
+const RESTART_STABLE_AFTER: std::time::Duration = std::time::Duration::from_secs(30);
+
 /// Grace period to let the transport flush end-of-stream frames after the
 /// session exits, before the gateway process terminates.
 const SESSION_EXIT_FLUSH_GRACE: std::time::Duration = std::time::Duration::from_millis(100);
@@ -72,6 +97,18 @@ const SIGKILL: i32 = 9;
 /// is torn down.
 const SHUTDOWN_FLUSH_GRACE_MS: u64 = 50;
 
+/// How long an upgrade waits for each session's reader thread to stop before
+/// giving up and rolling the handoff back.
+#[cfg(target_os = "linux")]
+const HANDOFF_FREEZE_GRACE: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// How long a replacement daemon keeps retrying the gateway bind while its
+/// predecessor drains and exits, and how often it retries.
+#[cfg(target_os = "linux")]
+const HANDOFF_BIND_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+#[cfg(target_os = "linux")]
+const HANDOFF_BIND_RETRY: std::time::Duration = std::time::Duration::from_millis(20);
+
 /// A connection's bind state. Identity is server-assigned (`conn_id`); a
 /// connection must `Attach` before it may spawn/resize/write.
 #[derive(Clone)]
@@ -94,6 +131,8 @@ struct ConnEntry {
     version: String,
     /// Remote peer IP for SSH attaches; `None` for local (reported at Attach).
     ssh_ip: Option<String>,
+    /// Encoding of this connection's output subscriptions (`NegotiateOutput`).
+    output_codec: OutputCodec,
 }
 
 #[derive(Clone)]
@@ -112,6 +151,20 @@ struct ClientEntry {
 struct SubscriberEntry {
     conn_id: usize,
     respond: StreamResponder,
+    encoder: OutputEncoder,
+}
+
+impl SubscriberEntry {
+    fn send(&mut self, data: &[u8]) {
+        self.respond.respond(self.encoder.encode(data), false);
+    }
+}
+
+/// A control-mode client. Batches go through a per-subscriber queue drained
+/// by one task, so a slow client never reorders or blocks the others.
+struct ControlSubscriber {
+    conn_id: usize,
+    tx: mpsc::UnboundedSender<Vec<ControlEvent>>,
 }
 
 /// Per-channel state. One gateway process hosts many channels; each channel
@@ -126,9 +179,14 @@ pub enum InputMode {
 }
 
 struct ChannelState {
+    /// Current name; changes on `RenameChannel`, so background tasks read it
+    /// from here instead of capturing it.
+    name: ChannelName,
     session: Option<Session>,
     clients: HashMap<usize, ClientEntry>,
     subscribers: Vec<SubscriberEntry>,
+    /// PTY output held back to coalesce a burst into fewer stream frames.
+    output_batch: OutputBatcher,
     notify: Arc<Notify>,
     /// Unix seconds when the channel was first created on the gateway.
     created_at_unix: u64,
@@ -143,6 +201,10 @@ struct ChannelState {
     created_seq: u64,
     /// Command template used to respawn the session after it exits.
     cmd: Vec<String>,
+    /// Working directory of the last spawn, reused by `CloneChannel`.
+    cwd: Option<PathWire>,
+    /// Extra environment applied to every spawn (`SetChannelEnv`).
+    env: BTreeMap<String, String>,
     input_tx: mpsc::Sender<Vec<u8>>,
     /// True between a SIGTERM request and the process group's actual exit (or
     /// the SIGKILL escalation). Cleared when the session is observed exited.
@@ -158,6 +220,34 @@ struct ChannelState {
     internal_wm_caller: Option<RpcIpcConnectionContextHandle>,
     /// The `conn_id` of the subscribing WM connection (for disconnect cleanup).
     internal_wm_conn_id: Option<usize>,
+    /// Set once a gateway upgrade committed: the session now belongs to the
+    /// replacement daemon, so the output task stops without finalizing
+    /// subscribers (an EOF would read as a session exit to clients).
+    handed_off: bool,
+    /// Control-mode clients receiving the internal WM's events.
+    control_subscribers: Vec<ControlSubscriber>,
+    restart_policy: RestartPolicy,
+    /// Automatic restarts so far; reported by `ListChannels`, never reset.
+    restart_count: u32,
+    /// Consecutive exits without a stable run (see `RESTART_STABLE_AFTER`).
+    restart_streak: u32,
+    last_exit_code: Option<i32>,
+    last_exit_unix: Option<u64>,
+    /// When the scheduled automatic restart is due. While set, the channel
+    /// is neither reaped nor are its subscribers finalized.
+    restart_at: Option<tokio::time::Instant>,
+    /// Start of the current session, to tell a crash loop from a long run.
+    session_started: Option<tokio::time::Instant>,
+    /// Size of the last session, reused by automatic restarts.
+    last_size: (u16, u16),
+    /// Default colors of the host terminal, from the OSC 10/11 replies an
+    /// attached client forwards as attributed input. The session's children
+    /// get these for their own OSC 10/11 queries; unknown ones go unanswered.
+    host_fg: Option<(u8, u8, u8)>,
+    host_bg: Option<(u8, u8, u8)>,
+    /// What the attached client probed on its host terminal, handed to an
+    /// inner WM that cannot probe through this channel's PTY.
+    host_display: Option<HostDisplay>,
 }
 
 /// Gateway coordination. Two tiers:
@@ -181,6 +271,9 @@ struct ServerState {
     conn_to_channel: std::sync::Mutex<HashMap<usize, String>>,
     /// Channel names with active internal WMs. Checked by StreamInput.
     internal_channels: std::sync::Mutex<HashSet<String>>,
+    /// Routing: control-mode conn_id → channel. Control clients never
+    /// `Attach`, so `evict_conn` needs this to find their subscription.
+    control_conns: std::sync::Mutex<HashMap<usize, ChannelName>>,
 }
 
 type SharedState = Arc<ServerState>;
@@ -204,26 +297,44 @@ fn now_unix() -> u64 {
 
 impl ChannelState {
     fn new(
+        name: ChannelName,
         cmd: Vec<String>,
         input_tx: mpsc::Sender<Vec<u8>>,
         notify: Arc<Notify>,
         created_seq: u64,
     ) -> Self {
         Self {
+            name,
             session: None,
             clients: HashMap::new(),
             subscribers: Vec::new(),
+            output_batch: OutputBatcher::default(),
             notify,
             created_at_unix: now_unix(),
             output_cache: Vec::new(),
             created_seq,
             cmd,
+            cwd: None,
+            env: BTreeMap::new(),
             input_tx,
             kill_pending: false,
             is_reaped: false,
             input_mode: InputMode::RawPty,
             internal_wm_caller: None,
             internal_wm_conn_id: None,
+            handed_off: false,
+            control_subscribers: Vec::new(),
+            restart_policy: RestartPolicy::Never,
+            restart_count: 0,
+            restart_streak: 0,
+            last_exit_code: None,
+            last_exit_unix: None,
+            restart_at: None,
+            session_started: None,
+            last_size: CLONE_FALLBACK_SIZE,
+            host_fg: None,
+            host_bg: None,
+            host_display: None,
         }
     }
 
@@ -239,12 +350,57 @@ impl ChannelState {
                 n.notify_one();
             }
         })));
+        self.last_size = (session.cols, session.rows);
+        session.pty.set_reported_colors(ReportedColors::Host {
+            fg: self.host_fg,
+            bg: self.host_bg,
+        });
         self.session = Some(session);
+        self.session_started = Some(tokio::time::Instant::now());
+        self.restart_at = None;
         // Prime notify to process initial startup output generated before the
         // callback was registered.
         self.notify.notify_one();
     }
 
+    /// Record a host default color and report it to the session's children.
+    fn set_host_color(&mut self, kind: HostColorKind, rgb: (u8, u8, u8)) {
+        match kind {
+            HostColorKind::Foreground => self.host_fg = Some(rgb),
+            HostColorKind::Background => self.host_bg = Some(rgb),
+        }
+        if let Some(session) = self.session.as_mut() {
+            session.pty.set_reported_colors(ReportedColors::Host {
+                fg: self.host_fg,
+                bg: self.host_bg,
+            });
+        }
+    }
+
+    /// Record host-terminal reports carried as attributed input.
+    fn observe_host_event(&mut self, event: &Event) {
+        match *event {
+            Event::HostColor(kind, rgb) => self.set_host_color(kind, rgb),
+            Event::HostDisplay(display) => self.host_display = Some(display),
+            _ => {}
+        }
+    }
+
+    /// The known host reports as the events a subscribing inner WM would have
+    /// received, so it can render for the host and pick its theme.
+    fn host_events(&self) -> Vec<Event> {
+        [
+            self.host_display.map(Event::HostDisplay),
+            self.host_fg
+                .map(|rgb| Event::HostColor(HostColorKind::Foreground, rgb)),
+            self.host_bg
+                .map(|rgb| Event::HostColor(HostColorKind::Background, rgb)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
     /// Append bytes to the retained-output cache with tail retention: at most
     /// `MAX_RETAINED_OUTPUT_BYTES` of the session's final output is ever kept,
     /// so an unsubscribed high-volume session cannot grow the cache unboundedly.
@@ -262,6 +418,60 @@ impl ChannelState {
         }
     }
 
+    /// Record the exit of the current session (already taken out of
+    /// `session`) and schedule an automatic restart if the policy asks for
+    /// one. Call before clearing `kill_pending`: a killed session stays dead.
+    fn on_session_exit(&mut self, exit_code: Option<i32>) {
+        self.last_exit_code = exit_code;
+        self.last_exit_unix = Some(now_unix());
+        if self
+            .session_started
+            .take()
+            .is_some_and(|t| t.elapsed() >= RESTART_STABLE_AFTER)
+        {
+            self.restart_streak = 0;
+        }
+        if self.kill_pending {
+            return;
+        }
+        if let Some(delay) = restart_delay(self.restart_policy, exit_code, self.restart_streak) {
+            self.restart_streak += 1;
+            self.restart_at = Some(tokio::time::Instant::now() + delay);
+        }
+    }
+
+    /// Run the scheduled automatic restart with the channel's command,
+    /// working directory and environment. Unlike a respawn on attach, the
+    /// retained output of the previous sessions is kept. A failed spawn
+    /// counts as a failed run: the next backoff step is scheduled and a
+    /// retry is consumed.
+    fn restart_session(&mut self) -> PtyResult<()> {
+        self.restart_at = None;
+        let (cols, rows) = self.last_size;
+        let spawned = Session::spawn(
+            SESSION_ID,
+            (!self.cmd.is_empty()).then(|| self.cmd.clone()),
+            cols,
+            rows,
+            Some(&self.name),
+            self.cwd.as_ref(),
+            &self.env_pairs(),
+        );
+        let session = match spawned {
+            Ok(session) => session,
+            Err(e) => {
+                self.on_session_exit(None);
+                return Err(e);
+            }
+        };
+        let retained = std::mem::take(&mut self.output_cache);
+        self.set_session(session);
+        self.output_cache = retained;
+        self.restart_count += 1;
+        self.recalculate_pty_size();
+        Ok(())
+    }
+
     /// Signal the session's process group (non-blocking) and arm the kill
     /// escalation flag. The caller is responsible for spawning the detached
     /// escalation task (see `spawn_kill_escalation`). Mechanism only — no
@@ -274,19 +484,33 @@ impl ChannelState {
             #[cfg(not(unix))]
             let _ = session.pty.kill_child();
         }
+        self.restart_at = None;
         self.kill_pending = true;
         self.notify.notify_one();
     }
 
+    /// Send a chunk of PTY output to every subscriber.
+    fn broadcast_output(&mut self, data: &[u8]) {
+        for sub in &mut self.subscribers {
+            sub.send(data);
+        }
+    }
+
+    /// Send whatever the batcher is holding, due or not.
+    fn flush_output_batch(&mut self) {
+        if let Some(data) = self.output_batch.flush(std::time::Instant::now()) {
+            self.broadcast_output(&data);
+        }
+    }
+
     /// Flush remaining PTY buffers and stream completion markers to all active
     /// subscribers, then drop them. Used by kill paths and on session exit.
     fn finalize_subscribers(&mut self) {
+        self.flush_output_batch();
         if let Some(session) = self.session.as_mut() {
             let raw = session.read_output();
             if !raw.is_empty() {
-                for sub in &self.subscribers {
-                    sub.respond.respond(raw.clone(), false);
-                }
+                self.broadcast_output(&raw);
             }
         }
         for sub in &self.subscribers {
@@ -350,6 +574,14 @@ impl ChannelState {
         }
     }
 
+    /// The channel environment as passed to `Session::spawn`.
+    fn env_pairs(&self) -> Vec<(String, String)> {
+        self.env
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    }
+
     fn to_info(&self, name: &ChannelName) -> ChannelInfo {
         let session = self.session.as_ref().map(|s| SessionInfo {
             id: s.id,
@@ -358,6 +590,9 @@ impl ChannelState {
             exited: s.exited,
             exit_code: s.exit_code,
             title: s.title.clone().unwrap_or_default(),
+            restart_count: self.restart_count,
+            last_exit_code: self.last_exit_code,
+            last_exit_unix: self.last_exit_unix,
         });
         // Sort by `conn_id`, which muxio assigns monotonically at connection
         // accept — ascending order = connection order, newest client last.
@@ -382,6 +617,9 @@ impl ChannelState {
             created_at_unix: self.created_at_unix,
             session,
             clients,
+            env: self.env_pairs(),
+            restart_policy: self.restart_policy,
+            restart_pending: self.restart_at.is_some(),
         }
     }
 }
@@ -483,12 +721,42 @@ async fn get_or_create_channel(
     let (input_tx, input_rx) = mpsc::channel::<Vec<u8>>(INPUT_CHANNEL_CAPACITY);
     let notify = Arc::new(Notify::new());
     let created_seq = state.next_channel_seq.fetch_add(1, Ordering::Relaxed);
-    let channel = Arc::new(Mutex::new(ChannelState::new(
-        Vec::new(),
-        input_tx,
-        notify,
-        created_seq,
-    )));
+    let channel = ChannelState::new(name.clone(), Vec::new(), input_tx, notify, created_seq);
+    let channel = spawn_channel_tasks(state, channel, input_rx).await;
+    channels.insert(name.clone(), Arc::clone(&channel));
+    channel
+}
+
+/// Remove `ch` from `ServerState::channels` if it is still registered under
+/// `name` and has neither a session nor clients. Used to roll back a channel
+/// created for a spawn that then failed.
+async fn discard_empty_channel(
+    state: &SharedState,
+    name: &ChannelName,
+    ch: &Arc<Mutex<ChannelState>>,
+) {
+    let mut channels = state.channels.write().await;
+    if let Some(arc) = channels.get(name)
+        && Arc::ptr_eq(arc, ch)
+    {
+        let mut locked = arc.lock().await;
+        if locked.session.is_none() && locked.clients.is_empty() {
+            locked.is_reaped = true;
+            drop(locked);
+            channels.remove(name);
+        }
+    }
+}
+
+/// Wrap a new channel and start its input-forwarding and output-polling
+/// tasks. `input_rx` must pair with `channel.input_tx`. The caller inserts
+/// the returned handle into `ServerState::channels`.
+async fn spawn_channel_tasks(
+    state: &SharedState,
+    channel: ChannelState,
+    input_rx: mpsc::Receiver<Vec<u8>>,
+) -> Arc<Mutex<ChannelState>> {
+    let channel = Arc::new(Mutex::new(channel));
     let ch = Arc::clone(&channel);
     tokio::spawn(async move {
         let mut input_rx = input_rx;
@@ -522,18 +790,40 @@ async fn get_or_create_channel(
             let locked = ch.lock().await;
             locked.notify.clone()
         };
-        let name_for_task = name.clone();
         tokio::spawn(async move {
+            // When the pending output batch is due (read under the guard).
+            let mut batch_deadline: Option<std::time::Instant> = None;
             loop {
+                let batch_due = batch_deadline
+                    .map_or_else(tokio::time::Instant::now, tokio::time::Instant::from_std);
                 tokio::select! {
                     _ = notify.notified() => {}
                     _ = tokio::time::sleep(SESSION_EXIT_POLL_INTERVAL) => {}
+                    _ = tokio::time::sleep_until(batch_due), if batch_deadline.is_some() => {}
                 }
                 let mut guard = ch.lock().await;
-                if guard.is_reaped {
+                if guard.is_reaped || guard.handed_off {
                     break;
                 }
+                let name_for_task = guard.name.clone();
+                if guard.session.is_none()
+                    && guard
+                        .restart_at
+                        .is_some_and(|at| at <= tokio::time::Instant::now())
+                {
+                    match guard.restart_session() {
+                        Ok(()) => tracing::info!(
+                            channel = %name_for_task,
+                            restarts = guard.restart_count,
+                            "Restarted session"
+                        ),
+                        Err(e) => {
+                            tracing::warn!(channel = %name_for_task, error = %e, "Session restart failed");
+                        }
+                    }
+                }
                 if guard.subscribers.is_empty() {
+                    guard.output_batch.clear();
                     if let Some(session) = guard.session.as_mut() {
                         session.sync_screen();
                         if session.check_exited() {
@@ -543,20 +833,27 @@ async fn get_or_create_channel(
                             // attaches after teardown still receives it, instead
                             // of dropping the Pty's pending buffer wholesale.
                             let final_out = session.read_final_output(READER_DRAIN_GRACE);
+                            let code = session.exit_code;
                             guard.retain_final_output(&final_out);
                             guard.session = None;
+                            guard.on_session_exit(code);
                             guard.kill_pending = false;
                         }
                     }
                 } else {
                     let (raw, exited, code) = {
                         let Some(session) = guard.session.as_mut() else {
-                            // No live session: finalize any lingering subscribers.
-                            for sub in &guard.subscribers {
-                                sub.respond.respond(Vec::new(), true);
+                            // No live session: finalize any lingering
+                            // subscribers, unless they wait for a restart.
+                            if guard.restart_at.is_none() {
+                                guard.flush_output_batch();
+                                for sub in &guard.subscribers {
+                                    sub.respond.respond(Vec::new(), true);
+                                }
+                                guard.subscribers.clear();
+                                guard.notify.notify_one();
                             }
-                            guard.subscribers.clear();
-                            guard.notify.notify_one();
+                            batch_deadline = guard.output_batch.deadline();
                             continue;
                         };
                         let raw = session.read_output();
@@ -564,32 +861,47 @@ async fn get_or_create_channel(
                         let code = session.exit_code;
                         (raw, exited, code)
                     };
-                    if !raw.is_empty() {
-                        for sub in &guard.subscribers {
-                            sub.respond.respond(raw.clone(), false);
-                        }
+                    let now = std::time::Instant::now();
+                    guard.output_batch.push(&raw, now);
+                    if let Some(data) = guard.output_batch.poll(now) {
+                        guard.broadcast_output(&data);
                     }
                     if exited {
+                        guard.flush_output_batch();
                         tracing::info!(channel = %name_for_task, "Session exited with code {:?}", code);
-                        for sub in &guard.subscribers {
-                            sub.respond.respond(Vec::new(), true);
-                        }
-                        guard.subscribers.clear();
                         guard.session = None;
+                        guard.on_session_exit(code);
                         guard.kill_pending = false;
+                        // Subscribers stay attached across an automatic restart.
+                        if guard.restart_at.is_none() {
+                            for sub in &guard.subscribers {
+                                sub.respond.respond(Vec::new(), true);
+                            }
+                            guard.subscribers.clear();
+                        }
                         guard.notify.notify_one();
                     }
                 }
-                let should_reap = guard.session.is_none() && guard.clients.is_empty();
+                let should_reap = guard.session.is_none()
+                    && guard.clients.is_empty()
+                    && guard.restart_at.is_none();
+                batch_deadline = guard.output_batch.deadline();
                 drop(guard);
 
                 if should_reap {
                     // GC: drop the channel guard before requesting `channels.write`
                     // (strict ordering, no AB-BA), then re-verify under the write lock.
+                    // The name may have moved to another channel by a rename
+                    // in between; only ever reap this task's own channel.
                     let mut channels = st.channels.write().await;
-                    if let Some(arc) = channels.get(&name_for_task) {
+                    if let Some(arc) = channels.get(&name_for_task)
+                        && Arc::ptr_eq(arc, &ch)
+                    {
                         let mut locked = arc.lock().await;
-                        if locked.session.is_none() && locked.clients.is_empty() {
+                        if locked.session.is_none()
+                            && locked.clients.is_empty()
+                            && locked.restart_at.is_none()
+                        {
                             locked.is_reaped = true;
                             drop(locked);
                             channels.remove(&name_for_task);
@@ -605,7 +917,6 @@ async fn get_or_create_channel(
         });
     }
 
-    channels.insert(name.clone(), Arc::clone(&channel));
     channel
 }
 
@@ -622,6 +933,19 @@ fn purge_input_forwarder(state: &ServerState, conn_id: usize) {
 /// channel's client/subscriber maps (authoritative teardown on disconnect).
 async fn evict_conn(state: &ServerState, conn_id: usize) {
     purge_input_forwarder(state, conn_id);
+    let control_channel = state
+        .control_conns
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(&conn_id);
+    if let Some(channel) = control_channel
+        && let Some(ch) = resolve_channel(state, &channel).await
+    {
+        ch.lock()
+            .await
+            .control_subscribers
+            .retain(|s| s.conn_id != conn_id);
+    }
     let channel = {
         let mut conns = state.conns.write().await;
         let entry = conns.remove(&conn_id);
@@ -682,6 +1006,100 @@ async fn evict_conn(state: &ServerState, conn_id: usize) {
     }
 }
 
+/// Move channel `from` to `to` together with every routing entry that names
+/// it, so attached clients, the internal WM and control subscribers carry on
+/// without reconnecting. Returns the internal WM's handle, if any, so the
+/// caller can tell it the new name.
+async fn rename_channel(
+    state: &ServerState,
+    from: &ChannelName,
+    to: &ChannelName,
+) -> Result<Option<RpcIpcConnectionContextHandle>, Box<dyn std::error::Error + Send + Sync>> {
+    // Same order as Attach (conns → channels → channel), and every lock is
+    // held until all tables agree, so no request sees a half-renamed channel.
+    let mut conns = state.conns.write().await;
+    let mut channels = state.channels.write().await;
+    if channels.contains_key(to) {
+        return Err(rpc_err(RPC_ERROR_CHANNEL_EXISTS));
+    }
+    let ch = channels
+        .remove(from)
+        .ok_or_else(|| rpc_err("channel not found"))?;
+    let wm_caller = {
+        let mut guard = ch.lock().await;
+        guard.name = to.clone();
+        guard.internal_wm_caller.clone()
+    };
+    channels.insert(to.clone(), ch);
+    for entry in conns.values_mut() {
+        if let ConnState::Attached(name) = &mut entry.state
+            && name == from
+        {
+            *name = to.clone();
+        }
+    }
+    let (from_str, to_str) = (from.to_string(), to.to_string());
+    for name in state
+        .conn_to_channel
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .values_mut()
+    {
+        if *name == from_str {
+            name.clone_from(&to_str);
+        }
+    }
+    {
+        let mut internal = state
+            .internal_channels
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if internal.remove(&from_str) {
+            internal.insert(to_str);
+        }
+    }
+    for name in state
+        .control_conns
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .values_mut()
+    {
+        if name == from {
+            *name = to.clone();
+        }
+    }
+    Ok(wm_caller)
+}
+
+/// Delay before restarting a session that exited with `exit_code` after
+/// `streak` consecutive quick exits, or `None` if `policy` says to stay down.
+fn restart_delay(
+    policy: RestartPolicy,
+    exit_code: Option<i32>,
+    streak: u32,
+) -> Option<std::time::Duration> {
+    match policy {
+        RestartPolicy::Never => return None,
+        RestartPolicy::OnFailure { max_retries } => {
+            if exit_code == Some(0) || streak >= max_retries {
+                return None;
+            }
+        }
+        RestartPolicy::Always => {}
+    }
+    let factor = 1u32 << streak.min(16);
+    Some(
+        RESTART_BACKOFF_BASE
+            .saturating_mul(factor)
+            .min(RESTART_BACKOFF_MAX),
+    )
+}
+
+/// Check a `SetChannelEnv` variable name: non-empty, no `=` and no NUL.
+fn valid_env_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains(['=', '\0'])
+}
+
 /// Spawn a detached escalation task for a kill-requested session, returning
 /// its `JoinHandle` so the caller (e.g. shutdown teardown) can await it.
 ///
@@ -723,12 +1141,266 @@ async fn spawn_kill_escalation(
     })
 }
 
+/// Hand every channel to a freshly spawned replacement daemon (see
+/// `crate::upgrade`). On success the sessions belong to the replacement and
+/// this daemon only has to exit; on any failure the sessions resume here, the
+/// replacement is killed, and the gateway is unsealed.
+#[cfg(target_os = "linux")]
+async fn hand_off_gateway(
+    state: &SharedState,
+    socket_name: &str,
+    binary: Option<std::path::PathBuf>,
+) -> Result<UpgradeGatewayResponse, Box<dyn std::error::Error + Send + Sync>> {
+    // Seal first: no attach, spawn, or kill may race the snapshot below.
+    if state.is_shutting_down.swap(true, Ordering::SeqCst) {
+        return Err(rpc_err(RPC_ERROR_SHUTTING_DOWN));
+    }
+    let launched =
+        tokio::task::spawn_blocking(move || crate::upgrade::launch_replacement(binary)).await;
+    let (replacement, sender) = match launched {
+        Ok(Ok(pair)) => pair,
+        Ok(Err(e)) => {
+            state.is_shutting_down.store(false, Ordering::SeqCst);
+            return Err(boxed_io(e));
+        }
+        Err(e) => {
+            state.is_shutting_down.store(false, Ordering::SeqCst);
+            return Err(rpc_err(&format!("gateway handoff: {e}")));
+        }
+    };
+
+    let channels: Vec<(ChannelName, Arc<Mutex<ChannelState>>)> = {
+        let chans = state.channels.read().await;
+        chans.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+    };
+    let mut wire = Vec::with_capacity(channels.len());
+    let mut handed = Vec::with_capacity(channels.len());
+    let mut failure = None;
+    for (name, ch) in channels {
+        let mut guard = ch.lock().await;
+        if guard.is_reaped {
+            continue;
+        }
+        let mut session_frame = None;
+        let mut master = None;
+        if let Some(session) = guard.session.as_mut().filter(|s| !s.exited) {
+            // Stop reading before the snapshot: whatever the child writes
+            // from here on stays in the kernel buffer for the replacement.
+            handed.push(Arc::clone(&ch));
+            if !session.pty.freeze(HANDOFF_FREEZE_GRACE) {
+                failure = Some(format!("gateway handoff: '{name}' reader did not stop"));
+                break;
+            }
+            session.sync_screen();
+            let (fd, child_pid) = (session.pty.try_clone_master_fd(), session.pty.child_pid());
+            let (Ok(fd), Some(child_pid)) = (fd, child_pid) else {
+                failure = Some(format!("gateway handoff: '{name}' has no transferable PTY"));
+                break;
+            };
+            master = Some(fd);
+            session_frame = Some(HandoffSession {
+                id: session.id,
+                child_pid,
+                cols: session.cols,
+                rows: session.rows,
+                title: session.title.clone(),
+                screen_state: session.generate_snapshot(),
+            });
+        } else {
+            handed.push(Arc::clone(&ch));
+        }
+        wire.push((
+            HandoffChannel {
+                name: name.to_string(),
+                cmd: guard.cmd.clone(),
+                cwd: guard.cwd.clone(),
+                env: guard.env_pairs(),
+                created_at_unix: guard.created_at_unix,
+                created_seq: guard.created_seq,
+                output_cache: guard.output_cache.clone(),
+                restart_policy: guard.restart_policy,
+                restart_count: guard.restart_count,
+                restart_streak: guard.restart_streak,
+                last_exit_code: guard.last_exit_code,
+                last_exit_unix: guard.last_exit_unix,
+                restart_pending: guard.restart_at.is_some(),
+                session: session_frame,
+            },
+            master,
+        ));
+    }
+
+    let count = wire.len() as u32;
+    let sent = match failure {
+        Some(message) => Err(message),
+        None => {
+            let gateway = socket_name.to_string();
+            let next_seq = state.next_channel_seq.load(Ordering::Relaxed);
+            tokio::task::spawn_blocking(move || {
+                for (channel, master) in wire {
+                    sender.send_channel(channel, master.as_ref())?;
+                }
+                sender.finish(gateway, next_seq)
+            })
+            .await
+            .map_err(|e| format!("gateway handoff: {e}"))
+            .and_then(|r| r.map_err(|e| e.to_string()))
+        }
+    };
+    if let Err(message) = sent {
+        tracing::warn!(error = %message, "Gateway upgrade failed; resuming sessions");
+        for ch in handed {
+            let mut guard = ch.lock().await;
+            if let Some(session) = guard.session.as_mut()
+                && let Err(e) = session.pty.thaw()
+            {
+                tracing::error!(error = %e, "Failed to resume PTY reader after handoff");
+            }
+            guard.notify.notify_one();
+        }
+        let _ = tokio::task::spawn_blocking(move || replacement.abort()).await;
+        state.is_shutting_down.store(false, Ordering::SeqCst);
+        return Err(rpc_err(&message));
+    }
+
+    // Committed: the replacement owns every session. Disarm the writers so
+    // dropping them on exit cannot send EOF to shells that live on.
+    for ch in handed {
+        let mut guard = ch.lock().await;
+        guard.flush_output_batch();
+        guard.handed_off = true;
+        if let Some(session) = guard.session.as_ref() {
+            session.pty.release_writer();
+        }
+        guard.notify.notify_one();
+    }
+    // Tell every client the coming disconnect is an upgrade, so viewers
+    // reconnect instead of exiting. Delivered before the deferred exit fires.
+    for entry in state.conns.read().await.values() {
+        let caller = entry.handle.clone();
+        tokio::spawn(async move {
+            if let Err(e) = OnGatewayUpgrade::call(&caller, ()).await {
+                tracing::debug!(error = ?e, "Failed to deliver OnGatewayUpgrade");
+            }
+        });
+    }
+    tracing::info!(
+        new_pid = replacement.pid(),
+        channels = count,
+        "Gateway handed off to replacement daemon"
+    );
+    Ok(UpgradeGatewayResponse {
+        new_pid: replacement.pid() as u64,
+        channels: count,
+    })
+}
+
+/// Rebuild the channels received from the previous daemon, then acknowledge
+/// so it can release them and exit.
+#[cfg(target_os = "linux")]
+async fn adopt_handoff(
+    state: &SharedState,
+    mut handoff: crate::upgrade::ReceivedHandoff,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let mut channels = state.channels.write().await;
+    for (wire, master) in std::mem::take(&mut handoff.channels) {
+        let name = ChannelName::parse(&wire.name).map_err(|e| rpc_err(&e))?;
+        let (input_tx, input_rx) = mpsc::channel::<Vec<u8>>(INPUT_CHANNEL_CAPACITY);
+        let mut channel = ChannelState::new(
+            name.clone(),
+            wire.cmd,
+            input_tx,
+            Arc::new(Notify::new()),
+            wire.created_seq,
+        );
+        channel.created_at_unix = wire.created_at_unix;
+        channel.cwd = wire.cwd;
+        channel.env = wire.env.into_iter().collect();
+        channel.restart_policy = wire.restart_policy;
+        channel.restart_count = wire.restart_count;
+        channel.restart_streak = wire.restart_streak;
+        channel.last_exit_code = wire.last_exit_code;
+        channel.last_exit_unix = wire.last_exit_unix;
+        if wire.restart_pending {
+            channel.restart_at = Some(tokio::time::Instant::now());
+        }
+        if let (Some(s), Some(master)) = (wire.session, master) {
+            channel.set_session(Session::adopt(
+                s.id,
+                master,
+                s.child_pid,
+                s.cols,
+                s.rows,
+                s.title,
+                &s.screen_state,
+            )?);
+        }
+        // After `set_session`, which clears the cache for fresh sessions.
+        channel.output_cache = wire.output_cache;
+        tracing::info!(channel = %name, "Adopted channel from previous gateway");
+        let ch = spawn_channel_tasks(state, channel, input_rx).await;
+        channels.insert(name, ch);
+    }
+    state
+        .next_channel_seq
+        .store(handoff.next_channel_seq, Ordering::Relaxed);
+    drop(channels);
+    tokio::task::spawn_blocking(move || handoff.acknowledge())
+        .await
+        .map_err(|e| rpc_err(&format!("gateway handoff: {e}")))?
+        .map_err(boxed_io)
+}
+
+/// Bind the gateway socket once the previous daemon releases it. It keeps
+/// the (abstract-namespace) name until it exits, shortly after our ack.
+#[cfg(target_os = "linux")]
+async fn bind_released_gateway(
+    socket_name: &str,
+) -> Result<interprocess::local_socket::tokio::Listener, String> {
+    use interprocess::local_socket::{GenericNamespaced, ListenerOptions, ToNsName};
+    let deadline = tokio::time::Instant::now() + HANDOFF_BIND_TIMEOUT;
+    loop {
+        let name = socket_name
+            .to_ns_name::<GenericNamespaced>()
+            .map_err(|e| format!("serve: {e:?}"))?;
+        match ListenerOptions::new().name(name).create_tokio() {
+            Ok(listener) => return Ok(listener),
+            Err(e)
+                if e.kind() == std::io::ErrorKind::AddrInUse
+                    && tokio::time::Instant::now() < deadline =>
+            {
+                tokio::time::sleep(HANDOFF_BIND_RETRY).await;
+            }
+            Err(e) => return Err(format!("serve: {e:?}")),
+        }
+    }
+}
+
 /// Run the gateway daemon. Hosts every channel in one process; returns after
 /// a `ShutdownGateway` (or transport error).
 pub async fn run_gateway(
     gateway: ChannelName,
 ) -> Result<i32, Box<dyn std::error::Error + Send + Sync>> {
+    // A daemon spawned by `UpgradeGateway` first receives its parent's
+    // channels, and serves the parent's gateway name rather than its own
+    // resolution (a different build may default to another environment).
+    #[cfg(target_os = "linux")]
+    let handoff = match std::env::var(term_wm_config::env::HANDOFF_ENV_VAR) {
+        Ok(name) => Some(
+            tokio::task::spawn_blocking(move || crate::upgrade::receive_handoff(&name))
+                .await
+                .map_err(|e| format!("handoff: {e}"))?
+                .map_err(|e| format!("handoff: {e}"))?,
+        ),
+        Err(_) => None,
+    };
+    #[cfg(target_os = "linux")]
+    let socket_name = handoff
+        .as_ref()
+        .map_or_else(|| gateway.to_string(), |h| h.gateway.clone());
+    #[cfg(not(target_os = "linux"))]
     let socket_name = gateway.to_string();
+    let remote_listen = crate::remote::RemoteListenConfig::from_env()?;
     let state: SharedState = Arc::new(ServerState {
         conns: RwLock::new(HashMap::new()),
         channels: RwLock::new(HashMap::new()),
@@ -737,9 +1409,20 @@ pub async fn run_gateway(
         input_forwarders: std::sync::Mutex::new(HashMap::new()),
         conn_to_channel: std::sync::Mutex::new(HashMap::new()),
         internal_channels: std::sync::Mutex::new(HashSet::new()),
+        control_conns: std::sync::Mutex::new(HashMap::new()),
     });
 
+    #[cfg(target_os = "linux")]
+    let adopted = match handoff {
+        Some(handoff) => {
+            adopt_handoff(&state, handoff).await?;
+            true
+        }
+        None => false,
+    };
+
     let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+    let remote_events = event_tx.clone();
     let server = RpcIpcServer::new(Some(event_tx));
     let endpoint = server.endpoint();
 
@@ -773,6 +1456,7 @@ pub async fn run_gateway(
                     user: String::new(),
                     version: String::new(),
                     ssh_ip: None,
+                    output_codec: OutputCodec::Raw,
                 });
                 let channel_str = name.to_string();
                 entry.state = ConnState::Attached(name);
@@ -907,7 +1591,9 @@ pub async fn run_gateway(
                     rows,
                     Some(&channel),
                     effective_cwd.as_ref(),
+                    &guard.env_pairs(),
                 )?;
+                guard.cwd = effective_cwd;
                 guard.set_session(session);
                 guard.recalculate_pty_size();
                 let targets: Vec<ClientEntry> = guard.clients.values().cloned().collect();
@@ -1106,6 +1792,26 @@ pub async fn run_gateway(
         .await
         .map_err(|e| format!("register stream handler STREAM_INPUT: {e:?}"))?;
 
+    // ── NegotiateOutput ──────────────────────────────────────────────
+    let st = Arc::clone(&state);
+    endpoint
+        .register_prebuffered(NegotiateOutput::METHOD_ID, move |payload, ctx| {
+            let state = Arc::clone(&st);
+            async move {
+                let offer = NegotiateOutput::decode_request(&payload)?;
+                let codec = OutputCodec::choose(&offer);
+                let mut conns = state.conns.write().await;
+                let Some(entry) = conns.get_mut(&ctx.conn_id) else {
+                    return Err(rpc_err(RPC_ERROR_UNATTACHED));
+                };
+                entry.output_codec = codec;
+                tracing::debug!(conn_id = ctx.conn_id, %codec, "Negotiated output codec");
+                NegotiateOutput::encode_response(codec).map_err(boxed_io)
+            }
+        })
+        .await
+        .map_err(|e| format!("register NegotiateOutput: {e:?}"))?;
+
     // ── SubscribeOutput ──────────────────────────────────────────────
     let st = Arc::clone(&state);
     endpoint
@@ -1119,11 +1825,20 @@ pub async fn run_gateway(
                     let Some(channel) = channel else {
                         return;
                     };
+                    let codec = st
+                        .conns
+                        .read()
+                        .await
+                        .get(&conn_id)
+                        .map_or(OutputCodec::Raw, |c| c.output_codec);
                     let ch = resolve_channel(&st, &channel).await;
                     let Some(ch) = ch else {
                         return;
                     };
                     let mut guard = ch.lock().await;
+                    // Output already read from the PTY belongs to the current
+                    // subscribers; the snapshot below reflects it.
+                    guard.flush_output_batch();
                     // Drain accumulated PTY output and the retained final output
                     // from a dead session (by clone, so a subscriber that
                     // attaches and drops does not consume the bytes for later
@@ -1137,12 +1852,12 @@ pub async fn run_gateway(
                         if all.is_empty() { None } else { Some(all) }
                     };
                     let snapshot = guard.session.as_mut().map(|s| s.generate_snapshot());
-                    guard.subscribers.push(SubscriberEntry {
+                    let mut sub = SubscriberEntry {
                         conn_id,
                         respond: respond.clone(),
-                    });
-                    guard.notify.notify_one();
-                    let is_dead = guard.session.is_none();
+                        encoder: OutputEncoder::new(codec),
+                    };
+                    let is_dead = guard.session.is_none() && guard.restart_at.is_none();
                     // Deliver the retained/live output and the end-of-stream
                     // marker while still holding the channel guard, so the
                     // polling task's dead-session finalization cannot interleave
@@ -1151,14 +1866,16 @@ pub async fn run_gateway(
                     if let Some(data) = snapshot
                         && !data.is_empty()
                     {
-                        respond.respond(data, false);
+                        sub.send(&data);
                     }
                     if let Some(data) = early {
-                        respond.respond(data, false);
+                        sub.send(&data);
                     }
                     if is_dead {
                         respond.respond(Vec::new(), true);
                     }
+                    guard.subscribers.push(sub);
+                    guard.notify.notify_one();
                     drop(guard);
                 });
             }
@@ -1320,10 +2037,174 @@ pub async fn run_gateway(
         .await
         .map_err(|e| format!("register KillClient: {e:?}"))?;
 
+    // ── RenameChannel ────────────────────────────────────────────────
+    let st = Arc::clone(&state);
+    endpoint
+        .register_prebuffered(RenameChannel::METHOD_ID, move |payload, _ctx| {
+            let state = Arc::clone(&st);
+            async move {
+                if state.is_shutting_down.load(Ordering::SeqCst) {
+                    return Err(rpc_err(RPC_ERROR_SHUTTING_DOWN));
+                }
+                let req = RenameChannel::decode_request(&payload).map_err(boxed_io)?;
+                let from = ChannelName::parse(&req.from).map_err(|e| rpc_err(&e))?;
+                let to = ChannelName::parse(&req.to).map_err(|e| rpc_err(&e))?;
+                let wm_caller = rename_channel(state.as_ref(), &from, &to).await?;
+                tracing::info!(from = %from, to = %to, "Renamed channel");
+                // The WM names its channel in its own requests (and on
+                // reconnect), so it has to learn the new one.
+                if let Some(caller) = wm_caller {
+                    let channel = to.to_string();
+                    tokio::spawn(async move {
+                        if let Err(e) =
+                            OnChannelRenamed::call(&caller, OnChannelRenamedRequest { channel })
+                                .await
+                        {
+                            tracing::debug!(error = ?e, "Failed to deliver OnChannelRenamed");
+                        }
+                    });
+                }
+                RenameChannel::encode_response(()).map_err(boxed_io)
+            }
+        })
+        .await
+        .map_err(|e| format!("register RenameChannel: {e:?}"))?;
+
+    // ── CloneChannel ─────────────────────────────────────────────────
+    let st = Arc::clone(&state);
+    endpoint
+        .register_prebuffered(CloneChannel::METHOD_ID, move |payload, _ctx| {
+            let state = Arc::clone(&st);
+            async move {
+                if state.is_shutting_down.load(Ordering::SeqCst) {
+                    return Err(rpc_err(RPC_ERROR_SHUTTING_DOWN));
+                }
+                let req = CloneChannel::decode_request(&payload).map_err(boxed_io)?;
+                let source = ChannelName::parse(&req.source).map_err(|e| rpc_err(&e))?;
+                let target = ChannelName::parse(&req.target).map_err(|e| rpc_err(&e))?;
+                let src = resolve_channel(state.as_ref(), &source)
+                    .await
+                    .ok_or_else(|| rpc_err("channel not found"))?;
+                let (cmd, cwd, env, (cols, rows)) = {
+                    let guard = src.lock().await;
+                    (
+                        guard.cmd.clone(),
+                        guard.cwd.clone(),
+                        guard.env.clone(),
+                        guard
+                            .session
+                            .as_ref()
+                            .map_or(CLONE_FALLBACK_SIZE, |s| (s.cols, s.rows)),
+                    )
+                };
+                if resolve_channel(state.as_ref(), &target).await.is_some() {
+                    return Err(rpc_err(RPC_ERROR_CHANNEL_EXISTS));
+                }
+                let ch = get_or_create_channel(&state, &target).await;
+                let mut guard = ch.lock().await;
+                // A racing Attach may have claimed the name since the check.
+                if guard.session.is_some() || !guard.clients.is_empty() {
+                    return Err(rpc_err(RPC_ERROR_CHANNEL_EXISTS));
+                }
+                guard.cmd = cmd.clone();
+                guard.env = env;
+                let spawned = Session::spawn(
+                    SESSION_ID,
+                    (!cmd.is_empty()).then_some(cmd),
+                    cols,
+                    rows,
+                    Some(&target),
+                    cwd.as_ref(),
+                    &guard.env_pairs(),
+                );
+                let session = match spawned {
+                    Ok(session) => session,
+                    Err(e) => {
+                        // Don't leave the freshly created, session-less
+                        // channel behind: it would squat the target name.
+                        drop(guard);
+                        discard_empty_channel(&state, &target, &ch).await;
+                        return Err(e);
+                    }
+                };
+                guard.cwd = cwd;
+                guard.set_session(session);
+                tracing::info!(source = %source, target = %target, "Cloned channel");
+                CloneChannel::encode_response(()).map_err(boxed_io)
+            }
+        })
+        .await
+        .map_err(|e| format!("register CloneChannel: {e:?}"))?;
+
+    // ── SetChannelEnv ────────────────────────────────────────────────
+    let st = Arc::clone(&state);
+    endpoint
+        .register_prebuffered(SetChannelEnv::METHOD_ID, move |payload, _ctx| {
+            let state = Arc::clone(&st);
+            async move {
+                if state.is_shutting_down.load(Ordering::SeqCst) {
+                    return Err(rpc_err(RPC_ERROR_SHUTTING_DOWN));
+                }
+                let req = SetChannelEnv::decode_request(&payload).map_err(boxed_io)?;
+                let name = ChannelName::parse(&req.channel).map_err(|e| rpc_err(&e))?;
+                if let Some(bad) = req
+                    .set
+                    .iter()
+                    .map(|(k, _)| k)
+                    .chain(&req.unset)
+                    .find(|k| !valid_env_name(k))
+                {
+                    return Err(rpc_err(&format!(
+                        "invalid environment variable name '{bad}'"
+                    )));
+                }
+                let ch = resolve_channel(state.as_ref(), &name)
+                    .await
+                    .ok_or_else(|| rpc_err("channel not found"))?;
+                let mut guard = ch.lock().await;
+                for key in &req.unset {
+                    guard.env.remove(key);
+                }
+                guard.env.extend(req.set);
+                SetChannelEnv::encode_response(()).map_err(boxed_io)
+            }
+        })
+        .await
+        .map_err(|e| format!("register SetChannelEnv: {e:?}"))?;
+
+    // ── SetRestartPolicy ─────────────────────────────────────────────
+    let st = Arc::clone(&state);
+    endpoint
+        .register_prebuffered(SetRestartPolicy::METHOD_ID, move |payload, _ctx| {
+            let state = Arc::clone(&st);
+            async move {
+                if state.is_shutting_down.load(Ordering::SeqCst) {
+                    return Err(rpc_err(RPC_ERROR_SHUTTING_DOWN));
+                }
+                let req = SetRestartPolicy::decode_request(&payload).map_err(boxed_io)?;
+                let name = ChannelName::parse(&req.channel).map_err(|e| rpc_err(&e))?;
+                let ch = resolve_channel(state.as_ref(), &name)
+                    .await
+                    .ok_or_else(|| rpc_err("channel not found"))?;
+                let mut guard = ch.lock().await;
+                guard.restart_policy = req.policy;
+                guard.restart_streak = 0;
+                if req.policy == RestartPolicy::Never {
+                    guard.restart_at = None;
+                    guard.notify.notify_one();
+                }
+                tracing::info!(channel = %name, policy = %req.policy, "Set restart policy");
+                SetRestartPolicy::encode_response(()).map_err(boxed_io)
+            }
+        })
+        .await
+        .map_err(|e| format!("register SetRestartPolicy: {e:?}"))?;
+
     // ── ShutdownGateway ──────────────────────────────────────────────
     let st = Arc::clone(&state);
     let (shutdown_tx, mut shutdown_rx) = oneshot::channel::<()>();
     let shutdown_tx = Arc::new(Mutex::new(Some(shutdown_tx)));
+    let upgrade_shutdown_tx = Arc::clone(&shutdown_tx);
     endpoint
         .register_prebuffered(ShutdownGateway::METHOD_ID, move |payload, _ctx| {
             let state = Arc::clone(&st);
@@ -1393,6 +2274,44 @@ pub async fn run_gateway(
         .await
         .map_err(|e| format!("register ShutdownGateway: {e:?}"))?;
 
+    // ── UpgradeGateway ───────────────────────────────────────────────
+    let st = Arc::clone(&state);
+    let upgrade_socket = socket_name.clone();
+    endpoint
+        .register_prebuffered(UpgradeGateway::METHOD_ID, move |payload, _ctx| {
+            let state = Arc::clone(&st);
+            let shutdown_tx = Arc::clone(&upgrade_shutdown_tx);
+            let socket = upgrade_socket.clone();
+            async move {
+                let req = UpgradeGateway::decode_request(&payload).map_err(boxed_io)?;
+                #[cfg(target_os = "linux")]
+                {
+                    let binary = req.binary.filter(|b| !b.is_empty()).map(|b| b.decode());
+                    let response = hand_off_gateway(&state, &socket, binary).await?;
+                    // Exit without touching the sessions, once the response
+                    // frame has flushed (same deferral as ShutdownGateway).
+                    tokio::spawn(async move {
+                        tokio::time::sleep(std::time::Duration::from_millis(
+                            SHUTDOWN_FLUSH_GRACE_MS,
+                        ))
+                        .await;
+                        let mut tx_guard = shutdown_tx.lock().await;
+                        if let Some(tx) = tx_guard.take() {
+                            let _ = tx.send(());
+                        }
+                    });
+                    UpgradeGateway::encode_response(response).map_err(boxed_io)
+                }
+                #[cfg(not(target_os = "linux"))]
+                {
+                    let _ = (req, state, shutdown_tx, socket);
+                    Err(rpc_err(RPC_ERROR_UPGRADE_UNSUPPORTED))
+                }
+            }
+        })
+        .await
+        .map_err(|e| format!("register UpgradeGateway: {e:?}"))?;
+
     // ── RebindWorkspace ──────────────────────────────────────────────
     let st = Arc::clone(&state);
     endpoint
@@ -1443,8 +2362,12 @@ pub async fn run_gateway(
                 }
                 let req = SubscribeInternalInput::decode_request(&payload).map_err(boxed_io)?;
                 let name = ChannelName::parse(&req.channel).map_err(|e| rpc_err(&e))?;
+                let mut replay = Vec::new();
                 if let Some(ch) = resolve_channel(state.as_ref(), &name).await {
                     let mut guard = ch.lock().await;
+                    // Host reports sent before the WM subscribed (the client
+                    // sends them right after attaching).
+                    replay = guard.host_events();
                     // Set input mode on the channel (source of truth)
                     guard.input_mode = InputMode::AttributedIpc {
                         wm_conn_id: ctx.conn_id,
@@ -1464,6 +2387,23 @@ pub async fn run_gateway(
                     // All existing conn_ids are already in conn_to_channel
                     // (added by Attach handler). No additional update needed.
                 }
+                if !replay.is_empty() {
+                    let caller = RpcIpcConnectionContextHandle(ctx.clone());
+                    let conn_id = ctx.conn_id;
+                    tokio::spawn(async move {
+                        for event in replay {
+                            if let Err(e) = OnAttributedInput::call(
+                                &caller,
+                                OnAttributedInputRequest { conn_id, event },
+                            )
+                            .await
+                            {
+                                tracing::debug!(error = ?e, "Failed to replay host reports");
+                                break;
+                            }
+                        }
+                    });
+                }
                 SubscribeInternalInput::encode_response(()).map_err(boxed_io)
             }
         })
@@ -1480,10 +2420,16 @@ pub async fn run_gateway(
                     return Err(rpc_err(RPC_ERROR_SHUTTING_DOWN));
                 }
                 let req = SendAttributedInput::decode_request(&payload).map_err(boxed_io)?;
-                let name = ChannelName::parse(&req.channel).map_err(|e| rpc_err(&e))?;
+                // An attached viewer's binding is authoritative: it follows a
+                // rename, while the name in the request may be stale.
+                let name = match bound_channel(state.as_ref(), ctx.conn_id).await {
+                    Some(name) => name,
+                    None => ChannelName::parse(&req.channel).map_err(|e| rpc_err(&e))?,
+                };
                 // Clone handle and drop lock BEFORE awaiting RPC
                 let caller = if let Some(ch) = resolve_channel(state.as_ref(), &name).await {
-                    let guard = ch.lock().await;
+                    let mut guard = ch.lock().await;
+                    guard.observe_host_event(&req.event);
                     guard.internal_wm_caller.clone()
                 } else {
                     None
@@ -1511,6 +2457,125 @@ pub async fn run_gateway(
         .await
         .map_err(|e| format!("register SendAttributedInput: {e:?}"))?;
 
+    // ── SubscribeControl ───────────────────────────────────────────
+    let st = Arc::clone(&state);
+    endpoint
+        .register_prebuffered(SubscribeControl::METHOD_ID, move |payload, ctx| {
+            let state = Arc::clone(&st);
+            async move {
+                if state.is_shutting_down.load(Ordering::SeqCst) {
+                    return Err(rpc_err(RPC_ERROR_SHUTTING_DOWN));
+                }
+                let req = SubscribeControl::decode_request(&payload).map_err(boxed_io)?;
+                let name = ChannelName::parse(&req.channel).map_err(|e| rpc_err(&e))?;
+                let ch = resolve_channel(state.as_ref(), &name)
+                    .await
+                    .ok_or_else(|| rpc_err("channel not found"))?;
+                let (tx, mut rx) = mpsc::unbounded_channel::<Vec<ControlEvent>>();
+                let handle = RpcIpcConnectionContextHandle(ctx.clone());
+                tokio::spawn(async move {
+                    while let Some(batch) = rx.recv().await {
+                        if let Err(e) = OnControlEvents::call(&handle, batch).await {
+                            tracing::debug!(error = ?e, "Control subscriber went away");
+                            break;
+                        }
+                    }
+                });
+                let wm_caller = {
+                    let mut guard = ch.lock().await;
+                    guard.control_subscribers.push(ControlSubscriber {
+                        conn_id: ctx.conn_id,
+                        tx,
+                    });
+                    guard.internal_wm_caller.clone()
+                };
+                state
+                    .control_conns
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .insert(ctx.conn_id, name);
+                // Start the new subscriber from a known state.
+                if let Some(caller) = wm_caller {
+                    tokio::spawn(async move {
+                        if let Err(e) =
+                            OnControlCommand::call(&caller, ControlCommand::ListWindows).await
+                        {
+                            tracing::debug!(error = ?e, "Failed to request window listing");
+                        }
+                    });
+                }
+                SubscribeControl::encode_response(()).map_err(boxed_io)
+            }
+        })
+        .await
+        .map_err(|e| format!("register SubscribeControl: {e:?}"))?;
+
+    // ── PublishControlEvents ───────────────────────────────────────
+    let st = Arc::clone(&state);
+    endpoint
+        .register_prebuffered(PublishControlEvents::METHOD_ID, move |payload, ctx| {
+            let state = Arc::clone(&st);
+            async move {
+                let req = PublishControlEvents::decode_request(&payload).map_err(boxed_io)?;
+                let name = ChannelName::parse(&req.channel).map_err(|e| rpc_err(&e))?;
+                if let Some(ch) = resolve_channel(state.as_ref(), &name).await {
+                    let mut guard = ch.lock().await;
+                    // Only the channel's own WM speaks for it.
+                    if guard.internal_wm_conn_id != Some(ctx.conn_id) {
+                        return Err(rpc_err(RPC_ERROR_NO_CONTROL_TARGET));
+                    }
+                    // Unbounded sends never block, so holding the lock is fine;
+                    // a closed queue means its forwarder gave up on the client.
+                    guard
+                        .control_subscribers
+                        .retain(|s| s.tx.send(req.events.clone()).is_ok());
+                }
+                PublishControlEvents::encode_response(()).map_err(boxed_io)
+            }
+        })
+        .await
+        .map_err(|e| format!("register PublishControlEvents: {e:?}"))?;
+
+    // ── SendControlCommand ─────────────────────────────────────────
+    let st = Arc::clone(&state);
+    endpoint
+        .register_prebuffered(SendControlCommand::METHOD_ID, move |payload, ctx| {
+            let state = Arc::clone(&st);
+            async move {
+                if state.is_shutting_down.load(Ordering::SeqCst) {
+                    return Err(rpc_err(RPC_ERROR_SHUTTING_DOWN));
+                }
+                let req = SendControlCommand::decode_request(&payload).map_err(boxed_io)?;
+                // A subscribed client follows renames of its channel.
+                let subscribed = state
+                    .control_conns
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .get(&ctx.conn_id)
+                    .cloned();
+                let name = match subscribed {
+                    Some(name) => name,
+                    None => ChannelName::parse(&req.channel).map_err(|e| rpc_err(&e))?,
+                };
+                // Clone handle and drop lock BEFORE awaiting RPC
+                let caller = match resolve_channel(state.as_ref(), &name).await {
+                    Some(ch) => ch.lock().await.internal_wm_caller.clone(),
+                    None => None,
+                };
+                let caller = caller.ok_or_else(|| rpc_err(RPC_ERROR_NO_CONTROL_TARGET))?;
+                // Awaited inline so commands from one client reach the WM in
+                // the order they were sent.
+                OnControlCommand::call(&caller, req.command)
+                    .await
+                    .map_err(|e| {
+                        rpc_err(&format!("gateway: control command not delivered: {e}"))
+                    })?;
+                SendControlCommand::encode_response(()).map_err(boxed_io)
+            }
+        })
+        .await
+        .map_err(|e| format!("register SendControlCommand: {e:?}"))?;
+
     // ── Connection event loop ────────────────────────────────────────
     let st = Arc::clone(&state);
     tokio::spawn(async move {
@@ -1533,6 +2598,7 @@ pub async fn run_gateway(
                         user: String::new(),
                         version: String::new(),
                         ssh_ip: None,
+                        output_codec: OutputCodec::Raw,
                     });
                 }
                 RpcIpcServerEvent::ClientDisconnected(conn_id) => {
@@ -1543,11 +2609,44 @@ pub async fn run_gateway(
         }
     });
 
-    tracing::info!("Gateway listening on channel {gateway}");
+    // ── Remote access ────────────────────────────────────────────────
+    if let Some(remote) = remote_listen {
+        #[cfg(target_os = "linux")]
+        let retry = adopted.then_some(HANDOFF_BIND_TIMEOUT);
+        #[cfg(not(target_os = "linux"))]
+        let retry = None;
+        match crate::remote::bind(&remote.addr, retry).await {
+            Ok(listener) => {
+                tracing::info!("Gateway accepting remote clients on {}", remote.addr);
+                tokio::spawn(crate::remote::serve(
+                    listener,
+                    remote.token,
+                    server.endpoint(),
+                    remote_events,
+                ));
+            }
+            // A replacement daemon already owns live sessions: losing remote
+            // access beats losing them.
+            Err(e) if retry.is_some() => {
+                tracing::error!(error = %e, "Remote access unavailable after upgrade");
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    tracing::info!("Gateway listening on channel {socket_name}");
 
     // Wait for either the server to finish or a shutdown signal.
     let exit_code = tokio::select! {
         result = async {
+            #[cfg(target_os = "linux")]
+            if adopted {
+                let listener = bind_released_gateway(&socket_name).await?;
+                return Arc::new(server)
+                    .serve_with_listener(listener)
+                    .await
+                    .map_err(|e| format!("serve: {e:?}"));
+            }
             server.serve(&socket_name).await.map_err(|e| format!("serve: {e:?}"))
         } => {
             result?;
@@ -1576,6 +2675,7 @@ mod tests {
     fn state_with_input(input_tx: mpsc::Sender<Vec<u8>>) -> SharedState {
         let name = ChannelName::parse("test/coalesce").expect("parse channel");
         let channel = Arc::new(Mutex::new(ChannelState::new(
+            name.clone(),
             Vec::new(),
             input_tx,
             Arc::new(Notify::new()),
@@ -1598,6 +2698,7 @@ mod tests {
             user: String::new(),
             version: String::new(),
             ssh_ip: None,
+            output_codec: OutputCodec::Raw,
         };
         let mut conns = HashMap::new();
         conns.insert(1, conn);
@@ -1609,6 +2710,7 @@ mod tests {
             input_forwarders: std::sync::Mutex::new(HashMap::new()),
             conn_to_channel: std::sync::Mutex::new(HashMap::new()),
             internal_channels: std::sync::Mutex::new(HashSet::new()),
+            control_conns: std::sync::Mutex::new(HashMap::new()),
         })
     }
 
@@ -1736,10 +2838,303 @@ mod tests {
         assert!(guard.internal_wm_caller.is_none());
     }
 
+    #[tokio::test]
+    async fn evict_conn_drops_control_subscription() {
+        // Control clients never attach, so eviction must find their channel
+        // through `control_conns` rather than the connection's attach state.
+        let (input_tx, _input_rx) = mpsc::channel(128);
+        let state = state_with_input(input_tx);
+        let name = ChannelName::parse("test/coalesce").expect("parse channel");
+        let ch = state
+            .channels
+            .read()
+            .await
+            .get(&name)
+            .expect("channel exists")
+            .clone();
+        let (tx, _rx) = mpsc::unbounded_channel();
+        ch.lock()
+            .await
+            .control_subscribers
+            .push(ControlSubscriber { conn_id: 7, tx });
+        state.control_conns.lock().unwrap().insert(7, name);
+
+        evict_conn(&state, 7).await;
+
+        assert!(state.control_conns.lock().unwrap().is_empty());
+        assert!(ch.lock().await.control_subscribers.is_empty());
+    }
+
+    #[tokio::test]
+    async fn rename_channel_moves_every_routing_entry() {
+        let (input_tx, _input_rx) = mpsc::channel(128);
+        let state = state_with_input(input_tx);
+        let from = ChannelName::parse("test/coalesce").unwrap();
+        let to = ChannelName::parse("test/renamed").unwrap();
+        state
+            .internal_channels
+            .lock()
+            .unwrap()
+            .insert(from.to_string());
+        state
+            .conn_to_channel
+            .lock()
+            .unwrap()
+            .insert(1, from.to_string());
+        state.control_conns.lock().unwrap().insert(7, from.clone());
+
+        rename_channel(&state, &from, &to).await.unwrap();
+
+        let channels = state.channels.read().await;
+        assert!(!channels.contains_key(&from));
+        let ch = channels.get(&to).expect("renamed channel").clone();
+        drop(channels);
+        assert_eq!(ch.lock().await.name, to);
+        assert_eq!(bound_channel(&state, 1).await, Some(to.clone()));
+        assert_eq!(
+            state.conn_to_channel.lock().unwrap().get(&1),
+            Some(&to.to_string())
+        );
+        let internal = state.internal_channels.lock().unwrap().clone();
+        assert_eq!(internal, HashSet::from([to.to_string()]));
+        assert_eq!(state.control_conns.lock().unwrap().get(&7), Some(&to));
+    }
+
+    #[tokio::test]
+    async fn rename_channel_refuses_taken_or_missing_names() {
+        let (input_tx, _input_rx) = mpsc::channel(128);
+        let state = state_with_input(input_tx);
+        let name = ChannelName::parse("test/coalesce").unwrap();
+        let missing = ChannelName::parse("test/missing").unwrap();
+
+        let err = rename_channel(&state, &name, &name).await.err().unwrap();
+        assert_eq!(err.to_string(), RPC_ERROR_CHANNEL_EXISTS);
+        let other = ChannelName::parse("test/x").unwrap();
+        let err = rename_channel(&state, &missing, &other)
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "channel not found");
+        assert_eq!(bound_channel(&state, 1).await, Some(name));
+    }
+
+    #[test]
+    fn env_names_reject_equals_and_nul() {
+        assert!(valid_env_name("EDITOR"));
+        assert!(!valid_env_name(""));
+        assert!(!valid_env_name("A=B"));
+        assert!(!valid_env_name("A\0B"));
+    }
+
+    #[test]
+    fn restart_delay_follows_policy_and_backs_off() {
+        use std::time::Duration;
+        let on_failure = RestartPolicy::OnFailure { max_retries: 3 };
+        assert_eq!(restart_delay(RestartPolicy::Never, Some(1), 0), None);
+        assert_eq!(restart_delay(on_failure, Some(0), 0), None);
+        assert_eq!(
+            restart_delay(on_failure, Some(1), 0),
+            Some(RESTART_BACKOFF_BASE)
+        );
+        assert_eq!(
+            restart_delay(on_failure, None, 0),
+            Some(RESTART_BACKOFF_BASE)
+        );
+        assert_eq!(
+            restart_delay(on_failure, Some(1), 2),
+            Some(RESTART_BACKOFF_BASE * 4)
+        );
+        assert_eq!(restart_delay(on_failure, Some(1), 3), None);
+        assert_eq!(
+            restart_delay(RestartPolicy::Always, Some(0), 0),
+            Some(RESTART_BACKOFF_BASE)
+        );
+        assert_eq!(
+            restart_delay(RestartPolicy::Always, Some(0), 40),
+            Some(RESTART_BACKOFF_MAX)
+        );
+        assert!(RESTART_BACKOFF_MAX >= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn session_exit_schedules_restart_unless_killed() {
+        let (input_tx, _input_rx) = mpsc::channel(128);
+        let name = ChannelName::parse("test/restart").unwrap();
+        let mut channel = ChannelState::new(name, Vec::new(), input_tx, Arc::new(Notify::new()), 1);
+        channel.restart_policy = RestartPolicy::OnFailure { max_retries: 2 };
+
+        channel.on_session_exit(Some(0));
+        assert!(channel.restart_at.is_none(), "clean exit stays down");
+        assert_eq!(channel.last_exit_code, Some(0));
+        assert!(channel.last_exit_unix.is_some());
+
+        channel.on_session_exit(Some(1));
+        assert!(channel.restart_at.is_some());
+        assert_eq!(channel.restart_streak, 1);
+
+        channel.restart_at = None;
+        channel.kill_pending = true;
+        channel.on_session_exit(Some(143));
+        assert!(channel.restart_at.is_none(), "a killed session stays dead");
+    }
+
+    #[tokio::test]
+    async fn restart_keeps_retained_output() {
+        let (input_tx, _input_rx) = mpsc::channel(128);
+        let name = ChannelName::parse("test/restart").unwrap();
+        let report = tempfile::tempdir().unwrap();
+        let cmd = vec![
+            term_session_mock::get_mock_bin()
+                .to_string_lossy()
+                .into_owned(),
+            "envvar".to_string(),
+            "TERM_SESSION_TEST_UNUSED".to_string(),
+            report.path().join("out").to_string_lossy().into_owned(),
+        ];
+        let mut channel = ChannelState::new(name, cmd, input_tx, Arc::new(Notify::new()), 1);
+        channel.retain_final_output(b"previous run");
+        channel.restart_at = Some(tokio::time::Instant::now());
+
+        channel.restart_session().unwrap();
+
+        assert!(channel.session.is_some());
+        assert!(channel.restart_at.is_none());
+        assert_eq!(channel.restart_count, 1);
+        assert_eq!(channel.output_cache, b"previous run");
+        if let Some(session) = channel.session.as_mut() {
+            session.pty.kill_child().ok();
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_restart_schedules_the_next_attempt() {
+        let (input_tx, _input_rx) = mpsc::channel(128);
+        let name = ChannelName::parse("test/restart").unwrap();
+        let missing = tempfile::tempdir().unwrap().path().join("no-such-binary");
+        let cmd = vec![missing.to_string_lossy().into_owned()];
+        let mut channel = ChannelState::new(name, cmd, input_tx, Arc::new(Notify::new()), 1);
+        channel.restart_policy = RestartPolicy::OnFailure { max_retries: 2 };
+        channel.on_session_exit(Some(1));
+        assert_eq!(channel.restart_streak, 1);
+
+        assert!(channel.restart_session().is_err());
+        assert!(channel.session.is_none());
+        assert!(channel.restart_at.is_some(), "a failed spawn is retried");
+        assert_eq!(channel.restart_streak, 2);
+        assert_eq!(channel.restart_count, 0);
+
+        assert!(channel.restart_session().is_err());
+        assert!(channel.restart_at.is_none(), "retries are exhausted");
+    }
+
+    #[tokio::test]
+    async fn discard_empty_channel_removes_only_its_own_entry() {
+        let (input_tx, _input_rx) = mpsc::channel(128);
+        let state = state_with_input(input_tx);
+        let existing = ChannelName::parse("test/coalesce").unwrap();
+        let target = ChannelName::parse("test/clone").unwrap();
+        let created = get_or_create_channel(&state, &target).await;
+        let other = state.channels.read().await.get(&existing).cloned().unwrap();
+
+        // A different channel's handle never removes the entry.
+        discard_empty_channel(&state, &target, &other).await;
+        assert!(state.channels.read().await.contains_key(&target));
+
+        discard_empty_channel(&state, &target, &created).await;
+        assert!(!state.channels.read().await.contains_key(&target));
+        assert!(created.lock().await.is_reaped);
+        assert!(state.channels.read().await.contains_key(&existing));
+    }
+
+    /// Read the session's output until it contains `needle` or `timeout`
+    /// passes; returns everything read.
+    #[cfg(unix)]
+    fn read_output_until(
+        session: &mut Session,
+        needle: &[u8],
+        timeout: std::time::Duration,
+    ) -> Vec<u8> {
+        let deadline = std::time::Instant::now() + timeout;
+        let mut out = Vec::new();
+        while std::time::Instant::now() < deadline
+            && !out.windows(needle.len()).any(|w| w == needle)
+        {
+            out.extend(session.read_output());
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        out
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn channel_answers_color_queries_with_host_colors_only() {
+        // `cat` sends the query back as real output, and the tty echo renders
+        // any reply written to the PTY as visible `^[]11;rgb:...` text.
+        let (input_tx, _input_rx) = mpsc::channel(128);
+        let name = ChannelName::parse("test/colors").unwrap();
+        let cmd = vec!["cat".to_string()];
+        let mut channel = ChannelState::new(name, cmd, input_tx, Arc::new(Notify::new()), 1);
+        channel.restart_session().unwrap();
+        let session = channel.session.as_mut().unwrap();
+        let timeout = std::time::Duration::from_secs(5);
+
+        // No host has reported its colors: the query goes unanswered rather
+        // than being answered with the daemon's process-wide defaults.
+        session.pty.write_bytes(b"\x1b]11;?\x07\n").unwrap();
+        let out = read_output_until(session, b"\x1b]11;?\x07", timeout);
+        assert!(out.windows(7).any(|w| w == b"\x1b]11;?\x07"));
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        let out = [out, session.read_output()].concat();
+        assert!(!out.windows(7).any(|w| w == b"11;rgb:"), "{out:?}");
+
+        channel.set_host_color(HostColorKind::Background, (0x12, 0x34, 0x56));
+        assert!(matches!(
+            channel.host_events().as_slice(),
+            [Event::HostColor(
+                HostColorKind::Background,
+                (0x12, 0x34, 0x56)
+            )]
+        ));
+        let session = channel.session.as_mut().unwrap();
+        session.pty.write_bytes(b"\x1b]11;?\x07\n").unwrap();
+        let reply = b"11;rgb:1212/3434/5656";
+        let out = read_output_until(session, reply, timeout);
+        assert!(out.windows(reply.len()).any(|w| w == reply), "{out:?}");
+        session.pty.kill_child().ok();
+    }
+
+    #[test]
+    fn host_reports_are_replayed_display_first() {
+        let (input_tx, _input_rx) = mpsc::channel(128);
+        let name = ChannelName::parse("test/host").unwrap();
+        let mut channel = ChannelState::new(name, Vec::new(), input_tx, Arc::new(Notify::new()), 1);
+        assert!(channel.host_events().is_empty());
+
+        let display = HostDisplay {
+            color_depth: term_wm_events::HostColorDepth::Ansi256,
+            unicode_box_drawing: false,
+            osc52: false,
+            synchronized_output: false,
+            mouse: true,
+        };
+        channel.observe_host_event(&Event::HostColor(HostColorKind::Foreground, (1, 2, 3)));
+        channel.observe_host_event(&Event::HostDisplay(display));
+        channel.observe_host_event(&Event::FocusGained);
+
+        assert!(matches!(
+            channel.host_events().as_slice(),
+            [
+                Event::HostDisplay(d),
+                Event::HostColor(HostColorKind::Foreground, (1, 2, 3)),
+            ] if *d == display
+        ));
+    }
+
     #[test]
     fn channel_state_defaults_to_raw_pty_mode() {
         let (input_tx, _input_rx) = mpsc::channel(128);
-        let channel = ChannelState::new(Vec::new(), input_tx, Arc::new(Notify::new()), 1);
+        let name = ChannelName::parse("test/defaults").unwrap();
+        let channel = ChannelState::new(name, Vec::new(), input_tx, Arc::new(Notify::new()), 1);
         assert_eq!(channel.input_mode, InputMode::RawPty);
         assert_eq!(channel.internal_wm_conn_id, None);
         assert!(channel.internal_wm_caller.is_none());
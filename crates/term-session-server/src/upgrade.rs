@@ -0,0 +1,320 @@
+//! Transport for the zero-downtime gateway upgrade (Linux).
+//!
+//! The running daemon spawns its replacement with [`HANDOFF_ENV_VAR`] naming
+//! a private abstract Unix socket, then streams every channel over it as
+//! [`HandoffFrame`]s with the PTY master attached via `SCM_RIGHTS`. Both ends
+//! check the peer's PID (the old daemon must be talking to the child it just
+//! spawned, the new one to its parent), so nothing else on the host can
+//! inject or receive a session. The old daemon only commits — releases its
+//! PTY writers and exits — after the replacement acknowledges.
+
+use std::io::{self, Read, Write};
+use std::os::linux::net::SocketAddrExt;
+use std::os::unix::io::{AsRawFd, OwnedFd};
+use std::os::unix::net::{SocketAddr, UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+use term_session_muxio_service_definitions::handoff::{
+    HANDOFF_ACK, HandoffChannel, HandoffFrame, MAX_HANDOFF_FRAME_BYTES,
+};
+use term_sys_io::{fd_passing, pty_handoff};
+use term_wm_config::env::HANDOFF_ENV_VAR;
+
+/// How long the old daemon waits for its replacement to connect, and each
+/// side waits on the other for a frame or the final acknowledgement.
+const HANDOFF_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Poll cadence while waiting for the replacement to connect.
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Suffix Linux appends to `/proc/self/exe` once the binary was replaced on
+/// disk — exactly the situation an upgrade is run in.
+const DELETED_EXE_SUFFIX: &str = " (deleted)";
+
+fn handoff_error(message: impl Into<String>) -> io::Error {
+    io::Error::other(format!("gateway handoff: {}", message.into()))
+}
+
+/// The binary the replacement daemon runs when the caller names none: this
+/// daemon's own executable path, even if the file was swapped underneath it.
+fn default_binary() -> io::Result<PathBuf> {
+    let exe = std::env::current_exe()?;
+    match exe
+        .to_str()
+        .and_then(|s| s.strip_suffix(DELETED_EXE_SUFFIX))
+    {
+        Some(stripped) => Ok(PathBuf::from(stripped)),
+        None => Ok(exe),
+    }
+}
+
+/// The replacement daemon process, owned by the old daemon until the handoff
+/// commits. Dropping it leaves the process running.
+pub(crate) struct Replacement {
+    child: Child,
+}
+
+impl Replacement {
+    pub(crate) fn pid(&self) -> u32 {
+        self.child.id()
+    }
+
+    /// Roll back: the replacement never took ownership, so end it.
+    pub(crate) fn abort(mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Spawn `binary --daemon` as the replacement and wait for it to connect.
+/// On failure the child (if any) is already cleaned up.
+pub(crate) fn launch_replacement(
+    binary: Option<PathBuf>,
+) -> io::Result<(Replacement, HandoffSender)> {
+    let binary = match binary {
+        Some(b) => b,
+        None => default_binary()?,
+    };
+    let name = format!(
+        "term-session-handoff-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0)
+    );
+    let listener = UnixListener::bind_addr(&SocketAddr::from_abstract_name(name.as_bytes())?)?;
+    listener.set_nonblocking(true)?;
+    let mut replacement = Replacement {
+        child: spawn_daemon(&binary, &name)?,
+    };
+    match accept_from(&listener, &mut replacement) {
+        Ok(stream) => Ok((replacement, HandoffSender { stream })),
+        Err(e) => {
+            replacement.abort();
+            Err(e)
+        }
+    }
+}
+
+fn spawn_daemon(binary: &Path, handoff_name: &str) -> io::Result<Child> {
+    let mut cmd = Command::new(binary);
+    cmd.arg("--daemon")
+        .env(HANDOFF_ENV_VAR, handoff_name)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    // Same detachment as `auto_spawn`: the replacement outlives us and must
+    // not share our session.
+    pty_handoff::detach_into_new_session(&mut cmd);
+    cmd.spawn()
+        .map_err(|e| handoff_error(format!("spawn {}: {e}", binary.display())))
+}
+
+fn accept_from(listener: &UnixListener, replacement: &mut Replacement) -> io::Result<UnixStream> {
+    let deadline = Instant::now() + HANDOFF_TIMEOUT;
+    loop {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                let peer = fd_passing::peer_pid(&stream)?;
+                if peer == replacement.pid() {
+                    stream.set_nonblocking(false)?;
+                    stream.set_read_timeout(Some(HANDOFF_TIMEOUT))?;
+                    stream.set_write_timeout(Some(HANDOFF_TIMEOUT))?;
+                    return Ok(stream);
+                }
+                // Someone else found the socket; keep waiting for ours.
+                tracing::warn!(peer, "Gateway handoff: rejected foreign connection");
+            }
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
+            Err(e) => return Err(e),
+        }
+        if let Some(status) = replacement.child.try_wait()? {
+            return Err(handoff_error(format!(
+                "replacement daemon exited during startup ({status})"
+            )));
+        }
+        if Instant::now() >= deadline {
+            return Err(handoff_error("replacement daemon never connected"));
+        }
+        std::thread::sleep(ACCEPT_POLL_INTERVAL);
+    }
+}
+
+/// Old-daemon end of the handoff stream.
+pub(crate) struct HandoffSender {
+    stream: UnixStream,
+}
+
+impl HandoffSender {
+    /// Send one channel. `master` must be `Some` exactly when the channel
+    /// carries a live session.
+    pub(crate) fn send_channel(
+        &self,
+        channel: HandoffChannel,
+        master: Option<&OwnedFd>,
+    ) -> io::Result<()> {
+        let fds: Vec<_> = master.iter().map(|fd| fd.as_raw_fd()).collect();
+        self.send_frame(&HandoffFrame::Channel(Box::new(channel)), &fds)
+    }
+
+    /// Close the stream and block until the replacement acknowledges that it
+    /// owns every channel sent so far.
+    pub(crate) fn finish(mut self, gateway: String, next_channel_seq: u64) -> io::Result<()> {
+        self.send_frame(
+            &HandoffFrame::Done {
+                gateway,
+                next_channel_seq,
+            },
+            &[],
+        )?;
+        let mut ack = [0u8; 1];
+        self.stream.read_exact(&mut ack)?;
+        if ack[0] != HANDOFF_ACK {
+            return Err(handoff_error("unexpected acknowledgement byte"));
+        }
+        Ok(())
+    }
+
+    fn send_frame(&self, frame: &HandoffFrame, fds: &[i32]) -> io::Result<()> {
+        let body = frame.encode();
+        let len = u32::try_from(body.len())
+            .ok()
+            .filter(|&n| n as usize <= MAX_HANDOFF_FRAME_BYTES)
+            .ok_or_else(|| handoff_error("frame too large"))?;
+        fd_passing::send_with_fds(&self.stream, &len.to_le_bytes(), fds)?;
+        (&self.stream).write_all(&body)
+    }
+}
+
+/// Everything the replacement daemon received, held until it acknowledges.
+pub(crate) struct ReceivedHandoff {
+    stream: UnixStream,
+    pub(crate) channels: Vec<(HandoffChannel, Option<OwnedFd>)>,
+    pub(crate) gateway: String,
+    pub(crate) next_channel_seq: u64,
+}
+
+impl ReceivedHandoff {
+    /// Tell the old daemon it may release its sessions and exit.
+    pub(crate) fn acknowledge(mut self) -> io::Result<()> {
+        self.stream.write_all(&[HANDOFF_ACK])
+    }
+}
+
+/// Replacement-daemon end: connect to the handoff socket named by
+/// [`HANDOFF_ENV_VAR`] and read every frame up to `Done`.
+pub(crate) fn receive_handoff(name: &str) -> io::Result<ReceivedHandoff> {
+    let stream = UnixStream::connect_addr(&SocketAddr::from_abstract_name(name.as_bytes())?)?;
+    if fd_passing::peer_pid(&stream)? != std::os::unix::process::parent_id() {
+        return Err(handoff_error("handoff socket is not served by our parent"));
+    }
+    stream.set_read_timeout(Some(HANDOFF_TIMEOUT))?;
+    stream.set_write_timeout(Some(HANDOFF_TIMEOUT))?;
+    let mut channels = Vec::new();
+    loop {
+        let (frame, mut fds) = recv_frame(&stream)?;
+        match frame {
+            HandoffFrame::Channel(channel) => {
+                let master = fds.pop();
+                if channel.session.is_some() != master.is_some() {
+                    return Err(handoff_error(format!(
+                        "channel '{}' arrived without its PTY master",
+                        channel.name
+                    )));
+                }
+                channels.push((*channel, master));
+            }
+            HandoffFrame::Done {
+                gateway,
+                next_channel_seq,
+            } => {
+                return Ok(ReceivedHandoff {
+                    stream,
+                    channels,
+                    gateway,
+                    next_channel_seq,
+                });
+            }
+        }
+    }
+}
+
+fn recv_frame(stream: &UnixStream) -> io::Result<(HandoffFrame, Vec<OwnedFd>)> {
+    let mut header = [0u8; 4];
+    let (n, fds) = fd_passing::recv_with_fds(stream, &mut header, 1)?;
+    if n == 0 {
+        return Err(handoff_error("stream closed before Done"));
+    }
+    let mut reader = stream;
+    reader.read_exact(&mut header[n..])?;
+    let len = u32::from_le_bytes(header) as usize;
+    if len > MAX_HANDOFF_FRAME_BYTES {
+        return Err(handoff_error("frame too large"));
+    }
+    let mut body = vec![0u8; len];
+    reader.read_exact(&mut body)?;
+    Ok((HandoffFrame::decode(&body)?, fds))
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use term_session_muxio_service_definitions::RestartPolicy;
+
+    #[test]
+    fn frames_carry_their_descriptor() {
+        let (a, b) = UnixStream::pair().unwrap();
+        let sender = HandoffSender { stream: a };
+        let file = tempfile::tempfile().unwrap();
+        let fd = OwnedFd::from(file);
+        let channel = HandoffChannel {
+            name: "dev/main".into(),
+            cmd: Vec::new(),
+            cwd: None,
+            env: Vec::new(),
+            created_at_unix: 0,
+            created_seq: 3,
+            output_cache: Vec::new(),
+            session: None,
+            restart_policy: RestartPolicy::Never,
+            restart_count: 0,
+            restart_streak: 0,
+            last_exit_code: None,
+            last_exit_unix: None,
+            restart_pending: false,
+        };
+        sender.send_channel(channel.clone(), Some(&fd)).unwrap();
+        let (frame, fds) = recv_frame(&b).unwrap();
+        assert_eq!(frame, HandoffFrame::Channel(Box::new(channel)));
+        assert_eq!(fds.len(), 1);
+    }
+
+    #[test]
+    fn finish_waits_for_the_acknowledgement() {
+        let (a, b) = UnixStream::pair().unwrap();
+        let sender = HandoffSender { stream: a };
+        let peer = std::thread::spawn(move || {
+            let (frame, _) = recv_frame(&b).unwrap();
+            (&b).write_all(&[HANDOFF_ACK]).unwrap();
+            frame
+        });
+        sender.finish("term-wm/gw".into(), 9).unwrap();
+        assert_eq!(
+            peer.join().unwrap(),
+            HandoffFrame::Done {
+                gateway: "term-wm/gw".into(),
+                next_channel_seq: 9,
+            }
+        );
+    }
+
+    #[test]
+    fn default_binary_strips_the_deleted_marker() {
+        let exe = default_binary().unwrap();
+        assert!(!exe.to_string_lossy().ends_with(DELETED_EXE_SUFFIX));
+    }
+}
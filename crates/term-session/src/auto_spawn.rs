@@ -3,6 +3,7 @@ use std::io;
 use std::thread;
 use std::time::{Duration, Instant};
 
+use term_session_client::transport::{probe_remote, remote_gateway};
 use term_session_muxio_service_definitions::{
     ChannelName, gateway_channel_name, probe_ipc_endpoint,
 };
@@ -17,6 +18,15 @@ pub fn resolve_gateway() -> ChannelName {
     gateway_channel_name()
 }
 
+/// Whether the gateway answers: the `--connect` remote one if set, else the
+/// local socket.
+fn probe_gateway(gateway: &ChannelName) -> bool {
+    match remote_gateway() {
+        Some(remote) => probe_remote(remote),
+        None => probe_ipc_endpoint(gateway),
+    }
+}
+
 /// Handle to a just-spawned daemon process, used to poll for early death during
 /// the gateway startup handshake.
 ///
@@ -262,6 +272,29 @@ fn windows_spawn_detached_server(bin: &std::path::Path) -> io::Result<DaemonChil
     }))
 }
 
+/// How long a viewer waits for the replacement daemon after a gateway upgrade.
+pub const GATEWAY_UPGRADE_RECONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Wait for a gateway that is already starting to become reachable, without
+/// ever spawning one. Used to reconnect after a gateway upgrade: spawning a
+/// fresh daemon in the gap would take the socket from the replacement that
+/// holds the live sessions.
+pub fn wait_for_gateway(timeout: Duration) -> io::Result<String> {
+    let gateway = resolve_gateway();
+    let start = Instant::now();
+    let poll_interval = Duration::from_millis(50);
+    while start.elapsed() < timeout {
+        if probe_gateway(&gateway) {
+            return Ok(gateway.to_string());
+        }
+        thread::sleep(poll_interval);
+    }
+    Err(io::Error::new(
+        io::ErrorKind::TimedOut,
+        format!("Timed out waiting for the upgraded gateway on channel '{gateway}'"),
+    ))
+}
+
 /// Wait for the gateway to become reachable, spawning a detached daemon if
 /// none is running.
 ///
@@ -272,7 +305,8 @@ pub fn connect_or_spawn_server(bin: Option<&std::path::Path>) -> io::Result<Stri
     let gateway = resolve_gateway();
     let socket_name = gateway.to_string();
 
-    if probe_ipc_endpoint(&gateway) {
+    // A remote gateway is never spawned here; connecting reports it down.
+    if remote_gateway().is_some() || probe_ipc_endpoint(&gateway) {
         return Ok(socket_name);
     }
 
@@ -0,0 +1,188 @@
+//! `term-session control`: a line-oriented JSON front end for control mode.
+//!
+//! Events the window manager publishes on the channel are printed to stdout,
+//! one JSON object per line; each stdin line is parsed as a
+//! [`ControlCommand`] and relayed to the window manager. Lines that fail to
+//! parse, and commands the gateway refuses, come back as `error` events in
+//! the same stream so a script only has to read stdout.
+
+use std::io::{self, BufRead, Write};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use muxio_rpc_service_endpoint::RpcServiceEndpointInterface;
+use muxio_tokio_rpc_ipc_client::{RpcCallPrebuffered, RpcServiceCallerInterface};
+use term_session_client::GatewayClient;
+use term_session_muxio_service_definitions::{
+    ControlCommand, ControlEvent, OnControlEvents, OnGatewayUpgrade, RpcMethodPrebuffered,
+    SendControlCommand, SendControlCommandRequest, SubscribeControl, SubscribeControlRequest,
+};
+use tokio::sync::mpsc;
+
+use crate::auto_spawn::{GATEWAY_UPGRADE_RECONNECT_TIMEOUT, wait_for_gateway};
+
+/// How often the command loop checks that the gateway link is still up.
+const LINK_CHECK_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Run control mode for `channel` until stdin closes or the gateway goes
+/// away. Survives `term-session upgrade` by resubscribing on the
+/// replacement daemon.
+pub fn run_control(channel: &str) -> io::Result<()> {
+    let (line_tx, mut line_rx) = mpsc::unbounded_channel::<String>();
+    std::thread::Builder::new()
+        .name("control-stdin".into())
+        .spawn(move || {
+            for line in io::stdin().lock().lines() {
+                let Ok(line) = line else { break };
+                if line_tx.send(line).is_err() {
+                    break;
+                }
+            }
+        })?;
+
+    let rt =
+        tokio::runtime::Runtime::new().map_err(|e| io::Error::other(format!("runtime: {e}")))?;
+    rt.block_on(async {
+        let mut socket = term_session_muxio_service_definitions::gateway_channel_name().to_string();
+        loop {
+            let client = GatewayClient::connect(&socket).await.map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::ConnectionRefused,
+                    format!("No gateway daemon is running on '{socket}'.\n  cause: {e}"),
+                )
+            })?;
+            let upgraded = register_handlers(&client).await?;
+            SubscribeControl::call(
+                &*client,
+                SubscribeControlRequest {
+                    channel: channel.to_string(),
+                },
+            )
+            .await
+            .map_err(|e| io::Error::other(format!("control: {e}")))?;
+
+            if !relay_commands(&client, channel, &mut line_rx).await {
+                return Ok(());
+            }
+            if !upgraded.load(Ordering::Relaxed) {
+                return Err(io::Error::new(
+                    io::ErrorKind::ConnectionReset,
+                    "gateway disconnected",
+                ));
+            }
+            socket =
+                tokio::task::spawn_blocking(|| wait_for_gateway(GATEWAY_UPGRADE_RECONNECT_TIMEOUT))
+                    .await
+                    .map_err(|e| io::Error::other(format!("reconnect: {e}")))??;
+        }
+    })
+}
+
+/// Register the event printer and the upgrade notice on `client`. The
+/// returned flag is set once the gateway announces its replacement.
+async fn register_handlers(client: &GatewayClient) -> io::Result<Arc<AtomicBool>> {
+    client
+        .get_endpoint()
+        .register_prebuffered(OnControlEvents::METHOD_ID, |payload, _ctx| async move {
+            let events = OnControlEvents::decode_request(&payload)
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+            print_events(&events);
+            OnControlEvents::encode_response(())
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+        })
+        .await
+        .map_err(|e| io::Error::other(format!("register OnControlEvents: {e:?}")))?;
+
+    let upgraded = Arc::new(AtomicBool::new(false));
+    let upgraded_ref = Arc::clone(&upgraded);
+    client
+        .get_endpoint()
+        .register_prebuffered(OnGatewayUpgrade::METHOD_ID, move |_payload, _ctx| {
+            let upgraded_ref = Arc::clone(&upgraded_ref);
+            async move {
+                upgraded_ref.store(true, Ordering::Relaxed);
+                OnGatewayUpgrade::encode_response(())
+                    .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+            }
+        })
+        .await
+        .map_err(|e| io::Error::other(format!("register OnGatewayUpgrade: {e:?}")))?;
+    Ok(upgraded)
+}
+
+/// Forward stdin commands until stdin closes (`false`) or the link drops
+/// (`true`).
+async fn relay_commands(
+    client: &GatewayClient,
+    channel: &str,
+    lines: &mut mpsc::UnboundedReceiver<String>,
+) -> bool {
+    while client.is_connected() {
+        let line = tokio::select! {
+            line = lines.recv() => line,
+            _ = tokio::time::sleep(LINK_CHECK_INTERVAL) => continue,
+        };
+        let Some(line) = line else {
+            return false;
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let command = match parse_command(&line) {
+            Ok(command) => command,
+            Err(event) => {
+                print_events(&[event]);
+                continue;
+            }
+        };
+        let request = SendControlCommandRequest {
+            channel: channel.to_string(),
+            command,
+        };
+        if let Err(e) = SendControlCommand::call(client, request).await {
+            print_events(&[ControlEvent::Error {
+                message: e.to_string(),
+            }]);
+        }
+    }
+    true
+}
+
+/// Parse one stdin line, mapping a bad line to the `error` event reported
+/// for it.
+fn parse_command(line: &str) -> Result<ControlCommand, ControlEvent> {
+    serde_json::from_str(line).map_err(|e| ControlEvent::Error {
+        message: format!("invalid command: {e}"),
+    })
+}
+
+fn print_events(events: &[ControlEvent]) {
+    let mut out = io::stdout().lock();
+    for event in events {
+        if let Ok(json) = serde_json::to_string(event) {
+            let _ = writeln!(out, "{json}");
+        }
+    }
+    let _ = out.flush();
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_command_reports_bad_lines_as_error_events() {
+        assert_eq!(
+            parse_command(r#"{"command":"focus","window":2}"#).unwrap(),
+            ControlCommand::Focus { window: 2 }
+        );
+        match parse_command("focus 2") {
+            Err(ControlEvent::Error { message }) => {
+                assert!(message.starts_with("invalid command"), "{message}");
+            }
+            other => panic!("expected an error event, got {other:?}"),
+        }
+    }
+}
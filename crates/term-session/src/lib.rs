@@ -1,4 +1,6 @@
 pub mod auto_spawn;
+pub mod control;
+pub mod remote;
 
 pub use muxio_tokio_rpc_ipc_client as rpc_client;
 pub use term_session_client as client;
@@ -13,7 +15,10 @@ use std::sync::Arc;
 
 use muxio_tokio_rpc_ipc_client::RpcCallPrebuffered;
 use term_session_muxio_service_definitions::{
-    KillChannel, KillClient, ListChannels, ListChannelsResponse, ShutdownGateway,
+    CloneChannel, CloneChannelRequest, KillChannel, KillClient, ListChannels, ListChannelsResponse,
+    PathWire, RenameChannel, RenameChannelRequest, RestartPolicy, SetChannelEnv,
+    SetChannelEnvRequest, SetRestartPolicy, SetRestartPolicyRequest, ShutdownGateway,
+    UpgradeGateway, UpgradeGatewayRequest, UpgradeGatewayResponse,
 };
 
 /// Run a CLI entry point and report any error identically across every
@@ -118,7 +123,7 @@ pub fn format_unix_relative_at(ts: u64, now: u64) -> String {
 /// panic).
 pub fn with_gateway<F, Fut, T>(op: F) -> io::Result<T>
 where
-    F: FnOnce(Arc<muxio_tokio_rpc_ipc_client::RpcIpcClient>) -> Fut + Send + 'static,
+    F: FnOnce(Arc<term_session_client::GatewayClient>) -> Fut + Send + 'static,
     Fut: std::future::Future<Output = T> + Send + 'static,
     T: Send + 'static,
 {
@@ -127,7 +132,7 @@ where
         let rt =
             tokio::runtime::Runtime::new().map_err(|e| io::Error::other(format!("runtime: {e}")))?;
         rt.block_on(async {
-            let client = muxio_tokio_rpc_ipc_client::RpcIpcClient::new(&gateway.to_string())
+            let client = term_session_client::GatewayClient::connect(&gateway.to_string())
                 .await
                 .map_err(|e| {
                     io::Error::new(
@@ -167,16 +172,35 @@ pub fn print_list() -> io::Result<()> {
     // Vertical list: one block per channel, one line per client. Kept short so
     // it wraps cleanly instead of being a wide table.
     for ch in &resp.channels {
-        let session = ch
-            .session
-            .as_ref()
-            .map(|s| format!("shared size: {}x{}", s.cols, s.rows))
-            .unwrap_or_else(|| "none".to_string());
+        let session = match &ch.session {
+            Some(s) => format!("shared size: {}x{}", s.cols, s.rows),
+            None if ch.restart_pending => "none (restart pending)".to_string(),
+            None => "none".to_string(),
+        };
         let nclients = ch.clients.len();
         println!();
         println!("channel: {}", ch.name);
         println!("  created: {}", format_unix_relative(ch.created_at_unix));
         println!("  {session}");
+        if ch.restart_policy != RestartPolicy::Never {
+            println!("  restart: {}", ch.restart_policy);
+        }
+        if let Some(s) = &ch.session
+            && let Some(exited) = s.last_exit_unix
+        {
+            let code = s
+                .last_exit_code
+                .map_or_else(|| "unknown".to_string(), |c| c.to_string());
+            println!(
+                "  restarts: {}, last exit: code {code}, {} ago",
+                s.restart_count,
+                format_unix_relative(exited)
+            );
+        }
+        if !ch.env.is_empty() {
+            let env: Vec<String> = ch.env.iter().map(|(k, v)| format!("{k}={v}")).collect();
+            println!("  env: {}", env.join(" "));
+        }
         println!(
             "  clients: {}",
             if nclients == 0 {
@@ -220,6 +244,54 @@ pub fn kill_client(channel: &str, conn_id: usize) -> io::Result<()> {
         .map_err(|e| io::Error::other(format!("kill client: {e}")))
 }
 
+/// Rename a channel. Its session, attached clients and control subscribers
+/// move with it; fails if `to` is already taken.
+pub fn rename_channel(from: &str, to: &str) -> io::Result<()> {
+    let request = RenameChannelRequest {
+        from: from.to_string(),
+        to: to.to_string(),
+    };
+    with_gateway(move |client| async move { RenameChannel::call(&*client, request).await })?
+        .map_err(|e| io::Error::other(format!("rename channel: {e}")))
+}
+
+/// Start a new channel `target` running `source`'s command in its working
+/// directory, with its environment.
+pub fn clone_channel(source: &str, target: &str) -> io::Result<()> {
+    let request = CloneChannelRequest {
+        source: source.to_string(),
+        target: target.to_string(),
+    };
+    with_gateway(move |client| async move { CloneChannel::call(&*client, request).await })?
+        .map_err(|e| io::Error::other(format!("clone channel: {e}")))
+}
+
+/// Set and unset variables in a channel's environment, applied from the
+/// next (re)spawn of its session on.
+pub fn set_channel_env(
+    channel: &str,
+    set: Vec<(String, String)>,
+    unset: Vec<String>,
+) -> io::Result<()> {
+    let request = SetChannelEnvRequest {
+        channel: channel.to_string(),
+        set,
+        unset,
+    };
+    with_gateway(move |client| async move { SetChannelEnv::call(&*client, request).await })?
+        .map_err(|e| io::Error::other(format!("set channel env: {e}")))
+}
+
+/// Set what the gateway does when `channel`'s session exits on its own.
+pub fn set_restart_policy(channel: &str, policy: RestartPolicy) -> io::Result<()> {
+    let request = SetRestartPolicyRequest {
+        channel: channel.to_string(),
+        policy,
+    };
+    with_gateway(move |client| async move { SetRestartPolicy::call(&*client, request).await })?
+        .map_err(|e| io::Error::other(format!("set restart policy: {e}")))
+}
+
 /// Request the gateway to rebind all viewers attached to `source_channel`
 /// over to the `target` workspace.
 pub fn request_workspace_rebind(source_channel: &str, target: &str) -> io::Result<()> {
@@ -250,6 +322,17 @@ pub fn stop_gateway(force: bool) -> io::Result<()> {
         .map_err(|e| io::Error::other(format!("shutdown: {e}")))
 }
 
+/// Replace the running gateway daemon with `binary --daemon` (the daemon's
+/// own executable when `None`) without ending any session. Linux only; other
+/// platforms get `RPC_ERROR_UPGRADE_UNSUPPORTED`.
+pub fn upgrade_gateway(binary: Option<std::path::PathBuf>) -> io::Result<UpgradeGatewayResponse> {
+    let request = UpgradeGatewayRequest {
+        binary: binary.map(PathWire::encode),
+    };
+    with_gateway(move |client| async move { UpgradeGateway::call(&*client, request).await })?
+        .map_err(|e| io::Error::other(format!("upgrade: {e}")))
+}
+
 /// Run the gateway daemon: rename the process, detach from the controlling
 /// terminal, and serve until `ShutdownGateway`. `selfcheck_marker` is a
 /// test-only path written with the platform's detachment proof once bound.
@@ -1,9 +1,14 @@
 use std::io;
 
 use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
-use term_session::auto_spawn::connect_or_spawn_server;
-use term_session_client::run_session;
-use term_session_muxio_service_definitions::ChannelName;
+use term_session::auto_spawn::{
+    GATEWAY_UPGRADE_RECONNECT_TIMEOUT, connect_or_spawn_server, wait_for_gateway,
+};
+use term_session_client::{is_gateway_upgrade, run_session};
+use term_session_muxio_service_definitions::{ChannelName, RestartPolicy};
+
+/// Retry budget for `on-failure` when the policy names none.
+const DEFAULT_MAX_RETRIES: u32 = 5;
 
 /// A marker file the daemon writes after successfully detaching, used by the
 /// test-only `--daemon-selfcheck` path.
@@ -50,6 +55,22 @@ struct Cli {
     /// (bypass the nesting-inception guard).
     #[arg(long)]
     allow_nested: bool,
+
+    /// Use the gateway on another machine, listening at HOST:PORT (see
+    /// `--listen`), instead of the local one.
+    #[arg(long, value_name = "HOST:PORT", conflicts_with_all = ["daemon", "listen"])]
+    connect: Option<String>,
+
+    /// Key file holding the remote token for `--connect` [default:
+    /// $TERM_SESSION_TOKEN, then $XDG_RUNTIME_DIR/term-wm/<env>/remote.key]
+    #[arg(long, value_name = "PATH", requires = "connect")]
+    token_file: Option<std::path::PathBuf>,
+
+    /// Also accept remote clients on this TCP address when this invocation
+    /// starts the gateway [or $TERM_SESSION_LISTEN]. The token is
+    /// $TERM_SESSION_TOKEN, else a key file created under $XDG_RUNTIME_DIR.
+    #[arg(long, value_name = "ADDR")]
+    listen: Option<String>,
 }
 
 #[derive(Subcommand, Debug)]
@@ -77,6 +98,62 @@ enum Command {
         /// The client's conn id.
         client_id: usize,
     },
+    /// Replace the gateway daemon with a new binary, keeping every session
+    /// running (Linux). Attached clients reconnect on their own.
+    #[command(name = "upgrade")]
+    Upgrade {
+        /// Daemon binary to start [default: this executable].
+        #[arg(long, value_name = "PATH")]
+        binary: Option<std::path::PathBuf>,
+    },
+    /// Rename a channel, keeping its session and attached clients.
+    #[command(name = "rename")]
+    Rename {
+        /// Current channel name.
+        from: String,
+        /// New channel name (must not exist).
+        to: String,
+    },
+    /// Start a new channel running a copy of another's command, working
+    /// directory and environment.
+    #[command(name = "clone")]
+    Clone {
+        /// Channel to copy.
+        source: String,
+        /// Name of the new channel (must not exist).
+        target: String,
+    },
+    /// Set or unset environment variables for every future (re)spawn of a
+    /// channel's session. `list` shows the current values.
+    #[command(name = "env")]
+    Env {
+        /// Channel name.
+        channel: String,
+        /// Variables to set.
+        #[arg(value_name = "NAME=VALUE", value_parser = parse_env_assignment)]
+        set: Vec<(String, String)>,
+        /// Variable to remove; repeatable.
+        #[arg(long, value_name = "NAME")]
+        unset: Vec<String>,
+    },
+    /// Choose what happens when a channel's session exits on its own:
+    /// `never`, `always`, or `on-failure[:MAX_RETRIES]` (default 5 retries).
+    /// Restarts back off exponentially; `ls` shows the count and last exit.
+    #[command(name = "restart-policy")]
+    RestartPolicy {
+        /// Channel name.
+        channel: String,
+        /// The policy.
+        #[arg(value_name = "POLICY", value_parser = parse_restart_policy)]
+        policy: RestartPolicy,
+    },
+    /// Stream a channel's window-manager events as JSON lines on stdout and
+    /// send it JSON commands read from stdin (needs `term-wm` in the channel).
+    #[command(name = "control")]
+    Control {
+        /// Channel name, e.g. `default/main`.
+        channel: String,
+    },
     /// Stop the gateway daemon.
     #[command(name = "stop")]
     Stop {
@@ -117,10 +194,28 @@ fn run() -> io::Result<()> {
         }
     }
 
+    // Only read by a daemon this invocation starts (directly or through
+    // auto-spawn, which inherits the environment).
+    if let Some(ref addr) = cli.listen {
+        unsafe {
+            std::env::set_var(term_wm_config::env::REMOTE_LISTEN_ENV_VAR, addr);
+        }
+    }
+
     if cli.daemon {
         return term_session::run_daemon(cli.daemon_selfcheck);
     }
 
+    // Remote gateway: every command below connects to it over TCP, and must
+    // never fall back to spawning a local daemon.
+    let remote = cli.connect.is_some();
+    if let Some(ref addr) = cli.connect {
+        let token = term_session_muxio_service_definitions::remote::client_token(
+            cli.token_file.as_deref(),
+        )?;
+        term_session::remote::connect_remote(addr, token)?;
+    }
+
     match cli.command {
         Some(Command::List) => term_session::print_list(),
         Some(Command::Kill {
@@ -133,6 +228,32 @@ fn run() -> io::Result<()> {
             println!("Detached client {client_id} from channel {channel}.");
             Ok(())
         }
+        Some(Command::Upgrade { binary }) => upgrade(binary, remote),
+        Some(Command::Rename { from, to }) => {
+            term_session::rename_channel(&from, &to)?;
+            println!("Renamed channel {from} to {to}.");
+            Ok(())
+        }
+        Some(Command::Clone { source, target }) => {
+            term_session::clone_channel(&source, &target)?;
+            println!("Cloned channel {source} into {target}.");
+            Ok(())
+        }
+        Some(Command::Env {
+            channel,
+            set,
+            unset,
+        }) => {
+            term_session::set_channel_env(&channel, set, unset)?;
+            println!("Updated environment of channel {channel}.");
+            Ok(())
+        }
+        Some(Command::RestartPolicy { channel, policy }) => {
+            term_session::set_restart_policy(&channel, policy)?;
+            println!("Restart policy of channel {channel}: {policy}.");
+            Ok(())
+        }
+        Some(Command::Control { channel }) => control(&channel),
         Some(Command::Stop { force }) => stop(force),
         None => {
             if cli.channel.is_some() || !cli.cmd.is_empty() {
@@ -159,15 +280,24 @@ fn attach(channel: Option<String>, cmd: &[String], allow_nested: bool) -> io::Re
     })?;
     // The argv comes straight from the outer shell (split exactly once);
     // the server spawns it directly, no shell involved there.
-    let socket_name = connect_or_spawn_server(None)?;
-    run_session(
-        &socket_name,
-        &channel.to_string(),
-        cmd,
-        allow_nested,
-        "term-session",
-    )
-    .map(|_| ())
+    let mut socket_name = connect_or_spawn_server(None)?;
+    loop {
+        match run_session(
+            &socket_name,
+            &channel.to_string(),
+            cmd,
+            allow_nested,
+            "term-session",
+            None,
+        ) {
+            // The daemon was replaced underneath us; the session lives on in
+            // the new one.
+            Err(e) if is_gateway_upgrade(&e) => {
+                socket_name = wait_for_gateway(GATEWAY_UPGRADE_RECONNECT_TIMEOUT)?;
+            }
+            result => return result.map(|_| ()),
+        }
+    }
 }
 
 fn kill(channel: &str, force: bool) -> io::Result<()> {
@@ -176,6 +306,56 @@ fn kill(channel: &str, force: bool) -> io::Result<()> {
     Ok(())
 }
 
+fn upgrade(binary: Option<std::path::PathBuf>, remote: bool) -> io::Result<()> {
+    // Default to this executable: running the freshly installed CLI is how
+    // an operator names the new build. A remote daemon cannot run a path from
+    // this machine, so there it defaults to its own executable.
+    let binary = match binary {
+        Some(b) => Some(b),
+        None if remote => None,
+        None => Some(std::env::current_exe()?),
+    };
+    let resp = term_session::upgrade_gateway(binary)?;
+    println!(
+        "Gateway upgraded: {} channel(s) handed to PID {}.",
+        resp.channels, resp.new_pid
+    );
+    Ok(())
+}
+
+/// Parse a `NAME=VALUE` argument of `term-session env`.
+fn parse_env_assignment(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((name, value)) if !name.is_empty() => Ok((name.to_string(), value.to_string())),
+        _ => Err(format!("expected NAME=VALUE, got '{arg}'")),
+    }
+}
+
+/// Parse the `POLICY` argument of `term-session restart-policy`.
+fn parse_restart_policy(arg: &str) -> Result<RestartPolicy, String> {
+    match arg.split_once(':') {
+        None if arg == "never" => Ok(RestartPolicy::Never),
+        None if arg == "always" => Ok(RestartPolicy::Always),
+        None if arg == "on-failure" => Ok(RestartPolicy::OnFailure {
+            max_retries: DEFAULT_MAX_RETRIES,
+        }),
+        Some(("on-failure", n)) => n
+            .parse()
+            .map(|max_retries| RestartPolicy::OnFailure { max_retries })
+            .map_err(|_| format!("invalid retry count '{n}'")),
+        _ => Err(format!(
+            "expected never, always or on-failure[:MAX_RETRIES], got '{arg}'"
+        )),
+    }
+}
+
+fn control(channel: &str) -> io::Result<()> {
+    let channel = ChannelName::parse(channel).map_err(|e| {
+        io::Error::new(io::ErrorKind::InvalidInput, format!("Invalid channel: {e}"))
+    })?;
+    term_session::control::run_control(&channel.to_string())
+}
+
 fn stop(force: bool) -> io::Result<()> {
     term_session::stop_gateway(force)?;
     println!("Gateway shutdown initiated.");
@@ -265,6 +445,141 @@ mod tests {
         assert!(matches!(cli.command, Some(Command::Stop { force: true })));
     }
 
+    #[test]
+    fn cli_parses_rename_and_clone_subcommands() {
+        let cli = Cli::try_parse_from(["term-session", "rename", "dev/main", "ops/main"]).unwrap();
+        match cli.command {
+            Some(Command::Rename { from, to }) => {
+                assert_eq!((from.as_str(), to.as_str()), ("dev/main", "ops/main"));
+            }
+            _ => panic!("expected Rename subcommand"),
+        }
+        let cli = Cli::try_parse_from(["term-session", "clone", "dev/main", "dev/copy"]).unwrap();
+        match cli.command {
+            Some(Command::Clone { source, target }) => {
+                assert_eq!((source.as_str(), target.as_str()), ("dev/main", "dev/copy"));
+            }
+            _ => panic!("expected Clone subcommand"),
+        }
+        assert!(Cli::try_parse_from(["term-session", "rename", "dev/main"]).is_err());
+    }
+
+    #[test]
+    fn cli_parses_env_subcommand() {
+        let cli = Cli::try_parse_from([
+            "term-session",
+            "env",
+            "dev/main",
+            "EDITOR=vim",
+            "GREETING=a=b",
+            "--unset",
+            "PAGER",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Command::Env {
+                channel,
+                set,
+                unset,
+            }) => {
+                assert_eq!(channel, "dev/main");
+                assert_eq!(
+                    set,
+                    vec![
+                        ("EDITOR".to_string(), "vim".to_string()),
+                        ("GREETING".to_string(), "a=b".to_string()),
+                    ]
+                );
+                assert_eq!(unset, vec!["PAGER".to_string()]);
+            }
+            _ => panic!("expected Env subcommand"),
+        }
+        assert!(Cli::try_parse_from(["term-session", "env", "dev/main", "EDITOR"]).is_err());
+    }
+
+    #[test]
+    fn cli_parses_restart_policy_subcommand() {
+        let parse = |policy: &str| -> Result<RestartPolicy, clap::Error> {
+            let cli =
+                Cli::try_parse_from(["term-session", "restart-policy", "dev/server", policy])?;
+            match cli.command {
+                Some(Command::RestartPolicy { policy, .. }) => Ok(policy),
+                _ => panic!("expected RestartPolicy subcommand"),
+            }
+        };
+        assert_eq!(parse("never").unwrap(), RestartPolicy::Never);
+        assert_eq!(parse("always").unwrap(), RestartPolicy::Always);
+        assert_eq!(
+            parse("on-failure").unwrap(),
+            RestartPolicy::OnFailure {
+                max_retries: DEFAULT_MAX_RETRIES
+            }
+        );
+        assert_eq!(
+            parse("on-failure:2").unwrap(),
+            RestartPolicy::OnFailure { max_retries: 2 }
+        );
+        assert!(parse("on-failure:x").is_err());
+        assert!(parse("sometimes").is_err());
+    }
+
+    #[test]
+    fn cli_parses_control_subcommand() {
+        let cli = Cli::try_parse_from(["term-session", "control", "dev/main"]).unwrap();
+        match cli.command {
+            Some(Command::Control { channel }) => assert_eq!(channel, "dev/main"),
+            _ => panic!("expected Control subcommand"),
+        }
+        assert!(Cli::try_parse_from(["term-session", "control"]).is_err());
+    }
+
+    #[test]
+    fn cli_parses_upgrade_subcommand() {
+        let cli = Cli::try_parse_from(["term-session", "upgrade"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Upgrade { binary: None })
+        ));
+        let cli = Cli::try_parse_from(["term-session", "upgrade", "--binary", "/opt/term-session"])
+            .unwrap();
+        match cli.command {
+            Some(Command::Upgrade { binary }) => {
+                assert_eq!(
+                    binary.as_deref(),
+                    Some(std::path::Path::new("/opt/term-session"))
+                );
+            }
+            _ => panic!("expected Upgrade subcommand"),
+        }
+    }
+
+    #[test]
+    fn cli_parses_remote_flags() {
+        let cli = Cli::try_parse_from([
+            "term-session",
+            "--connect",
+            "build-box:7722",
+            "--token-file",
+            "/tmp/remote.key",
+            "ls",
+        ])
+        .unwrap();
+        assert_eq!(cli.connect.as_deref(), Some("build-box:7722"));
+        assert_eq!(
+            cli.token_file.as_deref(),
+            Some(std::path::Path::new("/tmp/remote.key"))
+        );
+        assert!(matches!(cli.command, Some(Command::List)));
+
+        let cli = Cli::try_parse_from(["term-session", "--listen", "0.0.0.0:7722"]).unwrap();
+        assert_eq!(cli.listen.as_deref(), Some("0.0.0.0:7722"));
+
+        // A daemon always serves locally; --connect makes no sense for it,
+        // and a token file without --connect is a mistake.
+        assert!(Cli::try_parse_from(["term-session", "--daemon", "--connect", "h:1"]).is_err());
+        assert!(Cli::try_parse_from(["term-session", "--token-file", "/tmp/k", "ls"]).is_err());
+    }
+
     #[test]
     fn cli_parses_daemon_channel_and_positional_command() {
         let cli = Cli::try_parse_from(["term-session", "--daemon"]).unwrap();
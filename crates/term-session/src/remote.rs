@@ -0,0 +1,30 @@
+//! `term-session --connect`: talk to a gateway on another machine.
+//!
+//! The remote gateway is registered with the session client's transport, so
+//! every command — attach, `ls`, `kill`, `stop` — builds its RPC client
+//! directly on an authenticated TCP connection to it (see
+//! `term-session-server`'s remote module) instead of the local socket.
+
+use std::io;
+
+use term_session_client::GatewayClient;
+use term_session_client::transport::{RemoteGateway, set_remote_gateway};
+use term_session_muxio_service_definitions::remote::RemoteToken;
+
+/// Route this process's gateway connections to `addr` (`host:port`).
+///
+/// The token is checked once up front so a wrong token or an unreachable host
+/// fails here with a clear error rather than on the first RPC.
+pub fn connect_remote(addr: &str, token: RemoteToken) -> io::Result<()> {
+    let remote = RemoteGateway {
+        addr: addr.to_string(),
+        token,
+    };
+    let rt =
+        tokio::runtime::Runtime::new().map_err(|e| io::Error::other(format!("runtime: {e}")))?;
+    rt.block_on(async {
+        GatewayClient::connect_remote(&remote).await?;
+        Ok::<_, io::Error>(())
+    })?;
+    set_remote_gateway(remote)
+}
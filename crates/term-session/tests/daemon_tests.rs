@@ -1168,3 +1168,172 @@ fn attach_cross_gateway_proceeds_without_allow_nested() {
         "must NOT refuse cross-gateway nesting, got stderr: {stderr}"
     );
 }
+
+/// `term-session upgrade` replaces the daemon process while the session's
+/// child keeps running: the replacement serves the same gateway, lists the
+/// same channel, and its session is still alive.
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn upgrade_hands_live_session_to_new_daemon() {
+    let gateway = unique_gateway("upgrade");
+    let channel = "test/upgrade";
+    let (mut child, _marker) = spawn_daemon(&gateway, false);
+
+    let client = wait_connectable(&gateway).await;
+    attach_to(&client, channel, "t").await;
+    Spawn::call(
+        &*client,
+        SpawnRequest {
+            cmd: Some(vec![
+                mock_bin().to_string_lossy().to_string(),
+                "sleep".into(),
+                "60000".into(),
+            ]),
+            cols: 80u16,
+            rows: 24u16,
+            cwd: None,
+        },
+    )
+    .await
+    .unwrap();
+    let old_pid = ListChannels::call(&*client, ()).await.unwrap().gateway_pid;
+    drop(client);
+
+    let out = Command::new(bin())
+        .env("TERM_WM_GATEWAY", &gateway)
+        .args(["upgrade", "--binary"])
+        .arg(bin())
+        .output()
+        .expect("run upgrade");
+    assert!(
+        out.status.success(),
+        "upgrade failed: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("1 channel(s)"), "stdout: {stdout}");
+
+    // The old daemon exits on its own once the handoff commits.
+    let _ = child.wait();
+
+    let client = wait_connectable(&gateway).await;
+    let resp = ListChannels::call(&*client, ()).await.unwrap();
+    assert_ne!(
+        resp.gateway_pid, old_pid,
+        "a new daemon must serve the gateway"
+    );
+    let ch = resp
+        .channels
+        .iter()
+        .find(|c| c.name == channel)
+        .expect("channel survives the upgrade");
+    let session = ch.session.as_ref().expect("session survives the upgrade");
+    assert!(!session.exited, "the session child must keep running");
+
+    ShutdownGateway::call(&*client, true).await.unwrap();
+}
+
+/// Spawn the daemon with remote access on a free loopback port. Returns the
+/// child and the `host:port` it listens on.
+fn spawn_remote_daemon(gateway: &str, token: &str) -> (Child, String) {
+    let addr = std::net::TcpListener::bind("127.0.0.1:0")
+        .and_then(|l| l.local_addr())
+        .expect("free port")
+        .to_string();
+    let child = Command::new(bin())
+        .env("TERM_WM_GATEWAY", gateway)
+        .env("TERM_SESSION_LISTEN", &addr)
+        .env("TERM_SESSION_TOKEN", token)
+        .arg("--daemon")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("spawn daemon");
+    (child, addr)
+}
+
+/// Poll until the remote listener accepts TCP connections.
+async fn wait_listening(addr: &str) {
+    let start = Instant::now();
+    while tokio::net::TcpStream::connect(addr).await.is_err() {
+        assert!(
+            start.elapsed() < Duration::from_secs(20),
+            "remote listener {addr} never came up"
+        );
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+}
+
+/// `term-session --connect` lists a gateway it can only reach over TCP: the
+/// client's own gateway name points nowhere, so the answer must come from
+/// the remote daemon's RPC service, served over the authenticated TCP
+/// stream.
+#[tokio::test]
+async fn remote_client_reaches_gateway_over_loopback() {
+    let gateway = unique_gateway("remote");
+    let (_child, addr) = spawn_remote_daemon(&gateway, "loopback-token");
+    let client = wait_connectable(&gateway).await;
+    wait_listening(&addr).await;
+
+    let out = Command::new(bin())
+        .env("TERM_WM_GATEWAY", unique_gateway("remote-unused"))
+        .env("TERM_SESSION_TOKEN", "loopback-token")
+        .args(["--connect", &addr, "ls"])
+        .output()
+        .expect("run ls over --connect");
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(
+        out.status.success(),
+        "ls failed: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert!(
+        stdout.contains(&format!("Socket: {gateway}")),
+        "stdout: {stdout}"
+    );
+
+    let out = Command::new(bin())
+        .env("TERM_WM_GATEWAY", unique_gateway("remote-unused"))
+        .env("TERM_SESSION_TOKEN", "wrong-token")
+        .args(["--connect", &addr, "ls"])
+        .output()
+        .expect("run ls with a wrong token");
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("rejected the token"), "stderr: {stderr}");
+
+    ShutdownGateway::call(&*client, true).await.unwrap();
+}
+
+/// Repeated bad tokens lock the source address out: even the right token is
+/// refused afterwards.
+#[tokio::test]
+async fn remote_auth_failures_lock_out_the_address() {
+    use term_session_muxio_service_definitions::remote::{
+        REMOTE_AUTH_DENIED, REMOTE_AUTH_OK, RemoteToken, hello_frame,
+    };
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    async fn verdict(addr: &str, token: &str) -> u8 {
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let token = RemoteToken::new(token).unwrap();
+        let _ = stream.write_all(&hello_frame(&token)).await;
+        let mut byte = [0u8; 1];
+        stream.read_exact(&mut byte).await.unwrap();
+        byte[0]
+    }
+
+    let gateway = unique_gateway("remote-lockout");
+    let (_child, addr) = spawn_remote_daemon(&gateway, "right-token");
+    let client = wait_connectable(&gateway).await;
+    wait_listening(&addr).await;
+
+    assert_eq!(verdict(&addr, "right-token").await, REMOTE_AUTH_OK);
+    for _ in 0..5 {
+        assert_eq!(verdict(&addr, "guess").await, REMOTE_AUTH_DENIED);
+    }
+    assert_eq!(verdict(&addr, "right-token").await, REMOTE_AUTH_DENIED);
+
+    ShutdownGateway::call(&*client, true).await.unwrap();
+}
@@ -0,0 +1,265 @@
+//! Pass open file descriptors between processes over a Unix domain socket.
+//!
+//! The gateway upgrade hands each channel's PTY master to a freshly started
+//! daemon so the child processes never notice the swap. The kernel duplicates
+//! descriptors carried as `SCM_RIGHTS` ancillary data into the receiving
+//! process; these helpers wrap the `sendmsg`/`recvmsg` boilerplate and the
+//! peer-credential check used to make sure the descriptors land in the
+//! process we spawned.
+
+use std::io;
+use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use std::os::unix::net::UnixStream;
+
+/// Upper bound on descriptors carried by one message. Linux rejects more than
+/// `SCM_MAX_FD` (253) per `sendmsg`; callers that hand off many descriptors
+/// send one message per descriptor group instead.
+pub const MAX_FDS_PER_MESSAGE: usize = 253;
+
+/// Bytes of control-message buffer needed to carry `count` descriptors.
+fn cmsg_space(count: usize) -> usize {
+    // SAFETY: CMSG_SPACE is a pure size computation.
+    unsafe { libc::CMSG_SPACE((count * std::mem::size_of::<RawFd>()) as u32) as usize }
+}
+
+/// Send `payload` over `stream` with `fds` attached as `SCM_RIGHTS`.
+///
+/// The descriptors stay open in the caller; the receiver gets its own
+/// duplicates. The payload must be non-empty: ancillary data rides on the
+/// first byte of the message, so an empty write would carry nothing. Short
+/// writes of the payload tail are completed with ordinary `write` calls.
+pub fn send_with_fds(stream: &UnixStream, payload: &[u8], fds: &[RawFd]) -> io::Result<()> {
+    if payload.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "fd passing requires a non-empty payload",
+        ));
+    }
+    if fds.len() > MAX_FDS_PER_MESSAGE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "cannot pass {} descriptors in one message (max {MAX_FDS_PER_MESSAGE})",
+                fds.len()
+            ),
+        ));
+    }
+
+    let mut iov = libc::iovec {
+        iov_base: payload.as_ptr() as *mut libc::c_void,
+        iov_len: payload.len(),
+    };
+    let mut control = vec![0u8; cmsg_space(fds.len())];
+    // SAFETY: zeroed msghdr is a valid "empty" header on every Unix.
+    let mut msg: libc::msghdr = unsafe { std::mem::zeroed() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    if !fds.is_empty() {
+        msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+        msg.msg_controllen = control.len() as _;
+        // SAFETY: the control buffer is sized by CMSG_SPACE for `fds.len()`
+        // descriptors, so the first header and its data fit.
+        unsafe {
+            let cmsg = libc::CMSG_FIRSTHDR(&msg);
+            (*cmsg).cmsg_level = libc::SOL_SOCKET;
+            (*cmsg).cmsg_type = libc::SCM_RIGHTS;
+            (*cmsg).cmsg_len = libc::CMSG_LEN(std::mem::size_of_val(fds) as u32) as _;
+            std::ptr::copy_nonoverlapping(
+                fds.as_ptr(),
+                libc::CMSG_DATA(cmsg) as *mut RawFd,
+                fds.len(),
+            );
+        }
+    }
+
+    let sent = loop {
+        // SAFETY: `msg` points at live iovec/control buffers for the call.
+        let n = unsafe { libc::sendmsg(stream.as_raw_fd(), &msg, 0) };
+        if n >= 0 {
+            break n as usize;
+        }
+        let err = io::Error::last_os_error();
+        if err.kind() != io::ErrorKind::Interrupted {
+            return Err(err);
+        }
+    };
+    if sent < payload.len() {
+        use std::io::Write;
+        (&*stream).write_all(&payload[sent..])?;
+    }
+    Ok(())
+}
+
+/// Receive up to `buf.len()` bytes from `stream`, collecting any descriptors
+/// carried as `SCM_RIGHTS` (at most `max_fds`).
+///
+/// Returns the number of payload bytes read and the received descriptors,
+/// which are owned by the caller and marked close-on-exec. `Ok((0, _))` means
+/// the peer closed the connection. A message carrying more than `max_fds`
+/// descriptors is reported as an error; every descriptor it carried is closed
+/// (the control buffer is word-aligned, so the kernel may deliver a few more
+/// than requested before truncating).
+pub fn recv_with_fds(
+    stream: &UnixStream,
+    buf: &mut [u8],
+    max_fds: usize,
+) -> io::Result<(usize, Vec<OwnedFd>)> {
+    let max_fds = max_fds.min(MAX_FDS_PER_MESSAGE);
+    let mut iov = libc::iovec {
+        iov_base: buf.as_mut_ptr() as *mut libc::c_void,
+        iov_len: buf.len(),
+    };
+    let mut control = vec![0u8; cmsg_space(max_fds.max(1))];
+    // SAFETY: zeroed msghdr is a valid "empty" header on every Unix.
+    let mut msg: libc::msghdr = unsafe { std::mem::zeroed() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+    msg.msg_controllen = control.len() as _;
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    let flags = libc::MSG_CMSG_CLOEXEC;
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    let flags = 0;
+
+    let n = loop {
+        // SAFETY: `msg` points at live iovec/control buffers for the call.
+        let n = unsafe { libc::recvmsg(stream.as_raw_fd(), &mut msg, flags) };
+        if n >= 0 {
+            break n as usize;
+        }
+        let err = io::Error::last_os_error();
+        if err.kind() != io::ErrorKind::Interrupted {
+            return Err(err);
+        }
+    };
+
+    let mut fds = Vec::new();
+    // SAFETY: iterate the control messages the kernel wrote into `control`;
+    // each SCM_RIGHTS payload is an array of freshly installed descriptors
+    // that this process now owns.
+    unsafe {
+        let mut cmsg = libc::CMSG_FIRSTHDR(&msg);
+        while !cmsg.is_null() {
+            if (*cmsg).cmsg_level == libc::SOL_SOCKET && (*cmsg).cmsg_type == libc::SCM_RIGHTS {
+                let data_len = (*cmsg).cmsg_len as usize - libc::CMSG_LEN(0) as usize;
+                let count = data_len / std::mem::size_of::<RawFd>();
+                let data = libc::CMSG_DATA(cmsg) as *const RawFd;
+                for i in 0..count {
+                    let fd = std::ptr::read_unaligned(data.add(i));
+                    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+                    libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC);
+                    fds.push(OwnedFd::from_raw_fd(fd));
+                }
+            }
+            cmsg = libc::CMSG_NXTHDR(&msg, cmsg);
+        }
+    }
+    if msg.msg_flags & libc::MSG_CTRUNC != 0 || fds.len() > max_fds {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("peer sent more than {max_fds} descriptors; ancillary data truncated"),
+        ));
+    }
+    Ok((n, fds))
+}
+
+/// PID of the process on the other end of a connected Unix socket, as
+/// recorded by the kernel at `connect` time (`SO_PEERCRED`).
+///
+/// Used to refuse a descriptor handoff to anything but the process the
+/// sender spawned for it.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn peer_pid(stream: &UnixStream) -> io::Result<u32> {
+    // SAFETY: zeroed ucred is valid; getsockopt fills at most `len` bytes.
+    let mut cred: libc::ucred = unsafe { std::mem::zeroed() };
+    let mut len = std::mem::size_of::<libc::ucred>() as libc::socklen_t;
+    let rc = unsafe {
+        libc::getsockopt(
+            stream.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_PEERCRED,
+            &mut cred as *mut libc::ucred as *mut libc::c_void,
+            &mut len,
+        )
+    };
+    if rc == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(cred.pid as u32)
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+
+    /// A pipe whose ends are owned, so a test cannot leak descriptors.
+    fn pipe() -> (std::fs::File, std::fs::File) {
+        let mut fds = [0; 2];
+        assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+        unsafe {
+            (
+                std::fs::File::from_raw_fd(fds[0]),
+                std::fs::File::from_raw_fd(fds[1]),
+            )
+        }
+    }
+
+    #[test]
+    fn passed_descriptor_refers_to_the_same_open_file() {
+        let (a, b) = UnixStream::pair().unwrap();
+        let (mut read_end, write_end) = pipe();
+
+        send_with_fds(&a, b"pipe", &[write_end.as_raw_fd()]).unwrap();
+        drop(write_end);
+
+        let mut buf = [0u8; 16];
+        let (n, mut fds) = recv_with_fds(&b, &mut buf, 4).unwrap();
+        assert_eq!(&buf[..n], b"pipe");
+        assert_eq!(fds.len(), 1);
+
+        // Writing through the received duplicate reaches the original pipe.
+        let mut received = std::fs::File::from(fds.remove(0));
+        received.write_all(b"through the socket").unwrap();
+        drop(received);
+        let mut out = String::new();
+        read_end.read_to_string(&mut out).unwrap();
+        assert_eq!(out, "through the socket");
+    }
+
+    #[test]
+    fn message_without_descriptors_round_trips() {
+        let (a, b) = UnixStream::pair().unwrap();
+        send_with_fds(&a, b"plain", &[]).unwrap();
+        let mut buf = [0u8; 16];
+        let (n, fds) = recv_with_fds(&b, &mut buf, 4).unwrap();
+        assert_eq!(&buf[..n], b"plain");
+        assert!(fds.is_empty());
+    }
+
+    #[test]
+    fn empty_payload_is_rejected() {
+        let (a, _b) = UnixStream::pair().unwrap();
+        let err = send_with_fds(&a, b"", &[]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn excess_descriptors_are_reported_not_silently_dropped() {
+        let (a, b) = UnixStream::pair().unwrap();
+        let (r, w) = pipe();
+        send_with_fds(&a, b"x", &[r.as_raw_fd(), w.as_raw_fd()]).unwrap();
+        let mut buf = [0u8; 4];
+        let err = recv_with_fds(&b, &mut buf, 1).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn peer_pid_reports_this_process_for_a_socketpair() {
+        let (a, _b) = UnixStream::pair().unwrap();
+        assert_eq!(peer_pid(&a).unwrap(), std::process::id());
+    }
+}
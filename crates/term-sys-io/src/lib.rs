@@ -1,12 +1,17 @@
 //! Low-level cross-platform OS I/O primitives for term-wm.
 //!
 //! This leaf crate is the **single** home for all unsafe process-global
-//! FD/handle manipulation in the workspace: stderr suppression and
-//! pipe-based FD redirection.  It sits at the bottom of the dependency graph
-//! (`libc` + `tracing` only) so `term-clipboard`, `term-wm-pty-engine`,
-//! `term-session-client`, and the root package can all depend downward on it
-//! without cycles.
+//! FD/handle manipulation in the workspace: stderr suppression, pipe-based
+//! FD redirection, `SCM_RIGHTS` descriptor passing for gateway upgrades, and
+//! the descriptor/process calls behind adopted PTY sessions.
+//! It sits at the bottom of the dependency graph (`libc` + `tracing` only) so
+//! `term-clipboard`, `term-wm-pty-engine`, `term-session-client`, and the root
+//! package can all depend downward on it without cycles.
 
+#[cfg(unix)]
+pub mod fd_passing;
+#[cfg(unix)]
+pub mod pty_handoff;
 pub mod redirect_stdio;
 pub mod stderr_suppress;
 
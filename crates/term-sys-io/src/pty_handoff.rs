@@ -0,0 +1,131 @@
+//! Descriptor and process primitives for PTY sessions taken over from another
+//! process.
+//!
+//! After a gateway upgrade the new daemon holds PTY master descriptors it did
+//! not open and children it did not fork (see [`crate::fd_passing`]). These
+//! helpers wrap the raw `fcntl`/`ioctl`/`tcgetpgrp`/`kill`/`setsid` calls the
+//! adopted handles and the replacement daemon spawn need, so callers stay
+//! free of `unsafe`.
+
+use std::io;
+use std::os::unix::io::{FromRawFd, OwnedFd, RawFd};
+use std::os::unix::process::CommandExt;
+use std::process::Command;
+
+/// Terminal window size in cells and pixels, as carried by `TIOCSWINSZ`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WinSize {
+    pub rows: u16,
+    pub cols: u16,
+    pub pixel_width: u16,
+    pub pixel_height: u16,
+}
+
+/// Duplicate `fd` into a new descriptor marked close-on-exec.
+pub fn dup_cloexec(fd: RawFd) -> io::Result<OwnedFd> {
+    // SAFETY: F_DUPFD_CLOEXEC only reads `fd`; an invalid one yields EBADF.
+    let dup = unsafe { libc::fcntl(fd, libc::F_DUPFD_CLOEXEC, 0) };
+    if dup == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: `dup` was just returned by fcntl and is owned by nobody else.
+    Ok(unsafe { OwnedFd::from_raw_fd(dup) })
+}
+
+/// Set the window size of the terminal behind `fd`.
+pub fn set_winsize(fd: RawFd, size: WinSize) -> io::Result<()> {
+    let ws = libc::winsize {
+        ws_row: size.rows,
+        ws_col: size.cols,
+        ws_xpixel: size.pixel_width,
+        ws_ypixel: size.pixel_height,
+    };
+    // SAFETY: TIOCSWINSZ reads a winsize struct from the pointer.
+    if unsafe { libc::ioctl(fd, libc::TIOCSWINSZ as _, &ws as *const _) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Window size of the terminal behind `fd`.
+pub fn get_winsize(fd: RawFd) -> io::Result<WinSize> {
+    // SAFETY: zeroed winsize is valid; TIOCGWINSZ fills it.
+    let mut ws: libc::winsize = unsafe { std::mem::zeroed() };
+    // SAFETY: TIOCGWINSZ writes a winsize struct through the pointer.
+    if unsafe { libc::ioctl(fd, libc::TIOCGWINSZ as _, &mut ws as *mut _) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(WinSize {
+        rows: ws.ws_row,
+        cols: ws.ws_col,
+        pixel_width: ws.ws_xpixel,
+        pixel_height: ws.ws_ypixel,
+    })
+}
+
+/// Foreground process group of the terminal behind `fd`, if it has one.
+pub fn foreground_process_group(fd: RawFd) -> Option<libc::pid_t> {
+    // SAFETY: tcgetpgrp only reads the descriptor's foreground pgid.
+    match unsafe { libc::tcgetpgrp(fd) } {
+        pid if pid > 0 => Some(pid),
+        _ => None,
+    }
+}
+
+/// Send `signal` to `pid`. Signal `0` performs only the existence and
+/// permission check; a vanished process reports `ESRCH`.
+pub fn signal_pid(pid: u32, signal: libc::c_int) -> io::Result<()> {
+    // SAFETY: plain kill(2) on a caller-supplied PID.
+    if unsafe { libc::kill(pid as libc::pid_t, signal) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Make `cmd`'s child the leader of a new session, detached from the
+/// caller's controlling terminal.
+pub fn detach_into_new_session(cmd: &mut Command) {
+    // SAFETY: setsid is async-signal-safe and touches no parent state.
+    unsafe {
+        cmd.pre_exec(|| {
+            if libc::setsid() == -1 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::io::AsRawFd;
+
+    #[test]
+    fn dup_cloexec_sets_close_on_exec() {
+        let file = std::fs::File::open("/dev/null").unwrap();
+        let dup = dup_cloexec(file.as_raw_fd()).unwrap();
+        assert_ne!(dup.as_raw_fd(), file.as_raw_fd());
+        // SAFETY: F_GETFD only reads the descriptor flags.
+        let flags = unsafe { libc::fcntl(dup.as_raw_fd(), libc::F_GETFD) };
+        assert_ne!(flags & libc::FD_CLOEXEC, 0);
+    }
+
+    #[test]
+    fn invalid_descriptors_report_errors() {
+        assert!(dup_cloexec(-1).is_err());
+        assert!(get_winsize(-1).is_err());
+        assert_eq!(foreground_process_group(-1), None);
+    }
+
+    #[test]
+    fn signal_zero_checks_existence() {
+        signal_pid(std::process::id(), 0).unwrap();
+        let mut child = Command::new("true").spawn().unwrap();
+        let pid = child.id();
+        child.wait().unwrap();
+        let err = signal_pid(pid, 0).unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::ESRCH));
+    }
+}
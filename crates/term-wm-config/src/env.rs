@@ -17,6 +17,20 @@ pub const SESSION_ACTIVE_ENV_VAR: &str = "TERM_SESSION_ACTIVE";
 /// gateway socket path (e.g. `"term-wm/prod/user/gateway"`). Read by the
 /// term-session client for socket-aware nesting-inception detection.
 pub const SESSION_GATEWAY_ENV_VAR: &str = "TERM_SESSION_GATEWAY";
+/// Set by an upgrading gateway on the replacement daemon it spawns: the Unix
+/// socket path the new daemon connects to for the channel handoff. Read (and
+/// cleared) by `term-session-server` at startup.
+pub const HANDOFF_ENV_VAR: &str = "TERM_SESSION_HANDOFF";
+/// TCP address (`host:port`) the gateway daemon additionally accepts remote
+/// clients on. Unset means local IPC only. Read by `term-session-server`.
+pub const REMOTE_LISTEN_ENV_VAR: &str = "TERM_SESSION_LISTEN";
+/// Pre-shared token for remote gateway access. Read by the gateway (overrides
+/// its key file) and by `term-session --connect`.
+pub const REMOTE_TOKEN_ENV_VAR: &str = "TERM_SESSION_TOKEN";
+/// Output stream encoding a client offers the gateway (`lz4`, or `raw` to
+/// turn compression off). Unset offers every supported codec. Read by
+/// `term-session-client`.
+pub const OUTPUT_CODEC_ENV_VAR: &str = "TERM_SESSION_OUTPUT_CODEC";
 /// Enables dumping raw PTY→emulator bytes to a file (debugging). Read by
 /// `term-wm-pty-engine`.
 pub const ESC_TRACE_ENV: &str = "TERM_WM_TRACE_ESC";
@@ -24,6 +38,21 @@ pub const ESC_TRACE_ENV: &str = "TERM_WM_TRACE_ESC";
 /// Read by the `term-wm` binary.
 pub const NO_SESSION_PERSISTENCE_ENV_VAR: &str = "TERM_WM_NO_SESSION_PERSISTENCE";
 
+/// Name of the theme to start with (e.g. `paper`). Read by the `term-wm`
+/// crate when it builds a `TermWmApp`.
+pub const THEME_ENV_VAR: &str = "TERM_WM_THEME";
+/// Directory of user `*.theme` files, overriding
+/// `$XDG_CONFIG_HOME/term-wm/themes`. Read by `term-wm-core`.
+pub const THEME_DIR_ENV_VAR: &str = "TERM_WM_THEME_DIR";
+/// Directory exported screenshots are written to, overriding
+/// `$XDG_DATA_HOME/term-wm/screenshots`. Read by `term-wm-core`.
+pub const SCREENSHOT_DIR_ENV_VAR: &str = "TERM_WM_SCREENSHOT_DIR";
+
+/// Skips the startup query round-trip to the host terminal (DA1/DA2,
+/// XTVERSION, ...) and trusts `TERM`/`COLORTERM`/terminfo alone. Read by
+/// `term-wm-console`.
+pub const NO_TERMINAL_PROBE_ENV_VAR: &str = "TERM_WM_NO_TERMINAL_PROBE";
+
 /// Active environment override (`dev`/`prod`/`test`, case-insensitive).
 /// Read by `term-session-muxio-service-definitions` to scope the gateway.
 pub const ENVIRONMENT_ENV_VAR: &str = "TERM_WM_ENV";
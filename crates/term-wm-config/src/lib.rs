@@ -14,7 +14,9 @@ pub mod runtime;
 
 pub use env::{
     CHANNEL_ENV_VAR, ENVIRONMENT_ENV_VAR, ESC_TRACE_ENV, Environment, GATEWAY_CHANNEL_ENV_VAR,
-    GATEWAY_NAMESPACE, NO_SESSION_PERSISTENCE_ENV_VAR, SESSION_ACTIVE_ENV_VAR,
-    SESSION_GATEWAY_ENV_VAR, active_environment, default_environment, parse_environment,
+    GATEWAY_NAMESPACE, NO_SESSION_PERSISTENCE_ENV_VAR, NO_TERMINAL_PROBE_ENV_VAR,
+    REMOTE_LISTEN_ENV_VAR, REMOTE_TOKEN_ENV_VAR, SCREENSHOT_DIR_ENV_VAR, SESSION_ACTIVE_ENV_VAR,
+    SESSION_GATEWAY_ENV_VAR, THEME_DIR_ENV_VAR, THEME_ENV_VAR, active_environment,
+    default_environment, parse_environment,
 };
 pub use runtime::{RuntimeConfig, init, session_persistence_enabled};
@@ -93,8 +93,10 @@ impl Drop for BackgroundConsoleReader {
     }
 }
 
-/// The background thread body: poll crossterm, translate, forward raw events.
+/// The background thread body: poll crossterm, translate (reassembling OSC
+/// color replies), forward raw events.
 fn input_loop(tx: Sender<Event>, shutdown: Arc<AtomicBool>) {
+    let mut osc_replies = term_wm_crossterm_adapter::OscReplyAssembler::new();
     loop {
         if shutdown.load(Ordering::Acquire) {
             break;
@@ -104,9 +106,8 @@ fn input_loop(tx: Sender<Event>, shutdown: Arc<AtomicBool>) {
                 match crossterm::event::read() {
                     Ok(evt) => {
                         // NO normalization here — the consumer normalizes.
-                        let Some(core_evt) = term_wm_crossterm_adapter::try_translate_event(evt)
-                        else {
-                            continue; // unrecognized key — drop the event
+                        let Some(core_evt) = osc_replies.translate(evt) else {
+                            continue; // unrecognized key or OSC reply fragment — drop
                         };
                         if tx.send(core_evt).is_err() {
                             break; // receiver dropped
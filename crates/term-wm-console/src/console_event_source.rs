@@ -14,6 +14,7 @@ use term_wm_crossterm_adapter;
 /// which runs input on a background thread and integrates PTY wakeups.
 pub struct ConsoleEventSource {
     normalizer: KeyboardNormalizer,
+    osc_replies: term_wm_crossterm_adapter::OscReplyAssembler,
     event_queue: VecDeque<Event>,
     last_event_at: Option<Instant>,
     /// Set by the runner when there's pending work (e.g. countdown timer)
@@ -35,6 +36,7 @@ impl ConsoleEventSource {
     pub fn new() -> Self {
         Self {
             normalizer: KeyboardNormalizer::new(),
+            osc_replies: term_wm_crossterm_adapter::OscReplyAssembler::new(),
             event_queue: VecDeque::new(),
             last_event_at: None,
             pending_work: false,
@@ -45,7 +47,7 @@ impl ConsoleEventSource {
     fn read_internal(&mut self) -> io::Result<Event> {
         loop {
             let evt = crossterm::event::read()?;
-            if let Some(translated) = term_wm_crossterm_adapter::try_translate_event(evt)
+            if let Some(translated) = self.osc_replies.translate(evt)
                 && let Some(normalized) = self.normalizer.normalize(translated)
             {
                 return Ok(normalized);
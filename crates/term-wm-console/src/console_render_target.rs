@@ -1,7 +1,13 @@
 use std::io::{self, Stdout, Write};
 
-use crossterm::event::{DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste};
-use crossterm::terminal::{Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::event::{
+    DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+    EnableFocusChange,
+};
+use crossterm::terminal::{
+    BeginSynchronizedUpdate, Clear, ClearType, EndSynchronizedUpdate, EnterAlternateScreen,
+    LeaveAlternateScreen,
+};
 use crossterm::{execute, terminal};
 use ratatui::Terminal;
 use ratatui::backend::CrosstermBackend;
@@ -14,6 +20,8 @@ use ratatui::{TerminalOptions, Viewport};
 
 use crate::RatatuiBackend;
 use crate::RenderBackend;
+use crate::terminal_capabilities::TerminalCapabilities;
+use term_wm_core::events::HostDisplay;
 use term_wm_core::io::RenderTarget;
 
 #[cfg(test)]
@@ -32,16 +40,33 @@ use std::sync::{Arc, Mutex};
 /// Defaults to `true` for production.  Set to `false` in `new_capturing()` so
 /// tests can verify the ANSI byte stream without the test runner's OS state
 /// being mutated.
+///
+/// # Capabilities
+///
+/// Every frame is degraded to the host's [`TerminalCapabilities`] before it
+/// is flushed (palette quantization, ASCII box drawing) and wrapped in
+/// synchronized output when the terminal supports it.
 pub struct ConsoleRenderTarget<W: Write = Stdout> {
     terminal: Terminal<CrosstermBackend<W>>,
     pub(crate) entered: bool,
     pub manage_raw_mode: bool,
+    capabilities: TerminalCapabilities,
+    /// Whether [`query_host_colors`](RenderTarget::query_host_colors) writes
+    /// OSC 10/11 queries; off when nothing reads the replies from stdin.
+    host_color_queries: bool,
+    /// Cursor style last written to the host, so a change is sent once and
+    /// a non-default one is undone when it goes away or on exit.
+    emitted_cursor_style: term_wm_core::CursorStyle,
 }
 
 impl ConsoleRenderTarget<Stdout> {
-    /// Create a new render target writing to real stdout.
+    /// Create a new render target writing to real stdout, probing the host
+    /// terminal with [`TerminalCapabilities::detect`]. Create it before any
+    /// stdin reader starts, or probe earlier and use
+    /// [`with_capabilities`](Self::with_capabilities).
     pub fn new() -> io::Result<Self> {
-        Self::with_writer(io::stdout())
+        let capabilities = TerminalCapabilities::detect();
+        Ok(Self::with_writer(io::stdout())?.with_capabilities(capabilities))
     }
 }
 
@@ -108,6 +133,9 @@ impl ConsoleRenderTarget<CaptureWriter> {
             terminal,
             entered: false,
             manage_raw_mode: true,
+            capabilities: TerminalCapabilities::default(),
+            host_color_queries: true,
+            emitted_cursor_style: Default::default(),
         };
         rt.manage_raw_mode = false;
         (rt, writer)
@@ -115,7 +143,8 @@ impl ConsoleRenderTarget<CaptureWriter> {
 }
 
 impl<W: Write> ConsoleRenderTarget<W> {
-    /// Create a render target with an arbitrary writer backend.
+    /// Create a render target with an arbitrary writer backend. Assumes
+    /// [`TerminalCapabilities::default`] until told otherwise.
     pub fn with_writer(writer: W) -> io::Result<Self> {
         let backend = CrosstermBackend::new(writer);
         let terminal = Terminal::new(backend)?;
@@ -123,8 +152,29 @@ impl<W: Write> ConsoleRenderTarget<W> {
             terminal,
             entered: false,
             manage_raw_mode: true,
+            capabilities: TerminalCapabilities::default(),
+            host_color_queries: true,
+            emitted_cursor_style: Default::default(),
         })
     }
+
+    /// Render for a terminal with the given capabilities.
+    pub fn with_capabilities(mut self, capabilities: TerminalCapabilities) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// Never query the host's default colors. For an inner window manager
+    /// whose stdin is not read: the gateway reports the host colors instead.
+    pub fn without_host_color_queries(mut self) -> Self {
+        self.host_color_queries = false;
+        self
+    }
+
+    /// What the host terminal supports.
+    pub fn capabilities(&self) -> &TerminalCapabilities {
+        &self.capabilities
+    }
 }
 
 impl<W: Write> RenderTarget for ConsoleRenderTarget<W> {
@@ -138,6 +188,7 @@ impl<W: Write> RenderTarget for ConsoleRenderTarget<W> {
             self.terminal.backend_mut(),
             EnterAlternateScreen,
             EnableBracketedPaste,
+            EnableFocusChange,
         )?;
 
         // OS console API (enable_raw_mode on Windows) / raw mode switching.
@@ -147,6 +198,11 @@ impl<W: Write> RenderTarget for ConsoleRenderTarget<W> {
 
         // Also testable via CaptureWriter — see Hide::write_ansi.
         self.terminal.hide_cursor()?;
+        // Components quantize colors they compute (`map_rgb_to_color`,
+        // images) against the process-wide depth; tests keep theirs.
+        if self.manage_raw_mode {
+            term_wm_core::term_color::set_color_depth(self.capabilities.color_depth);
+        }
         self.entered = true;
         Ok(())
     }
@@ -173,10 +229,14 @@ impl<W: Write> RenderTarget for ConsoleRenderTarget<W> {
         execute!(
             self.terminal.backend_mut(),
             DisableBracketedPaste,
+            DisableFocusChange,
             Clear(ClearType::All),
             LeaveAlternateScreen,
         )?;
 
+        if !self.emitted_cursor_style.is_default() {
+            self.write_cursor_style(Default::default())?;
+        }
         // Also testable via CaptureWriter — see Show::write_ansi.
         self.terminal.show_cursor()?;
 
@@ -193,16 +253,45 @@ impl<W: Write> RenderTarget for ConsoleRenderTarget<W> {
     where
         F: FnOnce(&mut dyn RenderBackend),
     {
-        self.terminal
+        let sync = self.capabilities.synchronized_output;
+        if sync {
+            execute!(self.terminal.backend_mut(), BeginSynchronizedUpdate)?;
+        }
+        let capabilities = &self.capabilities;
+        let mut cursor_style = term_wm_core::CursorStyle::default();
+        let style_out = &mut cursor_style;
+        let drawn = self
+            .terminal
             .draw(move |frame| {
                 let area = frame.area();
                 let buffer = std::mem::replace(frame.buffer_mut(), Buffer::empty(area));
                 let mut backend = RatatuiBackend::new_simple(buffer, area);
                 f(&mut backend);
+                capabilities.degrade_buffer(&mut backend.buffer);
                 *frame.buffer_mut() = backend.buffer;
+                // Ratatui shows the cursor only when a position is set.
+                if let Some(cursor) = backend.host_cursor
+                    && cursor.x < area.right()
+                    && cursor.y < area.bottom()
+                {
+                    frame.set_cursor_position((cursor.x, cursor.y));
+                    *style_out = cursor.style;
+                }
             })
             .map(|_| ())
-            .map_err(|err| io::Error::other(err.to_string()))
+            .map_err(|err| io::Error::other(err.to_string()));
+        let drawn = drawn.and_then(|()| {
+            if cursor_style != self.emitted_cursor_style {
+                self.write_cursor_style(cursor_style)?;
+            }
+            Ok(())
+        });
+        // Always close the update, even after a failed write: a host left in
+        // mode 2026 stops repainting until its own timeout.
+        if sync {
+            execute!(self.terminal.backend_mut(), EndSynchronizedUpdate)?;
+        }
+        drawn
     }
 
     fn repair(&mut self) -> io::Result<()> {
@@ -211,6 +300,34 @@ impl<W: Write> RenderTarget for ConsoleRenderTarget<W> {
         execute!(self.terminal.backend_mut(), Clear(ClearType::All),)?;
         self.terminal.hide_cursor()
     }
+
+    fn set_host_display(&mut self, display: &HostDisplay) {
+        self.capabilities.apply_host_display(display);
+        if self.entered && self.manage_raw_mode {
+            term_wm_core::term_color::set_color_depth(self.capabilities.color_depth);
+        }
+    }
+
+    fn query_host_colors(&mut self) -> io::Result<()> {
+        if !self.host_color_queries {
+            return Ok(());
+        }
+        let backend = self.terminal.backend_mut();
+        backend.write_all(term_wm_crossterm_adapter::HOST_COLOR_QUERY.as_bytes())?;
+        backend.flush()?;
+        term_wm_crossterm_adapter::expect_host_color_replies();
+        Ok(())
+    }
+}
+
+impl<W: Write> ConsoleRenderTarget<W> {
+    fn write_cursor_style(&mut self, style: term_wm_core::CursorStyle) -> io::Result<()> {
+        let backend = self.terminal.backend_mut();
+        backend.write_all(style.escape_sequence().as_bytes())?;
+        backend.flush()?;
+        self.emitted_cursor_style = style;
+        Ok(())
+    }
 }
 
 impl<W: Write> Drop for ConsoleRenderTarget<W> {
@@ -314,6 +431,107 @@ mod tests {
         );
     }
 
+    /// Frames are quantized to the terminal's palette before they are
+    /// flushed, and wrapped in mode 2026 when the terminal supports it.
+    #[test]
+    fn draw_degrades_frames_and_synchronizes_output() {
+        let (rt, writer) = ConsoleRenderTarget::new_capturing();
+        let mut rt = rt.with_capabilities(TerminalCapabilities {
+            color_depth: term_wm_core::term_color::ColorDepth::Ansi16,
+            synchronized_output: true,
+            ..TerminalCapabilities::default()
+        });
+        rt.draw(|backend| {
+            let backend = backend
+                .as_any_mut()
+                .downcast_mut::<RatatuiBackend>()
+                .unwrap();
+            backend.buffer[(0, 0)]
+                .set_symbol("x")
+                .set_fg(ratatui::style::Color::Rgb(250, 10, 10));
+        })
+        .expect("draw");
+        let text = String::from_utf8_lossy(&writer.bytes()).to_string();
+        assert!(!text.contains("38;2;"), "no truecolor SGR: {text:?}");
+        assert!(text.contains("\x1b[38;5;9;"), "bright red SGR: {text:?}");
+        let begin = text.find("\x1b[?2026h").expect("begin sync");
+        let end = text.find("\x1b[?2026l").expect("end sync");
+        assert!(begin < text.find('x').unwrap() && text.find('x').unwrap() < end);
+    }
+
+    /// Inside a gateway the capabilities come from the session client; later
+    /// frames are degraded for the host it probed.
+    #[test]
+    fn draw_degrades_for_reported_host_display() {
+        let (mut rt, writer) = ConsoleRenderTarget::new_capturing();
+        let mut display = rt.capabilities().host_display();
+        display.color_depth = term_wm_core::events::HostColorDepth::Ansi16;
+        rt.set_host_display(&display);
+        assert_eq!(
+            rt.capabilities().color_depth,
+            term_wm_core::term_color::ColorDepth::Ansi16
+        );
+        rt.draw(|backend| {
+            let backend = backend
+                .as_any_mut()
+                .downcast_mut::<RatatuiBackend>()
+                .unwrap();
+            backend.buffer[(0, 0)]
+                .set_symbol("x")
+                .set_fg(ratatui::style::Color::Rgb(250, 10, 10));
+        })
+        .expect("draw");
+        let text = String::from_utf8_lossy(&writer.bytes()).to_string();
+        assert!(!text.contains("38;2;"), "no truecolor SGR: {text:?}");
+    }
+
+    /// A focused pane's cursor style is sent once, with the cursor shown at
+    /// its cell, and undone when no pane asks for one and on exit.
+    #[test]
+    fn draw_passes_host_cursor_style_through() {
+        use term_wm_core::components::HostCursor;
+        use term_wm_core::{CursorShape, CursorStyle};
+
+        let (mut rt, writer) = ConsoleRenderTarget::new_capturing();
+        rt.enter().expect("enter");
+        let style = CursorStyle {
+            shape: CursorShape::BlinkingUnderline,
+            color: Some((0, 255, 0)),
+        };
+        let draw = |rt: &mut ConsoleRenderTarget<CaptureWriter>, cursor: Option<HostCursor>| {
+            writer.clear();
+            rt.draw(|backend| {
+                let backend = backend
+                    .as_any_mut()
+                    .downcast_mut::<RatatuiBackend>()
+                    .unwrap();
+                backend.host_cursor = cursor;
+            })
+            .expect("draw");
+            String::from_utf8_lossy(&writer.bytes()).to_string()
+        };
+        let cursor = Some(HostCursor { x: 4, y: 2, style });
+
+        let text = draw(&mut rt, cursor);
+        assert!(text.contains(&style.escape_sequence()), "{text:?}");
+        assert!(text.contains("\x1b[3;5H"), "cursor moved: {text:?}");
+        assert!(text.contains("\x1b[?25h"), "cursor shown: {text:?}");
+
+        let text = draw(&mut rt, cursor);
+        assert!(!text.contains("\x1b[3 q"), "style sent once: {text:?}");
+
+        let reset = CursorStyle::default().escape_sequence();
+        let text = draw(&mut rt, None);
+        assert!(text.contains(&reset), "{text:?}");
+        assert!(text.contains("\x1b[?25l"), "cursor hidden: {text:?}");
+
+        draw(&mut rt, cursor);
+        writer.clear();
+        rt.exit().expect("exit");
+        let text = String::from_utf8_lossy(&writer.bytes()).to_string();
+        assert!(text.contains(&reset), "reset on exit: {text:?}");
+    }
+
     /// Tests that calling `enter()` twice does not write additional bytes
     /// — the `entered` guard on the second call should skip the body.
     #[test]
@@ -4,6 +4,7 @@ use ratatui::prelude::Frame;
 use term_wm_layout_engine::LayoutRect;
 
 use crate::RatatuiBackend;
+use crate::window_damage::WindowDamage;
 use term_wm_core::actions::TermWmAction;
 use term_wm_core::component_context::ComponentContext;
 use term_wm_core::components::{Component, ComponentAction, Overlay, TopPanelState, WmComponent};
@@ -30,10 +31,17 @@ pub struct ChromeCtx<'a> {
     pub wm_buttons: Vec<term_wm_core::window::WmButton>,
     pub borders_enabled: bool,
     pub header_enabled: bool,
+    pub border_style: BorderStyle,
+    pub title_alignment: TitleAlignment,
+    /// Border and label color overriding the theme's.
+    pub accent: Option<Color>,
+    /// Tag drawn ahead of the title.
+    pub label: Option<&'a str>,
 }
 
 use term_wm_core::chrome::{
-    LEFT_BORDER_WIDTH, RIGHT_BORDER_WIDTH, TOP_BORDER_HEIGHT, button_x_pos, content_rect,
+    BorderStyle, LEFT_BORDER_WIDTH, RIGHT_BORDER_WIDTH, TOP_BORDER_HEIGHT, TitleAlignment,
+    button_x_pos, content_rect,
 };
 use term_wm_core::constants::HEADER_BUTTON_GAP;
 
@@ -247,6 +255,9 @@ pub fn render_window_chrome(
         width,
         height,
     };
+    // A border-less style gives the border cells to the content, exactly as
+    // if the chrome rules had turned borders off.
+    let borders_enabled = ctx.borders_enabled && ctx.border_style.is_visible();
 
     // Draw chrome using existing renderer
     render_window(
@@ -257,10 +268,14 @@ pub fn render_window_chrome(
             focused: ctx.focused,
             floating: ctx.floating,
             hover_pos: ctx.hover_pos,
-            theme: ctx.theme,
+            theme: ctx.theme.clone(),
             wm_buttons: ctx.wm_buttons.clone(),
-            borders_enabled: ctx.borders_enabled,
+            borders_enabled,
             header_enabled: ctx.header_enabled,
+            border_style: ctx.border_style,
+            title_alignment: ctx.title_alignment,
+            accent: ctx.accent,
+            label: ctx.label,
         },
     );
 
@@ -273,7 +288,7 @@ pub fn render_window_chrome(
             screen_origin,
             content_hitbox_id,
             wm_buttons: ctx.wm_buttons.clone(),
-            borders_enabled: ctx.borders_enabled,
+            borders_enabled,
             header_enabled: ctx.header_enabled,
         },
     );
@@ -285,7 +300,7 @@ pub fn render_window_chrome(
         width,
         height,
     };
-    let inner = content_rect(full_area, ctx.borders_enabled, ctx.header_enabled);
+    let inner = content_rect(full_area, borders_enabled, ctx.header_enabled);
     LayoutRect {
         x: inner.x,
         y: inner.y,
@@ -297,7 +312,7 @@ pub fn render_window_chrome(
 /// Convert LayoutRect to Ratatui Rect with proportional truncation.
 /// Negative coordinates are clamped to 0; width/height are reduced by the
 /// same amount to prevent phantom column projection.
-fn layout_rect_to_clipped_rect(layout: LayoutRect) -> Rect {
+pub(crate) fn layout_rect_to_clipped_rect(layout: LayoutRect) -> Rect {
     let x_trunc = if layout.x < 0 { (-layout.x) as u16 } else { 0 };
     let y_trunc = if layout.y < 0 { (-layout.y) as u16 } else { 0 };
     Rect {
@@ -344,11 +359,14 @@ pub fn blit_buffer(src: &Buffer, dst: &mut Buffer, area: Rect) {
 /// blits the result to the main buffer, then returns it via `put_scratch()`.
 /// After the first frame the Buffer and mask capacity is stable — no heap
 /// allocations in steady state.
+/// Window compositing goes through [`WindowDamage`], which keeps each
+/// window's last frame and restacks only the damaged parts of the screen.
 pub struct DrawPlanRenderer {
     scratch_buffer: Buffer,
     scratch_mask: Vec<u8>,
     direct_buffer: Buffer,
     direct_mask: Vec<u8>,
+    window_damage: WindowDamage,
 }
 
 impl DrawPlanRenderer {
@@ -358,9 +376,19 @@ impl DrawPlanRenderer {
             scratch_mask: Vec::new(),
             direct_buffer: Buffer::empty(Rect::ZERO),
             direct_mask: Vec::new(),
+            window_damage: WindowDamage::new(),
         }
     }
 
+    /// Per-window frame cache used by `render_app`.
+    pub fn window_damage(&self) -> &WindowDamage {
+        &self.window_damage
+    }
+
+    pub fn window_damage_mut(&mut self) -> &mut WindowDamage {
+        &mut self.window_damage
+    }
+
     /// Downcast a `&mut dyn term_wm_render::RenderBackend` to `&mut RatatuiBackend`.
     pub fn downcast_to_ratatui<'a>(
         &self,
@@ -926,13 +954,16 @@ pub fn render_overlays<C: Component<TermWmAction>, L: WmComponent, O: Overlay<Te
 
     // Overlays (help, exit confirm, command palette)
     let overlay_keys = wm.overlay_keys();
+    // Overlays get the WM config so they follow the active theme.
+    let overlay_ctx = wm
+        .component_context(false)
+        .with_overlay(true)
+        .with_screen_area(full_area)
+        .with_hover_pos(hover_pos);
     for key in overlay_keys {
         if let Some(overlay) = wm.overlay_for_key_mut(key) {
             let mut hitbox = HitboxRegistry::new();
-            let ctx = ComponentContext::new(false)
-                .with_overlay(true)
-                .with_screen_area(full_area)
-                .with_hover_pos(hover_pos);
+            let ctx = overlay_ctx.clone();
             overlay.render(backend, full_area, &ctx, &mut hitbox);
             wm.hitbox_registry_mut().merge(hitbox);
         }
@@ -952,6 +983,23 @@ pub fn render_drop_shadow(
     dest: LayoutRect,
     z_depth: f32,
     theme: &Theme,
+) {
+    let area = buf.area;
+    apply_drop_shadow(buf, mask, dest, shadow_color(theme, z_depth), area);
+}
+
+/// Shadow tint for a drawable at `z_depth` (0.0 = topmost).
+pub(crate) fn shadow_color(theme: &Theme, z_depth: f32) -> ratatui::style::Color {
+    lerp_color(theme.shadow_tint, theme.shadow_bg, z_depth).to_ratatui()
+}
+
+/// [`render_drop_shadow`] restricted to the cells inside `clip`.
+pub(crate) fn apply_drop_shadow(
+    buf: &mut Buffer,
+    mask: &mut [u8],
+    dest: LayoutRect,
+    shadow_color: ratatui::style::Color,
+    clip: Rect,
 ) {
     let active_mask = &mut mask[..buf.content.len()];
     active_mask.fill(0);
@@ -962,10 +1010,11 @@ pub fn render_drop_shadow(
     let ex = sx.saturating_add(i32::from(dest.width));
     let ey = sy.saturating_add(i32::from(dest.height));
 
-    let buf_x = buf.area.x as i32;
-    let buf_y = buf.area.y as i32;
-    let buf_ex = buf_x + buf.area.width as i32;
-    let buf_ey = buf_y + buf.area.height as i32;
+    let bounds = buf.area.intersection(clip);
+    let buf_x = bounds.x as i32;
+    let buf_y = bounds.y as i32;
+    let buf_ex = buf_x + bounds.width as i32;
+    let buf_ey = buf_y + bounds.height as i32;
 
     let clip_x = sx.max(buf_x);
     let clip_y = sy.max(buf_y);
@@ -977,7 +1026,7 @@ pub fn render_drop_shadow(
     }
 
     let buf_w = buf.area.width as usize;
-    let rel_x_start = (clip_x - buf_x) as usize;
+    let rel_x_start = (clip_x - buf.area.x as i32) as usize;
     let copy_width = (clip_ex - clip_x) as usize;
 
     let y_start = clip_y as usize;
@@ -1000,7 +1049,6 @@ pub fn render_drop_shadow(
     }
 
     // Pass 2: Row-sliced mask apply
-    let shadow_color = lerp_color(theme.shadow_tint, theme.shadow_bg, z_depth).to_ratatui();
     for y in y_start..y_end {
         let rel_y = y - buf.area.y as usize;
         let row_start = rel_y * buf_w;
@@ -1028,12 +1076,63 @@ pub fn render_drop_shadow(
 /// Returns the inner content bounds (after chrome inset) and the chrome hitbox registry.
 pub fn composite_window<F>(
     backend: &mut dyn term_wm_render::RenderBackend,
+    surface: &WindowSurface,
+    key: WindowKey,
+    content_hitbox_id: HitboxId,
+    ctx: ChromeCtx<'_>,
+    render_content: F,
+    scratch: &mut Buffer,
+) -> (LayoutRect, HitboxRegistry)
+where
+    F: FnMut(&mut dyn term_wm_render::RenderBackend, LayoutRect),
+{
+    if surface.dest.width == 0 || surface.dest.height == 0 {
+        return (LayoutRect::default(), HitboxRegistry::new());
+    }
+    let theme = ctx.theme.clone();
+    let (inner_bounds, chrome_registry) = render_window_surface(
+        surface,
+        key,
+        content_hitbox_id,
+        ctx,
+        render_content,
+        scratch,
+    );
+    let Some(ratatui_backend) = backend.as_any_mut().downcast_mut::<RatatuiBackend>() else {
+        return (inner_bounds, chrome_registry);
+    };
+    let main_buf = &mut ratatui_backend.buffer;
+    let area = main_buf.area;
+    if surface.draw_shadow {
+        // Take mask ownership to avoid borrow conflicts with main_buf
+        let mut tmp_mask = std::mem::take(&mut ratatui_backend.mask_buffer);
+        let buf_len = main_buf.content.len();
+        if tmp_mask.len() < buf_len {
+            tmp_mask.resize(buf_len, 0);
+        }
+        render_drop_shadow(
+            main_buf,
+            &mut tmp_mask[..buf_len],
+            surface.dest,
+            1.0 - surface.z_depth,
+            &theme,
+        );
+        ratatui_backend.mask_buffer = tmp_mask;
+    }
+    blit_window(scratch, &mut ratatui_backend.buffer, surface.dest, area);
+    (inner_bounds, chrome_registry)
+}
+
+/// Draw a window's chrome and content into `buffer`, sized to the window
+/// and in window-local coordinates (the first half of [`composite_window`]).
+/// Returns the inner content bounds and the chrome hitbox registry.
+pub fn render_window_surface<F>(
     surface: &WindowSurface,
     key: WindowKey,
     content_hitbox_id: HitboxId,
     mut ctx: ChromeCtx<'_>,
     mut render_content: F,
-    scratch: &mut Buffer,
+    buffer: &mut Buffer,
 ) -> (LayoutRect, HitboxRegistry)
 where
     F: FnMut(&mut dyn term_wm_render::RenderBackend, LayoutRect),
@@ -1064,90 +1163,70 @@ where
         (local_x, local_y)
     });
     let focused = ctx.focused;
-    let theme = ctx.theme;
 
-    // Reuse caller's scratch buffer — resize instead of allocating
-    scratch.resize(local_area);
-    scratch.reset();
-    let mut buffer = std::mem::replace(scratch, Buffer::empty(Rect::ZERO));
-    let inner_bounds: LayoutRect;
+    // Reuse caller's buffer — resize instead of allocating
+    buffer.resize(local_area);
+    buffer.reset();
     let mut chrome_registry = HitboxRegistry::new();
-    {
-        let mut offscreen = RatatuiBackend::new_simple(buffer, local_area);
-        // Atomic single-pass: draw chrome + register hitboxes + get inner bounds
-        inner_bounds = render_window_chrome(
-            &mut offscreen.buffer,
-            &mut chrome_registry,
-            key,
-            (surface.dest.width, surface.dest.height),
-            (surface.dest.x as i16, surface.dest.y as i16),
-            content_hitbox_id,
-            &ctx,
-        );
-        render_content(&mut offscreen, inner_bounds);
-        buffer = offscreen.buffer;
-    }
+    let mut offscreen = RatatuiBackend::new_simple(
+        std::mem::replace(buffer, Buffer::empty(Rect::ZERO)),
+        local_area,
+    );
+    // Atomic single-pass: draw chrome + register hitboxes + get inner bounds
+    let inner_bounds = render_window_chrome(
+        &mut offscreen.buffer,
+        &mut chrome_registry,
+        key,
+        (surface.dest.width, surface.dest.height),
+        (surface.dest.x as i16, surface.dest.y as i16),
+        content_hitbox_id,
+        &ctx,
+    );
+    render_content(&mut offscreen, inner_bounds);
+    *buffer = offscreen.buffer;
     if !focused {
         for cell in buffer.content.iter_mut() {
             cell.modifier.insert(ratatui::style::Modifier::DIM);
         }
     }
-    let Some(ratatui_backend) = backend.as_any_mut().downcast_mut::<RatatuiBackend>() else {
-        // Return buffer to caller before early return
-        *scratch = buffer;
-        return (inner_bounds, chrome_registry);
-    };
-    let main_buf = &mut ratatui_backend.buffer;
-    if surface.draw_shadow {
-        // Take mask ownership to avoid borrow conflicts with main_buf
-        let mut tmp_mask = std::mem::take(&mut ratatui_backend.mask_buffer);
-        let buf_len = main_buf.content.len();
-        if tmp_mask.len() < buf_len {
-            tmp_mask.resize(buf_len, 0);
-        }
-        render_drop_shadow(
-            main_buf,
-            &mut tmp_mask[..buf_len],
-            surface.dest,
-            1.0 - surface.z_depth,
-            &theme,
-        );
-        ratatui_backend.mask_buffer = tmp_mask;
-    }
+    (inner_bounds, chrome_registry)
+}
+
+/// Copy a window-local buffer drawn by [`render_window_surface`] to its
+/// screen position `dest`, touching only cells inside `clip`.
+pub(crate) fn blit_window(src: &Buffer, dst: &mut Buffer, dest: LayoutRect, clip: Rect) {
     // Compute desired destination rect and clip to main buffer
-    let src_off_x = u16::try_from(-surface.dest.x.min(0)).unwrap_or(0);
-    let src_off_y = u16::try_from(-surface.dest.y.min(0)).unwrap_or(0);
-    let dest_x = surface.dest.x.max(0) as u16;
-    let dest_y = surface.dest.y.max(0) as u16;
-    let copy_w = local_area.width.saturating_sub(src_off_x);
-    let copy_h = local_area.height.saturating_sub(src_off_y);
+    let src_off_x = u16::try_from(-dest.x.min(0)).unwrap_or(0);
+    let src_off_y = u16::try_from(-dest.y.min(0)).unwrap_or(0);
+    let dest_x = dest.x.max(0) as u16;
+    let dest_y = dest.y.max(0) as u16;
+    let copy_w = src.area.width.saturating_sub(src_off_x);
+    let copy_h = src.area.height.saturating_sub(src_off_y);
     let dst_area = Rect::new(dest_x, dest_y, copy_w, copy_h);
-    let dst_clip = dst_area.intersection(main_buf.area);
-    if dst_clip.width > 0 && dst_clip.height > 0 {
-        let src_clip = Rect {
-            x: src_off_x + (dst_clip.x - dest_x),
-            y: src_off_y + (dst_clip.y - dest_y),
-            width: dst_clip.width,
-            height: dst_clip.height,
-        };
-        let src_w = buffer.area.width as usize;
-        let dst_w = main_buf.area.width as usize;
-        let copy_w = dst_clip.width as usize;
-        let y_end = dst_clip.y.saturating_add(dst_clip.height);
-        for y in dst_clip.y..y_end {
-            let src_y = (y - dst_clip.y + src_clip.y) as usize;
-            let dst_y = (y - main_buf.area.y) as usize;
-            let src_x = src_clip.x as usize;
-            let dst_x = (dst_clip.x - main_buf.area.x) as usize;
-            let src_start = src_y * src_w + src_x;
-            let dst_start = dst_y * dst_w + dst_x;
-            main_buf.content[dst_start..dst_start + copy_w]
-                .clone_from_slice(&buffer.content[src_start..src_start + copy_w]);
-        }
+    let dst_clip = dst_area.intersection(dst.area).intersection(clip);
+    if dst_clip.width == 0 || dst_clip.height == 0 {
+        return;
+    }
+    let src_clip = Rect {
+        x: src_off_x + (dst_clip.x - dest_x),
+        y: src_off_y + (dst_clip.y - dest_y),
+        width: dst_clip.width,
+        height: dst_clip.height,
+    };
+    let src_w = src.area.width as usize;
+    let dst_w = dst.area.width as usize;
+    let copy_w = dst_clip.width as usize;
+    let y_end = dst_clip.y.saturating_add(dst_clip.height);
+    for y in dst_clip.y..y_end {
+        let src_y = (y - dst_clip.y + src_clip.y) as usize;
+        let dst_y = (y - dst.area.y) as usize;
+        let src_x = src_clip.x as usize;
+        let dst_x = (dst_clip.x - dst.area.x) as usize;
+        let src_start = src_y * src_w + src_x;
+        let dst_start = dst_y * dst_w + dst_x;
+        dst.content[dst_start..dst_start + copy_w]
+            .clone_from_slice(&src.content[src_start..src_start + copy_w]);
     }
-    // Return the resized buffer to the caller's scratch for reuse next frame
-    *scratch = buffer;
-    (inner_bounds, chrome_registry)
 }
 
 /// Render window chrome (borders, title bar, hover-aware buttons, Direct Mode indicator).
@@ -1164,6 +1243,10 @@ fn render_window(buffer: &mut Buffer, rect: LayoutRect, ctx: ChromeCtx<'_>) {
         wm_buttons,
         borders_enabled,
         header_enabled,
+        border_style: border_set,
+        title_alignment,
+        accent,
+        label,
     } = ctx;
 
     let focused_header_style = Style::default()
@@ -1173,14 +1256,18 @@ fn render_window(buffer: &mut Buffer, rect: LayoutRect, ctx: ChromeCtx<'_>) {
     let normal_header_style = Style::default()
         .bg(theme.panel_bg.to_ratatui())
         .fg(theme.decorator_header_fg.to_ratatui());
-    let border_style = if focused {
-        Style::default()
-            .fg(theme.decorator_border_active.to_ratatui())
+    let border_style = match (accent, focused) {
+        (Some(accent), true) => Style::default()
+            .fg(accent.to_ratatui())
             .bg(Color::Reset)
-    } else {
-        Style::default()
+            .add_modifier(Modifier::BOLD),
+        (Some(accent), false) => Style::default().fg(accent.to_ratatui()).bg(Color::Reset),
+        (None, true) => Style::default()
+            .fg(theme.decorator_border_active.to_ratatui())
+            .bg(Color::Reset),
+        (None, false) => Style::default()
             .fg(theme.decorator_border.to_ratatui())
-            .bg(Color::Reset)
+            .bg(Color::Reset),
     };
 
     let header_style = if focused {
@@ -1233,8 +1320,40 @@ fn render_window(buffer: &mut Buffer, rect: LayoutRect, ctx: ChromeCtx<'_>) {
             }
         }
         let header_width = header_right.saturating_sub(header_left).saturating_add(1);
-        let display_title = truncate_with_ellipsis(title, header_width as usize);
-        let start_x = header_left + (header_width.saturating_sub(display_title.width() as u16)) / 2;
+        // The label leads the title as a `[tag]` in the accent color.
+        let (text, label_width) = match label {
+            Some(label) => {
+                let tag = format!("[{label}]");
+                let tag_width = tag.width() as u16;
+                (format!("{tag} {title}"), tag_width)
+            }
+            None => (title.to_string(), 0),
+        };
+        // Left and right titles keep a cell of padding and stay clear of the
+        // buttons; a centered title is centered on the whole header.
+        let buttons_left = wm_buttons
+            .len()
+            .checked_sub(1)
+            .map_or(header_right.saturating_add(1), |last| {
+                button_x_pos(outer_right, borders_enabled, last)
+            });
+        let (span_left, span_width) = match title_alignment {
+            TitleAlignment::Center => (header_left, header_width),
+            TitleAlignment::Left | TitleAlignment::Right => {
+                let left = header_left.saturating_add(1).min(header_right);
+                (left, buttons_left.saturating_sub(1).saturating_sub(left))
+            }
+        };
+        let display_title = truncate_with_ellipsis(&text, span_width as usize);
+        let title_width = display_title.width() as u16;
+        let start_x = match title_alignment {
+            TitleAlignment::Left => span_left,
+            TitleAlignment::Center => span_left + span_width.saturating_sub(title_width) / 2,
+            TitleAlignment::Right => span_left + span_width.saturating_sub(title_width),
+        };
+        let label_style = header_style
+            .fg(accent.unwrap_or(theme.accent).to_ratatui())
+            .add_modifier(Modifier::BOLD);
         let buf_w = buffer.area.width as usize;
         let rel_y = header_y as usize - buffer.area.y as usize;
         let mut cx = start_x;
@@ -1243,13 +1362,18 @@ fn render_window(buffer: &mut Buffer, rect: LayoutRect, ctx: ChromeCtx<'_>) {
             if cw == 0 {
                 continue;
             }
+            let style = if cx - start_x < label_width {
+                label_style
+            } else {
+                header_style
+            };
             let rel_x = cx as usize - buffer.area.x as usize;
             buffer.content[rel_y * buf_w + rel_x].set_symbol(&c.to_string());
-            buffer.content[rel_y * buf_w + rel_x].set_style(header_style);
+            buffer.content[rel_y * buf_w + rel_x].set_style(style);
             for i in 1..cw {
                 let span_x = rel_x + i as usize;
                 buffer.content[rel_y * buf_w + span_x].set_symbol("");
-                buffer.content[rel_y * buf_w + span_x].set_style(header_style);
+                buffer.content[rel_y * buf_w + span_x].set_style(style);
             }
             cx += cw;
         }
@@ -1277,13 +1401,14 @@ fn render_window(buffer: &mut Buffer, rect: LayoutRect, ctx: ChromeCtx<'_>) {
         }
     }
 
-    // Borders — rounded corners for floating windows
-    if borders_enabled {
-        let (tl, tr, bl, br) = if floating {
-            ("╭", "╮", "╰", "╯")
-        } else {
-            ("┌", "┐", "└", "┘")
-        };
+    // Borders — the configured set (by default rounded only when floating)
+    if borders_enabled && let Some(symbols) = border_set.symbols(floating) {
+        let (tl, tr, bl, br) = (
+            symbols.top_left,
+            symbols.top_right,
+            symbols.bottom_left,
+            symbols.bottom_right,
+        );
         {
             let buf_w = buffer.area.width as usize;
             let rel_top = outer_top as usize - buffer.area.y as usize;
@@ -1301,7 +1426,7 @@ fn render_window(buffer: &mut Buffer, rect: LayoutRect, ctx: ChromeCtx<'_>) {
                 } else if x == outer_right {
                     tr
                 } else {
-                    "─"
+                    symbols.horizontal
                 };
                 cell.set_symbol(sym);
                 cell.set_style(border_style);
@@ -1318,7 +1443,7 @@ fn render_window(buffer: &mut Buffer, rect: LayoutRect, ctx: ChromeCtx<'_>) {
                 } else if x == outer_right {
                     br
                 } else {
-                    "─"
+                    symbols.horizontal
                 };
                 cell.set_symbol(sym);
                 cell.set_style(border_style);
@@ -1329,9 +1454,9 @@ fn render_window(buffer: &mut Buffer, rect: LayoutRect, ctx: ChromeCtx<'_>) {
                 - buffer.area.y as usize)..rel_bottom
             {
                 let row_start = rel_y * buf_w;
-                buffer.content[row_start + rel_left].set_symbol("│");
+                buffer.content[row_start + rel_left].set_symbol(symbols.vertical);
                 buffer.content[row_start + rel_left].set_style(border_style);
-                buffer.content[row_start + rel_right].set_symbol("│");
+                buffer.content[row_start + rel_right].set_symbol(symbols.vertical);
                 buffer.content[row_start + rel_right].set_style(border_style);
             }
         }
@@ -1376,6 +1501,12 @@ pub fn render_handles_masked(
             width: handle.rect.width,
             height: handle.rect.height,
         };
+        // A gap wider than one row keeps its bar on the middle row.
+        let hr = Rect {
+            y: hr.y + hr.height.saturating_sub(1) / 2,
+            height: 1,
+            ..hr
+        };
         let clip = hr.intersection(buffer.area);
         let is_hovered = hover_rect == Some(handle.rect);
         let style = if is_hovered {
@@ -1507,7 +1638,7 @@ pub fn render_handles_masked(
     }
 }
 
-/// Fill a handle's cells with a fixed bar character.
+/// Draw a vertical handle bar down the middle column of the handle's gap.
 fn fill_handle_bar(
     buffer: &mut Buffer,
     handle: &SplitHandle,
@@ -1526,9 +1657,9 @@ fn fill_handle_bar(
         Style::default().fg(theme.decorator_border_active.to_ratatui())
     };
     let hr = Rect {
-        x: handle.rect.x.max(0) as u16,
+        x: handle.rect.x.max(0) as u16 + handle.rect.width.saturating_sub(1) / 2,
         y: handle.rect.y.max(0) as u16,
-        width: handle.rect.width,
+        width: 1,
         height: handle.rect.height,
     };
     let clip = hr.intersection(buffer.area);
@@ -2020,6 +2151,10 @@ mod tests {
             wm_buttons: test_wm_buttons(),
             borders_enabled: true,
             header_enabled: true,
+            border_style: BorderStyle::default(),
+            title_alignment: TitleAlignment::default(),
+            accent: None,
+            label: None,
         };
 
         let mut scratch = Buffer::empty(RatatuiRect {
@@ -2112,6 +2247,10 @@ mod tests {
             wm_buttons: test_wm_buttons(),
             borders_enabled: true,
             header_enabled: true,
+            border_style: BorderStyle::default(),
+            title_alignment: TitleAlignment::default(),
+            accent: None,
+            label: None,
         };
 
         let mut scratch = Buffer::empty(RatatuiRect {
@@ -2364,6 +2503,10 @@ mod tests {
             wm_buttons: test_wm_buttons(),
             borders_enabled: true,
             header_enabled: true,
+            border_style: BorderStyle::default(),
+            title_alignment: TitleAlignment::default(),
+            accent: None,
+            label: None,
         };
         let rect = LayoutRect {
             x: 0,
@@ -2375,6 +2518,58 @@ mod tests {
         insta::assert_debug_snapshot!("chrome_header_buttons", buffer);
     }
 
+    fn buffer_row(buffer: &Buffer, y: u16) -> String {
+        (0..buffer.area.width)
+            .map(|x| buffer[(x, y)].symbol())
+            .collect()
+    }
+
+    #[test]
+    fn chrome_border_style_alignment_and_label() {
+        let area = RatatuiRect::new(0, 0, 30, 6);
+        let rect = LayoutRect {
+            x: 0,
+            y: 0,
+            width: 30,
+            height: 6,
+        };
+        let ctx = |title_alignment, label| ChromeCtx {
+            title: "vim",
+            focused: true,
+            floating: true,
+            hover_pos: None,
+            theme: NOIR,
+            wm_buttons: test_wm_buttons(),
+            borders_enabled: true,
+            header_enabled: true,
+            border_style: BorderStyle::Ascii,
+            title_alignment,
+            accent: Some(term_wm_core::theme::Color::Magenta),
+            label,
+        };
+
+        let mut buffer = Buffer::empty(area);
+        render_window(&mut buffer, rect, ctx(TitleAlignment::Left, Some("db")));
+        let header = buffer_row(&buffer, 1);
+        assert!(header.starts_with("| [db] vim"), "{header:?}");
+        assert_eq!(buffer_row(&buffer, 5), format!("+{}+", "-".repeat(28)));
+        assert_eq!(buffer[(0, 2)].symbol(), "|");
+        assert_eq!(buffer[(0, 2)].fg, ratatui::style::Color::Magenta);
+        assert_eq!(
+            buffer[(3, 1)].fg,
+            ratatui::style::Color::Magenta,
+            "label takes the accent"
+        );
+
+        let mut buffer = Buffer::empty(area);
+        render_window(&mut buffer, rect, ctx(TitleAlignment::Right, None));
+        let header = buffer_row(&buffer, 1);
+        let title_end = header.find("vim").unwrap() + "vim".len();
+        let first_button = header.find('_').unwrap();
+        assert!(title_end < first_button, "{header:?}");
+        assert_eq!(first_button - title_end, 1, "{header:?}");
+    }
+
     #[test]
     fn resize_outline_masked_by_extra_obscuring() {
         use term_wm_core::layout::floating::{ResizeEdge, ResizeHandle};
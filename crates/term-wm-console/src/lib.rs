@@ -2,7 +2,12 @@ pub mod background_console_reader;
 pub mod console_event_source;
 pub mod console_render_target;
 pub mod draw_plan_renderer;
+pub mod screenshot;
+pub mod terminal_capabilities;
 pub mod widget_adapter;
+pub mod window_damage;
+
+pub use terminal_capabilities::TerminalCapabilities;
 
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect as RatatuiRect;
@@ -16,6 +21,9 @@ pub struct RatatuiBackend {
     pub buffer: Buffer,
     pub area: RatatuiRect,
     pub mask_buffer: Vec<u8>,
+    /// Where the host terminal's own cursor goes this frame, in the style
+    /// the focused pane asked for. `None` keeps it hidden.
+    pub host_cursor: Option<term_wm_core::components::HostCursor>,
 }
 
 impl RenderBackend for RatatuiBackend {
@@ -39,6 +47,7 @@ impl RatatuiBackend {
             buffer,
             area,
             mask_buffer,
+            host_cursor: None,
         }
     }
 
@@ -49,6 +58,7 @@ impl RatatuiBackend {
             buffer,
             area,
             mask_buffer: Vec::new(),
+            host_cursor: None,
         }
     }
 
@@ -0,0 +1,515 @@
+//! Screenshot export: serialize a composed ratatui [`Buffer`] (the whole
+//! frame, or one window's cached buffer) as standalone HTML, SVG, an ANSI
+//! escape stream, or PNG (the SVG rasterized with resvg).
+//!
+//! HTML, SVG and PNG resolve `Color::Reset` to the theme's text and
+//! background colors; the ANSI stream keeps it, so a replay picks up the
+//! viewing terminal's own defaults.
+
+use std::fmt::Write as _;
+use std::io::{self, Write as _};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::style::{Color, Modifier};
+use resvg::{tiny_skia, usvg};
+use term_wm_core::screenshot::{ScreenshotFormat, screenshot_file_name};
+use term_wm_core::term_color::palette_rgb;
+use term_wm_core::theme::{Theme, color_rgb};
+use unicode_width::UnicodeWidthStr;
+
+/// SVG cell size for a 14px monospace font (0.6em advance, 1.2 line height).
+const CELL_WIDTH: f32 = 8.4;
+const CELL_HEIGHT: f32 = 17.0;
+const FONT_SIZE: f32 = 14.0;
+/// Distance from the top of a cell to the text baseline.
+const BASELINE: f32 = 13.0;
+const FONT_FAMILY: &str = "'DejaVu Sans Mono', Menlo, Consolas, monospace";
+
+type Rgb = (u8, u8, u8);
+
+/// How a cell looks once theme defaults, reverse video, dim and hidden are
+/// applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Look {
+    fg: Rgb,
+    bg: Rgb,
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    crossed_out: bool,
+}
+
+/// Consecutive cells of one row sharing a [`Look`].
+struct Run {
+    x: u16,
+    /// Columns covered, counting the hidden halves of wide characters.
+    width: u16,
+    look: Look,
+    text: String,
+}
+
+/// Encode `buffer` as `format`.
+pub fn encode(buffer: &Buffer, theme: &Theme, format: ScreenshotFormat) -> io::Result<Vec<u8>> {
+    Ok(match format {
+        ScreenshotFormat::Html => to_html(buffer, theme).into_bytes(),
+        ScreenshotFormat::Svg => to_svg(buffer, theme).into_bytes(),
+        ScreenshotFormat::Ansi => to_ansi(buffer).into_bytes(),
+        ScreenshotFormat::Png => to_png(buffer, theme)?,
+    })
+}
+
+/// Write `buffer` as `format` to a new timestamped file in `dir` (created if
+/// missing) and return its path.
+pub fn save(
+    buffer: &Buffer,
+    theme: &Theme,
+    format: ScreenshotFormat,
+    dir: &Path,
+) -> io::Result<PathBuf> {
+    let bytes = encode(buffer, theme, format)?;
+    std::fs::create_dir_all(dir)?;
+    let name = screenshot_file_name(format, SystemTime::now());
+    let stem = name.trim_end_matches(&format!(".{}", format.extension()));
+    // Exports run on their own threads: claim the name atomically, and let
+    // several exports within one second take `-2`, `-3`, ... suffixes.
+    let mut path = dir.join(&name);
+    let mut n = 2;
+    let mut file = loop {
+        match std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+        {
+            Ok(file) => break file,
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+                path = dir.join(format!("{stem}-{n}.{}", format.extension()));
+                n += 1;
+            }
+            Err(err) => return Err(err),
+        }
+    };
+    file.write_all(&bytes)?;
+    Ok(path)
+}
+
+/// A standalone HTML page: one `<pre>` with inline-styled spans.
+pub fn to_html(buffer: &Buffer, theme: &Theme) -> String {
+    let default = default_look(theme);
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>term-wm screenshot</title>\n</head>\n\
+         <body style=\"margin:0;background:{bg}\">\n\
+         <pre style=\"margin:0;padding:8px;font:{FONT_SIZE}px/{CELL_HEIGHT}px {FONT_FAMILY};\
+         color:{fg};background:{bg}\">",
+        fg = hex(default.fg),
+        bg = hex(default.bg),
+    );
+    for row in rows(buffer, theme) {
+        for run in row {
+            if run.look == default {
+                escape_into(&mut out, &run.text);
+                continue;
+            }
+            let _ = write!(
+                out,
+                "<span style=\"color:{};background:{}",
+                hex(run.look.fg),
+                hex(run.look.bg)
+            );
+            if run.look.bold {
+                out.push_str(";font-weight:bold");
+            }
+            if run.look.italic {
+                out.push_str(";font-style:italic");
+            }
+            if let Some(decoration) = decoration(&run.look) {
+                let _ = write!(out, ";text-decoration:{decoration}");
+            }
+            out.push_str("\">");
+            escape_into(&mut out, &run.text);
+            out.push_str("</span>");
+        }
+        out.push('\n');
+    }
+    out.push_str("</pre>\n</body>\n</html>\n");
+    out
+}
+
+/// SVG with one background rect and one `<text>` per run, laid out on a
+/// fixed cell grid.
+pub fn to_svg(buffer: &Buffer, theme: &Theme) -> String {
+    let default = default_look(theme);
+    let area = buffer.area;
+    let width = f32::from(area.width) * CELL_WIDTH;
+    let height = f32::from(area.height) * CELL_HEIGHT;
+    let rows = rows(buffer, theme);
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width:.1}\" height=\"{height:.1}\" \
+         viewBox=\"0 0 {width:.1} {height:.1}\">"
+    );
+    let _ = writeln!(
+        out,
+        "<rect width=\"{width:.1}\" height=\"{height:.1}\" fill=\"{}\"/>",
+        hex(default.bg)
+    );
+    out.push_str("<g shape-rendering=\"crispEdges\">\n");
+    for (y, row) in rows.iter().enumerate() {
+        for run in row.iter().filter(|run| run.look.bg != default.bg) {
+            let _ = writeln!(
+                out,
+                "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{CELL_HEIGHT:.1}\" fill=\"{}\"/>",
+                f32::from(run.x) * CELL_WIDTH,
+                y as f32 * CELL_HEIGHT,
+                f32::from(run.width) * CELL_WIDTH,
+                hex(run.look.bg)
+            );
+        }
+    }
+    out.push_str("</g>\n");
+    let _ = writeln!(
+        out,
+        "<g font-family=\"{FONT_FAMILY}\" font-size=\"{FONT_SIZE}\" xml:space=\"preserve\">"
+    );
+    for (y, row) in rows.iter().enumerate() {
+        for run in row.iter().filter(|run| !run.text.trim().is_empty()) {
+            let _ = write!(
+                out,
+                "<text x=\"{:.1}\" y=\"{:.1}\" fill=\"{}\"",
+                f32::from(run.x) * CELL_WIDTH,
+                y as f32 * CELL_HEIGHT + BASELINE,
+                hex(run.look.fg)
+            );
+            if run.look.bold {
+                out.push_str(" font-weight=\"bold\"");
+            }
+            if run.look.italic {
+                out.push_str(" font-style=\"italic\"");
+            }
+            if let Some(decoration) = decoration(&run.look) {
+                let _ = write!(out, " text-decoration=\"{decoration}\"");
+            }
+            out.push('>');
+            escape_into(&mut out, &run.text);
+            out.push_str("</text>\n");
+        }
+    }
+    out.push_str("</g>\n</svg>\n");
+    out
+}
+
+/// The SVG rendering rasterized with the system's fonts.
+pub fn to_png(buffer: &Buffer, theme: &Theme) -> io::Result<Vec<u8>> {
+    let svg = to_svg(buffer, theme);
+    let mut options = usvg::Options::default();
+    options.fontdb_mut().load_system_fonts();
+    let tree = usvg::Tree::from_str(&svg, &options).map_err(io::Error::other)?;
+    let size = tree.size().to_int_size();
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height())
+        .ok_or_else(|| io::Error::other("screenshot too large to rasterize"))?;
+    resvg::render(
+        &tree,
+        tiny_skia::Transform::identity(),
+        &mut pixmap.as_mut(),
+    );
+    pixmap.encode_png().map_err(io::Error::other)
+}
+
+/// SGR-styled text, one line per row, that reproduces the frame when
+/// written to a terminal.
+pub fn to_ansi(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut out = String::new();
+    for y in area.top()..area.bottom() {
+        let mut current: Option<String> = None;
+        let mut skip = 0usize;
+        for x in area.left()..area.right() {
+            let cell = &buffer[(x, y)];
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            let sgr = sgr(cell);
+            if current.as_ref() != Some(&sgr) {
+                out.push_str(&sgr);
+                current = Some(sgr);
+            }
+            let symbol = visible_symbol(cell);
+            out.push_str(symbol);
+            skip = symbol.width().saturating_sub(1);
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+fn rows(buffer: &Buffer, theme: &Theme) -> Vec<Vec<Run>> {
+    let area = buffer.area;
+    let mut rows = Vec::with_capacity(usize::from(area.height));
+    for y in area.top()..area.bottom() {
+        let mut row: Vec<Run> = Vec::new();
+        let mut x = area.left();
+        while x < area.right() {
+            let cell = &buffer[(x, y)];
+            let symbol = visible_symbol(cell);
+            let width = (symbol.width().max(1) as u16).min(area.right() - x);
+            let look = resolve(cell, theme);
+            match row.last_mut() {
+                Some(run) if run.look == look => {
+                    run.text.push_str(symbol);
+                    run.width += width;
+                }
+                _ => row.push(Run {
+                    x: x - area.left(),
+                    width,
+                    look,
+                    text: symbol.to_string(),
+                }),
+            }
+            x += width;
+        }
+        rows.push(row);
+    }
+    rows
+}
+
+/// The cell's symbol, with an empty one standing in as a space so columns
+/// stay aligned.
+fn visible_symbol(cell: &Cell) -> &str {
+    match cell.symbol() {
+        "" => " ",
+        symbol => symbol,
+    }
+}
+
+fn default_look(theme: &Theme) -> Look {
+    Look {
+        fg: color_rgb(theme.text),
+        bg: color_rgb(theme.background),
+        bold: false,
+        italic: false,
+        underline: false,
+        crossed_out: false,
+    }
+}
+
+fn resolve(cell: &Cell, theme: &Theme) -> Look {
+    let modifier = cell.modifier;
+    let mut fg = rgb(cell.fg).unwrap_or_else(|| color_rgb(theme.text));
+    let mut bg = rgb(cell.bg).unwrap_or_else(|| color_rgb(theme.background));
+    if modifier.contains(Modifier::REVERSED) {
+        std::mem::swap(&mut fg, &mut bg);
+    }
+    if modifier.contains(Modifier::DIM) {
+        fg = mix(fg, bg);
+    }
+    if modifier.contains(Modifier::HIDDEN) {
+        fg = bg;
+    }
+    Look {
+        fg,
+        bg,
+        bold: modifier.contains(Modifier::BOLD),
+        italic: modifier.contains(Modifier::ITALIC),
+        underline: modifier.contains(Modifier::UNDERLINED),
+        crossed_out: modifier.contains(Modifier::CROSSED_OUT),
+    }
+}
+
+/// RGB of a ratatui color; `None` for `Reset`.
+fn rgb(color: Color) -> Option<Rgb> {
+    Some(match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Indexed(idx) => palette_rgb(idx),
+        named => palette_rgb(ansi_index(named)?),
+    })
+}
+
+/// Palette index of a named ANSI color.
+fn ansi_index(color: Color) -> Option<u8> {
+    Some(match color {
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+        Color::Reset | Color::Rgb(..) | Color::Indexed(_) => return None,
+    })
+}
+
+fn mix(a: Rgb, b: Rgb) -> Rgb {
+    let half = |x: u8, y: u8| ((u16::from(x) + u16::from(y)) / 2) as u8;
+    (half(a.0, b.0), half(a.1, b.1), half(a.2, b.2))
+}
+
+fn hex((r, g, b): Rgb) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+fn decoration(look: &Look) -> Option<&'static str> {
+    match (look.underline, look.crossed_out) {
+        (true, true) => Some("underline line-through"),
+        (true, false) => Some("underline"),
+        (false, true) => Some("line-through"),
+        (false, false) => None,
+    }
+}
+
+fn escape_into(out: &mut String, text: &str) {
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(ch),
+        }
+    }
+}
+
+/// The complete SGR sequence for `cell`, starting from a reset so runs never
+/// inherit attributes from their left neighbor.
+fn sgr(cell: &Cell) -> String {
+    let mut params = vec!["0".to_string()];
+    for (flag, code) in [
+        (Modifier::BOLD, "1"),
+        (Modifier::DIM, "2"),
+        (Modifier::ITALIC, "3"),
+        (Modifier::UNDERLINED, "4"),
+        (Modifier::SLOW_BLINK, "5"),
+        (Modifier::RAPID_BLINK, "6"),
+        (Modifier::REVERSED, "7"),
+        (Modifier::HIDDEN, "8"),
+        (Modifier::CROSSED_OUT, "9"),
+    ] {
+        if cell.modifier.contains(flag) {
+            params.push(code.to_string());
+        }
+    }
+    params.extend(color_param(cell.fg, 30));
+    params.extend(color_param(cell.bg, 40));
+    format!("\x1b[{}m", params.join(";"))
+}
+
+/// SGR parameter selecting `color` as foreground (`base` 30) or background
+/// (`base` 40).
+fn color_param(color: Color, base: u8) -> Option<String> {
+    match color {
+        Color::Reset => None,
+        Color::Rgb(r, g, b) => Some(format!("{};2;{r};{g};{b}", base + 8)),
+        Color::Indexed(idx) => Some(format!("{};5;{idx}", base + 8)),
+        named => {
+            let idx = ansi_index(named)?;
+            Some(if idx < 8 {
+                (base + idx).to_string()
+            } else {
+                (base + 60 + idx - 8).to_string()
+            })
+        }
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+    use ratatui::style::Style;
+    use term_wm_core::theme::NOIR;
+
+    fn sample() -> Buffer {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 6, 2));
+        buf.set_string(0, 0, "a<b", Style::default().fg(Color::Red));
+        buf.set_string(3, 0, "界", Style::default().bg(Color::Rgb(1, 2, 3)));
+        buf.set_string(0, 1, "ok", Style::default().add_modifier(Modifier::BOLD));
+        buf
+    }
+
+    #[test]
+    fn html_escapes_and_styles_runs() {
+        let html = to_html(&sample(), &NOIR);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains(">a&lt;b</span>"));
+        assert!(html.contains("color:#cd0000"));
+        assert!(html.contains("background:#010203\">界</span>"));
+        assert!(html.contains("font-weight:bold\">ok</span>"));
+        assert!(html.trim_end().ends_with("</html>"));
+    }
+
+    #[test]
+    fn svg_places_runs_on_the_cell_grid() {
+        let svg = to_svg(&sample(), &NOIR);
+        assert!(svg.contains("width=\"50.4\" height=\"34.0\""));
+        // The wide glyph's background spans both of its columns.
+        assert!(svg.contains("<rect x=\"25.2\" y=\"0.0\" width=\"16.8\""));
+        assert!(svg.contains("<text x=\"0.0\" y=\"30.0\""));
+        assert!(svg.contains(">a&lt;b</text>"));
+        assert!(usvg::Tree::from_str(&svg, &usvg::Options::default()).is_ok());
+    }
+
+    #[test]
+    fn ansi_replays_colors_and_skips_wide_char_tails() {
+        let ansi = to_ansi(&sample());
+        let lines: Vec<&str> = ansi.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], "\x1b[0;31ma<b\x1b[0;48;2;1;2;3m界\x1b[0m \x1b[0m");
+        assert_eq!(lines[1], "\x1b[0;1mok\x1b[0m    \x1b[0m");
+    }
+
+    #[test]
+    fn png_has_the_grid_size() {
+        let png = to_png(&sample(), &NOIR).unwrap();
+        assert_eq!(&png[1..4], b"PNG");
+        // IHDR width/height, big-endian, right after the 16-byte header.
+        let dim = |at: usize| u32::from_be_bytes(png[at..at + 4].try_into().unwrap());
+        assert_eq!((dim(16), dim(20)), (50, 34));
+    }
+
+    #[test]
+    fn save_never_overwrites() {
+        let dir = tempfile::tempdir().unwrap();
+        let buf = sample();
+        let first = save(&buf, &NOIR, ScreenshotFormat::Ansi, dir.path()).unwrap();
+        let second = save(&buf, &NOIR, ScreenshotFormat::Ansi, dir.path()).unwrap();
+        assert_ne!(first, second);
+        assert_eq!(std::fs::read_to_string(&second).unwrap(), to_ansi(&buf));
+    }
+
+    #[test]
+    fn concurrent_saves_get_distinct_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let buf = sample();
+        let barrier = std::sync::Barrier::new(2);
+        let (a, b) = std::thread::scope(|scope| {
+            let save = || {
+                barrier.wait();
+                save(&buf, &NOIR, ScreenshotFormat::Ansi, dir.path()).unwrap()
+            };
+            let a = scope.spawn(save);
+            let b = scope.spawn(save);
+            (a.join().unwrap(), b.join().unwrap())
+        });
+        assert_ne!(a, b);
+        for path in [a, b] {
+            assert_eq!(std::fs::read_to_string(path).unwrap(), to_ansi(&buf));
+        }
+    }
+}
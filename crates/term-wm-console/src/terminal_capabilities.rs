@@ -0,0 +1,799 @@
+//! Host terminal capability detection.
+//!
+//! [`TerminalCapabilities::detect`] combines what the environment says
+//! (`NO_COLOR`, `COLORTERM`, `TERM` and its compiled terminfo entry, the
+//! locale) with one short query round-trip to the terminal itself:
+//!
+//! | Query | Answers |
+//! | :--- | :--- |
+//! | XTVERSION `CSI > 0 q` | terminal name and version |
+//! | DA2 `CSI > c` | terminal type / firmware version (VTE) |
+//! | DECRQSS `DCS $ q m ST` after a direct-color SGR | whether 24-bit color stuck |
+//! | XTGETTCAP `RGB`, `Tc`, `Ms` | truecolor and OSC 52 clipboard support |
+//! | DECRQM `CSI ? 2026 $ p` | synchronized output |
+//! | OSC 10/11 `? ST` | default foreground/background color |
+//! | DA1 `CSI c` | sent last — every terminal answers it, ending the wait |
+//!
+//! The result drives [`ConsoleRenderTarget`](crate::console_render_target::ConsoleRenderTarget):
+//! [`degrade_buffer`](TerminalCapabilities::degrade_buffer) quantizes each
+//! frame to the displayable palette and swaps box-drawing glyphs for ASCII,
+//! and the feature flags gate OSC 52, synchronized output and mouse capture.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::style::{Color, Modifier};
+use term_wm_core::events::{HostColorKind, HostDisplay};
+use term_wm_core::term_color::{ColorDepth, palette_rgb};
+use term_wm_core::theme::Color as CoreColor;
+
+/// How long [`TerminalCapabilities::detect`] waits for the terminal to answer
+/// before falling back to what the environment says.
+pub const PROBE_TIMEOUT: Duration = Duration::from_millis(150);
+
+/// Every query of the startup probe, DA1 last as the end-of-replies sentinel.
+/// The direct-color SGR is read back by DECRQSS and reset right after.
+const PROBE_QUERY: &str = concat!(
+    "\x1b[>0q",
+    "\x1b[>c",
+    "\x1b[38;2;1;2;3m",
+    "\x1bP$qm\x1b\\",
+    "\x1b[0m",
+    "\x1bP+q524742;5463;4d73\x1b\\",
+    "\x1b[?2026$p",
+    "\x1b]10;?\x1b\\",
+    "\x1b]11;?\x1b\\",
+    "\x1b[c",
+);
+
+/// XTVERSION names of terminals known to render 24-bit color.
+const TRUECOLOR_TERMINALS: &[&str] = &[
+    "kitty",
+    "wezterm",
+    "foot",
+    "ghostty",
+    "iterm2",
+    "contour",
+    "alacritty",
+    "konsole",
+];
+/// XTVERSION names of terminals known to honor synchronized output (mode 2026).
+const SYNC_OUTPUT_TERMINALS: &[&str] =
+    &["kitty", "wezterm", "foot", "ghostty", "contour", "iterm2"];
+
+/// What the host terminal can display and which optional protocols it speaks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TerminalCapabilities {
+    /// Deepest palette the terminal renders; frames are quantized to it.
+    pub color_depth: ColorDepth,
+    /// Whether box-drawing and block glyphs render; ASCII fallbacks otherwise.
+    pub unicode_box_drawing: bool,
+    /// Whether OSC 52 clipboard writes reach the host clipboard.
+    pub osc52: bool,
+    /// Whether frames may be wrapped in synchronized-output mode 2026.
+    pub synchronized_output: bool,
+    /// Whether xterm mouse reporting is available.
+    pub mouse: bool,
+    /// XTVERSION reply, e.g. `kitty(0.35.2)`.
+    pub terminal_version: Option<String>,
+    /// DA1 attributes (`CSI ? 62 ; 4 c` → `[62, 4]`).
+    pub device_attributes: Vec<u16>,
+    /// Default foreground color reported by OSC 10.
+    pub default_fg: Option<(u8, u8, u8)>,
+    /// Default background color reported by OSC 11.
+    pub default_bg: Option<(u8, u8, u8)>,
+}
+
+impl Default for TerminalCapabilities {
+    /// A modern xterm-compatible terminal; synchronized output stays off
+    /// until a terminal confirms it.
+    fn default() -> Self {
+        Self {
+            color_depth: ColorDepth::TrueColor,
+            unicode_box_drawing: true,
+            osc52: true,
+            synchronized_output: false,
+            mouse: true,
+            terminal_version: None,
+            device_attributes: Vec::new(),
+            default_fg: None,
+            default_bg: None,
+        }
+    }
+}
+
+impl TerminalCapabilities {
+    /// Probe the terminal on stdin/stdout and merge its answers with the
+    /// environment. Briefly enables raw mode if it is not already on, so
+    /// call it before any thread starts reading stdin. Skipped (environment
+    /// only) when stdio is not a terminal, on terminals that do not parse
+    /// DCS queries (`linux`, `dumb`, `vt*`), and when `TERM_WM_NO_TERMINAL_PROBE`
+    /// is set.
+    pub fn detect() -> Self {
+        let env = |key: &str| std::env::var(key).ok();
+        let mut replies = ProbeReplies::default();
+        if should_probe(&env) {
+            match query_terminal(PROBE_TIMEOUT) {
+                Ok(bytes) => replies = ProbeReplies::parse(&bytes),
+                Err(err) => tracing::debug!(%err, "terminal capability probe failed"),
+            }
+        }
+        let caps = Self::resolve(&env, &replies);
+        tracing::info!(?caps, "host terminal capabilities");
+        caps
+    }
+
+    /// Capabilities from the environment alone, without talking to the
+    /// terminal.
+    pub fn from_env() -> Self {
+        Self::resolve(
+            &|key: &str| std::env::var(key).ok(),
+            &ProbeReplies::default(),
+        )
+    }
+
+    fn resolve(env: &dyn Fn(&str) -> Option<String>, replies: &ProbeReplies) -> Self {
+        let term = env("TERM").unwrap_or_default();
+        let legacy = term == "dumb" || term.starts_with("vt");
+        let basic_console = legacy || term == "linux";
+        let name = replies
+            .xtversion
+            .as_deref()
+            .map(str::to_ascii_lowercase)
+            .unwrap_or_default();
+        let known = |list: &[&str]| list.iter().any(|t| name.starts_with(t));
+
+        let color_depth = if env("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+            ColorDepth::Mono
+        } else if env("COLORTERM").is_some_and(|v| {
+            let v = v.to_ascii_lowercase();
+            v.contains("truecolor") || v.contains("24bit")
+        }) || replies.truecolor()
+            || known(TRUECOLOR_TERMINALS)
+        {
+            ColorDepth::TrueColor
+        } else {
+            match terminfo_colors(&term, env) {
+                Some(n) if n >= 1 << 24 => ColorDepth::TrueColor,
+                Some(n) if n >= 256 => ColorDepth::Ansi256,
+                Some(n) if n >= 8 => ColorDepth::Ansi16,
+                Some(_) => ColorDepth::Mono,
+                None if term == "dumb" => ColorDepth::Mono,
+                None => ColorDepth::from_term(&term),
+            }
+        };
+
+        // An explicit non-UTF-8 codeset (`en_US.ISO-8859-1`) cannot encode the
+        // glyphs; a bare `C`/`POSIX` locale is too common on UTF-8 terminals to
+        // count against them.
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .into_iter()
+            .find_map(|key| env(key).filter(|v| !v.is_empty()));
+        let non_utf8_locale = locale.is_some_and(|l| {
+            let l = l.to_ascii_lowercase().replace('-', "");
+            l.contains('.') && !l.contains("utf8")
+        });
+
+        Self {
+            color_depth,
+            unicode_box_drawing: !legacy && !non_utf8_locale,
+            osc52: replies.tcap.get("Ms").copied().unwrap_or(!basic_console),
+            synchronized_output: replies
+                .sync_output
+                .unwrap_or_else(|| known(SYNC_OUTPUT_TERMINALS)),
+            mouse: !basic_console,
+            terminal_version: replies.xtversion.clone(),
+            device_attributes: replies.da1.clone().unwrap_or_default(),
+            default_fg: replies.default_fg,
+            default_bg: replies.default_bg,
+        }
+    }
+
+    /// The display side of these capabilities, as a session client reports
+    /// them to the window manager running inside the gateway.
+    pub fn host_display(&self) -> HostDisplay {
+        HostDisplay {
+            color_depth: self.color_depth.into(),
+            unicode_box_drawing: self.unicode_box_drawing,
+            osc52: self.osc52,
+            synchronized_output: self.synchronized_output,
+            mouse: self.mouse,
+        }
+    }
+
+    /// Take over what a session client probed on the real host terminal.
+    pub fn apply_host_display(&mut self, display: &HostDisplay) {
+        self.color_depth = display.color_depth.into();
+        self.unicode_box_drawing = display.unicode_box_drawing;
+        self.osc52 = display.osc52;
+        self.synchronized_output = display.synchronized_output;
+        self.mouse = display.mouse;
+    }
+
+    /// Rewrite a finished frame so it only uses what this terminal can show:
+    /// colors quantized to [`color_depth`](Self::color_depth) (16-color frames
+    /// use the named basic colors, monochrome frames fall back to reverse video
+    /// and bold) and, without Unicode, box-drawing glyphs replaced by ASCII.
+    /// A no-op on truecolor Unicode terminals.
+    pub fn degrade_buffer(&self, buffer: &mut Buffer) {
+        let depth = self.color_depth;
+        if depth == ColorDepth::TrueColor && self.unicode_box_drawing {
+            return;
+        }
+        // In monochrome the frame's dominant colors are "plain"; anything
+        // else was drawn to stand out and keeps doing so through attributes.
+        let base = (depth == ColorDepth::Mono).then(|| dominant_colors(buffer));
+        for cell in &mut buffer.content {
+            match base {
+                Some((base_fg, base_bg)) => mono_cell(cell, base_fg, base_bg),
+                None => {
+                    cell.fg = quantize(depth, cell.fg);
+                    cell.bg = quantize(depth, cell.bg);
+                }
+            }
+            if !self.unicode_box_drawing
+                && let Some(ascii) = ascii_fallback(cell.symbol())
+            {
+                cell.set_symbol(ascii);
+            }
+        }
+    }
+}
+
+/// Replies collected from one probe round-trip.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct ProbeReplies {
+    da1: Option<Vec<u16>>,
+    da2: Option<Vec<u16>>,
+    xtversion: Option<String>,
+    /// DECRQSS read-back of the direct-color SGR: `Some(true)` if it stuck.
+    direct_color_sgr: Option<bool>,
+    /// XTGETTCAP answers, keyed by capability name.
+    tcap: HashMap<String, bool>,
+    /// DECRQM answer for mode 2026; `None` when unrecognized.
+    sync_output: Option<bool>,
+    /// OSC 10/11 answers.
+    default_fg: Option<(u8, u8, u8)>,
+    default_bg: Option<(u8, u8, u8)>,
+}
+
+impl ProbeReplies {
+    fn parse(bytes: &[u8]) -> Self {
+        let mut replies = Self::default();
+        let mut i = 0;
+        while i + 1 < bytes.len() {
+            if bytes[i] != 0x1b {
+                i += 1;
+                continue;
+            }
+            let start = i + 2;
+            match bytes[i + 1] {
+                b'[' => {
+                    let Some(len) = bytes[start..]
+                        .iter()
+                        .position(|b| (0x40..=0x7e).contains(b))
+                    else {
+                        break;
+                    };
+                    replies.csi(&bytes[start..start + len], bytes[start + len]);
+                    i = start + len + 1;
+                }
+                b'P' => {
+                    let Some((end, next)) = find_string_terminator(bytes, start) else {
+                        break;
+                    };
+                    replies.dcs(&String::from_utf8_lossy(&bytes[start..end]));
+                    i = next;
+                }
+                b']' => {
+                    let Some((end, next)) = find_string_terminator(bytes, start) else {
+                        break;
+                    };
+                    match HostColorKind::parse_reply(&String::from_utf8_lossy(&bytes[start..end])) {
+                        Some((HostColorKind::Foreground, rgb)) => replies.default_fg = Some(rgb),
+                        Some((HostColorKind::Background, rgb)) => replies.default_bg = Some(rgb),
+                        None => {}
+                    }
+                    i = next;
+                }
+                _ => i += 1,
+            }
+        }
+        replies
+    }
+
+    fn csi(&mut self, body: &[u8], final_byte: u8) {
+        let body = String::from_utf8_lossy(body);
+        let numbers = |params: &str| -> Vec<u16> {
+            params.split(';').filter_map(|p| p.parse().ok()).collect()
+        };
+        match final_byte {
+            b'c' => {
+                if let Some(params) = body.strip_prefix('?') {
+                    self.da1 = Some(numbers(params));
+                } else if let Some(params) = body.strip_prefix('>') {
+                    self.da2 = Some(numbers(params));
+                }
+            }
+            b'y' => {
+                if let Some(params) = body.strip_prefix('?').and_then(|b| b.strip_suffix('$'))
+                    && let [2026, state, ..] = numbers(params)[..]
+                {
+                    self.sync_output = Some(matches!(state, 1..=3));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn dcs(&mut self, body: &str) {
+        if let Some(version) = body.strip_prefix(">|") {
+            self.xtversion = Some(version.to_string());
+        } else if let Some(sgr) = body.strip_prefix("1$r") {
+            self.direct_color_sgr = Some(sgr.contains(":1:2:3") || sgr.contains(";1;2;3"));
+        } else if body.starts_with("0$r") {
+            self.direct_color_sgr = Some(false);
+        } else if let Some((supported, caps)) = body
+            .strip_prefix("1+r")
+            .map(|caps| (true, caps))
+            .or_else(|| body.strip_prefix("0+r").map(|caps| (false, caps)))
+        {
+            for cap in caps.split(';') {
+                let hex_name = cap.split('=').next().unwrap_or_default();
+                if let Some(name) = decode_hex(hex_name) {
+                    self.tcap.insert(name, supported);
+                }
+            }
+        }
+    }
+
+    fn truecolor(&self) -> bool {
+        // VTE answers DA2 with `65;<version>`; 0.36 (3600) added direct color.
+        let vte = matches!(self.da2.as_deref(), Some([65, version, ..]) if *version >= 3600);
+        self.direct_color_sgr == Some(true)
+            || self.tcap.get("RGB") == Some(&true)
+            || self.tcap.get("Tc") == Some(&true)
+            || vte
+    }
+}
+
+/// End of a DCS or OSC payload starting at `start`: the payload end and the index
+/// just past the terminator (`ESC \`, BEL or C1 ST).
+fn find_string_terminator(bytes: &[u8], start: usize) -> Option<(usize, usize)> {
+    (start..bytes.len()).find_map(|i| match bytes[i] {
+        0x07 | 0x9c => Some((i, i + 1)),
+        0x1b if bytes.get(i + 1) == Some(&b'\\') => Some((i, i + 2)),
+        _ => None,
+    })
+}
+
+fn decode_hex(hex: &str) -> Option<String> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    String::from_utf8(bytes).ok()
+}
+
+fn should_probe(env: &dyn Fn(&str) -> Option<String>) -> bool {
+    let term = env("TERM").unwrap_or_default();
+    env(term_wm_config::NO_TERMINAL_PROBE_ENV_VAR).is_none()
+        && term != "dumb"
+        && term != "linux"
+        && !term.starts_with("vt")
+}
+
+/// Write [`PROBE_QUERY`] and collect the replies until DA1 arrives or
+/// `timeout` passes.
+#[cfg(unix)]
+fn query_terminal(timeout: Duration) -> io::Result<Vec<u8>> {
+    use std::io::{IsTerminal, Write};
+    use std::time::Instant;
+
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        return Ok(Vec::new());
+    }
+    let was_raw = crossterm::terminal::is_raw_mode_enabled()?;
+    if !was_raw {
+        crossterm::terminal::enable_raw_mode()?;
+    }
+    let result = (|| {
+        let mut out = io::stdout().lock();
+        out.write_all(PROBE_QUERY.as_bytes())?;
+        out.flush()?;
+        drop(out);
+
+        let deadline = Instant::now() + timeout;
+        let mut replies = Vec::new();
+        let mut chunk = [0u8; 512];
+        while ProbeReplies::parse(&replies).da1.is_none() {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+            let mut fd = libc::pollfd {
+                fd: libc::STDIN_FILENO,
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            let millis = remaining.as_millis().clamp(1, i32::MAX as u128) as i32;
+            // SAFETY: `fd` is a valid pollfd for the duration of the call.
+            if unsafe { libc::poll(&mut fd, 1, millis) } <= 0 {
+                break;
+            }
+            // SAFETY: `chunk` is a writable buffer of `chunk.len()` bytes.
+            let n =
+                unsafe { libc::read(libc::STDIN_FILENO, chunk.as_mut_ptr().cast(), chunk.len()) };
+            if n <= 0 {
+                break;
+            }
+            replies.extend_from_slice(&chunk[..n as usize]);
+        }
+        Ok(replies)
+    })();
+    if !was_raw {
+        crossterm::terminal::disable_raw_mode()?;
+    }
+    result
+}
+
+/// Windows consoles answer through the console input API rather than stdin
+/// bytes; rely on the environment there.
+#[cfg(not(unix))]
+fn query_terminal(_timeout: Duration) -> io::Result<Vec<u8>> {
+    Ok(Vec::new())
+}
+
+/// The `colors` number of `term`'s compiled terminfo entry: `Some(-1)` when
+/// the entry has none, `None` when no entry was found.
+fn terminfo_colors(term: &str, env: &dyn Fn(&str) -> Option<String>) -> Option<i32> {
+    let first = term.chars().next()?;
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    dirs.extend(env("TERMINFO").map(PathBuf::from));
+    dirs.extend(env("HOME").map(|home| PathBuf::from(home).join(".terminfo")));
+    if let Some(list) = env("TERMINFO_DIRS") {
+        dirs.extend(list.split(':').map(|dir| {
+            PathBuf::from(if dir.is_empty() {
+                "/usr/share/terminfo"
+            } else {
+                dir
+            })
+        }));
+    }
+    dirs.extend(["/etc/terminfo", "/lib/terminfo", "/usr/share/terminfo"].map(PathBuf::from));
+    // ncurses files entries under their first letter; macOS under its hex code.
+    let subdirs = [first.to_string(), format!("{:x}", first as u32)];
+    dirs.iter()
+        .flat_map(|dir| subdirs.iter().map(move |sub| dir.join(sub).join(term)))
+        .find_map(|path| std::fs::read(path).ok())
+        .and_then(|data| parse_terminfo_colors(&data))
+}
+
+/// Read `colors` (numeric capability #13) from a compiled terminfo entry in
+/// either the legacy 16-bit or the ncurses 6 32-bit number format.
+fn parse_terminfo_colors(data: &[u8]) -> Option<i32> {
+    const COLORS: usize = 13;
+    let short = |i: usize| {
+        Some(u16::from_le_bytes([
+            *data.get(2 * i)?,
+            *data.get(2 * i + 1)?,
+        ]))
+    };
+    let width = match short(0)? {
+        0o432 => 2,
+        0o1036 => 4,
+        _ => return None,
+    };
+    let (names, bools, numbers) = (short(1)? as usize, short(2)? as usize, short(3)? as usize);
+    if numbers <= COLORS {
+        return Some(-1);
+    }
+    let mut offset = 12 + names + bools;
+    offset += offset % 2;
+    let at = offset + COLORS * width;
+    let raw = data.get(at..at + width)?;
+    Some(match *raw {
+        [a, b] => i16::from_le_bytes([a, b]) as i32,
+        [a, b, c, d] => i32::from_le_bytes([a, b, c, d]),
+        _ => return None,
+    })
+}
+
+/// The closest color to `color` that a `depth` terminal displays.
+fn quantize(depth: ColorDepth, color: Color) -> Color {
+    let rgb = match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Indexed(idx) => palette_rgb(idx),
+        _ => return color,
+    };
+    match (depth, color) {
+        (ColorDepth::TrueColor, _) | (ColorDepth::Ansi256, Color::Indexed(_)) => color,
+        _ => match depth.quantize(rgb.0, rgb.1, rgb.2).0 {
+            CoreColor::Indexed(idx) if depth == ColorDepth::Ansi256 => Color::Indexed(idx),
+            CoreColor::Indexed(idx) => named(idx),
+            _ => color,
+        },
+    }
+}
+
+/// The named color for a basic palette index.
+fn named(idx: u8) -> Color {
+    match idx {
+        0 => Color::Black,
+        1 => Color::Red,
+        2 => Color::Green,
+        3 => Color::Yellow,
+        4 => Color::Blue,
+        5 => Color::Magenta,
+        6 => Color::Cyan,
+        7 => Color::Gray,
+        8 => Color::DarkGray,
+        9 => Color::LightRed,
+        10 => Color::LightGreen,
+        11 => Color::LightYellow,
+        12 => Color::LightBlue,
+        13 => Color::LightMagenta,
+        14 => Color::LightCyan,
+        _ => Color::White,
+    }
+}
+
+/// Perceived brightness in 0..=255, or `None` for the terminal default.
+fn luma(color: Color) -> Option<u32> {
+    let (r, g, b) = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Indexed(idx) => palette_rgb(idx),
+        Color::Black => palette_rgb(0),
+        Color::Red => palette_rgb(1),
+        Color::Green => palette_rgb(2),
+        Color::Yellow => palette_rgb(3),
+        Color::Blue => palette_rgb(4),
+        Color::Magenta => palette_rgb(5),
+        Color::Cyan => palette_rgb(6),
+        Color::Gray => palette_rgb(7),
+        Color::DarkGray => palette_rgb(8),
+        Color::LightRed => palette_rgb(9),
+        Color::LightGreen => palette_rgb(10),
+        Color::LightYellow => palette_rgb(11),
+        Color::LightBlue => palette_rgb(12),
+        Color::LightMagenta => palette_rgb(13),
+        Color::LightCyan => palette_rgb(14),
+        Color::White => palette_rgb(15),
+    };
+    Some((r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000)
+}
+
+/// The most common foreground and background of the frame (first seen wins
+/// ties, keeping the choice stable across frames).
+fn dominant_colors(buffer: &Buffer) -> (Color, Color) {
+    fn most_common(colors: impl Iterator<Item = Color>) -> Color {
+        let mut counts: HashMap<Color, (usize, usize)> = HashMap::new();
+        for (seen, color) in colors.enumerate() {
+            counts.entry(color).or_insert((0, seen)).0 += 1;
+        }
+        counts
+            .into_iter()
+            .max_by(|(_, (a, a_seen)), (_, (b, b_seen))| a.cmp(b).then(b_seen.cmp(a_seen)))
+            .map_or(Color::Reset, |(color, _)| color)
+    }
+    (
+        most_common(buffer.content.iter().map(|cell| cell.fg)),
+        most_common(buffer.content.iter().map(|cell| cell.bg)),
+    )
+}
+
+/// Strip a cell's colors, keeping highlights visible: a non-base background
+/// becomes reverse video, a non-base foreground bold, and half-block pixels
+/// are thresholded into `█`/`▀`/`▄`/space.
+fn mono_cell(cell: &mut Cell, base_fg: Color, base_bg: Color) {
+    // Unset colors are the terminal defaults: light text on a dark screen.
+    let lit = |color: Color, default: bool| luma(color).map_or(default, |l| l >= 128);
+    let half = match cell.symbol() {
+        "▀" => Some((lit(cell.fg, true), lit(cell.bg, false))),
+        "▄" => Some((lit(cell.bg, false), lit(cell.fg, true))),
+        _ => None,
+    };
+    if let Some((top, bottom)) = half {
+        cell.set_symbol(match (top, bottom) {
+            (true, true) => "█",
+            (true, false) => "▀",
+            (false, true) => "▄",
+            (false, false) => " ",
+        });
+    } else if cell.bg != base_bg {
+        cell.modifier |= Modifier::REVERSED;
+    } else if cell.fg != base_fg && cell.symbol() != " " {
+        cell.modifier |= Modifier::BOLD;
+    }
+    cell.fg = Color::Reset;
+    cell.bg = Color::Reset;
+}
+
+/// ASCII stand-in for a box-drawing or block-element glyph.
+fn ascii_fallback(symbol: &str) -> Option<&'static str> {
+    let mut chars = symbol.chars();
+    let c = chars.next()?;
+    if chars.next().is_some() {
+        return None;
+    }
+    match c {
+        '─' | '━' | '┄' | '┅' | '┈' | '┉' | '╌' | '╍' | '═' | '╴' | '╶' | '╸' | '╺' | '╼' | '╾' => {
+            Some("-")
+        }
+        '│' | '┃' | '┆' | '┇' | '┊' | '┋' | '╎' | '╏' | '║' | '╵' | '╷' | '╹' | '╻' | '╽' | '╿' => {
+            Some("|")
+        }
+        '\u{2500}'..='\u{257f}' => Some("+"),
+        '\u{2580}'..='\u{259f}' => Some("#"),
+        _ => None,
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+
+    fn env_of<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |key| {
+            vars.iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, v)| v.to_string())
+        }
+    }
+
+    #[test]
+    fn parses_every_probe_reply() {
+        let bytes = b"\x1bP>|kitty(0.35.2)\x1b\\\x1b[>1;4000;21c\x1bP1$r0;38:2:1:2:3m\x1b\\\
+            \x1bP1+r524742=382F382F38;4d73\x1b\\\x1bP0+r5463\x1b\\\x1b[?2026;2$y\
+            \x1b]10;rgb:0000/0000/0000\x1b\\\x1b]11;rgb:ffff/ffff/dddd\x07\x1b[?62;4;22c";
+        let replies = ProbeReplies::parse(bytes);
+        assert_eq!(replies.xtversion.as_deref(), Some("kitty(0.35.2)"));
+        assert_eq!(replies.da2, Some(vec![1, 4000, 21]));
+        assert_eq!(replies.direct_color_sgr, Some(true));
+        assert_eq!(replies.tcap.get("RGB"), Some(&true));
+        assert_eq!(replies.tcap.get("Ms"), Some(&true));
+        assert_eq!(replies.tcap.get("Tc"), Some(&false));
+        assert_eq!(replies.sync_output, Some(true));
+        assert_eq!(replies.default_fg, Some((0, 0, 0)));
+        assert_eq!(replies.default_bg, Some((255, 255, 221)));
+        assert_eq!(replies.da1, Some(vec![62, 4, 22]));
+        assert!(replies.truecolor());
+    }
+
+    #[test]
+    fn only_da1_means_nothing_is_confirmed() {
+        let replies = ProbeReplies::parse(b"\x1b[?1;2c");
+        let caps = TerminalCapabilities::resolve(&env_of(&[("TERM", "xterm-256color")]), &replies);
+        assert_eq!(caps.color_depth, ColorDepth::Ansi256);
+        assert!(!caps.synchronized_output);
+        assert!(caps.osc52 && caps.mouse && caps.unicode_box_drawing);
+        assert_eq!(caps.device_attributes, vec![1, 2]);
+    }
+
+    #[test]
+    fn environment_decides_without_replies() {
+        let none = ProbeReplies::default();
+        let resolve = |vars: &[(&str, &str)]| TerminalCapabilities::resolve(&env_of(vars), &none);
+
+        let caps = resolve(&[
+            ("TERM", "xterm-256color"),
+            ("NO_COLOR", "1"),
+            ("COLORTERM", "truecolor"),
+        ]);
+        assert_eq!(caps.color_depth, ColorDepth::Mono);
+        let caps = resolve(&[("TERM", "xterm"), ("COLORTERM", "24bit")]);
+        assert_eq!(caps.color_depth, ColorDepth::TrueColor);
+
+        let caps = resolve(&[("TERM", "linux"), ("LANG", "en_US.UTF-8")]);
+        assert_eq!(caps.color_depth, ColorDepth::Ansi16);
+        assert!(caps.unicode_box_drawing);
+        assert!(!caps.mouse && !caps.osc52);
+
+        let caps = resolve(&[("TERM", "vt100")]);
+        assert!(!caps.unicode_box_drawing);
+        let caps = resolve(&[("TERM", "xterm"), ("LC_ALL", "de_DE.ISO-8859-1")]);
+        assert!(!caps.unicode_box_drawing);
+        let caps = resolve(&[("TERM", "xterm"), ("LANG", "C")]);
+        assert!(caps.unicode_box_drawing);
+    }
+
+    #[test]
+    fn reads_colors_from_compiled_terminfo() {
+        // Minimal legacy-format entry: names "t|test\0", no bools, 14 numbers.
+        let names = b"t|test\0";
+        let mut entry = Vec::new();
+        for v in [0o432u16, names.len() as u16, 0, 14, 0, 0] {
+            entry.extend_from_slice(&v.to_le_bytes());
+        }
+        entry.extend_from_slice(names);
+        entry.push(0); // pad to an even offset
+        for i in 0..14u16 {
+            let v: i16 = if i == 13 { 16 } else { -1 };
+            entry.extend_from_slice(&v.to_le_bytes());
+        }
+        assert_eq!(parse_terminfo_colors(&entry), Some(16));
+
+        let dir = std::env::temp_dir().join(format!("term-wm-terminfo-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("t")).unwrap();
+        std::fs::write(dir.join("t").join("test16"), &entry).unwrap();
+        let dir_str = dir.to_string_lossy().to_string();
+        let caps = TerminalCapabilities::resolve(
+            &env_of(&[("TERM", "test16"), ("TERMINFO", &dir_str)]),
+            &ProbeReplies::default(),
+        );
+        std::fs::remove_dir_all(&dir).ok();
+        assert_eq!(caps.color_depth, ColorDepth::Ansi16);
+    }
+
+    #[test]
+    fn degrades_frames_to_sixteen_colors_and_ascii() {
+        let caps = TerminalCapabilities {
+            color_depth: ColorDepth::Ansi16,
+            unicode_box_drawing: false,
+            ..TerminalCapabilities::default()
+        };
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 3, 1));
+        buffer[(0, 0)]
+            .set_symbol("┌")
+            .set_fg(Color::Rgb(250, 10, 10));
+        buffer[(1, 0)].set_symbol("─").set_bg(Color::Indexed(21));
+        buffer[(2, 0)].set_symbol("│").set_fg(Color::Yellow);
+        caps.degrade_buffer(&mut buffer);
+        assert_eq!(buffer[(0, 0)].symbol(), "+");
+        assert_eq!(buffer[(0, 0)].fg, Color::LightRed);
+        assert_eq!(buffer[(1, 0)].symbol(), "-");
+        assert_eq!(buffer[(1, 0)].bg, Color::Blue);
+        assert_eq!(buffer[(2, 0)].symbol(), "|");
+        assert_eq!(buffer[(2, 0)].fg, Color::Yellow);
+    }
+
+    #[test]
+    fn monochrome_frames_keep_highlights_as_attributes() {
+        let caps = TerminalCapabilities {
+            color_depth: ColorDepth::Mono,
+            ..TerminalCapabilities::default()
+        };
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 6, 1));
+        for x in 0..6 {
+            buffer[(x, 0)]
+                .set_symbol("a")
+                .set_fg(Color::Gray)
+                .set_bg(Color::Black);
+        }
+        buffer[(1, 0)].set_bg(Color::Blue);
+        buffer[(2, 0)].set_fg(Color::Cyan);
+        buffer[(3, 0)]
+            .set_symbol("▀")
+            .set_fg(Color::Black)
+            .set_bg(Color::White);
+        caps.degrade_buffer(&mut buffer);
+        assert!(
+            buffer
+                .content
+                .iter()
+                .all(|c| c.fg == Color::Reset && c.bg == Color::Reset)
+        );
+        assert!(buffer[(0, 0)].modifier.is_empty());
+        assert!(buffer[(1, 0)].modifier.contains(Modifier::REVERSED));
+        assert!(buffer[(2, 0)].modifier.contains(Modifier::BOLD));
+        assert_eq!(buffer[(3, 0)].symbol(), "▄");
+    }
+
+    #[test]
+    fn truecolor_unicode_frames_are_untouched() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 1, 1));
+        buffer[(0, 0)].set_symbol("┌").set_fg(Color::Rgb(1, 2, 3));
+        let before = buffer.clone();
+        TerminalCapabilities::default().degrade_buffer(&mut buffer);
+        assert_eq!(buffer, before);
+    }
+}
@@ -0,0 +1,488 @@
+//! Damage tracking for composited windows.
+//!
+//! Each window's chrome and content are drawn into a buffer of its own that
+//! is kept between frames, and the windows are stacked (with their drop
+//! shadows) into a persistent window layer that is copied into every frame.
+//! A window whose [`WindowSignature`] is unchanged and whose component has
+//! nothing new to draw keeps its cached buffer, and only the screen
+//! rectangles covered by redrawn, moved or closed windows are restacked —
+//! so a screen of idle panes costs one layer copy per frame.
+
+use std::collections::HashMap;
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect};
+use term_wm_core::chrome::{BorderStyle, TitleAlignment};
+use term_wm_core::constants::{SHADOW_OFFSET_X, SHADOW_OFFSET_Y};
+use term_wm_core::hitbox_registry::HitboxRegistry;
+use term_wm_core::theme::{Color, Theme};
+use term_wm_core::window::{WindowKey, WindowSurface};
+use term_wm_layout_engine::LayoutRect;
+
+use crate::draw_plan_renderer::{
+    ChromeCtx, apply_drop_shadow, blit_buffer, blit_window, layout_rect_to_clipped_rect,
+    shadow_color,
+};
+
+/// Everything outside a window's component that shapes its composited
+/// frame: geometry, stacking, chrome state and the WM's damage epoch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowSignature {
+    full: LayoutRect,
+    dest: LayoutRect,
+    z_depth: f32,
+    draw_shadow: bool,
+    focused: bool,
+    floating: bool,
+    title: String,
+    theme: String,
+    hover_pos: Option<(u16, u16)>,
+    buttons: Vec<&'static str>,
+    borders_enabled: bool,
+    header_enabled: bool,
+    border_style: BorderStyle,
+    title_alignment: TitleAlignment,
+    accent: Option<Color>,
+    label: Option<String>,
+    epoch: u64,
+}
+
+impl WindowSignature {
+    pub fn new(surface: &WindowSurface, ctx: &ChromeCtx<'_>, epoch: u64) -> Self {
+        Self {
+            full: surface.full,
+            dest: surface.dest,
+            // Depth only tints the shadow; stacking changes elsewhere in the
+            // plan must not invalidate shadowless (tiled) windows.
+            z_depth: if surface.draw_shadow {
+                surface.z_depth
+            } else {
+                0.0
+            },
+            draw_shadow: surface.draw_shadow,
+            focused: ctx.focused,
+            floating: ctx.floating,
+            title: ctx.title.to_string(),
+            theme: ctx.theme.name.to_string(),
+            // Chrome only reacts to the pointer over its own cells, so moving
+            // it elsewhere must not invalidate this window.
+            hover_pos: ctx.hover_pos.filter(|&(x, y)| {
+                layout_rect_to_clipped_rect(surface.dest).contains(Position::new(x, y))
+            }),
+            buttons: ctx.wm_buttons.iter().map(|b| b.symbol).collect(),
+            borders_enabled: ctx.borders_enabled,
+            header_enabled: ctx.header_enabled,
+            border_style: ctx.border_style,
+            title_alignment: ctx.title_alignment,
+            accent: ctx.accent,
+            label: ctx.label.map(str::to_string),
+            epoch,
+        }
+    }
+
+    /// Screen cells the window and its shadow cover, clipped to `area`.
+    fn footprint(&self, area: Rect) -> Rect {
+        let window = layout_rect_to_clipped_rect(self.dest);
+        let footprint = if self.draw_shadow {
+            let shadow = layout_rect_to_clipped_rect(self.shadow_rect());
+            if shadow.is_empty() {
+                window
+            } else {
+                window.union(shadow)
+            }
+        } else {
+            window
+        };
+        footprint.intersection(area)
+    }
+
+    fn shadow_rect(&self) -> LayoutRect {
+        LayoutRect {
+            x: self.dest.x.saturating_add(SHADOW_OFFSET_X),
+            y: self.dest.y.saturating_add(SHADOW_OFFSET_Y),
+            ..self.dest
+        }
+    }
+}
+
+/// Window counts of the last composited frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WindowDamageStats {
+    /// Windows whose chrome and content were drawn again.
+    pub redrawn: usize,
+    /// Windows whose cached buffer was reused as is.
+    pub reused: usize,
+    /// Screen cells restacked into the window layer.
+    pub damaged_cells: usize,
+}
+
+struct CachedWindow {
+    signature: WindowSignature,
+    /// Chrome + content in window-local coordinates.
+    buffer: Buffer,
+    /// Content and chrome hitboxes registered when `buffer` was drawn.
+    hitboxes: HitboxRegistry,
+    shadow: Option<ratatui::style::Color>,
+    footprint: Rect,
+    /// Frame in which the window was last part of the draw plan.
+    frame: u64,
+}
+
+/// Per-window frame cache plus the stacked window layer.
+pub struct WindowDamage {
+    enabled: bool,
+    layer: Buffer,
+    mask: Vec<u8>,
+    windows: HashMap<WindowKey, CachedWindow>,
+    /// Stacking order of the current frame, bottom first.
+    order: Vec<WindowKey>,
+    damage: Vec<Rect>,
+    frame: u64,
+    stats: WindowDamageStats,
+}
+
+impl WindowDamage {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            layer: Buffer::empty(Rect::ZERO),
+            mask: Vec::new(),
+            windows: HashMap::new(),
+            order: Vec::new(),
+            damage: Vec::new(),
+            frame: 0,
+            stats: WindowDamageStats::default(),
+        }
+    }
+
+    /// Turn caching off to redraw and restack every window each frame.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn stats(&self) -> WindowDamageStats {
+        self.stats
+    }
+
+    /// Chrome and content of `key` as drawn for the last frame, in
+    /// window-local coordinates; `None` when it was not on screen.
+    pub fn window_buffer(&self, key: WindowKey) -> Option<&Buffer> {
+        self.windows.get(&key).map(|cached| &cached.buffer)
+    }
+
+    /// Start a frame covering `area`. A new size damages the whole layer.
+    pub fn begin_frame(&mut self, area: Rect) {
+        self.frame = self.frame.wrapping_add(1);
+        self.order.clear();
+        self.damage.clear();
+        self.stats = WindowDamageStats::default();
+        if self.layer.area != area {
+            self.layer.resize(area);
+            self.damage.push(area);
+        }
+        if !self.enabled {
+            self.damage.push(area);
+        }
+    }
+
+    /// Reuse the cached frame of `key` if it was drawn under `signature`,
+    /// returning the hitboxes it registered back then.
+    pub fn reuse(&mut self, key: WindowKey, signature: &WindowSignature) -> Option<HitboxRegistry> {
+        if !self.enabled {
+            return None;
+        }
+        let cached = self
+            .windows
+            .get_mut(&key)
+            .filter(|cached| cached.signature == *signature)?;
+        cached.frame = self.frame;
+        self.order.push(key);
+        self.stats.reused += 1;
+        Some(cached.hitboxes.clone())
+    }
+
+    /// The buffer to redraw `key` into, recycling its cached allocation.
+    pub fn take_buffer(&mut self, key: WindowKey) -> Buffer {
+        self.windows
+            .get_mut(&key)
+            .map(|cached| std::mem::replace(&mut cached.buffer, Buffer::empty(Rect::ZERO)))
+            .unwrap_or_else(|| Buffer::empty(Rect::ZERO))
+    }
+
+    /// Cache a freshly drawn window and damage both where it was and where
+    /// it is now.
+    pub fn store(
+        &mut self,
+        key: WindowKey,
+        signature: WindowSignature,
+        buffer: Buffer,
+        hitboxes: HitboxRegistry,
+        theme: &Theme,
+    ) {
+        let footprint = signature.footprint(self.layer.area);
+        let shadow = signature
+            .draw_shadow
+            .then(|| shadow_color(theme, 1.0 - signature.z_depth));
+        let previous = self.windows.insert(
+            key,
+            CachedWindow {
+                signature,
+                buffer,
+                hitboxes,
+                shadow,
+                footprint,
+                frame: self.frame,
+            },
+        );
+        if let Some(previous) = previous
+            && previous.footprint != footprint
+        {
+            self.damage.push(previous.footprint);
+        }
+        self.damage.push(footprint);
+        self.order.push(key);
+        self.stats.redrawn += 1;
+    }
+
+    /// Forget windows missing from this frame, restack every damaged
+    /// rectangle and copy the window layer into `frame`.
+    pub fn finish_frame(&mut self, frame: &mut Buffer) {
+        let current = self.frame;
+        let damage = &mut self.damage;
+        self.windows.retain(|_, cached| {
+            let kept = cached.frame == current;
+            if !kept {
+                damage.push(cached.footprint);
+            }
+            kept
+        });
+        let area = self.layer.area;
+        if self.damage.contains(&area) {
+            self.damage.clear();
+            self.damage.push(area);
+        }
+
+        let buf_len = self.layer.content.len();
+        if self.mask.len() < buf_len {
+            self.mask.resize(buf_len, 0);
+        }
+        for rect in std::mem::take(&mut self.damage) {
+            let rect = rect.intersection(self.layer.area);
+            if rect.is_empty() {
+                continue;
+            }
+            self.stats.damaged_cells += rect.area() as usize;
+            for y in rect.top()..rect.bottom() {
+                for x in rect.left()..rect.right() {
+                    self.layer[(x, y)].reset();
+                }
+            }
+            for key in &self.order {
+                let Some(cached) = self.windows.get(key) else {
+                    continue;
+                };
+                if !cached.footprint.intersects(rect) {
+                    continue;
+                }
+                if let Some(color) = cached.shadow {
+                    apply_drop_shadow(
+                        &mut self.layer,
+                        &mut self.mask[..buf_len],
+                        cached.signature.dest,
+                        color,
+                        rect,
+                    );
+                }
+                blit_window(&cached.buffer, &mut self.layer, cached.signature.dest, rect);
+            }
+        }
+        let frame_area = frame.area;
+        blit_buffer(&self.layer, frame, frame_area);
+    }
+}
+
+impl Default for WindowDamage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::draw_plan_renderer::render_window_surface;
+    use term_wm_core::hitbox_registry::HitboxId;
+    use term_wm_core::theme::NOIR;
+
+    const AREA: Rect = Rect {
+        x: 0,
+        y: 0,
+        width: 40,
+        height: 12,
+    };
+
+    fn key(n: u64) -> WindowKey {
+        slotmap::KeyData::from_ffi((1 << 32) | n).into()
+    }
+
+    struct Win {
+        key: WindowKey,
+        dest: LayoutRect,
+        floating: bool,
+        fill: &'static str,
+    }
+
+    fn win(n: u64, x: i32, y: i32, width: u16, height: u16, floating: bool) -> Win {
+        Win {
+            key: key(n),
+            dest: LayoutRect {
+                x,
+                y,
+                width,
+                height,
+            },
+            floating,
+            fill: "a",
+        }
+    }
+
+    /// One frame of `render_app`'s window loop: `dirty` windows draw again,
+    /// the rest reuse their cache when their signature still matches.
+    fn frame(damage: &mut WindowDamage, windows: &[Win], dirty: &[WindowKey]) -> Buffer {
+        damage.begin_frame(AREA);
+        for (i, w) in windows.iter().enumerate() {
+            let surface = WindowSurface {
+                full: w.dest,
+                inner: w.dest,
+                dest: w.dest,
+                draw_shadow: w.floating,
+                z_depth: i as f32 / windows.len() as f32,
+            };
+            let ctx = ChromeCtx {
+                title: "win",
+                focused: w.floating,
+                floating: w.floating,
+                hover_pos: None,
+                theme: NOIR,
+                wm_buttons: Vec::new(),
+                borders_enabled: true,
+                header_enabled: true,
+                border_style: BorderStyle::default(),
+                title_alignment: TitleAlignment::default(),
+                accent: None,
+                label: None,
+            };
+            let signature = WindowSignature::new(&surface, &ctx, 0);
+            if !dirty.contains(&w.key) && damage.reuse(w.key, &signature).is_some() {
+                continue;
+            }
+            let mut buffer = damage.take_buffer(w.key);
+            let (_, hitboxes) = render_window_surface(
+                &surface,
+                w.key,
+                HitboxId::new(),
+                ctx,
+                |backend, inner| {
+                    let rb = backend
+                        .as_any_mut()
+                        .downcast_mut::<crate::RatatuiBackend>()
+                        .unwrap();
+                    let inner = layout_rect_to_clipped_rect(inner).intersection(rb.buffer.area);
+                    for y in inner.top()..inner.bottom() {
+                        for x in inner.left()..inner.right() {
+                            rb.buffer[(x, y)].set_symbol(w.fill);
+                        }
+                    }
+                },
+                &mut buffer,
+            );
+            damage.store(w.key, signature, buffer, hitboxes, &NOIR);
+        }
+        let mut out = Buffer::empty(AREA);
+        damage.finish_frame(&mut out);
+        out
+    }
+
+    /// What a cache-less compositor would produce for the same windows.
+    fn full_frame(windows: &[Win]) -> Buffer {
+        let mut damage = WindowDamage::new();
+        damage.set_enabled(false);
+        frame(&mut damage, windows, &[])
+    }
+
+    fn tiled_pair() -> Vec<Win> {
+        vec![win(1, 0, 0, 20, 12, false), win(2, 20, 0, 20, 12, false)]
+    }
+
+    #[test]
+    fn idle_windows_reuse_their_cached_frames() {
+        let mut damage = WindowDamage::new();
+        let windows = tiled_pair();
+        let first = frame(&mut damage, &windows, &[]);
+        assert_eq!(damage.stats().redrawn, 2);
+        assert_eq!(damage.stats().damaged_cells, 40 * 12);
+
+        let second = frame(&mut damage, &windows, &[]);
+        assert_eq!(
+            damage.stats(),
+            WindowDamageStats {
+                redrawn: 0,
+                reused: 2,
+                damaged_cells: 0,
+            }
+        );
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn only_the_dirty_window_is_restacked() {
+        let mut damage = WindowDamage::new();
+        let mut windows = tiled_pair();
+        frame(&mut damage, &windows, &[]);
+
+        windows[1].fill = "b";
+        let out = frame(&mut damage, &windows, &[key(2)]);
+        assert_eq!(damage.stats().redrawn, 1);
+        assert_eq!(damage.stats().reused, 1);
+        assert_eq!(damage.stats().damaged_cells, 20 * 12);
+        assert_eq!(out, full_frame(&windows));
+    }
+
+    #[test]
+    fn floating_window_over_a_dirty_pane_keeps_its_shadow() {
+        let mut damage = WindowDamage::new();
+        let mut windows = tiled_pair();
+        windows.push(win(3, 10, 3, 16, 5, true));
+        frame(&mut damage, &windows, &[]);
+
+        // The pane under the floating window and its shadow redraws; the
+        // floating window itself is reused but must be restacked on top.
+        windows[0].fill = "b";
+        let out = frame(&mut damage, &windows, &[key(1)]);
+        assert_eq!(damage.stats().redrawn, 1);
+        assert_eq!(out, full_frame(&windows));
+    }
+
+    #[test]
+    fn moved_and_closed_windows_damage_their_old_footprint() {
+        let mut damage = WindowDamage::new();
+        let mut windows = tiled_pair();
+        windows.push(win(3, 10, 3, 16, 5, true));
+        frame(&mut damage, &windows, &[]);
+
+        windows[2].dest.x = 2;
+        let out = frame(&mut damage, &windows, &[]);
+        assert_eq!(damage.stats().redrawn, 1);
+        assert_eq!(out, full_frame(&windows));
+
+        windows.pop();
+        let out = frame(&mut damage, &windows, &[]);
+        assert_eq!(damage.stats().redrawn, 0);
+        assert_eq!(out, full_frame(&windows));
+    }
+}
@@ -1,5 +1,8 @@
+use std::any::{Any, TypeId};
 use std::fmt;
+use std::sync::Arc;
 
+use crate::screenshot::ScreenshotFormat;
 use crate::window::WindowKey;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -102,6 +105,22 @@ pub enum TermWmAction {
     ToggleDebugWindow,
     ExitUi,
     ToggleSystemPanel,
+    /// Re-theme the UI with the named theme from the window manager's
+    /// [`ThemeRegistry`](crate::theme_registry::ThemeRegistry). An empty name
+    /// in a supported-actions list enables every theme entry.
+    SwitchTheme(String),
+    /// Save the whole screen, as composed in the next frame, to the
+    /// screenshot directory. Any `ExportScreenshot` in a supported-actions
+    /// list enables every screenshot entry, whole-screen and per-window.
+    ExportScreenshot(ScreenshotFormat),
+    /// Save one window's chrome and content as of the next frame.
+    ExportWindowScreenshot(WindowKey, ScreenshotFormat),
+    /// Color one window's border and label; `None` returns it to the theme.
+    SetWindowAccent(WindowKey, Option<crate::theme::Color>),
+    /// Tag one window with a label shown ahead of its title; `None` clears
+    /// it. The command palette fills in the label from its search query
+    /// (see [`TermWmAction::with_query_argument`]).
+    SetWindowLabel(WindowKey, Option<String>),
     SendNotification(String),
 
     // Clipboard
@@ -166,6 +185,9 @@ pub enum TermWmAction {
     // without modifying the framework enum. The numeric code is
     // application-defined; components interpret it in `update()`.
     Custom(u16),
+    /// A typed application message (see [`TermWmAction::message`]). Routed
+    /// like any component action: back to the window that emitted it.
+    Message(AppMessage),
 
     // --- Workspace actions ---
     /// Switch the outer viewer to a different workspace channel.
@@ -174,6 +196,10 @@ pub enum TermWmAction {
     /// Create a new workspace (prompts for name).
     #[cfg(feature = "session-persistence")]
     NewWorkspace,
+    /// Rename the current workspace. The command palette fills in the new
+    /// name from its search query (see [`TermWmAction::with_query_argument`]).
+    #[cfg(feature = "session-persistence")]
+    RenameWorkspace(String),
     /// Detach the current viewer connection from the session.
     #[cfg(feature = "session-persistence")]
     DetachCurrentClient,
@@ -224,7 +250,104 @@ impl<Msg> EventResult<Msg> {
     }
 }
 
+/// A user-defined message value carried by [`TermWmAction::Message`].
+///
+/// Applications define their own `Msg` enum and wrap values with
+/// [`TermWmAction::message`]; the receiving window gets them back with
+/// [`downcast_ref`](Self::downcast_ref). The payload is shared, so cloning
+/// the action (e.g. a button emitting it on every click) is cheap. Messages
+/// must be `Send + Sync`, like the rest of `TermWmAction` (keybinding tables
+/// holding actions live in statics).
+///
+/// Equality is by identity: clones of one message are equal, two separately
+/// built messages are not, even with equal payloads. Hashing is by payload
+/// type and ordering by type, then identity. This keeps `Msg` free of
+/// `Eq`/`Hash`/`Ord` bounds, so messages may carry `f64`s and the like; compare
+/// payloads by value through [`downcast_ref`](Self::downcast_ref) where an
+/// app needs to.
+#[derive(Clone)]
+pub struct AppMessage {
+    payload: Arc<dyn Any + Send + Sync>,
+    debug: fn(&dyn Any, &mut fmt::Formatter<'_>) -> fmt::Result,
+}
+
+impl AppMessage {
+    pub fn new<M: fmt::Debug + Send + Sync + 'static>(msg: M) -> Self {
+        Self {
+            payload: Arc::new(msg),
+            debug: |payload, f| match payload.downcast_ref::<M>() {
+                Some(msg) => fmt::Debug::fmt(msg, f),
+                None => f.write_str("AppMessage"),
+            },
+        }
+    }
+
+    pub fn is<M: 'static>(&self) -> bool {
+        (*self.payload).is::<M>()
+    }
+
+    pub fn downcast_ref<M: 'static>(&self) -> Option<&M> {
+        (*self.payload).downcast_ref()
+    }
+
+    fn payload_type(&self) -> TypeId {
+        (*self.payload).type_id()
+    }
+
+    fn addr(&self) -> usize {
+        Arc::as_ptr(&self.payload).cast::<()>() as usize
+    }
+}
+
+impl fmt::Debug for AppMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (self.debug)(&*self.payload, f)
+    }
+}
+
+impl PartialEq for AppMessage {
+    fn eq(&self, other: &Self) -> bool {
+        self.payload_type() == other.payload_type() && Arc::ptr_eq(&self.payload, &other.payload)
+    }
+}
+
+impl Eq for AppMessage {}
+
+impl std::hash::Hash for AppMessage {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.payload_type().hash(state);
+    }
+}
+
+impl PartialOrd for AppMessage {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for AppMessage {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.payload_type()
+            .cmp(&other.payload_type())
+            .then_with(|| self.addr().cmp(&other.addr()))
+    }
+}
+
 impl TermWmAction {
+    /// Wrap an application-defined message, e.g.
+    /// `TermWmAction::message(Msg::Refresh)`.
+    pub fn message<M: fmt::Debug + Send + Sync + 'static>(msg: M) -> Self {
+        Self::Message(AppMessage::new(msg))
+    }
+
+    /// The payload of a [`Message`](Self::Message) action, if it is an `M`.
+    pub fn as_message<M: 'static>(&self) -> Option<&M> {
+        match self {
+            Self::Message(msg) => msg.downcast_ref(),
+            _ => None,
+        }
+    }
+
     pub fn layer(&self) -> ActionLayer {
         match self {
             TermWmAction::OpenCommandPalette => ActionLayer::Global,
@@ -235,6 +358,25 @@ impl TermWmAction {
         }
     }
 
+    /// For palette entries that take their argument from the search query,
+    /// the action to run for `query`; `None` for every other action.
+    pub fn with_query_argument(&self, query: &str) -> Option<TermWmAction> {
+        let query = query.trim();
+        if query.is_empty() {
+            return None;
+        }
+        match self {
+            TermWmAction::SetWindowLabel(key, Some(_)) => {
+                Some(TermWmAction::SetWindowLabel(*key, Some(query.to_string())))
+            }
+            #[cfg(feature = "session-persistence")]
+            TermWmAction::RenameWorkspace(_) => {
+                Some(TermWmAction::RenameWorkspace(query.to_string()))
+            }
+            _ => None,
+        }
+    }
+
     pub fn category(&self) -> Category {
         match self {
             TermWmAction::Quit
@@ -244,7 +386,8 @@ impl TermWmAction {
             | TermWmAction::ProcessExited
             | TermWmAction::ProfileChange(_)
             | TermWmAction::RequestKeyboardFocus(_)
-            | TermWmAction::Custom(_) => Category::System,
+            | TermWmAction::Custom(_)
+            | TermWmAction::Message(_) => Category::System,
             TermWmAction::Callback(_)
             | TermWmAction::CycleNextWindow
             | TermWmAction::CyclePrevWindow
@@ -268,6 +411,11 @@ impl TermWmAction {
             | TermWmAction::ToggleDebugWindow
             | TermWmAction::ExitUi
             | TermWmAction::ToggleSystemPanel
+            | TermWmAction::SwitchTheme(_)
+            | TermWmAction::ExportScreenshot(_)
+            | TermWmAction::ExportWindowScreenshot(..)
+            | TermWmAction::SetWindowAccent(..)
+            | TermWmAction::SetWindowLabel(..)
             | TermWmAction::SendNotification(_)
             | TermWmAction::OpenCommandPalette
             | TermWmAction::CloseCommandPalette
@@ -282,6 +430,7 @@ impl TermWmAction {
             #[cfg(feature = "session-persistence")]
             TermWmAction::SwitchWorkspace(_)
             | TermWmAction::NewWorkspace
+            | TermWmAction::RenameWorkspace(_)
             | TermWmAction::DetachCurrentClient => Category::Windows,
 
             TermWmAction::MenuUp
@@ -397,6 +546,34 @@ impl fmt::Display for TermWmAction {
             TermWmAction::ToggleDebugWindow => "Toggle Debug Window",
             TermWmAction::ExitUi => "Exit UI",
             TermWmAction::ToggleSystemPanel => "Toggle System Panel",
+            TermWmAction::SwitchTheme(name) => {
+                if name.is_empty() {
+                    "Switch Theme"
+                } else {
+                    return write!(f, "Switch Theme: {name}");
+                }
+            }
+            TermWmAction::ExportScreenshot(format) => {
+                return write!(f, "Export Screenshot: {}", format.label());
+            }
+            TermWmAction::ExportWindowScreenshot(_, format) => {
+                return write!(f, "Export Window Screenshot: {}", format.label());
+            }
+            TermWmAction::SetWindowAccent(_, accent) => {
+                let name = match accent {
+                    None => "Theme",
+                    Some(color) => crate::chrome::accent_name(*color).unwrap_or("Custom"),
+                };
+                return write!(f, "Window Accent: {name}");
+            }
+            TermWmAction::SetWindowLabel(_, None) => "Clear Window Label",
+            TermWmAction::SetWindowLabel(_, Some(label)) => {
+                if label.is_empty() {
+                    "Label Window"
+                } else {
+                    return write!(f, "Label Window: {label}");
+                }
+            }
             TermWmAction::SendNotification(_) => "Send Notification",
             TermWmAction::ConfirmAction(_) => "Confirm Action",
             TermWmAction::ClipboardPaste(_) => "Clipboard Paste",
@@ -422,6 +599,7 @@ impl fmt::Display for TermWmAction {
             TermWmAction::PanDown => "Pan Down",
             TermWmAction::CycleViewMode => "Cycle View",
             TermWmAction::Custom(_) => "Custom Action",
+            TermWmAction::Message(_) => "Application Message",
             #[cfg(feature = "session-persistence")]
             TermWmAction::SwitchWorkspace(name) => {
                 return write!(f, "Switch to Workspace: {name}");
@@ -429,6 +607,14 @@ impl fmt::Display for TermWmAction {
             #[cfg(feature = "session-persistence")]
             TermWmAction::NewWorkspace => "New Workspace",
             #[cfg(feature = "session-persistence")]
+            TermWmAction::RenameWorkspace(name) => {
+                if name.is_empty() {
+                    "Rename Workspace"
+                } else {
+                    return write!(f, "Rename Workspace to: {name}");
+                }
+            }
+            #[cfg(feature = "session-persistence")]
             TermWmAction::DetachCurrentClient => "Detach Viewer",
         };
         write!(f, "{}", s)
@@ -455,6 +641,11 @@ pub enum SystemTask {
     /// The Direct Mode toast debounce window elapsed — flush the buffered
     /// mode for the window as a single toast.
     FlushDirectModeToast(WindowKey),
+    /// A screenshot was exported mid-frame — push its toast.
+    FlushScreenshotToast,
+    /// Screenshots are being written off the render thread — announce the
+    /// ones that finished.
+    PollScreenshotExports,
     /// Tab outline has elapsed — restore palette/panels to normal.
     ClearTabOutline,
 }
@@ -532,6 +723,39 @@ mod tests {
             (TermWmAction::ToggleDebugWindow, "Toggle Debug Window"),
             (TermWmAction::ExitUi, "Exit UI"),
             (TermWmAction::ToggleSystemPanel, "Toggle System Panel"),
+            (TermWmAction::SwitchTheme(String::new()), "Switch Theme"),
+            (
+                TermWmAction::SwitchTheme("paper".into()),
+                "Switch Theme: paper",
+            ),
+            (
+                TermWmAction::ExportScreenshot(ScreenshotFormat::Html),
+                "Export Screenshot: HTML",
+            ),
+            (
+                TermWmAction::ExportWindowScreenshot(key, ScreenshotFormat::Ansi),
+                "Export Window Screenshot: ANSI",
+            ),
+            (
+                TermWmAction::SetWindowAccent(key, Some(crate::theme::Color::Cyan)),
+                "Window Accent: Cyan",
+            ),
+            (
+                TermWmAction::SetWindowAccent(key, None),
+                "Window Accent: Theme",
+            ),
+            (
+                TermWmAction::SetWindowLabel(key, Some(String::new())),
+                "Label Window",
+            ),
+            (
+                TermWmAction::SetWindowLabel(key, Some("db".into())),
+                "Label Window: db",
+            ),
+            (
+                TermWmAction::SetWindowLabel(key, None),
+                "Clear Window Label",
+            ),
             (
                 TermWmAction::SendNotification("hi".into()),
                 "Send Notification",
@@ -578,6 +802,7 @@ mod tests {
             (TermWmAction::PanDown, "Pan Down"),
             (TermWmAction::CycleViewMode, "Cycle View"),
             (TermWmAction::Custom(4), "Custom Action"),
+            (TermWmAction::message(4_u64), "Application Message"),
             #[cfg(feature = "session-persistence")]
             (
                 TermWmAction::SwitchWorkspace("dev".into()),
@@ -586,6 +811,16 @@ mod tests {
             #[cfg(feature = "session-persistence")]
             (TermWmAction::NewWorkspace, "New Workspace"),
             #[cfg(feature = "session-persistence")]
+            (
+                TermWmAction::RenameWorkspace(String::new()),
+                "Rename Workspace",
+            ),
+            #[cfg(feature = "session-persistence")]
+            (
+                TermWmAction::RenameWorkspace("ops".into()),
+                "Rename Workspace to: ops",
+            ),
+            #[cfg(feature = "session-persistence")]
             (TermWmAction::DetachCurrentClient, "Detach Viewer"),
         ];
         for (action, expected) in cases {
@@ -604,6 +839,10 @@ mod tests {
                 Category::Windows
             );
             assert_eq!(TermWmAction::NewWorkspace.category(), Category::Windows);
+            assert_eq!(
+                TermWmAction::RenameWorkspace(String::new()).category(),
+                Category::Windows
+            );
             assert_eq!(
                 TermWmAction::DetachCurrentClient.category(),
                 Category::Windows
@@ -620,6 +859,30 @@ mod tests {
         assert_eq!(TermWmAction::ClearSelection.category(), Category::Selection);
     }
 
+    #[test]
+    fn query_argument_only_fills_query_taking_actions() {
+        assert_eq!(TermWmAction::NewTerminal.with_query_argument("ops"), None);
+        let key = WindowKey::default();
+        assert_eq!(
+            TermWmAction::SetWindowLabel(key, Some(String::new())).with_query_argument("db"),
+            Some(TermWmAction::SetWindowLabel(key, Some("db".into())))
+        );
+        // Clearing takes no argument.
+        assert_eq!(
+            TermWmAction::SetWindowLabel(key, None).with_query_argument("db"),
+            None
+        );
+        #[cfg(feature = "session-persistence")]
+        {
+            let rename = TermWmAction::RenameWorkspace(String::new());
+            assert_eq!(
+                rename.with_query_argument(" ops "),
+                Some(TermWmAction::RenameWorkspace("ops".into()))
+            );
+            assert_eq!(rename.with_query_argument("  "), None);
+        }
+    }
+
     #[test]
     fn bottom_hint_priorities_are_stable() {
         assert_eq!(
@@ -665,4 +928,35 @@ mod tests {
         assert_eq!(acted.clone().into_action(), Some(41));
         assert!(matches!(acted.map(|v| v + 1), EventResult::Action(42)));
     }
+
+    #[test]
+    fn app_messages_downcast_and_compare_by_identity() {
+        use std::hash::{BuildHasher, RandomState};
+
+        // No `Eq`/`Hash`/`Ord` needed: messages may carry floats.
+        #[derive(Debug, Clone, PartialEq)]
+        enum Msg {
+            Open(String),
+            Zoom(f64),
+        }
+        let action = TermWmAction::message(Msg::Open("a.txt".into()));
+        assert_eq!(action.as_message::<Msg>(), Some(&Msg::Open("a.txt".into())));
+        assert_eq!(action.as_message::<u32>(), None);
+        assert_eq!(TermWmAction::Quit.as_message::<Msg>(), None);
+        assert_eq!(format!("{action:?}"), "Message(Open(\"a.txt\"))");
+
+        let copy = action.clone();
+        assert_eq!(copy, action);
+        assert_eq!(copy.cmp(&action), std::cmp::Ordering::Equal);
+        let same = TermWmAction::message(Msg::Open("a.txt".into()));
+        assert_ne!(action, same);
+        assert_ne!(action.cmp(&same), std::cmp::Ordering::Equal);
+        assert_eq!(action.as_message::<Msg>(), same.as_message::<Msg>());
+        let hasher = RandomState::new();
+        assert_eq!(hasher.hash_one(&action), hasher.hash_one(&copy));
+        let zoom = TermWmAction::message(Msg::Zoom(1.5));
+        assert_eq!(zoom.as_message::<Msg>(), Some(&Msg::Zoom(1.5)));
+        assert_ne!(TermWmAction::message(7_u32), TermWmAction::message(7_u64));
+        assert_eq!(action.category(), Category::System);
+    }
 }
@@ -1,4 +1,5 @@
 pub mod metrics;
+pub mod style;
 pub mod target;
 
 pub use crate::constants::CHROME_BOTTOM_ROW as BOTTOM_BORDER_HEIGHT;
@@ -8,4 +9,5 @@ pub use crate::constants::CHROME_RIGHT_COL as RIGHT_BORDER_WIDTH;
 pub use crate::constants::CHROME_TOP_ROW as TOP_BORDER_HEIGHT;
 pub use metrics::button_x_pos;
 pub use metrics::content_rect;
+pub use style::{ACCENT_COLORS, BorderStyle, BorderSymbols, TitleAlignment, accent_name};
 pub use target::ChromeTarget;
@@ -0,0 +1,245 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::theme::Color;
+
+/// Line-drawing set used for window borders.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum BorderStyle {
+    /// Square corners on tiled windows, rounded ones on floating windows.
+    #[default]
+    Standard,
+    Plain,
+    Rounded,
+    Double,
+    Heavy,
+    /// `+`, `-` and `|` only, for consoles without box-drawing glyphs (the
+    /// Linux VT).
+    Ascii,
+    /// No borders: windows get the border rows and columns back as content.
+    None,
+}
+
+/// The glyphs of one border set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BorderSymbols {
+    pub top_left: &'static str,
+    pub top_right: &'static str,
+    pub bottom_left: &'static str,
+    pub bottom_right: &'static str,
+    pub horizontal: &'static str,
+    pub vertical: &'static str,
+}
+
+const PLAIN: BorderSymbols = BorderSymbols {
+    top_left: "┌",
+    top_right: "┐",
+    bottom_left: "└",
+    bottom_right: "┘",
+    horizontal: "─",
+    vertical: "│",
+};
+
+const ROUNDED: BorderSymbols = BorderSymbols {
+    top_left: "╭",
+    top_right: "╮",
+    bottom_left: "╰",
+    bottom_right: "╯",
+    ..PLAIN
+};
+
+const DOUBLE: BorderSymbols = BorderSymbols {
+    top_left: "╔",
+    top_right: "╗",
+    bottom_left: "╚",
+    bottom_right: "╝",
+    horizontal: "═",
+    vertical: "║",
+};
+
+const HEAVY: BorderSymbols = BorderSymbols {
+    top_left: "┏",
+    top_right: "┓",
+    bottom_left: "┗",
+    bottom_right: "┛",
+    horizontal: "━",
+    vertical: "┃",
+};
+
+const ASCII: BorderSymbols = BorderSymbols {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    horizontal: "-",
+    vertical: "|",
+};
+
+impl BorderStyle {
+    pub const ALL: [BorderStyle; 7] = [
+        BorderStyle::Standard,
+        BorderStyle::Plain,
+        BorderStyle::Rounded,
+        BorderStyle::Double,
+        BorderStyle::Heavy,
+        BorderStyle::Ascii,
+        BorderStyle::None,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BorderStyle::Standard => "standard",
+            BorderStyle::Plain => "plain",
+            BorderStyle::Rounded => "rounded",
+            BorderStyle::Double => "double",
+            BorderStyle::Heavy => "heavy",
+            BorderStyle::Ascii => "ascii",
+            BorderStyle::None => "none",
+        }
+    }
+
+    /// Whether this style draws borders at all.
+    pub fn is_visible(self) -> bool {
+        self != BorderStyle::None
+    }
+
+    /// Glyphs for a tiled or floating window; `None` for [`BorderStyle::None`].
+    pub fn symbols(self, floating: bool) -> Option<BorderSymbols> {
+        Some(match self {
+            BorderStyle::Standard if floating => ROUNDED,
+            BorderStyle::Standard | BorderStyle::Plain => PLAIN,
+            BorderStyle::Rounded => ROUNDED,
+            BorderStyle::Double => DOUBLE,
+            BorderStyle::Heavy => HEAVY,
+            BorderStyle::Ascii => ASCII,
+            BorderStyle::None => return None,
+        })
+    }
+}
+
+impl fmt::Display for BorderStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for BorderStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|style| style.name().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::ALL.iter().map(|style| style.name()).collect();
+                format!(
+                    "unknown border style `{s}` (expected one of: {})",
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// Where the title sits in a window's header row.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TitleAlignment {
+    Left,
+    #[default]
+    Center,
+    /// Flush against the window-management buttons.
+    Right,
+}
+
+impl TitleAlignment {
+    pub const ALL: [TitleAlignment; 3] = [
+        TitleAlignment::Left,
+        TitleAlignment::Center,
+        TitleAlignment::Right,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TitleAlignment::Left => "left",
+            TitleAlignment::Center => "center",
+            TitleAlignment::Right => "right",
+        }
+    }
+}
+
+impl fmt::Display for TitleAlignment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for TitleAlignment {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|align| align.name().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                format!("unknown title alignment `{s}` (expected left, center or right)")
+            })
+    }
+}
+
+/// Per-window accent colors offered by the command palette. Plain ANSI
+/// colors, so they follow the host terminal's palette like the content does.
+pub const ACCENT_COLORS: [(&str, Color); 6] = [
+    ("Red", Color::Red),
+    ("Green", Color::Green),
+    ("Yellow", Color::Yellow),
+    ("Blue", Color::Blue),
+    ("Magenta", Color::Magenta),
+    ("Cyan", Color::Cyan),
+];
+
+/// Palette name of an accent color, if it is one of [`ACCENT_COLORS`].
+pub fn accent_name(color: Color) -> Option<&'static str> {
+    ACCENT_COLORS
+        .iter()
+        .find(|(_, c)| *c == color)
+        .map(|(name, _)| *name)
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_round_trip() {
+        for style in BorderStyle::ALL {
+            assert_eq!(style.name().parse::<BorderStyle>().unwrap(), style);
+        }
+        for align in TitleAlignment::ALL {
+            assert_eq!(align.name().parse::<TitleAlignment>().unwrap(), align);
+        }
+        assert_eq!("ASCII".parse::<BorderStyle>().unwrap(), BorderStyle::Ascii);
+        assert!("dotted".parse::<BorderStyle>().is_err());
+    }
+
+    #[test]
+    fn standard_rounds_only_floating_corners() {
+        let tiled = BorderStyle::Standard.symbols(false).unwrap();
+        let floating = BorderStyle::Standard.symbols(true).unwrap();
+        assert_eq!(tiled.top_left, "┌");
+        assert_eq!(floating.top_left, "╭");
+        assert_eq!(BorderStyle::Double.symbols(true).unwrap().top_left, "╔");
+        assert_eq!(BorderStyle::None.symbols(false), None);
+        // The ASCII set must render on a console without box-drawing glyphs.
+        let ascii = BorderStyle::Ascii.symbols(true).unwrap();
+        for sym in [
+            ascii.top_left,
+            ascii.top_right,
+            ascii.bottom_left,
+            ascii.bottom_right,
+            ascii.horizontal,
+            ascii.vertical,
+        ] {
+            assert!(sym.is_ascii());
+        }
+    }
+}
@@ -64,6 +64,34 @@ impl FuzzyMatch {
         scored.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
         scored.into_iter().map(|(_, i)| i).collect()
     }
+
+    /// Score each haystack against `query`, in input order. `None` marks a
+    /// haystack that does not match; an empty query matches everything with
+    /// score 0.
+    pub fn score_each<'a>(
+        &mut self,
+        query: &str,
+        haystacks: impl IntoIterator<Item = &'a str>,
+    ) -> Vec<Option<u32>> {
+        if query.is_empty() {
+            return haystacks.into_iter().map(|_| Some(0)).collect();
+        }
+        self.matcher = Matcher::new(NucleoConfig::DEFAULT.match_paths());
+        let pattern = Pattern::new(
+            query,
+            CaseMatching::Ignore,
+            Normalization::Smart,
+            AtomKind::Fuzzy,
+        );
+        haystacks
+            .into_iter()
+            .map(|text| {
+                self.char_buf.clear();
+                let haystack = Utf32Str::new(text, &mut self.char_buf);
+                pattern.score(haystack, &mut self.matcher)
+            })
+            .collect()
+    }
 }
 
 /// Exponential decay MRU ranker.
@@ -124,6 +152,17 @@ impl MruRanker {
 mod tests {
     use super::*;
 
+    #[test]
+    fn score_each_keeps_input_order() {
+        let mut fmatch = FuzzyMatch::new();
+        let scores = fmatch.score_each("src", ["Cargo.toml", "src/main.rs", "README"]);
+        assert_eq!(scores.len(), 3);
+        assert!(scores[0].is_none());
+        assert!(scores[1].is_some());
+        assert!(scores[2].is_none());
+        assert_eq!(fmatch.score_each("", ["a", "b"]), vec![Some(0), Some(0)]);
+    }
+
     #[test]
     fn fuzzy_empty_query_returns_all() {
         let mut fmatch = FuzzyMatch::new();
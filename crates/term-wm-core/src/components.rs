@@ -21,6 +21,17 @@ pub struct SelectionStatus {
     pub dragging: bool,
 }
 
+/// The host terminal's own cursor, shown in place of the painted one for a
+/// pane whose application set a cursor style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HostCursor {
+    /// Screen column.
+    pub x: u16,
+    /// Screen row.
+    pub y: u16,
+    pub style: crate::CursorStyle,
+}
+
 /// Five-phase message-passing component lifecycle.
 ///
 /// 1. `init` — one-time setup on mount
@@ -186,6 +197,16 @@ pub trait Component<Msg> {
         registry: &mut crate::hitbox_registry::HitboxRegistry,
     );
 
+    /// Whether `render` would draw anything different from the last frame.
+    ///
+    /// The compositor caches each window's last frame and skips `render`
+    /// when this returns `false` and nothing around the window (geometry,
+    /// chrome, input) changed either. Only answer `false` when the output is
+    /// known to be identical; the default always re-renders.
+    fn needs_render(&self) -> bool {
+        true
+    }
+
     /// Phase 5: Teardown. Called before the component is unmounted.
     fn destroy(&mut self) {}
 
@@ -218,6 +239,13 @@ pub trait Component<Msg> {
         None
     }
 
+    /// Where the host cursor goes, as of the last `render`, when this
+    /// component wants the host terminal to draw its cursor. `None` keeps the
+    /// host cursor hidden.
+    fn host_cursor(&self) -> Option<HostCursor> {
+        None
+    }
+
     /// Extract child process and reader handles for the Reaper during teardown.
     /// Called during close_window, before the component is dropped.
     /// Default implementation returns None.
@@ -325,6 +353,9 @@ impl<Msg, C: Component<Msg> + ?Sized> Component<Msg> for &mut C {
     ) {
         (**self).render(backend, area, ctx, registry)
     }
+    fn needs_render(&self) -> bool {
+        (**self).needs_render()
+    }
     fn destroy(&mut self) {
         (**self).destroy()
     }
@@ -346,6 +377,9 @@ impl<Msg, C: Component<Msg> + ?Sized> Component<Msg> for &mut C {
     fn take_alternate_screen_transition(&mut self) -> Option<bool> {
         (**self).take_alternate_screen_transition()
     }
+    fn host_cursor(&self) -> Option<HostCursor> {
+        (**self).host_cursor()
+    }
     fn take_teardown_parts(
         &mut self,
     ) -> Option<(
@@ -385,6 +419,117 @@ impl Component<TermWmAction> for NoopComponent {
     fn destroy(&mut self) {}
 }
 
+/// Hosts a `Component<Msg>` with an application-defined `Msg` inside a window
+/// tree, which speaks [`TermWmAction`].
+///
+/// Actions the inner component emits travel as [`TermWmAction::Message`]
+/// (back to the owning window, like any component action), and `Message`
+/// actions carrying an `Msg` are delivered to its `update`. Other framework
+/// actions are not meaningful to a `Component<Msg>` and are dropped.
+pub struct MessageComponent<Msg, C> {
+    inner: C,
+    _msg: std::marker::PhantomData<fn(Msg)>,
+}
+
+impl<Msg, C: Component<Msg>> MessageComponent<Msg, C> {
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            _msg: std::marker::PhantomData,
+        }
+    }
+
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut C {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+impl<Msg, C> Component<TermWmAction> for MessageComponent<Msg, C>
+where
+    Msg: Clone + std::fmt::Debug + Send + Sync + 'static,
+    C: Component<Msg>,
+{
+    fn init(&mut self) {
+        self.inner.init()
+    }
+    fn on_mount(&mut self, key: WindowKey, app: &crate::app_context::AppContext) {
+        self.inner.on_mount(key, app)
+    }
+    fn hitbox_id(&self) -> Option<HitboxId> {
+        self.inner.hitbox_id()
+    }
+    fn handle_events(
+        &mut self,
+        event: &Event,
+        ctx: &ComponentContext,
+    ) -> EventResult<TermWmAction> {
+        self.inner
+            .handle_events(event, ctx)
+            .map(TermWmAction::message)
+    }
+    fn update(
+        &mut self,
+        action: TermWmAction,
+        ctx: &ComponentContext,
+        actions: &mut VecDeque<(WindowKey, TermWmAction)>,
+    ) {
+        let Some(msg) = action.as_message::<Msg>() else {
+            return;
+        };
+        let mut queue = VecDeque::new();
+        self.inner.update(msg.clone(), ctx, &mut queue);
+        actions.extend(
+            queue
+                .into_iter()
+                .map(|(key, msg)| (key, TermWmAction::message(msg))),
+        );
+    }
+    fn render(
+        &mut self,
+        backend: &mut dyn term_wm_render::RenderBackend,
+        area: LayoutRect,
+        ctx: &ComponentContext,
+        registry: &mut crate::hitbox_registry::HitboxRegistry,
+    ) {
+        self.inner.render(backend, area, ctx, registry)
+    }
+    fn needs_render(&self) -> bool {
+        self.inner.needs_render()
+    }
+    fn destroy(&mut self) {
+        self.inner.destroy()
+    }
+    fn clear_selection(&mut self) {
+        self.inner.clear_selection()
+    }
+    fn selection_status(&self) -> SelectionStatus {
+        self.inner.selection_status()
+    }
+    fn selection_text(&self) -> Option<String> {
+        self.inner.selection_text()
+    }
+    fn desired_height(&self, width: u16) -> u16 {
+        self.inner.desired_height(width)
+    }
+    fn take_pending_title(&mut self) -> Option<String> {
+        self.inner.take_pending_title()
+    }
+    fn set_selection_enabled(&mut self, enabled: bool) {
+        self.inner.set_selection_enabled(enabled)
+    }
+    fn paste(&mut self, text: &str) -> bool {
+        self.inner.paste(text)
+    }
+}
+
 pub trait Overlay<Msg>: Component<Msg> + std::any::Any {
     /// Returns the overlay's current render area, if available, for spatial
     /// hit-testing (e.g. dismissing the overlay on outside-click).
@@ -1331,6 +1476,10 @@ mod tests {
                     .push("take_alternate_screen_transition");
                 Some(true)
             }
+            fn host_cursor(&self) -> Option<HostCursor> {
+                self.calls.borrow_mut().push("host_cursor");
+                None
+            }
             fn take_teardown_parts(
                 &mut self,
             ) -> Option<(
@@ -1442,6 +1591,7 @@ mod tests {
                 <&mut FullRecorder as Component<()>>::take_alternate_screen_transition(&mut comp),
                 Some(true)
             );
+            assert!(<&mut FullRecorder as Component<()>>::host_cursor(&comp).is_none());
             assert!(<&mut FullRecorder as Component<()>>::take_teardown_parts(&mut comp).is_none());
             <&mut FullRecorder as Component<()>>::set_selection_enabled(&mut comp, true);
             assert!(<&mut FullRecorder as Component<()>>::paste(&mut comp, "x"));
@@ -1477,6 +1627,7 @@ mod tests {
             "desired_height",
             "take_pending_title",
             "take_alternate_screen_transition",
+            "host_cursor",
             "take_teardown_parts",
             "set_selection_enabled",
             "paste",
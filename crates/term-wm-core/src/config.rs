@@ -7,6 +7,7 @@ use crate::components::Overlay;
 use crate::components::WmComponent;
 use crate::keybindings::KeyBindings;
 use crate::theme::Theme;
+use crate::theme_registry::AutoTheme;
 use crate::window::WindowManager;
 use crate::wm_config::{HintVisibility, WmConfig};
 
@@ -72,8 +73,15 @@ impl<L: WmComponent> AppBuilder<L> {
         self
     }
 
+    /// Use `theme` regardless of the host terminal's background.
     pub fn theme(mut self, theme: Theme) -> Self {
         self.config.theme = theme;
+        self.config.auto_theme = None;
+        self
+    }
+
+    pub fn auto_theme(mut self, auto: Option<AutoTheme>) -> Self {
+        self.config.auto_theme = auto;
         self
     }
 
@@ -3,6 +3,7 @@
 use std::time::Duration;
 
 use crate::actions::TermWmAction;
+use crate::screenshot::ScreenshotFormat;
 
 /// Default actions available in the WM command menu when no explicit
 /// allow-list is configured via `AppBuilder::supported_menu_actions`.
@@ -19,6 +20,8 @@ pub const DEFAULT_SUPPORTED_MENU_ACTIONS: &[TermWmAction] = &[
     TermWmAction::ExitUi,
     TermWmAction::ToggleMonocle,
     TermWmAction::ToggleTiling,
+    TermWmAction::SwitchTheme(String::new()),
+    TermWmAction::ExportScreenshot(ScreenshotFormat::Html),
     #[cfg(feature = "session-persistence")]
     TermWmAction::NewWorkspace,
     #[cfg(feature = "session-persistence")]
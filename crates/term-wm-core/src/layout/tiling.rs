@@ -1,4 +1,5 @@
 pub use term_wm_layout_engine::Direction;
+pub use term_wm_layout_engine::Gaps;
 pub use term_wm_layout_engine::InsertPosition;
 pub use term_wm_layout_engine::LayoutNode;
 pub use term_wm_layout_engine::SplitGap;
@@ -32,6 +33,7 @@ pub struct DragState {
 #[derive(Debug)]
 pub struct TilingLayout<Id: Copy + Eq + Ord> {
     root: LayoutNode<Id>,
+    gaps: Gaps,
     drag: Option<DragState>,
     hover: Option<(u16, u16)>,
     monocle_active: bool,
@@ -42,6 +44,7 @@ impl<Id: Copy + Eq + Ord> TilingLayout<Id> {
     pub fn new(root: LayoutNode<Id>) -> Self {
         Self {
             root,
+            gaps: Gaps::default(),
             drag: None,
             hover: None,
             monocle_active: false,
@@ -72,6 +75,20 @@ impl<Id: Copy + Eq + Ord> TilingLayout<Id> {
         self.monocle_width_threshold
     }
 
+    /// Spacing around and between the tiles.
+    pub fn gaps(&self) -> Gaps {
+        self.gaps
+    }
+
+    pub fn set_gaps(&mut self, gaps: Gaps) {
+        self.gaps = gaps;
+    }
+
+    pub fn with_gaps(mut self, gaps: Gaps) -> Self {
+        self.gaps = gaps;
+        self
+    }
+
     pub fn root(&self) -> &LayoutNode<Id> {
         &self.root
     }
@@ -85,11 +102,11 @@ impl<Id: Copy + Eq + Ord> TilingLayout<Id> {
     }
 
     pub fn regions(&self, area: Rect) -> Vec<(Id, Rect)> {
-        self.root.layout_rects(area)
+        self.root.layout_with_gaps(area, self.gaps).0
     }
 
     pub fn void_regions(&self, area: Rect) -> Vec<(usize, Rect)> {
-        self.root.void_regions(area)
+        self.root.void_regions(area, self.gaps)
     }
 
     pub fn replace_void_by_id(&mut self, void_id: usize, new_leaf: LayoutNode<Id>) -> bool {
@@ -189,7 +206,8 @@ impl<Id: Copy + Eq + Ord> TilingLayout<Id> {
     }
 
     pub fn project_insert_void(&self, insert: Id, void_id: usize, area: Rect) -> Option<Rect> {
-        self.root.project_insert_void(insert, void_id, area)
+        self.root
+            .project_insert_void(insert, void_id, area, self.gaps)
     }
 
     pub fn project_insert(
@@ -199,11 +217,12 @@ impl<Id: Copy + Eq + Ord> TilingLayout<Id> {
         position: InsertPosition,
         area: Rect,
     ) -> Option<Rect> {
-        self.root.project_insert(target, insert, position, area)
+        self.root
+            .project_insert(target, insert, position, area, self.gaps)
     }
 
     pub fn handles(&self, area: Rect) -> Vec<SplitHandle> {
-        let (_, gaps) = self.root.layout_with_gaps(area);
+        let (_, gaps) = self.root.layout_with_gaps(area, self.gaps);
         gaps.into_iter()
             .map(|g| SplitHandle {
                 rect: g.rect,
@@ -217,7 +236,7 @@ impl<Id: Copy + Eq + Ord> TilingLayout<Id> {
 
     pub fn hovered_handle(&self, area: Rect) -> Option<SplitHandle> {
         let (column, row) = self.hover?;
-        let gap = self.root.hit_test_gap(area, column, row)?;
+        let gap = self.root.hit_test_gap(area, self.gaps, column, row)?;
         Some(SplitHandle {
             rect: gap.rect,
             path: gap.path,
@@ -235,7 +254,10 @@ impl<Id: Copy + Eq + Ord> TilingLayout<Id> {
         self.hover = Some((mouse.column, mouse.row));
         match mouse.kind {
             MouseEventKind::Press(_) => {
-                if let Some(gap) = self.root.hit_test_gap(area, mouse.column, mouse.row) {
+                if let Some(gap) = self
+                    .root
+                    .hit_test_gap(area, self.gaps, mouse.column, mouse.row)
+                {
                     self.drag = Some(DragState {
                         path: gap.path,
                         index: gap.index,
@@ -256,6 +278,7 @@ impl<Id: Copy + Eq + Ord> TilingLayout<Id> {
                     state.last_row = mouse.row;
                     return self.root.apply_drag(
                         area,
+                        self.gaps,
                         &state.path,
                         state.index,
                         state.direction,
@@ -574,6 +597,56 @@ mod tests {
         );
     }
 
+    #[test]
+    fn tiling_gaps_inset_regions_and_widen_handles() {
+        let area = Rect {
+            x: 0,
+            y: 0,
+            width: 80,
+            height: 24,
+        };
+        let root = LayoutNode::Split {
+            direction: Direction::Horizontal,
+            children: vec![LayoutNode::Leaf(1), LayoutNode::Leaf(2)],
+            weights: vec![1u16, 1u16],
+            resizable: true,
+        };
+        let mut layout = TilingLayout::new(root);
+        layout.set_gaps(Gaps { outer: 2, inner: 3 });
+
+        let regions = layout.regions(area);
+        assert_eq!(regions[0].1.x, 2);
+        assert_eq!(regions[0].1.y, 2);
+        assert_eq!(regions[0].1.height, 20);
+        let right = regions[1].1;
+        assert_eq!(right.x + i32::from(right.width), 78);
+
+        // The handle spans the whole inner gap, so any of its cells grabs it.
+        let handles = layout.handles(area);
+        assert_eq!(handles.len(), 1);
+        let gap = handles[0].rect;
+        assert_eq!(gap.width, 3);
+        assert_eq!(gap.x, regions[0].1.x + i32::from(regions[0].1.width));
+        let down = crate::events::Event::Mouse(crate::events::MouseEvent {
+            kind: crate::events::MouseEventKind::Press(crate::events::MouseButton::Left),
+            column: gap.x as u16,
+            row: 10,
+            modifiers: crate::events::KeyModifiers::NONE,
+        });
+        assert!(layout.handle_event(&down, area), "edge of the gap grabs");
+
+        // The outer gap is not a handle.
+        let mut layout = TilingLayout::new(layout.root().clone());
+        layout.set_gaps(Gaps { outer: 2, inner: 3 });
+        let outer = crate::events::Event::Mouse(crate::events::MouseEvent {
+            kind: crate::events::MouseEventKind::Press(crate::events::MouseButton::Left),
+            column: 1,
+            row: 10,
+            modifiers: crate::events::KeyModifiers::NONE,
+        });
+        assert!(!layout.handle_event(&outer, area));
+    }
+
     #[test]
     fn monocle_mode_toggles_on_narrow_terminal() {
         let root = LayoutNode::Split {
@@ -823,7 +896,7 @@ mod tests {
             width: 80,
             height: 24,
         };
-        assert!(!node.apply_drag(area, &[0], 0, Direction::Horizontal, 5, 4));
+        assert!(!node.apply_drag(area, Gaps::default(), &[0], 0, Direction::Horizontal, 5, 4));
     }
 
     #[test]
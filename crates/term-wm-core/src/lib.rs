@@ -22,11 +22,14 @@ pub mod power_profile;
 pub mod reaper;
 pub mod task_scheduler;
 pub mod term_color;
-pub use term_wm_pty_engine::PtyStatus;
+pub use term_wm_pty_engine::{CursorShape, CursorStyle, PtyStatus};
 pub mod config;
 pub mod runner;
+pub mod screenshot;
 pub mod theme;
+pub mod theme_registry;
 pub mod utils;
+pub mod view_support;
 pub mod window;
 pub mod wm_config;
 
@@ -62,6 +62,9 @@ macro_rules! impl_component_delegate {
             fn render(&mut self, backend: &mut dyn term_wm_render::RenderBackend, area: $crate::Rect, ctx: &$crate::component_context::ComponentContext, registry: &mut $crate::hitbox_registry::HitboxRegistry) {
                 match self { $(Self::$variant(c) => $crate::components::Component::<$crate::actions::TermWmAction>::render(c, backend, area, ctx, registry),)* }
             }
+            fn needs_render(&self) -> bool {
+                match self { $(Self::$variant(c) => c.needs_render(),)* }
+            }
             fn destroy(&mut self) {
                 match self { $(Self::$variant(c) => c.destroy(),)* }
             }
@@ -83,6 +86,9 @@ macro_rules! impl_component_delegate {
             fn take_alternate_screen_transition(&mut self) -> Option<bool> {
                 match self { $(Self::$variant(c) => c.take_alternate_screen_transition(),)* }
             }
+            fn host_cursor(&self) -> Option<$crate::components::HostCursor> {
+                match self { $(Self::$variant(c) => c.host_cursor(),)* }
+            }
             fn take_teardown_parts(&mut self) -> Option<(Box<dyn std::any::Any + Send + Sync>, std::thread::JoinHandle<()>)> {
                 match self { $(Self::$variant(c) => c.take_teardown_parts(),)* }
             }
@@ -147,6 +153,9 @@ macro_rules! impl_component_delegate {
             fn render(&mut self, backend: &mut dyn term_wm_render::RenderBackend, area: $crate::Rect, ctx: &$crate::component_context::ComponentContext, registry: &mut $crate::hitbox_registry::HitboxRegistry) {
                 match self { $(Self::$variant(c) => $crate::components::Component::<$crate::actions::TermWmAction>::render(c, backend, area, ctx, registry),)* }
             }
+            fn needs_render(&self) -> bool {
+                match self { $(Self::$variant(c) => c.needs_render(),)* }
+            }
             fn destroy(&mut self) {
                 match self { $(Self::$variant(c) => c.destroy(),)* }
             }
@@ -168,6 +177,9 @@ macro_rules! impl_component_delegate {
             fn take_alternate_screen_transition(&mut self) -> Option<bool> {
                 match self { $(Self::$variant(c) => c.take_alternate_screen_transition(),)* }
             }
+            fn host_cursor(&self) -> Option<$crate::components::HostCursor> {
+                match self { $(Self::$variant(c) => c.host_cursor(),)* }
+            }
             fn take_teardown_parts(&mut self) -> Option<(Box<dyn std::any::Any + Send + Sync>, std::thread::JoinHandle<()>)> {
                 match self { $(Self::$variant(c) => c.take_teardown_parts(),)* }
             }
@@ -278,6 +290,13 @@ macro_rules! impl_overlay_delegate {
 /// - `impl_view_component!(Ty, height = <expr>, child: <field>, …)` — static
 ///   height + child-field metadata delegation (see previous form).
 ///
+/// Every form also takes `msg = <Msg>` right after the type
+/// (`impl_view_component!(Ty, msg = Msg, height = 0)`): `Message` actions
+/// carrying a `Msg` — from `<Button msg={..}>`, a
+/// [`MessageComponent`](crate::components::MessageComponent) child or
+/// `TermWmApp::send_message` — are handed to the type's inherent
+/// `fn update(&mut self, msg: Msg)` instead of the view.
+///
 /// This is a single TT-muncher implementation: the four entry-point arms below
 /// only normalize the syntax; the `@impl` body defines every lifecycle method
 /// once, and small `@desired_height` / `@metadata_methods` rules parameterize
@@ -285,21 +304,21 @@ macro_rules! impl_overlay_delegate {
 #[macro_export]
 macro_rules! impl_view_component {
     // --- Normalized syntax entry points -------------------------------------
-    ($ty:ty, height = $h:expr, child: $first:ident $(, $rest:ident)* $(,)?) => {
-        $crate::impl_view_component!(@impl $ty, height = static $h, meta = child, head = $first, children = [$first $(, $rest)*]);
+    ($ty:ty, $(msg = $msg:ty,)? height = $h:expr, child: $first:ident $(, $rest:ident)* $(,)?) => {
+        $crate::impl_view_component!(@impl $ty, msg = [$($msg)?], height = static $h, meta = child, head = $first, children = [$first $(, $rest)*]);
     };
-    ($ty:ty, child: $first:ident $(, $rest:ident)* $(,)?) => {
-        $crate::impl_view_component!(@impl $ty, height = child, meta = child, head = $first, children = [$first $(, $rest)*]);
+    ($ty:ty, $(msg = $msg:ty,)? child: $first:ident $(, $rest:ident)* $(,)?) => {
+        $crate::impl_view_component!(@impl $ty, msg = [$($msg)?], height = child, meta = child, head = $first, children = [$first $(, $rest)*]);
     };
-    ($ty:ty, height = $h:expr $(,)?) => {
-        $crate::impl_view_component!(@impl $ty, height = static $h, meta = none, head = none, children = []);
+    ($ty:ty, $(msg = $msg:ty,)? height = $h:expr $(,)?) => {
+        $crate::impl_view_component!(@impl $ty, msg = [$($msg)?], height = static $h, meta = none, head = none, children = []);
     };
-    ($ty:ty $(,)?) => {
-        $crate::impl_view_component!(@impl $ty, height = view, meta = view, head = none, children = []);
+    ($ty:ty $(, msg = $msg:ty)? $(,)?) => {
+        $crate::impl_view_component!(@impl $ty, msg = [$($msg)?], height = view, meta = view, head = none, children = []);
     };
 
     // --- Single implementation body -----------------------------------------
-    (@impl $ty:ty, height = $h_kind:tt $($h_expr:expr)?, meta = $m_kind:tt, head = $head:tt, children = [$($child:ident),*]) => {
+    (@impl $ty:ty, msg = [$($msg:ty)?], height = $h_kind:tt $($h_expr:expr)?, meta = $m_kind:tt, head = $head:tt, children = [$($child:ident),*]) => {
         impl $crate::components::Component<$crate::actions::TermWmAction> for $ty {
             fn render(
                 &mut self,
@@ -328,6 +347,12 @@ macro_rules! impl_view_component {
                     $crate::actions::TermWmAction,
                 )>,
             ) {
+                $(
+                    if let Some(msg) = action.as_message::<$msg>() {
+                        <$ty>::update(self, ::std::clone::Clone::clone(msg));
+                        return;
+                    }
+                )?
                 let mut view = self.view();
                 $crate::components::Component::update(&mut view, action, ctx, actions);
             }
@@ -381,6 +406,9 @@ macro_rules! impl_view_component {
         fn selection_text(&self) -> Option<String> {
             $crate::components::Component::selection_text(&self.view())
         }
+        fn host_cursor(&self) -> Option<$crate::components::HostCursor> {
+            $crate::components::Component::host_cursor(&self.view())
+        }
         fn clear_selection(&mut self) {
             let mut view = self.view();
             $crate::components::Component::clear_selection(&mut view);
@@ -405,6 +433,9 @@ macro_rules! impl_view_component {
         fn selection_text(&self) -> Option<String> {
             None
         }
+        fn host_cursor(&self) -> Option<$crate::components::HostCursor> {
+            None
+        }
         fn clear_selection(&mut self) {
             let mut view = self.view();
             $crate::components::Component::clear_selection(&mut view);
@@ -446,6 +477,14 @@ macro_rules! impl_view_component {
             )+
             None
         }
+        fn host_cursor(&self) -> Option<$crate::components::HostCursor> {
+            $(
+                if let Some(cursor) = $crate::components::Component::host_cursor(&self.$child) {
+                    return Some(cursor);
+                }
+            )+
+            None
+        }
         fn clear_selection(&mut self) {
             $(
                 $crate::components::Component::clear_selection(&mut self.$child);
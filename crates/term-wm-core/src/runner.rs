@@ -111,6 +111,27 @@ fn dispatch_action<
         TermWmAction::ToggleWindowSelection => app.wm().toggle_window_selection(),
         TermWmAction::ToggleDebugWindow => app.toggle_debug_window(),
         TermWmAction::ToggleSystemPanel => app.toggle_system_panel(),
+        TermWmAction::ExportScreenshot(format) => {
+            app.wm()
+                .request_screenshot(crate::screenshot::ScreenshotRequest {
+                    window: None,
+                    format,
+                });
+        }
+        TermWmAction::ExportWindowScreenshot(key, format) => {
+            app.wm()
+                .request_screenshot(crate::screenshot::ScreenshotRequest {
+                    window: Some(key),
+                    format,
+                });
+        }
+        TermWmAction::SetWindowAccent(k, accent) => app.wm().set_window_accent(k, accent),
+        TermWmAction::SetWindowLabel(k, label) => app.wm().set_window_label(k, label),
+        TermWmAction::SwitchTheme(name) => {
+            if !app.wm().switch_theme(&name) {
+                tracing::warn!("unknown theme `{name}`");
+            }
+        }
         TermWmAction::FocusWindow(k) => {
             if app.wm().window_state(k) == Some(crate::window::WindowState::Iconic) {
                 app.wm()
@@ -171,6 +192,7 @@ fn dispatch_action<
         #[cfg(feature = "session-persistence")]
         action @ (TermWmAction::SwitchWorkspace(_)
         | TermWmAction::NewWorkspace
+        | TermWmAction::RenameWorkspace(_)
         | TermWmAction::DetachCurrentClient) => {
             if !app.handle_custom_action(&action) {
                 // Unhandled — forward to component update
@@ -362,6 +384,12 @@ where
                     SystemTask::FlushDirectModeToast(key) => {
                         app.wm().flush_direct_mode_toast(key);
                     }
+                    SystemTask::FlushScreenshotToast => {
+                        app.wm().flush_screenshot_toast();
+                    }
+                    SystemTask::PollScreenshotExports => {
+                        app.wm().poll_screenshot_exports();
+                    }
                     SystemTask::ClearTabOutline => {
                         if let Some(expires_at) = app.wm().tab_outline_until
                             && std::time::Instant::now() >= expires_at
@@ -373,6 +401,10 @@ where
             }
 
             // Drain app-level callback tasks (each carries its own closure).
+            // A callback that changes a window reaches it through an action
+            // (`process_action`) or `damage_window`, which drops that window's
+            // cached frame; animated components redraw through
+            // `Component::needs_render`.
             for (_id, task) in app_handle.drain_expired() {
                 task.run(app);
             }
@@ -400,7 +432,34 @@ where
             if !transitions.is_empty() {
                 tracing::info!("[STAGE 4] Draining {} transitions", transitions.len());
             }
+            // Input and input-mode changes can alter what a window shows
+            // without moving it, so only the windows they reach lose their
+            // cached frame. Geometry, focus, theme and chrome changes are in
+            // every window's signature, actions damage the window they reach,
+            // and panes report their own PTY output.
+            match &event {
+                Some(Event::Key(_) | Event::Paste(_)) => {
+                    let focused = app.wm().focused_window();
+                    app.wm().damage_window(focused);
+                }
+                Some(Event::Mouse(mouse)) => {
+                    let focused = app.wm().focused_window();
+                    app.wm().damage_window(focused);
+                    if let Some(key) = app.wm().window_at(mouse.column, mouse.row) {
+                        app.wm().damage_window(key);
+                    }
+                }
+                Some(
+                    Event::Resize(..)
+                    | Event::FocusGained
+                    | Event::FocusLost
+                    | Event::HostColor(..)
+                    | Event::HostDisplay(_),
+                ) => app.wm().damage_all_windows(),
+                None => {}
+            }
             for (key, mode) in transitions {
+                app.wm().damage_window(key);
                 // Debounced toast — coalesces rapid sub-mode transitions
                 // (e.g. vim's alt-screen + mouse-tracking startup pair) into a
                 // single notification with the combined access phrase.
@@ -476,6 +535,29 @@ where
 
                 frame_pacer.notify_pending(Instant::now());
 
+                // Host terminal default colors (OSC 10/11 replies) feed the
+                // automatic theme; re-query whenever the host regains focus
+                // since the user may have switched its color scheme meanwhile.
+                if let Event::HostColor(kind, rgb) = evt {
+                    app.wm().set_host_color(kind, rgb);
+                    driver.request_redraw();
+                    return flush_state_changes(app, driver, ControlFlow::Continue, false, None);
+                }
+                if matches!(evt, Event::FocusGained) {
+                    let _ = output.query_host_colors();
+                }
+                // Inside a gateway the session client probes the host terminal
+                // for us; render for it and drop what it cannot do.
+                if let Event::HostDisplay(display) = evt {
+                    output.set_host_display(&display);
+                    if !display.mouse {
+                        app.wm().set_mouse_capture_enabled(false);
+                    }
+                    app.wm().set_osc52_enabled(display.osc52);
+                    driver.request_redraw();
+                    return flush_state_changes(app, driver, ControlFlow::Continue, false, None);
+                }
+
                 // PRE-LAYER: Global app event observer.
                 // Runs before all layer checks so the app can observe or consume
                 // EVERY event — including those later consumed by overlays,
@@ -825,6 +907,8 @@ where
         }
     })?;
 
+    // A screenshot requested just before quitting still gets written.
+    app.wm().wait_for_screenshot_exports();
     Ok(())
 }
 
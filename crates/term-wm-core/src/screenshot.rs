@@ -0,0 +1,139 @@
+//! Screenshot export requests.
+//!
+//! An `ExportScreenshot` action only queues a [`ScreenshotRequest`] on the
+//! window manager; the renderer serializes the next composed frame (or one
+//! window of it) and hands the outcome back for a toast.
+
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::window::WindowKey;
+
+/// File format of an exported screenshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ScreenshotFormat {
+    /// Standalone HTML page with inline styles.
+    Html,
+    /// SVG text on a cell grid, colored with the active theme.
+    Svg,
+    /// ANSI escape stream that replays in any terminal (`cat` it).
+    Ansi,
+    /// The SVG rasterized to a PNG image.
+    Png,
+}
+
+impl ScreenshotFormat {
+    pub const ALL: [ScreenshotFormat; 4] = [
+        ScreenshotFormat::Html,
+        ScreenshotFormat::Svg,
+        ScreenshotFormat::Ansi,
+        ScreenshotFormat::Png,
+    ];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ScreenshotFormat::Html => "html",
+            ScreenshotFormat::Svg => "svg",
+            ScreenshotFormat::Ansi => "ans",
+            ScreenshotFormat::Png => "png",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ScreenshotFormat::Html => "HTML",
+            ScreenshotFormat::Svg => "SVG",
+            ScreenshotFormat::Ansi => "ANSI",
+            ScreenshotFormat::Png => "PNG",
+        }
+    }
+}
+
+/// What to capture, and how to save it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScreenshotRequest {
+    /// `None` captures the whole screen, `Some` one window's chrome and
+    /// content without whatever overlaps it.
+    pub window: Option<WindowKey>,
+    pub format: ScreenshotFormat,
+}
+
+/// Where screenshots are saved: `configured` (see
+/// [`WmConfig::screenshot_dir`](crate::wm_config::WmConfig::screenshot_dir)),
+/// else `$TERM_WM_SCREENSHOT_DIR`, else `$XDG_DATA_HOME/term-wm/screenshots`,
+/// else `~/.local/share/term-wm/screenshots`.
+pub fn screenshot_dir(configured: Option<&Path>) -> Option<PathBuf> {
+    if let Some(dir) = configured {
+        return Some(dir.to_path_buf());
+    }
+    let var = |name: &str| std::env::var_os(name).filter(|v| !v.is_empty());
+    if let Some(dir) = var(term_wm_config::SCREENSHOT_DIR_ENV_VAR) {
+        return Some(PathBuf::from(dir));
+    }
+    let data = var("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| var("HOME").map(|home| PathBuf::from(home).join(".local/share")))?;
+    Some(
+        data.join(term_wm_config::GATEWAY_NAMESPACE)
+            .join("screenshots"),
+    )
+}
+
+/// `term-wm-YYYYMMDD-HHMMSS.<ext>` for a screenshot taken at `time` (UTC).
+pub fn screenshot_file_name(format: ScreenshotFormat, time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    let rem = secs % 86_400;
+    format!(
+        "term-wm-{year:04}{month:02}{day:02}-{:02}{:02}{:02}.{}",
+        rem / 3_600,
+        rem / 60 % 60,
+        rem % 60,
+        format.extension()
+    )
+}
+
+/// Gregorian date of a day count since 1970-01-01 (Howard Hinnant's
+/// `civil_from_days`).
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn file_names_carry_the_utc_timestamp_and_extension() {
+        let at = |secs| UNIX_EPOCH + Duration::from_secs(secs);
+        assert_eq!(
+            screenshot_file_name(ScreenshotFormat::Html, at(0)),
+            "term-wm-19700101-000000.html"
+        );
+        // 2024-02-29 23:59:58 UTC
+        assert_eq!(
+            screenshot_file_name(ScreenshotFormat::Ansi, at(1_709_251_198)),
+            "term-wm-20240229-235958.ans"
+        );
+    }
+
+    #[test]
+    fn configured_dir_wins() {
+        let dir = Path::new("/tmp/shots");
+        assert_eq!(screenshot_dir(Some(dir)).unwrap(), dir);
+    }
+}
@@ -1,4 +1,7 @@
 // TODO: This and theme can likely migrate to the `term-wm-console` crate
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::events::HostColorDepth;
 use crate::theme::Color;
 
 /// Linear RGB interpolation between two colors.
@@ -19,17 +22,168 @@ pub fn lerp_color(a: Color, b: Color, t: f32) -> Color {
 }
 
 /// Map an RGB triple to a `ratatui::style::Color` appropriate for the
-/// current terminal. If truecolor is available (`COLORTERM` contains
-/// `truecolor` or `24bit`) we return `Color::Rgb(r,g,b)`. Otherwise
-/// we return the nearest xterm-256 `Color::Indexed(idx)`.
+/// current terminal: `Color::Rgb(r,g,b)` when truecolor is available,
+/// otherwise the nearest palette entry at [`ColorDepth::current`].
 pub fn map_rgb_to_color(r: u8, g: u8, b: u8) -> Color {
-    if let Ok(var) = std::env::var("COLORTERM") {
-        let lv = var.to_lowercase();
-        if lv.contains("truecolor") || lv.contains("24bit") {
-            return Color::Rgb(r, g, b);
+    ColorDepth::current().quantize(r, g, b).0
+}
+
+/// Depth recorded by [`set_color_depth`]; [`DEPTH_UNSET`] until then.
+static CURRENT_DEPTH: AtomicU8 = AtomicU8::new(DEPTH_UNSET);
+const DEPTH_UNSET: u8 = u8::MAX;
+
+/// Record the host terminal's color depth for the whole process. Called by
+/// the console backend once it has probed the terminal; until then
+/// [`ColorDepth::current`] falls back to [`ColorDepth::detect`].
+pub fn set_color_depth(depth: ColorDepth) {
+    CURRENT_DEPTH.store(depth as u8, Ordering::Relaxed);
+}
+
+/// How many colors the host terminal can display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorDepth {
+    /// 24-bit `Color::Rgb`.
+    TrueColor,
+    /// The xterm-256 palette (`Color::Indexed`).
+    Ansi256,
+    /// The 16 basic ANSI colors.
+    Ansi16,
+    /// No color at all (`NO_COLOR`, `TERM=dumb`); emphasis has to come from
+    /// attributes such as reverse video.
+    Mono,
+}
+
+impl ColorDepth {
+    /// The depth recorded by [`set_color_depth`], or [`ColorDepth::detect`]
+    /// when no terminal has been probed (tests, embedders).
+    pub fn current() -> Self {
+        match CURRENT_DEPTH.load(Ordering::Relaxed) {
+            0 => ColorDepth::TrueColor,
+            1 => ColorDepth::Ansi256,
+            2 => ColorDepth::Ansi16,
+            3 => ColorDepth::Mono,
+            _ => ColorDepth::detect(),
+        }
+    }
+
+    /// Guess the depth from the environment: a non-empty `NO_COLOR` selects
+    /// monochrome, `COLORTERM=truecolor|24bit` truecolor, a `TERM` without
+    /// 256-color support (`linux`, `vt100`, `*-16color`, ...) the basic 16
+    /// colors and anything else the xterm-256 palette.
+    pub fn detect() -> Self {
+        if std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+            return ColorDepth::Mono;
+        }
+        if let Ok(var) = std::env::var("COLORTERM") {
+            let lv = var.to_lowercase();
+            if lv.contains("truecolor") || lv.contains("24bit") {
+                return ColorDepth::TrueColor;
+            }
+        }
+        Self::from_term(&std::env::var("TERM").unwrap_or_default())
+    }
+
+    /// The depth a `TERM` name implies: 256 colors for `*256*` (or an unset
+    /// name), the basic 16 for `linux`, `dumb`, `vt*`, `*-16color` and
+    /// `*-color`, and the xterm-256 palette for anything else.
+    pub fn from_term(term: &str) -> Self {
+        match term {
+            term if term.contains("256") || term.is_empty() => ColorDepth::Ansi256,
+            term if term == "linux"
+                || term == "dumb"
+                || term.starts_with("vt")
+                || term.ends_with("-16color")
+                || term.ends_with("-color") =>
+            {
+                ColorDepth::Ansi16
+            }
+            _ => ColorDepth::Ansi256,
+        }
+    }
+
+    /// The closest displayable color to `(r, g, b)` at this depth, along
+    /// with the RGB value the terminal will actually show (used for error
+    /// diffusion when dithering).
+    pub fn quantize(self, r: u8, g: u8, b: u8) -> (Color, (u8, u8, u8)) {
+        match self {
+            ColorDepth::TrueColor => (Color::Rgb(r, g, b), (r, g, b)),
+            ColorDepth::Ansi256 => {
+                let idx = rgb_to_xterm_index(r, g, b);
+                (Color::Indexed(idx), palette_rgb(idx))
+            }
+            ColorDepth::Ansi16 => {
+                let (idx, rgb) = ANSI16_RGB
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, (cr, cg, cb))| color_distance_sq(r, g, b, *cr, *cg, *cb))
+                    .map(|(idx, rgb)| (idx as u8, *rgb))
+                    .unwrap_or((0, (0, 0, 0)));
+                (Color::Indexed(idx), rgb)
+            }
+            ColorDepth::Mono => {
+                let luma = (r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000;
+                if luma >= 128 {
+                    (Color::Indexed(15), (255, 255, 255))
+                } else {
+                    (Color::Indexed(0), (0, 0, 0))
+                }
+            }
+        }
+    }
+}
+
+impl From<HostColorDepth> for ColorDepth {
+    fn from(depth: HostColorDepth) -> Self {
+        match depth {
+            HostColorDepth::TrueColor => ColorDepth::TrueColor,
+            HostColorDepth::Ansi256 => ColorDepth::Ansi256,
+            HostColorDepth::Ansi16 => ColorDepth::Ansi16,
+            HostColorDepth::Mono => ColorDepth::Mono,
+        }
+    }
+}
+
+impl From<ColorDepth> for HostColorDepth {
+    fn from(depth: ColorDepth) -> Self {
+        match depth {
+            ColorDepth::TrueColor => HostColorDepth::TrueColor,
+            ColorDepth::Ansi256 => HostColorDepth::Ansi256,
+            ColorDepth::Ansi16 => HostColorDepth::Ansi16,
+            ColorDepth::Mono => HostColorDepth::Mono,
+        }
+    }
+}
+
+/// The xterm defaults for palette entries 0..16.
+const ANSI16_RGB: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// RGB value of an xterm-256 palette entry.
+pub fn palette_rgb(idx: u8) -> (u8, u8, u8) {
+    match idx {
+        0..=15 => ANSI16_RGB[idx as usize],
+        16..=231 => {
+            let i = idx - 16;
+            from_6cube(i / 36, (i / 6) % 6, i % 6)
         }
+        _ => from_gray(idx - 232),
     }
-    Color::Indexed(rgb_to_xterm_index(r, g, b))
 }
 
 fn rgb_to_xterm_index(r: u8, g: u8, b: u8) -> u8 {
@@ -153,6 +307,26 @@ mod tests {
         assert!((16..=255).contains(&idx));
     }
 
+    #[test]
+    fn quantize_reports_the_displayed_color() {
+        assert_eq!(
+            ColorDepth::TrueColor.quantize(1, 2, 3),
+            (Color::Rgb(1, 2, 3), (1, 2, 3))
+        );
+        let (color, rgb) = ColorDepth::Ansi256.quantize(255, 0, 0);
+        assert_eq!(color, Color::Indexed(196));
+        assert_eq!(rgb, (255, 0, 0));
+        let (color, rgb) = ColorDepth::Ansi16.quantize(250, 10, 10);
+        assert_eq!(color, Color::Indexed(9));
+        assert_eq!(rgb, (255, 0, 0));
+        assert_eq!(
+            ColorDepth::Mono.quantize(200, 200, 40),
+            (Color::Indexed(15), (255, 255, 255))
+        );
+        let (r, g, b) = palette_rgb(16 + 36 * 2 + 6 * 3 + 4);
+        assert_eq!((r, g, b), (135, 175, 215));
+    }
+
     #[test]
     fn map_rgb_to_color_returns_some_color() {
         let c = map_rgb_to_color(12, 34, 56);
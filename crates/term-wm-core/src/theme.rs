@@ -1,5 +1,7 @@
+use std::borrow::Cow;
+
 /// Simple color type owned by core (no ratatui dependency).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Color {
     Black,
     Red,
@@ -37,6 +39,12 @@ pub enum SemanticRole {
     Italic,
     Underlined,
     Dimmed,
+    /// Syntax-highlighted code (fenced blocks in the markdown viewer).
+    CodeKeyword,
+    CodeType,
+    CodeString,
+    CodeNumber,
+    CodeComment,
 }
 
 /// Pure data representation of text with a semantic role.
@@ -110,9 +118,10 @@ pub enum BgColor {
 // Theme struct – all RGB values live here.
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Theme {
-    pub name: &'static str,
+    /// Registry key (see [`crate::theme_registry::ThemeRegistry`]).
+    pub name: Cow<'static, str>,
     pub background: Color,
     pub surface: Color,
     pub panel_bg: Color,
@@ -210,12 +219,46 @@ impl Theme {
     }
 }
 
+/// Foreground/background pairs that always carry text together; user themes
+/// are checked against these when loaded.
+pub const TEXT_PAIRS: &[(FgColor, BgColor)] = &[
+    (FgColor::PanelFg, BgColor::PanelBg),
+    (FgColor::PanelInactiveFg, BgColor::PanelBg),
+    (FgColor::PanelActiveFg, BgColor::PanelActiveBg),
+    (FgColor::Accent, BgColor::PanelBg),
+    (FgColor::MenuFg, BgColor::MenuBg),
+    (FgColor::MenuSelectedFg, BgColor::MenuSelectedBg),
+    (FgColor::SelectionFg, BgColor::SelectionBg),
+    (FgColor::DialogFg, BgColor::DialogBg),
+    (FgColor::DecoratorHeaderFg, BgColor::DecoratorHeaderBg),
+    (FgColor::BottomPanelFg, BgColor::BottomPanelBg),
+    (FgColor::CursorFg, BgColor::CursorBg),
+    (FgColor::LinkColor, BgColor::Surface),
+];
+
+/// Minimum contrast for [`TEXT_PAIRS`] (WCAG AA for UI components and
+/// large text).
+pub const MIN_TEXT_CONTRAST: f64 = 3.0;
+
+impl Theme {
+    /// One message per [`TEXT_PAIRS`] entry below [`MIN_TEXT_CONTRAST`].
+    pub fn contrast_warnings(&self) -> Vec<String> {
+        TEXT_PAIRS
+            .iter()
+            .filter_map(|&(fg, bg)| {
+                let ratio = contrast_ratio(self.fg(fg), self.bg(bg));
+                (ratio < MIN_TEXT_CONTRAST).then(|| format!("{fg:?} on {bg:?}: {ratio:.2}:1"))
+            })
+            .collect()
+    }
+}
+
 // ---------------------------------------------------------------------------
 // NoirCast-inspired dark theme
 // ---------------------------------------------------------------------------
 
 pub const NOIR: Theme = Theme {
-    name: "noir",
+    name: Cow::Borrowed("noir"),
     // Core surfaces
     background: Color::Rgb(10, 10, 15),
     surface: Color::Rgb(20, 22, 30),
@@ -271,6 +314,205 @@ pub const NOIR: Theme = Theme {
     profile_low: Color::Rgb(0, 200, 83),
 };
 
+// ---------------------------------------------------------------------------
+// Light theme
+// ---------------------------------------------------------------------------
+
+pub const PAPER: Theme = Theme {
+    name: Cow::Borrowed("paper"),
+    background: Color::Rgb(250, 250, 247),
+    surface: Color::Rgb(240, 240, 236),
+    panel_bg: Color::Rgb(228, 229, 224),
+    panel_fg: Color::Rgb(30, 32, 40),
+    panel_inactive_fg: Color::Rgb(95, 98, 108),
+    panel_active_bg: Color::Rgb(210, 214, 222),
+    panel_active_fg: Color::Rgb(20, 22, 30),
+    text: Color::Rgb(30, 32, 40),
+    text_muted: Color::Rgb(95, 98, 108),
+    text_disabled: Color::Rgb(150, 152, 160),
+    accent: Color::Rgb(0, 120, 70),
+    accent_alt: Color::Rgb(170, 90, 0),
+    success: Color::Rgb(0, 125, 60),
+    warning: Color::Rgb(160, 110, 0),
+    error: Color::Rgb(200, 30, 30),
+    decorator_header_bg: Color::Rgb(218, 221, 228),
+    decorator_header_fg: Color::Rgb(30, 32, 40),
+    decorator_border: Color::Rgb(120, 124, 136),
+    decorator_border_active: Color::Rgb(60, 90, 160),
+    menu_bg: Color::Rgb(236, 237, 232),
+    menu_fg: Color::Rgb(30, 32, 40),
+    menu_selected_bg: Color::Rgb(0, 120, 70),
+    menu_selected_fg: Color::Rgb(255, 255, 255),
+    bottom_panel_bg: Color::Rgb(225, 226, 220),
+    bottom_panel_fg: Color::Rgb(70, 72, 82),
+    dialog_bg: Color::Rgb(245, 245, 241),
+    dialog_fg: Color::Rgb(30, 32, 40),
+    dialog_separator: Color::Rgb(140, 142, 150),
+    selection_bg: Color::Rgb(0, 120, 70),
+    selection_fg: Color::Rgb(255, 255, 255),
+    cursor_bg: Color::Rgb(0, 90, 200),
+    cursor_fg: Color::Rgb(255, 255, 255),
+    link_color: Color::Rgb(0, 90, 190),
+    link_underline: true,
+    // Shadows darken toward the top of the stack, as on NOIR.
+    shadow_bg: Color::Rgb(180, 182, 188),
+    shadow_tint: Color::Rgb(215, 216, 212),
+    debug_highlight: Color::Rgb(170, 90, 0),
+    profile_high: Color::Rgb(200, 30, 30),
+    profile_mid: Color::Rgb(160, 110, 0),
+    profile_low: Color::Rgb(0, 125, 60),
+};
+
+// ---------------------------------------------------------------------------
+// High-contrast theme: pure black, white text, yellow selection
+// ---------------------------------------------------------------------------
+
+pub const HIGH_CONTRAST: Theme = Theme {
+    name: Cow::Borrowed("high-contrast"),
+    background: Color::Rgb(0, 0, 0),
+    surface: Color::Rgb(0, 0, 0),
+    panel_bg: Color::Rgb(0, 0, 0),
+    panel_fg: Color::Rgb(255, 255, 255),
+    panel_inactive_fg: Color::Rgb(200, 200, 200),
+    panel_active_bg: Color::Rgb(0, 0, 140),
+    panel_active_fg: Color::Rgb(255, 255, 255),
+    text: Color::Rgb(255, 255, 255),
+    text_muted: Color::Rgb(210, 210, 210),
+    text_disabled: Color::Rgb(150, 150, 150),
+    accent: Color::Rgb(255, 255, 0),
+    accent_alt: Color::Rgb(0, 255, 255),
+    success: Color::Rgb(0, 255, 0),
+    warning: Color::Rgb(255, 200, 0),
+    error: Color::Rgb(255, 80, 80),
+    decorator_header_bg: Color::Rgb(0, 0, 0),
+    decorator_header_fg: Color::Rgb(255, 255, 255),
+    decorator_border: Color::Rgb(255, 255, 255),
+    decorator_border_active: Color::Rgb(255, 255, 0),
+    menu_bg: Color::Rgb(0, 0, 0),
+    menu_fg: Color::Rgb(255, 255, 255),
+    menu_selected_bg: Color::Rgb(255, 255, 0),
+    menu_selected_fg: Color::Rgb(0, 0, 0),
+    bottom_panel_bg: Color::Rgb(0, 0, 0),
+    bottom_panel_fg: Color::Rgb(255, 255, 255),
+    dialog_bg: Color::Rgb(0, 0, 0),
+    dialog_fg: Color::Rgb(255, 255, 255),
+    dialog_separator: Color::Rgb(255, 255, 255),
+    selection_bg: Color::Rgb(255, 255, 0),
+    selection_fg: Color::Rgb(0, 0, 0),
+    cursor_bg: Color::Rgb(0, 90, 200),
+    cursor_fg: Color::Rgb(255, 255, 255),
+    link_color: Color::Rgb(0, 255, 255),
+    link_underline: true,
+    shadow_bg: Color::Rgb(70, 70, 70),
+    shadow_tint: Color::Rgb(40, 40, 40),
+    debug_highlight: Color::Rgb(255, 0, 255),
+    profile_high: Color::Rgb(255, 80, 80),
+    profile_mid: Color::Rgb(255, 200, 0),
+    profile_low: Color::Rgb(0, 255, 0),
+};
+
+// ---------------------------------------------------------------------------
+// Solarized-style dark theme (Ethan Schoonover's palette)
+// ---------------------------------------------------------------------------
+
+pub const SOLARIZED: Theme = Theme {
+    name: Cow::Borrowed("solarized"),
+    background: Color::Rgb(0, 43, 54),
+    surface: Color::Rgb(7, 54, 66),
+    panel_bg: Color::Rgb(7, 54, 66),
+    panel_fg: Color::Rgb(147, 161, 161),
+    panel_inactive_fg: Color::Rgb(131, 148, 150),
+    panel_active_bg: Color::Rgb(0, 72, 90),
+    panel_active_fg: Color::Rgb(238, 232, 213),
+    text: Color::Rgb(147, 161, 161),
+    text_muted: Color::Rgb(131, 148, 150),
+    text_disabled: Color::Rgb(88, 110, 117),
+    accent: Color::Rgb(38, 139, 210),
+    accent_alt: Color::Rgb(181, 137, 0),
+    success: Color::Rgb(133, 153, 0),
+    warning: Color::Rgb(181, 137, 0),
+    error: Color::Rgb(220, 50, 47),
+    decorator_header_bg: Color::Rgb(7, 54, 66),
+    decorator_header_fg: Color::Rgb(147, 161, 161),
+    decorator_border: Color::Rgb(88, 110, 117),
+    decorator_border_active: Color::Rgb(38, 139, 210),
+    menu_bg: Color::Rgb(7, 54, 66),
+    menu_fg: Color::Rgb(147, 161, 161),
+    menu_selected_bg: Color::Rgb(38, 139, 210),
+    menu_selected_fg: Color::Rgb(0, 43, 54),
+    bottom_panel_bg: Color::Rgb(0, 43, 54),
+    bottom_panel_fg: Color::Rgb(131, 148, 150),
+    dialog_bg: Color::Rgb(7, 54, 66),
+    dialog_fg: Color::Rgb(147, 161, 161),
+    dialog_separator: Color::Rgb(88, 110, 117),
+    selection_bg: Color::Rgb(42, 161, 152),
+    selection_fg: Color::Rgb(0, 43, 54),
+    cursor_bg: Color::Rgb(108, 113, 196),
+    cursor_fg: Color::Rgb(255, 255, 255),
+    link_color: Color::Rgb(38, 139, 210),
+    link_underline: true,
+    shadow_bg: Color::Rgb(0, 30, 38),
+    shadow_tint: Color::Rgb(0, 36, 46),
+    debug_highlight: Color::Rgb(203, 75, 22),
+    profile_high: Color::Rgb(220, 50, 47),
+    profile_mid: Color::Rgb(181, 137, 0),
+    profile_low: Color::Rgb(133, 153, 0),
+};
+
+// ---------------------------------------------------------------------------
+// 16-color-safe theme: only the basic ANSI colors, so the host terminal's
+// own palette decides the exact shades (Linux VT, serial consoles, ...)
+// ---------------------------------------------------------------------------
+
+pub const ANSI16: Theme = Theme {
+    name: Cow::Borrowed("ansi16"),
+    background: Color::Black,
+    surface: Color::Black,
+    panel_bg: Color::Black,
+    panel_fg: Color::White,
+    panel_inactive_fg: Color::Gray,
+    panel_active_bg: Color::Blue,
+    panel_active_fg: Color::White,
+    text: Color::White,
+    text_muted: Color::Gray,
+    text_disabled: Color::DarkGray,
+    accent: Color::LightGreen,
+    accent_alt: Color::LightYellow,
+    success: Color::Green,
+    warning: Color::Yellow,
+    error: Color::LightRed,
+    decorator_header_bg: Color::Black,
+    decorator_header_fg: Color::White,
+    decorator_border: Color::DarkGray,
+    decorator_border_active: Color::LightCyan,
+    menu_bg: Color::Black,
+    menu_fg: Color::White,
+    menu_selected_bg: Color::Cyan,
+    menu_selected_fg: Color::Black,
+    bottom_panel_bg: Color::Black,
+    bottom_panel_fg: Color::Gray,
+    dialog_bg: Color::Black,
+    dialog_fg: Color::White,
+    dialog_separator: Color::DarkGray,
+    selection_bg: Color::Cyan,
+    selection_fg: Color::Black,
+    cursor_bg: Color::Blue,
+    cursor_fg: Color::White,
+    link_color: Color::LightBlue,
+    link_underline: true,
+    // Non-RGB colors don't interpolate; every shadow uses `shadow_bg`.
+    shadow_bg: Color::DarkGray,
+    shadow_tint: Color::DarkGray,
+    debug_highlight: Color::LightMagenta,
+    profile_high: Color::LightRed,
+    profile_mid: Color::LightYellow,
+    profile_low: Color::LightGreen,
+};
+
+/// The themes every [`ThemeRegistry`](crate::theme_registry::ThemeRegistry)
+/// starts with; [`NOIR`] is the default.
+pub const BUILTIN_THEMES: &[Theme] = &[NOIR, PAPER, HIGH_CONTRAST, SOLARIZED, ANSI16];
+
 // ---------------------------------------------------------------------------
 // Global accessor
 // ---------------------------------------------------------------------------
@@ -292,14 +534,45 @@ fn srgb_linearize(c: u8) -> f64 {
     }
 }
 
-fn relative_luminance(color: Color) -> f64 {
-    let (r, g, b) = match color {
+/// The RGB value `color` displays as, using the xterm palette for indexed
+/// and named colors.
+pub fn color_rgb(color: Color) -> (u8, u8, u8) {
+    match color {
         Color::Rgb(r, g, b) => (r, g, b),
-        _ => return 0.5,
-    };
+        Color::Indexed(idx) => crate::term_color::palette_rgb(idx),
+        named => crate::term_color::palette_rgb(ansi_index(named)),
+    }
+}
+
+pub(crate) fn relative_luminance(color: Color) -> f64 {
+    let (r, g, b) = color_rgb(color);
     0.2126 * srgb_linearize(r) + 0.7152 * srgb_linearize(g) + 0.0722 * srgb_linearize(b)
 }
 
+/// Palette index of a named ANSI color (`Rgb`/`Indexed` never reach here).
+fn ansi_index(color: Color) -> u8 {
+    match color {
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+        Color::Indexed(idx) => idx,
+        Color::Rgb(..) => 0,
+    }
+}
+
 pub fn contrast_ratio(a: Color, b: Color) -> f64 {
     let l1 = relative_luminance(a);
     let l2 = relative_luminance(b);
@@ -675,6 +948,30 @@ mod tests {
         assert!((r - 21.0).abs() < 0.5, "black/white ratio: {r}");
     }
 
+    #[test]
+    fn builtin_themes_keep_text_readable() {
+        for theme in BUILTIN_THEMES {
+            assert_eq!(
+                theme.contrast_warnings(),
+                Vec::<String>::new(),
+                "{}",
+                theme.name
+            );
+        }
+        let unreadable = Theme {
+            menu_fg: Color::Rgb(30, 30, 40),
+            ..NOIR
+        };
+        assert_eq!(unreadable.contrast_warnings().len(), 1);
+    }
+
+    #[test]
+    fn named_colors_use_the_xterm_palette_for_contrast() {
+        let r = contrast_ratio(Color::White, Color::Black);
+        assert!((r - 21.0).abs() < 0.5, "white/black ratio: {r}");
+        assert!(contrast_ratio(Color::Gray, Color::White) < 1.5);
+    }
+
     #[test]
     fn theme_is_initialized() {
         let t = NOIR;
@@ -0,0 +1,481 @@
+//! Named themes: the built-ins plus user-defined theme files.
+//!
+//! A theme file is a list of `key = value` lines (a subset of TOML) naming
+//! [`Theme`] fields. Fields it leaves out come from its `base` theme:
+//!
+//! ```text
+//! # ~/.config/term-wm/themes/dusk.theme
+//! name = "dusk"          # defaults to the file stem
+//! base = "noir"          # defaults to "noir"
+//! background = "#101018"
+//! accent = "#7aa2f7"
+//! menu_selected_fg = "black"
+//! selection_bg = 33      # xterm-256 palette index
+//! link_underline = false
+//! ```
+//!
+//! Colors are `#rrggbb`, `#rgb`, a palette index, or one of the sixteen ANSI
+//! names (`red`, `light-blue`, `dark-gray`, ...).
+
+use std::borrow::Cow;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::theme::{BUILTIN_THEMES, Color, NOIR, PAPER, Theme, relative_luminance};
+
+/// File extension of user theme files.
+pub const THEME_FILE_EXTENSION: &str = "theme";
+
+/// Error type for loading a theme file.
+#[derive(Debug)]
+pub enum ThemeError {
+    Io { path: PathBuf, source: io::Error },
+    Parse { line: usize, message: String },
+    UnknownBase(String),
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeError::Io { path, source } => write!(f, "{}: {source}", path.display()),
+            ThemeError::Parse { line, message } => write!(f, "line {line}: {message}"),
+            ThemeError::UnknownBase(name) => write!(f, "unknown base theme `{name}`"),
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ThemeError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// The themes available for switching, in display order.
+///
+/// Starts with [`BUILTIN_THEMES`]; [`register`](Self::register) and
+/// [`load_file`](Self::load_file) add more, replacing any theme of the same
+/// name. Names compare case-insensitively.
+#[derive(Debug, Clone)]
+pub struct ThemeRegistry {
+    themes: Vec<Theme>,
+}
+
+impl Default for ThemeRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ThemeRegistry {
+    pub fn new() -> Self {
+        Self {
+            themes: BUILTIN_THEMES.to_vec(),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Theme> {
+        self.themes
+            .iter()
+            .find(|theme| theme.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn themes(&self) -> &[Theme] {
+        &self.themes
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.themes.iter().map(|theme| theme.name.as_ref())
+    }
+
+    pub fn register(&mut self, theme: Theme) {
+        match self
+            .themes
+            .iter_mut()
+            .find(|t| t.name.eq_ignore_ascii_case(&theme.name))
+        {
+            Some(slot) => *slot = theme,
+            None => self.themes.push(theme),
+        }
+    }
+
+    /// Parse theme-file source. `default_name` is used when the file has no
+    /// `name` key.
+    pub fn parse(&self, source: &str, default_name: &str) -> Result<Theme, ThemeError> {
+        let mut entries = Vec::new();
+        let mut name = default_name.to_string();
+        let mut base = NOIR.name.to_string();
+        for (idx, raw) in source.lines().enumerate() {
+            let line = idx + 1;
+            let text = strip_comment(raw).trim();
+            if text.is_empty() {
+                continue;
+            }
+            let Some((key, value)) = text.split_once('=') else {
+                return Err(parse_error(
+                    line,
+                    format!("expected `key = value`, got `{text}`"),
+                ));
+            };
+            let key = key.trim();
+            let value = unquote(value.trim());
+            match key {
+                "name" => name = value.to_string(),
+                "base" => base = value.to_string(),
+                _ => entries.push((line, key, value)),
+            }
+        }
+        if name.is_empty() {
+            return Err(parse_error(1, "theme name is empty".to_string()));
+        }
+        let mut theme = self
+            .get(&base)
+            .cloned()
+            .ok_or(ThemeError::UnknownBase(base))?;
+        theme.name = Cow::Owned(name);
+        for (line, key, value) in entries {
+            if key == "link_underline" {
+                theme.link_underline = match value {
+                    "true" => true,
+                    "false" => false,
+                    _ => {
+                        return Err(parse_error(
+                            line,
+                            format!("expected true or false, got `{value}`"),
+                        ));
+                    }
+                };
+                continue;
+            }
+            let slot = color_field(&mut theme, key)
+                .ok_or_else(|| parse_error(line, format!("unknown theme field `{key}`")))?;
+            *slot = parse_color(value)
+                .ok_or_else(|| parse_error(line, format!("invalid color `{value}`")))?;
+        }
+        Ok(theme)
+    }
+
+    /// Parse and register the theme file at `path`, logging a warning for
+    /// each text pair below [`MIN_TEXT_CONTRAST`](crate::theme::MIN_TEXT_CONTRAST).
+    pub fn load_file(&mut self, path: &Path) -> Result<&Theme, ThemeError> {
+        let source = std::fs::read_to_string(path).map_err(|source| ThemeError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let stem = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default();
+        let theme = self.parse(&source, stem)?;
+        for warning in theme.contrast_warnings() {
+            tracing::warn!(
+                "theme `{}` ({}): low contrast, {warning}",
+                theme.name,
+                path.display()
+            );
+        }
+        let name = theme.name.clone();
+        self.register(theme);
+        Ok(self.get(&name).unwrap_or(&NOIR))
+    }
+
+    /// Load every `*.theme` file in `dir`, in file-name order. Files that
+    /// fail to load are logged and skipped. Returns the number loaded.
+    pub fn load_dir(&mut self, dir: &Path) -> usize {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return 0;
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|ext| ext == THEME_FILE_EXTENSION)
+            })
+            .collect();
+        paths.sort();
+        paths
+            .iter()
+            .filter(|path| match self.load_file(path) {
+                Ok(_) => true,
+                Err(err) => {
+                    tracing::warn!("skipping theme {}: {err}", path.display());
+                    false
+                }
+            })
+            .count()
+    }
+}
+
+/// Name that selects [`AutoTheme`] in `switch_theme` and `$TERM_WM_THEME`.
+pub const AUTO_THEME_NAME: &str = "auto";
+
+/// A dark/light theme pair chosen between by the host terminal's background.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutoTheme {
+    pub dark: Cow<'static, str>,
+    pub light: Cow<'static, str>,
+}
+
+impl Default for AutoTheme {
+    /// [`NOIR`] on dark backgrounds, [`PAPER`] on light ones.
+    fn default() -> Self {
+        Self {
+            dark: NOIR.name,
+            light: PAPER.name,
+        }
+    }
+}
+
+impl AutoTheme {
+    /// Luminance above which a background counts as light — the point where
+    /// black and white text have equal contrast against it.
+    const LIGHT_THRESHOLD: f64 = 0.179;
+
+    /// Name of the variant to use over the host background `bg`.
+    pub fn pick(&self, bg: (u8, u8, u8)) -> &str {
+        let luminance = relative_luminance(Color::Rgb(bg.0, bg.1, bg.2));
+        if luminance > Self::LIGHT_THRESHOLD {
+            &self.light
+        } else {
+            &self.dark
+        }
+    }
+}
+
+/// Where user theme files live: `$TERM_WM_THEME_DIR`, else
+/// `$XDG_CONFIG_HOME/term-wm/themes`, else `~/.config/term-wm/themes`.
+pub fn user_theme_dir() -> Option<PathBuf> {
+    let var = |name: &str| std::env::var_os(name).filter(|v| !v.is_empty());
+    if let Some(dir) = var(term_wm_config::THEME_DIR_ENV_VAR) {
+        return Some(PathBuf::from(dir));
+    }
+    let config = var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| var("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(
+        config
+            .join(term_wm_config::GATEWAY_NAMESPACE)
+            .join("themes"),
+    )
+}
+
+fn parse_error(line: usize, message: String) -> ThemeError {
+    ThemeError::Parse { line, message }
+}
+
+/// Drop a trailing `# comment`, leaving `#` inside quotes alone.
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (idx, ch) in line.char_indices() {
+        match ch {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..idx],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+fn parse_color(value: &str) -> Option<Color> {
+    if let Some(hex) = value.strip_prefix('#') {
+        // Checked up front: the slices below index bytes, and
+        // `from_str_radix` would also take a leading `+`.
+        if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        let channel = |s: &str| u8::from_str_radix(s, 16).ok();
+        return match hex.len() {
+            6 => Some(Color::Rgb(
+                channel(&hex[0..2])?,
+                channel(&hex[2..4])?,
+                channel(&hex[4..6])?,
+            )),
+            3 => {
+                let short = |i: usize| channel(&hex[i..i + 1]).map(|v| v * 17);
+                Some(Color::Rgb(short(0)?, short(1)?, short(2)?))
+            }
+            _ => None,
+        };
+    }
+    if let Ok(idx) = value.parse::<u8>() {
+        return Some(Color::Indexed(idx));
+    }
+    let name: String = value
+        .chars()
+        .filter(|c| !matches!(c, '-' | '_' | ' '))
+        .collect::<String>()
+        .to_ascii_lowercase()
+        .replace("grey", "gray");
+    Some(match name.as_str() {
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "gray" => Color::Gray,
+        "darkgray" => Color::DarkGray,
+        "lightred" => Color::LightRed,
+        "lightgreen" => Color::LightGreen,
+        "lightyellow" => Color::LightYellow,
+        "lightblue" => Color::LightBlue,
+        "lightmagenta" => Color::LightMagenta,
+        "lightcyan" => Color::LightCyan,
+        "white" => Color::White,
+        _ => return None,
+    })
+}
+
+/// The color field of `theme` named `key`.
+fn color_field<'a>(theme: &'a mut Theme, key: &str) -> Option<&'a mut Color> {
+    macro_rules! fields {
+        ($($field:ident),* $(,)?) => {
+            match key {
+                $(stringify!($field) => Some(&mut theme.$field),)*
+                _ => None,
+            }
+        };
+    }
+    fields!(
+        background,
+        surface,
+        panel_bg,
+        panel_fg,
+        panel_inactive_fg,
+        panel_active_bg,
+        panel_active_fg,
+        text,
+        text_muted,
+        text_disabled,
+        accent,
+        accent_alt,
+        success,
+        warning,
+        error,
+        decorator_header_bg,
+        decorator_header_fg,
+        decorator_border,
+        decorator_border_active,
+        menu_bg,
+        menu_fg,
+        menu_selected_bg,
+        menu_selected_fg,
+        bottom_panel_bg,
+        bottom_panel_fg,
+        dialog_bg,
+        dialog_fg,
+        dialog_separator,
+        selection_bg,
+        selection_fg,
+        cursor_bg,
+        cursor_fg,
+        shadow_bg,
+        shadow_tint,
+        link_color,
+        debug_highlight,
+        profile_high,
+        profile_mid,
+        profile_low,
+    )
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::{PAPER, SOLARIZED};
+
+    #[test]
+    fn builtins_are_registered_in_order() {
+        let registry = ThemeRegistry::new();
+        assert_eq!(
+            registry.names().collect::<Vec<_>>(),
+            ["noir", "paper", "high-contrast", "solarized", "ansi16"]
+        );
+        assert_eq!(registry.get("Paper"), Some(&PAPER));
+        assert!(registry.get("missing").is_none());
+    }
+
+    #[test]
+    fn parses_theme_files_over_a_base() {
+        let registry = ThemeRegistry::new();
+        let theme = registry
+            .parse(
+                r##"
+                # comment
+                base = "solarized"
+                background = "#101018"   # trailing comment
+                accent = "#fa0"
+                menu_selected_fg = light-cyan
+                selection_bg = 33
+                link_underline = false
+                "##,
+                "dusk",
+            )
+            .unwrap();
+        assert_eq!(theme.name, "dusk");
+        assert_eq!(theme.background, Color::Rgb(16, 16, 24));
+        assert_eq!(theme.accent, Color::Rgb(255, 170, 0));
+        assert_eq!(theme.menu_selected_fg, Color::LightCyan);
+        assert_eq!(theme.selection_bg, Color::Indexed(33));
+        assert!(!theme.link_underline);
+        assert_eq!(theme.panel_bg, SOLARIZED.panel_bg);
+    }
+
+    #[test]
+    fn reports_bad_theme_lines() {
+        let registry = ThemeRegistry::new();
+        let err = |src: &str| registry.parse(src, "x").unwrap_err().to_string();
+        assert_eq!(
+            err("name = \"a\"\nbogus = red"),
+            "line 2: unknown theme field `bogus`"
+        );
+        assert_eq!(err("accent = \"#12\""), "line 1: invalid color `#12`");
+        // Byte lengths that match 3 or 6 but split a UTF-8 character.
+        assert_eq!(err("accent = \"#€\""), "line 1: invalid color `#€`");
+        assert!(matches!(
+            registry.parse("background = \"#aé€\"", "x"),
+            Err(ThemeError::Parse { line: 1, .. })
+        ));
+        assert_eq!(
+            err("accent = \"#+f+f+f\""),
+            "line 1: invalid color `#+f+f+f`"
+        );
+        assert_eq!(
+            err("accent"),
+            "line 1: expected `key = value`, got `accent`"
+        );
+        assert_eq!(err("base = nope"), "unknown base theme `nope`");
+    }
+
+    #[test]
+    fn loads_and_replaces_themes_from_a_directory() {
+        let dir = std::env::temp_dir().join(format!("term-wm-themes-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("dusk.theme"), "accent = \"#7aa2f7\"\n").unwrap();
+        std::fs::write(dir.join("noir.theme"), "accent = red\n").unwrap();
+        std::fs::write(dir.join("broken.theme"), "accent = ???\n").unwrap();
+        std::fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let mut registry = ThemeRegistry::new();
+        assert_eq!(registry.load_dir(&dir), 2);
+        assert_eq!(
+            registry.get("dusk").unwrap().accent,
+            Color::Rgb(122, 162, 247)
+        );
+        assert_eq!(registry.get("noir").unwrap().accent, Color::Red);
+        assert_eq!(registry.themes().len(), 6);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
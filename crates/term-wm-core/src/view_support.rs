@@ -0,0 +1,23 @@
+//! Support types for the tag builders generated by `#[view_tag]` (see the
+//! `term-wm-view` crate).
+
+/// Placeholder for a required `view!` attribute that has not been set yet.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MissingAttribute;
+
+/// A filled required-attribute slot. Implemented for every `T`, so building
+/// a tag whose slot still holds [`MissingAttribute`] fails to compile.
+#[diagnostic::on_unimplemented(
+    message = "missing a required `view!` attribute of type `{T}`",
+    label = "required attribute not set",
+    note = "every constructor parameter of a `#[view_tag]` component is a required attribute"
+)]
+pub trait Provided<T> {
+    fn provide(self) -> T;
+}
+
+impl<T> Provided<T> for T {
+    fn provide(self) -> T {
+        self
+    }
+}
@@ -105,6 +105,10 @@ pub struct Window {
 
     /// Visual chrome rules across layout modes.
     chrome_config: ModeChromeConfig,
+    /// Border and label color overriding the theme's.
+    accent: Option<crate::theme::Color>,
+    /// Short tag shown ahead of the title.
+    label: Option<String>,
 
     component_key: ComponentKey,
     /// What happens when this window is closed.
@@ -137,6 +141,8 @@ impl Window {
             is_maximized: false,
             void_id: None,
             chrome_config: ModeChromeConfig::default(),
+            accent: None,
+            label: None,
             component_key,
             close_policy: ClosePolicy::default(),
             closable: true,
@@ -168,6 +174,24 @@ impl Window {
         self.title.clone().unwrap_or_else(|| format!("{:?}", key))
     }
 
+    // ── Accent & Label ────────────────────────────────────────────────────────
+
+    pub fn accent(&self) -> Option<crate::theme::Color> {
+        self.accent
+    }
+
+    pub fn set_accent(&mut self, accent: Option<crate::theme::Color>) {
+        self.accent = accent;
+    }
+
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    pub fn set_label(&mut self, label: Option<String>) {
+        self.label = label;
+    }
+
     // ── State ─────────────────────────────────────────────────────────────────
 
     pub fn state(&self) -> WindowState {
@@ -373,6 +373,7 @@ impl Component<TermWmAction> for SelComponent {
 pub struct RenderTracker {
     pub last_area: Option<crate::Rect>,
     pub render_count: usize,
+    pub host_cursor: Option<crate::components::HostCursor>,
 }
 
 impl Component<TermWmAction> for RenderTracker {
@@ -386,6 +387,9 @@ impl Component<TermWmAction> for RenderTracker {
         self.last_area = Some(area);
         self.render_count += 1;
     }
+    fn host_cursor(&self) -> Option<crate::components::HostCursor> {
+        self.host_cursor
+    }
     fn init(&mut self) {}
     fn on_mount(&mut self, _key: WindowKey, _app: &AppContext) {}
     fn hitbox_id(&self) -> Option<HitboxId> {
@@ -186,6 +186,14 @@ impl<C: Component<TermWmAction>, L: WmComponent, O: Overlay<TermWmAction>> Windo
                     }));
                 }
             }
+            // Only a hint: typing a name adds the real entry (see
+            // `TermWmAction::with_query_argument`).
+            items.push(MenuDisplayItem::Item(MenuItem {
+                label: "Rename Workspace: type the new name".into(),
+                icon: Some("✎"),
+                action: crate::actions::TermWmAction::RenameWorkspace(String::new()),
+                disabled: true,
+            }));
             items.push(MenuDisplayItem::Item(MenuItem {
                 label: "Detach Viewer".into(),
                 icon: Some("-"),
@@ -288,6 +296,95 @@ impl<C: Component<TermWmAction>, L: WmComponent, O: Overlay<TermWmAction>> Windo
             }
         }
 
+        // Theme group — one entry per registered theme plus the automatic
+        // dark/light one, the active choice disabled
+        {
+            items.push(MenuDisplayItem::Separator);
+            let auto = self.auto_theme().is_some();
+            let names = self
+                .theme_registry()
+                .names()
+                .map(|name| {
+                    let active = !auto && name.eq_ignore_ascii_case(&self.theme().name);
+                    (name, active)
+                })
+                .chain([(crate::theme_registry::AUTO_THEME_NAME, auto)]);
+            for (name, active) in names {
+                let action = crate::actions::TermWmAction::SwitchTheme(name.to_string());
+                items.push(MenuDisplayItem::Item(MenuItem {
+                    label: action.to_string().into(),
+                    icon: Some("◐"),
+                    action,
+                    disabled: active,
+                }));
+            }
+        }
+
+        // Window appearance group — accent colors (the current one disabled)
+        // and the label
+        if has_active {
+            items.push(MenuDisplayItem::Separator);
+            let raw_title = self.window_title(focused);
+            let title = crate::utils::truncate_with_ellipsis(&raw_title, 25);
+            let accent = self.window_accent(focused);
+            let accents = crate::chrome::ACCENT_COLORS
+                .iter()
+                .map(|&(name, color)| (name, Some(color)))
+                .chain([("Theme", None)]);
+            for (name, color) in accents {
+                items.push(MenuDisplayItem::Item(MenuItem {
+                    label: format!("Set {} Accent: {}", title, name).into(),
+                    icon: Some("◆"),
+                    action: crate::actions::TermWmAction::SetWindowAccent(focused, color),
+                    disabled: color == accent,
+                }));
+            }
+            // Only a hint: typing a label adds the real entry (see
+            // `TermWmAction::with_query_argument`).
+            items.push(MenuDisplayItem::Item(MenuItem {
+                label: format!("Label {}: type a label", title).into(),
+                icon: Some("✎"),
+                action: crate::actions::TermWmAction::SetWindowLabel(focused, Some(String::new())),
+                disabled: true,
+            }));
+            if self.window_label(focused).is_some() {
+                items.push(MenuDisplayItem::Item(MenuItem {
+                    label: format!("Clear {} Label", title).into(),
+                    icon: Some("✎"),
+                    action: crate::actions::TermWmAction::SetWindowLabel(focused, None),
+                    disabled: false,
+                }));
+            }
+        }
+
+        // Screenshot group — whole screen, then the focused window, per format
+        {
+            items.push(MenuDisplayItem::Separator);
+            for format in crate::screenshot::ScreenshotFormat::ALL {
+                let action = crate::actions::TermWmAction::ExportScreenshot(format);
+                items.push(MenuDisplayItem::Item(MenuItem {
+                    label: action.to_string().into(),
+                    icon: Some("⎙"),
+                    action,
+                    disabled: false,
+                }));
+            }
+            if has_active {
+                let raw_title = self.window_title(focused);
+                let title = crate::utils::truncate_with_ellipsis(&raw_title, 25);
+                for format in crate::screenshot::ScreenshotFormat::ALL {
+                    items.push(MenuDisplayItem::Item(MenuItem {
+                        label: format!("Export {} Screenshot: {}", title, format.label()).into(),
+                        icon: Some("⎙"),
+                        action: crate::actions::TermWmAction::ExportWindowScreenshot(
+                            focused, format,
+                        ),
+                        disabled: false,
+                    }));
+                }
+            }
+        }
+
         // Settings groups
         {
             {
@@ -635,6 +732,108 @@ mod tests {
         );
     }
 
+    #[test]
+    #[serial(wm_menu_items)]
+    fn wm_menu_items_list_themes_and_switching_applies_them() {
+        use crate::components::{MenuDisplayItem, MenuItem};
+        let mut wm = make_wm::<TestOverlay>();
+        let themes = |wm: &WindowManager<TestComponent, NoopWmComponent, TestOverlay>| {
+            wm.wm_menu_items(&[], "")
+                .into_iter()
+                .filter_map(|entry| match entry {
+                    MenuDisplayItem::Item(MenuItem {
+                        label,
+                        action: TermWmAction::SwitchTheme(_),
+                        disabled,
+                        ..
+                    }) => Some((label.to_string(), disabled)),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        let items = themes(&wm);
+        assert_eq!(items.len(), 6);
+        assert_eq!(items[0], ("Switch Theme: noir".to_string(), false));
+        assert_eq!(items[1], ("Switch Theme: paper".to_string(), false));
+        assert_eq!(items[5], ("Switch Theme: auto".to_string(), true));
+
+        assert!(wm.switch_theme("paper"));
+        assert_eq!(wm.theme(), &crate::theme::PAPER);
+        assert_eq!(
+            wm.component_context(true).config().theme,
+            crate::theme::PAPER
+        );
+        let items = themes(&wm);
+        assert!(items[1].1, "current theme entry is disabled");
+        assert!(!items[5].1, "choosing a theme turns off auto");
+        assert!(!wm.switch_theme("missing"));
+        assert_eq!(wm.theme().name, "paper");
+    }
+
+    #[test]
+    #[serial(wm_menu_items)]
+    fn wm_menu_items_offer_accents_and_label_for_focused_window() {
+        use crate::components::{MenuDisplayItem, MenuItem};
+        let mut wm = make_wm::<TestOverlay>();
+        let key = wm.create_window(TestComponent::Noop(crate::components::NoopComponent));
+        wm.focus_window_key(key);
+        wm.set_window_title(key, "alpha");
+        let appearance = |wm: &WindowManager<TestComponent, NoopWmComponent, TestOverlay>| {
+            wm.wm_menu_items(&[], "")
+                .into_iter()
+                .filter_map(|entry| match entry {
+                    MenuDisplayItem::Item(MenuItem {
+                        label,
+                        action:
+                            TermWmAction::SetWindowAccent(..) | TermWmAction::SetWindowLabel(..),
+                        disabled,
+                        ..
+                    }) => Some((label.to_string(), disabled)),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        let items = appearance(&wm);
+        assert_eq!(items.len(), 8);
+        assert_eq!(items[0], ("Set alpha Accent: Red".to_string(), false));
+        assert_eq!(items[6], ("Set alpha Accent: Theme".to_string(), true));
+        assert_eq!(items[7], ("Label alpha: type a label".to_string(), true));
+
+        wm.set_window_accent(key, Some(crate::theme::Color::Red));
+        wm.set_window_label(key, Some("  db ".to_string()));
+        assert_eq!(wm.window_label(key), Some("db"));
+        let items = appearance(&wm);
+        assert!(items[0].1, "current accent entry is disabled");
+        assert!(!items[6].1);
+        assert_eq!(items[8], ("Clear alpha Label".to_string(), false));
+
+        wm.set_window_label(key, Some("   ".to_string()));
+        assert_eq!(wm.window_label(key), None, "a blank label clears it");
+    }
+
+    #[test]
+    #[serial(wm_menu_items)]
+    fn auto_theme_follows_host_background_until_a_theme_is_chosen() {
+        use crate::events::HostColorKind;
+        let mut wm = make_wm::<TestOverlay>();
+        wm.set_host_color(HostColorKind::Background, (250, 250, 245));
+        assert_eq!(wm.theme().name, "paper");
+        wm.set_host_color(HostColorKind::Background, (20, 20, 30));
+        assert_eq!(wm.theme().name, "noir");
+        assert_eq!(
+            term_wm_pty_engine::default_colors().1,
+            (20, 20, 30),
+            "children are told the host background"
+        );
+
+        assert!(wm.switch_theme("solarized"));
+        wm.set_host_color(HostColorKind::Background, (255, 255, 255));
+        assert_eq!(wm.theme().name, "solarized");
+
+        assert!(wm.switch_theme("auto"));
+        assert_eq!(wm.theme().name, "paper");
+    }
+
     #[test]
     #[serial(wm_menu_items)]
     fn wm_menu_items_omits_workspace_group_when_runtime_disabled() {
@@ -659,6 +858,7 @@ mod tests {
                         action,
                         TermWmAction::NewWorkspace
                             | TermWmAction::SwitchWorkspace(_)
+                            | TermWmAction::RenameWorkspace(_)
                             | TermWmAction::DetachCurrentClient
                     )
             )
@@ -677,7 +877,7 @@ mod tests {
 
         // Runtime enabled by default: the workspace group must offer
         // New Workspace, Switch to Workspace entries (current one disabled),
-        // and Detach Viewer.
+        // the Rename Workspace hint and Detach Viewer.
         let items = wm.wm_menu_items(&["dev".into(), "prod".into()], "dev");
 
         let mut workspace: Vec<(String, bool)> = items
@@ -688,6 +888,7 @@ mod tests {
                     action:
                         TermWmAction::NewWorkspace
                         | TermWmAction::SwitchWorkspace(_)
+                        | TermWmAction::RenameWorkspace(_)
                         | TermWmAction::DetachCurrentClient,
                     disabled,
                     ..
@@ -702,6 +903,7 @@ mod tests {
             vec![
                 ("Detach Viewer".to_string(), false),
                 ("New Workspace".to_string(), false),
+                ("Rename Workspace: type the new name".to_string(), true),
                 ("Switch to Workspace: dev".to_string(), true),
                 ("Switch to Workspace: prod".to_string(), false),
             ],
@@ -4,6 +4,7 @@ use crate::components::{Component, Overlay, WmComponent};
 use term_wm_layout_engine::{EdgeResistance, LayoutRect, detect_corner_snap, detect_edge_snap};
 
 use super::{SnapPreviewState, WindowManager};
+use crate::layout::tiling::Gaps;
 use crate::layout::{InsertPosition, LayoutNode, TilingLayout};
 use crate::window::{WindowKey, WindowState};
 
@@ -60,6 +61,32 @@ impl<C: Component<TermWmAction>, L: WmComponent, O: Overlay<TermWmAction>> Windo
         true
     }
 
+    /// Float `key` at `rect`, detaching it from the tiling layout first if
+    /// it was tiled. Scripted moves and resizes (control mode) go through
+    /// here. Returns `false` for an unknown window.
+    pub fn place_floating(&mut self, key: WindowKey, rect: Rect) -> bool {
+        if !self.windows.contains_key(key) {
+            return false;
+        }
+        if !self.is_window_floating(key) && self.layout_contains(key) {
+            self.detach_from_tiling_layout(key);
+        }
+        self.set_floating_rect(
+            key,
+            Some(crate::window::FloatRectSpec::Absolute(
+                crate::window::FloatRect {
+                    x: rect.x,
+                    y: rect.y,
+                    width: rect.width.max(1),
+                    height: rect.height.max(1),
+                },
+            )),
+        );
+        self.bring_to_front_key(key);
+        self.mark_layout_dirty();
+        true
+    }
+
     pub(super) fn layout_contains(&self, key: WindowKey) -> bool {
         self.managed_layout
             .as_ref()
@@ -233,8 +260,13 @@ impl<C: Component<TermWmAction>, L: WmComponent, O: Overlay<TermWmAction>> Windo
                     .map(|key| (key, self.region(key)))
                     .collect();
                 if !floating.is_empty() {
-                    let positions =
-                        simulate_position_based_layout(floating, dragging_key, corner_pos, area);
+                    let positions = simulate_position_based_layout(
+                        floating,
+                        dragging_key,
+                        corner_pos,
+                        area,
+                        self.config.tile_gaps(),
+                    );
                     let dragged_rect = self.region(dragging_key);
                     self.snap_preview_cache.update(
                         mouse_x,
@@ -326,8 +358,13 @@ impl<C: Component<TermWmAction>, L: WmComponent, O: Overlay<TermWmAction>> Windo
                     .map(|key| (key, self.region(key)))
                     .collect();
                 if !floating.is_empty() {
-                    let positions =
-                        simulate_position_based_layout(floating, dragging_key, pos, area);
+                    let positions = simulate_position_based_layout(
+                        floating,
+                        dragging_key,
+                        pos,
+                        area,
+                        self.config.tile_gaps(),
+                    );
                     let dragged_rect = self.region(dragging_key);
                     self.snap_preview_cache.update(
                         mouse_x,
@@ -506,7 +543,7 @@ impl<C: Component<TermWmAction>, L: WmComponent, O: Overlay<TermWmAction>> Windo
                 layout.insert_window_balanced(key, self.managed_area);
             }
         } else {
-            self.managed_layout = Some(crate::layout::TilingLayout::new(LayoutNode::leaf(key)));
+            self.managed_layout = Some(self.new_tiling_layout(LayoutNode::leaf(key)));
         }
         self.focus_window_key(key);
         self.bifurcate_draw_order();
@@ -599,13 +636,13 @@ impl<C: Component<TermWmAction>, L: WmComponent, O: Overlay<TermWmAction>> Windo
 
             if !with_rects.is_empty() {
                 let root_node = LayoutNode::from_rects(&with_rects);
-                let mut layout = TilingLayout::new(root_node);
+                let mut layout = self.new_tiling_layout(root_node);
                 for key in without_rects {
                     layout.insert_window_balanced(key, self.managed_area);
                 }
                 self.managed_layout = Some(layout);
             } else if !without_rects.is_empty() {
-                let mut layout = TilingLayout::new(LayoutNode::leaf(without_rects[0]));
+                let mut layout = self.new_tiling_layout(LayoutNode::leaf(without_rects[0]));
                 for &key in &without_rects[1..] {
                     layout.insert_window_balanced(key, self.managed_area);
                 }
@@ -675,14 +712,14 @@ impl<C: Component<TermWmAction>, L: WmComponent, O: Overlay<TermWmAction>> Windo
 
         if others.is_empty() {
             // Create a void split to preserve snap geometry
-            let mut layout = TilingLayout::new_void();
+            let mut layout = self.new_tiling_layout(LayoutNode::void());
             layout.split_root(anchor_key, snap_position);
             self.managed_layout = Some(layout);
             return;
         }
 
         let sorted = calculate_tiling_order(others, target_rect);
-        let mut layout = TilingLayout::new(LayoutNode::leaf(sorted[0].0));
+        let mut layout = self.new_tiling_layout(LayoutNode::leaf(sorted[0].0));
         for (key, _) in &sorted[1..] {
             layout.insert_window_balanced(*key, area);
         }
@@ -720,6 +757,7 @@ fn simulate_position_based_layout(
     anchor_key: WindowKey,
     snap_position: InsertPosition,
     workspace_bounds: Rect,
+    gaps: Gaps,
 ) -> Vec<(WindowKey, Rect)> {
     if windows.is_empty() {
         return Vec::new();
@@ -738,7 +776,7 @@ fn simulate_position_based_layout(
         .collect();
 
     if others.is_empty() {
-        let mut layout = TilingLayout::new_void();
+        let mut layout = TilingLayout::new_void().with_gaps(gaps);
         layout.split_root(anchor_key, snap_position);
         return layout
             .regions(workspace_bounds)
@@ -748,7 +786,7 @@ fn simulate_position_based_layout(
     }
 
     let sorted = calculate_tiling_order(others, target_rect);
-    let mut layout = TilingLayout::new(LayoutNode::leaf(sorted[0].0));
+    let mut layout = TilingLayout::new(LayoutNode::leaf(sorted[0].0)).with_gaps(gaps);
     for (key, _) in &sorted[1..] {
         layout.insert_window_balanced(*key, workspace_bounds);
     }
@@ -85,6 +85,8 @@ impl<C: Component<TermWmAction>, L: WmComponent, O: Overlay<TermWmAction>> Windo
                             | crate::actions::TermWmAction::MaximizeWindow(_)
                             | crate::actions::TermWmAction::MinimizeWindow(_)
                             | crate::actions::TermWmAction::CloseWindow(_)
+                            | crate::actions::TermWmAction::SetWindowAccent(..)
+                            | crate::actions::TermWmAction::SetWindowLabel(..)
                             | crate::actions::TermWmAction::SendSuperKeyToWindow(_)
                             | crate::actions::TermWmAction::SendSuperKeyToFocusedWindow
                     );
@@ -95,7 +97,21 @@ impl<C: Component<TermWmAction>, L: WmComponent, O: Overlay<TermWmAction>> Windo
                                 item.action,
                                 crate::actions::TermWmAction::SwitchWorkspace(_)
                                     | crate::actions::TermWmAction::NewWorkspace
+                                    | crate::actions::TermWmAction::RenameWorkspace(_)
                             ));
+                    let always_pass = always_pass
+                        || (matches!(item.action, crate::actions::TermWmAction::SwitchTheme(_))
+                            && supported.contains(&crate::actions::TermWmAction::SwitchTheme(
+                                String::new(),
+                            )));
+                    let always_pass = always_pass
+                        || (matches!(
+                            item.action,
+                            crate::actions::TermWmAction::ExportScreenshot(_)
+                                | crate::actions::TermWmAction::ExportWindowScreenshot(..)
+                        ) && supported.iter().any(|a| {
+                            matches!(a, crate::actions::TermWmAction::ExportScreenshot(_))
+                        }));
                     supported.contains(&item.action) || always_pass
                 }
                 MenuDisplayItem::Separator => true,
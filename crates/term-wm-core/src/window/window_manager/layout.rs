@@ -157,11 +157,22 @@ impl<C: Component<TermWmAction>, L: WmComponent, O: Overlay<TermWmAction>> Windo
         self.handles.extend(handles);
     }
 
+    /// A tiling tree over `root` with the configured gaps. Every managed
+    /// layout is built here, so its regions and handles always include them.
+    pub(super) fn new_tiling_layout(&self, root: LayoutNode<WindowKey>) -> TilingLayout<WindowKey> {
+        TilingLayout::new(root).with_gaps(self.config.tile_gaps())
+    }
+
     pub fn set_managed_layout(&mut self, layout: TilingLayout<WindowKey>) {
-        self.managed_layout = Some(TilingLayout::new(super::map_layout_node(layout.root())));
+        self.managed_layout = Some(self.new_tiling_layout(super::map_layout_node(layout.root())));
         self.clear_all_floating();
     }
 
+    /// The tiling tree of managed windows, if any.
+    pub fn managed_layout(&self) -> Option<&TilingLayout<WindowKey>> {
+        self.managed_layout.as_ref()
+    }
+
     pub fn set_managed_layout_none(&mut self) {
         if self.managed_layout.is_none() {
             return;
@@ -250,6 +261,9 @@ impl<C: Component<TermWmAction>, L: WmComponent, O: Overlay<TermWmAction>> Windo
 
     /// Single read boundary for border rendering.
     pub fn window_borders_enabled(&self, key: WindowKey) -> bool {
+        if !self.config.border_style.is_visible() {
+            return false;
+        }
         self.window(key)
             .map(|w| {
                 w.chrome_config()
@@ -432,7 +446,7 @@ impl<C: Component<TermWmAction>, L: WmComponent, O: Overlay<TermWmAction>> Windo
                 .collect();
             tiled.sort_by_key(|k| self.windows.get(*k).map_or(0, Window::creation_order));
             if let Some(first) = tiled.first().copied() {
-                let mut layout = TilingLayout::new(LayoutNode::leaf(first));
+                let mut layout = self.new_tiling_layout(LayoutNode::leaf(first));
                 for key in tiled.iter().skip(1) {
                     layout.insert_window_balanced(*key, self.managed_area);
                 }
@@ -844,7 +858,7 @@ impl<C: Component<TermWmAction>, L: WmComponent, O: Overlay<TermWmAction>> Windo
             return;
         }
         if self.managed_layout.is_none() {
-            self.managed_layout = Some(TilingLayout::new(LayoutNode::leaf(key)));
+            self.managed_layout = Some(self.new_tiling_layout(LayoutNode::leaf(key)));
             return;
         }
 
@@ -26,7 +26,7 @@ pub mod system_tags {
 }
 
 use crate::Rect;
-use crate::events::{Event, MouseEvent, MouseEventKind};
+use crate::events::{Event, HostColorKind, MouseEvent, MouseEventKind};
 use slotmap::SlotMap;
 
 use super::ComponentKey;
@@ -43,7 +43,10 @@ use crate::layout::{InsertPosition, LayoutNode, RegionMap, SplitHandle, TilingLa
 use crate::notification::NotificationQueue;
 use crate::power_profile::PowerProfile;
 use crate::reaper::Reaper;
+use crate::screenshot::ScreenshotRequest;
 use crate::task_scheduler::{TaskHandle, TaskId};
+use crate::theme::{Theme, color_rgb};
+use crate::theme_registry::{AUTO_THEME_NAME, AutoTheme, ThemeRegistry};
 use crate::utils::DelayedReleaseBool;
 use crate::utils::KeyedTaskDebouncer;
 #[cfg(test)]
@@ -60,6 +63,11 @@ use term_wm_pty_engine::DirectInputMode;
 const DIRECT_MODE_TOAST_DEBOUNCE: Duration = Duration::from_millis(200);
 /// How long a Direct Input Mode toast stays visible.
 const DIRECT_MODE_TOAST_TTL: Duration = Duration::from_secs(3);
+/// How long the "Screenshot saved to ..." toast stays visible; long enough
+/// to read the path.
+const SCREENSHOT_TOAST_TTL: Duration = Duration::from_secs(6);
+/// How often finished background screenshot exports are checked for.
+const SCREENSHOT_POLL_INTERVAL: Duration = Duration::from_millis(50);
 
 /// State machine for in-progress mouse operations (drag, resize).
 ///
@@ -353,6 +361,11 @@ pub struct WindowManager<
     selection_dragging: bool,
     selection_text: Option<String>,
     config: WmConfig,
+    /// Themes offered by the command palette; always holds `config.theme`.
+    themes: ThemeRegistry,
+    /// Host terminal default colors from OSC 10/11, once reported.
+    host_fg: Option<(u8, u8, u8)>,
+    host_bg: Option<(u8, u8, u8)>,
     hint_visibility: HintVisibility,
     command_menu_opened_at: Option<Instant>,
     /// ID of the drag-snap timer in the TaskScheduler, for cancellation.
@@ -363,6 +376,21 @@ pub struct WindowManager<
     /// system-level timers (super-passthrough, drag-snap).
     system_task_handle: Option<TaskHandle<SystemTask>>,
     pub(crate) last_frame_area: LayoutRect,
+    /// Bumped whenever every cached window frame may be stale; part of
+    /// every window's damage signature.
+    window_damage_epoch: u64,
+    /// Per-window counterpart of `window_damage_epoch`, bumped by
+    /// `damage_window`.
+    window_damage: HashMap<WindowKey, u64>,
+    /// Screenshot waiting for the next composed frame.
+    pending_screenshot: Option<ScreenshotRequest>,
+    /// Outcome of the last export, pushed as a toast by
+    /// `SystemTask::FlushScreenshotToast`.
+    screenshot_toast: Option<String>,
+    /// Exports serializing and writing on their own threads.
+    screenshot_exports: Vec<std::thread::JoinHandle<std::io::Result<std::path::PathBuf>>>,
+    /// ID of the `SystemTask::PollScreenshotExports` timer while exports run.
+    screenshot_poll_id: Option<TaskId>,
     overlays: SlotMap<OverlayKey, O>,
     /// TypeId-keyed registries for strongly-typed system window/overlay resolution.
     pub system_windows: HashMap<TypeId, WindowKey>,
@@ -771,6 +799,33 @@ impl<C: Component<TermWmAction> + 'static, L: WmComponent, O: Overlay<TermWmActi
         }
     }
 
+    /// The window's accent color, drawn on its border and label in place of
+    /// the theme's border color.
+    pub fn window_accent(&self, key: WindowKey) -> Option<crate::theme::Color> {
+        self.window(key).and_then(Window::accent)
+    }
+
+    pub fn set_window_accent(&mut self, key: WindowKey, accent: Option<crate::theme::Color>) {
+        if let Some(w) = self.windows.get_mut(key) {
+            w.set_accent(accent);
+        }
+    }
+
+    /// The tag shown ahead of the window's title.
+    pub fn window_label(&self, key: WindowKey) -> Option<&str> {
+        self.window(key).and_then(Window::label)
+    }
+
+    /// Set the window's label; a blank one clears it.
+    pub fn set_window_label(&mut self, key: WindowKey, label: Option<String>) {
+        let label = label
+            .map(|l| l.trim().to_string())
+            .filter(|l| !l.is_empty());
+        if let Some(w) = self.windows.get_mut(key) {
+            w.set_label(label);
+        }
+    }
+
     pub fn window_title(&self, key: WindowKey) -> String {
         let base = self
             .window(key)
@@ -852,6 +907,8 @@ impl<C: Component<TermWmAction> + 'static, L: WmComponent, O: Overlay<TermWmActi
         let mouse_capture_enabled = config.mouse_capture_enabled;
         let clipboard = Some(Clipboard::new());
         let floating_resize_offscreen = config.floating_resize_offscreen;
+        let mut themes = ThemeRegistry::new();
+        themes.register(config.theme.clone());
         Self {
             focus: FocusRing::new(
                 /* placeholder, will be set on first window */
@@ -895,6 +952,9 @@ impl<C: Component<TermWmAction> + 'static, L: WmComponent, O: Overlay<TermWmActi
             window_selection_dirty: false,
             hint_visibility: config.hint_visibility,
             config,
+            themes,
+            host_fg: None,
+            host_bg: None,
             command_menu_opened_at: None,
             drag_timer_id: None,
             temporal_timer_id: None,
@@ -904,6 +964,8 @@ impl<C: Component<TermWmAction> + 'static, L: WmComponent, O: Overlay<TermWmActi
                 SystemTask::FlushDirectModeToast,
             ),
             last_frame_area: Rect::default(),
+            window_damage_epoch: 0,
+            window_damage: HashMap::new(),
             scroll_keyboard_enabled_default: true,
             floating_resize_offscreen,
             z_order: Vec::new(),
@@ -931,6 +993,10 @@ impl<C: Component<TermWmAction> + 'static, L: WmComponent, O: Overlay<TermWmActi
             system_windows: HashMap::new(),
             system_overlays: HashMap::new(),
             tab_outline_until: None,
+            pending_screenshot: None,
+            screenshot_toast: None,
+            screenshot_exports: Vec::new(),
+            screenshot_poll_id: None,
             last_snap_cursor: None,
             input_mode: crate::actions::WmInputMode::Passthrough,
             fab_enabled: true,
@@ -1130,6 +1196,106 @@ impl<C: Component<TermWmAction> + 'static, L: WmComponent, O: Overlay<TermWmActi
         &self.config.keybindings
     }
 
+    pub fn theme(&self) -> &Theme {
+        &self.config.theme
+    }
+
+    /// Apply `theme` to all chrome, panels and overlays from the next frame,
+    /// registering it if its name is new. Turns off the automatic theme.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.themes.register(theme.clone());
+        self.config.theme = theme;
+        self.config.auto_theme = None;
+        self.sync_default_colors();
+    }
+
+    /// Switch to the registered theme `name`, or back to the automatic
+    /// dark/light theme for [`AUTO_THEME_NAME`]. Returns `false` (and keeps
+    /// the current theme) when there is no such theme.
+    pub fn switch_theme(&mut self, name: &str) -> bool {
+        if name.eq_ignore_ascii_case(AUTO_THEME_NAME) {
+            self.set_auto_theme(Some(AutoTheme::default()));
+            return true;
+        }
+        let Some(theme) = self.themes.get(name).cloned() else {
+            return false;
+        };
+        self.config.theme = theme;
+        self.config.auto_theme = None;
+        self.sync_default_colors();
+        true
+    }
+
+    /// The dark/light pair followed by the host background, if enabled.
+    pub fn auto_theme(&self) -> Option<&AutoTheme> {
+        self.config.auto_theme.as_ref()
+    }
+
+    /// Follow `auto` (or stop following with `None`); applies the matching
+    /// variant at once when the host background is already known.
+    pub fn set_auto_theme(&mut self, auto: Option<AutoTheme>) {
+        self.config.auto_theme = auto;
+        self.apply_auto_theme();
+    }
+
+    /// Record a default color reported by the host terminal (OSC 10/11).
+    /// A new background re-picks the automatic theme.
+    pub fn set_host_color(&mut self, kind: HostColorKind, rgb: (u8, u8, u8)) {
+        match kind {
+            HostColorKind::Foreground => self.host_fg = Some(rgb),
+            HostColorKind::Background => self.host_bg = Some(rgb),
+        }
+        self.apply_auto_theme();
+    }
+
+    /// The host terminal's default background, once reported.
+    pub fn host_background(&self) -> Option<(u8, u8, u8)> {
+        self.host_bg
+    }
+
+    fn apply_auto_theme(&mut self) {
+        if let (Some(auto), Some(bg)) = (&self.config.auto_theme, self.host_bg)
+            && let Some(theme) = self.themes.get(auto.pick(bg))
+            && theme.name != self.config.theme.name
+        {
+            self.config.theme = theme.clone();
+        }
+        self.sync_default_colors();
+    }
+
+    /// Terminal panes leave default-colored cells unstyled, so children see
+    /// the host's own colors; fall back to the theme until the host reports.
+    fn sync_default_colors(&self) {
+        let theme = &self.config.theme;
+        term_wm_pty_engine::set_default_colors(
+            self.host_fg.unwrap_or_else(|| color_rgb(theme.text)),
+            self.host_bg.unwrap_or_else(|| color_rgb(theme.background)),
+        );
+    }
+
+    pub fn theme_registry(&self) -> &ThemeRegistry {
+        &self.themes
+    }
+
+    pub fn theme_registry_mut(&mut self) -> &mut ThemeRegistry {
+        &mut self.themes
+    }
+
+    /// Load the user's theme files (see
+    /// [`user_theme_dir`](crate::theme_registry::user_theme_dir)) and switch
+    /// to `$TERM_WM_THEME` when it names a known theme.
+    pub fn load_user_themes(&mut self) {
+        if let Some(dir) = crate::theme_registry::user_theme_dir() {
+            self.themes.load_dir(&dir);
+        }
+        if let Ok(name) = std::env::var(term_wm_config::THEME_ENV_VAR)
+            && !name.is_empty()
+            && !self.switch_theme(&name)
+        {
+            tracing::warn!("{}: unknown theme `{name}`", term_wm_config::THEME_ENV_VAR);
+        }
+    }
+
     pub fn hint_visibility(&self) -> HintVisibility {
         self.hint_visibility
     }
@@ -1179,6 +1345,35 @@ impl<C: Component<TermWmAction> + 'static, L: WmComponent, O: Overlay<TermWmActi
         ctx
     }
 
+    /// Drop every cached window frame: the next render redraws all windows.
+    /// For changes that reach every window at once, such as a host resize
+    /// or a new host palette.
+    pub fn damage_all_windows(&mut self) {
+        self.window_damage_epoch = self.window_damage_epoch.wrapping_add(1);
+    }
+
+    /// Drop `key`'s cached frame: the next render redraws that window. The
+    /// runner calls this for the window an input event lands on, and
+    /// [`process_action`](Self::process_action) for the window an action
+    /// reaches, since either may change what it shows without moving it.
+    pub fn damage_window(&mut self, key: WindowKey) {
+        let epoch = self.window_damage.entry(key).or_default();
+        *epoch = epoch.wrapping_add(1);
+    }
+
+    /// Current damage epoch of `key`; changes whenever
+    /// [`damage_window`](Self::damage_window) or
+    /// [`damage_all_windows`](Self::damage_all_windows) is called for it.
+    pub fn window_damage_epoch(&self, key: WindowKey) -> u64 {
+        let own = self.window_damage.get(&key).copied().unwrap_or(0);
+        self.window_damage_epoch.wrapping_add(own)
+    }
+
+    /// Window under the screen cell (`column`, `row`), topmost first.
+    pub fn window_at(&self, column: u16, row: u16) -> Option<WindowKey> {
+        self.hit_test_region_topmost(column, row, &self.managed_draw_order)
+    }
+
     /// Normalised z-depth [0.0–1.0] for a drawable at `position` in a
     /// stack of `total` items (windows + overlays).  The topmost item
     /// always maps to 1.0 (darkest shadow).
@@ -1311,6 +1506,23 @@ impl<C: Component<TermWmAction> + 'static, L: WmComponent, O: Overlay<TermWmActi
         }
     }
 
+    /// Where the host terminal should show its cursor: the focused window's
+    /// [`HostCursor`](crate::components::HostCursor), unless something drawn
+    /// over the window (an overlay, panel or another window) covers that cell.
+    pub fn focused_host_cursor(&self) -> Option<crate::components::HostCursor> {
+        let key = self.focused_window();
+        let cursor = self.component_for_key(key)?.host_cursor()?;
+        let pos = MousePosition {
+            column: i16::try_from(cursor.x).ok()?,
+            row: i16::try_from(cursor.y).ok()?,
+            space: CoordSpace::Screen,
+        };
+        match self.hitbox_registry.hit_test(pos) {
+            Some((_, ComponentOwner::Window(owner), _)) if owner == key => Some(cursor),
+            _ => None,
+        }
+    }
+
     /// Return the window region map (for resize outline rendering).
     pub fn regions(&self) -> &crate::layout::RegionMap<WindowKey> {
         &self.regions
@@ -2282,6 +2494,15 @@ impl<C: Component<TermWmAction> + 'static, L: WmComponent, O: Overlay<TermWmActi
         self.clipboard.as_mut()
     }
 
+    /// Rebuild the clipboard handle with OSC 52 emission on or off — off
+    /// when the host terminal cannot set the system clipboard, so copies stop
+    /// writing escape sequences it would print or ignore.
+    pub fn set_osc52_enabled(&mut self, enabled: bool) {
+        let mut config = term_clipboard::ClipboardConfig::default();
+        config.osc52_enabled = enabled;
+        self.clipboard = Some(Clipboard::with_config(config));
+    }
+
     pub fn power_profile(&self) -> PowerProfile {
         self.power_profile
     }
@@ -2615,6 +2836,7 @@ impl<C: Component<TermWmAction> + 'static, L: WmComponent, O: Overlay<TermWmActi
         let mut queue = VecDeque::new();
         queue.push_back((key, action));
         while let Some((k, act)) = queue.pop_front() {
+            self.damage_window(k);
             match &act {
                 TermWmAction::RequestKeyboardFocus(id) => {
                     self.set_keyboard_focus(k, *id);
@@ -2765,6 +2987,123 @@ impl<C: Component<TermWmAction> + 'static, L: WmComponent, O: Overlay<TermWmActi
         self.push_notification(message, DIRECT_MODE_TOAST_TTL);
     }
 
+    /// Capture the next composed frame, or one window of it, as
+    /// `request.format`. The renderer reports back via
+    /// [`finish_screenshot`](Self::finish_screenshot).
+    pub fn request_screenshot(&mut self, request: ScreenshotRequest) {
+        self.pending_screenshot = Some(request);
+        self.mark_layout_dirty();
+    }
+
+    pub fn take_screenshot_request(&mut self) -> Option<ScreenshotRequest> {
+        self.pending_screenshot.take()
+    }
+
+    /// Directory screenshots are saved to; see
+    /// [`screenshot_dir`](crate::screenshot::screenshot_dir).
+    pub fn screenshot_dir(&self) -> Option<std::path::PathBuf> {
+        crate::screenshot::screenshot_dir(self.config.screenshot_dir.as_deref())
+    }
+
+    /// Run `export` (serialize, rasterize, write) on its own thread so the
+    /// render pass only pays for copying the frame. Its outcome is announced
+    /// through [`finish_screenshot`](Self::finish_screenshot) once
+    /// [`poll_screenshot_exports`](Self::poll_screenshot_exports) sees it
+    /// finish.
+    pub fn export_screenshot_in_background<F>(&mut self, export: F)
+    where
+        F: FnOnce() -> std::io::Result<std::path::PathBuf> + Send + 'static,
+    {
+        match std::thread::Builder::new()
+            .name("screenshot-export".into())
+            .spawn(export)
+        {
+            Ok(job) => self.screenshot_exports.push(job),
+            Err(err) => {
+                self.finish_screenshot(Err(err));
+                return;
+            }
+        }
+        if self.screenshot_poll_id.is_none()
+            && let Some(handle) = &self.system_task_handle
+        {
+            self.screenshot_poll_id = Some(handle.schedule_repeating(
+                SCREENSHOT_POLL_INTERVAL,
+                false,
+                SystemTask::PollScreenshotExports,
+            ));
+        }
+    }
+
+    /// Announce the background exports that have finished; stops polling
+    /// once none are left.
+    pub fn poll_screenshot_exports(&mut self) {
+        let (done, running) = std::mem::take(&mut self.screenshot_exports)
+            .into_iter()
+            .partition::<Vec<_>, _>(|job| job.is_finished());
+        self.screenshot_exports = running;
+        for job in done {
+            self.finish_screenshot_job(job);
+        }
+        if self.screenshot_exports.is_empty()
+            && let Some(id) = self.screenshot_poll_id.take()
+            && let Some(handle) = &self.system_task_handle
+        {
+            handle.cancel(id);
+        }
+    }
+
+    /// Block until every background export has finished and announce them.
+    /// For shutdown and for hosts without a system task scheduler.
+    pub fn wait_for_screenshot_exports(&mut self) {
+        for job in std::mem::take(&mut self.screenshot_exports) {
+            self.finish_screenshot_job(job);
+        }
+        if let Some(id) = self.screenshot_poll_id.take()
+            && let Some(handle) = &self.system_task_handle
+        {
+            handle.cancel(id);
+        }
+    }
+
+    fn finish_screenshot_job(
+        &mut self,
+        job: std::thread::JoinHandle<std::io::Result<std::path::PathBuf>>,
+    ) {
+        let outcome = job
+            .join()
+            .unwrap_or_else(|_| Err(std::io::Error::other("export panicked")));
+        self.finish_screenshot(outcome);
+    }
+
+    /// Announce where a screenshot was saved, or why it was not.
+    ///
+    /// May be called from the render pass, after this frame's notifications
+    /// were laid out, so the toast is deferred to a zero-delay system task
+    /// whose redraw shows it.
+    pub fn finish_screenshot(&mut self, outcome: std::io::Result<std::path::PathBuf>) {
+        let message = match outcome {
+            Ok(path) => format!("Screenshot saved to {}", path.display()),
+            Err(err) => {
+                tracing::warn!("screenshot export failed: {err}");
+                format!("Screenshot failed: {err}")
+            }
+        };
+        if let Some(handle) = &self.system_task_handle {
+            self.screenshot_toast = Some(message);
+            handle.schedule_once(Duration::ZERO, SystemTask::FlushScreenshotToast);
+        } else {
+            self.push_notification(message, SCREENSHOT_TOAST_TTL);
+        }
+    }
+
+    /// Push the toast queued by [`finish_screenshot`](Self::finish_screenshot).
+    pub fn flush_screenshot_toast(&mut self) {
+        if let Some(message) = self.screenshot_toast.take() {
+            self.push_notification(message, SCREENSHOT_TOAST_TTL);
+        }
+    }
+
     /// Read-only access to the notification queue.
     pub fn notifications(&self) -> &NotificationQueue {
         &self.notification_queue
@@ -2999,6 +3338,69 @@ mod tests {
         assert!(!rects_intersect(a, c));
     }
 
+    #[test]
+    fn managed_layouts_are_built_with_configured_gaps() {
+        let config = WmConfig {
+            outer_gap: 2,
+            inner_gap: 1,
+            ..WmConfig::default()
+        };
+        let gaps = config.tile_gaps();
+        let mut wm = WindowManager::<TestComponent>::with_config(
+            config,
+            Arc::new(AppContext::new("test", "0.0.0")),
+            None,
+            crate::window::LayerManager::new(),
+            std::collections::HashMap::new(),
+        );
+        let a = wm.create_window(TestComponent::Noop(crate::components::NoopComponent));
+        let b = wm.create_window(TestComponent::Noop(crate::components::NoopComponent));
+
+        // No register pass runs in between: readers see the gaps at once.
+        wm.set_managed_layout_none();
+        wm.reattach_to_tiling_layout(a);
+        assert_eq!(wm.managed_layout().unwrap().gaps(), gaps);
+        wm.set_managed_layout(TilingLayout::new(LayoutNode::split(
+            Direction::Horizontal,
+            vec![LayoutNode::leaf(a), LayoutNode::leaf(b)],
+        )));
+        assert_eq!(wm.managed_layout().unwrap().gaps(), gaps);
+    }
+
+    #[test]
+    fn damage_reaches_only_the_targeted_window() {
+        let mut wm = WindowManager::<TestComponent>::with_config(
+            WmConfig::default(),
+            Arc::new(AppContext::new("test", "0.0.0")),
+            None,
+            crate::window::LayerManager::new(),
+            std::collections::HashMap::new(),
+        );
+        let a = wm.create_window(TestComponent::Noop(crate::components::NoopComponent));
+        let b = wm.create_window(TestComponent::Noop(crate::components::NoopComponent));
+        let epochs = |wm: &WindowManager<TestComponent>| {
+            (wm.window_damage_epoch(a), wm.window_damage_epoch(b))
+        };
+
+        let before = epochs(&wm);
+        wm.damage_window(a);
+        let after = epochs(&wm);
+        assert_ne!(after.0, before.0);
+        assert_eq!(after.1, before.1);
+
+        let before = after;
+        wm.process_action(b, TermWmAction::message("ping".to_string()));
+        let after = epochs(&wm);
+        assert_eq!(after.0, before.0);
+        assert_ne!(after.1, before.1);
+
+        let before = after;
+        wm.damage_all_windows();
+        let after = epochs(&wm);
+        assert_ne!(after.0, before.0);
+        assert_ne!(after.1, before.1);
+    }
+
     #[test]
     fn map_layout_node_maps_leaf_to_windowkey() {
         let mut wm = WindowManager::<TestComponent>::with_config(
@@ -4704,6 +5106,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn focused_host_cursor_is_hidden_when_covered() {
+        use crate::components::HostCursor;
+
+        let mut wm = WindowManager::<TestComponent>::with_config(
+            WmConfig::default(),
+            Arc::new(AppContext::new("test", "0.0.0")),
+            None,
+            crate::window::LayerManager::new(),
+            std::collections::HashMap::new(),
+        );
+        let cursor = HostCursor {
+            x: 5,
+            y: 5,
+            style: crate::CursorStyle {
+                shape: crate::CursorShape::SteadyBar,
+                color: None,
+            },
+        };
+        let front = wm.create_window(TestComponent::RenderTracker(
+            crate::window::test_component::RenderTracker {
+                host_cursor: Some(cursor),
+                ..Default::default()
+            },
+        ));
+        let back = make_keys(&mut wm, 1)[0];
+        wm.set_focus(front);
+        let content = Rect {
+            x: 0,
+            y: 0,
+            width: 20,
+            height: 10,
+        };
+        wm.hitbox_registry_mut()
+            .register(HitboxId::new(), ComponentOwner::Window(front), content);
+        assert_eq!(wm.focused_host_cursor(), Some(cursor));
+
+        // Another window drawn on top takes the cell.
+        wm.hitbox_registry_mut()
+            .register(HitboxId::new(), ComponentOwner::Window(back), content);
+        assert_eq!(wm.focused_host_cursor(), None);
+    }
+
     #[test]
     fn floating_window_offscreen_click_past_right_edge_hits_window_behind() {
         use crate::window::{FloatRect, FloatRectSpec};
@@ -4910,6 +5355,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn place_floating_detaches_tiled_window_at_rect() {
+        let mut wm = WindowManager::<TestComponent>::with_config(
+            WmConfig::default(),
+            Arc::new(AppContext::new("test", "0.0.0")),
+            None,
+            crate::window::LayerManager::new(),
+            std::collections::HashMap::new(),
+        );
+        let key = wm.create_window(TestComponent::Noop(crate::components::NoopComponent));
+        wm.set_panel_visible(false);
+        wm.set_managed_layout(TilingLayout::new(LayoutNode::leaf(key)));
+        let rect = Rect {
+            x: 4,
+            y: 2,
+            width: 30,
+            height: 10,
+        };
+
+        assert!(wm.place_floating(key, rect));
+
+        assert!(wm.is_window_floating(key));
+        assert!(wm.managed_layout.is_none());
+        assert_eq!(
+            wm.floating_panes(),
+            vec![(
+                key,
+                crate::window::FloatRectSpec::Absolute(crate::window::FloatRect {
+                    x: 4,
+                    y: 2,
+                    width: 30,
+                    height: 10,
+                })
+            )]
+        );
+        wm.close_window(key);
+        assert!(!wm.place_floating(key, rect));
+    }
+
     #[test]
     fn drag_hitbox_detaches_to_floating() {
         use crate::events::{Event, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
@@ -5769,6 +6253,68 @@ mod tests {
         );
     }
 
+    #[test]
+    fn background_screenshot_exports_are_polled_until_done() {
+        let (mut wm, handle, _key) = make_direct_mode_wm();
+        let (release, gate) = std::sync::mpsc::channel::<()>();
+        wm.export_screenshot_in_background(move || {
+            let _ = gate.recv();
+            Ok(std::path::PathBuf::from("/tmp/shot.png"))
+        });
+        assert!(wm.screenshot_poll_id.is_some());
+
+        wm.poll_screenshot_exports();
+        assert_eq!(wm.screenshot_exports.len(), 1, "still running");
+        assert!(wm.screenshot_poll_id.is_some());
+
+        release.send(()).unwrap();
+        while !wm.screenshot_exports[0].is_finished() {
+            std::thread::yield_now();
+        }
+        wm.poll_screenshot_exports();
+        assert!(wm.screenshot_exports.is_empty());
+        assert!(wm.screenshot_poll_id.is_none(), "polling stops");
+        let due: Vec<_> = handle.drain_expired().into_iter().map(|(_, t)| t).collect();
+        assert!(
+            due.iter()
+                .any(|t| matches!(t, SystemTask::FlushScreenshotToast)),
+            "{due:?}"
+        );
+        assert!(
+            !due.iter()
+                .any(|t| matches!(t, SystemTask::PollScreenshotExports)),
+            "{due:?}"
+        );
+        wm.flush_screenshot_toast();
+        assert_eq!(
+            direct_mode_messages(&wm),
+            ["Screenshot saved to /tmp/shot.png"]
+        );
+    }
+
+    #[test]
+    fn screenshot_toast_waits_for_its_system_task() {
+        use crate::screenshot::{ScreenshotFormat, ScreenshotRequest};
+        let (mut wm, handle, key) = make_direct_mode_wm();
+        let request = ScreenshotRequest {
+            window: Some(key),
+            format: ScreenshotFormat::Svg,
+        };
+        wm.request_screenshot(request);
+        assert_eq!(wm.take_screenshot_request(), Some(request));
+        assert_eq!(wm.take_screenshot_request(), None);
+
+        wm.finish_screenshot(Ok(std::path::PathBuf::from("/tmp/shot.svg")));
+        assert!(direct_mode_messages(&wm).is_empty());
+        let due: Vec<_> = handle.drain_expired().into_iter().map(|(_, t)| t).collect();
+        assert!(matches!(due[..], [SystemTask::FlushScreenshotToast]));
+        wm.flush_screenshot_toast();
+        assert_eq!(
+            direct_mode_messages(&wm),
+            ["Screenshot saved to /tmp/shot.svg"]
+        );
+    }
+
     #[test]
     fn direct_mode_toast_cap_does_not_reschedule() {
         let (mut wm, scheduler, key) = make_direct_mode_wm();
@@ -1,9 +1,12 @@
+use std::path::PathBuf;
 use std::time::Duration;
 
 use crate::actions::TermWmAction;
+use crate::chrome::{BorderStyle, TitleAlignment};
 use crate::constants::DEFAULT_SCROLLBACK_LEN;
 use crate::keybindings::KeyBindings;
 use crate::theme::NOIR;
+use crate::theme_registry::AutoTheme;
 
 fn super_passthrough_window_default() -> Duration {
     const ESC_PASSTHROUGH_DEFAULT: u64 = 600;
@@ -107,14 +110,31 @@ pub struct WmConfig {
     pub keybindings: KeyBindings,
     /// Visibility mode for keybinding hints.
     pub hint_visibility: HintVisibility,
+    /// Line-drawing set for window borders; [`BorderStyle::None`] drops the
+    /// borders entirely, as if every chrome rule hid them.
+    pub border_style: BorderStyle,
+    /// Where window titles sit in the header row.
+    pub title_alignment: TitleAlignment,
+    /// Cells between the managed area's edges and the tiles.
+    pub outer_gap: u16,
+    /// Cells between neighbouring tiles. The split handle is drawn on the
+    /// gap's middle line and the whole gap is its hitbox, so values below 1
+    /// are treated as 1.
+    pub inner_gap: u16,
     /// Color theme.
     pub theme: crate::theme::Theme,
+    /// Initial dark/light pair followed when the host terminal reports its
+    /// background color; `None` keeps `theme` regardless of the host.
+    pub auto_theme: Option<AutoTheme>,
     /// Scrollback buffer size (in lines) for terminal windows.
     pub scrollback_lines: usize,
     /// Minimum width for a floating window (in cells).
     pub floating_min_width: u16,
     /// Minimum height for a floating window (in cells).
     pub floating_min_height: u16,
+    /// Directory exported screenshots are written to; `None` falls back to
+    /// [`screenshot_dir`](crate::screenshot::screenshot_dir)'s defaults.
+    pub screenshot_dir: Option<PathBuf>,
 }
 
 impl Default for WmConfig {
@@ -139,10 +159,16 @@ impl Default for WmConfig {
             hint_visibility: HintVisibility::Always,
             menu_outline_timeout: Duration::from_millis(500),
             drag_snap_timeout: Some(Duration::from_millis(2000)),
+            border_style: BorderStyle::default(),
+            title_alignment: TitleAlignment::default(),
+            outer_gap: 0,
+            inner_gap: 1,
             theme: NOIR,
+            auto_theme: Some(AutoTheme::default()),
             scrollback_lines: DEFAULT_SCROLLBACK_LEN,
             floating_min_width: 6,
             floating_min_height: 3,
+            screenshot_dir: None,
         }
     }
 }
@@ -151,4 +177,12 @@ impl WmConfig {
     pub fn panel_active(&self) -> bool {
         self.panels_enabled
     }
+
+    /// Tiling gaps from `outer_gap` and `inner_gap`.
+    pub fn tile_gaps(&self) -> crate::layout::tiling::Gaps {
+        crate::layout::tiling::Gaps {
+            outer: self.outer_gap,
+            inner: self.inner_gap,
+        }
+    }
 }
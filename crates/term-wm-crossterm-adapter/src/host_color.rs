@@ -0,0 +1,147 @@
+//! OSC 10/11 default-color queries to the host terminal.
+//!
+//! crossterm has no parser for OSC replies: `ESC ] 11 ; rgb:… ST` reaches the
+//! reader as `Alt+]` followed by one key event per character and `Alt+\`
+//! (or `Ctrl+G` for a BEL terminator). [`OscReplyAssembler`] stitches those
+//! keys back together into [`Event::HostColor`] — but only for a short
+//! window after [`expect_host_color_replies`], so a user typing `Alt+]` is
+//! never swallowed.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use term_wm_events::{Event, HostColorKind};
+
+use crate::translate::try_translate_event;
+
+/// OSC 10 and OSC 11 queries, ST-terminated.
+pub const HOST_COLOR_QUERY: &str = "\x1b]10;?\x1b\\\x1b]11;?\x1b\\";
+
+/// How long replies are captured after a query.
+const REPLY_WINDOW: Duration = Duration::from_secs(1);
+
+/// Longest reply body accepted (`11;rgb:ffff/ffff/ffff` is 21 bytes).
+const MAX_REPLY_LEN: usize = 32;
+
+/// Deadline of the current capture window, if any.
+static EXPECT_REPLIES_UNTIL: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Open the capture window for replies to a [`HOST_COLOR_QUERY`] just
+/// written to the terminal.
+pub fn expect_host_color_replies() {
+    *EXPECT_REPLIES_UNTIL
+        .lock()
+        .unwrap_or_else(|err| err.into_inner()) = Some(Instant::now() + REPLY_WINDOW);
+}
+
+fn expecting_replies() -> bool {
+    EXPECT_REPLIES_UNTIL
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .is_some_and(|until| Instant::now() < until)
+}
+
+/// Per-reader state that reassembles OSC color replies from key events.
+#[derive(Debug, Default)]
+pub struct OscReplyAssembler {
+    /// Reply body collected so far; `Some` while inside a reply.
+    body: Option<String>,
+}
+
+impl OscReplyAssembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Translate `evt` like [`try_translate_event`], except that the keys of
+    /// an expected OSC 10/11 reply are consumed (`None`) and the final one
+    /// yields the assembled [`Event::HostColor`].
+    pub fn translate(&mut self, evt: crossterm::event::Event) -> Option<Event> {
+        use crossterm::event::{Event as CtEvent, KeyCode, KeyEvent, KeyModifiers};
+
+        let CtEvent::Key(KeyEvent {
+            code: KeyCode::Char(c),
+            modifiers,
+            ..
+        }) = evt
+        else {
+            self.body = None;
+            return try_translate_event(evt);
+        };
+        let alt = modifiers.contains(KeyModifiers::ALT);
+        let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+        match self.body.as_mut() {
+            None if alt && c == ']' && expecting_replies() => {
+                self.body = Some(String::new());
+                None
+            }
+            None => try_translate_event(evt),
+            // ST (`ESC \`) or BEL (`Ctrl+G`) ends the reply.
+            Some(_) if (alt && c == '\\') || (ctrl && c == 'g') => {
+                let body = self.body.take().unwrap_or_default();
+                HostColorKind::parse_reply(&body).map(|(kind, rgb)| Event::HostColor(kind, rgb))
+            }
+            Some(body) if !alt && !ctrl && body.len() < MAX_REPLY_LEN => {
+                body.push(c);
+                None
+            }
+            Some(_) => {
+                self.body = None;
+                try_translate_event(evt)
+            }
+        }
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{Event as CtEvent, KeyCode, KeyEvent, KeyModifiers};
+
+    fn key(c: char, modifiers: KeyModifiers) -> CtEvent {
+        CtEvent::Key(KeyEvent::new(KeyCode::Char(c), modifiers))
+    }
+
+    /// The key events crossterm produces for `ESC ] <body> <terminator>`.
+    fn reply_keys(body: &str, terminator: CtEvent) -> Vec<CtEvent> {
+        let mut keys = vec![key(']', KeyModifiers::ALT)];
+        keys.extend(body.chars().map(|c| key(c, KeyModifiers::NONE)));
+        keys.push(terminator);
+        keys
+    }
+
+    #[test]
+    fn reassembles_expected_replies_and_passes_other_keys() {
+        let mut assembler = OscReplyAssembler::new();
+        // Outside the capture window Alt+] is an ordinary key.
+        *EXPECT_REPLIES_UNTIL.lock().unwrap() = None;
+        assert!(matches!(
+            assembler.translate(key(']', KeyModifiers::ALT)),
+            Some(Event::Key(_))
+        ));
+
+        expect_host_color_replies();
+        let mut events = Vec::new();
+        for evt in reply_keys("10;rgb:ffff/ffff/ffff", key('\\', KeyModifiers::ALT))
+            .into_iter()
+            .chain(reply_keys(
+                "11;rgb:0000/0000/8080",
+                key('g', KeyModifiers::CONTROL),
+            ))
+            .chain([key('x', KeyModifiers::NONE)])
+        {
+            events.extend(assembler.translate(evt));
+        }
+        assert_eq!(events.len(), 3, "{events:?}");
+        assert!(matches!(
+            events[0],
+            Event::HostColor(HostColorKind::Foreground, (255, 255, 255))
+        ));
+        assert!(matches!(
+            events[1],
+            Event::HostColor(HostColorKind::Background, (0, 0, 128))
+        ));
+        assert!(matches!(events[2], Event::Key(_)));
+    }
+}
@@ -6,10 +6,14 @@
 //! - [`terminal`] — side-effecting terminal/console state (mouse capture). The
 //!   ANSI bytes are host-agnostic; Windows additionally flips the console input
 //!   mode (`ENABLE_MOUSE_INPUT`) so a ConPTY child can read routed mouse records.
+//! - [`host_color`] — reassembles OSC 10/11 default-color replies, which
+//!   crossterm delivers as stray key events.
 
+pub mod host_color;
 pub mod terminal;
 pub mod translate;
 
+pub use host_color::{HOST_COLOR_QUERY, OscReplyAssembler, expect_host_color_replies};
 pub use terminal::{set_mouse_capture, set_mouse_capture_with};
 pub use translate::{
     translate_key_code, translate_key_modifiers, translate_mouse_event, try_translate_event,
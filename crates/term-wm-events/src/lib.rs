@@ -213,6 +213,119 @@ pub enum Event {
     FocusLost,
     /// Paste from clipboard
     Paste(String),
+    /// The host terminal's answer to an OSC 10/11 default-color query
+    HostColor(HostColorKind, (u8, u8, u8)),
+    /// What the outermost host terminal can display, as probed by the
+    /// session client attached to it
+    HostDisplay(HostDisplay),
+}
+
+// ============================================================================
+// Host display capabilities
+// ============================================================================
+
+/// How many colors the host terminal can display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, bitcode::Encode, bitcode::Decode)]
+pub enum HostColorDepth {
+    /// 24-bit RGB.
+    TrueColor,
+    /// The xterm-256 palette.
+    Ansi256,
+    /// The 16 basic ANSI colors.
+    Ansi16,
+    /// No color at all.
+    Mono,
+}
+
+/// The probed capabilities an inner window manager renders for. Its own
+/// stdio is a gateway PTY, so it cannot ask the host terminal itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, bitcode::Encode, bitcode::Decode)]
+pub struct HostDisplay {
+    /// Deepest palette the host renders.
+    pub color_depth: HostColorDepth,
+    /// Whether box-drawing and block glyphs render.
+    pub unicode_box_drawing: bool,
+    /// Whether OSC 52 clipboard writes reach the host clipboard.
+    pub osc52: bool,
+    /// Whether frames may be wrapped in synchronized-output mode 2026.
+    pub synchronized_output: bool,
+    /// Whether xterm mouse reporting is available.
+    pub mouse: bool,
+}
+
+// ============================================================================
+// Host default colors (OSC 10 / OSC 11)
+// ============================================================================
+
+/// Which default color an OSC 10/11 query or reply is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, bitcode::Encode, bitcode::Decode)]
+pub enum HostColorKind {
+    /// OSC 10, the default text color.
+    Foreground,
+    /// OSC 11, the default background color.
+    Background,
+}
+
+impl HostColorKind {
+    /// The OSC number: 10 or 11.
+    pub fn osc(self) -> u8 {
+        match self {
+            HostColorKind::Foreground => 10,
+            HostColorKind::Background => 11,
+        }
+    }
+
+    /// Parse a reply body without the `ESC ]` introducer and terminator,
+    /// e.g. `11;rgb:1e1e/1e1e/2e2e`. Components may have 1–4 hex digits.
+    pub fn parse_reply(body: &str) -> Option<(Self, (u8, u8, u8))> {
+        let (osc, spec) = body.split_once(';')?;
+        let kind = match osc {
+            "10" => HostColorKind::Foreground,
+            "11" => HostColorKind::Background,
+            _ => return None,
+        };
+        parse_color_spec(spec).map(|rgb| (kind, rgb))
+    }
+
+    /// The reply a terminal sends for this color, terminated like the query
+    /// was (`bel` for BEL, otherwise ST).
+    pub fn format_reply(self, (r, g, b): (u8, u8, u8), bel: bool) -> String {
+        let terminator = if bel { "\x07" } else { "\x1b\\" };
+        format!(
+            "\x1b]{};rgb:{r:02x}{r:02x}/{g:02x}{g:02x}/{b:02x}{b:02x}{terminator}",
+            self.osc()
+        )
+    }
+}
+
+/// Parse an X11 color spec as used by the OSC color sequences:
+/// `rgb:<r>/<g>/<b>` with 1–4 hex digits per component, or `#rgb`,
+/// `#rrggbb`, `#rrrgggbbb` and `#rrrrggggbbbb`.
+pub fn parse_color_spec(spec: &str) -> Option<(u8, u8, u8)> {
+    fn scale(hex: &str) -> Option<u8> {
+        if hex.is_empty() || hex.len() > 4 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        let max = (1u32 << (4 * hex.len())) - 1;
+        let value = u32::from_str_radix(hex, 16).ok()?;
+        Some(((value * 255 + max / 2) / max) as u8)
+    }
+    if let Some(hex) = spec.strip_prefix('#') {
+        if hex.is_empty() || hex.len() % 3 != 0 || !hex.is_ascii() {
+            return None;
+        }
+        // `#` specs keep the high bits, unlike `rgb:` which scales.
+        let n = hex.len() / 3;
+        let mut parts = (0..3).map(|i| {
+            let part = &hex[i * n..(i + 1) * n];
+            let padded = format!("{part:0<2}");
+            u8::from_str_radix(&padded[..2], 16).ok()
+        });
+        return Some((parts.next()??, parts.next()??, parts.next()??));
+    }
+    let mut parts = spec.strip_prefix("rgb:")?.split('/').map(scale);
+    let rgb = (parts.next()??, parts.next()??, parts.next()??);
+    parts.next().is_none().then_some(rgb)
 }
 
 // ============================================================================
@@ -289,6 +402,7 @@ pub fn core_event_to_wm(event: &Event) -> Option<WmEvent> {
         Event::FocusGained => Some(WmEvent::FocusGained),
         Event::FocusLost => Some(WmEvent::FocusLost),
         Event::Paste(text) => Some(WmEvent::Paste(text.clone())),
+        Event::HostColor(..) | Event::HostDisplay(_) => None,
     }
 }
 
@@ -390,4 +504,36 @@ mod tests {
         let result = m.to_local_offset(make_screen(), 10, 5, 80, 24);
         assert_eq!(result.map(|r| (r.column, r.row)), Some((15, 8)));
     }
+
+    #[test]
+    fn host_color_replies_round_trip() {
+        assert_eq!(
+            HostColorKind::parse_reply("11;rgb:1e1e/ffff/0000"),
+            Some((HostColorKind::Background, (0x1e, 0xff, 0x00)))
+        );
+        assert_eq!(
+            HostColorKind::parse_reply("10;rgb:f/80/abc"),
+            Some((HostColorKind::Foreground, (255, 128, 171)))
+        );
+        assert_eq!(HostColorKind::parse_reply("11;?"), None);
+        assert_eq!(HostColorKind::parse_reply("12;rgb:0/0/0"), None);
+        let reply = HostColorKind::Background.format_reply((0x12, 0x34, 0x56), true);
+        assert_eq!(reply, "\x1b]11;rgb:1212/3434/5656\x07");
+        let body = reply.trim_start_matches("\x1b]").trim_end_matches('\x07');
+        assert_eq!(
+            HostColorKind::parse_reply(body),
+            Some((HostColorKind::Background, (0x12, 0x34, 0x56)))
+        );
+    }
+
+    #[test]
+    fn color_specs_accept_hash_forms() {
+        assert_eq!(parse_color_spec("#ff8000"), Some((0xff, 0x80, 0x00)));
+        assert_eq!(parse_color_spec("#f80"), Some((0xf0, 0x80, 0x00)));
+        assert_eq!(parse_color_spec("#ffff80800000"), Some((0xff, 0x80, 0x00)));
+        assert_eq!(parse_color_spec("rgb:ff/80/0"), Some((0xff, 0x80, 0x00)));
+        assert_eq!(parse_color_spec("#ff80"), None);
+        assert_eq!(parse_color_spec("rgb:+f/0/0"), None);
+        assert_eq!(parse_color_spec("red"), None);
+    }
 }
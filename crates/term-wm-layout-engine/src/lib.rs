@@ -45,7 +45,7 @@ pub use snap::{
     detect_corner_snap, detect_edge_snap, edge_preview_rect, tiled_preview_rect,
 };
 pub use split::{
-    build_rects_from_sizes, gap_size, handle_thickness, split_rect_bsp, split_rects_nary,
+    Gaps, build_rects_from_sizes, gap_size, handle_thickness, split_rect_bsp, split_rects_nary,
     split_rects_weighted, split_rects_with_gaps, split_sizes,
 };
 
@@ -1,4 +1,4 @@
-use crate::rect::{LayoutRect, Orientation, Ratio};
+use crate::rect::{LayoutRect, Orientation, Ratio, inset};
 
 /// Split a rectangle into two along the given orientation using a ratio.
 pub fn split_rect_bsp(
@@ -184,12 +184,50 @@ pub fn handle_thickness(orientation: Orientation, _total_dim: u16) -> u16 {
     }
 }
 
-/// Compute the per-gap size between children in a split.
+/// Spacing of a tiled layout: `outer` cells between the layout area and the
+/// tiles on every side, `inner` cells between neighbouring tiles.
+///
+/// The inner gap is where a resizable split's handle lives, so it never drops
+/// below [`handle_thickness`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gaps {
+    pub outer: u16,
+    pub inner: u16,
+}
+
+impl Default for Gaps {
+    /// Tiles fill the area and are separated by the split handle only.
+    fn default() -> Self {
+        Self { outer: 0, inner: 1 }
+    }
+}
+
+impl Gaps {
+    /// `area` shrunk by the outer gap. An axis too small to keep a cell of
+    /// content after the inset is left untouched.
+    pub fn inset(&self, area: LayoutRect) -> LayoutRect {
+        let horizontal = if area.width > self.outer.saturating_mul(2) {
+            self.outer
+        } else {
+            0
+        };
+        let vertical = if area.height > self.outer.saturating_mul(2) {
+            self.outer
+        } else {
+            0
+        };
+        inset(area, horizontal, horizontal, vertical, vertical)
+    }
+}
+
+/// Compute the per-gap size between children in a split, aiming for `inner`
+/// cells (at least the handle thickness) while every child keeps a cell.
 pub fn gap_size(
     orientation: Orientation,
     total_dim: u16,
     child_count: usize,
     resizable: bool,
+    inner: u16,
 ) -> u16 {
     if !resizable || child_count < 2 {
         return 0;
@@ -203,7 +241,9 @@ pub fn gap_size(
     }
     let max_gap = total_dim.saturating_sub(min_content);
     let per_gap = max_gap / (child_count as u16).saturating_sub(1);
-    handle_thickness(orientation, total_dim).min(per_gap)
+    handle_thickness(orientation, total_dim)
+        .max(inner)
+        .min(per_gap)
 }
 
 /// Weighted split of a rect into `child_count` rects using integer weights.
@@ -464,21 +504,53 @@ mod tests {
 
     #[test]
     fn gap_size_no_gap_when_not_resizable() {
-        assert_eq!(gap_size(Orientation::Horizontal, 80, 2, false), 0);
+        assert_eq!(gap_size(Orientation::Horizontal, 80, 2, false, 1), 0);
     }
 
     #[test]
     fn gap_size_zero_when_too_small() {
-        assert_eq!(gap_size(Orientation::Horizontal, 2, 3, true), 0);
-        assert_eq!(gap_size(Orientation::Horizontal, 0, 2, true), 0);
+        assert_eq!(gap_size(Orientation::Horizontal, 2, 3, true, 1), 0);
+        assert_eq!(gap_size(Orientation::Horizontal, 0, 2, true, 1), 0);
     }
 
     #[test]
     fn gap_size_returns_gap() {
-        let g = gap_size(Orientation::Horizontal, 80, 4, true);
+        let g = gap_size(Orientation::Horizontal, 80, 4, true, 1);
         assert!(g >= 1);
     }
 
+    #[test]
+    fn gap_size_follows_inner_gap_within_limits() {
+        assert_eq!(gap_size(Orientation::Horizontal, 80, 2, true, 3), 3);
+        // Never thinner than the handle.
+        assert_eq!(gap_size(Orientation::Horizontal, 80, 2, true, 0), 1);
+        // Never so wide that a child loses its last cell.
+        assert_eq!(gap_size(Orientation::Horizontal, 6, 3, true, 4), 1);
+    }
+
+    #[test]
+    fn gaps_inset_skips_axes_that_are_too_small() {
+        let gaps = Gaps { outer: 2, inner: 1 };
+        assert_eq!(
+            gaps.inset(area(80, 24)),
+            LayoutRect {
+                x: 2,
+                y: 2,
+                width: 76,
+                height: 20,
+            }
+        );
+        assert_eq!(
+            gaps.inset(area(80, 4)),
+            LayoutRect {
+                x: 2,
+                y: 0,
+                width: 76,
+                height: 4,
+            }
+        );
+    }
+
     #[test]
     fn split_rects_weighted_two_equal() {
         let rects = split_rects_weighted(area(80, 24), Orientation::Horizontal, &[1, 1], 2);
@@ -3,7 +3,7 @@ use core::sync::atomic::{AtomicUsize, Ordering};
 use crate::BspNode;
 use crate::rect::{LayoutRect, Orientation, rect_contains as engine_rect_contains};
 use crate::snap::InsertPosition;
-use crate::split;
+use crate::split::{self, Gaps};
 
 static VOID_ID_COUNTER: AtomicUsize = AtomicUsize::new(1);
 
@@ -120,14 +120,27 @@ impl<Id: Copy + Eq + Ord> LayoutNode<Id> {
     }
 
     pub fn layout_rects(&self, area: LayoutRect) -> Vec<(Id, LayoutRect)> {
-        self.layout_with_gaps(area).0
+        self.layout_with_gaps(area, Gaps::default()).0
     }
 
-    pub fn layout_with_gaps(&self, area: LayoutRect) -> (Vec<(Id, LayoutRect)>, Vec<SplitGap>) {
+    /// Tile regions and the split gaps between them. The outer gap is trimmed
+    /// from `area` first; every returned gap rect spans the whole inner gap,
+    /// so it doubles as the split's resize handle.
+    pub fn layout_with_gaps(
+        &self,
+        area: LayoutRect,
+        gaps: Gaps,
+    ) -> (Vec<(Id, LayoutRect)>, Vec<SplitGap>) {
         let mut regions = Vec::new();
-        let mut gaps = Vec::new();
-        self.layout_recursive(area, &mut regions, &mut gaps, &mut Vec::new());
-        (regions, gaps)
+        let mut split_gaps = Vec::new();
+        self.layout_recursive(
+            gaps.inset(area),
+            gaps.inner,
+            &mut regions,
+            &mut split_gaps,
+            &mut Vec::new(),
+        );
+        (regions, split_gaps)
     }
 
     pub fn node_at_path(&self, path: &[usize]) -> Option<&LayoutNode<Id>> {
@@ -265,22 +278,31 @@ impl<Id: Copy + Eq + Ord> LayoutNode<Id> {
         walk(self, &mut predicate)
     }
 
-    pub fn hit_test_gap(&self, area: LayoutRect, column: u16, row: u16) -> Option<SplitGap> {
-        let (_, gaps) = self.layout_with_gaps(area);
-        gaps.into_iter()
+    pub fn hit_test_gap(
+        &self,
+        area: LayoutRect,
+        gaps: Gaps,
+        column: u16,
+        row: u16,
+    ) -> Option<SplitGap> {
+        let (_, split_gaps) = self.layout_with_gaps(area, gaps);
+        split_gaps
+            .into_iter()
             .find(|gap| engine_rect_contains(&gap.rect, column, row))
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn apply_drag(
         &mut self,
         area: LayoutRect,
+        gaps: Gaps,
         path: &[usize],
         index: usize,
         direction: Direction,
         delta: i16,
         min_size: i16,
     ) -> bool {
-        let Some(split_area) = split_area_for_path(self, area, path) else {
+        let Some(split_area) = split_area_for_path(self, area, gaps, path) else {
             return false;
         };
         let Some(split) = split_at_path_mut(self, path) else {
@@ -303,7 +325,13 @@ impl<Id: Copy + Eq + Ord> LayoutNode<Id> {
             Direction::Horizontal => split_area.width,
             Direction::Vertical => split_area.height,
         };
-        let gap = split::gap_size(orientation, total_dim, children.len(), *resizable);
+        let gap = split::gap_size(
+            orientation,
+            total_dim,
+            children.len(),
+            *resizable,
+            gaps.inner,
+        );
         let sizes = split::split_sizes(
             split_area,
             orientation,
@@ -527,13 +555,18 @@ impl<Id: Copy + Eq + Ord> LayoutNode<Id> {
         }
     }
 
-    pub fn void_regions(&self, area: LayoutRect) -> Vec<(usize, LayoutRect)> {
+    pub fn void_regions(&self, area: LayoutRect, gaps: Gaps) -> Vec<(usize, LayoutRect)> {
         let mut rects = Vec::new();
-        self.void_regions_recursive(area, &mut rects);
+        self.void_regions_recursive(gaps.inset(area), gaps.inner, &mut rects);
         rects
     }
 
-    fn void_regions_recursive(&self, area: LayoutRect, out: &mut Vec<(usize, LayoutRect)>) {
+    fn void_regions_recursive(
+        &self,
+        area: LayoutRect,
+        inner: u16,
+        out: &mut Vec<(usize, LayoutRect)>,
+    ) {
         match self {
             LayoutNode::Void(id) => out.push((*id, area)),
             LayoutNode::Split {
@@ -547,7 +580,8 @@ impl<Id: Copy + Eq + Ord> LayoutNode<Id> {
                     Direction::Horizontal => area.width,
                     Direction::Vertical => area.height,
                 };
-                let gap = split::gap_size(orientation, total_dim, children.len(), *resizable);
+                let gap =
+                    split::gap_size(orientation, total_dim, children.len(), *resizable, inner);
                 let (rects, _) = split::split_rects_with_gaps(
                     area,
                     orientation,
@@ -556,7 +590,7 @@ impl<Id: Copy + Eq + Ord> LayoutNode<Id> {
                     gap,
                 );
                 for (child, sub) in children.iter().zip(rects) {
-                    child.void_regions_recursive(sub, out);
+                    child.void_regions_recursive(sub, inner, out);
                 }
             }
             _ => {}
@@ -702,6 +736,7 @@ impl<Id: Copy + Eq + Ord> LayoutNode<Id> {
     fn layout_recursive(
         &self,
         area: LayoutRect,
+        inner: u16,
         regions: &mut Vec<(Id, LayoutRect)>,
         gaps: &mut Vec<SplitGap>,
         path: &mut Vec<usize>,
@@ -722,7 +757,8 @@ impl<Id: Copy + Eq + Ord> LayoutNode<Id> {
                     Direction::Horizontal => area.width,
                     Direction::Vertical => area.height,
                 };
-                let gap = split::gap_size(orientation, total_dim, children.len(), *resizable);
+                let gap =
+                    split::gap_size(orientation, total_dim, children.len(), *resizable, inner);
                 let (rects, split_gaps) = split::split_rects_with_gaps(
                     area,
                     orientation,
@@ -732,7 +768,7 @@ impl<Id: Copy + Eq + Ord> LayoutNode<Id> {
                 );
                 for (idx, (child, rect)) in children.iter().zip(rects.iter().copied()).enumerate() {
                     path.push(idx);
-                    child.layout_recursive(rect, regions, gaps, path);
+                    child.layout_recursive(rect, inner, regions, gaps, path);
                     path.pop();
                 }
                 if *resizable && children.len() > 1 {
@@ -1247,6 +1283,7 @@ impl<Id: Copy + Eq + Ord> LayoutNode<Id> {
         insert: Id,
         position: InsertPosition,
         area: LayoutRect,
+        gaps: Gaps,
     ) -> Option<LayoutRect> {
         let mut root = self.clone();
         let removed = root.remove_leaf(insert);
@@ -1260,7 +1297,8 @@ impl<Id: Copy + Eq + Ord> LayoutNode<Id> {
         if !success {
             root.split_root(insert, position);
         }
-        root.layout_rects(area)
+        root.layout_with_gaps(area, gaps)
+            .0
             .into_iter()
             .find(|(id, _)| *id == insert)
             .map(|(_, r)| r)
@@ -1271,11 +1309,13 @@ impl<Id: Copy + Eq + Ord> LayoutNode<Id> {
         insert: Id,
         void_id: usize,
         area: LayoutRect,
+        gaps: Gaps,
     ) -> Option<LayoutRect> {
         let mut root = self.clone();
         root.remove_leaf(insert);
         if root.replace_void_by_id(void_id, LayoutNode::leaf(insert)) {
-            root.layout_rects(area)
+            root.layout_with_gaps(area, gaps)
+                .0
                 .into_iter()
                 .find(|(id, _)| *id == insert)
                 .map(|(_, r)| r)
@@ -1288,9 +1328,10 @@ impl<Id: Copy + Eq + Ord> LayoutNode<Id> {
 pub fn split_area_for_path<Id: Copy + Eq + Ord>(
     node: &LayoutNode<Id>,
     area: LayoutRect,
+    gaps: Gaps,
     path: &[usize],
 ) -> Option<LayoutRect> {
-    let mut area = area;
+    let mut area = gaps.inset(area);
     let mut current = node;
     for &idx in path {
         let LayoutNode::Split {
@@ -1308,7 +1349,13 @@ pub fn split_area_for_path<Id: Copy + Eq + Ord>(
             Direction::Horizontal => area.width,
             Direction::Vertical => area.height,
         };
-        let gap = split::gap_size(orientation, total_dim, children.len(), *resizable);
+        let gap = split::gap_size(
+            orientation,
+            total_dim,
+            children.len(),
+            *resizable,
+            gaps.inner,
+        );
         let (rects, _) = split::split_rects_with_gaps(
             area,
             orientation,
@@ -1569,7 +1616,7 @@ mod tests {
             width: 100,
             height: 24,
         };
-        let (regions, _) = node.layout_with_gaps(area);
+        let (regions, _) = node.layout_with_gaps(area, Gaps::default());
         assert_eq!(regions.len(), 2);
         let sum_w: u16 = regions.iter().map(|(_, r)| r.width).sum();
         assert!(
@@ -1611,7 +1658,8 @@ mod tests {
             width: 10,
             height: 4,
         };
-        let sub = split_area_for_path(&node, area, &[1]).expect("should get area for path");
+        let sub = split_area_for_path(&node, area, Gaps::default(), &[1])
+            .expect("should get area for path");
         assert!(sub.x > 0);
         assert!(node.remove_leaf(2));
         assert_eq!(node.unwrap_leaf(), Some(1));
@@ -1631,7 +1679,7 @@ mod tests {
             weights: vec![1u16, 1u16],
             resizable: true,
         };
-        let (_, gaps) = node.layout_with_gaps(area);
+        let (_, gaps) = node.layout_with_gaps(area, Gaps::default());
         assert_eq!(gaps.len(), 1, "2-window split must produce 1 gap");
         let gap = &gaps[0];
         assert_eq!(gap.direction, Direction::Horizontal);
@@ -1640,11 +1688,52 @@ mod tests {
         assert_eq!(gap.rect.height, 24);
         let center_col = (gap.rect.x + i32::from(gap.rect.width) / 2) as u16;
         let center_row = (gap.rect.y + i32::from(gap.rect.height) / 2) as u16;
-        let found = node.hit_test_gap(area, center_col, center_row);
+        let found = node.hit_test_gap(area, Gaps::default(), center_col, center_row);
         assert!(found.is_some(), "hit_test_gap must find the gap");
         assert_eq!(found.unwrap().direction, Direction::Horizontal);
     }
 
+    #[test]
+    fn layout_with_gaps_applies_outer_and_inner_gaps() {
+        let area = LayoutRect {
+            x: 0,
+            y: 0,
+            width: 80,
+            height: 24,
+        };
+        let node = LayoutNode::Split {
+            direction: Direction::Horizontal,
+            children: vec![LayoutNode::Leaf(1), LayoutNode::Leaf(2)],
+            weights: vec![1u16, 1u16],
+            resizable: true,
+        };
+        let gaps = Gaps { outer: 1, inner: 2 };
+        let (regions, split_gaps) = node.layout_with_gaps(area, gaps);
+        let (left, right) = (regions[0].1, regions[1].1);
+        assert_eq!((left.x, left.y, left.height), (1, 1, 22));
+        assert_eq!(right.x + i32::from(right.width), 79);
+        assert_eq!(right.x - (left.x + i32::from(left.width)), 2);
+        // The handle covers the whole inner gap, so every gap cell hits it.
+        let handle = &split_gaps[0];
+        assert_eq!(handle.rect.x, left.x + i32::from(left.width));
+        assert_eq!((handle.rect.width, handle.rect.height), (2, 22));
+        for col in [handle.rect.x, handle.rect.x + 1] {
+            assert!(node.hit_test_gap(area, gaps, col as u16, 12).is_some());
+        }
+        // The outer gap belongs to no tile and no handle.
+        assert!(node.hit_test_gap(area, gaps, 0, 12).is_none());
+
+        // Dragging keeps both tiles and the gap inside the inset area.
+        let mut node = node;
+        assert!(node.apply_drag(area, gaps, &[], 0, Direction::Horizontal, 5, 4));
+        let (regions, _) = node.layout_with_gaps(area, gaps);
+        assert_eq!(regions[0].1.width, left.width + 5);
+        assert_eq!(
+            regions[1].1.x + i32::from(regions[1].1.width),
+            right.x + i32::from(right.width)
+        );
+    }
+
     #[test]
     fn normalize_weights_resets_to_equal() {
         let mut node = LayoutNode::Split {
@@ -1800,7 +1889,7 @@ mod tests {
             width: 80,
             height: 24,
         };
-        let voids = node.void_regions(area);
+        let voids = node.void_regions(area, Gaps::default());
         assert_eq!(voids.len(), 1);
         assert_eq!(voids[0].0, 99);
     }
@@ -0,0 +1,195 @@
+//! PTY master and child handles for sessions inherited from another process.
+//!
+//! A gateway upgrade hands each channel's PTY master descriptor to the new
+//! daemon (see `term_sys_io::fd_passing`). The child keeps running under its
+//! original parent chain, so the new owner can neither `waitpid` it nor reuse
+//! portable-pty's openpty-backed handles. [`AdoptedMaster`] drives the raw
+//! master fd directly and [`AdoptedChild`] tracks the child by PID only.
+
+use std::io;
+use std::os::unix::io::{AsRawFd, OwnedFd, RawFd};
+use std::time::Duration;
+
+use portable_pty::{Child, ChildKiller, ExitStatus, MasterPty, PtySize};
+use term_sys_io::pty_handoff::{self, WinSize};
+
+/// How often [`AdoptedChild::wait`] re-checks liveness. Only used on teardown
+/// paths; the session server polls `try_wait` on its own cadence.
+const ADOPTED_WAIT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Master side of a PTY whose descriptor was received from another process.
+pub struct AdoptedMaster {
+    fd: OwnedFd,
+}
+
+impl AdoptedMaster {
+    pub fn new(fd: OwnedFd) -> Self {
+        Self { fd }
+    }
+
+    /// Fresh `File` over a duplicate of the master fd, marked close-on-exec.
+    fn dup_file(&self) -> io::Result<std::fs::File> {
+        pty_handoff::dup_cloexec(self.fd.as_raw_fd()).map(std::fs::File::from)
+    }
+}
+
+impl MasterPty for AdoptedMaster {
+    fn resize(&self, size: PtySize) -> Result<(), anyhow::Error> {
+        let size = WinSize {
+            rows: size.rows,
+            cols: size.cols,
+            pixel_width: size.pixel_width,
+            pixel_height: size.pixel_height,
+        };
+        pty_handoff::set_winsize(self.fd.as_raw_fd(), size)?;
+        Ok(())
+    }
+
+    fn get_size(&self) -> Result<PtySize, anyhow::Error> {
+        let ws = pty_handoff::get_winsize(self.fd.as_raw_fd())?;
+        Ok(PtySize {
+            rows: ws.rows,
+            cols: ws.cols,
+            pixel_width: ws.pixel_width,
+            pixel_height: ws.pixel_height,
+        })
+    }
+
+    fn try_clone_reader(&self) -> Result<Box<dyn io::Read + Send>, anyhow::Error> {
+        Ok(Box::new(self.dup_file()?))
+    }
+
+    /// Unlike portable-pty's writer, dropping this one sends nothing to the
+    /// slave: an adopted session may be handed off again, and a stray EOF
+    /// would end the shell.
+    fn take_writer(&self) -> Result<Box<dyn io::Write + Send>, anyhow::Error> {
+        Ok(Box::new(self.dup_file()?))
+    }
+
+    fn process_group_leader(&self) -> Option<libc::pid_t> {
+        pty_handoff::foreground_process_group(self.fd.as_raw_fd())
+    }
+
+    fn as_raw_fd(&self) -> Option<RawFd> {
+        Some(self.fd.as_raw_fd())
+    }
+
+    fn tty_name(&self) -> Option<std::path::PathBuf> {
+        None
+    }
+}
+
+/// A PTY child known only by PID. The exit code belongs to the original
+/// parent, so a finished child reports a generic failure status.
+#[derive(Debug)]
+pub struct AdoptedChild {
+    pid: u32,
+}
+
+impl AdoptedChild {
+    pub fn new(pid: u32) -> Self {
+        Self { pid }
+    }
+
+    fn is_alive(&self) -> bool {
+        match pty_handoff::signal_pid(self.pid, 0) {
+            Ok(()) => !is_zombie(self.pid),
+            Err(e) => e.raw_os_error() != Some(libc::ESRCH),
+        }
+    }
+}
+
+/// A zombie still answers `kill(pid, 0)` until its parent reaps it; treat it
+/// as exited. Only Linux exposes the state cheaply.
+#[cfg(target_os = "linux")]
+fn is_zombie(pid: u32) -> bool {
+    std::fs::read_to_string(format!("/proc/{pid}/stat"))
+        .ok()
+        .and_then(|stat| {
+            // The state follows the parenthesised command name, which may
+            // itself contain spaces or parentheses.
+            let after = &stat[stat.rfind(')')? + 1..];
+            after.split_whitespace().next().map(|s| s == "Z")
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_zombie(_pid: u32) -> bool {
+    false
+}
+
+impl ChildKiller for AdoptedChild {
+    fn kill(&mut self) -> io::Result<()> {
+        match pty_handoff::signal_pid(self.pid, libc::SIGHUP) {
+            Err(e) if e.raw_os_error() != Some(libc::ESRCH) => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    fn clone_killer(&self) -> Box<dyn ChildKiller + Send + Sync> {
+        Box::new(AdoptedChild { pid: self.pid })
+    }
+}
+
+impl Child for AdoptedChild {
+    fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
+        if self.is_alive() {
+            Ok(None)
+        } else {
+            Ok(Some(ExitStatus::with_exit_code(1)))
+        }
+    }
+
+    fn wait(&mut self) -> io::Result<ExitStatus> {
+        loop {
+            if let Some(status) = self.try_wait()? {
+                return Ok(status);
+            }
+            std::thread::sleep(ADOPTED_WAIT_POLL_INTERVAL);
+        }
+    }
+
+    fn process_id(&self) -> Option<u32> {
+        Some(self.pid)
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adopted_child_tracks_liveness_by_pid() {
+        let mut proc = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        let mut child = AdoptedChild::new(proc.id());
+        assert!(child.try_wait().unwrap().is_none());
+        child.kill().unwrap();
+        // Reap as the real parent would; the adopted view then reports exit.
+        proc.wait().unwrap();
+        assert!(child.try_wait().unwrap().is_some());
+    }
+
+    #[test]
+    fn adopted_master_resizes_and_reports_size() {
+        let pair = portable_pty::native_pty_system()
+            .openpty(PtySize::default())
+            .unwrap();
+        let fd = pair.master.as_raw_fd().unwrap();
+        // Duplicate so the adopted handle owns an independent fd.
+        let master = AdoptedMaster::new(pty_handoff::dup_cloexec(fd).unwrap());
+        let size = PtySize {
+            rows: 33,
+            cols: 101,
+            pixel_width: 0,
+            pixel_height: 0,
+        };
+        master.resize(size).unwrap();
+        assert_eq!(master.get_size().unwrap(), size);
+        assert_eq!(pair.master.get_size().unwrap(), size);
+    }
+}
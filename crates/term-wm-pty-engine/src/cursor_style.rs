@@ -0,0 +1,106 @@
+/// Cursor shape requested with DECSCUSR (`CSI Ps SP q`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CursorShape {
+    /// `Ps = 0`: whatever the host terminal is configured to show.
+    #[default]
+    Default,
+    BlinkingBlock,
+    SteadyBlock,
+    BlinkingUnderline,
+    SteadyUnderline,
+    BlinkingBar,
+    SteadyBar,
+}
+
+impl CursorShape {
+    /// The shape for a DECSCUSR parameter; `None` for values past 6.
+    pub fn from_decscusr(ps: u16) -> Option<Self> {
+        Some(match ps {
+            0 => Self::Default,
+            1 => Self::BlinkingBlock,
+            2 => Self::SteadyBlock,
+            3 => Self::BlinkingUnderline,
+            4 => Self::SteadyUnderline,
+            5 => Self::BlinkingBar,
+            6 => Self::SteadyBar,
+            _ => return None,
+        })
+    }
+
+    /// The DECSCUSR parameter selecting this shape.
+    pub fn decscusr(self) -> u8 {
+        match self {
+            Self::Default => 0,
+            Self::BlinkingBlock => 1,
+            Self::SteadyBlock => 2,
+            Self::BlinkingUnderline => 3,
+            Self::SteadyUnderline => 4,
+            Self::BlinkingBar => 5,
+            Self::SteadyBar => 6,
+        }
+    }
+
+    pub fn is_blinking(self) -> bool {
+        matches!(
+            self,
+            Self::BlinkingBlock | Self::BlinkingUnderline | Self::BlinkingBar
+        )
+    }
+}
+
+/// Cursor shape and color a child application asked for. The default
+/// leaves both to the host terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CursorStyle {
+    pub shape: CursorShape,
+    /// Set with OSC 12, cleared with OSC 112.
+    pub color: Option<(u8, u8, u8)>,
+}
+
+impl CursorStyle {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The sequences that put a terminal's cursor in this style. The default
+    /// style resets the host's shape (DECSCUSR 0) and color (OSC 112).
+    pub fn escape_sequence(&self) -> String {
+        let color = match self.color {
+            Some((r, g, b)) => format!("\x1b]12;#{r:02x}{g:02x}{b:02x}\x1b\\"),
+            None => "\x1b]112\x1b\\".to_string(),
+        };
+        format!("\x1b[{} q{color}", self.shape.decscusr())
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decscusr_round_trips() {
+        for ps in 0..=6 {
+            assert_eq!(
+                u16::from(CursorShape::from_decscusr(ps).unwrap().decscusr()),
+                ps
+            );
+        }
+        assert_eq!(CursorShape::from_decscusr(7), None);
+        assert!(CursorShape::BlinkingBar.is_blinking());
+        assert!(!CursorShape::SteadyUnderline.is_blinking());
+    }
+
+    #[test]
+    fn escape_sequence_sets_or_resets_shape_and_color() {
+        let style = CursorStyle {
+            shape: CursorShape::SteadyBar,
+            color: Some((0xff, 0x80, 0x00)),
+        };
+        assert_eq!(style.escape_sequence(), "\x1b[6 q\x1b]12;#ff8000\x1b\\");
+        assert_eq!(
+            CursorStyle::default().escape_sequence(),
+            "\x1b[0 q\x1b]112\x1b\\"
+        );
+    }
+}
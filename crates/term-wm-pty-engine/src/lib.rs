@@ -1,3 +1,6 @@
+#[cfg(unix)]
+pub mod adopted;
+pub mod cursor_style;
 pub mod input_encoding;
 #[cfg(windows)]
 pub mod job_object;
@@ -7,11 +10,12 @@ pub mod pty_state_tracker;
 pub mod signal;
 pub mod title;
 
+pub use cursor_style::{CursorShape, CursorStyle};
 pub use input_encoding::{ctrl_char, key_to_bytes, mouse_event_allowed, mouse_event_to_bytes};
 pub use pane::Pane;
-pub use pty::{Pty, PtyResult};
+pub use pty::{Pty, PtyResult, ReportedColors, default_colors, set_default_colors};
 pub use pty_state_tracker::{
-    DirectInputMode, DirectInputTracker, MouseTrackingMode, PtyStateTracker,
+    DirectInputMode, DirectInputTracker, MouseTrackingMode, PtyStateObserver, PtyStateTracker,
 };
 
 /// Status notifications from the PTY reader thread to the main loop.
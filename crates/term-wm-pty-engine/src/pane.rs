@@ -60,6 +60,11 @@ pub trait Pane {
     fn is_application_cursor_keys_active(&self) -> bool {
         false
     }
+
+    /// Cursor shape and color the child asked for (DECSCUSR, OSC 12).
+    fn cursor_style(&self) -> crate::CursorStyle {
+        crate::CursorStyle::default()
+    }
 }
 
 impl Pane for crate::Pty {
@@ -143,6 +148,10 @@ impl Pane for crate::Pty {
         self.tracker.is_application_cursor_keys_active()
     }
 
+    fn cursor_style(&self) -> crate::CursorStyle {
+        self.tracker.cursor_style()
+    }
+
     fn shared_parser(&mut self) -> Arc<Mutex<term_wm_vt100::Parser>> {
         self.shared_parser.clone()
     }
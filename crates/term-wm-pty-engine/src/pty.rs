@@ -1,17 +1,20 @@
 use std::io::{Read, Write};
 use std::sync::{
     Arc, Condvar, Mutex, OnceLock,
-    atomic::{AtomicBool, AtomicUsize, Ordering},
+    atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering},
 };
 use std::thread::{self, JoinHandle};
 use std::time::Instant;
 
 use portable_pty::{Child, CommandBuilder, MasterPty, PtySize, native_pty_system};
 
+#[cfg(unix)]
+use crate::adopted::{AdoptedChild, AdoptedMaster};
 #[cfg(windows)]
 use crate::job_object::JobObject;
 use crate::pty_state_tracker::PtyPerformAdapter;
 use term_clipboard::{Clipboard, Osc52Extractor};
+use term_wm_events::HostColorKind;
 
 /// Size of the PTY master read buffer (single `read()` call).
 /// 64KB keeps the reader parked most of the time under heavy output
@@ -111,6 +114,59 @@ const HISTORY_TAIL_LEN: usize = 8;
 /// Length of the DSR request sequence `\x1b[6n`.
 const DSR_PATTERN_LEN: usize = 4;
 
+/// Bits of [`Pty`]'s pending OSC 10/11 default-color queries.
+const QUERY_FG: u8 = 1;
+const QUERY_BG: u8 = 2;
+/// The child terminated its query with BEL; answer the same way.
+const QUERY_BEL: u8 = 4;
+
+/// Colors reported to children that query the default foreground/background
+/// (OSC 10/11). The window manager keeps these in step with what it renders
+/// behind terminal panes.
+static DEFAULT_COLORS: Mutex<(Rgb, Rgb)> = Mutex::new(((229, 229, 229), (0, 0, 0)));
+
+type Rgb = (u8, u8, u8);
+
+/// Where a [`Pty`] takes the colors it reports for OSC 10/11 queries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReportedColors {
+    /// The process-wide colors from [`set_default_colors`]: what the window
+    /// manager draws behind its terminal panes.
+    #[default]
+    Process,
+    /// Colors of a particular host terminal, e.g. the one attached to a
+    /// gateway channel. A query for a color that is `None` goes unanswered
+    /// rather than being answered with a guess.
+    Host { fg: Option<Rgb>, bg: Option<Rgb> },
+}
+
+/// Set the default foreground and background colors reported to children.
+pub fn set_default_colors(fg: Rgb, bg: Rgb) {
+    *DEFAULT_COLORS.lock().unwrap_or_else(|err| err.into_inner()) = (fg, bg);
+}
+
+/// The colors last passed to [`set_default_colors`].
+pub fn default_colors() -> (Rgb, Rgb) {
+    *DEFAULT_COLORS.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// `QUERY_*` bits for the OSC 10/11 queries (`ESC ] 1x ; ? BEL|ST`) in `bytes`.
+fn osc_color_queries(bytes: &[u8]) -> u8 {
+    bytes
+        .windows(6)
+        .enumerate()
+        .filter_map(|(i, w)| {
+            let kind = match w {
+                b"\x1b]10;?" => QUERY_FG,
+                b"\x1b]11;?" => QUERY_BG,
+                _ => return None,
+            };
+            let bel = bytes.get(i + 6) == Some(&0x07);
+            Some(if bel { kind | QUERY_BEL } else { kind })
+        })
+        .fold(0, |acc, bits| acc | bits)
+}
+
 /// Env var that enables dumping raw PTY→emulator bytes (as hex) to a file.
 /// Temporary diagnostic aid for seeing exactly what a child app sends (e.g.
 /// pico's escape sequences at the right margin of a long line).
@@ -186,6 +242,31 @@ impl PtyWriter {
         writer.write_all(input)?;
         writer.flush()
     }
+
+    /// Swap the real writer for one that always fails, leaking the original
+    /// so its drop-time EOF never reaches the slave (see
+    /// [`Pty::release_writer`]).
+    fn release(&self) {
+        let mut writer = self.inner.lock().unwrap_or_else(|err| err.into_inner());
+        let released = std::mem::replace(&mut *writer, Box::new(ReleasedWriter));
+        std::mem::forget(released);
+    }
+}
+
+/// Placeholder left behind by [`PtyWriter::release`].
+struct ReleasedWriter;
+
+impl Write for ReleasedWriter {
+    fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::BrokenPipe,
+            "PTY writer was released to another process",
+        ))
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
 }
 
 pub struct Pty {
@@ -202,6 +283,10 @@ pub struct Pty {
     bytes_received: Arc<AtomicUsize>,
     last_bytes: Arc<Mutex<Vec<u8>>>,
     dsr_requested: Arc<AtomicBool>,
+    /// `QUERY_*` bits set by the reader thread for OSC 10/11 queries.
+    color_queries: Arc<AtomicU8>,
+    /// Colors those queries are answered with.
+    reported_colors: ReportedColors,
     pending_title: Arc<Mutex<Option<String>>>,
     foreground_title: Arc<Mutex<Option<String>>>,
     last_fg_pid: u32,
@@ -234,6 +319,9 @@ pub struct Pty {
     job: Option<JobObject>,
     exited: bool,
     exit_status: Option<portable_pty::ExitStatus>,
+    /// False for an adopted child: its real parent reaps it, so the status
+    /// `try_wait` reports is a placeholder and must not surface.
+    exit_status_known: bool,
     /// Guards against double-fire of the Exited callback from both
     /// has_exited() and the reader thread (EOF detection race).
     exited_emitted: Arc<AtomicBool>,
@@ -358,18 +446,65 @@ impl Pty {
             }
             job
         };
-        let reader = pair
-            .master
+        Self::from_master(
+            pair.master,
+            child,
+            #[cfg(windows)]
+            job,
+            size,
+            scrollback_len,
+            &[],
+        )
+    }
+
+    /// Take over a PTY whose master descriptor was received from another
+    /// process (gateway upgrade). The child keeps running untouched; this
+    /// process can signal it and drive the master, but never reaps it, so
+    /// [`Pty::exit_status`] stays `None` after it exits.
+    ///
+    /// `screen_state` is the previous owner's [`Pty::generate_snapshot`]; it
+    /// primes the emulator before the reader starts so the grid, cursor, and
+    /// input modes survive the handoff.
+    #[cfg(unix)]
+    pub fn adopt(
+        master: std::os::unix::io::OwnedFd,
+        child_pid: u32,
+        size: PtySize,
+        scrollback_len: usize,
+        screen_state: &[u8],
+    ) -> PtyResult<Self> {
+        let mut pty = Self::from_master(
+            Box::new(AdoptedMaster::new(master)),
+            Box::new(AdoptedChild::new(child_pid)),
+            size,
+            scrollback_len,
+            screen_state,
+        )?;
+        pty.exit_status_known = false;
+        Ok(pty)
+    }
+
+    /// Shared tail of [`Pty::spawn_with_scrollback`] and [`Pty::adopt`]: wire
+    /// the reader thread and shared state around an open master and child.
+    fn from_master(
+        master: Box<dyn MasterPty + Send>,
+        child: Box<dyn Child + Send + Sync>,
+        #[cfg(windows)] job: Option<JobObject>,
+        size: PtySize,
+        scrollback_len: usize,
+        screen_state: &[u8],
+    ) -> PtyResult<Self> {
+        let reader = master
             .try_clone_reader()
             .map_err(|err| wrap_err("try_clone_reader", err))?;
         let writer = PtyWriter::new(
-            pair.master
+            master
                 .take_writer()
                 .map_err(|err| wrap_err("take_writer", err))?,
         );
         // The reader thread applies drain-synchronized resizes (reflow + ioctl),
         // so master must be shared with it.
-        let master = Arc::new(Mutex::new(pair.master));
+        let master = Arc::new(Mutex::new(master));
         let reader_master = Arc::clone(&master);
         let size_arc = Arc::new(Mutex::new(size));
         let reader_size = Arc::clone(&size_arc);
@@ -385,6 +520,8 @@ impl Pty {
         let reader_bytes = Arc::clone(&bytes_received);
         let reader_last = Arc::clone(&last_bytes);
         let reader_dsr = Arc::clone(&dsr_requested);
+        let color_queries = Arc::new(AtomicU8::new(0));
+        let reader_color_queries = Arc::clone(&color_queries);
         let status_cb: StatusCallback = Arc::new(Mutex::new(None));
         let reader_status_cb = Arc::clone(&status_cb);
         let exited_emitted = Arc::new(AtomicBool::new(false));
@@ -392,8 +529,13 @@ impl Pty {
 
         let pending_title = Arc::new(Mutex::new(None));
         let foreground_title = Arc::new(Mutex::new(None));
-        let initial_parser = term_wm_vt100::Parser::new(size.rows, size.cols, scrollback_len);
+        let mut initial_parser = term_wm_vt100::Parser::new(size.rows, size.cols, scrollback_len);
         let tracker = std::sync::Arc::new(crate::PtyStateTracker::new(size.rows));
+        if !screen_state.is_empty() {
+            initial_parser.process(screen_state);
+            let mut adapter = PtyPerformAdapter::new(std::sync::Arc::clone(&tracker));
+            vte::Parser::new().advance(&mut adapter, screen_state);
+        }
         let reader_tracker = std::sync::Arc::clone(&tracker);
         let shared_parser = Arc::new(Mutex::new(initial_parser));
         let dirty = Arc::new(AtomicBool::new(false));
@@ -413,6 +555,7 @@ impl Pty {
                 bytes_received: reader_bytes,
                 last_bytes: reader_last,
                 dsr_requested: reader_dsr,
+                color_queries: reader_color_queries,
                 shared_parser: reader_parser,
                 dirty: reader_dirty,
                 dirty_cond: reader_dirty_cond,
@@ -439,6 +582,8 @@ impl Pty {
             bytes_received,
             last_bytes,
             dsr_requested,
+            color_queries,
+            reported_colors: ReportedColors::default(),
             pending_title,
             foreground_title,
             last_fg_pid: 0,
@@ -457,6 +602,7 @@ impl Pty {
             job,
             exited: false,
             exit_status: None,
+            exit_status_known: true,
             reader: Some(reader_handle),
             status_cb,
             exited_emitted,
@@ -482,7 +628,7 @@ impl Pty {
                 && let Ok(Some(status)) = child.try_wait()
             {
                 self.exited = true;
-                self.exit_status = Some(status);
+                self.exit_status = self.exit_status_known.then_some(status);
                 self.child = None;
                 self.exited_emitted.store(true, Ordering::Release);
                 fire_cb = cb;
@@ -525,6 +671,106 @@ impl Pty {
         self.reader.is_some()
     }
 
+    /// PID of the PTY child, while it is still tracked.
+    pub fn child_pid(&self) -> Option<u32> {
+        self.child.as_ref().and_then(|c| c.process_id())
+    }
+
+    /// Duplicate of the PTY master descriptor (close-on-exec), for handing the
+    /// session to another process. The original stays owned by this `Pty`.
+    #[cfg(unix)]
+    pub fn try_clone_master_fd(&self) -> std::io::Result<std::os::unix::io::OwnedFd> {
+        use std::os::unix::io::BorrowedFd;
+        let master = self.master.lock().unwrap_or_else(|err| err.into_inner());
+        let fd = master.as_raw_fd().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::Unsupported, "PTY master has no fd")
+        })?;
+        // SAFETY: `fd` belongs to the master held by the guard, so it stays
+        // open for the duration of the borrow.
+        unsafe { BorrowedFd::borrow_raw(fd) }.try_clone_to_owned()
+    }
+
+    /// Stop the reader thread without touching the child, so no further
+    /// output is consumed from the master. Everything read so far is already
+    /// parsed, so a [`Pty::generate_snapshot`] taken afterwards is complete.
+    ///
+    /// Returns `false` if the reader did not stop within `grace` (it keeps
+    /// running detached). Undo with [`Pty::thaw`].
+    #[cfg(unix)]
+    pub fn freeze(&mut self, grace: std::time::Duration) -> bool {
+        self.shutdown.store(true, Ordering::Release);
+        // Release a reader parked on the I/O burst budget so it can observe
+        // the shutdown flag.
+        self.dirty.store(false, Ordering::Release);
+        {
+            let (lock, cvar) = &*self.dirty_cond;
+            let _guard = lock.lock().unwrap_or_else(|err| err.into_inner());
+            cvar.notify_all();
+        }
+        self.wake_reader();
+        let Some(handle) = self.reader.take() else {
+            return true;
+        };
+        let deadline = Instant::now() + grace;
+        while !handle.is_finished() && Instant::now() < deadline {
+            thread::sleep(std::time::Duration::from_millis(2));
+        }
+        if !handle.is_finished() {
+            return false;
+        }
+        let _ = handle.join();
+        true
+    }
+
+    /// Restart the reader after [`Pty::freeze`], e.g. when a handoff is
+    /// rolled back. A no-op while the reader is still running.
+    #[cfg(unix)]
+    pub fn thaw(&mut self) -> PtyResult<()> {
+        if self.reader.is_some() {
+            return Ok(());
+        }
+        let reader = self
+            .master
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .try_clone_reader()
+            .map_err(|err| wrap_err("try_clone_reader", err))?;
+        self.shutdown.store(false, Ordering::Release);
+        let args = ParserReadLoopArgs {
+            reader,
+            pending: Arc::clone(&self.pending),
+            bytes_received: Arc::clone(&self.bytes_received),
+            last_bytes: Arc::clone(&self.last_bytes),
+            dsr_requested: Arc::clone(&self.dsr_requested),
+            color_queries: Arc::clone(&self.color_queries),
+            shared_parser: Arc::clone(&self.shared_parser),
+            dirty: Arc::clone(&self.dirty),
+            dirty_cond: Arc::clone(&self.dirty_cond),
+            pending_title: Arc::clone(&self.pending_title),
+            status_cb: Arc::clone(&self.status_cb),
+            scrollback_len: self.scrollback_len,
+            osc52_text: None,
+            clipboard: None,
+            exited_emitted: Arc::clone(&self.exited_emitted),
+            tracker: Arc::clone(&self.tracker),
+            master: Arc::clone(&self.master),
+            size: Arc::clone(&self.size),
+            pending_resize: Arc::clone(&self.pending_resize),
+            wake_read_fd: self.resize_wake.read_fd(),
+            shutdown: Arc::clone(&self.shutdown),
+        };
+        self.reader = Some(thread::spawn(move || parser_read_loop(args)));
+        Ok(())
+    }
+
+    /// Give up the master writer without closing it. portable-pty's writer
+    /// sends EOF to the slave when dropped, which would end a shell that now
+    /// belongs to another process; call this once a handoff has committed.
+    /// Later writes through this `Pty` (or any [`PtyWriter`] clone) fail.
+    pub fn release_writer(&self) {
+        self.writer.release();
+    }
+
     /// Request a resize. The reader thread applies it (emulator reflow + OS
     /// `ioctl` / SIGWINCH) at the next pipe-drain boundary, so the grid width
     /// never changes while the shell is mid-draw and SIGWINCH is delivered only
@@ -711,7 +957,7 @@ impl Pty {
         match child.try_wait() {
             Ok(Some(status)) => {
                 self.exited = true;
-                self.exit_status = Some(status);
+                self.exit_status = self.exit_status_known.then_some(status);
                 self.child = None;
 
                 // ConPTY pipes on Windows frequently swallow EOF, leaving the
@@ -810,6 +1056,15 @@ impl Pty {
         Ok(())
     }
 
+    /// Choose the colors reported for the child's OSC 10/11 queries.
+    pub fn set_reported_colors(&mut self, colors: ReportedColors) {
+        self.reported_colors = colors;
+    }
+
+    pub fn reported_colors(&self) -> ReportedColors {
+        self.reported_colors
+    }
+
     pub fn size(&self) -> PtySize {
         *self.size.lock().unwrap_or_else(|err| err.into_inner())
     }
@@ -818,18 +1073,43 @@ impl Pty {
     /// The caller should then lock `shared_parser()` directly for cell access.
     pub fn screen(&mut self) {
         self.poll_foreground();
-        if self.dirty.swap(false, Ordering::Acquire) {
-            // Send DSR response if requested by the reader thread.
-            if self.dsr_requested.swap(false, Ordering::Relaxed) {
-                let parser = self
-                    .shared_parser
-                    .lock()
-                    .unwrap_or_else(|err| err.into_inner());
-                let (row, col) = parser.screen().cursor_position();
-                drop(parser);
-                let response = format!("\x1b[{};{}R", row.saturating_add(1), col.saturating_add(1));
+        // Queries are answered whether or not the pane is still dirty: the
+        // compositor may already have consumed the flag via `take_dirty`.
+        // Send DSR response if requested by the reader thread.
+        if self.dsr_requested.swap(false, Ordering::Relaxed) {
+            let parser = self
+                .shared_parser
+                .lock()
+                .unwrap_or_else(|err| err.into_inner());
+            let (row, col) = parser.screen().cursor_position();
+            drop(parser);
+            let response = format!("\x1b[{};{}R", row.saturating_add(1), col.saturating_add(1));
+            let _ = self.write_bytes(response.as_bytes());
+        }
+        // Answer OSC 10/11 with the colors drawn behind this pane, or the
+        // host's when this PTY belongs to a gateway channel.
+        let queries = self.color_queries.swap(0, Ordering::Relaxed);
+        if queries != 0 {
+            let (fg, bg) = match self.reported_colors {
+                ReportedColors::Process => {
+                    let (fg, bg) = default_colors();
+                    (Some(fg), Some(bg))
+                }
+                ReportedColors::Host { fg, bg } => (fg, bg),
+            };
+            let bel = queries & QUERY_BEL != 0;
+            let mut response = String::new();
+            if let Some(fg) = fg.filter(|_| queries & QUERY_FG != 0) {
+                response.push_str(&HostColorKind::Foreground.format_reply(fg, bel));
+            }
+            if let Some(bg) = bg.filter(|_| queries & QUERY_BG != 0) {
+                response.push_str(&HostColorKind::Background.format_reply(bg, bel));
+            }
+            if !response.is_empty() {
                 let _ = self.write_bytes(response.as_bytes());
             }
+        }
+        if self.dirty.swap(false, Ordering::Acquire) {
             // Acquire the lock to prevent lost wakeups on the condition variable
             let (lock, cvar) = &*self.dirty_cond;
             let _guard = lock.lock().unwrap_or_else(|err| err.into_inner());
@@ -939,6 +1219,7 @@ struct ParserReadLoopArgs {
     bytes_received: Arc<AtomicUsize>,
     last_bytes: Arc<Mutex<Vec<u8>>>,
     dsr_requested: Arc<AtomicBool>,
+    color_queries: Arc<AtomicU8>,
     shared_parser: Arc<Mutex<term_wm_vt100::Parser>>,
     dirty: Arc<AtomicBool>,
     dirty_cond: Arc<(std::sync::Mutex<()>, Condvar)>,
@@ -978,6 +1259,7 @@ fn parser_read_loop(args: ParserReadLoopArgs) {
         bytes_received,
         last_bytes,
         dsr_requested,
+        color_queries,
         shared_parser,
         dirty,
         dirty_cond,
@@ -1114,11 +1396,6 @@ fn parser_read_loop(args: ParserReadLoopArgs) {
                 Ok(n) => {
                     bytes_received.fetch_add(n, Ordering::Relaxed);
                     bytes_since_render += n;
-                    // DSR detection: 65536-byte reads make cross-chunk splitting
-                    // of the 4-byte \x1b[6n pattern vanishingly unlikely.
-                    if buf[..n].windows(DSR_PATTERN_LEN).any(|w| w == b"\x1b[6n") {
-                        dsr_requested.store(true, Ordering::Relaxed);
-                    }
                     let mut last = last_bytes.lock().unwrap_or_else(|err| err.into_inner());
                     last.clear();
                     last.extend_from_slice(&buf[..n]);
@@ -1158,6 +1435,17 @@ fn parser_read_loop(args: ParserReadLoopArgs) {
                             shared_parser.lock().unwrap_or_else(|err| err.into_inner());
                         shared.process(&buf[..n]);
                     }
+                    // Flag queries only once the chunk is in the parser, so the
+                    // UI thread answers DSR with the cursor it moved to.
+                    // DSR detection: 65536-byte reads make cross-chunk splitting
+                    // of the 4-byte \x1b[6n pattern vanishingly unlikely.
+                    if buf[..n].windows(DSR_PATTERN_LEN).any(|w| w == b"\x1b[6n") {
+                        dsr_requested.store(true, Ordering::Relaxed);
+                    }
+                    let queries = osc_color_queries(&buf[..n]);
+                    if queries != 0 {
+                        color_queries.fetch_or(queries, Ordering::Relaxed);
+                    }
                     esc_trace_chunk(&buf[..n]);
 
                     if let Some(title) = extract_osc_title(&buf[..n]) {
@@ -1625,6 +1913,7 @@ mod tests {
             bytes_received: Arc::new(AtomicUsize::new(0)),
             last_bytes: Arc::new(Mutex::new(Vec::new())),
             dsr_requested: Arc::new(AtomicBool::new(false)),
+            color_queries: Arc::new(AtomicU8::new(0)),
             shared_parser: Arc::new(Mutex::new(term_wm_vt100::Parser::new(24, 80, 0))),
             dirty: Arc::new(AtomicBool::new(false)),
             dirty_cond: Arc::new((Mutex::new(()), Condvar::new())),
@@ -1776,6 +2065,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parser_read_loop_flags_default_color_queries() {
+        let args = make_parser_test_args(b"\x1b]11;?\x07\x1b]10;?\x1b\\");
+        let color_queries = Arc::clone(&args.color_queries);
+
+        parser_read_loop(args);
+
+        assert_eq!(
+            color_queries.load(Ordering::Relaxed),
+            QUERY_FG | QUERY_BG | QUERY_BEL
+        );
+        assert_eq!(osc_color_queries(b"\x1b]11;rgb:0/0/0\x07"), 0);
+    }
+
     #[test]
     fn parser_read_loop_tracks_tail_for_cross_boundary_dsr() {
         let args = make_parser_test_args(b"XX\x1b[6nYY");
@@ -2833,4 +3136,83 @@ mod tests {
     fn expected_child_term() -> &'static str {
         CHILD_TERM
     }
+
+    // ── freeze / thaw / adopt (gateway upgrade handoff) ─────────────
+
+    /// Pump `pty` until its drained output contains `needle`.
+    #[cfg(unix)]
+    fn wait_for_output(pty: &mut Pty, needle: &str) {
+        let mut accumulated = Vec::new();
+        let start = std::time::Instant::now();
+        loop {
+            pty.screen();
+            accumulated.extend_from_slice(&pty.drain_pending());
+            if String::from_utf8_lossy(&accumulated).contains(needle) {
+                return;
+            }
+            assert!(
+                start.elapsed() < std::time::Duration::from_secs(5),
+                "never saw {needle:?}; got {}",
+                String::from_utf8_lossy(&accumulated)
+            );
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn freeze_then_thaw_resumes_reading() {
+        let mut pty = Pty::spawn(
+            CommandBuilder::new(get_test_executable()),
+            PtySize::default(),
+        )
+        .unwrap();
+        assert!(pty.freeze(std::time::Duration::from_secs(2)));
+        assert!(!pty.reader_is_alive());
+        pty.thaw().unwrap();
+        pty.write_str("thawed\r").unwrap();
+        wait_for_output(&mut pty, "thawed");
+        pty.kill_child().unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn adopted_pty_keeps_screen_and_drives_the_same_child() {
+        let size = PtySize::default();
+        let mut old = Pty::spawn(CommandBuilder::new(get_test_executable()), size).unwrap();
+        old.write_str("before\r").unwrap();
+        wait_for_output(&mut old, "before");
+
+        // Hand off exactly as the gateway does: freeze, snapshot, duplicate
+        // the master, then disarm the old writer before dropping it.
+        assert!(old.freeze(std::time::Duration::from_secs(2)));
+        let snapshot = old.generate_snapshot();
+        let pid = old.child_pid().unwrap();
+        let dup = old.try_clone_master_fd().unwrap();
+        old.release_writer();
+        assert!(old.write_str("lost").is_err());
+        drop(old);
+
+        let mut adopted = Pty::adopt(dup, pid, size, 0, &snapshot).unwrap();
+        assert!(
+            adopted.screen_lines().iter().any(|l| l.contains("before")),
+            "snapshot must prime the adopted screen"
+        );
+        assert!(!adopted.has_exited(), "handoff must not end the child");
+        adopted.write_str("after\r").unwrap();
+        wait_for_output(&mut adopted, "after");
+
+        adopted.signal_process_group(libc::SIGKILL).unwrap();
+        let start = std::time::Instant::now();
+        while !adopted.has_exited() {
+            assert!(start.elapsed() < std::time::Duration::from_secs(5));
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        assert!(
+            adopted.exit_status().is_none(),
+            "an adopted child's exit code belongs to its real parent"
+        );
+        // This test process is that parent: reap the zombie.
+        unsafe { libc::waitpid(pid as libc::pid_t, std::ptr::null_mut(), 0) };
+    }
 }
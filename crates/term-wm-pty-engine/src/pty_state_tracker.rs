@@ -1,7 +1,12 @@
-use std::sync::atomic::{AtomicBool, AtomicU8, AtomicU16, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicU16, AtomicU32, Ordering};
 
 use vte::{Params, Perform};
 
+use crate::cursor_style::{CursorShape, CursorStyle};
+
+/// Marks a stored cursor color as set, above the 24 RGB bits.
+const CURSOR_COLOR_SET: u32 = 1 << 24;
+
 /// Structured snapshot of a window's current direct-input capture level.
 ///
 /// The keyboard and mouse dimensions are independent: an application on the
@@ -96,7 +101,8 @@ impl MouseTrackingMode {
 }
 
 /// Lock-free state tracker that observes the PTY byte stream for application
-/// state heuristics (alternate screen, mouse tracking, custom margins).
+/// state heuristics (alternate screen, mouse tracking, custom margins) and
+/// the cursor style the application asked for (DECSCUSR, OSC 12).
 ///
 /// Shared between the reader thread (writer) and the main UI thread (reader)
 /// via `Arc`. All fields are atomics so the main thread can query without
@@ -111,6 +117,9 @@ pub struct PtyStateTracker {
     is_alt_scroll_mode_active: AtomicBool,
     has_custom_margins: AtomicBool,
     terminal_height: AtomicU16,
+    cursor_shape: AtomicU8,
+    /// `CURSOR_COLOR_SET | 0xRRGGBB`, or 0 for the host's color.
+    cursor_color: AtomicU32,
 }
 
 impl PtyStateTracker {
@@ -124,6 +133,8 @@ impl PtyStateTracker {
             is_alt_scroll_mode_active: AtomicBool::new(false),
             has_custom_margins: AtomicBool::new(false),
             terminal_height: AtomicU16::new(terminal_height),
+            cursor_shape: AtomicU8::new(0),
+            cursor_color: AtomicU32::new(0),
         }
     }
 
@@ -190,6 +201,22 @@ impl PtyStateTracker {
         self.has_custom_margins.load(Ordering::Acquire)
     }
 
+    /// Cursor shape and color last requested by the application.
+    pub fn cursor_style(&self) -> CursorStyle {
+        let shape =
+            CursorShape::from_decscusr(u16::from(self.cursor_shape.load(Ordering::Acquire)))
+                .unwrap_or_default();
+        let color = self.cursor_color.load(Ordering::Acquire);
+        CursorStyle {
+            shape,
+            color: (color & CURSOR_COLOR_SET != 0).then_some((
+                (color >> 16) as u8,
+                (color >> 8) as u8,
+                color as u8,
+            )),
+        }
+    }
+
     /// Update terminal height on SIGWINCH. Called from main thread.
     pub fn resize(&self, height: u16) {
         self.terminal_height.store(height, Ordering::Release);
@@ -223,6 +250,17 @@ impl PtyStateTracker {
         self.has_custom_margins.store(active, Ordering::Release);
     }
 
+    pub(crate) fn set_cursor_shape(&self, shape: CursorShape) {
+        self.cursor_shape.store(shape.decscusr(), Ordering::Release);
+    }
+
+    pub(crate) fn set_cursor_color(&self, color: Option<(u8, u8, u8)>) {
+        let packed = color.map_or(0, |(r, g, b)| {
+            CURSOR_COLOR_SET | u32::from(r) << 16 | u32::from(g) << 8 | u32::from(b)
+        });
+        self.cursor_color.store(packed, Ordering::Release);
+    }
+
     /// Conditional mouse mode update: set unconditionally, but clear only
     /// via CAS so we don't clobber a different active mode.
     pub(crate) fn update_mouse_tracking(&self, target_mode: u8, is_set: bool) {
@@ -250,6 +288,8 @@ impl PtyStateTracker {
         self.is_alt_scroll_mode_active
             .store(false, Ordering::Release);
         self.has_custom_margins.store(false, Ordering::Release);
+        self.cursor_shape.store(0, Ordering::Release);
+        self.cursor_color.store(0, Ordering::Release);
     }
 }
 
@@ -272,6 +312,27 @@ impl PtyPerformAdapter {
     }
 }
 
+/// Feeds a byte stream into a [`PtyStateTracker`] outside the PTY reader
+/// thread, e.g. for a session client following a remote pane's output.
+/// Keeps its own parser so sequences split across chunks are still seen.
+pub struct PtyStateObserver {
+    parser: vte::Parser,
+    adapter: PtyPerformAdapter,
+}
+
+impl PtyStateObserver {
+    pub fn new(tracker: std::sync::Arc<PtyStateTracker>) -> Self {
+        Self {
+            parser: vte::Parser::new(),
+            adapter: PtyPerformAdapter::new(tracker),
+        }
+    }
+
+    pub fn observe(&mut self, bytes: &[u8]) {
+        self.parser.advance(&mut self.adapter, bytes);
+    }
+}
+
 impl Perform for PtyPerformAdapter {
     fn esc_dispatch(&mut self, intermediates: &[u8], ignore: bool, byte: u8) {
         if ignore {
@@ -294,6 +355,19 @@ impl Perform for PtyPerformAdapter {
             return;
         }
 
+        // DECSCUSR (Set Cursor Style): CSI Ps SP q
+        if action == 'q' && intermediates == *b" " {
+            let ps = params
+                .iter()
+                .next()
+                .and_then(|g| g.first().copied())
+                .unwrap_or(0);
+            if let Some(shape) = CursorShape::from_decscusr(ps) {
+                self.tracker.set_cursor_shape(shape);
+            }
+            return;
+        }
+
         let is_dec_private = intermediates.first() == Some(&b'?');
         match action {
             'h' | 'l' if is_dec_private => {
@@ -346,7 +420,22 @@ impl Perform for PtyPerformAdapter {
     fn hook(&mut self, _params: &Params, _intermediates: &[u8], _ignore: bool, _action: char) {}
     fn put(&mut self, _byte: u8) {}
     fn unhook(&mut self) {}
-    fn osc_dispatch(&mut self, _params: &[&[u8]], _bell_terminated: bool) {}
+    fn osc_dispatch(&mut self, params: &[&[u8]], _bell_terminated: bool) {
+        match params {
+            // OSC 12: set the cursor color (`?` queries are not ours to answer)
+            [b"12", spec, ..] => {
+                if let Some(rgb) = std::str::from_utf8(spec)
+                    .ok()
+                    .and_then(term_wm_events::parse_color_spec)
+                {
+                    self.tracker.set_cursor_color(Some(rgb));
+                }
+            }
+            // OSC 112: reset the cursor color
+            [b"112", ..] => self.tracker.set_cursor_color(None),
+            _ => {}
+        }
+    }
 }
 
 #[allow(clippy::unwrap_used)]
@@ -696,6 +785,63 @@ mod tests {
         assert_eq!(mode.access_label(), "off");
     }
 
+    #[test]
+    fn test_decscusr_sets_cursor_shape() {
+        let tracker = std::sync::Arc::new(make_tracker(24));
+        assert!(tracker.cursor_style().is_default());
+        feed(&tracker, b"\x1b[6 q");
+        assert_eq!(tracker.cursor_style().shape, CursorShape::SteadyBar);
+        feed(&tracker, b"\x1b[3 q");
+        assert_eq!(tracker.cursor_style().shape, CursorShape::BlinkingUnderline);
+        // Out-of-range shapes are ignored; an empty parameter means default.
+        feed(&tracker, b"\x1b[9 q");
+        assert_eq!(tracker.cursor_style().shape, CursorShape::BlinkingUnderline);
+        feed(&tracker, b"\x1b[ q");
+        assert_eq!(tracker.cursor_style().shape, CursorShape::Default);
+        // `CSI q` without the space is DECLL, not a cursor style.
+        feed(&tracker, b"\x1b[2q");
+        assert_eq!(tracker.cursor_style().shape, CursorShape::Default);
+    }
+
+    #[test]
+    fn test_osc12_sets_and_osc112_resets_cursor_color() {
+        let tracker = std::sync::Arc::new(make_tracker(24));
+        feed(&tracker, b"\x1b]12;#ff8000\x07");
+        assert_eq!(tracker.cursor_style().color, Some((0xff, 0x80, 0x00)));
+        feed(&tracker, b"\x1b]12;rgb:00/ff/00\x1b\\");
+        assert_eq!(tracker.cursor_style().color, Some((0x00, 0xff, 0x00)));
+        // Queries and unknown names leave the color alone.
+        feed(&tracker, b"\x1b]12;?\x07\x1b]12;chartreuse\x07");
+        assert_eq!(tracker.cursor_style().color, Some((0x00, 0xff, 0x00)));
+        feed(&tracker, b"\x1b]112\x07");
+        assert_eq!(tracker.cursor_style().color, None);
+    }
+
+    #[test]
+    fn test_cursor_style_reset_by_ris() {
+        let tracker = std::sync::Arc::new(make_tracker(24));
+        feed(&tracker, b"\x1b[2 q\x1b]12;#123456\x07");
+        assert!(!tracker.cursor_style().is_default());
+        feed(&tracker, b"\x1bc");
+        assert!(tracker.cursor_style().is_default());
+    }
+
+    #[test]
+    fn test_observer_follows_sequences_split_across_chunks() {
+        let tracker = std::sync::Arc::new(make_tracker(24));
+        let mut observer = PtyStateObserver::new(tracker.clone());
+        observer.observe(b"text\x1b[5");
+        observer.observe(b" qmore\x1b]12;#0");
+        observer.observe(b"000ff\x07");
+        assert_eq!(
+            tracker.cursor_style(),
+            CursorStyle {
+                shape: CursorShape::BlinkingBar,
+                color: Some((0, 0, 0xff)),
+            }
+        );
+    }
+
     #[test]
     fn test_direct_input_mode_reset_by_ris() {
         let tracker = std::sync::Arc::new(make_tracker(24));
@@ -25,4 +25,12 @@ pub trait RenderTarget {
         self.exit()?;
         self.enter()
     }
+    /// Ask the host terminal for its default colors (OSC 10/11); answers
+    /// arrive later as input events. Targets without a host terminal ignore it.
+    fn query_host_colors(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+    /// Render for the host terminal a session client probed on our behalf.
+    /// Targets without a host terminal ignore it.
+    fn set_host_display(&mut self, _display: &term_wm_events::HostDisplay) {}
 }
@@ -363,6 +363,10 @@ impl Component<TermWmAction> for WmCommandPaletteComponent {
         Some(self.hitbox_id)
     }
 
+    fn paste(&mut self, text: &str) -> bool {
+        self.palette.paste(text)
+    }
+
     fn destroy(&mut self) {}
 }
 
@@ -413,8 +417,7 @@ impl WmComponent for WmCommandPaletteComponent {
         match action {
             ComponentAction::Restore => {
                 self.dialog.set_visible(true);
-                self.palette.query.clear();
-                self.palette.cursor = 0;
+                self.palette.clear_query();
                 self.palette.selected = 0;
                 self.palette.data_dirty = true;
                 self.palette.query_dirty = true;
@@ -549,7 +552,7 @@ mod tests {
     fn process_action_restore_resets_state() {
         let mut palette = WmCommandPaletteComponent::new();
         palette.show();
-        palette.palette.query = "test".to_string();
+        palette.palette.set_query("test");
         palette.palette.selected = 5;
         palette.palette.data_dirty = false;
         palette.palette.query_dirty = false;
@@ -557,7 +560,7 @@ mod tests {
         palette.process_action(&ComponentAction::Restore);
 
         assert!(<WmCommandPaletteComponent as Overlay<TermWmAction>>::visible(&palette));
-        assert!(palette.palette.query.is_empty());
+        assert!(palette.palette.query().is_empty());
         assert_eq!(palette.palette.selected, 0);
         assert!(palette.palette.data_dirty);
         assert!(palette.palette.query_dirty);
@@ -702,7 +705,7 @@ mod tests {
         assert!(full.0 >= 30);
 
         // Filtering down to a single row must not change the footprint.
-        palette.palette.query = "alp".to_string();
+        palette.palette.set_query("alp");
         palette.palette.query_dirty = true;
         palette.refresh_if_dirty();
         assert_eq!(palette.palette.display_nodes.len(), 1);
@@ -773,7 +776,7 @@ mod tests {
         assert_eq!(rows_full.height, 4);
 
         // Filter down to one row and re-render.
-        palette.palette.query = "alp".to_string();
+        palette.palette.set_query("alp");
         palette.palette.query_dirty = true;
         render_palette(&mut palette, area);
         let bounds_filtered =
@@ -797,7 +800,7 @@ mod tests {
         palette.set_items(sample_items());
         palette.show();
 
-        palette.palette.query = "zzz".to_string();
+        palette.palette.set_query("zzz");
         palette.palette.query_dirty = true;
         render_palette(&mut palette, area);
 
@@ -817,8 +820,7 @@ mod tests {
     fn ctrl_c_clears_query_and_keeps_palette_open() {
         let mut palette = WmCommandPaletteComponent::new();
         palette.show();
-        palette.palette.query = "alp".to_string();
-        palette.palette.cursor = 3;
+        palette.palette.set_query("alp");
         palette.palette.query_dirty = true;
 
         let ctx = ComponentContext::new(true);
@@ -834,8 +836,7 @@ mod tests {
         });
         let result = palette.handle_events(&event, &ctx);
         assert!(result.is_consumed());
-        assert!(palette.palette.query.is_empty());
-        assert_eq!(palette.palette.cursor, 0);
+        assert!(palette.palette.query().is_empty());
         assert!(
             <WmCommandPaletteComponent as Overlay<TermWmAction>>::visible(&palette),
             "consumed events must not dismiss the palette"
@@ -846,7 +847,7 @@ mod tests {
     fn ctrl_c_empty_query_closes_palette() {
         let mut palette = WmCommandPaletteComponent::new();
         palette.show();
-        palette.palette.query = String::new();
+        palette.palette.clear_query();
         palette.palette.query_dirty = true;
 
         let ctx = ComponentContext::new(true);
@@ -865,6 +866,6 @@ mod tests {
             result,
             EventResult::Action(TermWmAction::CloseMenu)
         ));
-        assert!(palette.palette.query.is_empty());
+        assert!(palette.palette.query().is_empty());
     }
 }
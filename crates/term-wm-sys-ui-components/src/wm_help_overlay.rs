@@ -11,7 +11,9 @@ use term_wm_core::actions::{EventResult, TermWmAction};
 use term_wm_core::app_context::AppContext;
 use term_wm_core::components::{Component, ComponentContext, Overlay, SelectionStatus};
 use term_wm_core::keybindings::KeyBindings;
+use term_wm_core::theme::Theme;
 use term_wm_core::window::WindowKey;
+use term_wm_core::wm_config::WmConfig;
 use term_wm_ui_components::helpers::layout_rect_to_clipped_rect;
 use term_wm_ui_components::{
     DialogOverlayComponent, MarkdownViewerComponent, ScrollKeyMode, ScrollViewComponent,
@@ -35,7 +37,7 @@ impl Component<TermWmAction> for WmHelpOverlayComponent {
         &mut self,
         backend: &mut dyn term_wm_render::RenderBackend,
         area: LayoutRect,
-        _ctx: &ComponentContext,
+        ctx: &ComponentContext,
         registry: &mut term_wm_core::hitbox_registry::HitboxRegistry,
     ) {
         self.area.set(area);
@@ -66,7 +68,7 @@ impl Component<TermWmAction> for WmHelpOverlayComponent {
             width: rect.width.saturating_sub(2),
             height: rect.height.saturating_sub(2),
         };
-        let ctx = ComponentContext::new(true).with_overlay(true);
+        let ctx = ctx.with_focus(true).with_overlay(true);
         self.content.render(backend, inner_layout, &ctx, registry);
     }
 
@@ -80,6 +82,14 @@ impl Component<TermWmAction> for WmHelpOverlayComponent {
         }
         match event {
             Event::Key(key) => {
+                // An open search gets the keys first (Esc clears it, typed
+                // `q` extends the query) before they can close the overlay.
+                if self.content.content.borrow().is_searching() {
+                    let result = self.content.handle_events(event, ctx);
+                    if !result.is_ignored() {
+                        return result;
+                    }
+                }
                 if self.keybindings.matches(TermWmAction::CloseHelp, key) {
                     self.close();
                     EventResult::Consumed
@@ -162,11 +172,12 @@ impl Overlay<TermWmAction> for WmHelpOverlayComponent {
 }
 
 impl WmHelpOverlayComponent {
-    pub fn new(app_ctx: &Arc<AppContext>, keybindings: KeyBindings) -> Self {
+    /// Build the overlay with its markdown styled for `theme`; it restyles
+    /// itself if the theme it is rendered with changes.
+    pub fn new(app_ctx: &Arc<AppContext>, keybindings: KeyBindings, theme: &Theme) -> Self {
         let mut dialog = DialogOverlayComponent::new();
         dialog.set_dim_backdrop(true);
         dialog.set_auto_close_on_outside_click(true);
-        dialog.set_bg(term_wm_core::theme::NOIR.dialog_bg);
         dialog.set_size(70, 20);
         let viewer = ScrollViewComponent::new(MarkdownViewerComponent::new());
         let mut overlay = Self {
@@ -211,11 +222,7 @@ impl WmHelpOverlayComponent {
                 .replace("%MENU_SELECT%", &select)
                 .replace("%SUPER%", &super_key)
                 .replace("%HELP_MENU%", &help_label);
-            overlay
-                .content
-                .content
-                .borrow_mut()
-                .set_markdown(&s, &term_wm_core::theme::NOIR);
+            overlay.content.content.borrow_mut().set_markdown(&s, theme);
         }
         overlay
             .content
@@ -259,6 +266,7 @@ impl Default for WmHelpOverlayComponent {
         Self::new(
             &Arc::new(AppContext::new("unknown", "0.0.0")),
             KeyBindings::default(),
+            &WmConfig::default().theme,
         )
     }
 }
@@ -278,6 +286,7 @@ mod tests {
         let h = WmHelpOverlayComponent::new(
             &Arc::new(AppContext::new("test", "0.0.0")),
             KeyBindings::default(),
+            &term_wm_core::theme::NOIR,
         );
         let _ = h;
     }
@@ -290,6 +299,7 @@ mod tests {
                 env!("CARGO_PKG_VERSION"),
             )),
             KeyBindings::default(),
+            &term_wm_core::theme::NOIR,
         );
         overlay.show();
         use ratatui::buffer::Buffer;
@@ -367,6 +377,7 @@ mod tests {
         let mut overlay = WmHelpOverlayComponent::new(
             &Arc::new(AppContext::new("test", "0.0.0")),
             KeyBindings::default(),
+            &term_wm_core::theme::NOIR,
         );
         assert!(!overlay.visible(), "initially hidden");
 
@@ -382,6 +393,7 @@ mod tests {
         let mut overlay = WmHelpOverlayComponent::new(
             &Arc::new(AppContext::new("test", "0.0.0")),
             KeyBindings::default(),
+            &term_wm_core::theme::NOIR,
         );
         overlay.show();
         let ev = Event::Key(KeyEvent {
@@ -399,6 +411,7 @@ mod tests {
         let mut overlay = WmHelpOverlayComponent::new(
             &Arc::new(AppContext::new("test", "0.0.0")),
             KeyBindings::default(),
+            &term_wm_core::theme::NOIR,
         );
         overlay.dialog.set_auto_close_on_outside_click(true);
         overlay.show();
@@ -434,6 +447,7 @@ mod tests {
         let overlay = WmHelpOverlayComponent::new(
             &Arc::new(AppContext::new("test", "0.0.0")),
             KeyBindings::new(),
+            &term_wm_core::theme::NOIR,
         );
         assert_eq!(
             <WmHelpOverlayComponent as Overlay<TermWmAction>>::render_area(&overlay),
@@ -446,6 +460,7 @@ mod tests {
         let mut overlay = WmHelpOverlayComponent::new(
             &Arc::new(AppContext::new("test", "0.0.0")),
             KeyBindings::new(),
+            &term_wm_core::theme::NOIR,
         );
         overlay.dialog.set_visible(true);
         let area = LayoutRect {
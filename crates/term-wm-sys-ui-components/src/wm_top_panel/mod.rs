@@ -155,7 +155,7 @@ impl WmTopPanelComponent {
         ctx: &ComponentContext,
         registry: &mut term_wm_core::hitbox_registry::HitboxRegistry,
     ) {
-        let theme = ctx.config().theme;
+        let theme = &ctx.config().theme;
         let area = self.area;
         if area.width == 0 || area.height == 0 {
             return;
@@ -189,7 +189,7 @@ impl WmTopPanelComponent {
                 width: menu_width,
                 height: 1,
             };
-            self.menu.render(backend, menu_slot, self.menu_open, &theme);
+            self.menu.render(backend, menu_slot, self.menu_open, theme);
         }
 
         // Center region: [menu + gap, max_x - tiling_width - TILING_GAP).
@@ -212,7 +212,7 @@ impl WmTopPanelComponent {
         };
 
         if let Some(status) = self.status_line.clone() {
-            self.status.render(backend, bar_rect, &status, &theme);
+            self.status.render(backend, bar_rect, &status, theme);
         } else if self.focus_current.is_some() {
             let items: Vec<TabItem<WindowKey>> = self
                 .display_order
@@ -235,7 +235,7 @@ impl WmTopPanelComponent {
 
         // Tiling indicator (right edge).
         if tiling_width > 0 {
-            self.tiling.render(backend, area, &theme);
+            self.tiling.render(backend, area, theme);
         }
     }
 }
@@ -252,12 +252,12 @@ impl Component<TermWmAction> for WmTopPanelComponent {
         ctx: &ComponentContext,
         registry: &mut term_wm_core::hitbox_registry::HitboxRegistry,
     ) {
-        let theme = ctx.config().theme;
+        let theme = &ctx.config().theme;
         if !self.active {
             // Still render the tiling indicator even when inactive so the
             // label is visible and its rect is populated for clicks.
             self.bar.clear_drag_state();
-            self.tiling.render(backend, area, &theme);
+            self.tiling.render(backend, area, theme);
             return;
         }
         let app_name = ctx.app_name().to_string();
@@ -0,0 +1,313 @@
+//! End-to-end driver for a [`TermWmApp`].
+
+use std::io;
+use std::sync::{Mutex, Once, PoisonError};
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use term_wm::helpers::layout_rect_to_clipped_rect;
+use term_wm::term_wm_app::TermWmApp;
+use term_wm_core::actions::TermWmAction;
+use term_wm_core::components::{Component, NoopComponent};
+use term_wm_core::debug_event_flags;
+use term_wm_core::events::{Event, KeyCode, KeyModifiers, MouseEventKind};
+use term_wm_core::layout::{Direction, LayoutNode};
+use term_wm_core::runner::run_with_defaults;
+use term_wm_core::window::WindowKey;
+
+use crate::frame;
+use crate::headless::HeadlessRenderTarget;
+use crate::script::{self, ScriptedEventSource, is_script_finished};
+
+static RUN_LOCK: Mutex<()> = Mutex::new(());
+
+enum Step {
+    Event(Event),
+    Resize(u16, u16),
+}
+
+/// Drives a [`TermWmApp`] through the real event loop with scripted input
+/// and a [`HeadlessRenderTarget`], then asserts on what it drew.
+///
+/// Input methods only queue; [`run`](Self::run) replays the queue, one
+/// rendered frame per event, and the frame and app state it leaves behind
+/// are what the assertions see.
+///
+/// ```no_run
+/// # use term_wm::{AppContext, term_wm_app::TermWmApp};
+/// # use term_wm_testing::UiTest;
+/// let app = TermWmApp::<term_wm::NoopComponent>::new_custom(AppContext::new("demo", "0.1.0"));
+/// let mut ui = UiTest::new(app, 80, 24);
+/// ui.ctrl('a').run().unwrap();
+/// ui.assert_text("New Terminal");
+/// ```
+pub struct UiTest<C: Component<TermWmAction> + 'static = NoopComponent> {
+    app: TermWmApp<C>,
+    target: HeadlessRenderTarget,
+    steps: Vec<Step>,
+}
+
+impl<C: Component<TermWmAction> + 'static> UiTest<C> {
+    pub fn new(app: TermWmApp<C>, width: u16, height: u16) -> Self {
+        echo_panics_to_stderr();
+        Self {
+            app,
+            target: HeadlessRenderTarget::new(width, height),
+            steps: Vec::new(),
+        }
+    }
+
+    pub fn app(&mut self) -> &mut TermWmApp<C> {
+        &mut self.app
+    }
+
+    pub fn into_app(self) -> TermWmApp<C> {
+        self.app
+    }
+
+    // ── Input ───────────────────────────────────────────────────────────
+
+    pub fn send(&mut self, event: Event) -> &mut Self {
+        self.steps.push(Step::Event(event));
+        self
+    }
+
+    pub fn send_all(&mut self, events: impl IntoIterator<Item = Event>) -> &mut Self {
+        self.steps.extend(events.into_iter().map(Step::Event));
+        self
+    }
+
+    pub fn key(&mut self, code: KeyCode) -> &mut Self {
+        self.send(script::key(code, KeyModifiers::NONE))
+    }
+
+    pub fn key_with(&mut self, code: KeyCode, modifiers: KeyModifiers) -> &mut Self {
+        self.send(script::key(code, modifiers))
+    }
+
+    pub fn ctrl(&mut self, c: char) -> &mut Self {
+        self.send(script::ctrl(c))
+    }
+
+    /// One key press per character; `\n` presses Enter.
+    pub fn type_text(&mut self, text: &str) -> &mut Self {
+        self.send_all(script::text(text))
+    }
+
+    /// A bracketed paste.
+    pub fn paste(&mut self, text: &str) -> &mut Self {
+        self.send(Event::Paste(text.to_string()))
+    }
+
+    pub fn click(&mut self, column: u16, row: u16) -> &mut Self {
+        self.send_all(script::click(column, row))
+    }
+
+    /// Click the first cell of `needle` in the current frame.
+    ///
+    /// # Panics
+    /// If the text is not on screen.
+    pub fn click_text(&mut self, needle: &str) -> &mut Self {
+        let Some((column, row)) = frame::find_text(self.frame(), needle) else {
+            panic!(
+                "{needle:?} is not on screen:\n{}",
+                frame::frame_text(self.frame())
+            );
+        };
+        self.click(column, row)
+    }
+
+    pub fn drag(&mut self, from: (u16, u16), to: (u16, u16)) -> &mut Self {
+        self.send_all(script::drag(from, to))
+    }
+
+    pub fn scroll_up(&mut self, column: u16, row: u16) -> &mut Self {
+        self.send(script::mouse(MouseEventKind::ScrollUp, column, row))
+    }
+
+    pub fn scroll_down(&mut self, column: u16, row: u16) -> &mut Self {
+        self.send(script::mouse(MouseEventKind::ScrollDown, column, row))
+    }
+
+    /// Resize the screen; events queued before it are replayed at the old
+    /// size.
+    pub fn resize(&mut self, width: u16, height: u16) -> &mut Self {
+        self.steps.push(Step::Resize(width, height));
+        self
+    }
+
+    /// Replay the queued input through the app's event loop, drawing a
+    /// frame after every event. With nothing queued this draws one frame.
+    ///
+    /// Each run starts a fresh loop, so timers scheduled in one run (toast
+    /// dismissal, animations) do not fire in the next.
+    pub fn run(&mut self) -> io::Result<()> {
+        // The debug log and its panic/error flags are process-wide, so runs
+        // from tests executing in parallel take turns, and flags raised
+        // between runs (test-harness output on the app's redirected stderr)
+        // belong to no app.
+        let _turn = RUN_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        debug_event_flags::take_panic_pending();
+        debug_event_flags::take_error_pending();
+        let mut steps = std::mem::take(&mut self.steps).into_iter().peekable();
+        let mut resized = None;
+        loop {
+            let mut source = ScriptedEventSource::new(self.target.frame_counter());
+            source.extend(resized.take());
+            while let Some(Step::Event(event)) = steps.next_if(|s| matches!(s, Step::Event(_))) {
+                source.push(event);
+            }
+            match run_with_defaults(&mut self.target, &mut source, &mut self.app) {
+                // The app quit; whatever is left of the script is moot.
+                Ok(()) => return Ok(()),
+                Err(err) if !is_script_finished(&err) => return Err(err),
+                Err(_) => {}
+            }
+            let Some(Step::Resize(width, height)) = steps.next() else {
+                return Ok(());
+            };
+            self.target.resize(width, height);
+            resized = Some(Event::Resize(width, height));
+        }
+    }
+
+    // ── Inspection ──────────────────────────────────────────────────────
+
+    /// The frame drawn last.
+    pub fn frame(&self) -> &Buffer {
+        self.target.frame()
+    }
+
+    pub fn text(&self) -> String {
+        frame::frame_text(self.frame())
+    }
+
+    pub fn text_in(&self, area: Rect) -> String {
+        frame::text_in(self.frame(), area)
+    }
+
+    /// Style of one cell of the last frame.
+    ///
+    /// # Panics
+    /// If the cell is off screen.
+    pub fn style_at(&self, column: u16, row: u16) -> Style {
+        self.frame()
+            .cell((column, row))
+            .unwrap_or_else(|| panic!("({column}, {row}) is off screen"))
+            .style()
+    }
+
+    /// On-screen part of the window titled `title`, chrome included.
+    pub fn window_rect(&mut self, title: &str) -> Option<Rect> {
+        let key = self.window_key(title)?;
+        Some(layout_rect_to_clipped_rect(self.app.wm().full_region(key)))
+    }
+
+    pub fn window_key(&mut self, title: &str) -> Option<WindowKey> {
+        self.app
+            .wm()
+            .window_titles()
+            .into_iter()
+            .find_map(|(key, t)| (t == title).then_some(key))
+    }
+
+    pub fn focused_title(&mut self) -> String {
+        let wm = self.app.wm();
+        wm.window_title(wm.focused_window())
+    }
+
+    /// The tiling tree with windows named by title: `H(a, V(b, c))` for a
+    /// horizontal split whose right half is split vertically, `_` for an
+    /// empty slot. `None` when nothing is tiled.
+    pub fn layout_shape(&mut self) -> Option<String> {
+        let wm = self.app.wm();
+        let root = wm.managed_layout()?.root().clone();
+        Some(shape(&root, &|key| wm.window_title(key)))
+    }
+
+    // ── Assertions ──────────────────────────────────────────────────────
+
+    #[track_caller]
+    pub fn assert_text(&self, needle: &str) {
+        let text = self.text();
+        assert!(
+            text.contains(needle),
+            "{needle:?} is not on screen:\n{text}"
+        );
+    }
+
+    #[track_caller]
+    pub fn assert_no_text(&self, needle: &str) {
+        let text = self.text();
+        assert!(
+            !text.contains(needle),
+            "{needle:?} is unexpectedly on screen:\n{text}"
+        );
+    }
+
+    #[track_caller]
+    pub fn assert_text_in(&self, area: Rect, needle: &str) {
+        let text = self.text_in(area);
+        assert!(
+            text.contains(needle),
+            "{needle:?} is not in {area}:\n{text}"
+        );
+    }
+
+    /// Checks only what `expected` sets: its colors when `Some`, and that
+    /// its added modifiers are present.
+    #[track_caller]
+    pub fn assert_style_at(&self, column: u16, row: u16, expected: Style) {
+        let actual = self.style_at(column, row);
+        let matches = expected.fg.is_none_or(|fg| actual.fg == Some(fg))
+            && expected.bg.is_none_or(|bg| actual.bg == Some(bg))
+            && actual.add_modifier.contains(expected.add_modifier);
+        assert!(
+            matches,
+            "style at ({column}, {row}) is {actual:?}, expected {expected:?}"
+        );
+    }
+
+    #[track_caller]
+    pub fn assert_focused(&mut self, title: &str) {
+        assert_eq!(self.focused_title(), title, "focused window");
+    }
+
+    #[track_caller]
+    pub fn assert_layout(&mut self, expected: &str) {
+        assert_eq!(self.layout_shape().as_deref(), Some(expected), "layout");
+    }
+}
+
+/// `TermWmApp` installs a panic hook that only writes to its debug log,
+/// which would hide failed assertions; print them as the default hook would.
+fn echo_panics_to_stderr() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        let app_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            eprintln!("{info}");
+            app_hook(info);
+        }));
+    });
+}
+
+fn shape(node: &LayoutNode<WindowKey>, title: &dyn Fn(WindowKey) -> String) -> String {
+    match node {
+        LayoutNode::Leaf(key) => title(*key),
+        LayoutNode::Void(_) => "_".to_string(),
+        LayoutNode::Split {
+            direction,
+            children,
+            ..
+        } => {
+            let tag = match direction {
+                Direction::Horizontal => "H",
+                Direction::Vertical => "V",
+            };
+            let children: Vec<_> = children.iter().map(|c| shape(c, title)).collect();
+            format!("{tag}({})", children.join(", "))
+        }
+    }
+}
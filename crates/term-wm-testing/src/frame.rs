@@ -0,0 +1,95 @@
+//! Reading composed frames as text.
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use unicode_width::UnicodeWidthStr;
+
+/// The cells of row `y` between `x0` and `x1` (exclusive) as
+/// `(column, symbol)`, skipping the cells covered by a wide character.
+fn row_cells(buffer: &Buffer, y: u16, x0: u16, x1: u16) -> Vec<(u16, &str)> {
+    let mut cells = Vec::new();
+    let mut x = x0;
+    while x < x1 {
+        let Some(cell) = buffer.cell((x, y)) else {
+            break;
+        };
+        let symbol = cell.symbol();
+        cells.push((x, symbol));
+        x += (symbol.width() as u16).max(1);
+    }
+    cells
+}
+
+/// Text of `area` within `buffer`, one line per row with trailing blanks
+/// trimmed.
+pub fn text_in(buffer: &Buffer, area: Rect) -> String {
+    let area = area.intersection(buffer.area);
+    (area.top()..area.bottom())
+        .map(|y| {
+            let row: String = row_cells(buffer, y, area.left(), area.right())
+                .into_iter()
+                .map(|(_, symbol)| symbol)
+                .collect();
+            row.trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Text of the whole frame; the form snapshots are stored in.
+pub fn frame_text(buffer: &Buffer) -> String {
+    text_in(buffer, buffer.area)
+}
+
+/// `(column, row)` of the first cell where `needle` starts, scanning rows
+/// top to bottom. Matches never span rows.
+pub fn find_text(buffer: &Buffer, needle: &str) -> Option<(u16, u16)> {
+    let area = buffer.area;
+    (area.top()..area.bottom()).find_map(|y| {
+        let cells = row_cells(buffer, y, area.left(), area.right());
+        let mut row = String::new();
+        let mut starts = Vec::with_capacity(cells.len());
+        for (x, symbol) in cells {
+            starts.push((row.len(), x));
+            row.push_str(symbol);
+        }
+        let offset = row.find(needle)?;
+        starts
+            .iter()
+            .rev()
+            .find(|(start, _)| *start <= offset)
+            .map(|&(_, x)| (x, y))
+    })
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::widgets::Widget;
+
+    fn buffer(lines: &[&str]) -> Buffer {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 10, lines.len() as u16));
+        for (y, line) in lines.iter().enumerate() {
+            buffer.set_string(0, y as u16, line, ratatui::style::Style::default());
+        }
+        buffer
+    }
+
+    #[test]
+    fn text_is_trimmed_and_clipped_to_the_rect() {
+        let buffer = buffer(&["ab  cd", "", "xyz"]);
+        assert_eq!(frame_text(&buffer), "ab  cd\n\nxyz");
+        assert_eq!(text_in(&buffer, Rect::new(1, 0, 2, 3)), "b\n\nyz");
+    }
+
+    #[test]
+    fn find_reports_columns_past_wide_characters() {
+        let mut buffer = buffer(&["", "日本 ok"]);
+        assert_eq!(find_text(&buffer, "ok"), Some((5, 1)));
+        assert_eq!(find_text(&buffer, "本"), Some((2, 1)));
+        assert_eq!(find_text(&buffer, "nope"), None);
+        "ok".render(Rect::new(0, 0, 2, 1), &mut buffer);
+        assert_eq!(find_text(&buffer, "ok"), Some((0, 0)));
+    }
+}
@@ -0,0 +1,128 @@
+//! In-memory render target.
+
+use std::cell::Cell;
+use std::io;
+use std::rc::Rc;
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use term_wm_console::RatatuiBackend;
+use term_wm_core::io::RenderTarget;
+use term_wm_render::RenderBackend;
+
+/// Number of frames a [`HeadlessRenderTarget`] has drawn, shared with the
+/// [`ScriptedEventSource`](crate::ScriptedEventSource) so it can hold each
+/// event back until the previous one has been rendered.
+#[derive(Debug, Clone, Default)]
+pub struct FrameCounter(Rc<Cell<u64>>);
+
+impl FrameCounter {
+    pub fn get(&self) -> u64 {
+        self.0.get()
+    }
+
+    fn bump(&self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+/// A [`RenderTarget`] with no terminal behind it: every frame is composed
+/// into a fresh buffer of a fixed size and kept for inspection.
+///
+/// No capability degradation is applied, so frames hold exactly what the
+/// compositor drew.
+pub struct HeadlessRenderTarget {
+    backend: RatatuiBackend,
+    frames: FrameCounter,
+}
+
+impl HeadlessRenderTarget {
+    pub fn new(width: u16, height: u16) -> Self {
+        let area = Rect::new(0, 0, width, height);
+        Self {
+            backend: RatatuiBackend::new_simple(Buffer::empty(area), area),
+            frames: FrameCounter::default(),
+        }
+    }
+
+    /// The most recently drawn frame (blank before the first draw).
+    pub fn frame(&self) -> &Buffer {
+        &self.backend.buffer
+    }
+
+    pub fn area(&self) -> Rect {
+        self.backend.area
+    }
+
+    /// Change the screen size. The next frame is drawn at the new size; the
+    /// app learns about it from an `Event::Resize`.
+    pub fn resize(&mut self, width: u16, height: u16) {
+        let area = Rect::new(0, 0, width, height);
+        self.backend.area = area;
+        self.backend.buffer.resize(area);
+    }
+
+    pub fn frame_count(&self) -> u64 {
+        self.frames.get()
+    }
+
+    pub fn frame_counter(&self) -> FrameCounter {
+        self.frames.clone()
+    }
+}
+
+impl RenderTarget for HeadlessRenderTarget {
+    fn enter(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn exit(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn draw<F>(&mut self, f: F) -> io::Result<()>
+    where
+        F: FnOnce(&mut dyn RenderBackend),
+    {
+        self.backend.buffer.reset();
+        f(&mut self.backend);
+        self.frames.bump();
+        Ok(())
+    }
+
+    fn repair(&mut self) -> io::Result<()> {
+        self.backend.buffer.reset();
+        Ok(())
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::widgets::Widget;
+
+    #[test]
+    fn draw_replaces_the_frame_and_counts_it() {
+        let mut target = HeadlessRenderTarget::new(6, 2);
+        let counter = target.frame_counter();
+        target
+            .draw(|backend| {
+                let backend = backend
+                    .as_any_mut()
+                    .downcast_mut::<RatatuiBackend>()
+                    .unwrap();
+                let area = backend.area;
+                "hello".render(area, &mut backend.buffer);
+            })
+            .unwrap();
+        assert_eq!(counter.get(), 1);
+        assert_eq!(target.frame().cell((0, 0)).unwrap().symbol(), "h");
+
+        target.resize(3, 1);
+        target.draw(|_| {}).unwrap();
+        assert_eq!(counter.get(), 2);
+        assert_eq!(target.frame().area, Rect::new(0, 0, 3, 1));
+        assert_eq!(target.frame().cell((0, 0)).unwrap().symbol(), " ");
+    }
+}
@@ -0,0 +1,44 @@
+//! Headless end-to-end testing for term-wm apps.
+//!
+//! [`UiTest`] runs a [`TermWmApp`](term_wm::term_wm_app::TermWmApp) through
+//! the real event loop, feeding it scripted keys, mouse, resize and paste
+//! events from a [`ScriptedEventSource`] and drawing into a
+//! [`HeadlessRenderTarget`] — no PTY, no terminal, no timing flakiness.
+//! Assertions read the composed frame (text in a rect, style at a cell) and
+//! the window manager (focused window, tiling tree), and
+//! [`assert_frame_snapshot!`] stores whole frames with insta.
+
+pub mod driver;
+pub mod frame;
+pub mod headless;
+pub mod script;
+
+pub use driver::UiTest;
+pub use headless::{FrameCounter, HeadlessRenderTarget};
+pub use script::ScriptedEventSource;
+
+#[doc(hidden)]
+pub use insta;
+
+/// Snapshot the last frame of a [`UiTest`] as text with insta, or only the
+/// part of it inside a rect.
+///
+/// ```no_run
+/// # use ratatui::layout::Rect;
+/// # use term_wm::{AppContext, term_wm_app::TermWmApp};
+/// # use term_wm_testing::{UiTest, assert_frame_snapshot};
+/// # let app = TermWmApp::<term_wm::NoopComponent>::new_custom(AppContext::new("demo", "0.1.0"));
+/// let mut ui = UiTest::new(app, 80, 24);
+/// ui.run().unwrap();
+/// assert_frame_snapshot!(ui);
+/// assert_frame_snapshot!(ui, Rect::new(0, 0, 80, 23));
+/// ```
+#[macro_export]
+macro_rules! assert_frame_snapshot {
+    ($ui:expr $(,)?) => {
+        $crate::insta::assert_snapshot!($ui.text())
+    };
+    ($ui:expr, $area:expr $(,)?) => {
+        $crate::insta::assert_snapshot!($ui.text_in($area))
+    };
+}
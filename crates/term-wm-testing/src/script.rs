@@ -0,0 +1,251 @@
+//! Scripted input.
+
+use std::collections::VecDeque;
+use std::io;
+use std::time::{Duration, Instant};
+
+use term_wm_core::events::{
+    Event, KeyCode, KeyEvent, KeyKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use term_wm_core::io::EventSource;
+
+use crate::headless::FrameCounter;
+
+/// How long [`ScriptedEventSource`] waits for a frame before giving up.
+const FRAME_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Error payload returned once the script is exhausted; see
+/// [`is_script_finished`].
+#[derive(Debug)]
+struct ScriptFinished;
+
+impl std::fmt::Display for ScriptFinished {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("input script finished")
+    }
+}
+
+impl std::error::Error for ScriptFinished {}
+
+/// True for the error a [`ScriptedEventSource`] ends the event loop with
+/// once every event has been delivered and rendered.
+pub fn is_script_finished(err: &io::Error) -> bool {
+    err.get_ref()
+        .is_some_and(|inner| inner.is::<ScriptFinished>())
+}
+
+/// An [`EventSource`] that replays a queue of events.
+///
+/// Each event is held back until the frame for the previous one has been
+/// drawn (tracked through the render target's [`FrameCounter`]), so every
+/// event sees the layout and hitboxes its predecessor produced — the same
+/// ordering a human typing at a terminal gets. When the queue is empty and
+/// the last frame is drawn, `read` fails with an error that
+/// [`is_script_finished`] recognizes, ending the loop.
+pub struct ScriptedEventSource {
+    queue: VecDeque<Event>,
+    frames: FrameCounter,
+    /// Frame count and time when the last event was delivered.
+    delivered_at: (u64, Instant),
+    redraw: bool,
+}
+
+impl ScriptedEventSource {
+    pub fn new(frames: FrameCounter) -> Self {
+        let delivered_at = (frames.get(), Instant::now());
+        Self {
+            queue: VecDeque::new(),
+            frames,
+            delivered_at,
+            redraw: false,
+        }
+    }
+
+    pub fn push(&mut self, event: Event) {
+        self.queue.push_back(event);
+    }
+
+    pub fn extend(&mut self, events: impl IntoIterator<Item = Event>) {
+        self.queue.extend(events);
+    }
+
+    pub fn pending(&self) -> usize {
+        self.queue.len()
+    }
+
+    fn settled(&self) -> bool {
+        self.frames.get() > self.delivered_at.0
+    }
+}
+
+impl EventSource for ScriptedEventSource {
+    fn poll(&mut self, timeout: Duration) -> io::Result<bool> {
+        if !self.settled() {
+            // The runner only draws from its idle branch, which needs a
+            // `false` here; nap briefly so the frame pacer's deadline passes.
+            if self.delivered_at.1.elapsed() >= FRAME_TIMEOUT {
+                return Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    "no frame was drawn after a scripted event",
+                ));
+            }
+            std::thread::sleep(timeout.min(Duration::from_millis(1)));
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    fn read(&mut self) -> io::Result<Event> {
+        let event = self
+            .queue
+            .pop_front()
+            .ok_or_else(|| io::Error::other(ScriptFinished))?;
+        self.delivered_at = (self.frames.get(), Instant::now());
+        Ok(event)
+    }
+
+    fn next_key(&mut self) -> io::Result<KeyEvent> {
+        loop {
+            if let Event::Key(key) = self.read()? {
+                return Ok(key);
+            }
+        }
+    }
+
+    fn next_mouse(&mut self) -> io::Result<MouseEvent> {
+        loop {
+            if let Event::Mouse(mouse) = self.read()? {
+                return Ok(mouse);
+            }
+        }
+    }
+
+    fn request_redraw(&mut self) {
+        self.redraw = true;
+    }
+
+    fn take_redraw_request(&mut self) -> bool {
+        std::mem::take(&mut self.redraw)
+    }
+}
+
+/// A key press.
+pub fn key(code: KeyCode, modifiers: KeyModifiers) -> Event {
+    Event::Key(KeyEvent::new(code, modifiers, KeyKind::Press))
+}
+
+/// A Ctrl+`c` press.
+pub fn ctrl(c: char) -> Event {
+    key(
+        KeyCode::Char(c),
+        KeyModifiers {
+            control: true,
+            ..KeyModifiers::NONE
+        },
+    )
+}
+
+/// One key press per character, `\n` as Enter.
+pub fn text(text: &str) -> impl Iterator<Item = Event> + '_ {
+    text.chars().map(|c| match c {
+        '\n' => key(KeyCode::Enter, KeyModifiers::NONE),
+        c => key(KeyCode::Char(c), KeyModifiers::NONE),
+    })
+}
+
+pub fn mouse(kind: MouseEventKind, column: u16, row: u16) -> Event {
+    Event::Mouse(MouseEvent {
+        kind,
+        modifiers: KeyModifiers::NONE,
+        column,
+        row,
+    })
+}
+
+/// A left-button press and release at one cell.
+pub fn click(column: u16, row: u16) -> [Event; 2] {
+    [
+        mouse(MouseEventKind::Press(MouseButton::Left), column, row),
+        mouse(MouseEventKind::Release(MouseButton::Left), column, row),
+    ]
+}
+
+/// A left-button drag from `from` to `to` (each `(column, row)`), moving
+/// one cell at a time along each axis.
+pub fn drag(from: (u16, u16), to: (u16, u16)) -> Vec<Event> {
+    let mut events = vec![mouse(
+        MouseEventKind::Press(MouseButton::Left),
+        from.0,
+        from.1,
+    )];
+    let (mut column, mut row) = from;
+    while (column, row) != to {
+        column = step_toward(column, to.0);
+        row = step_toward(row, to.1);
+        events.push(mouse(MouseEventKind::Drag(MouseButton::Left), column, row));
+    }
+    events.push(mouse(
+        MouseEventKind::Release(MouseButton::Left),
+        to.0,
+        to.1,
+    ));
+    events
+}
+
+fn step_toward(from: u16, to: u16) -> u16 {
+    match from.cmp(&to) {
+        std::cmp::Ordering::Less => from + 1,
+        std::cmp::Ordering::Greater => from - 1,
+        std::cmp::Ordering::Equal => from,
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_wait_for_a_frame_and_the_end_is_recognizable() {
+        let mut target = crate::HeadlessRenderTarget::new(1, 1);
+        let mut source = ScriptedEventSource::new(target.frame_counter());
+        source.extend(text("a\n"));
+
+        // Nothing drawn yet: the first event is held back.
+        assert!(!source.poll(Duration::ZERO).unwrap());
+        term_wm_core::io::RenderTarget::draw(&mut target, |_| {}).unwrap();
+        assert!(source.poll(Duration::ZERO).unwrap());
+        assert!(matches!(source.read().unwrap(), Event::Key(k) if k.code == KeyCode::Char('a')));
+        assert!(
+            !source.poll(Duration::ZERO).unwrap(),
+            "waits for the next frame"
+        );
+
+        term_wm_core::io::RenderTarget::draw(&mut target, |_| {}).unwrap();
+        assert!(matches!(source.read().unwrap(), Event::Key(k) if k.code == KeyCode::Enter));
+        term_wm_core::io::RenderTarget::draw(&mut target, |_| {}).unwrap();
+        assert!(source.poll(Duration::ZERO).unwrap());
+        assert!(is_script_finished(&source.read().unwrap_err()));
+    }
+
+    #[test]
+    fn drag_walks_cell_by_cell() {
+        let events = drag((0, 0), (2, 1));
+        let cells: Vec<_> = events
+            .iter()
+            .map(|e| match e {
+                Event::Mouse(m) => (m.kind, m.column, m.row),
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(
+            cells,
+            [
+                (MouseEventKind::Press(MouseButton::Left), 0, 0),
+                (MouseEventKind::Drag(MouseButton::Left), 1, 1),
+                (MouseEventKind::Drag(MouseButton::Left), 2, 1),
+                (MouseEventKind::Release(MouseButton::Left), 2, 1),
+            ]
+        );
+    }
+}
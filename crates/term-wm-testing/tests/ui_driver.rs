@@ -0,0 +1,128 @@
+//! Drives a small two-window app end to end through `UiTest`.
+
+use std::collections::VecDeque;
+
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use term_wm::Rect as LayoutRect;
+use term_wm::actions::{EventResult, TermWmAction};
+use term_wm::component_context::ComponentContext;
+use term_wm::components::AppRootComponent;
+use term_wm::events::{Event, KeyCode};
+use term_wm::helpers::{downcast_ratatui, layout_rect_to_clipped_rect};
+use term_wm::hitbox_registry::HitboxRegistry;
+use term_wm::term_wm_app::TermWmApp;
+use term_wm::window::WindowKey;
+use term_wm::{AppContext, Component, RenderBackend};
+use term_wm_testing::{UiTest, assert_frame_snapshot};
+
+/// Counts `+` presses and remembers the last paste.
+#[derive(Default)]
+struct Counter {
+    count: u32,
+    pasted: String,
+}
+
+impl Component<TermWmAction> for Counter {
+    fn render(
+        &mut self,
+        backend: &mut dyn RenderBackend,
+        area: LayoutRect,
+        _ctx: &ComponentContext,
+        _registry: &mut HitboxRegistry,
+    ) {
+        let rect = layout_rect_to_clipped_rect(area);
+        let text = format!("count={} pasted={}", self.count, self.pasted);
+        text.as_str()
+            .render(rect, &mut downcast_ratatui(backend).buffer);
+    }
+
+    fn handle_events(
+        &mut self,
+        event: &Event,
+        _ctx: &ComponentContext,
+    ) -> EventResult<TermWmAction> {
+        match event {
+            Event::Key(key) if key.code == KeyCode::Char('+') => {
+                self.count += 1;
+                EventResult::Consumed
+            }
+            Event::Paste(text) => {
+                self.pasted = text.clone();
+                EventResult::Consumed
+            }
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn update(
+        &mut self,
+        _action: TermWmAction,
+        _ctx: &ComponentContext,
+        _actions: &mut VecDeque<(WindowKey, TermWmAction)>,
+    ) {
+    }
+}
+
+fn two_counters() -> UiTest<Counter> {
+    let mut app: TermWmApp<Counter> = TermWmApp::new_custom(AppContext::new("demo", "0.1.0"));
+    for title in ["Left", "Right"] {
+        let key = app.open_window(AppRootComponent::Custom(Counter::default()));
+        app.set_window_title(key, title);
+    }
+    UiTest::new(app, 100, 20)
+}
+
+#[test]
+fn keys_paste_clicks_and_resize_reach_the_app() {
+    let mut ui = two_counters();
+    ui.run().expect("first frame");
+    ui.assert_layout("H(Left, Right)");
+    ui.assert_focused("Right");
+
+    ui.type_text("++").paste("hi").run().expect("typing");
+    let right = ui.window_rect("Right").expect("right window");
+    ui.assert_text_in(right, "count=2 pasted=hi");
+
+    let left = ui.window_rect("Left").expect("left window");
+    ui.click(left.x + left.width / 2, left.y + left.height / 2)
+        .type_text("+")
+        .run()
+        .expect("click");
+    ui.assert_focused("Left");
+    ui.assert_text_in(left, "count=1 pasted=");
+
+    // Narrow screens switch to monocle mode, showing only the focused window.
+    ui.resize(60, 16).run().expect("resize");
+    assert_eq!(ui.frame().area.width, 60);
+    ui.assert_text("Monocle mode (auto): enabled");
+    ui.assert_text("count=1");
+    ui.assert_no_text("count=2");
+}
+
+#[test]
+fn frames_snapshot_as_text() {
+    let mut ui = two_counters();
+    ui.type_text("+").run().expect("first frame");
+    // The bottom panel names the host OS; keep the snapshot portable.
+    let area = ui.frame().area;
+    assert_frame_snapshot!(
+        ui,
+        Rect {
+            height: area.height - 1,
+            ..area
+        }
+    );
+}
+
+#[test]
+fn palette_entries_are_clickable() {
+    let mut ui = two_counters();
+    ui.ctrl('a').run().expect("open palette");
+    ui.assert_text("New Terminal");
+    ui.assert_focused("Right");
+
+    ui.click_text("Switch to: Left").run().expect("pick entry");
+    ui.assert_no_text("New Terminal");
+    ui.assert_focused("Left");
+}
@@ -0,0 +1,195 @@
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+use std::time::Duration;
+
+use term_wm_core::task_scheduler::{AppTask, TaskHandle, TaskId};
+
+/// Interval between animation frames (spinner glyphs, indeterminate bars).
+pub const DEFAULT_FRAME_INTERVAL: Duration = Duration::from_millis(100);
+
+thread_local! {
+    static GLOBAL: AnimationClock = AnimationClock::new();
+}
+
+/// A shared frame counter for animated components.
+///
+/// Once [attached](Self::attach) to the app scheduler, the clock advances on
+/// a repeating task — but only while something animates: animated components
+/// call [`animate`](Self::animate) when they render, which starts the task,
+/// and a tick that finds no `animate` call since the previous one cancels it.
+/// With nothing animating there is no pending task, so the event loop (and
+/// the power profile) can go idle.
+///
+/// Components use [`AnimationClock::global`] unless given a clock;
+/// `TermWmApp` attaches the global clock to its scheduler. A detached clock
+/// only moves when [`tick`](Self::tick) is called, which keeps tests
+/// deterministic.
+#[derive(Clone)]
+pub struct AnimationClock {
+    inner: Rc<ClockInner>,
+}
+
+struct ClockInner {
+    frame: Cell<u64>,
+    /// Whether an animated component rendered since the last tick.
+    wanted: Cell<bool>,
+    interval: Cell<Duration>,
+    driver: RefCell<Option<Driver>>,
+}
+
+/// Starts and cancels the repeating tick task on the attached scheduler.
+struct Driver {
+    start: Box<dyn Fn(Weak<ClockInner>, Duration) -> TaskId>,
+    cancel: Box<dyn Fn(TaskId)>,
+    task: Option<TaskId>,
+}
+
+impl AnimationClock {
+    pub fn new() -> Self {
+        Self {
+            inner: Rc::new(ClockInner {
+                frame: Cell::new(0),
+                wanted: Cell::new(false),
+                interval: Cell::new(DEFAULT_FRAME_INTERVAL),
+                driver: RefCell::new(None),
+            }),
+        }
+    }
+
+    /// The clock shared by components on this thread.
+    pub fn global() -> Self {
+        GLOBAL.with(Clone::clone)
+    }
+
+    pub fn with_interval(self, interval: Duration) -> Self {
+        self.inner.interval.set(interval);
+        self
+    }
+
+    /// Drive the clock from `handle`'s scheduler, replacing any previous
+    /// attachment.
+    pub fn attach<A: 'static>(&self, handle: &TaskHandle<AppTask<A>>) {
+        self.detach();
+        let start_handle = handle.clone();
+        let cancel_handle = handle.clone();
+        *self.inner.driver.borrow_mut() = Some(Driver {
+            start: Box::new(move |clock, interval| {
+                let task = AppTask::new(move |_: &mut A| {
+                    if let Some(inner) = clock.upgrade() {
+                        AnimationClock { inner }.tick();
+                    }
+                });
+                start_handle.schedule_repeating(interval, false, task)
+            }),
+            cancel: Box::new(move |id| cancel_handle.cancel(id)),
+            task: None,
+        });
+    }
+
+    /// Stop driving the clock from a scheduler.
+    pub fn detach(&self) {
+        if let Some(mut driver) = self.inner.driver.borrow_mut().take()
+            && let Some(id) = driver.task.take()
+        {
+            (driver.cancel)(id);
+        }
+    }
+
+    /// Time between ticks while running.
+    pub fn interval(&self) -> Duration {
+        self.inner.interval.get()
+    }
+
+    /// The current frame number.
+    pub fn frame(&self) -> u64 {
+        self.inner.frame.get()
+    }
+
+    /// Whether the repeating tick task is scheduled.
+    pub fn is_running(&self) -> bool {
+        self.inner
+            .driver
+            .borrow()
+            .as_ref()
+            .is_some_and(|driver| driver.task.is_some())
+    }
+
+    /// Request further frames; called by animated components as they render.
+    pub fn animate(&self) {
+        self.inner.wanted.set(true);
+        let mut driver = self.inner.driver.borrow_mut();
+        if let Some(driver) = driver.as_mut()
+            && driver.task.is_none()
+        {
+            driver.task = Some((driver.start)(
+                Rc::downgrade(&self.inner),
+                self.inner.interval.get(),
+            ));
+        }
+    }
+
+    /// Advance one frame. Cancels the tick task when nothing has animated
+    /// since the previous tick.
+    pub fn tick(&self) {
+        self.inner.frame.set(self.inner.frame.get().wrapping_add(1));
+        if self.inner.wanted.replace(false) {
+            return;
+        }
+        if let Some(driver) = self.inner.driver.borrow_mut().as_mut()
+            && let Some(id) = driver.task.take()
+        {
+            (driver.cancel)(id);
+        }
+    }
+}
+
+impl Default for AnimationClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for AnimationClock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnimationClock")
+            .field("frame", &self.frame())
+            .field("running", &self.is_running())
+            .finish()
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use term_wm_core::task_scheduler::TaskScheduler;
+
+    #[test]
+    fn tick_task_runs_only_while_something_animates() {
+        let scheduler = TaskScheduler::<AppTask<()>>::new();
+        let handle = scheduler.handle();
+        let clock = AnimationClock::new().with_interval(Duration::from_millis(1));
+        clock.attach(&handle);
+        assert!(!handle.has_pending());
+
+        clock.animate();
+        assert!(clock.is_running());
+        std::thread::sleep(Duration::from_millis(2));
+        let fired = handle.drain_expired_once();
+        assert_eq!(fired.len(), 1);
+        fired[0].1.run(&mut ());
+        assert_eq!(clock.frame(), 1);
+        assert!(clock.is_running(), "animated since the last tick");
+
+        // No render called `animate` during this frame: the task stops.
+        clock.tick();
+        assert_eq!(clock.frame(), 2);
+        assert!(!clock.is_running());
+        assert!(!handle.has_pending());
+
+        clock.animate();
+        assert!(handle.has_pending());
+        clock.detach();
+        assert!(!handle.has_pending());
+    }
+}
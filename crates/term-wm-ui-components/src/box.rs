@@ -4,7 +4,7 @@ use ratatui::style::Style;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Paragraph, Widget};
 use term_wm_core::actions::{EventResult, TermWmAction};
-use term_wm_core::components::{Component, ComponentContext, SelectionStatus};
+use term_wm_core::components::{Component, ComponentContext, HostCursor, SelectionStatus};
 use term_wm_core::events::{Event, MouseEventKind};
 use term_wm_core::hitbox_registry::{HitboxId, HitboxRegistry};
 use term_wm_core::theme::Color;
@@ -264,6 +264,10 @@ impl<C: Component<TermWmAction>> Component<TermWmAction> for BoxComponent<C> {
         self.content.take_alternate_screen_transition()
     }
 
+    fn host_cursor(&self) -> Option<HostCursor> {
+        self.content.host_cursor()
+    }
+
     fn take_teardown_parts(
         &mut self,
     ) -> Option<(
@@ -16,6 +16,10 @@ use term_wm_layout_engine::LayoutRect;
 use crate::helpers::{color_to_ratatui, layout_rect_to_clipped_rect, safe_set_string};
 use crate::menu::MenuComponent;
 use crate::scroll_view::{ScrollKeyMode, ScrollViewComponent};
+use crate::text_input::{TextInputComponent, TextInputStyle};
+
+/// Prompt shown in the empty search bar.
+const SEARCH_PLACEHOLDER: &str = "[type to search]";
 
 /// Placeholder shown under the search bar when the query filters out every
 /// item, matching the bracket style of the `[type to search]` prompt.
@@ -49,12 +53,12 @@ pub enum PaletteDisplayNode {
 
 /// A universal, fuzzy-searchable Command Palette component.
 ///
-/// The search bar is rendered directly; the item list is wrapped in a
+/// The search bar is a [`TextInputComponent`] (so it gets cursor movement,
+/// word kills, undo and paste for free); the item list is wrapped in a
 /// `ScrollViewComponent<MenuComponent>` which handles rendering, hover,
 /// click, and scroll.
 pub struct CommandPaletteComponent {
-    pub query: String,
-    pub cursor: usize,
+    input: TextInputComponent,
     pub filtered_items: Vec<PaletteItem>,
     /// Full display list for the next render (includes separators when
     /// the query is empty, items-only during active search).
@@ -115,8 +119,7 @@ impl CommandPaletteComponent {
         list_scroll.set_keyboard_mode(ScrollKeyMode::PaginationOnly);
 
         Self {
-            query: String::new(),
-            cursor: 0,
+            input: TextInputComponent::new().with_placeholder(SEARCH_PLACEHOLDER),
             filtered_items: Vec::new(),
             display_nodes: Vec::new(),
             selected: 0,
@@ -158,6 +161,21 @@ impl CommandPaletteComponent {
             .unwrap_or(item_idx)
     }
 
+    /// The current search query.
+    pub fn query(&self) -> &str {
+        self.input.value()
+    }
+
+    pub fn set_query(&mut self, query: impl Into<String>) {
+        self.input.set_value(query);
+        self.input.take_changed();
+        self.query_dirty = true;
+    }
+
+    pub fn clear_query(&mut self) {
+        self.set_query(String::new());
+    }
+
     pub fn mark_data_dirty(&mut self) {
         self.data_dirty = true;
     }
@@ -249,7 +267,7 @@ impl CommandPaletteComponent {
             })
             .collect();
 
-        self.filtered_items = if self.query.is_empty() {
+        self.filtered_items = if self.input.value().is_empty() {
             // Empty query: all items in cache order
             item_indices
                 .iter()
@@ -273,7 +291,7 @@ impl CommandPaletteComponent {
                 })
                 .collect()
         } else {
-            let indices = fmatch.score(&self.query, &searchable);
+            let indices = fmatch.score(self.input.value(), &searchable);
             indices
                 .iter()
                 .filter_map(|&idx| {
@@ -309,7 +327,7 @@ impl CommandPaletteComponent {
             .min(self.filtered_items.len().saturating_sub(1));
 
         // Build display_nodes for rendering
-        if self.query.is_empty() {
+        if self.input.value().is_empty() {
             self.display_nodes = self
                 .display_cache
                 .iter()
@@ -417,13 +435,13 @@ impl CommandPaletteComponent {
             }
         };
 
-        if self.query.is_empty() {
+        if self.input.value().is_empty() {
             self.filtered_items = item_indices
                 .iter()
                 .filter_map(|&i| resolve_item(i))
                 .collect();
         } else {
-            let indices = fmatch.score(&self.query, &searchable);
+            let indices = fmatch.score(self.input.value(), &searchable);
             self.filtered_items = indices
                 .iter()
                 .filter_map(|&idx| {
@@ -438,12 +456,14 @@ impl CommandPaletteComponent {
             let wb = mru.weight(&b.stable_id);
             wb.partial_cmp(&wa).unwrap_or(std::cmp::Ordering::Equal)
         });
+        let query_items = self.query_argument_items();
+        self.filtered_items.extend(query_items);
         self.selected = self
             .selected
             .min(self.filtered_items.len().saturating_sub(1));
 
         // Build display_nodes for rendering
-        if self.query.is_empty() {
+        if self.input.value().is_empty() {
             self.display_nodes = self
                 .display_cache
                 .iter()
@@ -477,8 +497,37 @@ impl CommandPaletteComponent {
         self.query_dirty = false;
     }
 
+    /// Entries that take the search query as their argument, listed after
+    /// the matches whatever the query is (it names something new, so it
+    /// need not match their label).
+    fn query_argument_items(&self) -> Vec<PaletteItem> {
+        self.display_cache
+            .iter()
+            .filter_map(|entry| match entry {
+                DisplayCacheEntry::Item {
+                    stable_id,
+                    description,
+                    icon,
+                    action,
+                    ..
+                } => {
+                    let action = action.with_query_argument(self.input.value())?;
+                    Some(PaletteItem {
+                        stable_id: format!("{stable_id}:query"),
+                        display_name: action.to_string(),
+                        description: description.clone(),
+                        action,
+                        icon: *icon,
+                        disabled: false,
+                    })
+                }
+                DisplayCacheEntry::Separator => None,
+            })
+            .collect()
+    }
+
     fn render_search_bar(
-        &self,
+        &mut self,
         buffer: &mut ratatui::buffer::Buffer,
         area: Rect,
         theme: &term_wm_core::theme::Theme,
@@ -497,7 +546,7 @@ impl CommandPaletteComponent {
         }
 
         let prefix = " > ";
-        let inner_w = (area.width as usize).saturating_sub(prefix.len());
+        let inner_w = area.width.saturating_sub(prefix.len() as u16);
         for (i, ch) in prefix.chars().enumerate() {
             if let Some(cell) = buffer.cell_mut((area.x + i as u16, area.y)) {
                 cell.set_symbol(&ch.to_string());
@@ -505,30 +554,23 @@ impl CommandPaletteComponent {
             }
         }
 
-        let query_display: String = self.query.chars().take(inner_w).collect();
-        if query_display.is_empty() {
-            let placeholder = "[type to search]";
-            let style = Style::default()
-                .bg(color_to_ratatui(theme.panel_active_bg))
-                .fg(color_to_ratatui(theme.panel_inactive_fg));
-            let text: String = placeholder.chars().take(inner_w).collect();
-            safe_set_string(
-                buffer,
-                area,
-                area.x + prefix.len() as u16,
-                area.y,
-                &text,
-                style,
-            );
-        } else {
-            let x0 = area.x + prefix.len() as u16;
-            for (i, ch) in query_display.chars().enumerate() {
-                if let Some(cell) = buffer.cell_mut((x0 + i as u16, area.y)) {
-                    cell.set_symbol(&ch.to_string());
-                    cell.set_style(search_style);
-                }
-            }
-        }
+        let input_area = Rect {
+            x: area.x + prefix.len() as u16,
+            y: area.y,
+            width: inner_w,
+            height: 1,
+        };
+        let placeholder = Style::default()
+            .bg(color_to_ratatui(theme.panel_active_bg))
+            .fg(color_to_ratatui(theme.panel_inactive_fg));
+        let themed = TextInputStyle::from_theme(theme);
+        self.input.set_style(Some(TextInputStyle {
+            text: search_style,
+            placeholder,
+            error: search_style,
+            ..themed
+        }));
+        self.input.render_into(buffer, input_area, true, theme);
     }
 
     /// Render a dim placeholder into the first list row when no items match,
@@ -660,6 +702,11 @@ impl Component<TermWmAction> for CommandPaletteComponent {
             return result;
         }
 
+        if let Event::Paste(text) = event {
+            self.paste(text);
+            return EventResult::Consumed;
+        }
+
         // Keyboard
         let Event::Key(key) = event else {
             return EventResult::Ignored;
@@ -694,30 +741,38 @@ impl Component<TermWmAction> for CommandPaletteComponent {
             .keybindings
             .matches(TermWmAction::ClearCommandPaletteQuery, key)
         {
-            if self.query.is_empty() {
+            if self.input.value().is_empty() {
                 return EventResult::Action(TermWmAction::CloseMenu);
             }
-            self.query.clear();
-            self.cursor = 0;
-            self.query_dirty = true;
+            self.clear_query();
             return EventResult::Consumed;
         }
 
-        // Char input for search bar
-        match key.code {
-            KeyCode::Esc => EventResult::Action(TermWmAction::CloseMenu),
-            KeyCode::Char(ch) if !key.modifiers.control => {
-                self.query.push(ch);
-                self.query_dirty = true;
-                EventResult::Consumed
-            }
-            KeyCode::Backspace => {
-                self.query.pop();
-                self.query_dirty = true;
-                EventResult::Consumed
-            }
-            _ => EventResult::Ignored,
+        if key.code == KeyCode::Esc {
+            return EventResult::Action(TermWmAction::CloseMenu);
+        }
+        // The SUPER key (`Ctrl+A` by default) belongs to the WM while the
+        // palette is open; don't let the search bar read it as "line start".
+        let keybindings = &ctx.config().keybindings;
+        if keybindings.matches(TermWmAction::OpenCommandPalette, key)
+            || keybindings.matches(TermWmAction::SendSuperKeyToFocusedWindow, key)
+        {
+            return EventResult::Ignored;
+        }
+        // Everything else edits the search bar.
+        let result = self.input.handle_key(key);
+        if self.input.take_changed() {
+            self.query_dirty = true;
+        }
+        result
+    }
+
+    fn paste(&mut self, text: &str) -> bool {
+        let changed = self.input.insert_text(text);
+        if self.input.take_changed() {
+            self.query_dirty = true;
         }
+        changed
     }
 
     fn update(
@@ -881,13 +936,13 @@ mod tests {
         });
         palette.handle_events(&event, &ctx);
         assert!(palette.query_dirty);
-        assert_eq!(palette.query, "n");
+        assert_eq!(palette.query(), "n");
     }
 
     #[test]
     fn backspace_removes_from_query() {
         let mut palette = make_palette_with_items();
-        palette.query = "abc".to_string();
+        palette.set_query("abc");
         palette.query_dirty = false;
         let ctx = ComponentContext::new(true);
         let event = Event::Key(KeyEvent {
@@ -896,7 +951,32 @@ mod tests {
             kind: KeyKind::Press,
         });
         palette.handle_events(&event, &ctx);
-        assert_eq!(palette.query, "ab");
+        assert_eq!(palette.query(), "ab");
+        assert!(palette.query_dirty);
+    }
+
+    #[test]
+    fn search_bar_supports_word_kill_and_paste() {
+        let mut palette = make_palette_with_items();
+        palette.set_query("new term");
+        palette.query_dirty = false;
+        let ctx = ComponentContext::new(true);
+        let ctrl_w = Event::Key(KeyEvent {
+            code: KeyCode::Char('w'),
+            modifiers: KeyModifiers {
+                control: true,
+                ..KeyModifiers::NONE
+            },
+            kind: KeyKind::Press,
+        });
+        assert!(palette.handle_events(&ctrl_w, &ctx).is_consumed());
+        assert_eq!(palette.query(), "new ");
+        assert!(palette.query_dirty);
+
+        palette.query_dirty = false;
+        let paste = Event::Paste("window\n".to_string());
+        assert!(palette.handle_events(&paste, &ctx).is_consumed());
+        assert_eq!(palette.query(), "new window ");
         assert!(palette.query_dirty);
     }
 
@@ -970,15 +1050,14 @@ mod tests {
             result,
             EventResult::Action(TermWmAction::CloseMenu)
         ));
-        assert!(palette.query.is_empty());
+        assert!(palette.query().is_empty());
         assert!(!palette.query_dirty);
     }
 
     #[test]
     fn ctrl_c_clears_populated_query() {
         let mut palette = make_palette_with_items();
-        palette.query = "new t".to_string();
-        palette.cursor = 6;
+        palette.set_query("new t");
         palette.query_dirty = false;
         let ctx = ComponentContext::new(true);
         let event = Event::Key(KeyEvent {
@@ -992,15 +1071,14 @@ mod tests {
         });
         let result = palette.handle_events(&event, &ctx);
         assert!(result.is_consumed());
-        assert!(palette.query.is_empty());
-        assert_eq!(palette.cursor, 0);
+        assert!(palette.query().is_empty());
         assert!(palette.query_dirty);
     }
 
     #[test]
     fn control_chars_other_than_clear_binding_stay_ignored() {
         let mut palette = make_palette_with_items();
-        palette.query = "new t".to_string();
+        palette.set_query("new t");
         palette.query_dirty = false;
         let ctx = ComponentContext::new(true);
         let event = Event::Key(KeyEvent {
@@ -1014,7 +1092,7 @@ mod tests {
         });
         let result = palette.handle_events(&event, &ctx);
         assert!(result.is_ignored());
-        assert_eq!(palette.query, "new t");
+        assert_eq!(palette.query(), "new t");
         assert!(!palette.query_dirty);
     }
 
@@ -1029,7 +1107,7 @@ mod tests {
             kind: KeyKind::Press,
         });
         palette.handle_events(&event_j, &ctx);
-        assert_eq!(palette.query, "j");
+        assert_eq!(palette.query(), "j");
         assert_eq!(palette.selected, 0);
 
         let event_k = Event::Key(KeyEvent {
@@ -1038,7 +1116,7 @@ mod tests {
             kind: KeyKind::Press,
         });
         palette.handle_events(&event_k, &ctx);
-        assert_eq!(palette.query, "jk");
+        assert_eq!(palette.query(), "jk");
         assert_eq!(palette.selected, 0);
     }
 
@@ -1332,7 +1410,7 @@ mod tests {
         let mut palette = CommandPaletteComponent::new();
         palette.data_dirty = false;
         palette.query_dirty = false;
-        palette.query = "zzz".to_string();
+        palette.set_query("zzz");
         palette.display_nodes = Vec::new();
 
         let area = ratatui::prelude::Rect::new(0, 0, 80, 5);
@@ -201,7 +201,6 @@ impl Overlay<TermWmAction> for ConfirmOverlayComponent {
 impl ConfirmOverlayComponent {
     pub fn new() -> Self {
         let mut dialog = DialogOverlayComponent::new();
-        dialog.set_bg(term_wm_core::theme::NOIR.dialog_bg);
         dialog.set_auto_close_on_outside_click(false);
         Self {
             dialog,
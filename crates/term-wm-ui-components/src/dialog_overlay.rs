@@ -20,7 +20,8 @@ pub struct DialogOverlayComponent {
     visible: bool,
     width: u16,
     height: u16,
-    bg: Color,
+    /// Explicit background; `None` follows the active theme's `dialog_bg`.
+    bg: Option<Color>,
     dim_backdrop: bool,
     auto_close_on_outside_click: bool,
 }
@@ -30,7 +31,7 @@ impl Component<TermWmAction> for DialogOverlayComponent {
         &mut self,
         backend: &mut dyn term_wm_render::RenderBackend,
         area: LayoutRect,
-        _ctx: &ComponentContext,
+        ctx: &ComponentContext,
         _registry: &mut term_wm_core::hitbox_registry::HitboxRegistry,
     ) {
         let area = layout_rect_to_clipped_rect(area);
@@ -55,7 +56,11 @@ impl Component<TermWmAction> for DialogOverlayComponent {
             .title(self.title.as_str())
             .borders(Borders::ALL);
         let paragraph = Paragraph::new(self.body.as_str())
-            .style(Style::default().bg(self.bg))
+            .style(
+                Style::default().bg(self
+                    .bg
+                    .unwrap_or_else(|| color_to_ratatui(ctx.config().theme.dialog_bg))),
+            )
             .block(block)
             .alignment(Alignment::Center)
             .wrap(Wrap { trim: true });
@@ -97,7 +102,7 @@ impl DialogOverlayComponent {
             visible: false,
             width: 70,
             height: 9,
-            bg: crate::helpers::color_to_ratatui(term_wm_core::theme::NOIR.dialog_bg),
+            bg: None,
             dim_backdrop: false,
             auto_close_on_outside_click: false,
         }
@@ -167,7 +172,7 @@ impl DialogOverlayComponent {
     }
 
     pub fn set_bg(&mut self, bg: term_wm_core::theme::Color) {
-        self.bg = color_to_ratatui(bg);
+        self.bg = Some(color_to_ratatui(bg));
     }
 
     pub fn set_dim_backdrop(&mut self, dim: bool) {
@@ -522,7 +527,7 @@ mod tests {
         dlg.set_visible(true);
         assert!(dlg.visible());
         dlg.set_bg(term_wm_core::theme::Color::Red);
-        assert_eq!(dlg.bg, Color::Red);
+        assert_eq!(dlg.bg, Some(Color::Red));
         dlg.set_dim_backdrop(true);
         assert!(dlg.dim_backdrop);
     }
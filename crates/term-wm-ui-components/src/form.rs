@@ -0,0 +1,1107 @@
+use std::collections::VecDeque;
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use term_wm_core::actions::{EventResult, TermWmAction};
+use term_wm_core::components::{Component, ComponentContext};
+use term_wm_core::events::{Event, KeyCode, KeyEvent, MouseButton, MouseEventKind};
+use term_wm_core::hitbox_registry::{ComponentOwner, HitboxId, HitboxRegistry};
+use term_wm_core::layout::rect_contains;
+use term_wm_core::theme::Theme;
+use term_wm_core::window::WindowKey;
+use term_wm_layout_engine::LayoutRect;
+use unicode_width::UnicodeWidthStr;
+
+use crate::helpers::{color_to_ratatui, layout_rect_to_clipped_rect, safe_set_string};
+use crate::text_input::TextInputComponent;
+
+/// Message shown for an empty [`required`](FormField::required) field.
+const REQUIRED_MESSAGE: &str = "Required";
+/// Gap between the label column and the controls.
+const LABEL_GAP: u16 = 2;
+const BUTTON_GAP: u16 = 2;
+
+/// The value of one form field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldValue {
+    Text(String),
+    Bool(bool),
+    /// Index into a select field's options.
+    Choice(usize),
+}
+
+/// Every field value of a form, keyed by field name in field order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FormValues {
+    entries: Vec<(String, FieldValue)>,
+}
+
+impl FormValues {
+    pub fn get(&self, name: &str) -> Option<&FieldValue> {
+        self.entries
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value)
+    }
+
+    pub fn text(&self, name: &str) -> Option<&str> {
+        match self.get(name)? {
+            FieldValue::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub fn flag(&self, name: &str) -> Option<bool> {
+        match self.get(name)? {
+            FieldValue::Bool(checked) => Some(*checked),
+            _ => None,
+        }
+    }
+
+    pub fn choice(&self, name: &str) -> Option<usize> {
+        match self.get(name)? {
+            FieldValue::Choice(index) => Some(*index),
+            _ => None,
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &FieldValue)> {
+        self.entries
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+    }
+}
+
+/// Validation failures as `(field name, message)` pairs. A name that matches
+/// no field is shown as a form-level message above the buttons.
+pub type FieldErrors = Vec<(String, String)>;
+
+/// Turns the submitted values into the form's typed result.
+pub type FormParser<R> = Box<dyn Fn(&FormValues) -> Result<R, FieldErrors>>;
+
+enum FieldKind {
+    Text(Box<TextInputComponent>),
+    Toggle(bool),
+    Select {
+        options: Vec<String>,
+        selected: usize,
+    },
+}
+
+/// A labeled form control: a text input, a toggle or a select.
+pub struct FormField {
+    name: String,
+    label: String,
+    kind: FieldKind,
+    required: bool,
+    error: Option<String>,
+    hitbox_id: HitboxId,
+}
+
+impl FormField {
+    fn with_kind(name: impl Into<String>, label: impl Into<String>, kind: FieldKind) -> Self {
+        Self {
+            name: name.into(),
+            label: label.into(),
+            kind,
+            required: false,
+            error: None,
+            hitbox_id: HitboxId::new(),
+        }
+    }
+
+    pub fn text(name: impl Into<String>, label: impl Into<String>) -> Self {
+        let input = TextInputComponent::new();
+        Self::with_kind(name, label, FieldKind::Text(Box::new(input)))
+    }
+
+    pub fn toggle(name: impl Into<String>, label: impl Into<String>, checked: bool) -> Self {
+        Self::with_kind(name, label, FieldKind::Toggle(checked))
+    }
+
+    pub fn select<S: Into<String>>(
+        name: impl Into<String>,
+        label: impl Into<String>,
+        options: impl IntoIterator<Item = S>,
+    ) -> Self {
+        let options = options.into_iter().map(Into::into).collect();
+        Self::with_kind(
+            name,
+            label,
+            FieldKind::Select {
+                options,
+                selected: 0,
+            },
+        )
+    }
+
+    /// Initial text of a text field.
+    pub fn with_value(mut self, value: impl Into<String>) -> Self {
+        if let FieldKind::Text(input) = &mut self.kind {
+            input.set_value(value.into());
+            input.take_changed();
+        }
+        self
+    }
+
+    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        if let FieldKind::Text(input) = &mut self.kind {
+            input.set_placeholder(placeholder);
+        }
+        self
+    }
+
+    /// Live validation for a text field; see
+    /// [`TextInputComponent::with_validator`].
+    pub fn with_validator(
+        mut self,
+        validator: impl Fn(&str) -> Result<(), String> + 'static,
+    ) -> Self {
+        if let FieldKind::Text(input) = std::mem::replace(&mut self.kind, FieldKind::Toggle(false))
+        {
+            self.kind = FieldKind::Text(Box::new(input.with_validator(validator)));
+        }
+        self
+    }
+
+    /// Initially selected option of a select field.
+    pub fn with_selected(mut self, index: usize) -> Self {
+        if let FieldKind::Select { options, selected } = &mut self.kind {
+            *selected = index.min(options.len().saturating_sub(1));
+        }
+        self
+    }
+
+    /// Reject submission while a text field is empty.
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn value(&self) -> FieldValue {
+        match &self.kind {
+            FieldKind::Text(input) => FieldValue::Text(input.value().to_string()),
+            FieldKind::Toggle(checked) => FieldValue::Bool(*checked),
+            FieldKind::Select { selected, .. } => FieldValue::Choice(*selected),
+        }
+    }
+
+    /// The message shown under the field: the last submit's error, or the
+    /// live validator's.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref().or(match &self.kind {
+            FieldKind::Text(input) => input.error(),
+            _ => None,
+        })
+    }
+
+    fn validate(&self) -> Option<String> {
+        match &self.kind {
+            FieldKind::Text(input) if self.required && input.value().trim().is_empty() => {
+                Some(REQUIRED_MESSAGE.to_string())
+            }
+            FieldKind::Text(input) => input.error().map(str::to_string),
+            _ => None,
+        }
+    }
+
+    fn rows(&self) -> u16 {
+        1 + u16::from(self.error().is_some())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ButtonRole {
+    Submit,
+    Cancel,
+    /// Emit an arbitrary action without submitting.
+    Action(TermWmAction),
+}
+
+/// A button in the form's button row.
+#[derive(Debug, Clone)]
+pub struct FormButton {
+    label: String,
+    role: ButtonRole,
+    hitbox_id: HitboxId,
+}
+
+impl FormButton {
+    pub fn new(label: impl Into<String>, role: ButtonRole) -> Self {
+        Self {
+            label: label.into(),
+            role,
+            hitbox_id: HitboxId::new(),
+        }
+    }
+
+    pub fn submit(label: impl Into<String>) -> Self {
+        Self::new(label, ButtonRole::Submit)
+    }
+
+    pub fn cancel(label: impl Into<String>) -> Self {
+        Self::new(label, ButtonRole::Cancel)
+    }
+
+    pub fn action(label: impl Into<String>, action: TermWmAction) -> Self {
+        Self::new(label, ButtonRole::Action(action))
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn text(&self) -> String {
+        format!("[ {} ]", self.label)
+    }
+}
+
+/// Styles for [`FormComponent`]; [`FormStyle::from_theme`] is the default.
+/// Text fields keep their own [`TextInputStyle`](crate::TextInputStyle).
+#[derive(Debug, Clone, Copy)]
+pub struct FormStyle {
+    pub label: Style,
+    pub control: Style,
+    pub focused: Style,
+    pub error: Style,
+    pub button: Style,
+    pub button_focused: Style,
+}
+
+impl FormStyle {
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            label: Style::default().fg(color_to_ratatui(theme.text_muted)),
+            control: Style::default().fg(color_to_ratatui(theme.text)),
+            focused: Style::default()
+                .bg(color_to_ratatui(theme.menu_selected_bg))
+                .fg(color_to_ratatui(theme.menu_selected_fg)),
+            error: Style::default()
+                .fg(color_to_ratatui(theme.error))
+                .add_modifier(Modifier::ITALIC),
+            button: Style::default()
+                .fg(color_to_ratatui(theme.dialog_fg))
+                .bg(color_to_ratatui(theme.panel_bg)),
+            button_focused: Style::default()
+                .fg(color_to_ratatui(theme.decorator_header_fg))
+                .bg(color_to_ratatui(theme.decorator_header_bg))
+                .add_modifier(Modifier::BOLD),
+        }
+    }
+}
+
+/// A vertical form of labeled fields above a row of buttons.
+///
+/// Tab/Shift+Tab (the `FocusNext`/`FocusPrev` bindings) and Up/Down move the
+/// focus, which is announced to the window with `RequestKeyboardFocus` so
+/// sibling components stop receiving keys. Enter submits (or presses the
+/// focused button), Esc cancels, Space flips toggles and Left/Right cycle
+/// select options.
+///
+/// Submitting checks required fields and each text field's validator, then
+/// runs the parser set with [`with_parser`](Self::with_parser); its typed
+/// result is collected with [`take_result`](Self::take_result), typically
+/// after the submit action arrives. Errors are shown under their field and
+/// the focus moves to the first invalid one.
+pub struct FormComponent<R = FormValues> {
+    fields: Vec<FormField>,
+    buttons: Vec<FormButton>,
+    /// Index into fields followed by buttons.
+    focus: usize,
+    parser: FormParser<R>,
+    result: Option<R>,
+    cancelled: bool,
+    form_error: Option<String>,
+    submit_action: Option<TermWmAction>,
+    cancel_action: Option<TermWmAction>,
+    style: Option<FormStyle>,
+    /// Screen rect of every focusable from the last render, in focus order.
+    rects: Vec<LayoutRect>,
+}
+
+impl FormComponent<FormValues> {
+    /// An empty form with Cancel and OK buttons whose result is the raw
+    /// [`FormValues`].
+    pub fn new() -> Self {
+        Self {
+            fields: Vec::new(),
+            buttons: vec![FormButton::cancel("Cancel"), FormButton::submit("OK")],
+            focus: 0,
+            parser: Box::new(|values| Ok(values.clone())),
+            result: None,
+            cancelled: false,
+            form_error: None,
+            submit_action: None,
+            cancel_action: None,
+            style: None,
+            rects: Vec::new(),
+        }
+    }
+}
+
+impl Default for FormComponent<FormValues> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R> FormComponent<R> {
+    /// Build the typed result from the values, or reject them with
+    /// per-field errors.
+    pub fn with_parser<S>(
+        self,
+        parser: impl Fn(&FormValues) -> Result<S, FieldErrors> + 'static,
+    ) -> FormComponent<S> {
+        FormComponent {
+            fields: self.fields,
+            buttons: self.buttons,
+            focus: self.focus,
+            parser: Box::new(parser),
+            result: None,
+            cancelled: self.cancelled,
+            form_error: self.form_error,
+            submit_action: self.submit_action,
+            cancel_action: self.cancel_action,
+            style: self.style,
+            rects: Vec::new(),
+        }
+    }
+
+    pub fn with_field(mut self, field: FormField) -> Self {
+        self.add_field(field);
+        self
+    }
+
+    pub fn add_field(&mut self, field: FormField) {
+        self.fields.push(field);
+    }
+
+    /// Replace the default Cancel/OK buttons.
+    pub fn with_buttons(mut self, buttons: Vec<FormButton>) -> Self {
+        self.buttons = buttons;
+        self
+    }
+
+    /// Emit `action` once a submission produced a result.
+    pub fn with_submit_action(mut self, action: TermWmAction) -> Self {
+        self.submit_action = Some(action);
+        self
+    }
+
+    /// Emit `action` when the form is cancelled.
+    pub fn with_cancel_action(mut self, action: TermWmAction) -> Self {
+        self.cancel_action = Some(action);
+        self
+    }
+
+    /// Override the theme-derived styles.
+    pub fn with_style(mut self, style: FormStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    pub fn fields(&self) -> &[FormField] {
+        &self.fields
+    }
+
+    pub fn field(&self, name: &str) -> Option<&FormField> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    pub fn values(&self) -> FormValues {
+        FormValues {
+            entries: self
+                .fields
+                .iter()
+                .map(|field| (field.name.clone(), field.value()))
+                .collect(),
+        }
+    }
+
+    /// Name of the focused field, or `None` when a button has the focus.
+    pub fn focused_field(&self) -> Option<&str> {
+        self.fields.get(self.focus).map(FormField::name)
+    }
+
+    /// Label of the focused button.
+    pub fn focused_button(&self) -> Option<&str> {
+        self.focus
+            .checked_sub(self.fields.len())
+            .and_then(|i| self.buttons.get(i))
+            .map(FormButton::label)
+    }
+
+    /// Focus the field called `name`.
+    pub fn focus_field(&mut self, name: &str) {
+        if let Some(index) = self.fields.iter().position(|field| field.name == name) {
+            self.focus = index;
+        }
+    }
+
+    /// Form-level message from the parser, shown above the buttons.
+    pub fn form_error(&self) -> Option<&str> {
+        self.form_error.as_deref()
+    }
+
+    /// The typed result of the last successful submission.
+    pub fn take_result(&mut self) -> Option<R> {
+        self.result.take()
+    }
+
+    /// Whether the form was cancelled since the last call.
+    pub fn take_cancelled(&mut self) -> bool {
+        std::mem::take(&mut self.cancelled)
+    }
+
+    pub fn has_result(&self) -> bool {
+        self.result.is_some()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    /// Forget an uncollected result or cancellation.
+    pub(crate) fn reset_outcome(&mut self) {
+        self.result = None;
+        self.cancelled = false;
+    }
+
+    /// Validate and parse the current values. Emits the submit action on
+    /// success, or focuses the first invalid field.
+    pub fn submit(&mut self) -> EventResult<TermWmAction> {
+        self.form_error = None;
+        let mut first_invalid = None;
+        for (index, field) in self.fields.iter_mut().enumerate() {
+            field.error = field.validate();
+            if field.error.is_some() && first_invalid.is_none() {
+                first_invalid = Some(index);
+            }
+        }
+        if first_invalid.is_none() {
+            match (self.parser)(&self.values()) {
+                Ok(result) => {
+                    self.result = Some(result);
+                    return match &self.submit_action {
+                        Some(action) => EventResult::Action(action.clone()),
+                        None => EventResult::Consumed,
+                    };
+                }
+                Err(errors) => {
+                    for (name, message) in errors {
+                        match self.fields.iter().position(|field| field.name == name) {
+                            Some(index) => {
+                                self.fields[index].error = Some(message);
+                                first_invalid = Some(first_invalid.map_or(index, |i| i.min(index)));
+                            }
+                            None => self.form_error = Some(message),
+                        }
+                    }
+                }
+            }
+        }
+        match first_invalid {
+            Some(index) => self.set_focus(index),
+            None => EventResult::Consumed,
+        }
+    }
+
+    /// Mark the form cancelled and emit the cancel action.
+    pub fn cancel(&mut self) -> EventResult<TermWmAction> {
+        self.cancelled = true;
+        match &self.cancel_action {
+            Some(action) => EventResult::Action(action.clone()),
+            None => EventResult::Consumed,
+        }
+    }
+
+    /// Rows needed to show every field, its message and the button row.
+    pub fn content_height(&self) -> u16 {
+        let fields: u16 = self.fields.iter().map(FormField::rows).sum();
+        let buttons = if self.buttons.is_empty() { 0 } else { 2 };
+        fields + u16::from(self.form_error.is_some()) + buttons
+    }
+
+    fn focusable_len(&self) -> usize {
+        self.fields.len() + self.buttons.len()
+    }
+
+    fn focus_id(&self, index: usize) -> Option<HitboxId> {
+        match self.fields.get(index) {
+            Some(field) => Some(field.hitbox_id),
+            None => self
+                .buttons
+                .get(index - self.fields.len())
+                .map(|button| button.hitbox_id),
+        }
+    }
+
+    fn focus_index_of(&self, id: HitboxId) -> Option<usize> {
+        (0..self.focusable_len()).find(|&i| self.focus_id(i) == Some(id))
+    }
+
+    fn set_focus(&mut self, index: usize) -> EventResult<TermWmAction> {
+        self.focus = index;
+        match self.focus_id(index) {
+            Some(id) => EventResult::Action(TermWmAction::RequestKeyboardFocus(id)),
+            None => EventResult::Consumed,
+        }
+    }
+
+    fn move_focus(&mut self, forward: bool) -> EventResult<TermWmAction> {
+        let len = self.focusable_len();
+        if len == 0 {
+            return EventResult::Ignored;
+        }
+        let next = if forward {
+            (self.focus + 1) % len
+        } else {
+            (self.focus + len - 1) % len
+        };
+        self.set_focus(next)
+    }
+
+    /// Adopt the window's keyboard focus when it points at one of our
+    /// controls (e.g. after the WM applied a `RequestKeyboardFocus`).
+    /// Returns `false` when another component owns the keyboard.
+    fn sync_focus(&mut self, ctx: &ComponentContext) -> bool {
+        match ctx.keyboard_focus_id() {
+            None => true,
+            Some(id) => match self.focus_index_of(id) {
+                Some(index) => {
+                    self.focus = index;
+                    true
+                }
+                None => false,
+            },
+        }
+    }
+
+    fn press_button(&mut self, index: usize) -> EventResult<TermWmAction> {
+        let Some(button) = self.buttons.get(index) else {
+            return EventResult::Ignored;
+        };
+        match button.role.clone() {
+            ButtonRole::Submit => self.submit(),
+            ButtonRole::Cancel => self.cancel(),
+            ButtonRole::Action(action) => EventResult::Action(action),
+        }
+    }
+
+    fn handle_key(&mut self, key: &KeyEvent, ctx: &ComponentContext) -> EventResult<TermWmAction> {
+        let kb = &ctx.config().keybindings;
+        if kb.matches(TermWmAction::FocusNext, key) {
+            return self.move_focus(true);
+        }
+        if kb.matches(TermWmAction::FocusPrev, key) {
+            return self.move_focus(false);
+        }
+        if key.code == KeyCode::Esc {
+            return self.cancel();
+        }
+        let focus = self.focus;
+        if let Some(button) = focus.checked_sub(self.fields.len()) {
+            return match key.code {
+                KeyCode::Enter | KeyCode::Char(' ') => self.press_button(button),
+                KeyCode::Left if button > 0 => self.set_focus(focus - 1),
+                KeyCode::Right if button + 1 < self.buttons.len() => self.set_focus(focus + 1),
+                KeyCode::Up => self.move_focus(false),
+                _ => EventResult::Ignored,
+            };
+        }
+        if key.code == KeyCode::Enter {
+            return self.submit();
+        }
+        let result = match &mut self.fields[focus].kind {
+            FieldKind::Text(input) => {
+                let result = input.handle_key(key);
+                if input.take_changed() {
+                    self.fields[focus].error = None;
+                }
+                result
+            }
+            FieldKind::Toggle(checked) if key.code == KeyCode::Char(' ') => {
+                *checked = !*checked;
+                EventResult::Consumed
+            }
+            FieldKind::Select { options, selected } if !options.is_empty() => {
+                let len = options.len();
+                match key.code {
+                    KeyCode::Left => *selected = (*selected + len - 1) % len,
+                    KeyCode::Right | KeyCode::Char(' ') => *selected = (*selected + 1) % len,
+                    _ => return self.arrow_focus(key),
+                }
+                EventResult::Consumed
+            }
+            _ => EventResult::Ignored,
+        };
+        if result.is_ignored() {
+            return self.arrow_focus(key);
+        }
+        result
+    }
+
+    /// Up/Down fall back to moving between fields when the control itself
+    /// has no use for them.
+    fn arrow_focus(&mut self, key: &KeyEvent) -> EventResult<TermWmAction> {
+        match key.code {
+            KeyCode::Up if self.focus > 0 => self.move_focus(false),
+            KeyCode::Down => self.move_focus(true),
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn handle_mouse(&mut self, event: &Event, ctx: &ComponentContext) -> EventResult<TermWmAction> {
+        let Event::Mouse(mouse) = event else {
+            return EventResult::Ignored;
+        };
+        if !matches!(mouse.kind, MouseEventKind::Press(MouseButton::Left)) {
+            // Drags and releases belong to the focused text field's selection.
+            if let Some(FormField {
+                kind: FieldKind::Text(input),
+                ..
+            }) = self.fields.get_mut(self.focus)
+                && let Some(&rect) = self.rects.get(self.focus)
+            {
+                return input.handle_events(event, &ctx.with_screen_area(rect));
+            }
+            return EventResult::Ignored;
+        }
+        let Some(index) = self
+            .rects
+            .iter()
+            .position(|&rect| rect_contains(rect, mouse.column, mouse.row))
+        else {
+            return EventResult::Ignored;
+        };
+        if let Some(button) = index.checked_sub(self.fields.len()) {
+            self.focus = index;
+            return self.press_button(button);
+        }
+        let rect = self.rects[index];
+        match &mut self.fields[index].kind {
+            FieldKind::Text(input) => {
+                input.handle_events(event, &ctx.with_screen_area(rect));
+            }
+            FieldKind::Toggle(checked) => *checked = !*checked,
+            FieldKind::Select { options, selected } if !options.is_empty() => {
+                *selected = (*selected + 1) % options.len();
+            }
+            FieldKind::Select { .. } => {}
+        }
+        self.set_focus(index)
+    }
+
+    fn label_width(&self) -> u16 {
+        self.fields
+            .iter()
+            .map(|field| field.label.width() as u16)
+            .max()
+            .map_or(0, |width| width + LABEL_GAP)
+    }
+
+    /// Draw the form into `area`; `origin` maps it to screen coordinates for
+    /// mouse hit-testing.
+    fn render_into(
+        &mut self,
+        buffer: &mut Buffer,
+        area: Rect,
+        origin: (i32, i32),
+        focused: bool,
+        theme: &Theme,
+    ) {
+        let style = self.style.unwrap_or_else(|| FormStyle::from_theme(theme));
+        let to_screen = |rect: Rect| LayoutRect {
+            x: origin.0 + i32::from(rect.x - area.x),
+            y: origin.1 + i32::from(rect.y - area.y),
+            width: rect.width,
+            height: rect.height,
+        };
+        self.rects.clear();
+        let label_width = self.label_width().min(area.width / 2);
+        let control_x = area.x + label_width;
+        let control_width = area.width - label_width;
+        let bottom = area.y + area.height;
+        let mut y = area.y;
+
+        for (index, field) in self.fields.iter_mut().enumerate() {
+            let has_focus = focused && self.focus == index;
+            let rect = Rect::new(
+                control_x,
+                y.min(bottom),
+                control_width,
+                u16::from(y < bottom),
+            );
+            self.rects.push(to_screen(rect));
+            if y >= bottom {
+                continue;
+            }
+            let label: String = field.label.chars().take(usize::from(label_width)).collect();
+            safe_set_string(buffer, area, area.x, y, &label, style.label);
+            match &mut field.kind {
+                FieldKind::Text(input) => input.render_into(buffer, rect, has_focus, theme),
+                FieldKind::Toggle(checked) => {
+                    let text = if *checked { "[x]" } else { "[ ]" };
+                    let control = if has_focus {
+                        style.focused
+                    } else {
+                        style.control
+                    };
+                    safe_set_string(buffer, rect, rect.x, y, text, control);
+                }
+                FieldKind::Select { options, selected } => {
+                    let option = options.get(*selected).map_or("", String::as_str);
+                    let text = format!("◂ {option} ▸");
+                    let control = if has_focus {
+                        style.focused
+                    } else {
+                        style.control
+                    };
+                    safe_set_string(buffer, rect, rect.x, y, &text, control);
+                }
+            }
+            y += 1;
+            if let Some(error) = field.error.as_deref().or(match &field.kind {
+                FieldKind::Text(input) => input.error(),
+                _ => None,
+            }) {
+                safe_set_string(buffer, area, control_x, y, error, style.error);
+                y += 1;
+            }
+        }
+
+        if let Some(error) = &self.form_error {
+            safe_set_string(buffer, area, area.x, y, error, style.error);
+            y += 1;
+        }
+        if self.buttons.is_empty() {
+            return;
+        }
+        // Buttons sit on the last row, right-aligned, below a spacer row.
+        let button_y = (y + 1).min(bottom.saturating_sub(1));
+        let texts: Vec<String> = self.buttons.iter().map(FormButton::text).collect();
+        let total: u16 = texts.iter().map(|t| t.width() as u16).sum::<u16>()
+            + BUTTON_GAP * (texts.len() as u16 - 1);
+        let mut x = area.x + area.width.saturating_sub(total);
+        for (i, text) in texts.iter().enumerate() {
+            let index = self.fields.len() + i;
+            let width = text.width() as u16;
+            let button_style = if focused && self.focus == index {
+                style.button_focused
+            } else {
+                style.button
+            };
+            safe_set_string(buffer, area, x, button_y, text, button_style);
+            self.rects.push(to_screen(Rect::new(x, button_y, width, 1)));
+            x += width + BUTTON_GAP;
+        }
+    }
+}
+
+impl<R> Component<TermWmAction> for FormComponent<R> {
+    fn desired_height(&self, _width: u16) -> u16 {
+        self.content_height()
+    }
+
+    fn handle_events(
+        &mut self,
+        event: &Event,
+        ctx: &ComponentContext,
+    ) -> EventResult<TermWmAction> {
+        match event {
+            Event::Mouse(_) => self.handle_mouse(event, ctx),
+            _ if !self.sync_focus(ctx) => EventResult::Ignored,
+            Event::Key(key) => self.handle_key(key, ctx),
+            Event::Paste(text) => {
+                if self.paste(text) {
+                    EventResult::Consumed
+                } else {
+                    EventResult::Ignored
+                }
+            }
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn update(
+        &mut self,
+        action: TermWmAction,
+        _ctx: &ComponentContext,
+        _actions: &mut VecDeque<(WindowKey, TermWmAction)>,
+    ) {
+        if let TermWmAction::ClipboardPaste(text) = action {
+            self.paste(&text);
+        }
+    }
+
+    fn render(
+        &mut self,
+        backend: &mut dyn term_wm_render::RenderBackend,
+        area: LayoutRect,
+        ctx: &ComponentContext,
+        registry: &mut HitboxRegistry,
+    ) {
+        let rect = layout_rect_to_clipped_rect(area);
+        let backend = crate::helpers::downcast_ratatui(backend);
+        let bounds = rect.intersection(backend.buffer.area);
+        if bounds.width == 0 || bounds.height == 0 {
+            return;
+        }
+        let focused = ctx.focused() && self.sync_focus(ctx);
+        let screen = ctx.screen_area().unwrap_or(area);
+        let origin = (
+            screen.x + i32::from(bounds.x) - area.x,
+            screen.y + i32::from(bounds.y) - area.y,
+        );
+        self.render_into(
+            &mut backend.buffer,
+            bounds,
+            origin,
+            focused,
+            &ctx.config().theme,
+        );
+        if let Some(key) = ctx.window_key() {
+            for (index, rect) in self.rects.iter().enumerate() {
+                if let Some(id) = self.focus_id(index) {
+                    registry.register(id, ComponentOwner::Window(key), *rect);
+                }
+            }
+        }
+    }
+
+    fn paste(&mut self, text: &str) -> bool {
+        match self.fields.get_mut(self.focus) {
+            Some(FormField {
+                kind: FieldKind::Text(input),
+                error,
+                ..
+            }) => {
+                *error = None;
+                input.insert_text(text)
+            }
+            _ => false,
+        }
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use term_wm_core::events::{KeyKind, KeyModifiers, MouseEvent};
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE, KeyKind::Press))
+    }
+
+    fn shift_tab() -> Event {
+        let shift = KeyModifiers {
+            shift: true,
+            ..KeyModifiers::NONE
+        };
+        Event::Key(KeyEvent::new(KeyCode::Tab, shift, KeyKind::Press))
+    }
+
+    fn send<R>(form: &mut FormComponent<R>, event: Event) -> EventResult<TermWmAction> {
+        form.handle_events(&event, &ComponentContext::new(true))
+    }
+
+    fn type_str<R>(form: &mut FormComponent<R>, text: &str) {
+        for c in text.chars() {
+            send(form, key(KeyCode::Char(c)));
+        }
+    }
+
+    fn render<R>(form: &mut FormComponent<R>, width: u16, height: u16) -> Vec<String> {
+        let rect = Rect::new(0, 0, width, height);
+        let mut backend = term_wm_console::RatatuiBackend::new_simple(Buffer::empty(rect), rect);
+        let mut registry = HitboxRegistry::new();
+        let area = LayoutRect {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        };
+        form.render(
+            &mut backend,
+            area,
+            &ComponentContext::new(true),
+            &mut registry,
+        );
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| backend.buffer[(x, y)].symbol().to_string())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Layout {
+        name: String,
+        floating: bool,
+    }
+
+    fn save_layout_form() -> FormComponent<Layout> {
+        FormComponent::new()
+            .with_field(
+                FormField::text("name", "Name")
+                    .with_placeholder("layout name")
+                    .required(),
+            )
+            .with_field(FormField::toggle("floating", "Floating", false))
+            .with_field(FormField::select("mode", "Mode", ["Tiled", "Monocle"]))
+            .with_parser(|values| {
+                let name = values.text("name").unwrap_or_default();
+                if name == "default" {
+                    return Err(vec![("name".into(), "name is reserved".into())]);
+                }
+                Ok(Layout {
+                    name: name.to_string(),
+                    floating: values.flag("floating").unwrap_or(false),
+                })
+            })
+            .with_submit_action(TermWmAction::Help)
+    }
+
+    #[test]
+    fn tab_traverses_fields_then_buttons_and_requests_focus() {
+        let mut form = save_layout_form();
+        assert_eq!(form.focused_field(), Some("name"));
+        let result = send(&mut form, key(KeyCode::Tab));
+        let floating_id = form.fields[1].hitbox_id;
+        assert!(matches!(
+            result,
+            EventResult::Action(TermWmAction::RequestKeyboardFocus(id)) if id == floating_id
+        ));
+        send(&mut form, key(KeyCode::Tab));
+        send(&mut form, key(KeyCode::Tab));
+        assert_eq!(form.focused_button(), Some("Cancel"));
+        send(&mut form, key(KeyCode::Tab));
+        send(&mut form, key(KeyCode::Tab));
+        assert_eq!(form.focused_field(), Some("name"));
+        send(&mut form, shift_tab());
+        assert_eq!(form.focused_button(), Some("OK"));
+    }
+
+    #[test]
+    fn submit_validates_and_returns_typed_result() {
+        let mut form = save_layout_form();
+        send(&mut form, key(KeyCode::Tab));
+        let name_id = form.fields[0].hitbox_id;
+        let result = send(&mut form, key(KeyCode::Enter));
+        assert!(matches!(
+            result,
+            EventResult::Action(TermWmAction::RequestKeyboardFocus(id)) if id == name_id
+        ));
+        assert_eq!(form.field("name").unwrap().error(), Some(REQUIRED_MESSAGE));
+        assert_eq!(form.focused_field(), Some("name"));
+        assert!(form.take_result().is_none());
+
+        type_str(&mut form, "default");
+        assert_eq!(form.field("name").unwrap().error(), None);
+        send(&mut form, key(KeyCode::Enter));
+        assert_eq!(
+            form.field("name").unwrap().error(),
+            Some("name is reserved")
+        );
+
+        for _ in 0.."default".len() {
+            send(&mut form, key(KeyCode::Backspace));
+        }
+        type_str(&mut form, "work");
+        send(&mut form, key(KeyCode::Down));
+        send(&mut form, key(KeyCode::Char(' ')));
+        let result = send(&mut form, key(KeyCode::Enter));
+        assert!(matches!(result, EventResult::Action(TermWmAction::Help)));
+        assert_eq!(
+            form.take_result(),
+            Some(Layout {
+                name: "work".into(),
+                floating: true,
+            })
+        );
+    }
+
+    #[test]
+    fn select_cycles_and_esc_cancels() {
+        let mut form = FormComponent::new()
+            .with_field(FormField::select("mode", "Mode", ["a", "b", "c"]).with_selected(2))
+            .with_cancel_action(TermWmAction::CloseMenu);
+        send(&mut form, key(KeyCode::Right));
+        assert_eq!(form.values().choice("mode"), Some(0));
+        send(&mut form, key(KeyCode::Left));
+        assert_eq!(form.values().choice("mode"), Some(2));
+        let result = send(&mut form, key(KeyCode::Esc));
+        assert!(matches!(
+            result,
+            EventResult::Action(TermWmAction::CloseMenu)
+        ));
+        assert!(form.take_cancelled());
+        assert!(!form.take_cancelled());
+    }
+
+    #[test]
+    fn keys_are_ignored_while_another_component_has_focus() {
+        let mut form = save_layout_form();
+        let ctx = ComponentContext::new(true).with_keyboard_focus_id(HitboxId::new());
+        assert!(
+            form.handle_events(&key(KeyCode::Char('x')), &ctx)
+                .is_ignored()
+        );
+        let toggle = form.fields[1].hitbox_id;
+        let ctx = ComponentContext::new(true).with_keyboard_focus_id(toggle);
+        form.handle_events(&key(KeyCode::Char(' ')), &ctx);
+        assert_eq!(form.values().flag("floating"), Some(true));
+    }
+
+    #[test]
+    fn renders_labels_controls_errors_and_buttons() {
+        let mut form = save_layout_form();
+        send(&mut form, key(KeyCode::Enter));
+        assert_eq!(form.content_height(), 6);
+        let lines = render(&mut form, 30, 6);
+        assert_eq!(lines[0], "Name      layout name");
+        assert_eq!(lines[1], "          Required");
+        assert_eq!(lines[2], "Floating  [ ]");
+        assert_eq!(lines[3], "Mode      ◂ Tiled ▸");
+        assert_eq!(lines[5], "            [ Cancel ]  [ OK ]");
+    }
+
+    #[test]
+    fn clicks_focus_fields_and_press_buttons() {
+        let mut form = save_layout_form();
+        render(&mut form, 30, 6);
+        let click = |column, row| {
+            Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Press(MouseButton::Left),
+                modifiers: KeyModifiers::NONE,
+                column,
+                row,
+            })
+        };
+        send(&mut form, click(11, 1));
+        assert_eq!(form.values().flag("floating"), Some(true));
+        assert_eq!(form.focused_field(), Some("floating"));
+        let result = send(&mut form, click(14, 4));
+        assert!(result.is_consumed());
+        assert!(form.take_cancelled());
+        send(&mut form, Event::Paste("ignored".into()));
+        send(&mut form, click(12, 0));
+        assert_eq!(form.focused_field(), Some("name"));
+        send(&mut form, Event::Paste("pasted".into()));
+        assert_eq!(form.values().text("name"), Some("pasted"));
+    }
+}
@@ -0,0 +1,225 @@
+use ratatui::layout::Rect;
+use term_wm_core::events::Event;
+
+use std::collections::VecDeque;
+
+use crate::dialog_overlay::DialogOverlayComponent;
+use crate::form::{FormComponent, FormValues};
+use crate::helpers::layout_rect_to_clipped_rect;
+use term_wm_core::actions::{EventResult, TermWmAction};
+use term_wm_core::components::{Component, ComponentContext, Overlay};
+use term_wm_core::hitbox_registry::HitboxRegistry;
+use term_wm_core::window::WindowKey;
+use term_wm_layout_engine::LayoutRect;
+
+/// A [`FormComponent`] shown in a centered dialog.
+///
+/// The dialog grows to the form's height and hides itself once the form is
+/// submitted successfully or cancelled; collect the outcome with
+/// [`form_mut`](Self::form_mut)`().take_result()`. Focus moves stay inside
+/// the dialog, so the form's `RequestKeyboardFocus` actions are not forwarded
+/// to the window underneath.
+pub struct FormOverlayComponent<R = FormValues> {
+    dialog: DialogOverlayComponent,
+    form: FormComponent<R>,
+    width: u16,
+    area: Option<LayoutRect>,
+}
+
+impl<R> FormOverlayComponent<R> {
+    pub fn new(form: FormComponent<R>) -> Self {
+        let mut dialog = DialogOverlayComponent::new();
+        dialog.set_dim_backdrop(true);
+        Self {
+            dialog,
+            form,
+            width: 60,
+            area: None,
+        }
+    }
+
+    /// Preferred dialog width including the border.
+    pub fn set_width(&mut self, width: u16) {
+        self.width = width;
+    }
+
+    pub fn open(&mut self, title: impl Into<String>) {
+        self.dialog.set_title(title);
+        self.dialog.set_visible(true);
+        self.form.reset_outcome();
+    }
+
+    pub fn close(&mut self) {
+        self.dialog.set_visible(false);
+    }
+
+    pub fn visible(&self) -> bool {
+        self.dialog.visible()
+    }
+
+    pub fn form(&self) -> &FormComponent<R> {
+        &self.form
+    }
+
+    pub fn form_mut(&mut self) -> &mut FormComponent<R> {
+        &mut self.form
+    }
+
+    /// The form's area inside the dialog border and padding.
+    fn form_rect(&self, area: Rect) -> Rect {
+        let rect = self.dialog.rect_for(area);
+        Rect {
+            x: rect.x.saturating_add(2),
+            y: rect.y.saturating_add(1),
+            width: rect.width.saturating_sub(4),
+            height: rect.height.saturating_sub(2),
+        }
+    }
+}
+
+impl<R> Component<TermWmAction> for FormOverlayComponent<R> {
+    fn render(
+        &mut self,
+        backend: &mut dyn term_wm_render::RenderBackend,
+        area: LayoutRect,
+        ctx: &ComponentContext,
+        registry: &mut HitboxRegistry,
+    ) {
+        if !self.visible() || area.width == 0 || area.height == 0 {
+            return;
+        }
+        self.dialog
+            .set_size(self.width, self.form.content_height().saturating_add(2));
+        let dialog_ctx = ctx.with_overlay(true).with_focus(true);
+        self.dialog.render(backend, area, &dialog_ctx, registry);
+        let rect = self.form_rect(layout_rect_to_clipped_rect(area));
+        if rect.width == 0 || rect.height == 0 {
+            return;
+        }
+        let form_area = LayoutRect {
+            x: rect.x as i32,
+            y: rect.y as i32,
+            width: rect.width,
+            height: rect.height,
+        };
+        self.area = Some(form_area);
+        self.form.render(
+            backend,
+            form_area,
+            &dialog_ctx.with_screen_area(form_area),
+            registry,
+        );
+    }
+
+    fn handle_events(
+        &mut self,
+        event: &Event,
+        ctx: &ComponentContext,
+    ) -> EventResult<TermWmAction> {
+        if !self.visible() {
+            return EventResult::Ignored;
+        }
+        let mut form_ctx = ctx.with_overlay(true).with_focus(true);
+        if let Some(area) = self.area {
+            form_ctx = form_ctx.with_screen_area(area);
+        }
+        let result = self.form.handle_events(event, &form_ctx);
+        if self.form.has_result() || self.form.is_cancelled() {
+            self.close();
+        }
+        match result {
+            EventResult::Action(TermWmAction::RequestKeyboardFocus(_)) => EventResult::Consumed,
+            // The dialog is modal: swallow keys the form has no use for.
+            EventResult::Ignored if matches!(event, Event::Key(_) | Event::Paste(_)) => {
+                EventResult::Consumed
+            }
+            result => result,
+        }
+    }
+
+    fn update(
+        &mut self,
+        action: TermWmAction,
+        ctx: &ComponentContext,
+        actions: &mut VecDeque<(WindowKey, TermWmAction)>,
+    ) {
+        self.form.update(action, ctx, actions);
+    }
+
+    fn destroy(&mut self) {}
+}
+
+impl<R: 'static> Overlay<TermWmAction> for FormOverlayComponent<R> {
+    fn render_area(&self) -> Option<LayoutRect> {
+        self.area
+    }
+    fn visible(&self) -> bool {
+        self.visible()
+    }
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::form::FormField;
+    use ratatui::buffer::Buffer;
+    use term_wm_core::events::{KeyCode, KeyEvent, KeyKind, KeyModifiers};
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE, KeyKind::Press))
+    }
+
+    #[test]
+    fn submits_inside_dialog_and_closes() {
+        let form = FormComponent::new()
+            .with_field(FormField::text("name", "Name").required())
+            .with_parser(|values| Ok(values.text("name").unwrap_or_default().len()));
+        let mut overlay = FormOverlayComponent::new(form);
+        overlay.open("Rename");
+        let rect = Rect::new(0, 0, 80, 20);
+        let mut backend = term_wm_console::RatatuiBackend::new_simple(Buffer::empty(rect), rect);
+        let area = LayoutRect {
+            x: 0,
+            y: 0,
+            width: 80,
+            height: 20,
+        };
+        let ctx = ComponentContext::new(true);
+        overlay.render(&mut backend, area, &ctx, &mut HitboxRegistry::new());
+        let form_area = overlay.render_area().unwrap();
+        let title_row: String = (0..80)
+            .map(|x| {
+                backend.buffer[(x, form_area.y as u16 - 1)]
+                    .symbol()
+                    .to_string()
+            })
+            .collect();
+        assert!(title_row.contains("Rename"));
+
+        assert!(
+            overlay
+                .handle_events(&key(KeyCode::Tab), &ctx)
+                .is_consumed()
+        );
+        assert!(
+            overlay
+                .handle_events(&key(KeyCode::F(5)), &ctx)
+                .is_consumed()
+        );
+        overlay.handle_events(&key(KeyCode::Tab), &ctx);
+        overlay.handle_events(&key(KeyCode::Tab), &ctx);
+        overlay.handle_events(&key(KeyCode::Enter), &ctx);
+        assert!(overlay.visible());
+        assert_eq!(overlay.form().focused_field(), Some("name"));
+
+        overlay.handle_events(&key(KeyCode::Char('a')), &ctx);
+        overlay.handle_events(&key(KeyCode::Char('b')), &ctx);
+        overlay.handle_events(&key(KeyCode::Enter), &ctx);
+        assert!(!overlay.visible());
+        assert_eq!(overlay.form_mut().take_result(), Some(2));
+    }
+}
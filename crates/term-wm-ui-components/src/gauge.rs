@@ -0,0 +1,215 @@
+use std::collections::VecDeque;
+
+use ratatui::style::Style;
+use term_wm_core::actions::{EventResult, TermWmAction};
+use term_wm_core::components::{Component, ComponentContext};
+use term_wm_core::window::WindowKey;
+use term_wm_layout_engine::LayoutRect;
+use term_wm_view::view_tag;
+use unicode_width::UnicodeWidthStr;
+
+use crate::helpers::{color_to_ratatui, layout_rect_to_clipped_rect};
+use crate::progress_bar::{BarStyle, FULL, TRACK};
+
+/// A one-row meter for a value against a maximum: `CPU ██████░░░░  62%`.
+///
+/// Unlike [`ProgressBarComponent`](crate::ProgressBarComponent) the bar fills
+/// whole cells, and the fill changes color once the value reaches the
+/// [`warning`](Self::with_warning) or [`critical`](Self::with_critical)
+/// threshold (theme warning / error colors).
+#[derive(Debug, Clone)]
+pub struct GaugeComponent {
+    value: f64,
+    max: f64,
+    label: Option<String>,
+    warning: Option<f64>,
+    critical: Option<f64>,
+    style: Option<BarStyle>,
+}
+
+#[view_tag(Gauge)]
+impl GaugeComponent {
+    pub fn new(value: impl Into<f64>) -> Self {
+        Self {
+            value: value.into(),
+            max: 100.0,
+            label: None,
+            warning: None,
+            critical: None,
+            style: None,
+        }
+    }
+
+    /// Full-scale value; defaults to 100.
+    pub fn with_max(mut self, max: impl Into<f64>) -> Self {
+        self.max = max.into();
+        self
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn with_warning(mut self, at: impl Into<f64>) -> Self {
+        self.warning = Some(at.into());
+        self
+    }
+
+    pub fn with_critical(mut self, at: impl Into<f64>) -> Self {
+        self.critical = Some(at.into());
+        self
+    }
+
+    pub fn with_style(mut self, style: BarStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    pub fn set_value(&mut self, value: impl Into<f64>) {
+        self.value = value.into();
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// `value / max`, clamped to `0.0..=1.0`.
+    pub fn ratio(&self) -> f64 {
+        if self.max > 0.0 && self.value.is_finite() {
+            (self.value / self.max).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Component<TermWmAction> for GaugeComponent {
+    fn desired_height(&self, _width: u16) -> u16 {
+        1
+    }
+
+    fn render(
+        &mut self,
+        backend: &mut dyn term_wm_render::RenderBackend,
+        area: LayoutRect,
+        ctx: &ComponentContext,
+        _registry: &mut term_wm_core::hitbox_registry::HitboxRegistry,
+    ) {
+        let rect = layout_rect_to_clipped_rect(area);
+        if rect.width == 0 || rect.height == 0 {
+            return;
+        }
+        let theme = &ctx.config().theme;
+        let style = self.style.unwrap_or_else(|| BarStyle::from_theme(theme));
+        let fill = if self.critical.is_some_and(|at| self.value >= at) {
+            Style::default().fg(color_to_ratatui(theme.error))
+        } else if self.warning.is_some_and(|at| self.value >= at) {
+            Style::default().fg(color_to_ratatui(theme.warning))
+        } else {
+            style.fill
+        };
+        let buffer = &mut crate::helpers::downcast_ratatui(backend).buffer;
+        let right = rect.x + rect.width;
+        let mut x = rect.x;
+        if let Some(label) = &self.label {
+            let (end, _) = buffer.set_stringn(
+                x,
+                rect.y,
+                format!("{label} "),
+                usize::from(rect.width),
+                style.text,
+            );
+            x = end;
+        }
+        let percent = format!(" {:>3}%", (self.ratio() * 100.0).round() as u32);
+        let bar_end = right.saturating_sub(percent.width() as u16).max(x);
+        let width = bar_end - x;
+        let filled = (self.ratio() * f64::from(width)).round() as u16;
+        for i in 0..width {
+            let (cell, cell_style) = if i < filled {
+                (FULL, fill)
+            } else {
+                (TRACK, style.track)
+            };
+            buffer.set_string(x + i, rect.y, cell, cell_style);
+        }
+        if bar_end < right {
+            buffer.set_stringn(
+                bar_end,
+                rect.y,
+                &percent,
+                usize::from(right - bar_end),
+                style.text,
+            );
+        }
+    }
+
+    fn handle_events(
+        &mut self,
+        _event: &term_wm_core::events::Event,
+        _ctx: &ComponentContext,
+    ) -> EventResult<TermWmAction> {
+        EventResult::Ignored
+    }
+
+    fn update(
+        &mut self,
+        _action: TermWmAction,
+        _ctx: &ComponentContext,
+        _actions: &mut VecDeque<(WindowKey, TermWmAction)>,
+    ) {
+    }
+
+    fn destroy(&mut self) {}
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{buffer_rows, render_buffer};
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::Rect;
+    use ratatui::style::Color;
+    use term_wm_core::theme::NOIR;
+
+    fn plain() -> BarStyle {
+        BarStyle {
+            fill: Style::default().fg(Color::Green),
+            track: Style::default().fg(Color::DarkGray),
+            text: Style::default(),
+        }
+    }
+
+    #[test]
+    fn fills_whole_cells_in_proportion() {
+        let mut gauge = GaugeComponent::new(62)
+            .with_label("CPU")
+            .with_style(plain());
+        let mut expected = Buffer::with_lines(["CPU ██████░░░░  62%"]);
+        expected.set_style(Rect::new(4, 0, 6, 1), plain().fill);
+        expected.set_style(Rect::new(10, 0, 4, 1), plain().track);
+        assert_eq!(render_buffer(&mut gauge, 19, 1), expected);
+
+        let mut gauge = GaugeComponent::new(3).with_max(4);
+        assert_eq!(buffer_rows(&render_buffer(&mut gauge, 9, 1)), ["███░  75%"]);
+        gauge.set_value(9);
+        assert_eq!(gauge.ratio(), 1.0);
+    }
+
+    #[test]
+    fn thresholds_recolor_the_fill() {
+        let theme = NOIR;
+        let fill_color = |value: f64| {
+            let mut gauge = GaugeComponent::new(value)
+                .with_warning(70)
+                .with_critical(90)
+                .with_style(plain());
+            render_buffer(&mut gauge, 10, 1)[(0, 0)].fg
+        };
+        assert_eq!(fill_color(50.0), Color::Green);
+        assert_eq!(fill_color(75.0), color_to_ratatui(theme.warning));
+        assert_eq!(fill_color(95.0), color_to_ratatui(theme.error));
+    }
+}
@@ -59,7 +59,7 @@ pub fn resolve_sizes(dim: u16, constraints: &[GridConstraint]) -> Vec<u16> {
 pub const FRACTION_COL_MIN_WIDTH: u16 = 10;
 
 /// Minimum total width a constraint list needs to keep multiple columns.
-fn min_total_width(cols: &[GridConstraint]) -> u16 {
+pub(crate) fn min_total_width(cols: &[GridConstraint]) -> u16 {
     cols.iter()
         .map(|c| match c {
             GridConstraint::Fixed(n) => *n,
@@ -238,6 +238,21 @@ pub fn role_to_style(
             Style::default().add_modifier(Modifier::DIM)
         }
         term_wm_core::theme::SemanticRole::Normal => Style::default(),
+        term_wm_core::theme::SemanticRole::CodeKeyword => Style::default()
+            .fg(color_to_ratatui(theme.accent))
+            .add_modifier(Modifier::BOLD),
+        term_wm_core::theme::SemanticRole::CodeType => {
+            Style::default().fg(color_to_ratatui(theme.accent_alt))
+        }
+        term_wm_core::theme::SemanticRole::CodeString => {
+            Style::default().fg(color_to_ratatui(theme.success))
+        }
+        term_wm_core::theme::SemanticRole::CodeNumber => {
+            Style::default().fg(color_to_ratatui(theme.warning))
+        }
+        term_wm_core::theme::SemanticRole::CodeComment => Style::default()
+            .fg(color_to_ratatui(theme.text_muted))
+            .add_modifier(Modifier::ITALIC),
     }
 }
 
@@ -346,11 +361,47 @@ pub fn route_mouse_by_rects<C: Component<TermWmAction>>(
     EventResult::Ignored
 }
 
+/// Render `comp` into a fresh `width`×`height` buffer at the origin, for
+/// snapshot tests.
+#[cfg(test)]
+pub(crate) fn render_buffer(
+    comp: &mut impl Component<TermWmAction>,
+    width: u16,
+    height: u16,
+) -> Buffer {
+    let rect = Rect::new(0, 0, width, height);
+    let mut backend = term_wm_console::RatatuiBackend::new_simple(Buffer::empty(rect), rect);
+    let area = LayoutRect {
+        x: 0,
+        y: 0,
+        width,
+        height,
+    };
+    let ctx = ComponentContext::new(true).with_screen_area(area);
+    let mut registry = term_wm_core::hitbox_registry::HitboxRegistry::new();
+    comp.render(&mut backend, area, &ctx, &mut registry);
+    backend.buffer
+}
+
+/// The symbols of each buffer row, trailing spaces trimmed.
+#[cfg(test)]
+pub(crate) fn buffer_rows(buffer: &Buffer) -> Vec<String> {
+    let area = buffer.area;
+    (area.y..area.y + area.height)
+        .map(|y| {
+            (area.x..area.x + area.width)
+                .map(|x| buffer[(x, y)].symbol())
+                .collect::<String>()
+                .trim_end()
+                .to_string()
+        })
+        .collect()
+}
+
 #[allow(clippy::unwrap_used)]
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ratatui::buffer::Buffer;
     use ratatui::layout::Rect;
     use ratatui::style::{Color, Modifier, Style};
     use term_wm_core::theme::{Color as TColor, NOIR, SemanticRole, Theme};
@@ -600,6 +651,11 @@ mod tests {
         let _ = role_to_style(SemanticRole::Muted, theme);
         let _ = role_to_style(SemanticRole::Disabled, theme);
         let _ = role_to_style(SemanticRole::Normal, theme);
+        let _ = role_to_style(SemanticRole::CodeKeyword, theme);
+        let _ = role_to_style(SemanticRole::CodeType, theme);
+        let _ = role_to_style(SemanticRole::CodeString, theme);
+        let _ = role_to_style(SemanticRole::CodeNumber, theme);
+        let _ = role_to_style(SemanticRole::CodeComment, theme);
     }
 
     #[test]
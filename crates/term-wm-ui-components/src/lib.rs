@@ -1,5 +1,6 @@
 extern crate self as term_wm_ui_components;
 
+pub mod animation;
 pub mod ascii_image;
 pub mod button;
 pub mod canvas_scroll_view;
@@ -7,24 +8,38 @@ pub mod center;
 pub mod command_palette;
 pub mod confirm_overlay;
 pub mod dialog_overlay;
+pub mod form;
+pub mod form_overlay;
+pub mod gauge;
 pub mod grid;
 pub mod helpers;
 pub mod label;
 pub mod list;
 pub mod markdown_viewer;
 pub mod menu;
+pub mod pixel_image;
+pub mod progress_bar;
 // `box` is a reserved keyword, so the module uses a raw identifier (loads
 // `src/box.rs`); consumers only ever see the re-exported `BoxComponent`.
 mod r#box;
 pub mod scroll_view;
+pub mod sparkline;
+pub mod spinner;
 pub mod stack;
 
 pub mod svg_image;
+pub mod syntax_highlight;
 pub mod tab_bar;
+pub mod table;
 pub mod terminal;
+pub mod text_area;
+pub mod text_buffer;
+pub mod text_input;
 pub mod text_renderer;
 pub mod toggle_list;
+pub mod tree_view;
 
+pub use animation::{AnimationClock, DEFAULT_FRAME_INTERVAL};
 pub use ascii_image::AsciiImageComponent;
 pub use r#box::BoxComponent;
 pub use button::ButtonComponent;
@@ -33,6 +48,12 @@ pub use center::CenterComponent;
 pub use command_palette::CommandPaletteComponent;
 pub use confirm_overlay::ConfirmOverlayComponent;
 pub use dialog_overlay::DialogOverlayComponent;
+pub use form::{
+    ButtonRole, FieldErrors, FieldValue, FormButton, FormComponent, FormField, FormParser,
+    FormStyle, FormValues,
+};
+pub use form_overlay::FormOverlayComponent;
+pub use gauge::{Gauge, GaugeComponent};
 pub use grid::{
     FRACTION_COL_MIN_WIDTH, GridComponent, GridConstraint, grid_reflows, resolve_sizes,
 };
@@ -40,14 +61,24 @@ pub use label::LabelComponent;
 pub use list::ListComponent;
 pub use markdown_viewer::MarkdownViewerComponent;
 pub use menu::MenuComponent;
+pub use pixel_image::{ImageFit, PixelImageComponent};
+pub use progress_bar::{BarStyle, ProgressBar, ProgressBarComponent};
 pub use scroll_view::{
     ScrollKeyMode, ScrollViewComponent, ScrollbarAxis, ScrollbarDrag, render_scrollbar,
     render_scrollbar_oriented,
 };
+pub use sparkline::{Sparkline, SparklineComponent};
+pub use spinner::{BRAILLE_FRAMES, LINE_FRAMES, Spinner, SpinnerComponent};
 pub use stack::{HStackComponent, VStackComponent};
 
 pub use svg_image::SvgImageComponent;
+pub use syntax_highlight::{TokenKind, highlight_code};
 pub use tab_bar::{TabBarComponent, TabBarEvent, TabItem};
+pub use table::{ColumnAlign, SortDirection, Table, TableColumn, TableComponent, TableStyle};
 pub use terminal::{TerminalComponent, default_shell, default_shell_command};
-pub use text_renderer::TextRendererComponent;
+pub use text_area::TextAreaComponent;
+pub use text_buffer::TextBuffer;
+pub use text_input::{InputHistory, TextInputComponent, TextInputStyle, Validator};
+pub use text_renderer::{TextHighlight, TextRendererComponent};
 pub use toggle_list::{ToggleItem, ToggleListComponent};
+pub use tree_view::{TreeLoader, TreeNode, TreeNodeId, TreeViewComponent, TreeViewStyle};
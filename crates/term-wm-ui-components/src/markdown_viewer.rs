@@ -1,34 +1,97 @@
 use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
 use std::str;
 
-use pulldown_cmark::{Event as MdEvent, Options, Parser, Tag};
+use pulldown_cmark::{Alignment, CodeBlockKind, Event as MdEvent, Options, Parser, Tag};
+use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Modifier, Style};
-use term_wm_core::events::Event;
+use term_wm_core::events::{Event, KeyCode, KeyKind};
+use unicode_width::UnicodeWidthStr;
 
 use std::sync::Arc;
 
-use crate::helpers::{layout_rect_to_clipped_rect, style_to_role};
-use crate::text_renderer::TextRendererComponent;
+use crate::helpers::{color_to_ratatui, layout_rect_to_clipped_rect, style_to_role};
+use crate::svg_image::SvgImageComponent;
+use crate::syntax_highlight::highlight_code;
+use crate::text_renderer::{TextHighlight, TextRendererComponent};
 use term_wm_core::actions::{EventResult, TermWmAction};
 use term_wm_core::components::{Component, ComponentContext};
+use term_wm_core::theme::{SemanticRole, Theme};
 use term_wm_core::utils::linkifier::{LinkFragment, LinkHandler, Linkifier};
 use term_wm_core::window::WindowKey;
 use term_wm_layout_engine::LayoutRect;
 
+const RULE_PLACEHOLDER: &str = "\0RULE\0";
+/// Rows reserved for an inline image unless changed with
+/// [`MarkdownViewerComponent::set_image_rows`].
+const DEFAULT_IMAGE_ROWS: u16 = 12;
+
+/// Renders CommonMark + GFM: headings with `#anchor` links, lists and task
+/// lists, tables with column alignment, footnotes, syntax-highlighted fenced
+/// code and inline images (drawn with [`SvgImageComponent`]; SVG and PNM
+/// files, resolved against [`set_base_dir`](Self::set_base_dir)).
+///
+/// `/` (or Ctrl+F) starts an incremental search: matches are highlighted as
+/// the query is typed, Enter keeps them and `n`/`N` step through them, Esc
+/// clears the search.
+///
+/// Styling follows the theme in the render context: when it differs from the
+/// one the document was last styled with, the document is restyled.
 pub struct MarkdownViewerComponent {
     text: TextRendererComponent,
     link_handler: Option<LinkHandler>,
     linkifier: Linkifier,
     anchors: HashMap<String, usize>,
+    base_dir: Option<PathBuf>,
+    image_rows: u16,
+    images: Vec<InlineImage>,
+    search: SearchState,
+    /// Area of the last render, for scrolling search matches into view.
+    last_area: Rect,
+    /// The markdown last set and the theme it was styled with.
+    source: String,
+    styled_with: Option<Theme>,
+}
+
+/// An image drawn over `rows` blank lines starting at logical line `line`.
+struct InlineImage {
+    line: usize,
+    rows: u16,
+    image: SvgImageComponent,
+}
+
+#[derive(Debug, Default)]
+struct SearchState {
+    query: String,
+    editing: bool,
+    matches: Vec<SearchMatch>,
+    current: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SearchMatch {
+    line: usize,
+    start: usize,
+    end: usize,
+}
+
+/// A GFM table gathered cell by cell until it ends.
+struct TableBuilder {
+    alignments: Vec<Alignment>,
+    rows: Vec<Vec<Vec<LinkFragment>>>,
+    header_rows: usize,
 }
 
 impl fmt::Debug for MarkdownViewerComponent {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("MarkdownViewerComponent")
             .field("text", &"TextRendererComponent")
+            .field("images", &self.images.len())
+            .field("search", &self.search)
             .finish()
     }
 }
@@ -82,17 +145,45 @@ impl MarkdownViewerComponent {
             link_handler: None,
             linkifier: Linkifier::new(),
             anchors: HashMap::new(),
+            base_dir: None,
+            image_rows: DEFAULT_IMAGE_ROWS,
+            images: Vec::new(),
+            search: SearchState::default(),
+            last_area: Rect::default(),
+            source: String::new(),
+            styled_with: None,
         }
     }
 
-    pub fn from_bytes(bytes: &[u8]) -> Self {
+    pub fn from_bytes(bytes: &[u8], theme: &Theme) -> Self {
         let mut mv = Self::new();
-        if let Ok(s) = str::from_utf8(bytes) {
-            mv.set_markdown(s, &term_wm_core::theme::NOIR);
-        }
+        mv.set_markdown_bytes(bytes, theme);
         mv
     }
 
+    /// Read a markdown file, resolving its relative image paths against the
+    /// file's directory.
+    pub fn load_file(&mut self, path: impl AsRef<Path>, theme: &Theme) -> io::Result<()> {
+        let path = path.as_ref();
+        let raw = std::fs::read_to_string(path)?;
+        self.base_dir = path.parent().map(Path::to_path_buf);
+        self.set_markdown(&raw, theme);
+        Ok(())
+    }
+
+    /// Directory that relative image paths are resolved against (the
+    /// current directory when unset). Takes effect on the next
+    /// [`set_markdown`](Self::set_markdown).
+    pub fn set_base_dir(&mut self, dir: Option<PathBuf>) {
+        self.base_dir = dir;
+    }
+
+    /// Height in rows of inline images. Takes effect on the next
+    /// [`set_markdown`](Self::set_markdown).
+    pub fn set_image_rows(&mut self, rows: u16) {
+        self.image_rows = rows.max(1);
+    }
+
     pub fn set_link_handler(&mut self, handler: Option<LinkHandler>) {
         self.link_handler = handler;
     }
@@ -105,16 +196,20 @@ impl MarkdownViewerComponent {
     }
 
     pub fn reset(&mut self) {
+        self.clear_search();
         self.text.reset();
     }
 
-    pub fn set_markdown(&mut self, raw: &str, theme: &term_wm_core::theme::Theme) {
+    pub fn set_markdown(&mut self, raw: &str, theme: &Theme) {
+        self.source = raw.to_string();
+        self.styled_with = Some(theme.clone());
         let parser = Parser::new_ext(raw, Options::all());
 
         let mut lines: Vec<Vec<LinkFragment>> = Vec::new();
         let mut current: Vec<LinkFragment> = Vec::new();
 
         self.anchors.clear();
+        self.images.clear();
 
         let mut list_start: Vec<Option<usize>> = Vec::new();
         let mut list_count: Vec<usize> = Vec::new();
@@ -129,18 +224,27 @@ impl MarkdownViewerComponent {
             CodeBlock,
             Paragraph,
             Link,
+            Image,
+            Table,
+            TableHead,
+            TableCell,
             Other,
         }
 
         let mut tag_stack: Vec<TagKind> = Vec::new();
         let mut bold = false;
         let mut italic = false;
-        let mut in_code_block = false;
         let mut current_link: Option<String> = None;
 
         let mut gathering_anchor = false;
         let mut current_anchor_text = String::new();
 
+        // Fenced code is collected whole, then highlighted line by line.
+        let mut code: Option<(String, String)> = None;
+        // Image destination and alt text while inside `![alt](dest)`.
+        let mut image: Option<(String, String)> = None;
+        let mut table: Option<TableBuilder> = None;
+
         for ev in parser {
             match ev {
                 MdEvent::Start(tag) => match tag {
@@ -169,15 +273,16 @@ impl MarkdownViewerComponent {
                         } else {
                             format!("{}- ", indent)
                         };
-                        current.push(LinkFragment::new(
-                            bullet,
-                            term_wm_core::theme::SemanticRole::Normal,
-                            None,
-                        ));
+                        current.push(LinkFragment::new(bullet, SemanticRole::Normal, None));
                     }
-                    Tag::CodeBlock(_) => {
+                    Tag::CodeBlock(kind) => {
                         tag_stack.push(TagKind::CodeBlock);
-                        in_code_block = true;
+                        flush_current_line(&mut lines, &mut current);
+                        let lang = match kind {
+                            CodeBlockKind::Fenced(lang) => lang.to_string(),
+                            CodeBlockKind::Indented => String::new(),
+                        };
+                        code = Some((lang, String::new()));
                     }
                     Tag::Paragraph => {
                         tag_stack.push(TagKind::Paragraph);
@@ -186,12 +291,50 @@ impl MarkdownViewerComponent {
                         tag_stack.push(TagKind::Link);
                         current_link = Some(dest_url.to_string());
                     }
+                    Tag::Image { dest_url, .. } => {
+                        tag_stack.push(TagKind::Image);
+                        image = Some((dest_url.to_string(), String::new()));
+                    }
                     Tag::Heading { .. } => {
                         tag_stack.push(TagKind::Heading);
                         bold = true;
                         gathering_anchor = true;
                         current_anchor_text.clear();
                     }
+                    Tag::Table(alignments) => {
+                        tag_stack.push(TagKind::Table);
+                        flush_current_line(&mut lines, &mut current);
+                        table = Some(TableBuilder {
+                            alignments,
+                            rows: Vec::new(),
+                            header_rows: 0,
+                        });
+                    }
+                    Tag::TableHead => {
+                        tag_stack.push(TagKind::TableHead);
+                        if let Some(table) = table.as_mut() {
+                            table.rows.push(Vec::new());
+                            table.header_rows += 1;
+                        }
+                    }
+                    Tag::TableRow => {
+                        tag_stack.push(TagKind::Other);
+                        if let Some(table) = table.as_mut() {
+                            table.rows.push(Vec::new());
+                        }
+                    }
+                    Tag::TableCell => tag_stack.push(TagKind::TableCell),
+                    Tag::FootnoteDefinition(label) => {
+                        tag_stack.push(TagKind::Other);
+                        flush_current_line(&mut lines, &mut current);
+                        self.anchors
+                            .insert(format!("fn-{}", slugify(&label)), lines.len());
+                        current.push(LinkFragment::new(
+                            format!("[{label}]: "),
+                            SemanticRole::Muted,
+                            None,
+                        ));
+                    }
                     _ => tag_stack.push(TagKind::Other),
                 },
                 MdEvent::End(_) => {
@@ -209,7 +352,25 @@ impl MarkdownViewerComponent {
                                     push_blank_line(&mut lines);
                                 }
                             }
-                            TagKind::CodeBlock => in_code_block = false,
+                            TagKind::CodeBlock => {
+                                if let Some((lang, body)) = code.take() {
+                                    let indent = "  ".repeat(list_count.len() + 1);
+                                    for tokens in highlight_code(&lang, &body) {
+                                        let mut line = vec![LinkFragment::new(
+                                            &indent,
+                                            SemanticRole::Normal,
+                                            None,
+                                        )];
+                                        line.extend(tokens.into_iter().map(|(text, kind)| {
+                                            LinkFragment::new(text, kind.role(), None)
+                                        }));
+                                        lines.push(line);
+                                    }
+                                }
+                                if !tag_stack.iter().any(|k| matches!(k, TagKind::Item)) {
+                                    push_blank_line(&mut lines);
+                                }
+                            }
                             TagKind::Paragraph => {
                                 flush_current_line(&mut lines, &mut current);
                                 let in_list_item =
@@ -231,11 +392,36 @@ impl MarkdownViewerComponent {
                             TagKind::Link => {
                                 current_link = None;
                             }
+                            TagKind::Image => {
+                                if let Some((dest, alt)) = image.take() {
+                                    self.push_image(&mut lines, &mut current, dest, alt);
+                                }
+                            }
+                            TagKind::TableCell => {
+                                if let Some(row) = table.as_mut().and_then(|t| t.rows.last_mut()) {
+                                    row.push(std::mem::take(&mut current));
+                                }
+                            }
+                            TagKind::TableHead => {}
+                            TagKind::Table => {
+                                if let Some(table) = table.take() {
+                                    lines.extend(table.into_lines());
+                                }
+                                push_blank_line(&mut lines);
+                            }
                             TagKind::Other => {}
                         }
                     }
                 }
                 MdEvent::Text(text) => {
+                    if let Some((_, body)) = code.as_mut() {
+                        body.push_str(&text);
+                        continue;
+                    }
+                    if let Some((_, alt)) = image.as_mut() {
+                        alt.push_str(&text);
+                        continue;
+                    }
                     let mut base_style = Style::default();
                     if bold {
                         base_style = base_style.add_modifier(Modifier::BOLD);
@@ -243,9 +429,6 @@ impl MarkdownViewerComponent {
                     if italic {
                         base_style = base_style.add_modifier(Modifier::ITALIC);
                     }
-                    if in_code_block {
-                        base_style = Style::default().fg(Color::Yellow);
-                    }
 
                     if gathering_anchor {
                         current_anchor_text.push_str(&text);
@@ -257,10 +440,10 @@ impl MarkdownViewerComponent {
                         current_link.clone(),
                     ));
                 }
-                MdEvent::Html(text) => {
+                MdEvent::Html(text) | MdEvent::InlineHtml(text) => {
                     current.push(LinkFragment::new(
                         text.to_string(),
-                        term_wm_core::theme::SemanticRole::Normal,
+                        SemanticRole::Normal,
                         current_link.clone(),
                     ));
                 }
@@ -275,25 +458,37 @@ impl MarkdownViewerComponent {
                         current_link.clone(),
                     ));
                 }
-                MdEvent::SoftBreak => {
-                    if in_code_block {
-                        flush_current_line(&mut lines, &mut current);
-                    } else {
-                        current.push(LinkFragment::new(
-                            " ",
-                            term_wm_core::theme::SemanticRole::Normal,
-                            None,
-                        ));
+                MdEvent::TaskListMarker(checked) => {
+                    // Replace the item's "- " bullet with a checkbox.
+                    if let Some(bullet) = current.last_mut()
+                        && let Some(indent) = bullet.text.strip_suffix("- ")
+                    {
+                        bullet.text = indent.to_string();
                     }
+                    let (glyph, role) = if checked {
+                        ("☑ ", SemanticRole::Success)
+                    } else {
+                        ("☐ ", SemanticRole::Muted)
+                    };
+                    current.push(LinkFragment::new(glyph, role, None));
+                }
+                MdEvent::FootnoteReference(label) => {
+                    current.push(LinkFragment::new(
+                        format!("[{label}]"),
+                        SemanticRole::Normal,
+                        Some(format!("#fn-{}", slugify(&label))),
+                    ));
+                }
+                MdEvent::SoftBreak => {
+                    current.push(LinkFragment::new(" ", SemanticRole::Normal, None));
                 }
                 MdEvent::HardBreak => {
                     flush_current_line(&mut lines, &mut current);
                 }
                 MdEvent::Rule => {
-                    const RULE_PLACEHOLDER: &str = "\0RULE\0";
                     lines.push(vec![LinkFragment::new(
                         RULE_PLACEHOLDER.to_string(),
-                        term_wm_core::theme::SemanticRole::Normal,
+                        SemanticRole::Normal,
                         None,
                     )]);
                 }
@@ -310,9 +505,64 @@ impl MarkdownViewerComponent {
         let linkified = self.linkifier.linkify_fragments(lines, theme);
         self.text.set_linkified_text(linkified, theme);
         self.text.set_wrap(true);
+        self.refresh_search(false);
+    }
+
+    /// Restyle the current document if `theme` is not the one it was styled
+    /// with. Text, scroll position and search are kept.
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        if self.styled_with.as_ref() == Some(theme) {
+            return;
+        }
+        let source = std::mem::take(&mut self.source);
+        self.set_markdown(&source, theme);
+    }
+
+    /// Reserve rows for an image that loads, or fall back to its alt text.
+    fn push_image(
+        &mut self,
+        lines: &mut Vec<Vec<LinkFragment>>,
+        current: &mut Vec<LinkFragment>,
+        dest: String,
+        alt: String,
+    ) {
+        let Some(image) = self.load_image(&dest) else {
+            let label = if alt.is_empty() { &dest } else { &alt };
+            current.push(LinkFragment::new(
+                format!("[image: {label}]"),
+                SemanticRole::Muted,
+                Some(dest),
+            ));
+            return;
+        };
+        flush_current_line(lines, current);
+        self.images.push(InlineImage {
+            line: lines.len(),
+            rows: self.image_rows,
+            image,
+        });
+        for _ in 0..self.image_rows {
+            push_blank_line(lines);
+        }
+        if !alt.is_empty() {
+            lines.push(vec![LinkFragment::new(alt, SemanticRole::Muted, None)]);
+        }
+    }
+
+    fn load_image(&self, dest: &str) -> Option<SvgImageComponent> {
+        if dest.contains("://") {
+            return None;
+        }
+        let path = match &self.base_dir {
+            Some(dir) => dir.join(dest),
+            None => PathBuf::from(dest),
+        };
+        let mut image = SvgImageComponent::new();
+        image.load_from_path(path).ok()?;
+        Some(image)
     }
 
-    pub fn set_markdown_bytes(&mut self, bytes: &[u8], theme: &term_wm_core::theme::Theme) {
+    pub fn set_markdown_bytes(&mut self, bytes: &[u8], theme: &Theme) {
         if let Ok(s) = str::from_utf8(bytes) {
             self.set_markdown(s, theme);
         }
@@ -322,6 +572,97 @@ impl MarkdownViewerComponent {
         self.text.rendered_lines()
     }
 
+    /// Open the search prompt; typed characters extend the query.
+    pub fn start_search(&mut self) {
+        self.search.editing = true;
+    }
+
+    /// Search for `query` and scroll the first match at or after the current
+    /// one into view. Lowercase queries match case-insensitively.
+    pub fn set_search(&mut self, query: impl Into<String>) {
+        self.search.query = query.into();
+        self.refresh_search(true);
+    }
+
+    pub fn clear_search(&mut self) {
+        self.search = SearchState::default();
+        self.text.set_highlights(Vec::new());
+    }
+
+    pub fn search_query(&self) -> &str {
+        &self.search.query
+    }
+
+    /// Whether the search prompt is open or matches are highlighted.
+    pub fn is_searching(&self) -> bool {
+        self.search.editing || !self.search.query.is_empty()
+    }
+
+    pub fn search_match_count(&self) -> usize {
+        self.search.matches.len()
+    }
+
+    /// Index of the current match, if any matched.
+    pub fn current_match(&self) -> Option<usize> {
+        (!self.search.matches.is_empty()).then_some(self.search.current)
+    }
+
+    pub fn next_match(&mut self) {
+        let count = self.search.matches.len();
+        if count > 0 {
+            self.search.current = (self.search.current + 1) % count;
+            self.reveal_current_match();
+        }
+    }
+
+    pub fn prev_match(&mut self) {
+        let count = self.search.matches.len();
+        if count > 0 {
+            self.search.current = (self.search.current + count - 1) % count;
+            self.reveal_current_match();
+        }
+    }
+
+    /// Recompute matches for the query, keeping the position in the
+    /// document: the new current match is the first one at or after the old.
+    fn refresh_search(&mut self, reveal: bool) {
+        let anchor = self
+            .search
+            .matches
+            .get(self.search.current)
+            .map(|m| (m.line, m.start))
+            .unwrap_or_default();
+        self.search.matches = if self.search.query.is_empty() {
+            Vec::new()
+        } else {
+            find_matches(&self.text.rendered_lines(), &self.search.query)
+        };
+        self.search.current = self
+            .search
+            .matches
+            .iter()
+            .position(|m| (m.line, m.start) >= anchor)
+            .unwrap_or(0);
+        if reveal {
+            self.reveal_current_match();
+        }
+    }
+
+    fn reveal_current_match(&mut self) {
+        let Some(m) = self.search.matches.get(self.search.current) else {
+            return;
+        };
+        let area = self.last_area;
+        if area.width == 0 {
+            return;
+        }
+        let visible = area.height.saturating_sub(1) as isize;
+        let row = self.text.line_row_in_view(m.line, area.width);
+        if row < 0 || row >= visible {
+            self.text.jump_to_logical_line(m.line, area);
+        }
+    }
+
     pub fn handle_pointer_event(
         &mut self,
         event: &Event,
@@ -381,7 +722,37 @@ impl MarkdownViewerComponent {
         key: &term_wm_core::events::KeyEvent,
         ctx: &ComponentContext,
     ) -> EventResult<TermWmAction> {
-        self.text.handle_events(&Event::Key(*key), ctx)
+        if key.kind != KeyKind::Press {
+            return self.text.handle_events(&Event::Key(*key), ctx);
+        }
+        let plain = !key.modifiers.control && !key.modifiers.alt;
+        if self.search.editing {
+            match key.code {
+                KeyCode::Char(c) if plain => {
+                    let mut query = std::mem::take(&mut self.search.query);
+                    query.push(c);
+                    self.set_search(query);
+                }
+                KeyCode::Backspace => {
+                    let mut query = std::mem::take(&mut self.search.query);
+                    query.pop();
+                    self.set_search(query);
+                }
+                KeyCode::Enter => self.search.editing = false,
+                KeyCode::Esc => self.clear_search(),
+                _ => return self.text.handle_events(&Event::Key(*key), ctx),
+            }
+            return EventResult::Consumed;
+        }
+        match key.code {
+            KeyCode::Char('/') if plain => self.start_search(),
+            KeyCode::Char('f') if key.modifiers.control => self.start_search(),
+            KeyCode::Char('n') if plain && !self.search.query.is_empty() => self.next_match(),
+            KeyCode::Char('N') if plain && !self.search.query.is_empty() => self.prev_match(),
+            KeyCode::Esc if self.is_searching() => self.clear_search(),
+            _ => return self.text.handle_events(&Event::Key(*key), ctx),
+        }
+        EventResult::Consumed
     }
 
     pub fn render_content(
@@ -391,7 +762,72 @@ impl MarkdownViewerComponent {
         ctx: &ComponentContext,
         registry: &mut term_wm_core::hitbox_registry::HitboxRegistry,
     ) {
+        let rect = layout_rect_to_clipped_rect(area);
+        self.last_area = rect;
+        let theme = &ctx.config().theme;
+        self.apply_theme(theme);
+        let current = Style::default()
+            .fg(color_to_ratatui(theme.background))
+            .bg(color_to_ratatui(theme.accent));
+        let other = Style::default().bg(color_to_ratatui(theme.selection_bg));
+        self.text.set_highlights(
+            self.search
+                .matches
+                .iter()
+                .enumerate()
+                .map(|(i, m)| TextHighlight {
+                    line: m.line,
+                    start: m.start,
+                    end: m.end,
+                    style: if i == self.search.current {
+                        current
+                    } else {
+                        other
+                    },
+                })
+                .collect(),
+        );
         self.text.render(backend, area, ctx, registry);
+        if rect.width == 0 || rect.height == 0 {
+            return;
+        }
+        let buffer = &mut crate::helpers::downcast_ratatui(backend).buffer;
+        for image in &mut self.images {
+            let top = self.text.line_row_in_view(image.line, rect.width);
+            draw_image_rows(buffer, rect, top, image, ctx);
+        }
+        if self.is_searching() {
+            self.render_search_bar(buffer, rect, theme);
+        }
+    }
+
+    fn render_search_bar(&self, buffer: &mut Buffer, area: Rect, theme: &Theme) {
+        let y = area.y + area.height - 1;
+        let width = usize::from(area.width);
+        let style = Style::default()
+            .fg(color_to_ratatui(theme.text))
+            .bg(color_to_ratatui(theme.surface));
+        buffer.set_stringn(area.x, y, " ".repeat(width), width, style);
+        let status = match (self.search.matches.len(), self.search.query.is_empty()) {
+            (_, true) => String::new(),
+            (0, false) => "no matches".to_string(),
+            (count, false) => format!("{}/{count}", self.search.current + 1),
+        };
+        let status_x = area.x + area.width.saturating_sub(status.width() as u16);
+        buffer.set_stringn(
+            area.x,
+            y,
+            format!("/{}", self.search.query),
+            usize::from(status_x - area.x).saturating_sub(1),
+            style,
+        );
+        buffer.set_stringn(
+            status_x,
+            y,
+            &status,
+            width,
+            style.fg(color_to_ratatui(theme.text_muted)),
+        );
     }
 }
 
@@ -401,6 +837,154 @@ impl Default for MarkdownViewerComponent {
     }
 }
 
+/// Draw the rows of `image` that fall inside `area` when its first row is
+/// `top` rows below the area's top (negative once scrolled past).
+fn draw_image_rows(
+    buffer: &mut Buffer,
+    area: Rect,
+    top: isize,
+    image: &mut InlineImage,
+    ctx: &ComponentContext,
+) {
+    let rows = image.rows as isize;
+    let height = area.height as isize;
+    if top >= height || top + rows <= 0 {
+        return;
+    }
+    // Render the whole image off-screen so partially scrolled images keep
+    // their proportions, then copy the visible rows.
+    let scratch_area = Rect::new(0, 0, area.width, image.rows);
+    let mut scratch =
+        term_wm_console::RatatuiBackend::new_simple(Buffer::empty(scratch_area), scratch_area);
+    image.image.render(
+        &mut scratch,
+        LayoutRect {
+            x: 0,
+            y: 0,
+            width: area.width,
+            height: image.rows,
+        },
+        ctx,
+        &mut term_wm_core::hitbox_registry::HitboxRegistry::new(),
+    );
+    for row in top.max(0)..(top + rows).min(height) {
+        let src_y = (row - top) as u16;
+        for x in 0..area.width {
+            if let Some(cell) = buffer.cell_mut((area.x + x, area.y + row as u16)) {
+                *cell = scratch.buffer[(x, src_y)].clone();
+            }
+        }
+    }
+}
+
+impl TableBuilder {
+    /// Lay the table out as text: cells padded to their column width and
+    /// aligned, `│` between columns and a rule under the header.
+    fn into_lines(self) -> Vec<Vec<LinkFragment>> {
+        let columns = self
+            .rows
+            .iter()
+            .map(Vec::len)
+            .max()
+            .unwrap_or(0)
+            .max(self.alignments.len());
+        let cell_width =
+            |cell: &Vec<LinkFragment>| -> usize { cell.iter().map(|f| f.text.width()).sum() };
+        let mut widths = vec![1usize; columns];
+        for row in &self.rows {
+            for (col, cell) in row.iter().enumerate() {
+                widths[col] = widths[col].max(cell_width(cell));
+            }
+        }
+        let separator = || LinkFragment::new(" │ ", SemanticRole::Muted, None);
+        let mut lines = Vec::with_capacity(self.rows.len() + 1);
+        for (index, row) in self.rows.into_iter().enumerate() {
+            let header = index < self.header_rows;
+            let mut line = Vec::new();
+            let mut cells = row.into_iter();
+            for (col, width) in widths.iter().enumerate() {
+                if col > 0 {
+                    line.push(separator());
+                }
+                let mut cell = cells.next().unwrap_or_default();
+                let pad = width - cell_width(&cell);
+                let (left, right) = match self.alignments.get(col) {
+                    Some(Alignment::Right) => (pad, 0),
+                    Some(Alignment::Center) => (pad / 2, pad - pad / 2),
+                    _ => (0, pad),
+                };
+                if header {
+                    for fragment in &mut cell {
+                        if fragment.role == SemanticRole::Normal {
+                            fragment.role = SemanticRole::Bold;
+                        }
+                    }
+                }
+                if left > 0 {
+                    line.push(LinkFragment::new(
+                        " ".repeat(left),
+                        SemanticRole::Normal,
+                        None,
+                    ));
+                }
+                line.extend(cell);
+                if right > 0 {
+                    line.push(LinkFragment::new(
+                        " ".repeat(right),
+                        SemanticRole::Normal,
+                        None,
+                    ));
+                }
+            }
+            lines.push(line);
+            if index + 1 == self.header_rows {
+                let rule = widths
+                    .iter()
+                    .map(|&w| "─".repeat(w))
+                    .collect::<Vec<_>>()
+                    .join("─┼─");
+                lines.push(vec![LinkFragment::new(rule, SemanticRole::Muted, None)]);
+            }
+        }
+        lines
+    }
+}
+
+/// Every occurrence of `query` in `lines`, as char ranges. Queries with an
+/// uppercase letter match case-sensitively.
+fn find_matches(lines: &[String], query: &str) -> Vec<SearchMatch> {
+    let fold = !query.chars().any(char::is_uppercase);
+    let normalize = |c: char| {
+        if fold {
+            c.to_lowercase().next().unwrap_or(c)
+        } else {
+            c
+        }
+    };
+    let needle: Vec<char> = query.chars().map(normalize).collect();
+    let mut matches = Vec::new();
+    for (line, text) in lines.iter().enumerate() {
+        if text == RULE_PLACEHOLDER {
+            continue;
+        }
+        let hay: Vec<char> = text.chars().map(normalize).collect();
+        let mut start = 0;
+        while start + needle.len() <= hay.len() {
+            if hay[start..start + needle.len()] == needle[..] {
+                matches.push(SearchMatch {
+                    line,
+                    start,
+                    end: start + needle.len(),
+                });
+                start += needle.len().max(1);
+            } else {
+                start += 1;
+            }
+        }
+    }
+    matches
+}
+
 fn flush_current_line(lines: &mut Vec<Vec<LinkFragment>>, current: &mut Vec<LinkFragment>) {
     if !current.is_empty() {
         lines.push(std::mem::take(current));
@@ -408,11 +992,7 @@ fn flush_current_line(lines: &mut Vec<Vec<LinkFragment>>, current: &mut Vec<Link
 }
 
 fn push_blank_line(lines: &mut Vec<Vec<LinkFragment>>) {
-    lines.push(vec![LinkFragment::new(
-        "",
-        term_wm_core::theme::SemanticRole::Normal,
-        None,
-    )]);
+    lines.push(vec![LinkFragment::new("", SemanticRole::Normal, None)]);
 }
 
 fn slugify(text: &str) -> String {
@@ -781,4 +1361,229 @@ mod markdown_tests {
             "rule should occupy a single visual row regardless of content width"
         );
     }
+
+    fn viewer(md: &str) -> MarkdownViewerComponent {
+        let mut mv = MarkdownViewerComponent::new();
+        mv.set_markdown(md, &term_wm_core::theme::NOIR);
+        mv
+    }
+
+    fn press(mv: &mut MarkdownViewerComponent, code: term_wm_core::events::KeyCode) {
+        use term_wm_core::events::{KeyEvent, KeyModifiers};
+        let key = KeyEvent::new(code, KeyModifiers::NONE, KeyKind::Press);
+        mv.handle_key_event(&key, &ComponentContext::new(true));
+    }
+
+    #[test]
+    fn tables_lay_out_aligned_columns() {
+        let mv = viewer(indoc! {
+            "
+            | Name | Size | Kind |
+            |:-----|-----:|:----:|
+            | a    | 10   | x    |
+            | bbb  | 2    | yy   |
+
+            After.
+            "
+        });
+        assert_eq!(
+            mv.rendered_lines()[..6],
+            [
+                "Name │ Size │ Kind",
+                "─────┼──────┼─────",
+                "a    │   10 │  x  ",
+                "bbb  │    2 │  yy ",
+                "",
+                "After.",
+            ]
+        );
+        let header = &mv.text.text_ref().lines[0];
+        assert!(header.spans[0].style.add_modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn task_lists_and_footnotes() {
+        let mv = viewer(indoc! {
+            "
+            - [x] done
+            - [ ] todo
+
+            See the note[^n].
+
+            [^n]: The note.
+            "
+        });
+        let lines = mv.rendered_lines();
+        assert_eq!(lines[..2], ["☑ done", "☐ todo"]);
+        assert!(lines.contains(&"See the note[n].".to_string()), "{lines:?}");
+        let def = mv.anchors["fn-n"];
+        assert_eq!(lines[def], "[n]: The note.");
+    }
+
+    #[test]
+    fn fenced_code_is_highlighted_per_line() {
+        let mv = viewer(indoc! {
+            "
+            ```rust
+            fn main() {
+                // hi
+            }
+            ```
+            Text.
+            "
+        });
+        let lines = mv.rendered_lines();
+        assert_eq!(
+            lines[..5],
+            ["  fn main() {", "      // hi", "  }", "", "Text."]
+        );
+        let theme = &term_wm_core::theme::NOIR;
+        let text = mv.text.text_ref();
+        let style_of = |line: usize, word: &str| {
+            text.lines[line]
+                .spans
+                .iter()
+                .find(|span| span.content == word)
+                .map(|span| span.style)
+        };
+        assert_eq!(
+            style_of(0, "fn"),
+            Some(crate::helpers::role_to_style(
+                SemanticRole::CodeKeyword,
+                theme
+            ))
+        );
+        assert_eq!(
+            style_of(1, "// hi"),
+            Some(crate::helpers::role_to_style(
+                SemanticRole::CodeComment,
+                theme
+            ))
+        );
+    }
+
+    #[test]
+    fn rendering_with_another_theme_restyles_the_document() {
+        use term_wm_core::theme::{NOIR, PAPER};
+        use term_wm_core::wm_config::WmConfig;
+
+        let mut mv = MarkdownViewerComponent::new();
+        mv.set_markdown("```rust\nfn main() {}\n```\n", &NOIR);
+        let keyword_style = |mv: &MarkdownViewerComponent| {
+            mv.text.text_ref().lines[0]
+                .spans
+                .iter()
+                .find(|span| span.content == "fn")
+                .map(|span| span.style)
+        };
+        let noir = keyword_style(&mv);
+        assert_eq!(
+            noir,
+            Some(crate::helpers::role_to_style(
+                SemanticRole::CodeKeyword,
+                &NOIR
+            ))
+        );
+
+        let rect = Rect::new(0, 0, 20, 4);
+        let mut backend = term_wm_console::RatatuiBackend::new_simple(Buffer::empty(rect), rect);
+        let area = LayoutRect {
+            x: 0,
+            y: 0,
+            width: 20,
+            height: 4,
+        };
+        let config = WmConfig {
+            theme: PAPER,
+            ..WmConfig::default()
+        };
+        let ctx = ComponentContext::new(true).with_config(Arc::new(config));
+        let mut registry = term_wm_core::hitbox_registry::HitboxRegistry::new();
+        mv.render(&mut backend, area, &ctx, &mut registry);
+
+        assert_eq!(
+            keyword_style(&mv),
+            Some(crate::helpers::role_to_style(
+                SemanticRole::CodeKeyword,
+                &PAPER
+            ))
+        );
+        assert_ne!(keyword_style(&mv), noir);
+        assert_eq!(mv.rendered_lines()[0], "  fn main() {}");
+    }
+
+    #[test]
+    fn search_highlights_matches_and_steps_through_them() {
+        use crate::helpers::{buffer_rows, render_buffer};
+        use term_wm_core::events::KeyCode;
+
+        let mut mv = viewer("alpha beta\n\nBeta gamma beta\n");
+        press(&mut mv, KeyCode::Char('/'));
+        for c in "beta".chars() {
+            press(&mut mv, KeyCode::Char(c));
+        }
+        press(&mut mv, KeyCode::Enter);
+        assert_eq!(mv.search_query(), "beta");
+        assert_eq!(mv.search_match_count(), 3, "lowercase queries ignore case");
+        assert_eq!(mv.current_match(), Some(0));
+
+        let buffer = render_buffer(&mut mv, 20, 4);
+        assert_eq!(
+            buffer_rows(&buffer),
+            ["alpha beta", "", "Beta gamma beta", "/beta            1/3"]
+        );
+        let theme = &term_wm_core::theme::NOIR;
+        assert_eq!(buffer[(6, 0)].bg, color_to_ratatui(theme.accent));
+        assert_eq!(buffer[(0, 2)].bg, color_to_ratatui(theme.selection_bg));
+        assert_eq!(buffer[(5, 0)].bg, Color::Reset);
+
+        press(&mut mv, KeyCode::Char('n'));
+        press(&mut mv, KeyCode::Char('n'));
+        assert_eq!(mv.current_match(), Some(2));
+        press(&mut mv, KeyCode::Char('n'));
+        assert_eq!(mv.current_match(), Some(0));
+        press(&mut mv, KeyCode::Char('N'));
+        assert_eq!(mv.current_match(), Some(2));
+
+        mv.set_search("Beta");
+        assert_eq!(
+            mv.search_match_count(),
+            1,
+            "uppercase makes it case-sensitive"
+        );
+        press(&mut mv, KeyCode::Esc);
+        assert!(!mv.is_searching());
+        assert_eq!(
+            buffer_rows(&render_buffer(&mut mv, 20, 4)),
+            ["alpha beta", "", "Beta gamma beta", ""]
+        );
+    }
+
+    #[test]
+    fn images_render_inline_or_fall_back_to_alt_text() {
+        use crate::helpers::{buffer_rows, render_buffer};
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut pgm = b"P5\n4 4\n255\n".to_vec();
+        pgm.extend([255u8; 16]);
+        std::fs::write(dir.path().join("logo.pgm"), pgm).unwrap();
+
+        let mut mv = MarkdownViewerComponent::new();
+        mv.set_base_dir(Some(dir.path().to_path_buf()));
+        mv.set_image_rows(3);
+        mv.set_markdown(
+            "Top\n\n![The logo](logo.pgm)\n\n![gone](missing.pgm)\n",
+            &term_wm_core::theme::NOIR,
+        );
+        let lines = mv.rendered_lines();
+        assert_eq!(lines[..7], ["Top", "", "", "", "", "The logo", ""]);
+        assert_eq!(lines[7], "[image: gone]");
+
+        let rows = buffer_rows(&render_buffer(&mut mv, 10, 8));
+        assert_eq!(rows[..2], ["Top", ""]);
+        for row in &rows[2..5] {
+            assert!(!row.is_empty(), "image rows are drawn: {rows:?}");
+        }
+        assert_eq!(rows[5], "The logo");
+    }
 }
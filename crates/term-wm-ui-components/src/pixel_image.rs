@@ -0,0 +1,588 @@
+use std::io::{self, Cursor};
+use std::path::Path;
+use std::time::Duration;
+
+use image::codecs::gif::GifDecoder;
+use image::imageops::{self, FilterType};
+use image::{AnimationDecoder, ImageFormat, RgbaImage};
+use resvg::{tiny_skia, usvg};
+use term_wm_core::actions::TermWmAction;
+use term_wm_core::components::{Component, ComponentContext};
+use term_wm_core::term_color::ColorDepth;
+use term_wm_core::theme::Color;
+use term_wm_layout_engine::LayoutRect;
+
+use crate::animation::AnimationClock;
+use crate::helpers::{color_to_ratatui, layout_rect_to_clipped_rect};
+
+const MAX_SVG_DIM: u32 = 1024;
+/// Browsers play zero-delay GIF frames at roughly this rate.
+const DEFAULT_GIF_DELAY: Duration = Duration::from_millis(100);
+
+/// How an image is scaled into the component's area.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ImageFit {
+    /// Scale to fit inside the area, keeping the aspect ratio (letterboxed).
+    #[default]
+    Fit,
+    /// Scale to cover the area, keeping the aspect ratio (cropped).
+    Fill,
+    /// Scale to exactly the area, ignoring the aspect ratio.
+    Stretch,
+}
+
+struct ImageFrame {
+    pixels: RgbaImage,
+    delay: Duration,
+}
+
+/// Quantized pixels of the current frame, laid out over the whole area at
+/// two pixel rows per cell row. `None` is a transparent pixel.
+struct PixelCache {
+    key: CacheKey,
+    width: usize,
+    pixels: Vec<Option<Color>>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct CacheKey {
+    cols: u16,
+    rows: u16,
+    frame: usize,
+    fit: ImageFit,
+    depth: ColorDepth,
+    background: (u8, u8, u8),
+}
+
+/// Displays an RGB(A) image at two pixels per cell.
+///
+/// Each cell is an upper half block (`▀`) whose foreground is the top pixel
+/// and background the bottom pixel. Images decode from PNG, JPEG, GIF, or
+/// SVG (rasterized with resvg). On terminals without truecolor the pixels
+/// are Floyd–Steinberg dithered to the xterm-256 or 16-color palette; the
+/// depth comes from [`ColorDepth::current`] unless set explicitly.
+///
+/// Animated GIFs play on an [`AnimationClock`] (the global one, driven by the
+/// app's task scheduler, unless [`with_clock`](Self::with_clock) is used),
+/// honoring each frame's delay to the resolution of the clock's interval.
+/// Semi-transparent pixels are blended over the theme background; fully
+/// transparent ones leave the cell underneath showing.
+pub struct PixelImageComponent {
+    frames: Vec<ImageFrame>,
+    fit: ImageFit,
+    depth: Option<ColorDepth>,
+    clock: AnimationClock,
+    playing: bool,
+    /// Clock frame at which playback (re)started; set on the next render.
+    started_at: Option<u64>,
+    /// Playback position when playback (re)started.
+    offset: Duration,
+    cache: Option<PixelCache>,
+}
+
+impl PixelImageComponent {
+    pub fn new() -> Self {
+        Self {
+            frames: Vec::new(),
+            fit: ImageFit::Fit,
+            depth: None,
+            clock: AnimationClock::global(),
+            playing: true,
+            started_at: None,
+            offset: Duration::ZERO,
+            cache: None,
+        }
+    }
+
+    pub fn with_fit(mut self, fit: ImageFit) -> Self {
+        self.set_fit(fit);
+        self
+    }
+
+    /// Force a color depth instead of detecting it from the environment.
+    pub fn with_color_depth(mut self, depth: ColorDepth) -> Self {
+        self.depth = Some(depth);
+        self
+    }
+
+    /// Play animations on `clock` instead of [`AnimationClock::global`].
+    pub fn with_clock(mut self, clock: AnimationClock) -> Self {
+        self.clock = clock;
+        self.started_at = None;
+        self
+    }
+
+    pub fn set_fit(&mut self, fit: ImageFit) {
+        self.fit = fit;
+    }
+
+    pub fn fit(&self) -> ImageFit {
+        self.fit
+    }
+
+    pub fn set_color_depth(&mut self, depth: Option<ColorDepth>) {
+        self.depth = depth;
+    }
+
+    /// Size of the image in pixels, or `None` when nothing is loaded.
+    pub fn dimensions(&self) -> Option<(u32, u32)> {
+        self.frames.first().map(|frame| frame.pixels.dimensions())
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Index of the animation frame currently shown.
+    pub fn current_frame(&self) -> usize {
+        let total: Duration = self.frames.iter().map(|frame| frame.delay).sum();
+        if self.frames.len() < 2 || total.is_zero() {
+            return 0;
+        }
+        let mut at = Duration::from_nanos((self.position().as_nanos() % total.as_nanos()) as u64);
+        for (idx, frame) in self.frames.iter().enumerate() {
+            if at < frame.delay {
+                return idx;
+            }
+            at -= frame.delay;
+        }
+        0
+    }
+
+    /// Pause or resume an animated image.
+    pub fn set_playing(&mut self, playing: bool) {
+        if playing == self.playing {
+            return;
+        }
+        self.offset = self.position();
+        self.started_at = None;
+        self.playing = playing;
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    pub fn clear(&mut self) {
+        self.frames.clear();
+        self.cache = None;
+        self.started_at = None;
+        self.offset = Duration::ZERO;
+    }
+
+    /// Show a single still frame of `width * height` RGBA pixels. A buffer of
+    /// the wrong size clears the image.
+    pub fn set_rgba8(&mut self, width: u32, height: u32, rgba: Vec<u8>) {
+        self.clear();
+        if let Some(pixels) = RgbaImage::from_raw(width, height, rgba)
+            && width > 0
+            && height > 0
+        {
+            self.frames.push(ImageFrame {
+                pixels,
+                delay: Duration::ZERO,
+            });
+        }
+    }
+
+    pub fn load_from_path<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        let bytes = std::fs::read(path)?;
+        self.load_from_bytes(&bytes)
+    }
+
+    /// Decode a PNG, JPEG, GIF (all frames) or SVG image.
+    pub fn load_from_bytes(&mut self, bytes: &[u8]) -> io::Result<()> {
+        let frames = match image::guess_format(bytes) {
+            Ok(ImageFormat::Gif) => decode_gif(bytes)?,
+            Ok(format) => {
+                let pixels = image::load_from_memory_with_format(bytes, format)
+                    .map_err(invalid_data)?
+                    .into_rgba8();
+                vec![ImageFrame {
+                    pixels,
+                    delay: Duration::ZERO,
+                }]
+            }
+            Err(_) => return self.load_svg_from_bytes(bytes),
+        };
+        self.clear();
+        self.frames = frames;
+        Ok(())
+    }
+
+    pub fn load_svg_from_bytes(&mut self, bytes: &[u8]) -> io::Result<()> {
+        let tree = usvg::Tree::from_data(bytes, &usvg::Options::default()).map_err(invalid_data)?;
+        let size = tree.size().to_int_size();
+        let max_dim = size.width().max(size.height());
+        let scale = if max_dim > MAX_SVG_DIM {
+            MAX_SVG_DIM as f32 / max_dim as f32
+        } else {
+            1.0
+        };
+        let width = ((size.width() as f32 * scale).round() as u32).max(1);
+        let height = ((size.height() as f32 * scale).round() as u32).max(1);
+        let mut pixmap = tiny_skia::Pixmap::new(width, height)
+            .ok_or_else(|| invalid_data("pixmap alloc failed"))?;
+        resvg::render(
+            &tree,
+            tiny_skia::Transform::from_scale(scale, scale),
+            &mut pixmap.as_mut(),
+        );
+        // tiny-skia stores premultiplied alpha.
+        let rgba = pixmap
+            .pixels()
+            .iter()
+            .flat_map(|px| {
+                let c = px.demultiply();
+                [c.red(), c.green(), c.blue(), c.alpha()]
+            })
+            .collect();
+        self.set_rgba8(width, height, rgba);
+        Ok(())
+    }
+
+    fn position(&self) -> Duration {
+        match self.started_at {
+            Some(start) if self.playing => {
+                let ticks = self.clock.frame().wrapping_sub(start);
+                self.offset + self.clock.interval() * ticks.min(u64::from(u32::MAX)) as u32
+            }
+            _ => self.offset,
+        }
+    }
+
+    fn rebuild_cache(&mut self, key: CacheKey) {
+        let cols = usize::from(key.cols);
+        let rows = usize::from(key.rows) * 2;
+        let mut pixels = vec![None; cols * rows];
+        let source = &self.frames[key.frame].pixels;
+        let (scaled, dx, dy) = scale_into(source, key.cols.into(), (rows) as u32, key.fit);
+        // Blend onto the background, then diffuse quantization error
+        // across the visible pixels.
+        let (sw, sh) = (scaled.width() as usize, scaled.height() as usize);
+        let mut work: Vec<Option<[f32; 3]>> = scaled
+            .pixels()
+            .map(|px| {
+                let [r, g, b, a] = px.0;
+                (a > 0).then(|| {
+                    let a = f32::from(a) / 255.0;
+                    let (br, bg, bb) = key.background;
+                    [
+                        f32::from(r) * a + f32::from(br) * (1.0 - a),
+                        f32::from(g) * a + f32::from(bg) * (1.0 - a),
+                        f32::from(b) * a + f32::from(bb) * (1.0 - a),
+                    ]
+                })
+            })
+            .collect();
+        let dither = key.depth != ColorDepth::TrueColor;
+        for y in 0..sh {
+            for x in 0..sw {
+                let Some(rgb) = work[y * sw + x] else {
+                    continue;
+                };
+                let [r, g, b] = rgb.map(|c| c.round().clamp(0.0, 255.0) as u8);
+                let (color, shown) = key.depth.quantize(r, g, b);
+                pixels[(y + dy) * cols + x + dx] = Some(color);
+                if !dither {
+                    continue;
+                }
+                let err = [
+                    rgb[0] - f32::from(shown.0),
+                    rgb[1] - f32::from(shown.1),
+                    rgb[2] - f32::from(shown.2),
+                ];
+                let mut spread = |nx: usize, ny: usize, weight: f32| {
+                    if nx < sw
+                        && ny < sh
+                        && let Some(px) = work[ny * sw + nx].as_mut()
+                    {
+                        for (c, e) in px.iter_mut().zip(err) {
+                            *c += e * weight;
+                        }
+                    }
+                };
+                spread(x + 1, y, 7.0 / 16.0);
+                if x > 0 {
+                    spread(x - 1, y + 1, 3.0 / 16.0);
+                }
+                spread(x, y + 1, 5.0 / 16.0);
+                spread(x + 1, y + 1, 1.0 / 16.0);
+            }
+        }
+        self.cache = Some(PixelCache {
+            key,
+            width: cols,
+            pixels,
+        });
+    }
+}
+
+impl Default for PixelImageComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component<TermWmAction> for PixelImageComponent {
+    fn render(
+        &mut self,
+        backend: &mut dyn term_wm_render::RenderBackend,
+        area: LayoutRect,
+        ctx: &ComponentContext,
+        _registry: &mut term_wm_core::hitbox_registry::HitboxRegistry,
+    ) {
+        let rect = layout_rect_to_clipped_rect(area);
+        if rect.width == 0 || rect.height == 0 || self.frames.is_empty() {
+            return;
+        }
+        if self.frames.len() > 1 && self.playing {
+            self.started_at.get_or_insert(self.clock.frame());
+            self.clock.animate();
+        }
+        let background = match ctx.config().theme.background {
+            Color::Rgb(r, g, b) => (r, g, b),
+            _ => (0, 0, 0),
+        };
+        let key = CacheKey {
+            cols: rect.width,
+            rows: rect.height,
+            frame: self.current_frame(),
+            fit: self.fit,
+            depth: self.depth.unwrap_or_else(ColorDepth::current),
+            background,
+        };
+        if self.cache.as_ref().is_none_or(|cache| cache.key != key) {
+            self.rebuild_cache(key);
+        }
+        let Some(cache) = &self.cache else {
+            return;
+        };
+        let buffer = &mut crate::helpers::downcast_ratatui(backend).buffer;
+        for row in 0..rect.height {
+            let top_row = usize::from(row) * 2 * cache.width;
+            for col in 0..rect.width {
+                let top = cache.pixels[top_row + usize::from(col)];
+                let bottom = cache.pixels[top_row + cache.width + usize::from(col)];
+                let Some(cell) = buffer.cell_mut((rect.x + col, rect.y + row)) else {
+                    continue;
+                };
+                match (top, bottom) {
+                    (Some(top), Some(bottom)) => {
+                        cell.set_symbol("▀")
+                            .set_fg(color_to_ratatui(top))
+                            .set_bg(color_to_ratatui(bottom));
+                    }
+                    (Some(top), None) => {
+                        cell.set_symbol("▀").set_fg(color_to_ratatui(top));
+                    }
+                    (None, Some(bottom)) => {
+                        cell.set_symbol("▄").set_fg(color_to_ratatui(bottom));
+                    }
+                    (None, None) => {}
+                }
+            }
+        }
+    }
+}
+
+/// Resize `source` for a `width` x `height` pixel area, returning the scaled
+/// image and its offset within the area.
+fn scale_into(
+    source: &RgbaImage,
+    width: u32,
+    height: u32,
+    fit: ImageFit,
+) -> (RgbaImage, usize, usize) {
+    let (iw, ih) = (source.width() as f64, source.height() as f64);
+    let (sx, sy) = (f64::from(width) / iw, f64::from(height) / ih);
+    let resize = |w: f64, h: f64| {
+        let (w, h) = ((w.round() as u32).max(1), (h.round() as u32).max(1));
+        if (w, h) == source.dimensions() {
+            source.clone()
+        } else {
+            imageops::resize(source, w, h, FilterType::Triangle)
+        }
+    };
+    match fit {
+        ImageFit::Stretch => (resize(f64::from(width), f64::from(height)), 0, 0),
+        ImageFit::Fit => {
+            let scale = sx.min(sy);
+            let scaled = resize(
+                (iw * scale).min(f64::from(width)),
+                (ih * scale).min(f64::from(height)),
+            );
+            let dx = (width - scaled.width().min(width)) / 2;
+            let dy = (height - scaled.height().min(height)) / 2;
+            (scaled, dx as usize, dy as usize)
+        }
+        ImageFit::Fill => {
+            let scale = sx.max(sy);
+            let scaled = resize(
+                (iw * scale).max(f64::from(width)),
+                (ih * scale).max(f64::from(height)),
+            );
+            let x = (scaled.width() - width) / 2;
+            let y = (scaled.height() - height) / 2;
+            (
+                imageops::crop_imm(&scaled, x, y, width, height).to_image(),
+                0,
+                0,
+            )
+        }
+    }
+}
+
+fn decode_gif(bytes: &[u8]) -> io::Result<Vec<ImageFrame>> {
+    let frames = GifDecoder::new(Cursor::new(bytes))
+        .and_then(|decoder| decoder.into_frames().collect_frames())
+        .map_err(invalid_data)?;
+    Ok(frames
+        .into_iter()
+        .map(|frame| {
+            let delay = Duration::from(frame.delay());
+            ImageFrame {
+                delay: if delay.is_zero() {
+                    DEFAULT_GIF_DELAY
+                } else {
+                    delay
+                },
+                pixels: frame.into_buffer(),
+            }
+        })
+        .collect())
+}
+
+fn invalid_data(err: impl ToString) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err.to_string())
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{buffer_rows, render_buffer};
+    use image::codecs::gif::GifEncoder;
+    use image::{Delay, Frame, Rgba};
+    use ratatui::style::Color as RColor;
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const BLUE: [u8; 4] = [0, 0, 255, 255];
+    const CLEAR: [u8; 4] = [0, 0, 0, 0];
+
+    fn image(width: u32, pixels: &[[u8; 4]]) -> PixelImageComponent {
+        let mut comp = PixelImageComponent::new().with_color_depth(ColorDepth::TrueColor);
+        let height = pixels.len() as u32 / width;
+        comp.set_rgba8(width, height, pixels.concat());
+        comp
+    }
+
+    #[test]
+    fn packs_two_pixels_per_cell() {
+        let mut comp = image(1, &[RED, BLUE]);
+        let buffer = render_buffer(&mut comp, 1, 1);
+        let cell = &buffer[(0, 0)];
+        assert_eq!(cell.symbol(), "▀");
+        assert_eq!(cell.fg, RColor::Rgb(255, 0, 0));
+        assert_eq!(cell.bg, RColor::Rgb(0, 0, 255));
+    }
+
+    #[test]
+    fn transparent_pixels_leave_the_cell_background() {
+        let mut comp = image(3, &[CLEAR, RED, CLEAR, BLUE, CLEAR, CLEAR]);
+        let buffer = render_buffer(&mut comp, 3, 1);
+        assert_eq!(buffer_rows(&buffer), ["▄▀"]);
+        assert_eq!(buffer[(0, 0)].fg, RColor::Rgb(0, 0, 255));
+        assert_eq!(buffer[(0, 0)].bg, RColor::Reset);
+        assert_eq!(buffer[(1, 0)].fg, RColor::Rgb(255, 0, 0));
+    }
+
+    #[test]
+    fn fit_fill_and_stretch_scaling() {
+        let mut comp = image(2, &[RED, BLUE, BLUE, RED]);
+        assert_eq!(buffer_rows(&render_buffer(&mut comp, 4, 1)), [" ▀▀"]);
+        comp.set_fit(ImageFit::Stretch);
+        assert_eq!(buffer_rows(&render_buffer(&mut comp, 4, 1)), ["▀▀▀▀"]);
+        comp.set_fit(ImageFit::Fill);
+        assert_eq!(buffer_rows(&render_buffer(&mut comp, 4, 1)), ["▀▀▀▀"]);
+        // Fill crops the middle rows of the 4x4 upscale.
+        assert_eq!(comp.fit(), ImageFit::Fill);
+    }
+
+    #[test]
+    fn low_color_depths_dither_to_the_palette() {
+        let gray = [180, 180, 180, 255];
+        let mut comp = image(8, &[gray; 64]).with_color_depth(ColorDepth::Ansi16);
+        let buffer = render_buffer(&mut comp, 8, 4);
+        let mut colors = std::collections::HashSet::new();
+        for cell in buffer.content() {
+            for color in [cell.fg, cell.bg] {
+                let RColor::Indexed(idx) = color else {
+                    panic!("expected a palette color, got {color:?}");
+                };
+                assert!(idx < 16);
+                colors.insert(idx);
+            }
+        }
+        assert!(colors.len() > 1, "flat gray should dither: {colors:?}");
+    }
+
+    #[test]
+    fn decodes_png_and_svg() {
+        let mut png = Vec::new();
+        RgbaImage::from_pixel(3, 2, Rgba(RED))
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+        let mut comp = PixelImageComponent::new();
+        comp.load_from_bytes(&png).unwrap();
+        assert_eq!(comp.dimensions(), Some((3, 2)));
+        assert_eq!(comp.frame_count(), 1);
+
+        let svg = br##"<svg xmlns="http://www.w3.org/2000/svg" width="4" height="4">
+            <rect width="4" height="4" fill="#00ff00"/></svg>"##;
+        comp.load_from_bytes(svg).unwrap();
+        assert_eq!(comp.dimensions(), Some((4, 4)));
+        let mut comp = comp.with_color_depth(ColorDepth::TrueColor);
+        assert_eq!(
+            render_buffer(&mut comp, 2, 1)[(0, 0)].fg,
+            RColor::Rgb(0, 255, 0)
+        );
+
+        assert!(comp.load_from_bytes(b"not an image").is_err());
+    }
+
+    #[test]
+    fn gif_frames_follow_the_clock() {
+        let mut gif = Vec::new();
+        {
+            let mut encoder = GifEncoder::new(&mut gif);
+            for (color, ms) in [(RED, 100), (BLUE, 200)] {
+                let frame = Frame::from_parts(
+                    RgbaImage::from_pixel(2, 2, Rgba(color)),
+                    0,
+                    0,
+                    Delay::from_numer_denom_ms(ms, 1),
+                );
+                encoder.encode_frame(frame).unwrap();
+            }
+        }
+        let clock = AnimationClock::new();
+        let mut comp = PixelImageComponent::new()
+            .with_color_depth(ColorDepth::TrueColor)
+            .with_clock(clock.clone());
+        comp.load_from_bytes(&gif).unwrap();
+        assert_eq!(comp.frame_count(), 2);
+
+        let mut shown = Vec::new();
+        for _ in 0..5 {
+            render_buffer(&mut comp, 2, 1);
+            shown.push(comp.current_frame());
+            clock.tick();
+        }
+        assert_eq!(shown, [0, 1, 1, 0, 1]);
+
+        comp.set_playing(false);
+        clock.tick();
+        assert_eq!(comp.current_frame(), 1);
+    }
+}
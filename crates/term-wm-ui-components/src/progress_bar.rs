@@ -0,0 +1,304 @@
+use std::collections::VecDeque;
+
+use ratatui::style::Style;
+use term_wm_core::actions::{EventResult, TermWmAction};
+use term_wm_core::components::{Component, ComponentContext};
+use term_wm_core::theme::Theme;
+use term_wm_core::window::WindowKey;
+use term_wm_layout_engine::LayoutRect;
+use term_wm_view::view_tag;
+use unicode_width::UnicodeWidthStr;
+
+use crate::animation::AnimationClock;
+use crate::helpers::{color_to_ratatui, layout_rect_to_clipped_rect};
+
+/// Partial cells for a fill edge, in eighths (index 1..=7).
+const EIGHTHS: [&str; 8] = [" ", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+pub(crate) const FULL: &str = "█";
+pub(crate) const TRACK: &str = "░";
+
+/// Styles for [`ProgressBarComponent`] and [`GaugeComponent`](crate::GaugeComponent);
+/// [`BarStyle::from_theme`] is the default.
+#[derive(Debug, Clone, Copy)]
+pub struct BarStyle {
+    pub fill: Style,
+    pub track: Style,
+    pub text: Style,
+}
+
+impl BarStyle {
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            fill: Style::default().fg(color_to_ratatui(theme.accent)),
+            track: Style::default().fg(color_to_ratatui(theme.text_muted)),
+            text: Style::default().fg(color_to_ratatui(theme.text)),
+        }
+    }
+}
+
+/// A one-row progress bar: `label ███▊░░░░░  42%`.
+///
+/// Determinate bars fill to [`progress`](Self::progress) with eighth-cell
+/// precision. An [indeterminate](Self::indeterminate) bar shows a block
+/// sweeping back and forth, advanced by an [`AnimationClock`] — frames are
+/// only scheduled while the bar is rendered.
+#[derive(Debug, Clone)]
+pub struct ProgressBarComponent {
+    /// `None` while indeterminate.
+    progress: Option<f64>,
+    label: Option<String>,
+    show_percent: bool,
+    style: Option<BarStyle>,
+    clock: AnimationClock,
+}
+
+#[view_tag(ProgressBar)]
+impl ProgressBarComponent {
+    pub fn new() -> Self {
+        Self {
+            progress: Some(0.0),
+            label: None,
+            show_percent: true,
+            style: None,
+            clock: AnimationClock::global(),
+        }
+    }
+
+    /// Completed fraction, clamped to `0.0..=1.0`.
+    pub fn with_progress(mut self, progress: f64) -> Self {
+        self.set_progress(progress);
+        self
+    }
+
+    pub fn indeterminate(mut self) -> Self {
+        self.progress = None;
+        self
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn hide_percent(mut self) -> Self {
+        self.show_percent = false;
+        self
+    }
+
+    pub fn with_style(mut self, style: BarStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    pub fn with_clock(mut self, clock: AnimationClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn set_progress(&mut self, progress: f64) {
+        self.progress = Some(if progress.is_nan() {
+            0.0
+        } else {
+            progress.clamp(0.0, 1.0)
+        });
+    }
+
+    pub fn set_indeterminate(&mut self) {
+        self.progress = None;
+    }
+
+    /// Completed fraction, or `None` while indeterminate.
+    pub fn progress(&self) -> Option<f64> {
+        self.progress
+    }
+}
+
+impl Default for ProgressBarComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Cell symbols of a bar `width` cells wide filled to `fraction`, with an
+/// eighth-cell partial edge.
+pub(crate) fn fill_cells(fraction: f64, width: usize) -> Vec<&'static str> {
+    let eighths = (fraction.clamp(0.0, 1.0) * (width * 8) as f64).round() as usize;
+    (0..width)
+        .map(|cell| match eighths.saturating_sub(cell * 8) {
+            0 => TRACK,
+            n if n >= 8 => FULL,
+            n => EIGHTHS[n],
+        })
+        .collect()
+}
+
+/// Position of a sweeping block of `block` cells in a `width`-cell track at
+/// animation `frame` (bounces off both ends).
+fn sweep_offset(frame: u64, width: usize, block: usize) -> usize {
+    let travel = width.saturating_sub(block);
+    if travel == 0 {
+        return 0;
+    }
+    let period = (travel * 2) as u64;
+    let step = (frame % period) as usize;
+    if step <= travel {
+        step
+    } else {
+        2 * travel - step
+    }
+}
+
+impl Component<TermWmAction> for ProgressBarComponent {
+    fn desired_height(&self, _width: u16) -> u16 {
+        1
+    }
+
+    fn render(
+        &mut self,
+        backend: &mut dyn term_wm_render::RenderBackend,
+        area: LayoutRect,
+        ctx: &ComponentContext,
+        _registry: &mut term_wm_core::hitbox_registry::HitboxRegistry,
+    ) {
+        let rect = layout_rect_to_clipped_rect(area);
+        if rect.width == 0 || rect.height == 0 {
+            return;
+        }
+        let style = self
+            .style
+            .unwrap_or_else(|| BarStyle::from_theme(&ctx.config().theme));
+        let buffer = &mut crate::helpers::downcast_ratatui(backend).buffer;
+        let mut x = rect.x;
+        let right = rect.x + rect.width;
+
+        if let Some(label) = &self.label {
+            let (end, _) = buffer.set_stringn(
+                x,
+                rect.y,
+                format!("{label} "),
+                usize::from(rect.width),
+                style.text,
+            );
+            x = end;
+        }
+        let percent = match self.progress {
+            Some(progress) if self.show_percent => {
+                format!(" {:>3}%", (progress * 100.0).round() as u32)
+            }
+            _ => String::new(),
+        };
+        let bar_end = right.saturating_sub(percent.width() as u16).max(x);
+        let width = usize::from(bar_end - x);
+
+        match self.progress {
+            Some(progress) => {
+                for (i, cell) in fill_cells(progress, width).into_iter().enumerate() {
+                    let cell_style = if cell == TRACK {
+                        style.track
+                    } else {
+                        style.fill
+                    };
+                    buffer.set_string(x + i as u16, rect.y, cell, cell_style);
+                }
+            }
+            None => {
+                self.clock.animate();
+                let block = (width / 4).max(3).min(width);
+                let start = sweep_offset(self.clock.frame(), width, block);
+                for i in 0..width {
+                    let (cell, cell_style) = if (start..start + block).contains(&i) {
+                        (FULL, style.fill)
+                    } else {
+                        (TRACK, style.track)
+                    };
+                    buffer.set_string(x + i as u16, rect.y, cell, cell_style);
+                }
+            }
+        }
+        if !percent.is_empty() && bar_end < right {
+            buffer.set_stringn(
+                bar_end,
+                rect.y,
+                &percent,
+                usize::from(right - bar_end),
+                style.text,
+            );
+        }
+    }
+
+    fn handle_events(
+        &mut self,
+        _event: &term_wm_core::events::Event,
+        _ctx: &ComponentContext,
+    ) -> EventResult<TermWmAction> {
+        EventResult::Ignored
+    }
+
+    fn update(
+        &mut self,
+        _action: TermWmAction,
+        _ctx: &ComponentContext,
+        _actions: &mut VecDeque<(WindowKey, TermWmAction)>,
+    ) {
+    }
+
+    fn destroy(&mut self) {}
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{buffer_rows, render_buffer};
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::Rect;
+    use ratatui::style::Color;
+
+    fn plain() -> BarStyle {
+        BarStyle {
+            fill: Style::default().fg(Color::Cyan),
+            track: Style::default().fg(Color::DarkGray),
+            text: Style::default(),
+        }
+    }
+
+    #[test]
+    fn determinate_bar_fills_with_eighths() {
+        let mut bar = ProgressBarComponent::new()
+            .with_label("build")
+            .with_progress(0.42)
+            .with_style(plain());
+        let mut expected = Buffer::with_lines(["build ███▊░░░░░  42%"]);
+        expected.set_style(Rect::new(6, 0, 4, 1), plain().fill);
+        expected.set_style(Rect::new(10, 0, 5, 1), plain().track);
+        assert_eq!(render_buffer(&mut bar, 20, 1), expected);
+
+        bar.set_progress(1.5);
+        assert_eq!(bar.progress(), Some(1.0));
+        assert_eq!(
+            buffer_rows(&render_buffer(&mut bar, 16, 1)),
+            ["build █████ 100%"]
+        );
+
+        let mut bare = ProgressBarComponent::new().hide_percent();
+        assert_eq!(buffer_rows(&render_buffer(&mut bare, 4, 1)), ["░░░░"]);
+    }
+
+    #[test]
+    fn indeterminate_bar_sweeps_with_the_clock() {
+        let clock = AnimationClock::new();
+        let mut bar = ProgressBarComponent::new()
+            .indeterminate()
+            .with_clock(clock.clone());
+        assert_eq!(bar.progress(), None);
+        assert_eq!(buffer_rows(&render_buffer(&mut bar, 8, 1)), ["███░░░░░"]);
+        clock.tick();
+        clock.tick();
+        assert_eq!(buffer_rows(&render_buffer(&mut bar, 8, 1)), ["░░███░░░"]);
+        for _ in 0..5 {
+            clock.tick();
+        }
+        // Bounced off the right edge at frame 5.
+        assert_eq!(buffer_rows(&render_buffer(&mut bar, 8, 1)), ["░░░███░░"]);
+    }
+}
@@ -13,7 +13,7 @@ use crate::helpers::layout_rect_to_clipped_rect;
 use ratatui::widgets::StatefulWidget;
 use term_wm_core::actions::{EventResult, TermWmAction};
 use term_wm_core::component_context::{ScrollBounds, ScrollHandle};
-use term_wm_core::components::{Component, ComponentContext, SelectionStatus};
+use term_wm_core::components::{Component, ComponentContext, HostCursor, SelectionStatus};
 use term_wm_core::window::WindowKey;
 use term_wm_layout_engine::LayoutRect;
 
@@ -650,6 +650,12 @@ impl<C: Component<TermWmAction>> Component<TermWmAction> for ScrollViewComponent
         }
     }
 
+    /// Scroll position only moves through events or the content's own
+    /// render, so the content decides.
+    fn needs_render(&self) -> bool {
+        self.content.borrow().needs_render()
+    }
+
     fn destroy(&mut self) {
         self.content.borrow_mut().destroy();
     }
@@ -670,6 +676,10 @@ impl<C: Component<TermWmAction>> Component<TermWmAction> for ScrollViewComponent
         self.content.borrow_mut().take_alternate_screen_transition()
     }
 
+    fn host_cursor(&self) -> Option<HostCursor> {
+        self.content.borrow().host_cursor()
+    }
+
     fn clear_selection(&mut self) {
         self.content.borrow_mut().clear_selection();
     }
@@ -0,0 +1,191 @@
+use std::collections::VecDeque;
+
+use ratatui::style::Style;
+use term_wm_core::actions::{EventResult, TermWmAction};
+use term_wm_core::components::{Component, ComponentContext};
+use term_wm_core::window::WindowKey;
+use term_wm_layout_engine::LayoutRect;
+use term_wm_view::view_tag;
+
+use crate::helpers::{color_to_ratatui, layout_rect_to_clipped_rect};
+
+/// Column heights in eighths of a cell (index 0 is empty).
+const LEVELS: [&str; 9] = [" ", "▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+
+/// A compact series chart, one column per sample: `▁▂▄▇█▅▃`.
+///
+/// The newest samples are drawn right-aligned, so a series longer than the
+/// area shows its tail. Values scale against [`with_max`](Self::with_max),
+/// or the largest visible sample when no maximum is set. Taller sparklines
+/// ([`with_height`](Self::with_height)) stack the eighth-cell levels.
+#[derive(Debug, Clone)]
+pub struct SparklineComponent {
+    data: VecDeque<u64>,
+    capacity: Option<usize>,
+    max: Option<u64>,
+    height: u16,
+    style: Option<Style>,
+}
+
+#[view_tag(Sparkline)]
+impl SparklineComponent {
+    pub fn new(data: impl IntoIterator<Item = u64>) -> Self {
+        Self {
+            data: data.into_iter().collect(),
+            capacity: None,
+            max: None,
+            height: 1,
+            style: None,
+        }
+    }
+
+    pub fn with_max(mut self, max: u64) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Rows to draw; defaults to 1.
+    pub fn with_height(mut self, height: u16) -> Self {
+        self.height = height.max(1);
+        self
+    }
+
+    /// Keep at most `capacity` samples, dropping the oldest on
+    /// [`push`](Self::push).
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = Some(capacity);
+        self.trim();
+        self
+    }
+
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    pub fn push(&mut self, value: u64) {
+        self.data.push_back(value);
+        self.trim();
+    }
+
+    pub fn set_data(&mut self, data: impl IntoIterator<Item = u64>) {
+        self.data = data.into_iter().collect();
+        self.trim();
+    }
+
+    pub fn data(&self) -> &VecDeque<u64> {
+        &self.data
+    }
+
+    fn trim(&mut self) {
+        if let Some(capacity) = self.capacity {
+            while self.data.len() > capacity {
+                self.data.pop_front();
+            }
+        }
+    }
+}
+
+impl Component<TermWmAction> for SparklineComponent {
+    fn desired_height(&self, _width: u16) -> u16 {
+        self.height
+    }
+
+    fn render(
+        &mut self,
+        backend: &mut dyn term_wm_render::RenderBackend,
+        area: LayoutRect,
+        ctx: &ComponentContext,
+        _registry: &mut term_wm_core::hitbox_registry::HitboxRegistry,
+    ) {
+        let rect = layout_rect_to_clipped_rect(area);
+        if rect.width == 0 || rect.height == 0 {
+            return;
+        }
+        let style = self
+            .style
+            .unwrap_or_else(|| Style::default().fg(color_to_ratatui(ctx.config().theme.accent)));
+        let rows = rect.height.min(self.height);
+        let visible = self.data.len().min(usize::from(rect.width));
+        let samples = self.data.iter().skip(self.data.len() - visible);
+        let max = self
+            .max
+            .unwrap_or_else(|| samples.clone().copied().max().unwrap_or(0));
+        if max == 0 {
+            return;
+        }
+        let buffer = &mut crate::helpers::downcast_ratatui(backend).buffer;
+        let levels = u64::from(rows) * 8;
+        let x0 = rect.x + rect.width - visible as u16;
+        let bottom = rect.y + rows - 1;
+        for (i, value) in samples.enumerate() {
+            let height = (value.min(&max) * levels).div_ceil(max) as usize;
+            for row in 0..rows {
+                let level = height.saturating_sub(usize::from(row) * 8).min(8);
+                if level > 0 {
+                    buffer.set_string(x0 + i as u16, bottom - row, LEVELS[level], style);
+                }
+            }
+        }
+    }
+
+    fn handle_events(
+        &mut self,
+        _event: &term_wm_core::events::Event,
+        _ctx: &ComponentContext,
+    ) -> EventResult<TermWmAction> {
+        EventResult::Ignored
+    }
+
+    fn update(
+        &mut self,
+        _action: TermWmAction,
+        _ctx: &ComponentContext,
+        _actions: &mut VecDeque<(WindowKey, TermWmAction)>,
+    ) {
+    }
+
+    fn destroy(&mut self) {}
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{buffer_rows, render_buffer};
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::Rect;
+    use ratatui::style::Color;
+
+    #[test]
+    fn scales_samples_to_eighths() {
+        let style = Style::default().fg(Color::Cyan);
+        let mut spark = SparklineComponent::new([0, 1, 2, 4, 8, 6, 3]).with_style(style);
+        let mut expected = Buffer::with_lines([" ▁▂▄█▆▃"]);
+        expected.set_style(Rect::new(1, 0, 6, 1), style);
+        assert_eq!(render_buffer(&mut spark, 7, 1), expected);
+    }
+
+    #[test]
+    fn shows_the_newest_samples_right_aligned() {
+        let mut spark = SparklineComponent::new([8, 8, 1, 2])
+            .with_max(8)
+            .with_capacity(5);
+        assert_eq!(buffer_rows(&render_buffer(&mut spark, 6, 1)), ["  ██▁▂"]);
+        assert_eq!(buffer_rows(&render_buffer(&mut spark, 2, 1)), ["▁▂"]);
+        for v in [4, 8] {
+            spark.push(v);
+        }
+        assert_eq!(spark.data().len(), 5);
+        assert_eq!(buffer_rows(&render_buffer(&mut spark, 6, 1)), [" █▁▂▄█"]);
+    }
+
+    #[test]
+    fn tall_sparklines_stack_levels() {
+        let mut spark = SparklineComponent::new([16, 12, 4, 0])
+            .with_max(16)
+            .with_height(2);
+        assert_eq!(spark.desired_height(4), 2);
+        assert_eq!(buffer_rows(&render_buffer(&mut spark, 4, 2)), ["█▄", "██▄"]);
+    }
+}
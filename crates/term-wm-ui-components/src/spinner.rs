@@ -0,0 +1,202 @@
+use std::collections::VecDeque;
+
+use ratatui::style::Style;
+use term_wm_core::actions::{EventResult, TermWmAction};
+use term_wm_core::components::{Component, ComponentContext};
+use term_wm_core::window::WindowKey;
+use term_wm_layout_engine::LayoutRect;
+use term_wm_view::view_tag;
+use unicode_width::UnicodeWidthStr;
+
+use crate::animation::AnimationClock;
+use crate::helpers::{color_to_ratatui, layout_rect_to_clipped_rect};
+
+/// Braille dots, the default spinner.
+pub const BRAILLE_FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+/// Plain ASCII spinner for terminals without braille glyphs.
+pub const LINE_FRAMES: &[&str] = &["-", "\\", "|", "/"];
+
+/// A one-row activity indicator: `⠹ Building…`.
+///
+/// The glyph advances with an [`AnimationClock`] while the spinner is
+/// running and rendered; a stopped spinner shows its `done` glyph (blank by
+/// default) and schedules no frames.
+#[derive(Debug, Clone)]
+pub struct SpinnerComponent {
+    label: String,
+    frames: &'static [&'static str],
+    done: &'static str,
+    running: bool,
+    clock: AnimationClock,
+}
+
+#[view_tag(Spinner)]
+impl SpinnerComponent {
+    pub fn new() -> Self {
+        Self {
+            label: String::new(),
+            frames: BRAILLE_FRAMES,
+            done: " ",
+            running: true,
+            clock: AnimationClock::global(),
+        }
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    pub fn with_frames(mut self, frames: &'static [&'static str]) -> Self {
+        if !frames.is_empty() {
+            self.frames = frames;
+        }
+        self
+    }
+
+    /// Glyph shown once the spinner stops, e.g. `"✓"`.
+    pub fn with_done(mut self, done: &'static str) -> Self {
+        self.done = done;
+        self
+    }
+
+    pub fn with_running(mut self, running: bool) -> Self {
+        self.running = running;
+        self
+    }
+
+    pub fn with_clock(mut self, clock: AnimationClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn set_label(&mut self, label: impl Into<String>) {
+        self.label = label.into();
+    }
+
+    pub fn set_running(&mut self, running: bool) {
+        self.running = running;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+}
+
+impl Default for SpinnerComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component<TermWmAction> for SpinnerComponent {
+    fn desired_height(&self, _width: u16) -> u16 {
+        1
+    }
+
+    fn render(
+        &mut self,
+        backend: &mut dyn term_wm_render::RenderBackend,
+        area: LayoutRect,
+        ctx: &ComponentContext,
+        _registry: &mut term_wm_core::hitbox_registry::HitboxRegistry,
+    ) {
+        let rect = layout_rect_to_clipped_rect(area);
+        if rect.width == 0 || rect.height == 0 {
+            return;
+        }
+        let theme = &ctx.config().theme;
+        let glyph = if self.running {
+            self.clock.animate();
+            self.frames[(self.clock.frame() % self.frames.len() as u64) as usize]
+        } else {
+            self.done
+        };
+        let buffer = &mut crate::helpers::downcast_ratatui(backend).buffer;
+        let glyph_style = Style::default().fg(color_to_ratatui(theme.accent));
+        buffer.set_stringn(rect.x, rect.y, glyph, usize::from(rect.width), glyph_style);
+        let x = rect.x + glyph.width() as u16 + 1;
+        if !self.label.is_empty() && x < rect.x + rect.width {
+            buffer.set_stringn(
+                x,
+                rect.y,
+                &self.label,
+                usize::from(rect.x + rect.width - x),
+                Style::default().fg(color_to_ratatui(theme.text)),
+            );
+        }
+    }
+
+    fn handle_events(
+        &mut self,
+        _event: &term_wm_core::events::Event,
+        _ctx: &ComponentContext,
+    ) -> EventResult<TermWmAction> {
+        EventResult::Ignored
+    }
+
+    fn update(
+        &mut self,
+        _action: TermWmAction,
+        _ctx: &ComponentContext,
+        _actions: &mut VecDeque<(WindowKey, TermWmAction)>,
+    ) {
+    }
+
+    fn destroy(&mut self) {}
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{buffer_rows, render_buffer};
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::Rect;
+    use term_wm_core::theme::NOIR;
+
+    #[test]
+    fn glyph_follows_the_clock_while_running() {
+        let clock = AnimationClock::new();
+        let mut spinner = SpinnerComponent::new()
+            .with_label("Building")
+            .with_clock(clock.clone());
+        let theme = NOIR;
+        let mut expected = Buffer::with_lines(["⠋ Building  "]);
+        expected.set_style(
+            Rect::new(0, 0, 1, 1),
+            Style::default().fg(color_to_ratatui(theme.accent)),
+        );
+        expected.set_style(
+            Rect::new(2, 0, 8, 1),
+            Style::default().fg(color_to_ratatui(theme.text)),
+        );
+        assert_eq!(render_buffer(&mut spinner, 12, 1), expected);
+
+        clock.tick();
+        clock.tick();
+        assert_eq!(
+            buffer_rows(&render_buffer(&mut spinner, 12, 1)),
+            ["⠹ Building"]
+        );
+        for _ in 0..8 {
+            clock.tick();
+        }
+        assert_eq!(
+            buffer_rows(&render_buffer(&mut spinner, 12, 1)),
+            ["⠋ Building"]
+        );
+    }
+
+    #[test]
+    fn stopped_spinner_shows_done_glyph() {
+        let mut spinner = SpinnerComponent::new()
+            .with_label("Done")
+            .with_frames(LINE_FRAMES)
+            .with_done("✓")
+            .with_clock(AnimationClock::new());
+        assert_eq!(buffer_rows(&render_buffer(&mut spinner, 8, 1)), ["- Done"]);
+        spinner.set_running(false);
+        assert_eq!(buffer_rows(&render_buffer(&mut spinner, 8, 1)), ["✓ Done"]);
+    }
+}
@@ -0,0 +1,486 @@
+//! A small lexical highlighter for fenced code blocks.
+//!
+//! This is not a parser: each language is a keyword list plus its comment and
+//! string syntax, which is enough to color the common cases in documentation
+//! (`rust`, `python`, `js`/`ts`, `go`, `c`/`cpp`, `java`, `sh`, `json`,
+//! `toml`, `yaml`). Unknown languages come back as plain text.
+
+use term_wm_core::theme::SemanticRole;
+
+/// Lexical class of a highlighted token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Plain,
+    Keyword,
+    Type,
+    String,
+    Number,
+    Comment,
+}
+
+impl TokenKind {
+    pub fn role(self) -> SemanticRole {
+        match self {
+            TokenKind::Plain => SemanticRole::Normal,
+            TokenKind::Keyword => SemanticRole::CodeKeyword,
+            TokenKind::Type => SemanticRole::CodeType,
+            TokenKind::String => SemanticRole::CodeString,
+            TokenKind::Number => SemanticRole::CodeNumber,
+            TokenKind::Comment => SemanticRole::CodeComment,
+        }
+    }
+}
+
+struct Language {
+    keywords: &'static [&'static str],
+    types: &'static [&'static str],
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+    /// Identifiers starting with an uppercase letter are types.
+    capitalized_types: bool,
+    /// `'` opens a char literal only when it closes right away (`'a'`,
+    /// `'\n'`); otherwise it is a lifetime or label.
+    char_literals: bool,
+}
+
+const RUST: Language = Language {
+    keywords: &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+        "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait",
+        "true", "type", "unsafe", "use", "where", "while",
+    ],
+    types: &[
+        "bool", "char", "f32", "f64", "i8", "i16", "i32", "i64", "i128", "isize", "str", "u8",
+        "u16", "u32", "u64", "u128", "usize",
+    ],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"'],
+    capitalized_types: true,
+    char_literals: true,
+};
+
+const PYTHON: Language = Language {
+    keywords: &[
+        "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class",
+        "continue", "def", "del", "elif", "else", "except", "finally", "for", "from", "global",
+        "if", "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return",
+        "try", "while", "with", "yield",
+    ],
+    types: &[
+        "bool", "bytes", "dict", "float", "int", "list", "object", "set", "str", "tuple",
+    ],
+    line_comments: &["#"],
+    block_comment: None,
+    quotes: &['"', '\''],
+    capitalized_types: false,
+    char_literals: false,
+};
+
+const JAVASCRIPT: Language = Language {
+    keywords: &[
+        "async",
+        "await",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "const",
+        "continue",
+        "default",
+        "delete",
+        "do",
+        "else",
+        "export",
+        "extends",
+        "false",
+        "finally",
+        "for",
+        "from",
+        "function",
+        "if",
+        "import",
+        "in",
+        "instanceof",
+        "interface",
+        "let",
+        "new",
+        "null",
+        "of",
+        "return",
+        "static",
+        "super",
+        "switch",
+        "this",
+        "throw",
+        "true",
+        "try",
+        "type",
+        "typeof",
+        "undefined",
+        "var",
+        "void",
+        "while",
+        "yield",
+    ],
+    types: &["any", "boolean", "never", "number", "string", "unknown"],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\'', '`'],
+    capitalized_types: true,
+    char_literals: false,
+};
+
+const GO: Language = Language {
+    keywords: &[
+        "break",
+        "case",
+        "chan",
+        "const",
+        "continue",
+        "default",
+        "defer",
+        "else",
+        "false",
+        "for",
+        "func",
+        "go",
+        "goto",
+        "if",
+        "import",
+        "interface",
+        "map",
+        "nil",
+        "package",
+        "range",
+        "return",
+        "select",
+        "struct",
+        "switch",
+        "true",
+        "type",
+        "var",
+    ],
+    types: &[
+        "bool", "byte", "error", "float32", "float64", "int", "int8", "int16", "int32", "int64",
+        "rune", "string", "uint", "uint8", "uint16", "uint32", "uint64",
+    ],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '`', '\''],
+    capitalized_types: false,
+    char_literals: false,
+};
+
+const C_LIKE: Language = Language {
+    keywords: &[
+        "auto",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "const",
+        "continue",
+        "default",
+        "delete",
+        "do",
+        "else",
+        "enum",
+        "extends",
+        "extern",
+        "false",
+        "final",
+        "finally",
+        "for",
+        "goto",
+        "if",
+        "implements",
+        "import",
+        "include",
+        "namespace",
+        "new",
+        "null",
+        "nullptr",
+        "package",
+        "private",
+        "protected",
+        "public",
+        "return",
+        "sizeof",
+        "static",
+        "struct",
+        "switch",
+        "template",
+        "this",
+        "throw",
+        "throws",
+        "true",
+        "try",
+        "typedef",
+        "union",
+        "using",
+        "virtual",
+        "void",
+        "volatile",
+        "while",
+    ],
+    types: &[
+        "bool", "boolean", "char", "double", "float", "int", "long", "short", "signed", "size_t",
+        "unsigned",
+    ],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\''],
+    capitalized_types: true,
+    char_literals: false,
+};
+
+const SHELL: Language = Language {
+    keywords: &[
+        "case", "do", "done", "elif", "else", "esac", "exit", "export", "fi", "for", "function",
+        "if", "in", "local", "return", "then", "until", "while",
+    ],
+    types: &[],
+    line_comments: &["#"],
+    block_comment: None,
+    quotes: &['"', '\''],
+    capitalized_types: false,
+    char_literals: false,
+};
+
+const DATA: Language = Language {
+    keywords: &["false", "null", "true", "yes", "no", "on", "off"],
+    types: &[],
+    line_comments: &["#"],
+    block_comment: None,
+    quotes: &['"', '\''],
+    capitalized_types: false,
+    char_literals: false,
+};
+
+fn language(tag: &str) -> Option<&'static Language> {
+    // Info strings may carry attributes: ```rust,ignore
+    let name = tag
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .next()
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    Some(match name.as_str() {
+        "rust" | "rs" => &RUST,
+        "python" | "py" => &PYTHON,
+        "javascript" | "js" | "jsx" | "typescript" | "ts" | "tsx" => &JAVASCRIPT,
+        "go" | "golang" => &GO,
+        "c" | "h" | "cpp" | "c++" | "cc" | "hpp" | "java" | "kotlin" | "cs" | "csharp" => &C_LIKE,
+        "sh" | "bash" | "shell" | "zsh" | "console" => &SHELL,
+        "json" | "toml" | "yaml" | "yml" | "ini" => &DATA,
+        _ => return None,
+    })
+}
+
+/// Split `code` into lines of `(text, kind)` tokens for the language named by
+/// a fenced block's info string. Adjacent tokens of the same kind are merged;
+/// block comments and strings may span lines.
+pub fn highlight_code(lang: &str, code: &str) -> Vec<Vec<(String, TokenKind)>> {
+    let code = code.strip_suffix('\n').unwrap_or(code);
+    let mut out = Lines::default();
+    match language(lang) {
+        Some(language) => tokenize(language, code, &mut out),
+        None => out.push(code, TokenKind::Plain),
+    }
+    out.finish()
+}
+
+#[derive(Default)]
+struct Lines {
+    done: Vec<Vec<(String, TokenKind)>>,
+    current: Vec<(String, TokenKind)>,
+}
+
+impl Lines {
+    fn push(&mut self, text: &str, kind: TokenKind) {
+        for (i, part) in text.split('\n').enumerate() {
+            if i > 0 {
+                self.done.push(std::mem::take(&mut self.current));
+            }
+            if part.is_empty() {
+                continue;
+            }
+            match self.current.last_mut() {
+                Some((last, last_kind)) if *last_kind == kind => last.push_str(part),
+                _ => self.current.push((part.to_string(), kind)),
+            }
+        }
+    }
+
+    fn finish(mut self) -> Vec<Vec<(String, TokenKind)>> {
+        self.done.push(self.current);
+        self.done
+    }
+}
+
+fn tokenize(language: &Language, code: &str, out: &mut Lines) {
+    let mut rest = code;
+    while let Some(c) = rest.chars().next() {
+        let len = if let Some(len) = comment_len(language, rest) {
+            out.push(&rest[..len], TokenKind::Comment);
+            len
+        } else if let Some(len) = string_len(language, rest) {
+            out.push(&rest[..len], TokenKind::String);
+            len
+        } else if c.is_ascii_digit() {
+            let len = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '.' || c == '_'))
+                .unwrap_or(rest.len());
+            out.push(&rest[..len], TokenKind::Number);
+            len
+        } else if c.is_alphabetic() || c == '_' {
+            let len = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let word = &rest[..len];
+            let kind = if language.keywords.contains(&word) {
+                TokenKind::Keyword
+            } else if language.types.contains(&word)
+                || (language.capitalized_types && c.is_uppercase())
+            {
+                TokenKind::Type
+            } else {
+                TokenKind::Plain
+            };
+            out.push(word, kind);
+            len
+        } else {
+            out.push(&rest[..c.len_utf8()], TokenKind::Plain);
+            c.len_utf8()
+        };
+        rest = &rest[len..];
+    }
+}
+
+fn comment_len(language: &Language, rest: &str) -> Option<usize> {
+    if let Some((open, close)) = language.block_comment
+        && rest.starts_with(open)
+    {
+        return Some(
+            rest[open.len()..]
+                .find(close)
+                .map_or(rest.len(), |end| open.len() + end + close.len()),
+        );
+    }
+    language
+        .line_comments
+        .iter()
+        .any(|marker| rest.starts_with(marker))
+        .then(|| rest.find('\n').unwrap_or(rest.len()))
+}
+
+fn string_len(language: &Language, rest: &str) -> Option<usize> {
+    let quote = rest.chars().next()?;
+    let literal = language.quotes.contains(&quote) || (language.char_literals && quote == '\'');
+    if !literal {
+        return None;
+    }
+    let mut escaped = false;
+    for (i, c) in rest.char_indices().skip(1) {
+        if language.char_literals && quote == '\'' && i > 3 {
+            // Not a char literal: a lifetime such as `'a`.
+            return None;
+        }
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '\n' if quote != '`' => {
+                return (!(language.char_literals && quote == '\'')).then_some(i);
+            }
+            c if c == quote => return Some(i + c.len_utf8()),
+            _ => {}
+        }
+    }
+    (!(language.char_literals && quote == '\'')).then_some(rest.len())
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(lang: &str, code: &str) -> Vec<Vec<(&'static str, TokenKind)>> {
+        highlight_code(lang, code)
+            .into_iter()
+            .map(|line| {
+                line.into_iter()
+                    .map(|(text, kind)| (&*text.leak(), kind))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rust_keywords_strings_and_comments() {
+        use TokenKind::*;
+        assert_eq!(
+            kinds(
+                "rust",
+                "fn main() {\n    let s: &str = \"hi\"; // greet\n}\n"
+            ),
+            [
+                vec![("fn", Keyword), (" main() {", Plain)],
+                vec![
+                    ("    ", Plain),
+                    ("let", Keyword),
+                    (" s: &", Plain),
+                    ("str", Type),
+                    (" = ", Plain),
+                    ("\"hi\"", String),
+                    ("; ", Plain),
+                    ("// greet", Comment),
+                ],
+                vec![("}", Plain)],
+            ]
+        );
+    }
+
+    #[test]
+    fn lifetimes_are_not_char_literals() {
+        use TokenKind::*;
+        assert_eq!(
+            kinds("rs", "x: &'a T = 'b';"),
+            [vec![
+                ("x: &'a ", Plain),
+                ("T", Type),
+                (" = ", Plain),
+                ("'b'", String),
+                (";", Plain),
+            ]]
+        );
+    }
+
+    #[test]
+    fn block_comments_span_lines_and_unknown_languages_stay_plain() {
+        use TokenKind::*;
+        assert_eq!(
+            kinds("c", "/* a\nb */ int x = 42;"),
+            [
+                vec![("/* a", Comment)],
+                vec![
+                    ("b */", Comment),
+                    (" ", Plain),
+                    ("int", Type),
+                    (" x = ", Plain),
+                    ("42", Number),
+                    (";", Plain),
+                ],
+            ]
+        );
+        assert_eq!(
+            kinds("python", "# note\nreturn 'x'"),
+            [
+                vec![("# note", Comment)],
+                vec![("return", Keyword), (" ", Plain), ("'x'", String)],
+            ]
+        );
+        assert_eq!(kinds("brainfuck", "+[-]"), [vec![("+[-]", Plain)]]);
+    }
+}
@@ -250,7 +250,7 @@ impl<K: Copy + PartialEq + Eq + std::hash::Hash + std::fmt::Debug + 'static>
         ctx: &ComponentContext,
         _registry: &mut term_wm_core::hitbox_registry::HitboxRegistry,
     ) {
-        let theme = ctx.config().theme;
+        let theme = &ctx.config().theme;
         if rect.width == 0 || rect.height == 0 {
             return;
         }
@@ -0,0 +1,911 @@
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use ratatui::style::{Modifier, Style};
+use term_wm_core::actions::{EventResult, TermWmAction};
+use term_wm_core::components::{Component, ComponentContext};
+use term_wm_core::events::{Event, KeyCode, KeyModifiers, MouseButton};
+use term_wm_core::theme::Theme;
+use term_wm_core::utils::truncate_with_ellipsis;
+use term_wm_core::window::WindowKey;
+use term_wm_layout_engine::LayoutRect;
+use term_wm_view::view_tag;
+use unicode_width::UnicodeWidthStr;
+
+use crate::grid::{GridConstraint, min_total_width, resolve_sizes};
+use crate::helpers::{color_to_ratatui, layout_rect_to_clipped_rect, slice_by_columns};
+
+/// Gap between adjacent columns. In the header it doubles as the resize
+/// handle for the column on its left.
+const HEADER_SEPARATOR: &str = "│";
+const ROW_SEPARATOR: &str = " ";
+/// Narrowest width a column can be dragged down to.
+const MIN_COLUMN_WIDTH: u16 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+impl SortDirection {
+    fn indicator(self) -> &'static str {
+        match self {
+            SortDirection::Ascending => "▲",
+            SortDirection::Descending => "▼",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColumnAlign {
+    #[default]
+    Left,
+    Right,
+}
+
+type CellFn<Row> = Rc<dyn Fn(&Row) -> String>;
+type CompareFn<Row> = Box<dyn Fn(&Row, &Row) -> Ordering>;
+
+/// A typed column of a [`TableComponent`]: a header title, a width
+/// constraint and an accessor that formats the cell text for a row.
+/// Columns only sort once they are given an ordering.
+pub struct TableColumn<Row> {
+    title: String,
+    width: GridConstraint,
+    align: ColumnAlign,
+    cell: CellFn<Row>,
+    compare: Option<CompareFn<Row>>,
+}
+
+impl<Row: 'static> TableColumn<Row> {
+    pub fn new(
+        title: impl Into<String>,
+        width: GridConstraint,
+        cell: impl Fn(&Row) -> String + 'static,
+    ) -> Self {
+        Self {
+            title: title.into(),
+            width,
+            align: ColumnAlign::Left,
+            cell: Rc::new(cell),
+            compare: None,
+        }
+    }
+
+    pub fn with_align(mut self, align: ColumnAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Sort by the formatted cell text.
+    pub fn sortable(mut self) -> Self {
+        let cell = self.cell.clone();
+        self.compare = Some(Box::new(move |a, b| cell(a).cmp(&cell(b))));
+        self
+    }
+
+    /// Sort by a typed key, e.g. a numeric field that would compare wrongly
+    /// as text.
+    pub fn sort_by_key<K: Ord>(mut self, key: impl Fn(&Row) -> K + 'static) -> Self {
+        self.compare = Some(Box::new(move |a, b| key(a).cmp(&key(b))));
+        self
+    }
+
+    /// Sort with a custom comparator.
+    pub fn sort_by(mut self, compare: impl Fn(&Row, &Row) -> Ordering + 'static) -> Self {
+        self.compare = Some(Box::new(compare));
+        self
+    }
+}
+
+impl<Row> TableColumn<Row> {
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn width(&self) -> GridConstraint {
+        self.width
+    }
+
+    pub fn is_sortable(&self) -> bool {
+        self.compare.is_some()
+    }
+
+    pub fn cell_text(&self, row: &Row) -> String {
+        (self.cell)(row)
+    }
+}
+
+/// Styles for [`TableComponent`]; [`TableStyle::from_theme`] is the default.
+#[derive(Debug, Clone, Copy)]
+pub struct TableStyle {
+    pub header: Style,
+    pub row: Style,
+    pub selected: Style,
+}
+
+impl TableStyle {
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            header: Style::default()
+                .bg(color_to_ratatui(theme.panel_active_bg))
+                .fg(color_to_ratatui(theme.panel_active_fg))
+                .add_modifier(Modifier::BOLD),
+            row: Style::default().fg(color_to_ratatui(theme.text)),
+            selected: Style::default()
+                .bg(color_to_ratatui(theme.selection_bg))
+                .fg(color_to_ratatui(theme.selection_fg)),
+        }
+    }
+}
+
+/// An in-progress header drag resizing one column.
+#[derive(Debug, Clone, Copy)]
+struct ResizeDrag {
+    column: usize,
+    anchor_x: usize,
+    start_width: u16,
+}
+
+/// A data table over typed rows with a sticky header.
+///
+/// Meant to be wrapped in a [`ScrollViewComponent`](crate::ScrollViewComponent):
+/// the table reports its content size and reads the viewport offsets back, so
+/// the scroll view supplies both scrollbars. Only the rows inside the
+/// viewport are formatted and drawn, which keeps very large tables cheap.
+///
+/// Sorting permutes a display order and never moves the rows themselves, so
+/// [`selected`](Self::selected) and [`rows`](Self::rows) stay in insertion
+/// order.
+pub struct TableComponent<Row> {
+    columns: Vec<TableColumn<Row>>,
+    rows: Vec<Row>,
+    /// Display order: `order[display_index]` is an index into `rows`.
+    order: Vec<usize>,
+    sort: Option<(usize, SortDirection)>,
+    /// Widths set by dragging a header separator; they replace the
+    /// column's constraint with a fixed size.
+    width_overrides: Vec<Option<u16>>,
+    /// Selected display index.
+    selected: usize,
+    activate_action: Option<TermWmAction>,
+    style: Option<TableStyle>,
+    /// Column widths resolved by the last render, for header hit testing.
+    last_widths: Vec<u16>,
+    last_selected: usize,
+    last_viewport_rows: usize,
+    resize_drag: Option<ResizeDrag>,
+}
+
+#[view_tag(Table)]
+impl<Row: 'static> TableComponent<Row> {
+    pub fn new(columns: Vec<TableColumn<Row>>) -> Self {
+        let width_overrides = vec![None; columns.len()];
+        Self {
+            columns,
+            rows: Vec::new(),
+            order: Vec::new(),
+            sort: None,
+            width_overrides,
+            selected: 0,
+            activate_action: None,
+            style: None,
+            last_widths: Vec::new(),
+            last_selected: 0,
+            last_viewport_rows: 0,
+            resize_drag: None,
+        }
+    }
+
+    pub fn with_rows(mut self, rows: Vec<Row>) -> Self {
+        self.set_rows(rows);
+        self
+    }
+
+    /// Emit `action` when Enter is pressed on a selected row.
+    pub fn with_activate_action(mut self, action: TermWmAction) -> Self {
+        self.activate_action = Some(action);
+        self
+    }
+
+    /// Override the theme-derived styles.
+    pub fn with_style(mut self, style: TableStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Replace all rows, keeping the active sort and resetting the selection.
+    pub fn set_rows(&mut self, rows: Vec<Row>) {
+        self.rows = rows;
+        self.selected = 0;
+        self.last_selected = 0;
+        self.last_viewport_rows = 0;
+        self.rebuild_order();
+    }
+
+    /// Replace the rows in place WITHOUT resetting the selection or the
+    /// scroll-follow guard, for periodic live refreshes (see
+    /// [`ListComponent::update_items`](crate::ListComponent::update_items)).
+    pub fn update_rows(&mut self, rows: Vec<Row>) {
+        self.rows = rows;
+        self.rebuild_order();
+        if self.selected >= self.order.len() {
+            self.selected = self.order.len().saturating_sub(1);
+        }
+    }
+
+    /// Append a row at its sorted position.
+    pub fn push_row(&mut self, row: Row) {
+        let index = self.rows.len();
+        self.rows.push(row);
+        let position = match self.sort {
+            Some(sort) => self
+                .order
+                .partition_point(|&i| self.compare_rows(sort, i, index) != Ordering::Greater),
+            None => self.order.len(),
+        };
+        self.order.insert(position, index);
+        if position <= self.selected && self.order.len() > 1 {
+            self.selected += 1;
+        }
+    }
+
+    pub fn rows(&self) -> &[Row] {
+        &self.rows
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    pub fn columns(&self) -> &[TableColumn<Row>] {
+        &self.columns
+    }
+
+    /// Index into [`rows`](Self::rows) of the selected row.
+    pub fn selected(&self) -> Option<usize> {
+        self.order.get(self.selected).copied()
+    }
+
+    pub fn selected_row(&self) -> Option<&Row> {
+        self.selected().map(|i| &self.rows[i])
+    }
+
+    /// Select the row at `index` into [`rows`](Self::rows).
+    pub fn select(&mut self, index: usize) {
+        if let Some(position) = self.order.iter().position(|&i| i == index) {
+            self.selected = position;
+        }
+    }
+
+    pub fn move_selection(&mut self, delta: isize) {
+        self.bump_selection(delta);
+    }
+
+    /// Rows in display order.
+    pub fn display_rows(&self) -> impl Iterator<Item = &Row> {
+        self.order.iter().map(|&i| &self.rows[i])
+    }
+
+    pub fn sort(&self) -> Option<(usize, SortDirection)> {
+        self.sort
+    }
+
+    /// Sort by `column`. Ignored for columns without an ordering. The
+    /// selected row stays selected.
+    pub fn sort_by_column(&mut self, column: usize, direction: SortDirection) {
+        if !self
+            .columns
+            .get(column)
+            .is_some_and(TableColumn::is_sortable)
+        {
+            return;
+        }
+        self.sort = Some((column, direction));
+        self.reorder_keeping_selection();
+    }
+
+    /// Header-click behaviour: sort ascending, or flip the direction when
+    /// `column` is already the sort column.
+    pub fn toggle_sort(&mut self, column: usize) {
+        let direction = match self.sort {
+            Some((current, SortDirection::Ascending)) if current == column => {
+                SortDirection::Descending
+            }
+            _ => SortDirection::Ascending,
+        };
+        self.sort_by_column(column, direction);
+    }
+
+    /// Restore insertion order.
+    pub fn clear_sort(&mut self) {
+        self.sort = None;
+        self.reorder_keeping_selection();
+    }
+
+    /// Pin `column` to a fixed width, replacing its constraint.
+    pub fn set_column_width(&mut self, column: usize, width: u16) {
+        if let Some(slot) = self.width_overrides.get_mut(column) {
+            *slot = Some(width.max(MIN_COLUMN_WIDTH));
+        }
+    }
+
+    /// Grow or shrink `column` relative to its last rendered width.
+    pub fn resize_column(&mut self, column: usize, delta: i32) {
+        let Some(&current) = self.last_widths.get(column) else {
+            return;
+        };
+        let width = (i32::from(current) + delta).clamp(0, i32::from(u16::MAX)) as u16;
+        self.set_column_width(column, width);
+    }
+
+    /// Drop a manual width so `column` follows its constraint again.
+    pub fn reset_column_width(&mut self, column: usize) {
+        if let Some(slot) = self.width_overrides.get_mut(column) {
+            *slot = None;
+        }
+    }
+
+    /// Column widths resolved by the last render.
+    pub fn column_widths(&self) -> &[u16] {
+        &self.last_widths
+    }
+
+    fn bump_selection(&mut self, delta: isize) {
+        if self.order.is_empty() {
+            return;
+        }
+        let max = self.order.len() - 1;
+        self.selected = (self.selected as isize + delta).clamp(0, max as isize) as usize;
+    }
+
+    fn compare_rows(
+        &self,
+        (column, direction): (usize, SortDirection),
+        a: usize,
+        b: usize,
+    ) -> Ordering {
+        let Some(compare) = self.columns.get(column).and_then(|c| c.compare.as_ref()) else {
+            return a.cmp(&b);
+        };
+        let ord = compare(&self.rows[a], &self.rows[b]);
+        match direction {
+            SortDirection::Ascending => ord,
+            SortDirection::Descending => ord.reverse(),
+        }
+    }
+
+    fn rebuild_order(&mut self) {
+        let mut order: Vec<usize> = (0..self.rows.len()).collect();
+        if let Some(sort) = self.sort {
+            // Stable, so equal keys keep insertion order.
+            order.sort_by(|&a, &b| self.compare_rows(sort, a, b));
+        }
+        self.order = order;
+    }
+
+    fn reorder_keeping_selection(&mut self) {
+        let selected = self.selected();
+        self.rebuild_order();
+        if let Some(index) = selected {
+            self.select(index);
+        }
+    }
+
+    fn constraints(&self) -> Vec<GridConstraint> {
+        self.columns
+            .iter()
+            .zip(&self.width_overrides)
+            .map(|(column, width)| width.map_or(column.width, GridConstraint::Fixed))
+            .collect()
+    }
+
+    /// Resolve column widths for a viewport `width`. Fraction columns share
+    /// the viewport but never drop below their minimum; past that point the
+    /// table grows wider than the viewport and scrolls horizontally.
+    fn resolve_widths(&self, width: u16) -> Vec<u16> {
+        let constraints = self.constraints();
+        let separators = constraints.len().saturating_sub(1) as u16;
+        let available = width
+            .saturating_sub(separators)
+            .max(min_total_width(&constraints));
+        resolve_sizes(available, &constraints)
+    }
+
+    /// Start column (in content coordinates) of every column.
+    fn column_starts(widths: &[u16]) -> Vec<usize> {
+        let mut x = 0usize;
+        widths
+            .iter()
+            .map(|w| {
+                let start = x;
+                x += usize::from(*w) + 1;
+                start
+            })
+            .collect()
+    }
+
+    fn header_line(&self, widths: &[u16]) -> String {
+        let mut line = String::new();
+        for (i, (column, width)) in self.columns.iter().zip(widths).enumerate() {
+            if i > 0 {
+                line.push_str(HEADER_SEPARATOR);
+            }
+            let title = match self.sort {
+                Some((sorted, direction)) if sorted == i => {
+                    format!("{} {}", column.title, direction.indicator())
+                }
+                _ => column.title.clone(),
+            };
+            push_cell(&mut line, &title, *width, column.align);
+        }
+        line
+    }
+
+    fn row_line(&self, row: &Row, widths: &[u16]) -> String {
+        let mut line = String::new();
+        for (i, (column, width)) in self.columns.iter().zip(widths).enumerate() {
+            if i > 0 {
+                line.push_str(ROW_SEPARATOR);
+            }
+            push_cell(&mut line, &column.cell_text(row), *width, column.align);
+        }
+        line
+    }
+
+    fn on_header_press(&mut self, content_x: usize) -> EventResult<TermWmAction> {
+        let starts = Self::column_starts(&self.last_widths);
+        for (column, (&start, &width)) in starts.iter().zip(&self.last_widths).enumerate() {
+            let end = start + usize::from(width);
+            if content_x == end && column + 1 < self.columns.len() {
+                self.resize_drag = Some(ResizeDrag {
+                    column,
+                    anchor_x: content_x,
+                    start_width: width,
+                });
+                return EventResult::Consumed;
+            }
+            if (start..end).contains(&content_x) {
+                if self.columns[column].is_sortable() {
+                    self.toggle_sort(column);
+                    return EventResult::Consumed;
+                }
+                return EventResult::Ignored;
+            }
+        }
+        EventResult::Ignored
+    }
+}
+
+/// Append `text` to `line` truncated and padded to exactly `width` columns.
+fn push_cell(line: &mut String, text: &str, width: u16, align: ColumnAlign) {
+    let width = usize::from(width);
+    let text = truncate_with_ellipsis(text, width);
+    let pad = width.saturating_sub(text.width());
+    match align {
+        ColumnAlign::Left => {
+            line.push_str(&text);
+            line.extend(std::iter::repeat_n(' ', pad));
+        }
+        ColumnAlign::Right => {
+            line.extend(std::iter::repeat_n(' ', pad));
+            line.push_str(&text);
+        }
+    }
+}
+
+impl<Row: 'static> Component<TermWmAction> for TableComponent<Row> {
+    fn render(
+        &mut self,
+        backend: &mut dyn term_wm_render::RenderBackend,
+        area: LayoutRect,
+        ctx: &ComponentContext,
+        _registry: &mut term_wm_core::hitbox_registry::HitboxRegistry,
+    ) {
+        let area = layout_rect_to_clipped_rect(area);
+        let backend = crate::helpers::downcast_ratatui(backend);
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+        let style = self
+            .style
+            .unwrap_or_else(|| TableStyle::from_theme(&ctx.config().theme));
+        let widths = self.resolve_widths(area.width);
+        let content_width =
+            widths.iter().map(|w| usize::from(*w)).sum::<usize>() + widths.len().saturating_sub(1);
+        let viewport_rows = usize::from(area.height.saturating_sub(1));
+
+        // The header is sticky: it always occupies the first viewport row, so
+        // the content is one row taller than the data and `offset_y` indexes
+        // rows directly.
+        if let Some(handle) = ctx.scroll_handle() {
+            handle.set_content_size(content_width, self.order.len() + 1);
+            handle.ensure_selection_visible(
+                self.selected,
+                viewport_rows,
+                &mut self.last_selected,
+                &mut self.last_viewport_rows,
+            );
+        }
+        let vp = ctx.viewport();
+        let visible_width = usize::from(area.width);
+
+        let header = self.header_line(&widths);
+        let buffer = &mut backend.buffer;
+        let header_rect = ratatui::layout::Rect { height: 1, ..area };
+        buffer.set_style(header_rect, style.header);
+        buffer.set_stringn(
+            area.x,
+            area.y,
+            slice_by_columns(&header, vp.offset_x, visible_width),
+            visible_width,
+            style.header,
+        );
+
+        for (line_index, display_index) in (vp.offset_y..self.order.len())
+            .take(viewport_rows)
+            .enumerate()
+        {
+            let y = area.y + 1 + line_index as u16;
+            let row_style = if display_index == self.selected {
+                style.selected
+            } else {
+                style.row
+            };
+            let line = self.row_line(&self.rows[self.order[display_index]], &widths);
+            buffer.set_style(
+                ratatui::layout::Rect {
+                    y,
+                    height: 1,
+                    ..area
+                },
+                row_style,
+            );
+            buffer.set_stringn(
+                area.x,
+                y,
+                slice_by_columns(&line, vp.offset_x, visible_width),
+                visible_width,
+                row_style,
+            );
+        }
+        self.last_widths = widths;
+    }
+
+    fn on_mouse_press(
+        &mut self,
+        local_x: u16,
+        local_y: u16,
+        button: MouseButton,
+        _modifiers: KeyModifiers,
+        ctx: &ComponentContext,
+    ) -> EventResult<TermWmAction> {
+        if button != MouseButton::Left || !ctx.focused() {
+            return EventResult::Ignored;
+        }
+        let vp = ctx.viewport();
+        if local_y == 0 {
+            return self.on_header_press(vp.offset_x + usize::from(local_x));
+        }
+        let index = vp.offset_y + usize::from(local_y - 1);
+        if index < self.order.len() {
+            self.selected = index;
+            return EventResult::Consumed;
+        }
+        EventResult::Ignored
+    }
+
+    fn on_mouse_drag(
+        &mut self,
+        local_x: u16,
+        _local_y: u16,
+        _button: MouseButton,
+        _modifiers: KeyModifiers,
+        ctx: &ComponentContext,
+    ) -> EventResult<TermWmAction> {
+        let Some(drag) = self.resize_drag else {
+            return EventResult::Ignored;
+        };
+        let x = ctx.viewport().offset_x + usize::from(local_x);
+        let delta = x as i64 - drag.anchor_x as i64;
+        let width = (i64::from(drag.start_width) + delta).clamp(0, i64::from(u16::MAX)) as u16;
+        self.set_column_width(drag.column, width);
+        EventResult::Consumed
+    }
+
+    fn on_mouse_release(
+        &mut self,
+        _local_x: u16,
+        _local_y: u16,
+        _button: MouseButton,
+        _modifiers: KeyModifiers,
+        _ctx: &ComponentContext,
+    ) -> EventResult<TermWmAction> {
+        if self.resize_drag.take().is_some() {
+            return EventResult::Consumed;
+        }
+        EventResult::Ignored
+    }
+
+    fn on_key(&mut self, event: &Event, _ctx: &ComponentContext) -> EventResult<TermWmAction> {
+        if let Event::Key(key) = event {
+            let kb = term_wm_core::keybindings::KeyBindings::default();
+            if kb.matches(TermWmAction::MenuUp, key) || kb.matches(TermWmAction::MenuPrev, key) {
+                return EventResult::Action(TermWmAction::MenuUp);
+            } else if kb.matches(TermWmAction::MenuDown, key)
+                || kb.matches(TermWmAction::MenuNext, key)
+            {
+                return EventResult::Action(TermWmAction::MenuDown);
+            } else if kb.matches(TermWmAction::ScrollPageUp, key) {
+                return EventResult::Action(TermWmAction::ScrollPageUp);
+            } else if kb.matches(TermWmAction::ScrollPageDown, key) {
+                return EventResult::Action(TermWmAction::ScrollPageDown);
+            } else if kb.matches(TermWmAction::ScrollHome, key) {
+                return EventResult::Action(TermWmAction::ScrollHome);
+            } else if kb.matches(TermWmAction::ScrollEnd, key) {
+                return EventResult::Action(TermWmAction::ScrollEnd);
+            } else if key.code == KeyCode::Enter
+                && !self.order.is_empty()
+                && let Some(action) = &self.activate_action
+            {
+                return EventResult::Action(action.clone());
+            }
+        }
+        EventResult::Ignored
+    }
+
+    fn update(
+        &mut self,
+        action: TermWmAction,
+        _ctx: &ComponentContext,
+        _actions: &mut VecDeque<(WindowKey, TermWmAction)>,
+    ) {
+        let page = self.last_viewport_rows.max(1) as isize;
+        match action {
+            TermWmAction::MenuUp | TermWmAction::MenuPrev => self.bump_selection(-1),
+            TermWmAction::MenuDown | TermWmAction::MenuNext => self.bump_selection(1),
+            TermWmAction::ScrollPageUp => self.bump_selection(-page),
+            TermWmAction::ScrollPageDown => self.bump_selection(page),
+            TermWmAction::ScrollHome => self.selected = 0,
+            TermWmAction::ScrollEnd if !self.order.is_empty() => {
+                self.selected = self.order.len() - 1;
+            }
+            _ => {}
+        }
+    }
+
+    fn selection_text(&self) -> Option<String> {
+        let row = self.selected_row()?;
+        Some(
+            self.columns
+                .iter()
+                .map(|column| column.cell_text(row))
+                .collect::<Vec<_>>()
+                .join("\t"),
+        )
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+    use term_wm_core::component_context::{ScrollBounds, ScrollHandle};
+    use term_wm_core::events::{KeyEvent, KeyKind};
+
+    #[derive(Debug, Clone)]
+    struct Proc {
+        pid: u32,
+        name: String,
+    }
+
+    fn procs(n: u32) -> Vec<Proc> {
+        (0..n)
+            .map(|pid| Proc {
+                pid,
+                name: format!("proc-{pid}"),
+            })
+            .collect()
+    }
+
+    fn table() -> TableComponent<Proc> {
+        TableComponent::new(vec![
+            TableColumn::new("PID", GridConstraint::Fixed(6), |p: &Proc| {
+                p.pid.to_string()
+            })
+            .with_align(ColumnAlign::Right)
+            .sort_by_key(|p: &Proc| p.pid),
+            TableColumn::new("Name", GridConstraint::Fraction(1), |p: &Proc| {
+                p.name.clone()
+            })
+            .sortable(),
+        ])
+    }
+
+    fn scroll_ctx() -> (ComponentContext, ScrollHandle) {
+        let handle = ScrollHandle {
+            scroll: Rc::new(RefCell::new(ScrollBounds::default())),
+        };
+        let ctx = ComponentContext::new(true).with_viewport(handle.info(), Some(handle.clone()));
+        (ctx, handle)
+    }
+
+    fn render(table: &mut TableComponent<Proc>, width: u16, height: u16) -> Vec<String> {
+        let (ctx, _handle) = scroll_ctx();
+        render_with(table, width, height, &ctx)
+    }
+
+    fn render_with(
+        table: &mut TableComponent<Proc>,
+        width: u16,
+        height: u16,
+        ctx: &ComponentContext,
+    ) -> Vec<String> {
+        let rect = ratatui::layout::Rect::new(0, 0, width, height);
+        let mut backend =
+            term_wm_console::RatatuiBackend::new_simple(ratatui::buffer::Buffer::empty(rect), rect);
+        let mut registry = term_wm_core::hitbox_registry::HitboxRegistry::new();
+        let area = LayoutRect {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        };
+        table.render(&mut backend, area, ctx, &mut registry);
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| backend.buffer[(x, y)].symbol().to_string())
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    fn names(table: &TableComponent<Proc>) -> Vec<u32> {
+        table.display_rows().map(|p| p.pid).collect()
+    }
+
+    #[test]
+    fn renders_header_and_aligned_cells() {
+        let mut t = table().with_rows(procs(3));
+        let lines = render(&mut t, 20, 4);
+        assert_eq!(lines[0], "   PID│Name         ");
+        assert_eq!(lines[1], "     0 proc-0       ");
+        assert_eq!(t.column_widths(), &[6, 13]);
+    }
+
+    #[test]
+    fn header_click_toggles_sort_and_keeps_selection() {
+        let mut t = table().with_rows(procs(5));
+        render(&mut t, 20, 6);
+        t.select(3);
+        let ctx = ComponentContext::new(true);
+        // Click on the PID title.
+        t.on_mouse_press(2, 0, MouseButton::Left, KeyModifiers::NONE, &ctx);
+        assert_eq!(t.sort(), Some((0, SortDirection::Ascending)));
+        t.on_mouse_press(2, 0, MouseButton::Left, KeyModifiers::NONE, &ctx);
+        assert_eq!(t.sort(), Some((0, SortDirection::Descending)));
+        assert_eq!(names(&t), vec![4, 3, 2, 1, 0]);
+        assert_eq!(t.selected(), Some(3));
+        assert!(render(&mut t, 20, 6)[0].contains("PID ▼"));
+        t.clear_sort();
+        assert_eq!(names(&t), vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn typed_sort_key_orders_numerically() {
+        let mut t = table().with_rows(vec![
+            Proc {
+                pid: 10,
+                name: "a".into(),
+            },
+            Proc {
+                pid: 9,
+                name: "b".into(),
+            },
+        ]);
+        t.sort_by_column(0, SortDirection::Ascending);
+        assert_eq!(names(&t), vec![9, 10]);
+        t.push_row(Proc {
+            pid: 5,
+            name: "c".into(),
+        });
+        assert_eq!(names(&t), vec![5, 9, 10]);
+    }
+
+    #[test]
+    fn dragging_header_separator_resizes_column() {
+        let mut t = table().with_rows(procs(2));
+        render(&mut t, 30, 4);
+        let ctx = ComponentContext::new(true);
+        // The separator after PID sits at column 6.
+        let pressed = t.on_mouse_press(6, 0, MouseButton::Left, KeyModifiers::NONE, &ctx);
+        assert!(pressed.is_consumed());
+        t.on_mouse_drag(10, 0, MouseButton::Left, KeyModifiers::NONE, &ctx);
+        t.on_mouse_release(10, 0, MouseButton::Left, KeyModifiers::NONE, &ctx);
+        render(&mut t, 30, 4);
+        assert_eq!(t.column_widths()[0], 10);
+        assert_eq!(t.sort(), None);
+    }
+
+    #[test]
+    fn keys_and_clicks_move_selection() {
+        let mut t = table().with_rows(procs(10));
+        let ctx = ComponentContext::new(true);
+        let down = Event::Key(KeyEvent::new(
+            KeyCode::Down,
+            KeyModifiers::NONE,
+            KeyKind::Press,
+        ));
+        if let EventResult::Action(action) = t.handle_events(&down, &ctx) {
+            t.update(action, &ctx, &mut VecDeque::new());
+        }
+        assert_eq!(t.selected(), Some(1));
+        t.on_mouse_press(0, 4, MouseButton::Left, KeyModifiers::NONE, &ctx);
+        assert_eq!(t.selected(), Some(3));
+        assert_eq!(t.selection_text().as_deref(), Some("3\tproc-3"));
+    }
+
+    #[test]
+    fn only_visible_rows_are_formatted() {
+        thread_local! {
+            static CALLS: Cell<usize> = const { Cell::new(0) };
+        }
+        let mut t = TableComponent::new(vec![TableColumn::new(
+            "Name",
+            GridConstraint::Fraction(1),
+            |p: &Proc| {
+                CALLS.with(|c| c.set(c.get() + 1));
+                p.name.clone()
+            },
+        )])
+        .with_rows(procs(100_000));
+        // Park the viewport (and the selection, which it follows) mid-table.
+        t.select(50_000);
+        let (ctx, handle) = scroll_ctx();
+        {
+            let mut bounds = handle.scroll.borrow_mut();
+            bounds.height = 10;
+            bounds.content_height = 100_001;
+            bounds.offset_y = 50_000;
+        }
+        let lines = render_with(
+            &mut t,
+            20,
+            10,
+            &ctx.with_viewport(handle.info(), Some(handle.clone())),
+        );
+        assert_eq!(CALLS.with(Cell::get), 9);
+        assert!(lines[1].starts_with("proc-50000"));
+        assert_eq!(handle.scroll.borrow().content_height, 100_001);
+    }
+
+    #[test]
+    fn narrow_viewport_scrolls_horizontally() {
+        let mut t = table().with_rows(procs(2));
+        let (ctx, handle) = scroll_ctx();
+        {
+            let mut bounds = handle.scroll.borrow_mut();
+            bounds.width = 8;
+            bounds.height = 4;
+            bounds.content_width = 17;
+            bounds.offset_x = 7;
+        }
+        let lines = render_with(
+            &mut t,
+            8,
+            4,
+            &ctx.with_viewport(handle.info(), Some(handle.clone())),
+        );
+        // PID (6) + separator + Name at its 10-column minimum.
+        assert_eq!(handle.scroll.borrow().content_width, 17);
+        assert_eq!(lines[1], "proc-0  ");
+    }
+}
@@ -14,7 +14,7 @@ use crate::helpers::{
     localize_coordinate_clamped,
 };
 use term_wm_core::actions::{EventResult, TermWmAction};
-use term_wm_core::components::{Component, ComponentContext, SelectionStatus};
+use term_wm_core::components::{Component, ComponentContext, HostCursor, SelectionStatus};
 use term_wm_core::hitbox_registry::HitboxId;
 use term_wm_core::utils::linkifier::{LinkHandler, LinkOverlay, Linkifier, OverlaySignature};
 use term_wm_core::utils::selectable_text::{
@@ -56,6 +56,12 @@ pub struct TerminalComponent {
     last_max_scrollback: Cell<usize>,
     last_mode_suppressed_scroll: Cell<bool>,
     reported_alt_screen: Cell<bool>,
+    /// `bytes_received` as of the last drawn frame, so output that arrives
+    /// without raising the dirty flag (remote panes) still redraws.
+    rendered_bytes: Cell<usize>,
+    /// Where the host terminal should show its own cursor, set when the
+    /// focused pane picked a cursor style and so isn't painted a block.
+    host_cursor: Cell<Option<HostCursor>>,
     window_key: Option<term_wm_core::window::WindowKey>,
     /// Characters treated as word characters in addition to alphanumeric +
     /// underscore, for double-click word selection. Default (empty) treats
@@ -291,6 +297,17 @@ impl Component<TermWmAction> for TerminalComponent {
         self.render_screen(backend, area, ctx, resized);
     }
 
+    /// Idle panes keep their cached frame: redraw only on new output (the
+    /// pane's dirty flag or byte counter) or while a selection drag may
+    /// auto-scroll.
+    fn needs_render(&self) -> bool {
+        let pane = self.pane.borrow();
+        let dirty = pane.take_dirty();
+        dirty
+            || pane.bytes_received() != self.rendered_bytes.get()
+            || self.selection.borrow().is_dragging()
+    }
+
     fn destroy(&mut self) {
         // Kill the child process on teardown so the OS reaps it.
         let _ = self.pane.get_mut().kill_child();
@@ -333,6 +350,10 @@ impl Component<TermWmAction> for TerminalComponent {
         self.pane.get_mut().take_pending_title()
     }
 
+    fn host_cursor(&self) -> Option<HostCursor> {
+        self.host_cursor.get()
+    }
+
     fn take_alternate_screen_transition(&mut self) -> Option<bool> {
         let current = self.pane.get_mut().alternate_screen();
         if current != self.reported_alt_screen.get() {
@@ -386,6 +407,8 @@ impl TerminalComponent {
             last_max_scrollback: Cell::new(0),
             last_mode_suppressed_scroll: Cell::new(false),
             reported_alt_screen: Cell::new(false),
+            rendered_bytes: Cell::new(0),
+            host_cursor: Cell::new(None),
             window_key: None,
             word_extra_chars: DEFAULT_WORD_EXTRA_CHARS.to_string(),
         }
@@ -498,6 +521,7 @@ impl TerminalComponent {
         resized: bool,
     ) {
         let screen_area = ctx.screen_area().unwrap_or(area);
+        self.host_cursor.set(None);
 
         // 1. PULL EXTERNAL SCROLL STATE FIRST — apply scroll wheel changes
         //    to internal scrollback before drag maintenance uses them.
@@ -616,6 +640,7 @@ impl TerminalComponent {
         let screen = parser.screen();
 
         let bytes_seen = pane.bytes_received();
+        self.rendered_bytes.set(bytes_seen);
         let signature = OverlaySignature::new(
             bytes_seen,
             scrollback_value,
@@ -697,13 +722,13 @@ impl TerminalComponent {
                         symbol = ' ';
                     }
 
-                    let theme = ctx.config().theme;
+                    let theme = &ctx.config().theme;
                     if self
                         .link_overlay
                         .borrow()
                         .is_link_cell(viewport_row, viewport_col)
                     {
-                        style = decorate_link_style(style, &theme);
+                        style = decorate_link_style(style, theme);
                     }
 
                     if let Some(range) = selection_range {
@@ -737,11 +762,25 @@ impl TerminalComponent {
 
         if focused && !screen.hide_cursor() && show_cursor {
             let (row, col) = screen.cursor_position();
-            if row < area.height
-                && col < area.width
-                && let Some(cell) = buffer.cell_mut((area.x + col, area.y + row))
-            {
-                cell.set_style(cell.style().add_modifier(Modifier::REVERSED));
+            let cursor_style = pane.cursor_style();
+            if row >= area.height || col >= area.width {
+                // Cursor is outside the pane; nothing to draw.
+            } else if cursor_style.is_default() {
+                if let Some(cell) = buffer.cell_mut((area.x + col, area.y + row)) {
+                    cell.set_style(cell.style().add_modifier(Modifier::REVERSED));
+                }
+            } else {
+                // The app chose a shape or color, which only the host's own
+                // cursor can show, so leave the cell unpainted.
+                let x = u16::try_from(screen_area.x + i32::from(col)).ok();
+                let y = u16::try_from(screen_area.y + i32::from(row)).ok();
+                if let (Some(x), Some(y)) = (x, y) {
+                    self.host_cursor.set(Some(HostCursor {
+                        x,
+                        y,
+                        style: cursor_style,
+                    }));
+                }
             }
         }
     }
@@ -1218,6 +1257,8 @@ struct TestPane {
     pending_title: Option<String>,
     kill_count: std::sync::Arc<std::sync::atomic::AtomicUsize>,
     written_bytes: Vec<u8>,
+    bytes_received: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    cursor_style: term_wm_pty_engine::CursorStyle,
 }
 
 #[cfg(test)]
@@ -1233,6 +1274,8 @@ impl TestPane {
             pending_title: None,
             kill_count: std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0)),
             written_bytes: Vec::new(),
+            bytes_received: Default::default(),
+            cursor_style: Default::default(),
         }
     }
 
@@ -1248,6 +1291,8 @@ impl TestPane {
             pending_title: None,
             kill_count: std::sync::Arc::clone(&kill_count),
             written_bytes: Vec::new(),
+            bytes_received: Default::default(),
+            cursor_style: Default::default(),
         };
         (pane, kill_count)
     }
@@ -1257,6 +1302,8 @@ impl TestPane {
     }
 
     fn write_to_parser(&mut self, bytes: &[u8]) {
+        self.bytes_received
+            .fetch_add(bytes.len(), std::sync::atomic::Ordering::Relaxed);
         let mut parser = self.parser.lock().unwrap_or_else(|err| err.into_inner());
         parser.process(bytes);
     }
@@ -1287,6 +1334,10 @@ impl Pane for TestPane {
         self.alt_screen
     }
 
+    fn cursor_style(&self) -> term_wm_pty_engine::CursorStyle {
+        self.cursor_style
+    }
+
     fn requires_app_routing(&self) -> bool {
         self.alt_screen
     }
@@ -1325,7 +1376,8 @@ impl Pane for TestPane {
     }
 
     fn bytes_received(&self) -> usize {
-        0
+        self.bytes_received
+            .load(std::sync::atomic::Ordering::Relaxed)
     }
 
     fn last_bytes_text(&self) -> String {
@@ -1456,6 +1508,20 @@ mod tests {
         term.pane_mut().scrollback()
     }
 
+    #[test]
+    fn needs_render_only_after_new_output() {
+        let pane = TestPane::new(200);
+        let received = std::sync::Arc::clone(&pane.bytes_received);
+        let mut term = TerminalComponent::from_pane(Box::new(pane));
+        run_sync(&mut term, 0);
+        assert!(!term.needs_render(), "idle pane keeps its cached frame");
+
+        received.fetch_add(5, std::sync::atomic::Ordering::Relaxed);
+        assert!(term.needs_render());
+        run_sync(&mut term, 0);
+        assert!(!term.needs_render());
+    }
+
     // --- Destroy / kill tests ---
 
     #[test]
@@ -3137,6 +3203,52 @@ mod tests {
         assert_eq!(r2, None);
     }
 
+    #[test]
+    fn styled_cursor_is_left_to_the_host() {
+        let render = |style: term_wm_pty_engine::CursorStyle| {
+            let mut pane = TestPane::new(0);
+            pane.set_parser_size(10, 20);
+            pane.write_to_parser(b"abc");
+            pane.cursor_style = style;
+            let mut term = TerminalComponent::from_pane(Box::new(pane));
+            let local = LayoutRect {
+                x: 0,
+                y: 0,
+                width: 20,
+                height: 10,
+            };
+            let ctx = ComponentContext::new(true).with_screen_area(LayoutRect {
+                x: 5,
+                y: 2,
+                ..local
+            });
+            let area = Rect::new(0, 0, 20, 10);
+            let mut backend =
+                term_wm_console::RatatuiBackend::new_simple(Buffer::empty(area), area);
+            term.render(
+                &mut backend,
+                local,
+                &ctx,
+                &mut term_wm_core::hitbox_registry::HitboxRegistry::new(),
+            );
+            let reversed = backend.buffer[(3, 0)]
+                .style()
+                .add_modifier
+                .contains(Modifier::REVERSED);
+            (term.host_cursor(), reversed)
+        };
+
+        assert_eq!(render(Default::default()), (None, true));
+
+        let style = term_wm_pty_engine::CursorStyle {
+            shape: term_wm_pty_engine::CursorShape::SteadyBar,
+            color: Some((1, 2, 3)),
+        };
+        let (host, reversed) = render(style);
+        assert_eq!(host, Some(HostCursor { x: 8, y: 2, style }));
+        assert!(!reversed, "styled cursor must not be painted");
+    }
+
     #[test]
     fn render_screen_entering_direct_mode_suppresses_scroll() {
         let (handle, shared) = make_handle();
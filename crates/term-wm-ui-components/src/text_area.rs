@@ -0,0 +1,544 @@
+use std::collections::VecDeque;
+
+use ratatui::layout::Rect;
+use ratatui::style::Modifier;
+use term_wm_core::actions::{EventResult, TermWmAction};
+use term_wm_core::components::{Component, ComponentContext, SelectionStatus};
+use term_wm_core::events::{Event, KeyCode, KeyEvent, KeyKind, MouseButton, MouseEventKind};
+use term_wm_core::hitbox_registry::{ComponentOwner, HitboxId, HitboxRegistry};
+use term_wm_core::window::WindowKey;
+use term_wm_layout_engine::LayoutRect;
+
+use crate::helpers::{layout_rect_to_clipped_rect, safe_set_string};
+use crate::text_buffer::{TextBuffer, apply_edit_key, multi_line};
+use crate::text_input::{TextInputStyle, Validator, render_text_row, scroll_to_show};
+
+/// Rows a text area asks for when none are configured.
+pub const DEFAULT_TEXT_AREA_ROWS: u16 = 5;
+
+/// A multi-line editable text field.
+///
+/// Shares the editing model of [`TextInputComponent`](crate::TextInputComponent)
+/// (grapheme/word movement, selection, undo/redo, emacs kills, placeholder,
+/// validation, paste as a single edit). Enter inserts a line break; Up/Down
+/// and PageUp/PageDown move between lines keeping the display column;
+/// `Ctrl+Home`/`Ctrl+End` jump to the start/end of the text. Lines do not
+/// wrap — the view scrolls both ways to follow the cursor.
+pub struct TextAreaComponent {
+    buffer: TextBuffer,
+    placeholder: String,
+    validator: Option<Validator>,
+    error: Option<String>,
+    style: Option<TextInputStyle>,
+    rows: u16,
+    /// First visible line and display column.
+    scroll_row: usize,
+    scroll_col: usize,
+    /// Display column Up/Down aim for, kept across short lines.
+    goal_column: Option<usize>,
+    /// Scroll the view to the cursor on the next render. Cleared by wheel
+    /// scrolling so the view can move away until the next key.
+    follow_cursor: bool,
+    /// Text area of the last render (screen coordinates) for mouse mapping.
+    last_area: LayoutRect,
+    dragging: bool,
+    changed: bool,
+    hitbox_id: HitboxId,
+}
+
+impl std::fmt::Debug for TextAreaComponent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TextAreaComponent")
+            .field("value", &self.buffer.text())
+            .field("rows", &self.rows)
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for TextAreaComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextAreaComponent {
+    pub fn new() -> Self {
+        Self {
+            buffer: TextBuffer::new(),
+            placeholder: String::new(),
+            validator: None,
+            error: None,
+            style: None,
+            rows: DEFAULT_TEXT_AREA_ROWS,
+            scroll_row: 0,
+            scroll_col: 0,
+            goal_column: None,
+            follow_cursor: true,
+            last_area: LayoutRect::default(),
+            dragging: false,
+            changed: false,
+            hitbox_id: HitboxId::new(),
+        }
+    }
+
+    pub fn with_value(mut self, value: impl Into<String>) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Preferred height in text rows (the validation message adds one more).
+    pub fn with_rows(mut self, rows: u16) -> Self {
+        self.rows = rows.max(1);
+        self
+    }
+
+    pub fn with_validator(
+        mut self,
+        validator: impl Fn(&str) -> Result<(), String> + 'static,
+    ) -> Self {
+        self.validator = Some(Box::new(validator));
+        self.validate();
+        self
+    }
+
+    pub fn with_style(mut self, style: TextInputStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    pub fn value(&self) -> &str {
+        self.buffer.text()
+    }
+
+    /// Replace the value programmatically (clears undo history).
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        let value = value.into();
+        self.buffer.set_text(multi_line(&value));
+        self.scroll_row = 0;
+        self.scroll_col = 0;
+        self.goal_column = None;
+        self.after_edit();
+    }
+
+    pub fn clear(&mut self) {
+        self.set_value(String::new());
+    }
+
+    pub fn buffer(&self) -> &TextBuffer {
+        &self.buffer
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Returns `true` once after any change to the value.
+    pub fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+
+    /// Insert text as a single edit (paste / IME commit).
+    pub fn insert_text(&mut self, text: &str) -> bool {
+        let changed = self.buffer.insert(&multi_line(text));
+        if changed {
+            self.goal_column = None;
+            self.after_edit();
+        }
+        changed
+    }
+
+    /// Handle a key press without the keyboard-focus check.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> EventResult<TermWmAction> {
+        if key.kind == KeyKind::Release {
+            return EventResult::Ignored;
+        }
+        self.follow_cursor = true;
+        let page = self.visible_rows().max(1) as isize;
+        let lines = match key.code {
+            KeyCode::Up => -1,
+            KeyCode::Down => 1,
+            KeyCode::PageUp => -page,
+            KeyCode::PageDown => page,
+            KeyCode::Enter if !key.modifiers.control && !key.modifiers.alt => {
+                self.insert_text("\n");
+                return EventResult::Consumed;
+            }
+            _ => {
+                let Some(changed) = apply_edit_key(&mut self.buffer, key, multi_line) else {
+                    return EventResult::Ignored;
+                };
+                self.goal_column = None;
+                if changed {
+                    self.after_edit();
+                }
+                return EventResult::Consumed;
+            }
+        };
+        let column = *self
+            .goal_column
+            .get_or_insert_with(|| self.buffer.column_of(self.buffer.cursor()));
+        if !self.buffer.move_lines(lines, column, key.modifiers.shift) {
+            // Already on the first/last line: go to its start/end instead.
+            if lines < 0 {
+                self.buffer.move_to_start(key.modifiers.shift);
+            } else {
+                self.buffer.move_to_end(key.modifiers.shift);
+            }
+        }
+        EventResult::Consumed
+    }
+
+    fn after_edit(&mut self) {
+        self.changed = true;
+        self.follow_cursor = true;
+        self.validate();
+    }
+
+    fn validate(&mut self) {
+        self.error = self
+            .validator
+            .as_ref()
+            .and_then(|validate| validate(self.buffer.text()).err());
+    }
+
+    fn visible_rows(&self) -> u16 {
+        let reserved = u16::from(self.validator.is_some());
+        self.last_area.height.saturating_sub(reserved)
+    }
+
+    fn owns_keyboard(&self, ctx: &ComponentContext) -> bool {
+        ctx.keyboard_focus_id()
+            .is_none_or(|focus| focus == self.hitbox_id)
+    }
+
+    /// Byte offset under screen cell (`column`, `row`), relative to the last
+    /// rendered area.
+    fn offset_at(&self, column: u16, row: u16) -> usize {
+        let local_col = (i32::from(column) - self.last_area.x).max(0) as usize;
+        let local_row = (i32::from(row) - self.last_area.y).max(0) as usize;
+        let line = (self.scroll_row + local_row).min(self.buffer.line_count() - 1);
+        let start = self
+            .buffer
+            .text()
+            .split('\n')
+            .take(line)
+            .map(|l| l.len() + 1)
+            .sum();
+        self.buffer
+            .byte_at_column(start, self.scroll_col + local_col)
+    }
+}
+
+impl Component<TermWmAction> for TextAreaComponent {
+    fn hitbox_id(&self) -> Option<HitboxId> {
+        Some(self.hitbox_id)
+    }
+
+    fn desired_height(&self, _width: u16) -> u16 {
+        self.rows + u16::from(self.validator.is_some())
+    }
+
+    fn handle_events(
+        &mut self,
+        event: &Event,
+        ctx: &ComponentContext,
+    ) -> EventResult<TermWmAction> {
+        match event {
+            Event::Key(key) if self.owns_keyboard(ctx) => self.handle_key(key),
+            Event::Paste(text) if self.owns_keyboard(ctx) => {
+                self.insert_text(text);
+                EventResult::Consumed
+            }
+            Event::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::Press(MouseButton::Left) => {
+                    if ctx.localize_mouse_click(event, MouseButton::Left).is_none() {
+                        return EventResult::Ignored;
+                    }
+                    if let Some(area) = ctx.screen_area() {
+                        self.last_area = area;
+                    }
+                    let pos = self.offset_at(mouse.column, mouse.row);
+                    self.buffer.move_to(pos, mouse.modifiers.shift);
+                    self.goal_column = None;
+                    self.dragging = true;
+                    EventResult::Action(TermWmAction::RequestKeyboardFocus(self.hitbox_id))
+                }
+                MouseEventKind::Drag(MouseButton::Left) if self.dragging => {
+                    let pos = self.offset_at(mouse.column, mouse.row);
+                    self.buffer.move_to(pos, true);
+                    EventResult::Consumed
+                }
+                MouseEventKind::Release(MouseButton::Left) if self.dragging => {
+                    self.dragging = false;
+                    EventResult::Consumed
+                }
+                MouseEventKind::ScrollUp | MouseEventKind::ScrollDown
+                    if ctx
+                        .screen_area()
+                        .is_some_and(|area| area.contains(mouse.column, mouse.row)) =>
+                {
+                    self.follow_cursor = false;
+                    self.scroll_row = if mouse.kind == MouseEventKind::ScrollUp {
+                        self.scroll_row.saturating_sub(1)
+                    } else {
+                        (self.scroll_row + 1).min(self.buffer.line_count() - 1)
+                    };
+                    EventResult::Consumed
+                }
+                _ => EventResult::Ignored,
+            },
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn update(
+        &mut self,
+        action: TermWmAction,
+        _ctx: &ComponentContext,
+        _actions: &mut VecDeque<(WindowKey, TermWmAction)>,
+    ) {
+        if let TermWmAction::ClipboardPaste(text) = action {
+            self.insert_text(&text);
+        }
+    }
+
+    fn render(
+        &mut self,
+        backend: &mut dyn term_wm_render::RenderBackend,
+        area: LayoutRect,
+        ctx: &ComponentContext,
+        registry: &mut HitboxRegistry,
+    ) {
+        let rect = layout_rect_to_clipped_rect(area);
+        let backend = crate::helpers::downcast_ratatui(backend);
+        let bounds = rect.intersection(backend.buffer.area);
+        if bounds.width == 0 || bounds.height == 0 {
+            return;
+        }
+        let screen_area = ctx.screen_area().unwrap_or(area);
+        self.last_area = screen_area;
+        if let Some(key) = ctx.window_key() {
+            registry.register(self.hitbox_id, ComponentOwner::Window(key), screen_area);
+        }
+        let focused = ctx.focused()
+            && ctx
+                .keyboard_focus_id()
+                .is_none_or(|id| id == self.hitbox_id);
+        let style = self
+            .style
+            .unwrap_or_else(|| TextInputStyle::from_theme(&ctx.config().theme));
+        let buffer = &mut backend.buffer;
+
+        let reserved = u16::from(self.error.is_some() && bounds.height >= 2);
+        let text_rows = bounds.height - reserved;
+        let text_rect = Rect {
+            height: text_rows,
+            ..bounds
+        };
+        buffer.set_style(text_rect, style.text);
+
+        let cursor = self.buffer.cursor();
+        if self.follow_cursor {
+            let line = self.buffer.line_index(cursor);
+            let column = self.buffer.column_of(cursor);
+            self.scroll_row = scroll_to_show(self.scroll_row, line, usize::from(text_rows));
+            self.scroll_col = scroll_to_show(self.scroll_col, column, usize::from(bounds.width));
+        }
+
+        if self.buffer.is_empty() {
+            for (i, line) in self
+                .placeholder
+                .lines()
+                .take(usize::from(text_rows))
+                .enumerate()
+            {
+                let text: String = line.chars().take(usize::from(bounds.width)).collect();
+                let y = bounds.y + i as u16;
+                safe_set_string(buffer, text_rect, bounds.x, y, &text, style.placeholder);
+            }
+            if focused && let Some(cell) = buffer.cell_mut((bounds.x, bounds.y)) {
+                cell.set_style(style.cursor);
+            }
+        } else {
+            let text = self.buffer.text();
+            let selection = self.buffer.selection();
+            let mut start = 0;
+            for (index, line) in text.split('\n').enumerate() {
+                let range = start..start + line.len();
+                start = range.end + 1;
+                if index < self.scroll_row {
+                    continue;
+                }
+                let row = index - self.scroll_row;
+                if row >= usize::from(text_rows) {
+                    break;
+                }
+                render_text_row(
+                    buffer,
+                    text_rect,
+                    bounds.y + row as u16,
+                    text,
+                    range,
+                    self.scroll_col,
+                    selection.as_ref(),
+                    focused.then_some(cursor),
+                    &style,
+                );
+            }
+        }
+
+        if reserved == 1
+            && let Some(error) = &self.error
+        {
+            let y = bounds.y + text_rows;
+            let text: String = error.chars().take(usize::from(bounds.width)).collect();
+            safe_set_string(
+                buffer,
+                bounds,
+                bounds.x,
+                y,
+                &text,
+                style.error.add_modifier(Modifier::ITALIC),
+            );
+        }
+    }
+
+    fn selection_status(&self) -> SelectionStatus {
+        SelectionStatus {
+            active: self.buffer.selection().is_some(),
+            dragging: self.dragging,
+        }
+    }
+
+    fn selection_text(&self) -> Option<String> {
+        self.buffer.selected_text().map(str::to_string)
+    }
+
+    fn clear_selection(&mut self) {
+        self.buffer.clear_selection();
+    }
+
+    fn paste(&mut self, text: &str) -> bool {
+        self.insert_text(text)
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::buffer::Buffer;
+    use term_wm_core::events::KeyModifiers;
+
+    fn press(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE, KeyKind::Press))
+    }
+
+    fn type_str(area: &mut TextAreaComponent, text: &str) {
+        let ctx = ComponentContext::new(true);
+        for c in text.chars() {
+            let event = if c == '\n' {
+                press(KeyCode::Enter)
+            } else {
+                press(KeyCode::Char(c))
+            };
+            area.handle_events(&event, &ctx);
+        }
+    }
+
+    fn render_rows(area: &mut TextAreaComponent, width: u16, height: u16) -> Vec<String> {
+        let rect = Rect::new(0, 0, width, height);
+        let mut backend = term_wm_console::RatatuiBackend::new_simple(Buffer::empty(rect), rect);
+        let layout = LayoutRect {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        };
+        let ctx = ComponentContext::new(true).with_screen_area(layout);
+        area.render(&mut backend, layout, &ctx, &mut HitboxRegistry::new());
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| backend.buffer[(x, y)].symbol().to_string())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn enter_inserts_line_breaks_and_arrows_move_between_lines() {
+        let mut area = TextAreaComponent::new();
+        type_str(&mut area, "first line\nab\nthird line");
+        assert_eq!(area.value(), "first line\nab\nthird line");
+
+        let ctx = ComponentContext::new(true);
+        area.handle_events(&press(KeyCode::Up), &ctx);
+        // "ab" is shorter than column 10: the cursor lands at its end...
+        assert_eq!(area.buffer().cursor(), "first line\nab".len());
+        area.handle_events(&press(KeyCode::Up), &ctx);
+        // ...but the goal column survives the short line.
+        assert_eq!(area.buffer().cursor(), "first line".len());
+        area.handle_events(&press(KeyCode::Up), &ctx);
+        assert_eq!(area.buffer().cursor(), 0);
+    }
+
+    #[test]
+    fn paste_normalizes_line_endings_as_one_undo_step() {
+        let mut area = TextAreaComponent::new();
+        assert!(area.paste("a\r\nb\tc"));
+        assert_eq!(area.value(), "a\nb    c");
+        let undo = Event::Key(KeyEvent::new(
+            KeyCode::Char('z'),
+            KeyModifiers {
+                control: true,
+                ..KeyModifiers::NONE
+            },
+            KeyKind::Press,
+        ));
+        area.handle_events(&undo, &ComponentContext::new(true));
+        assert_eq!(area.value(), "");
+    }
+
+    #[test]
+    fn render_scrolls_to_keep_cursor_visible() {
+        let mut area = TextAreaComponent::new().with_value("1\n2\n3\n4\n5");
+        let rows = render_rows(&mut area, 4, 3);
+        assert_eq!(rows[0].trim(), "3");
+        assert_eq!(rows[2].trim(), "5");
+
+        area.set_value("");
+        area = area.with_placeholder("notes");
+        assert_eq!(render_rows(&mut area, 8, 3)[0].trim(), "notes");
+    }
+
+    #[test]
+    fn click_maps_to_line_and_column() {
+        let mut area = TextAreaComponent::new().with_value("abc\ndefgh");
+        render_rows(&mut area, 10, 3);
+        let ctx = ComponentContext::new(true).with_screen_area(LayoutRect {
+            x: 0,
+            y: 0,
+            width: 10,
+            height: 3,
+        });
+        let click = Event::Mouse(term_wm_core::events::MouseEvent {
+            kind: MouseEventKind::Press(MouseButton::Left),
+            modifiers: KeyModifiers::NONE,
+            column: 2,
+            row: 1,
+        });
+        area.handle_events(&click, &ctx);
+        assert_eq!(area.buffer().cursor(), "abc\nde".len());
+    }
+}
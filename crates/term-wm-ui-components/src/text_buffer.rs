@@ -0,0 +1,709 @@
+//! Grapheme-aware editable text shared by [`TextInputComponent`] and
+//! [`TextAreaComponent`].
+//!
+//! The cursor and the selection anchor are byte offsets that always sit on a
+//! grapheme-cluster boundary, so a combining sequence or an emoji composed by
+//! an IME is moved over and deleted as one unit. Undo history stores whole
+//! snapshots; consecutive typed (or deleted) graphemes coalesce into a single
+//! step so `Ctrl+Z` undoes a word at a time rather than a keystroke.
+//!
+//! [`TextInputComponent`]: crate::TextInputComponent
+//! [`TextAreaComponent`]: crate::TextAreaComponent
+
+use std::collections::VecDeque;
+use std::ops::Range;
+
+use term_wm_core::events::{KeyCode, KeyEvent};
+use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
+use unicode_width::UnicodeWidthStr;
+
+/// Maximum number of undo snapshots kept per buffer.
+const UNDO_LIMIT: usize = 200;
+
+/// Columns a pasted or typed tab expands to; the renderers draw one grapheme
+/// per cell and have no tab stops.
+const TAB_WIDTH: usize = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Snapshot {
+    text: String,
+    cursor: usize,
+    anchor: Option<usize>,
+}
+
+/// What the previous mutation was, for undo coalescing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditKind {
+    /// A single typed grapheme.
+    Type,
+    /// A single grapheme removed by Backspace/Delete.
+    Erase,
+    /// Anything else (paste, word/line kills, replacing a selection).
+    Other,
+}
+
+/// An editable string with a grapheme-aligned cursor, selection and undo.
+#[derive(Debug, Clone, Default)]
+pub struct TextBuffer {
+    text: String,
+    cursor: usize,
+    anchor: Option<usize>,
+    undo: VecDeque<Snapshot>,
+    redo: Vec<Snapshot>,
+    last_edit: Option<EditKind>,
+}
+
+impl TextBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A buffer holding `text` with the cursor at its end.
+    pub fn with_text(text: impl Into<String>) -> Self {
+        let mut buffer = Self::new();
+        buffer.set_text(text);
+        buffer
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Byte offset of the cursor.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Replace the whole text, moving the cursor to the end. Programmatic
+    /// replacement is not an edit: undo history is cleared.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        self.cursor = self.text.len();
+        self.anchor = None;
+        self.undo.clear();
+        self.redo.clear();
+        self.last_edit = None;
+    }
+
+    /// The selected byte range, if a non-empty selection exists.
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.anchor?;
+        if anchor == self.cursor {
+            return None;
+        }
+        Some(anchor.min(self.cursor)..anchor.max(self.cursor))
+    }
+
+    pub fn selected_text(&self) -> Option<&str> {
+        self.selection().map(|range| &self.text[range])
+    }
+
+    pub fn select_all(&mut self) {
+        self.anchor = Some(0);
+        self.cursor = self.text.len();
+        self.last_edit = None;
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.anchor = None;
+    }
+
+    // ── Movement ────────────────────────────────────────────────────────
+
+    /// Move the cursor to `pos` (snapped back to a grapheme boundary),
+    /// extending the selection when `extend` is set.
+    pub fn move_to(&mut self, pos: usize, extend: bool) {
+        let pos = self.snap(pos.min(self.text.len()));
+        if extend {
+            self.anchor.get_or_insert(self.cursor);
+        } else {
+            self.anchor = None;
+        }
+        self.cursor = pos;
+        self.last_edit = None;
+    }
+
+    /// One grapheme left. Without `extend`, an active selection collapses to
+    /// its start instead.
+    pub fn move_left(&mut self, extend: bool) {
+        match self.selection() {
+            Some(range) if !extend => self.move_to(range.start, false),
+            _ => self.move_to(self.prev_boundary(self.cursor), extend),
+        }
+    }
+
+    /// One grapheme right. Without `extend`, an active selection collapses to
+    /// its end instead.
+    pub fn move_right(&mut self, extend: bool) {
+        match self.selection() {
+            Some(range) if !extend => self.move_to(range.end, false),
+            _ => self.move_to(self.next_boundary(self.cursor), extend),
+        }
+    }
+
+    pub fn move_word_left(&mut self, extend: bool) {
+        self.move_to(self.prev_word_boundary(self.cursor), extend);
+    }
+
+    pub fn move_word_right(&mut self, extend: bool) {
+        self.move_to(self.next_word_boundary(self.cursor), extend);
+    }
+
+    /// Start of the cursor's line.
+    pub fn move_home(&mut self, extend: bool) {
+        self.move_to(self.line_start(self.cursor), extend);
+    }
+
+    /// End of the cursor's line.
+    pub fn move_end(&mut self, extend: bool) {
+        self.move_to(self.line_end(self.cursor), extend);
+    }
+
+    pub fn move_to_start(&mut self, extend: bool) {
+        self.move_to(0, extend);
+    }
+
+    pub fn move_to_end(&mut self, extend: bool) {
+        self.move_to(self.text.len(), extend);
+    }
+
+    /// Move `lines` lines up (negative) or down (positive), landing at display
+    /// column `column` or the end of a shorter line. Returns false when the
+    /// cursor is already on the first/last line.
+    pub fn move_lines(&mut self, lines: isize, column: usize, extend: bool) -> bool {
+        let current = self.line_index(self.cursor);
+        let last = self.line_count() - 1;
+        let target = current.saturating_add_signed(lines).min(last);
+        if target == current {
+            return false;
+        }
+        let start = self.line_start_of(target);
+        let pos = self.byte_at_column(start, column);
+        self.move_to(pos, extend);
+        true
+    }
+
+    // ── Editing ─────────────────────────────────────────────────────────
+
+    /// Insert `text` at the cursor, replacing any selection. Returns whether
+    /// the buffer changed. Callers sanitize first ([`single_line`] /
+    /// [`multi_line`]); the buffer stores whatever it is given.
+    pub fn insert(&mut self, text: &str) -> bool {
+        if text.is_empty() && self.selection().is_none() {
+            return false;
+        }
+        let typed = self.selection().is_none() && text.graphemes(true).count() == 1;
+        self.checkpoint(if typed {
+            EditKind::Type
+        } else {
+            EditKind::Other
+        });
+        self.delete_selection_unrecorded();
+        self.text.insert_str(self.cursor, text);
+        self.cursor += text.len();
+        // Whitespace ends a typing group so undo steps back a word at a time.
+        if typed && text.chars().all(char::is_whitespace) {
+            self.last_edit = None;
+        }
+        true
+    }
+
+    /// Delete the selection, or the grapheme before the cursor.
+    pub fn backspace(&mut self) -> bool {
+        if self.selection().is_some() {
+            return self.delete_selection();
+        }
+        let start = self.prev_boundary(self.cursor);
+        self.erase(start..self.cursor, EditKind::Erase)
+    }
+
+    /// Delete the selection, or the grapheme after the cursor.
+    pub fn delete(&mut self) -> bool {
+        if self.selection().is_some() {
+            return self.delete_selection();
+        }
+        let end = self.next_boundary(self.cursor);
+        self.erase(self.cursor..end, EditKind::Erase)
+    }
+
+    /// Delete back to the previous word start (`Ctrl+W`).
+    pub fn delete_word_back(&mut self) -> bool {
+        if self.selection().is_some() {
+            return self.delete_selection();
+        }
+        let start = self.prev_word_boundary(self.cursor);
+        self.erase(start..self.cursor, EditKind::Other)
+    }
+
+    /// Delete forward to the next word end (`Alt+D`).
+    pub fn delete_word_forward(&mut self) -> bool {
+        if self.selection().is_some() {
+            return self.delete_selection();
+        }
+        let end = self.next_word_boundary(self.cursor);
+        self.erase(self.cursor..end, EditKind::Other)
+    }
+
+    /// Delete back to the start of the line (`Ctrl+U`).
+    pub fn delete_to_line_start(&mut self) -> bool {
+        let start = self.line_start(self.cursor);
+        self.anchor = None;
+        self.erase(start..self.cursor, EditKind::Other)
+    }
+
+    /// Delete forward to the end of the line (`Ctrl+K`).
+    pub fn delete_to_line_end(&mut self) -> bool {
+        let end = self.line_end(self.cursor);
+        self.anchor = None;
+        self.erase(self.cursor..end, EditKind::Other)
+    }
+
+    pub fn delete_selection(&mut self) -> bool {
+        if self.selection().is_none() {
+            return false;
+        }
+        self.checkpoint(EditKind::Other);
+        self.delete_selection_unrecorded();
+        true
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn undo(&mut self) -> bool {
+        let Some(snapshot) = self.undo.pop_back() else {
+            return false;
+        };
+        self.redo.push(self.snapshot());
+        self.restore(snapshot);
+        true
+    }
+
+    pub fn redo(&mut self) -> bool {
+        let Some(snapshot) = self.redo.pop() else {
+            return false;
+        };
+        self.undo.push_back(self.snapshot());
+        self.restore(snapshot);
+        true
+    }
+
+    // ── Geometry ────────────────────────────────────────────────────────
+
+    /// Byte offset where the line containing `pos` starts.
+    pub fn line_start(&self, pos: usize) -> usize {
+        self.text[..pos].rfind('\n').map_or(0, |i| i + 1)
+    }
+
+    /// Byte offset where the line containing `pos` ends (before its `\n`).
+    pub fn line_end(&self, pos: usize) -> usize {
+        self.text[pos..]
+            .find('\n')
+            .map_or(self.text.len(), |i| pos + i)
+    }
+
+    /// Zero-based line number of `pos`.
+    pub fn line_index(&self, pos: usize) -> usize {
+        self.text[..pos].matches('\n').count()
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.text.matches('\n').count() + 1
+    }
+
+    /// Display column of `pos` within its line.
+    pub fn column_of(&self, pos: usize) -> usize {
+        self.text[self.line_start(pos)..pos].width()
+    }
+
+    /// Byte offset on the line starting at `line_start` whose display column
+    /// is the last one not past `column`.
+    pub fn byte_at_column(&self, line_start: usize, column: usize) -> usize {
+        let end = self.line_end(line_start);
+        let mut width = 0;
+        for (i, g) in self.text[line_start..end].grapheme_indices(true) {
+            let w = g.width();
+            if width + w > column {
+                return line_start + i;
+            }
+            width += w;
+        }
+        end
+    }
+
+    fn line_start_of(&self, line: usize) -> usize {
+        if line == 0 {
+            return 0;
+        }
+        self.text
+            .match_indices('\n')
+            .nth(line - 1)
+            .map_or(self.text.len(), |(i, _)| i + 1)
+    }
+
+    fn snap(&self, pos: usize) -> usize {
+        let mut cursor = GraphemeCursor::new(pos, self.text.len(), true);
+        if cursor.is_boundary(&self.text, 0).unwrap_or(true) {
+            pos
+        } else {
+            self.prev_boundary(pos)
+        }
+    }
+
+    fn prev_boundary(&self, pos: usize) -> usize {
+        let mut cursor = GraphemeCursor::new(pos, self.text.len(), true);
+        cursor
+            .prev_boundary(&self.text, 0)
+            .ok()
+            .flatten()
+            .unwrap_or(0)
+    }
+
+    fn next_boundary(&self, pos: usize) -> usize {
+        let mut cursor = GraphemeCursor::new(pos, self.text.len(), true);
+        cursor
+            .next_boundary(&self.text, 0)
+            .ok()
+            .flatten()
+            .unwrap_or(self.text.len())
+    }
+
+    fn prev_word_boundary(&self, pos: usize) -> usize {
+        let mut at = pos;
+        let mut graphemes = self.text[..pos].grapheme_indices(true).rev().peekable();
+        while let Some((i, _)) = graphemes.next_if(|(_, g)| !is_word(g)) {
+            at = i;
+        }
+        while let Some((i, _)) = graphemes.next_if(|(_, g)| is_word(g)) {
+            at = i;
+        }
+        at
+    }
+
+    fn next_word_boundary(&self, pos: usize) -> usize {
+        let mut at = pos;
+        let mut graphemes = self.text[pos..].grapheme_indices(true).peekable();
+        while let Some((i, g)) = graphemes.next_if(|(_, g)| !is_word(g)) {
+            at = pos + i + g.len();
+        }
+        while let Some((i, g)) = graphemes.next_if(|(_, g)| is_word(g)) {
+            at = pos + i + g.len();
+        }
+        at
+    }
+
+    // ── Internals ───────────────────────────────────────────────────────
+
+    fn erase(&mut self, range: Range<usize>, kind: EditKind) -> bool {
+        if range.is_empty() {
+            return false;
+        }
+        self.checkpoint(kind);
+        self.text.replace_range(range.clone(), "");
+        self.cursor = range.start;
+        self.anchor = None;
+        true
+    }
+
+    fn delete_selection_unrecorded(&mut self) {
+        if let Some(range) = self.selection() {
+            self.text.replace_range(range.clone(), "");
+            self.cursor = range.start;
+        }
+        self.anchor = None;
+    }
+
+    /// Record the pre-edit state unless this edit continues a coalescing run.
+    fn checkpoint(&mut self, kind: EditKind) {
+        let coalesces = kind != EditKind::Other && self.last_edit == Some(kind);
+        self.last_edit = Some(kind);
+        if coalesces {
+            return;
+        }
+        self.undo.push_back(self.snapshot());
+        if self.undo.len() > UNDO_LIMIT {
+            self.undo.pop_front();
+        }
+        self.redo.clear();
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            text: self.text.clone(),
+            cursor: self.cursor,
+            anchor: self.anchor,
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.text = snapshot.text;
+        self.cursor = snapshot.cursor;
+        self.anchor = snapshot.anchor;
+        self.last_edit = None;
+    }
+}
+
+fn is_word(grapheme: &str) -> bool {
+    grapheme
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Normalize text for a single-line field: line breaks and tabs become spaces
+/// and other control characters are dropped.
+pub fn single_line(text: &str) -> String {
+    text.replace("\r\n", " ")
+        .chars()
+        .filter_map(|c| match c {
+            '\n' | '\r' | '\t' => Some(' '),
+            c if c.is_control() => None,
+            c => Some(c),
+        })
+        .collect()
+}
+
+/// Normalize text for a multi-line field: `\r\n`/`\r` become `\n`, tabs
+/// expand to spaces and other control characters are dropped.
+pub fn multi_line(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.replace("\r\n", "\n").chars() {
+        match c {
+            '\n' => out.push('\n'),
+            '\r' => out.push('\n'),
+            '\t' => out.extend(std::iter::repeat_n(' ', TAB_WIDTH)),
+            c if c.is_control() => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Apply the editing keys both text widgets share: character input,
+/// grapheme/word movement (`Shift` extends the selection), Backspace/Delete,
+/// the emacs bindings (`Ctrl+A/E/B/F/W/U/K/H/D`, `Alt+B/F/D`) and undo/redo
+/// (`Ctrl+Z`, `Ctrl+Y` or `Ctrl+Shift+Z`). Returns `None` for keys it does not
+/// handle, so each widget can add its own (Enter, Up/Down, …).
+pub(crate) fn apply_edit_key(
+    buffer: &mut TextBuffer,
+    key: &KeyEvent,
+    sanitize: fn(&str) -> String,
+) -> Option<bool> {
+    let m = key.modifiers;
+    let extend = m.shift;
+    let word = m.control || m.alt;
+    let changed = match key.code {
+        KeyCode::Char(c) if m.control => match c.to_ascii_lowercase() {
+            'a' => {
+                buffer.move_home(false);
+                false
+            }
+            'e' => {
+                buffer.move_end(false);
+                false
+            }
+            'b' => {
+                buffer.move_left(false);
+                false
+            }
+            'f' => {
+                buffer.move_right(false);
+                false
+            }
+            'w' => buffer.delete_word_back(),
+            'u' => buffer.delete_to_line_start(),
+            'k' => buffer.delete_to_line_end(),
+            'h' => buffer.backspace(),
+            'd' => buffer.delete(),
+            'z' if m.shift => buffer.redo(),
+            'z' => buffer.undo(),
+            'y' => buffer.redo(),
+            _ => return None,
+        },
+        KeyCode::Char(c) if m.alt => match c {
+            'b' => {
+                buffer.move_word_left(false);
+                false
+            }
+            'f' => {
+                buffer.move_word_right(false);
+                false
+            }
+            'd' => buffer.delete_word_forward(),
+            _ => return None,
+        },
+        KeyCode::Char(c) => buffer.insert(&sanitize(c.encode_utf8(&mut [0u8; 4]))),
+        KeyCode::Backspace if word => buffer.delete_word_back(),
+        KeyCode::Backspace => buffer.backspace(),
+        KeyCode::Delete if word => buffer.delete_word_forward(),
+        KeyCode::Delete => buffer.delete(),
+        KeyCode::Left if word => {
+            buffer.move_word_left(extend);
+            false
+        }
+        KeyCode::Left => {
+            buffer.move_left(extend);
+            false
+        }
+        KeyCode::Right if word => {
+            buffer.move_word_right(extend);
+            false
+        }
+        KeyCode::Right => {
+            buffer.move_right(extend);
+            false
+        }
+        KeyCode::Home if m.control => {
+            buffer.move_to_start(extend);
+            false
+        }
+        KeyCode::Home => {
+            buffer.move_home(extend);
+            false
+        }
+        KeyCode::End if m.control => {
+            buffer.move_to_end(extend);
+            false
+        }
+        KeyCode::End => {
+            buffer.move_end(extend);
+            false
+        }
+        _ => return None,
+    };
+    Some(changed)
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use term_wm_core::events::{KeyKind, KeyModifiers};
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers, KeyKind::Press)
+    }
+
+    const CTRL: KeyModifiers = KeyModifiers {
+        shift: false,
+        control: true,
+        alt: false,
+    };
+
+    #[test]
+    fn cursor_moves_over_whole_grapheme_clusters() {
+        // "e" + combining acute, then a family emoji (ZWJ sequence).
+        let mut buffer = TextBuffer::with_text("e\u{301}👨‍👩‍👧x");
+        buffer.move_left(false);
+        assert_eq!(&buffer.text()[buffer.cursor()..], "x");
+        buffer.move_left(false);
+        assert_eq!(&buffer.text()[buffer.cursor()..], "👨‍👩‍👧x");
+        buffer.backspace();
+        assert_eq!(buffer.text(), "👨‍👩‍👧x");
+        buffer.delete();
+        assert_eq!(buffer.text(), "x");
+    }
+
+    #[test]
+    fn word_motion_and_kills() {
+        let mut buffer = TextBuffer::with_text("git commit --amend");
+        buffer.move_word_left(false);
+        assert_eq!(&buffer.text()[buffer.cursor()..], "amend");
+        buffer.move_word_left(false);
+        assert_eq!(&buffer.text()[buffer.cursor()..], "commit --amend");
+        buffer.move_word_right(false);
+        assert_eq!(&buffer.text()[buffer.cursor()..], " --amend");
+
+        buffer.move_to_end(false);
+        assert!(buffer.delete_word_back());
+        assert_eq!(buffer.text(), "git commit --");
+        assert!(buffer.delete_to_line_start());
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn typing_replaces_selection() {
+        let mut buffer = TextBuffer::with_text("hello world");
+        buffer.move_word_left(false);
+        buffer.move_to_end(true);
+        assert_eq!(buffer.selected_text(), Some("world"));
+        buffer.insert("there");
+        assert_eq!(buffer.text(), "hello there");
+        assert_eq!(buffer.selection(), None);
+    }
+
+    #[test]
+    fn undo_coalesces_typing_per_word() {
+        let mut buffer = TextBuffer::new();
+        for c in "ab cd".chars() {
+            buffer.insert(&c.to_string());
+        }
+        assert!(buffer.undo());
+        assert_eq!(buffer.text(), "ab ");
+        assert!(buffer.undo());
+        assert_eq!(buffer.text(), "");
+        assert!(!buffer.undo());
+        assert!(buffer.redo());
+        assert_eq!(buffer.text(), "ab ");
+        assert!(buffer.redo());
+        assert_eq!(buffer.text(), "ab cd");
+        assert!(!buffer.redo());
+    }
+
+    #[test]
+    fn edit_after_undo_drops_redo() {
+        let mut buffer = TextBuffer::new();
+        buffer.insert("one");
+        buffer.undo();
+        buffer.insert("two");
+        assert!(!buffer.can_redo());
+        assert_eq!(buffer.text(), "two");
+    }
+
+    #[test]
+    fn line_motion_keeps_display_column() {
+        let mut buffer = TextBuffer::with_text("abcdef\nxy\n漢字漢字");
+        buffer.move_to(4, false);
+        assert!(buffer.move_lines(1, 4, false));
+        assert_eq!(buffer.cursor(), buffer.line_end(buffer.cursor()));
+        assert!(buffer.move_lines(1, 4, false));
+        // Column 4 falls after two double-width characters.
+        assert_eq!(&buffer.text()[buffer.cursor()..], "漢字");
+        assert!(!buffer.move_lines(1, 4, false));
+    }
+
+    #[test]
+    fn sanitizers_normalize_pasted_text() {
+        assert_eq!(single_line("a\r\nb\tc\x07"), "a b c");
+        assert_eq!(multi_line("a\r\nb\rc\td"), "a\nb\nc    d");
+    }
+
+    #[test]
+    fn emacs_keys_edit_buffer() {
+        let mut buffer = TextBuffer::with_text("foo bar");
+        assert_eq!(
+            apply_edit_key(&mut buffer, &key(KeyCode::Char('w'), CTRL), single_line),
+            Some(true)
+        );
+        assert_eq!(buffer.text(), "foo ");
+        apply_edit_key(&mut buffer, &key(KeyCode::Char('a'), CTRL), single_line);
+        assert_eq!(buffer.cursor(), 0);
+        apply_edit_key(&mut buffer, &key(KeyCode::Char('k'), CTRL), single_line);
+        assert!(buffer.is_empty());
+        apply_edit_key(&mut buffer, &key(KeyCode::Char('z'), CTRL), single_line);
+        assert_eq!(buffer.text(), "foo ");
+        assert_eq!(
+            apply_edit_key(&mut buffer, &key(KeyCode::Char('q'), CTRL), single_line),
+            None
+        );
+    }
+}
@@ -0,0 +1,804 @@
+use std::collections::VecDeque;
+use std::ops::Range;
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use term_wm_core::actions::{EventResult, TermWmAction};
+use term_wm_core::components::{Component, ComponentContext, SelectionStatus};
+use term_wm_core::events::{Event, KeyCode, KeyEvent, KeyKind, MouseButton, MouseEventKind};
+use term_wm_core::hitbox_registry::{ComponentOwner, HitboxId, HitboxRegistry};
+use term_wm_core::theme::Theme;
+use term_wm_core::window::WindowKey;
+use term_wm_layout_engine::LayoutRect;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::helpers::{color_to_ratatui, layout_rect_to_clipped_rect, safe_set_string};
+use crate::text_buffer::{TextBuffer, apply_edit_key, single_line};
+
+/// Validation hook: `Err(message)` marks the value invalid and the message is
+/// shown under the field.
+pub type Validator = Box<dyn Fn(&str) -> Result<(), String>>;
+
+/// Default number of entries an [`InputHistory`] keeps.
+pub const DEFAULT_HISTORY_LIMIT: usize = 100;
+
+/// Submitted values, browsable with Up/Down like a shell prompt. The text that
+/// was being typed before browsing started is kept as a draft and restored
+/// when browsing walks back past the newest entry.
+#[derive(Debug, Clone)]
+pub struct InputHistory {
+    entries: VecDeque<String>,
+    limit: usize,
+    /// Index into `entries` while browsing; `None` when editing the draft.
+    browsing: Option<usize>,
+    draft: String,
+}
+
+impl Default for InputHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_LIMIT)
+    }
+}
+
+impl InputHistory {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            limit: limit.max(1),
+            browsing: None,
+            draft: String::new(),
+        }
+    }
+
+    /// Record a submitted value. Empty values and repeats of the newest entry
+    /// are skipped. Ends any browsing.
+    pub fn push(&mut self, entry: impl Into<String>) {
+        let entry = entry.into();
+        self.browsing = None;
+        self.draft.clear();
+        if entry.is_empty() || self.entries.back() == Some(&entry) {
+            return;
+        }
+        self.entries.push_back(entry);
+        while self.entries.len() > self.limit {
+            self.entries.pop_front();
+        }
+    }
+
+    /// Oldest first.
+    pub fn entries(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Step to the next older entry. `current` is saved as the draft when
+    /// browsing starts.
+    pub fn older(&mut self, current: &str) -> Option<&str> {
+        let index = match self.browsing {
+            None => {
+                if self.entries.is_empty() {
+                    return None;
+                }
+                self.draft = current.to_string();
+                self.entries.len() - 1
+            }
+            Some(0) => return None,
+            Some(i) => i - 1,
+        };
+        self.browsing = Some(index);
+        self.entries.get(index).map(String::as_str)
+    }
+
+    /// Step to the next newer entry, or back to the draft past the newest.
+    pub fn newer(&mut self) -> Option<&str> {
+        let index = self.browsing?;
+        if index + 1 < self.entries.len() {
+            self.browsing = Some(index + 1);
+            self.entries.get(index + 1).map(String::as_str)
+        } else {
+            self.browsing = None;
+            Some(&self.draft)
+        }
+    }
+}
+
+/// Styles for the text widgets; [`TextInputStyle::from_theme`] is the
+/// default.
+#[derive(Debug, Clone, Copy)]
+pub struct TextInputStyle {
+    pub text: Style,
+    pub placeholder: Style,
+    pub selection: Style,
+    pub cursor: Style,
+    pub error: Style,
+}
+
+impl TextInputStyle {
+    pub fn from_theme(theme: &Theme) -> Self {
+        let text = Style::default()
+            .bg(color_to_ratatui(theme.surface))
+            .fg(color_to_ratatui(theme.text));
+        Self {
+            text,
+            placeholder: text.fg(color_to_ratatui(theme.text_muted)),
+            selection: Style::default()
+                .bg(color_to_ratatui(theme.selection_bg))
+                .fg(color_to_ratatui(theme.selection_fg)),
+            cursor: Style::default()
+                .bg(color_to_ratatui(theme.cursor_bg))
+                .fg(color_to_ratatui(theme.cursor_fg)),
+            error: text.fg(color_to_ratatui(theme.error)),
+        }
+    }
+}
+
+/// Draw one line of text into row `y` of `area`, starting at display column
+/// `scroll`. `line` is the byte range of the line within `text`; `selection`
+/// and `cursor` are byte offsets into `text` (the cursor is only drawn when
+/// `Some`). Wide graphemes cut by the left edge are skipped whole.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn render_text_row(
+    buffer: &mut Buffer,
+    area: Rect,
+    y: u16,
+    text: &str,
+    line: Range<usize>,
+    scroll: usize,
+    selection: Option<&Range<usize>>,
+    cursor: Option<usize>,
+    style: &TextInputStyle,
+) {
+    let right = usize::from(area.width);
+    let mut col = 0usize;
+    for (i, g) in text[line.clone()].grapheme_indices(true) {
+        let pos = line.start + i;
+        let w = g.width();
+        if col >= scroll + right {
+            break;
+        }
+        if col >= scroll && col + w <= scroll + right {
+            let cell_style = if cursor == Some(pos) {
+                style.cursor
+            } else if selection.is_some_and(|r| r.contains(&pos)) {
+                style.selection
+            } else {
+                style.text
+            };
+            let x = area.x + (col - scroll) as u16;
+            buffer.set_stringn(x, y, g, w.max(1), cell_style);
+        }
+        col += w;
+    }
+    // A cursor at the end of the line sits on the cell after the text.
+    if cursor == Some(line.end) && col >= scroll && col < scroll + right {
+        let x = area.x + (col - scroll) as u16;
+        if let Some(cell) = buffer.cell_mut((x, y)) {
+            cell.set_symbol(" ");
+            cell.set_style(style.cursor);
+        }
+    }
+}
+
+/// Smallest scroll offset change that keeps display column `col` inside a
+/// viewport `width` columns wide.
+pub(crate) fn scroll_to_show(scroll: usize, col: usize, width: usize) -> usize {
+    if width == 0 {
+        return scroll;
+    }
+    if col < scroll {
+        col
+    } else if col >= scroll + width {
+        col + 1 - width
+    } else {
+        scroll
+    }
+}
+
+/// A single-line editable text field.
+///
+/// Supports grapheme- and word-wise cursor movement, Shift-selection, mouse
+/// click/drag selection, undo/redo, the emacs kill bindings (`Ctrl+W`,
+/// `Ctrl+U`, `Ctrl+K`), placeholder text, an optional [`Validator`] and an
+/// optional [`InputHistory`] browsed with Up/Down. Pasted text (bracketed
+/// `Event::Paste`, `Component::paste`, or the clipboard `ClipboardPaste`
+/// action) is inserted as one edit and one undo step, with line breaks folded
+/// to spaces — so IME commits and multi-line clipboards never submit early.
+///
+/// Keys are accepted when the field holds keyboard focus, or when no
+/// component in the window claimed focus yet; clicking the field requests
+/// focus.
+pub struct TextInputComponent {
+    buffer: TextBuffer,
+    placeholder: String,
+    validator: Option<Validator>,
+    error: Option<String>,
+    history: Option<InputHistory>,
+    submit_action: Option<TermWmAction>,
+    style: Option<TextInputStyle>,
+    /// First visible display column.
+    scroll: usize,
+    /// Text area of the last render (screen coordinates) for mouse mapping.
+    last_area: LayoutRect,
+    dragging: bool,
+    changed: bool,
+    hitbox_id: HitboxId,
+}
+
+impl std::fmt::Debug for TextInputComponent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TextInputComponent")
+            .field("value", &self.buffer.text())
+            .field("placeholder", &self.placeholder)
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for TextInputComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextInputComponent {
+    pub fn new() -> Self {
+        Self {
+            buffer: TextBuffer::new(),
+            placeholder: String::new(),
+            validator: None,
+            error: None,
+            history: None,
+            submit_action: None,
+            style: None,
+            scroll: 0,
+            last_area: LayoutRect::default(),
+            dragging: false,
+            changed: false,
+            hitbox_id: HitboxId::new(),
+        }
+    }
+
+    pub fn with_value(mut self, value: impl Into<String>) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Validate the value after every edit; see [`Validator`].
+    pub fn with_validator(
+        mut self,
+        validator: impl Fn(&str) -> Result<(), String> + 'static,
+    ) -> Self {
+        self.validator = Some(Box::new(validator));
+        self.validate();
+        self
+    }
+
+    /// Keep up to `limit` submitted values, browsable with Up/Down.
+    pub fn with_history(mut self, limit: usize) -> Self {
+        self.history = Some(InputHistory::new(limit));
+        self
+    }
+
+    /// Emit `action` when Enter is pressed on a valid value.
+    pub fn with_submit_action(mut self, action: TermWmAction) -> Self {
+        self.submit_action = Some(action);
+        self
+    }
+
+    /// Override the theme-derived styles.
+    pub fn with_style(mut self, style: TextInputStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    pub fn set_style(&mut self, style: Option<TextInputStyle>) {
+        self.style = style;
+    }
+
+    pub fn value(&self) -> &str {
+        self.buffer.text()
+    }
+
+    /// Replace the value programmatically (clears undo history). Newlines are
+    /// folded to spaces.
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        let value = value.into();
+        self.buffer.set_text(single_line(&value));
+        self.scroll = 0;
+        self.after_edit();
+    }
+
+    pub fn clear(&mut self) {
+        self.set_value(String::new());
+    }
+
+    pub fn placeholder(&self) -> &str {
+        &self.placeholder
+    }
+
+    pub fn set_placeholder(&mut self, placeholder: impl Into<String>) {
+        self.placeholder = placeholder.into();
+    }
+
+    pub fn buffer(&self) -> &TextBuffer {
+        &self.buffer
+    }
+
+    /// The current validation message, if the value is invalid.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.error.is_none()
+    }
+
+    pub fn history(&self) -> Option<&InputHistory> {
+        self.history.as_ref()
+    }
+
+    pub fn history_mut(&mut self) -> Option<&mut InputHistory> {
+        self.history.as_mut()
+    }
+
+    /// Returns `true` once after any change to the value, so owners can react
+    /// to edits (re-filter, mark dirty) without diffing.
+    pub fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+
+    /// Insert text as a single edit (paste / IME commit). Returns whether the
+    /// value changed.
+    pub fn insert_text(&mut self, text: &str) -> bool {
+        let changed = self.buffer.insert(&single_line(text));
+        if changed {
+            self.after_edit();
+        }
+        changed
+    }
+
+    /// Handle a key press without the keyboard-focus check — for owners that
+    /// embed the field and route keys to it themselves.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> EventResult<TermWmAction> {
+        if key.kind == KeyKind::Release {
+            return EventResult::Ignored;
+        }
+        if let Some(changed) = apply_edit_key(&mut self.buffer, key, single_line) {
+            if changed {
+                self.after_edit();
+            }
+            return EventResult::Consumed;
+        }
+        match key.code {
+            KeyCode::Up if self.history.is_some() => {
+                let current = self.buffer.text().to_string();
+                if let Some(entry) = self.history.as_mut().and_then(|h| h.older(&current)) {
+                    let entry = entry.to_string();
+                    self.buffer.set_text(entry);
+                    self.after_edit();
+                }
+                EventResult::Consumed
+            }
+            KeyCode::Down if self.history.is_some() => {
+                if let Some(entry) = self.history.as_mut().and_then(InputHistory::newer) {
+                    let entry = entry.to_string();
+                    self.buffer.set_text(entry);
+                    self.after_edit();
+                }
+                EventResult::Consumed
+            }
+            KeyCode::Enter => self.submit(),
+            _ => EventResult::Ignored,
+        }
+    }
+
+    /// Enter: record the value in the history and emit the submit action,
+    /// unless the value fails validation.
+    fn submit(&mut self) -> EventResult<TermWmAction> {
+        if !self.is_valid() {
+            return EventResult::Consumed;
+        }
+        if let Some(history) = &mut self.history {
+            history.push(self.buffer.text());
+        }
+        match &self.submit_action {
+            Some(action) => EventResult::Action(action.clone()),
+            None if self.history.is_some() => EventResult::Consumed,
+            None => EventResult::Ignored,
+        }
+    }
+
+    fn after_edit(&mut self) {
+        self.changed = true;
+        self.validate();
+    }
+
+    fn validate(&mut self) {
+        self.error = self
+            .validator
+            .as_ref()
+            .and_then(|validate| validate(self.buffer.text()).err());
+    }
+
+    fn owns_keyboard(&self, ctx: &ComponentContext) -> bool {
+        ctx.keyboard_focus_id()
+            .is_none_or(|focus| focus == self.hitbox_id)
+    }
+
+    /// Byte offset under screen column `column`, relative to the last
+    /// rendered area (drags may leave the field).
+    fn offset_at(&self, column: u16) -> usize {
+        let local = (i32::from(column) - self.last_area.x).max(0) as usize;
+        self.buffer.byte_at_column(0, self.scroll + local)
+    }
+
+    /// Draw the field into `area` (row 0 text, row 1 validation message when
+    /// there is room). `focused` controls whether the cursor is drawn.
+    pub fn render_into(&mut self, buffer: &mut Buffer, area: Rect, focused: bool, theme: &Theme) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+        let style = self
+            .style
+            .unwrap_or_else(|| TextInputStyle::from_theme(theme));
+        let row = Rect { height: 1, ..area };
+        buffer.set_style(row, style.text);
+
+        let width = usize::from(area.width);
+        let cursor_col = self.buffer.column_of(self.buffer.cursor());
+        self.scroll = scroll_to_show(self.scroll, cursor_col, width);
+        // Never leave blank space on the right when text could fill it.
+        let text_width = self.buffer.text().width();
+        self.scroll = self.scroll.min((text_width + 1).saturating_sub(width));
+
+        if self.buffer.is_empty() {
+            let text: String = self.placeholder.chars().take(width).collect();
+            safe_set_string(buffer, row, area.x, area.y, &text, style.placeholder);
+            if focused && let Some(cell) = buffer.cell_mut((area.x, area.y)) {
+                cell.set_style(style.cursor);
+            }
+        } else {
+            let selection = self.buffer.selection();
+            render_text_row(
+                buffer,
+                row,
+                area.y,
+                self.buffer.text(),
+                0..self.buffer.text().len(),
+                self.scroll,
+                selection.as_ref(),
+                focused.then_some(self.buffer.cursor()),
+                &style,
+            );
+        }
+
+        if let Some(error) = &self.error
+            && area.height >= 2
+        {
+            let error_row = Rect {
+                y: area.y + 1,
+                height: 1,
+                ..area
+            };
+            let text: String = error.chars().take(width).collect();
+            safe_set_string(
+                buffer,
+                error_row,
+                area.x,
+                error_row.y,
+                &text,
+                style.error.add_modifier(Modifier::ITALIC),
+            );
+        }
+    }
+}
+
+impl Component<TermWmAction> for TextInputComponent {
+    fn hitbox_id(&self) -> Option<HitboxId> {
+        Some(self.hitbox_id)
+    }
+
+    fn desired_height(&self, _width: u16) -> u16 {
+        if self.validator.is_some() { 2 } else { 1 }
+    }
+
+    fn handle_events(
+        &mut self,
+        event: &Event,
+        ctx: &ComponentContext,
+    ) -> EventResult<TermWmAction> {
+        match event {
+            Event::Key(key) if self.owns_keyboard(ctx) => self.handle_key(key),
+            Event::Paste(text) if self.owns_keyboard(ctx) => {
+                self.insert_text(text);
+                EventResult::Consumed
+            }
+            Event::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::Press(MouseButton::Left) => {
+                    let Some((column, 0)) = ctx.localize_mouse_click(event, MouseButton::Left)
+                    else {
+                        return EventResult::Ignored;
+                    };
+                    if let Some(area) = ctx.screen_area() {
+                        self.last_area = area;
+                    }
+                    let pos = self
+                        .buffer
+                        .byte_at_column(0, self.scroll + usize::from(column));
+                    self.buffer.move_to(pos, mouse.modifiers.shift);
+                    self.dragging = true;
+                    EventResult::Action(TermWmAction::RequestKeyboardFocus(self.hitbox_id))
+                }
+                MouseEventKind::Drag(MouseButton::Left) if self.dragging => {
+                    let pos = self.offset_at(mouse.column);
+                    self.buffer.move_to(pos, true);
+                    EventResult::Consumed
+                }
+                MouseEventKind::Release(MouseButton::Left) if self.dragging => {
+                    self.dragging = false;
+                    EventResult::Consumed
+                }
+                _ => EventResult::Ignored,
+            },
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn update(
+        &mut self,
+        action: TermWmAction,
+        _ctx: &ComponentContext,
+        _actions: &mut VecDeque<(WindowKey, TermWmAction)>,
+    ) {
+        if let TermWmAction::ClipboardPaste(text) = action {
+            self.insert_text(&text);
+        }
+    }
+
+    fn render(
+        &mut self,
+        backend: &mut dyn term_wm_render::RenderBackend,
+        area: LayoutRect,
+        ctx: &ComponentContext,
+        registry: &mut HitboxRegistry,
+    ) {
+        let rect = layout_rect_to_clipped_rect(area);
+        let backend = crate::helpers::downcast_ratatui(backend);
+        let bounds = rect.intersection(backend.buffer.area);
+        if bounds.width == 0 || bounds.height == 0 {
+            return;
+        }
+        let screen_area = ctx.screen_area().unwrap_or(area);
+        self.last_area = screen_area;
+        if let Some(key) = ctx.window_key() {
+            registry.register(self.hitbox_id, ComponentOwner::Window(key), screen_area);
+        }
+        let focused = ctx.focused()
+            && ctx
+                .keyboard_focus_id()
+                .is_none_or(|id| id == self.hitbox_id);
+        self.render_into(&mut backend.buffer, bounds, focused, &ctx.config().theme);
+    }
+
+    fn selection_status(&self) -> SelectionStatus {
+        SelectionStatus {
+            active: self.buffer.selection().is_some(),
+            dragging: self.dragging,
+        }
+    }
+
+    fn selection_text(&self) -> Option<String> {
+        self.buffer.selected_text().map(str::to_string)
+    }
+
+    fn clear_selection(&mut self) {
+        self.buffer.clear_selection();
+    }
+
+    fn paste(&mut self, text: &str) -> bool {
+        self.insert_text(text)
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use term_wm_core::events::{KeyModifiers, MouseEvent};
+
+    fn press(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE, KeyKind::Press))
+    }
+
+    fn type_str(input: &mut TextInputComponent, text: &str) {
+        let ctx = ComponentContext::new(true);
+        for c in text.chars() {
+            input.handle_events(&press(KeyCode::Char(c)), &ctx);
+        }
+    }
+
+    fn render_row(input: &mut TextInputComponent, width: u16) -> String {
+        let rect = Rect::new(0, 0, width, 2);
+        let mut backend = term_wm_console::RatatuiBackend::new_simple(Buffer::empty(rect), rect);
+        let area = LayoutRect {
+            x: 0,
+            y: 0,
+            width,
+            height: 2,
+        };
+        let ctx = ComponentContext::new(true).with_screen_area(area);
+        input.render(&mut backend, area, &ctx, &mut HitboxRegistry::new());
+        backend
+            .buffer
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect()
+    }
+
+    #[test]
+    fn typing_and_editing_updates_value() {
+        let mut input = TextInputComponent::new();
+        type_str(&mut input, "hello wrld");
+        assert!(input.take_changed());
+        assert!(!input.take_changed());
+        let ctx = ComponentContext::new(true);
+        for _ in 0..3 {
+            input.handle_events(&press(KeyCode::Left), &ctx);
+        }
+        type_str(&mut input, "o");
+        assert_eq!(input.value(), "hello world");
+    }
+
+    #[test]
+    fn enter_submits_into_history_and_emits_action() {
+        let mut input = TextInputComponent::new()
+            .with_history(10)
+            .with_submit_action(TermWmAction::Help);
+        type_str(&mut input, "first");
+        let ctx = ComponentContext::new(true);
+        let result = input.handle_events(&press(KeyCode::Enter), &ctx);
+        assert!(matches!(result, EventResult::Action(TermWmAction::Help)));
+        input.clear();
+        type_str(&mut input, "second");
+        input.handle_events(&press(KeyCode::Enter), &ctx);
+        input.set_value("draft");
+
+        input.handle_events(&press(KeyCode::Up), &ctx);
+        assert_eq!(input.value(), "second");
+        input.handle_events(&press(KeyCode::Up), &ctx);
+        assert_eq!(input.value(), "first");
+        input.handle_events(&press(KeyCode::Up), &ctx);
+        assert_eq!(input.value(), "first");
+        input.handle_events(&press(KeyCode::Down), &ctx);
+        input.handle_events(&press(KeyCode::Down), &ctx);
+        assert_eq!(input.value(), "draft");
+    }
+
+    #[test]
+    fn invalid_value_blocks_submit_and_renders_message() {
+        let mut input = TextInputComponent::new()
+            .with_validator(|v| {
+                if v.parse::<u16>().is_ok() {
+                    Ok(())
+                } else {
+                    Err("not a port".into())
+                }
+            })
+            .with_submit_action(TermWmAction::Help);
+        assert_eq!(input.desired_height(20), 2);
+        type_str(&mut input, "80x");
+        assert_eq!(input.error(), Some("not a port"));
+        let ctx = ComponentContext::new(true);
+        let result = input.handle_events(&press(KeyCode::Enter), &ctx);
+        assert!(result.is_consumed());
+        assert!(render_row(&mut input, 20).contains("not a port"));
+
+        input.handle_events(&press(KeyCode::Backspace), &ctx);
+        assert!(input.is_valid());
+        let result = input.handle_events(&press(KeyCode::Enter), &ctx);
+        assert!(matches!(result, EventResult::Action(TermWmAction::Help)));
+    }
+
+    #[test]
+    fn paste_is_one_undo_step_and_single_line() {
+        let mut input = TextInputComponent::new();
+        type_str(&mut input, "a ");
+        let ctx = ComponentContext::new(true);
+        input.handle_events(&Event::Paste("b\nc".into()), &ctx);
+        assert_eq!(input.value(), "a b c");
+        let undo = Event::Key(KeyEvent::new(
+            KeyCode::Char('z'),
+            KeyModifiers {
+                control: true,
+                ..KeyModifiers::NONE
+            },
+            KeyKind::Press,
+        ));
+        input.handle_events(&undo, &ctx);
+        assert_eq!(input.value(), "a ");
+
+        assert!(input.paste("x"));
+        input.update(
+            TermWmAction::ClipboardPaste("y".into()),
+            &ctx,
+            &mut VecDeque::new(),
+        );
+        assert_eq!(input.value(), "a xy");
+    }
+
+    #[test]
+    fn keys_ignored_when_another_component_has_focus() {
+        let mut input = TextInputComponent::new();
+        let ctx = ComponentContext::new(true).with_keyboard_focus_id(HitboxId::new());
+        let result = input.handle_events(&press(KeyCode::Char('a')), &ctx);
+        assert!(result.is_ignored());
+        let ctx = ctx.with_keyboard_focus_id(input.hitbox_id);
+        let result = input.handle_events(&press(KeyCode::Char('a')), &ctx);
+        assert!(result.is_consumed());
+    }
+
+    #[test]
+    fn click_places_cursor_and_requests_focus() {
+        let mut input = TextInputComponent::new().with_value("abcdef");
+        let area = LayoutRect {
+            x: 10,
+            y: 3,
+            width: 20,
+            height: 1,
+        };
+        let ctx = ComponentContext::new(true).with_screen_area(area);
+        let click = Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Press(MouseButton::Left),
+            modifiers: KeyModifiers::NONE,
+            column: 12,
+            row: 3,
+        });
+        let result = input.handle_events(&click, &ctx);
+        assert!(matches!(
+            result,
+            EventResult::Action(TermWmAction::RequestKeyboardFocus(_))
+        ));
+        assert_eq!(input.buffer().cursor(), 2);
+        let drag = Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Drag(MouseButton::Left),
+            modifiers: KeyModifiers::NONE,
+            column: 15,
+            row: 3,
+        });
+        input.handle_events(&drag, &ctx);
+        assert_eq!(input.selection_text().as_deref(), Some("cde"));
+    }
+
+    #[test]
+    fn render_shows_placeholder_then_scrolls_long_values() {
+        let mut input = TextInputComponent::new().with_placeholder("search…");
+        assert!(render_row(&mut input, 10).contains("search…"));
+        input.set_value("0123456789abcdef");
+        let row = render_row(&mut input, 10);
+        // The cursor sits past the end, so the tail stays visible.
+        assert!(row.starts_with("789abcdef "), "{row:?}");
+    }
+
+    #[test]
+    fn wide_graphemes_render_in_two_cells() {
+        let mut input = TextInputComponent::new().with_value("漢字");
+        let row = render_row(&mut input, 6);
+        assert!(row.starts_with("漢"), "{row:?}");
+        assert!(row.contains('字'));
+    }
+}
@@ -22,6 +22,17 @@ use term_wm_core::utils::selectable_text::{
 use term_wm_core::window::WindowKey;
 use term_wm_layout_engine::LayoutRect;
 
+/// A style patched over a character range of one logical line when it is
+/// drawn (search matches, for example). Columns count `char`s of the line's
+/// text, `end` exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextHighlight {
+    pub line: usize,
+    pub start: usize,
+    pub end: usize,
+    pub style: Style,
+}
+
 pub struct TextRendererComponent {
     text: Text<'static>,
     wrap: bool,
@@ -36,6 +47,8 @@ pub struct TextRendererComponent {
     /// underscore, for double-click word selection. Default (empty) treats
     /// hyphens and other punctuation as word boundaries.
     word_extra_chars: String,
+    /// Sorted by line; see [`TextRendererComponent::set_highlights`].
+    highlights: Vec<TextHighlight>,
 }
 
 impl fmt::Debug for TextRendererComponent {
@@ -152,7 +165,7 @@ impl Component<TermWmAction> for TextRendererComponent {
                 continue;
             }
 
-            let single_text = Text::from(vec![line.clone()]);
+            let single_text = Text::from(vec![self.highlighted_line(idx, line)]);
             let mut paragraph = Paragraph::new(single_text);
             if self.wrap {
                 paragraph = paragraph.wrap(Wrap { trim: false });
@@ -292,12 +305,14 @@ impl TextRendererComponent {
             content_width: Cell::new(0),
             content_height: Cell::new(0),
             word_extra_chars: DEFAULT_WORD_EXTRA_CHARS.to_string(),
+            highlights: Vec::new(),
         }
     }
 
     pub fn set_text(&mut self, text: Text<'static>) {
         self.text = text;
         self.link_map.clear();
+        self.highlights.clear();
     }
 
     pub fn set_linkified_text(
@@ -308,6 +323,38 @@ impl TextRendererComponent {
         let (text, link_map) = linkified_to_text(linkified, theme);
         self.text = text;
         self.link_map = link_map;
+        self.highlights.clear();
+    }
+
+    /// Replace the highlighted ranges. They only affect drawing: wrapping,
+    /// selection and link hit-testing see the unstyled text.
+    pub fn set_highlights(&mut self, mut highlights: Vec<TextHighlight>) {
+        highlights.sort_by_key(|h| (h.line, h.start));
+        self.highlights = highlights;
+    }
+
+    pub fn highlights(&self) -> &[TextHighlight] {
+        &self.highlights
+    }
+
+    /// Row at which logical line `line_idx` starts, relative to the top of the
+    /// viewport at the last rendered scroll offset; negative once scrolled
+    /// past.
+    pub fn line_row_in_view(&self, line_idx: usize, width: u16) -> isize {
+        let rows: usize = self
+            .text
+            .lines
+            .iter()
+            .take(line_idx)
+            .map(|line| {
+                if self.wrap {
+                    actual_wrapped_height(line, width)
+                } else {
+                    1
+                }
+            })
+            .sum();
+        rows as isize - self.viewport_cache.get().offset_y as isize
     }
 
     pub fn set_wrap(&mut self, wrap: bool) {
@@ -339,23 +386,19 @@ impl TextRendererComponent {
             return;
         }
 
-        let usable = area.width.max(1) as usize;
-        let mut offset = 0;
-        for (i, line) in self.text.lines.iter().enumerate() {
-            if i >= line_idx {
-                break;
-            }
-            if self.wrap {
-                let w = line.width();
-                if w == 0 {
-                    offset += 1;
+        let offset = self
+            .text
+            .lines
+            .iter()
+            .take(line_idx)
+            .map(|line| {
+                if self.wrap {
+                    actual_wrapped_height(line, area.width)
                 } else {
-                    offset += (w + usable - 1).div_euclid(usable);
+                    1
                 }
-            } else {
-                offset += 1;
-            }
-        }
+            })
+            .sum();
         if let Some(handle) = self.scroll_handle.borrow().as_ref() {
             handle.scroll_vertical_to(offset);
         }
@@ -386,6 +429,51 @@ impl TextRendererComponent {
 
     // Internal helper methods
 
+    /// `line` with the highlights for logical line `idx` patched in.
+    fn highlighted_line(&self, idx: usize, line: &Line<'static>) -> Line<'static> {
+        let first = self.highlights.partition_point(|h| h.line < idx);
+        let ranges = &self.highlights[first..];
+        let ranges = &ranges[..ranges.partition_point(|h| h.line == idx)];
+        if ranges.is_empty() {
+            return line.clone();
+        }
+        let mut spans: Vec<Span<'static>> = Vec::with_capacity(line.spans.len() + ranges.len() * 2);
+        let mut col = 0usize;
+        for span in &line.spans {
+            let span_end = col + span.content.chars().count();
+            let mut cut = col;
+            // Split the span at every highlight boundary inside it.
+            let mut bounds: Vec<usize> = ranges
+                .iter()
+                .flat_map(|h| [h.start, h.end])
+                .filter(|&b| b > col && b < span_end)
+                .collect();
+            bounds.push(span_end);
+            bounds.sort_unstable();
+            bounds.dedup();
+            for bound in bounds {
+                let piece: String = span
+                    .content
+                    .chars()
+                    .skip(cut - col)
+                    .take(bound - cut)
+                    .collect();
+                let style = ranges
+                    .iter()
+                    .filter(|h| h.start <= cut && cut < h.end)
+                    .fold(span.style, |style, h| style.patch(h.style));
+                spans.push(Span::styled(piece, style));
+                cut = bound;
+            }
+            col = span_end;
+        }
+        Line {
+            spans,
+            style: line.style,
+            alignment: line.alignment,
+        }
+    }
+
     fn apply_focus_state(&self, focused: bool) {
         if !focused {
             self.selection.borrow_mut().clear();
@@ -1126,6 +1214,40 @@ mod tests {
         assert!(result.is_ignored());
     }
 
+    #[test]
+    fn highlights_split_spans_without_changing_text() {
+        let mut comp = TextRendererComponent::new();
+        let bold = Style::default().add_modifier(ratatui::style::Modifier::BOLD);
+        comp.set_text(Text::from(vec![Line::from(vec![
+            Span::raw("hello "),
+            Span::styled("wide world", bold),
+        ])]));
+        let mark = Style::default().bg(Color::Blue);
+        comp.set_highlights(vec![TextHighlight {
+            line: 0,
+            start: 4,
+            end: 8,
+            style: mark,
+        }]);
+        let line = comp.highlighted_line(0, &comp.text.lines[0]);
+        let pieces: Vec<(&str, Style)> = line
+            .spans
+            .iter()
+            .map(|span| (span.content.as_ref(), span.style))
+            .collect();
+        assert_eq!(
+            pieces,
+            [
+                ("hell", Style::default()),
+                ("o ", mark),
+                ("wi", bold.patch(mark)),
+                ("de world", bold),
+            ]
+        );
+        assert_eq!(comp.rendered_lines(), ["hello wide world"]);
+        assert!(comp.highlighted_line(1, &Line::raw("x")).spans.len() == 1);
+    }
+
     #[test]
     fn actual_wrapped_height_accounts_for_word_boundaries() {
         // A 132-char paragraph at width 67 wraps to 3 visual lines because
@@ -0,0 +1,919 @@
+use std::collections::{BTreeSet, VecDeque};
+
+use ratatui::style::{Modifier, Style};
+use term_wm_core::actions::{EventResult, TermWmAction};
+use term_wm_core::command_menu::FuzzyMatch;
+use term_wm_core::components::{Component, ComponentContext};
+use term_wm_core::events::{Event, KeyCode, KeyModifiers, MouseButton};
+use term_wm_core::theme::Theme;
+use term_wm_core::window::WindowKey;
+use term_wm_layout_engine::LayoutRect;
+use unicode_width::UnicodeWidthStr;
+
+use crate::helpers::{color_to_ratatui, layout_rect_to_clipped_rect, slice_by_columns};
+
+/// Columns of indentation per tree level.
+const INDENT: usize = 2;
+/// Width of the expand/collapse glyph column, including its trailing space.
+const GLYPH_WIDTH: usize = 2;
+const EXPANDED_GLYPH: &str = "▾ ";
+const COLLAPSED_GLYPH: &str = "▸ ";
+const LEAF_GLYPH: &str = "  ";
+
+/// Builds the children of a lazy node the first time it is expanded.
+pub type TreeLoader<T> = Box<dyn FnMut(&T) -> Vec<TreeNode<T>>>;
+
+enum TreeChildren<T> {
+    Leaf,
+    Loaded(Vec<TreeNode<T>>),
+    Lazy,
+}
+
+/// A node handed to [`TreeViewComponent`]: a label, the caller's payload and
+/// its children, which are either supplied up front or loaded on demand.
+pub struct TreeNode<T> {
+    label: String,
+    data: T,
+    children: TreeChildren<T>,
+}
+
+impl<T> TreeNode<T> {
+    pub fn leaf(label: impl Into<String>, data: T) -> Self {
+        Self {
+            label: label.into(),
+            data,
+            children: TreeChildren::Leaf,
+        }
+    }
+
+    pub fn branch(label: impl Into<String>, data: T, children: Vec<TreeNode<T>>) -> Self {
+        Self {
+            label: label.into(),
+            data,
+            children: TreeChildren::Loaded(children),
+        }
+    }
+
+    /// A node whose children come from the tree's loader on first expand.
+    pub fn lazy(label: impl Into<String>, data: T) -> Self {
+        Self {
+            label: label.into(),
+            data,
+            children: TreeChildren::Lazy,
+        }
+    }
+}
+
+/// Stable handle to a node inside one [`TreeViewComponent`]. Ids stay valid
+/// until [`set_roots`](TreeViewComponent::set_roots) replaces the tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TreeNodeId(usize);
+
+struct Slot<T> {
+    label: String,
+    data: T,
+    parent: Option<usize>,
+    depth: usize,
+    /// `None` until a lazy node is first expanded.
+    children: Option<Vec<usize>>,
+    /// Whether the node has, or may lazily load, children.
+    has_children: bool,
+    expanded: bool,
+}
+
+/// Styles for [`TreeViewComponent`]; [`TreeViewStyle::from_theme`] is the
+/// default.
+#[derive(Debug, Clone, Copy)]
+pub struct TreeViewStyle {
+    pub row: Style,
+    pub cursor: Style,
+    pub selected: Style,
+    pub filter: Style,
+}
+
+impl TreeViewStyle {
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            row: Style::default().fg(color_to_ratatui(theme.text)),
+            cursor: Style::default()
+                .bg(color_to_ratatui(theme.menu_selected_bg))
+                .fg(color_to_ratatui(theme.menu_selected_fg)),
+            selected: Style::default()
+                .bg(color_to_ratatui(theme.selection_bg))
+                .fg(color_to_ratatui(theme.selection_fg)),
+            filter: Style::default()
+                .fg(color_to_ratatui(theme.accent))
+                .add_modifier(Modifier::BOLD),
+        }
+    }
+}
+
+/// An expandable tree for file trees, JSON inspectors, process trees and the
+/// like.
+///
+/// Keys: Up/Down/PageUp/PageDown/Home/End move the cursor, Right expands (or
+/// steps into the first child), Left collapses (or steps out to the parent),
+/// Enter emits the activate action or toggles the node, and Space toggles the
+/// node's selection when multi-select is on. Typing filters the tree with the
+/// fuzzy matcher used by the Command Palette: matching nodes are shown with
+/// their ancestors, Backspace edits the query and Esc clears it. Filtering
+/// only searches children that have already been loaded.
+///
+/// Meant to be wrapped in a [`ScrollViewComponent`](crate::ScrollViewComponent);
+/// only the rows inside the viewport are drawn.
+pub struct TreeViewComponent<T> {
+    nodes: Vec<Slot<T>>,
+    roots: Vec<usize>,
+    loader: Option<TreeLoader<T>>,
+    /// Node ids in display order.
+    visible: Vec<usize>,
+    /// Cursor position within `visible`.
+    cursor: usize,
+    selected: BTreeSet<usize>,
+    multi_select: bool,
+    /// Node that a Shift+click range extends from.
+    anchor: Option<usize>,
+    filter: String,
+    fuzzy: FuzzyMatch,
+    activate_action: Option<TermWmAction>,
+    style: Option<TreeViewStyle>,
+    content_width: usize,
+    last_cursor: usize,
+    last_viewport_rows: usize,
+}
+
+impl<T> TreeViewComponent<T> {
+    pub fn new(roots: Vec<TreeNode<T>>) -> Self {
+        let mut tree = Self {
+            nodes: Vec::new(),
+            roots: Vec::new(),
+            loader: None,
+            visible: Vec::new(),
+            cursor: 0,
+            selected: BTreeSet::new(),
+            multi_select: false,
+            anchor: None,
+            filter: String::new(),
+            fuzzy: FuzzyMatch::new(),
+            activate_action: None,
+            style: None,
+            content_width: 0,
+            last_cursor: 0,
+            last_viewport_rows: 0,
+        };
+        tree.set_roots(roots);
+        tree
+    }
+
+    /// Load the children of [`TreeNode::lazy`] nodes.
+    pub fn with_loader(mut self, loader: impl FnMut(&T) -> Vec<TreeNode<T>> + 'static) -> Self {
+        self.loader = Some(Box::new(loader));
+        self
+    }
+
+    /// Allow selecting several nodes with Space, Ctrl+click and Shift+click.
+    pub fn with_multi_select(mut self, multi_select: bool) -> Self {
+        self.multi_select = multi_select;
+        self
+    }
+
+    /// Emit `action` when Enter is pressed; the cursor node is available
+    /// through [`cursor`](Self::cursor).
+    pub fn with_activate_action(mut self, action: TermWmAction) -> Self {
+        self.activate_action = Some(action);
+        self
+    }
+
+    /// Override the theme-derived styles.
+    pub fn with_style(mut self, style: TreeViewStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Replace the whole tree. Invalidates every [`TreeNodeId`].
+    pub fn set_roots(&mut self, roots: Vec<TreeNode<T>>) {
+        self.nodes.clear();
+        self.selected.clear();
+        self.anchor = None;
+        self.cursor = 0;
+        self.last_cursor = 0;
+        self.last_viewport_rows = 0;
+        self.roots = roots
+            .into_iter()
+            .map(|node| self.insert(node, None, 0))
+            .collect();
+        self.rebuild();
+    }
+
+    pub fn roots(&self) -> Vec<TreeNodeId> {
+        self.roots.iter().map(|&i| TreeNodeId(i)).collect()
+    }
+
+    pub fn label(&self, id: TreeNodeId) -> Option<&str> {
+        self.nodes.get(id.0).map(|slot| slot.label.as_str())
+    }
+
+    pub fn data(&self, id: TreeNodeId) -> Option<&T> {
+        self.nodes.get(id.0).map(|slot| &slot.data)
+    }
+
+    pub fn data_mut(&mut self, id: TreeNodeId) -> Option<&mut T> {
+        self.nodes.get_mut(id.0).map(|slot| &mut slot.data)
+    }
+
+    pub fn parent(&self, id: TreeNodeId) -> Option<TreeNodeId> {
+        self.nodes.get(id.0)?.parent.map(TreeNodeId)
+    }
+
+    /// Loaded children of `id`; empty for leaves and unloaded lazy nodes.
+    pub fn children(&self, id: TreeNodeId) -> Vec<TreeNodeId> {
+        self.nodes
+            .get(id.0)
+            .and_then(|slot| slot.children.as_ref())
+            .map(|children| children.iter().map(|&i| TreeNodeId(i)).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn is_expanded(&self, id: TreeNodeId) -> bool {
+        self.nodes.get(id.0).is_some_and(|slot| slot.expanded)
+    }
+
+    /// Expand `id`, loading its children first if it is lazy.
+    pub fn expand(&mut self, id: TreeNodeId) {
+        if self.expand_node(id.0) {
+            self.rebuild();
+        }
+    }
+
+    pub fn collapse(&mut self, id: TreeNodeId) {
+        if let Some(slot) = self.nodes.get_mut(id.0)
+            && slot.expanded
+        {
+            slot.expanded = false;
+            self.rebuild();
+        }
+    }
+
+    pub fn toggle(&mut self, id: TreeNodeId) {
+        if self.is_expanded(id) {
+            self.collapse(id);
+        } else {
+            self.expand(id);
+        }
+    }
+
+    /// Node under the cursor.
+    pub fn cursor(&self) -> Option<TreeNodeId> {
+        self.visible.get(self.cursor).map(|&i| TreeNodeId(i))
+    }
+
+    /// Move the cursor to `id`, expanding its ancestors so it is visible.
+    pub fn set_cursor(&mut self, id: TreeNodeId) {
+        if id.0 >= self.nodes.len() {
+            return;
+        }
+        let mut parent = self.nodes[id.0].parent;
+        while let Some(p) = parent {
+            self.nodes[p].expanded = true;
+            parent = self.nodes[p].parent;
+        }
+        self.rebuild();
+        if let Some(position) = self.visible.iter().position(|&i| i == id.0) {
+            self.cursor = position;
+        }
+    }
+
+    /// Multi-selected nodes in tree-insertion order.
+    pub fn selected(&self) -> Vec<TreeNodeId> {
+        self.selected.iter().map(|&i| TreeNodeId(i)).collect()
+    }
+
+    pub fn is_selected(&self, id: TreeNodeId) -> bool {
+        self.selected.contains(&id.0)
+    }
+
+    /// Toggle `id` in the multi-selection. Ignored unless multi-select is on.
+    pub fn toggle_selected(&mut self, id: TreeNodeId) {
+        if !self.multi_select || id.0 >= self.nodes.len() {
+            return;
+        }
+        if !self.selected.remove(&id.0) {
+            self.selected.insert(id.0);
+        }
+        self.anchor = Some(id.0);
+    }
+
+    pub fn clear_selected(&mut self) {
+        self.selected.clear();
+    }
+
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    /// Filter the tree and move the cursor to the best match.
+    /// Clearing the filter keeps the cursor on the node it was on, expanding
+    /// its ancestors if needed.
+    pub fn set_filter(&mut self, query: impl Into<String>) {
+        let cursor_node = self.cursor();
+        self.filter = query.into();
+        if !self.filter.is_empty() {
+            self.rebuild();
+            self.jump_to_best_match();
+        } else if let Some(id) = cursor_node {
+            self.set_cursor(id);
+        } else {
+            self.rebuild();
+        }
+    }
+
+    pub fn clear_filter(&mut self) {
+        self.set_filter(String::new());
+    }
+
+    /// Number of rows currently shown.
+    pub fn visible_len(&self) -> usize {
+        self.visible.len()
+    }
+
+    fn insert(&mut self, node: TreeNode<T>, parent: Option<usize>, depth: usize) -> usize {
+        let id = self.nodes.len();
+        let (children, has_children) = match node.children {
+            TreeChildren::Leaf => (Vec::new(), false),
+            TreeChildren::Loaded(children) => {
+                let has = !children.is_empty();
+                (children, has)
+            }
+            TreeChildren::Lazy => (Vec::new(), true),
+        };
+        let loaded = has_children && !children.is_empty();
+        self.nodes.push(Slot {
+            label: node.label,
+            data: node.data,
+            parent,
+            depth,
+            children: (!has_children || loaded).then(Vec::new),
+            has_children,
+            expanded: false,
+        });
+        let ids: Vec<usize> = children
+            .into_iter()
+            .map(|child| self.insert(child, Some(id), depth + 1))
+            .collect();
+        if loaded {
+            self.nodes[id].children = Some(ids);
+        }
+        id
+    }
+
+    /// Expand without rebuilding the visible rows. Returns whether anything
+    /// changed.
+    fn expand_node(&mut self, id: usize) -> bool {
+        let Some(slot) = self.nodes.get(id) else {
+            return false;
+        };
+        if slot.expanded || !slot.has_children {
+            return false;
+        }
+        if slot.children.is_none() {
+            let loaded = match self.loader.as_mut() {
+                Some(loader) => loader(&self.nodes[id].data),
+                None => Vec::new(),
+            };
+            let depth = self.nodes[id].depth + 1;
+            let ids: Vec<usize> = loaded
+                .into_iter()
+                .map(|child| self.insert(child, Some(id), depth))
+                .collect();
+            self.nodes[id].has_children = !ids.is_empty();
+            self.nodes[id].children = Some(ids);
+        }
+        self.nodes[id].expanded = self.nodes[id].has_children;
+        true
+    }
+
+    /// Recompute the displayed rows, keeping the cursor on the same node
+    /// when it is still shown.
+    fn rebuild(&mut self) {
+        let cursor_node = self.visible.get(self.cursor).copied();
+        let keep = self.filter_mask();
+        let filtering = keep.is_some();
+
+        self.visible.clear();
+        let mut stack: Vec<usize> = self.roots.iter().rev().copied().collect();
+        while let Some(id) = stack.pop() {
+            if keep.as_ref().is_some_and(|keep| !keep[id]) {
+                continue;
+            }
+            self.visible.push(id);
+            let slot = &self.nodes[id];
+            if (slot.expanded || filtering)
+                && let Some(children) = &slot.children
+            {
+                stack.extend(children.iter().rev());
+            }
+        }
+
+        self.content_width = self
+            .visible
+            .iter()
+            .map(|&id| {
+                let slot = &self.nodes[id];
+                slot.depth * INDENT + GLYPH_WIDTH + slot.label.width()
+            })
+            .max()
+            .unwrap_or(0);
+        self.cursor = cursor_node
+            .and_then(|node| self.visible.iter().position(|&i| i == node))
+            .unwrap_or(self.cursor)
+            .min(self.visible.len().saturating_sub(1));
+    }
+
+    /// With a filter active, which nodes to show: the matches plus their
+    /// ancestors. Children always have larger ids than their parent, so a
+    /// single reverse pass propagates matches upward.
+    fn filter_mask(&mut self) -> Option<Vec<bool>> {
+        if self.filter.is_empty() {
+            return None;
+        }
+        let scores = self.fuzzy.score_each(
+            &self.filter,
+            self.nodes.iter().map(|slot| slot.label.as_str()),
+        );
+        let mut keep: Vec<bool> = scores.iter().map(Option::is_some).collect();
+        for id in (0..self.nodes.len()).rev() {
+            if keep[id]
+                && let Some(parent) = self.nodes[id].parent
+            {
+                keep[parent] = true;
+            }
+        }
+        Some(keep)
+    }
+
+    fn jump_to_best_match(&mut self) {
+        let labels = self.visible.iter().map(|&id| self.nodes[id].label.as_str());
+        let scores = self.fuzzy.score_each(&self.filter, labels);
+        if let Some((position, _)) = scores
+            .iter()
+            .enumerate()
+            .filter_map(|(i, score)| score.map(|s| (i, s)))
+            .max_by_key(|&(i, score)| (score, std::cmp::Reverse(i)))
+        {
+            self.cursor = position;
+        }
+    }
+
+    fn move_cursor(&mut self, delta: isize) {
+        if self.visible.is_empty() {
+            return;
+        }
+        let max = self.visible.len() - 1;
+        self.cursor = (self.cursor as isize + delta).clamp(0, max as isize) as usize;
+    }
+
+    fn move_to_node(&mut self, id: usize) {
+        if let Some(position) = self.visible.iter().position(|&i| i == id) {
+            self.cursor = position;
+        }
+    }
+
+    fn on_right(&mut self) {
+        let Some(id) = self.visible.get(self.cursor).copied() else {
+            return;
+        };
+        if !self.filter.is_empty() || self.nodes[id].expanded {
+            if let Some(&child) = self.nodes[id].children.as_ref().and_then(|c| c.first()) {
+                self.move_to_node(child);
+            }
+        } else {
+            self.expand(TreeNodeId(id));
+        }
+    }
+
+    fn on_left(&mut self) {
+        let Some(id) = self.visible.get(self.cursor).copied() else {
+            return;
+        };
+        if self.filter.is_empty() && self.nodes[id].expanded {
+            self.collapse(TreeNodeId(id));
+        } else if let Some(parent) = self.nodes[id].parent {
+            self.move_to_node(parent);
+        }
+    }
+
+    /// Select every shown row between the anchor and `position`.
+    fn select_range(&mut self, position: usize) {
+        let anchor = self
+            .anchor
+            .and_then(|a| self.visible.iter().position(|&i| i == a))
+            .unwrap_or(position);
+        let (start, end) = (anchor.min(position), anchor.max(position));
+        self.selected.extend(&self.visible[start..=end]);
+    }
+
+    fn filter_rows(&self) -> usize {
+        usize::from(!self.filter.is_empty())
+    }
+
+    fn row_line(&self, id: usize) -> String {
+        let slot = &self.nodes[id];
+        let glyph = match (slot.has_children, slot.expanded || !self.filter.is_empty()) {
+            (false, _) => LEAF_GLYPH,
+            (true, true) if slot.children.is_some() => EXPANDED_GLYPH,
+            (true, _) => COLLAPSED_GLYPH,
+        };
+        format!("{}{glyph}{}", " ".repeat(slot.depth * INDENT), slot.label)
+    }
+}
+
+impl<T> Component<TermWmAction> for TreeViewComponent<T> {
+    fn render(
+        &mut self,
+        backend: &mut dyn term_wm_render::RenderBackend,
+        area: LayoutRect,
+        ctx: &ComponentContext,
+        _registry: &mut term_wm_core::hitbox_registry::HitboxRegistry,
+    ) {
+        let area = layout_rect_to_clipped_rect(area);
+        let backend = crate::helpers::downcast_ratatui(backend);
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+        let style = self
+            .style
+            .unwrap_or_else(|| TreeViewStyle::from_theme(&ctx.config().theme));
+        // The filter query is pinned above the rows while it is active.
+        let header = self.filter_rows();
+        let viewport_rows = usize::from(area.height).saturating_sub(header);
+        if let Some(handle) = ctx.scroll_handle() {
+            handle.set_content_size(self.content_width, self.visible.len() + header);
+            handle.ensure_selection_visible(
+                self.cursor,
+                viewport_rows,
+                &mut self.last_cursor,
+                &mut self.last_viewport_rows,
+            );
+        }
+        let vp = ctx.viewport();
+        let width = usize::from(area.width);
+        let buffer = &mut backend.buffer;
+
+        if header > 0 {
+            let text = format!("/ {}", self.filter);
+            buffer.set_stringn(area.x, area.y, text, width, style.filter);
+        }
+        for (line, position) in (vp.offset_y..self.visible.len())
+            .take(viewport_rows)
+            .enumerate()
+        {
+            let id = self.visible[position];
+            let y = area.y + (header + line) as u16;
+            let row_style = if position == self.cursor {
+                style.cursor
+            } else if self.selected.contains(&id) {
+                style.selected
+            } else {
+                style.row
+            };
+            buffer.set_style(
+                ratatui::layout::Rect {
+                    y,
+                    height: 1,
+                    ..area
+                },
+                row_style,
+            );
+            buffer.set_stringn(
+                area.x,
+                y,
+                slice_by_columns(&self.row_line(id), vp.offset_x, width),
+                width,
+                row_style,
+            );
+        }
+    }
+
+    fn on_mouse_press(
+        &mut self,
+        local_x: u16,
+        local_y: u16,
+        button: MouseButton,
+        modifiers: KeyModifiers,
+        ctx: &ComponentContext,
+    ) -> EventResult<TermWmAction> {
+        if button != MouseButton::Left || !ctx.focused() {
+            return EventResult::Ignored;
+        }
+        let vp = ctx.viewport();
+        let Some(row) = usize::from(local_y).checked_sub(self.filter_rows()) else {
+            return EventResult::Ignored;
+        };
+        let position = vp.offset_y + row;
+        let Some(&id) = self.visible.get(position) else {
+            return EventResult::Ignored;
+        };
+        self.cursor = position;
+        let x = vp.offset_x + usize::from(local_x);
+        let glyph_start = self.nodes[id].depth * INDENT;
+        if self.nodes[id].has_children && (glyph_start..glyph_start + GLYPH_WIDTH).contains(&x) {
+            self.toggle(TreeNodeId(id));
+        } else if self.multi_select && modifiers.shift {
+            self.select_range(position);
+        } else if self.multi_select && modifiers.control {
+            self.toggle_selected(TreeNodeId(id));
+        } else {
+            self.anchor = Some(id);
+        }
+        EventResult::Consumed
+    }
+
+    fn on_key(&mut self, event: &Event, _ctx: &ComponentContext) -> EventResult<TermWmAction> {
+        let Event::Key(key) = event else {
+            return EventResult::Ignored;
+        };
+        let page = self.last_viewport_rows.max(1) as isize;
+        match key.code {
+            KeyCode::Up => self.move_cursor(-1),
+            KeyCode::Down => self.move_cursor(1),
+            KeyCode::PageUp => self.move_cursor(-page),
+            KeyCode::PageDown => self.move_cursor(page),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.visible.len().saturating_sub(1),
+            KeyCode::Right => self.on_right(),
+            KeyCode::Left => self.on_left(),
+            KeyCode::Enter => {
+                if let Some(action) = &self.activate_action {
+                    return EventResult::Action(action.clone());
+                }
+                if let Some(id) = self.cursor() {
+                    self.toggle(id);
+                }
+            }
+            KeyCode::Char(' ') if self.multi_select => {
+                if let Some(id) = self.cursor() {
+                    self.toggle_selected(id);
+                }
+            }
+            KeyCode::Esc if !self.filter.is_empty() => self.clear_filter(),
+            KeyCode::Backspace if !self.filter.is_empty() => {
+                let mut query = std::mem::take(&mut self.filter);
+                query.pop();
+                self.set_filter(query);
+            }
+            KeyCode::Char(c) if c != ' ' && !key.modifiers.control && !key.modifiers.alt => {
+                let mut query = std::mem::take(&mut self.filter);
+                query.push(c);
+                self.set_filter(query);
+            }
+            _ => return EventResult::Ignored,
+        }
+        EventResult::Consumed
+    }
+
+    fn update(
+        &mut self,
+        _action: TermWmAction,
+        _ctx: &ComponentContext,
+        _actions: &mut VecDeque<(WindowKey, TermWmAction)>,
+    ) {
+    }
+
+    fn selection_text(&self) -> Option<String> {
+        let ids: Vec<usize> = if self.selected.is_empty() {
+            self.visible.get(self.cursor).copied().into_iter().collect()
+        } else {
+            self.selected.iter().copied().collect()
+        };
+        if ids.is_empty() {
+            return None;
+        }
+        Some(
+            ids.iter()
+                .map(|&id| self.nodes[id].label.as_str())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+    use term_wm_core::component_context::{ScrollBounds, ScrollHandle};
+    use term_wm_core::events::{KeyEvent, KeyKind};
+
+    fn sample() -> TreeViewComponent<&'static str> {
+        TreeViewComponent::new(vec![
+            TreeNode::branch(
+                "src",
+                "src",
+                vec![
+                    TreeNode::leaf("main.rs", "src/main.rs"),
+                    TreeNode::branch(
+                        "ui",
+                        "src/ui",
+                        vec![TreeNode::leaf("tree.rs", "src/ui/tree.rs")],
+                    ),
+                ],
+            ),
+            TreeNode::leaf("Cargo.toml", "Cargo.toml"),
+        ])
+    }
+
+    fn press(
+        tree: &mut TreeViewComponent<&'static str>,
+        code: KeyCode,
+    ) -> EventResult<TermWmAction> {
+        let ctx = ComponentContext::new(true);
+        let event = Event::Key(KeyEvent::new(code, KeyModifiers::NONE, KeyKind::Press));
+        tree.handle_events(&event, &ctx)
+    }
+
+    fn labels(tree: &TreeViewComponent<&'static str>) -> Vec<&'static str> {
+        tree.visible.iter().map(|&id| tree.nodes[id].data).collect()
+    }
+
+    fn render<T>(tree: &mut TreeViewComponent<T>, width: u16, height: u16) -> Vec<String> {
+        let handle = ScrollHandle {
+            scroll: Rc::new(RefCell::new(ScrollBounds::default())),
+        };
+        render_with(tree, &handle, width, height)
+    }
+
+    fn render_with<T>(
+        tree: &mut TreeViewComponent<T>,
+        handle: &ScrollHandle,
+        width: u16,
+        height: u16,
+    ) -> Vec<String> {
+        let ctx = ComponentContext::new(true).with_viewport(handle.info(), Some(handle.clone()));
+        let rect = ratatui::layout::Rect::new(0, 0, width, height);
+        let mut backend =
+            term_wm_console::RatatuiBackend::new_simple(ratatui::buffer::Buffer::empty(rect), rect);
+        let mut registry = term_wm_core::hitbox_registry::HitboxRegistry::new();
+        let area = LayoutRect {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        };
+        tree.render(&mut backend, area, &ctx, &mut registry);
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| backend.buffer[(x, y)].symbol().to_string())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn arrows_expand_collapse_and_navigate() {
+        let mut tree = sample();
+        assert_eq!(labels(&tree), vec!["src", "Cargo.toml"]);
+        press(&mut tree, KeyCode::Right);
+        assert_eq!(
+            labels(&tree),
+            vec!["src", "src/main.rs", "src/ui", "Cargo.toml"]
+        );
+        press(&mut tree, KeyCode::Right);
+        assert_eq!(tree.data(tree.cursor().unwrap()), Some(&"src/main.rs"));
+        press(&mut tree, KeyCode::Left);
+        assert_eq!(tree.data(tree.cursor().unwrap()), Some(&"src"));
+        press(&mut tree, KeyCode::Left);
+        assert_eq!(labels(&tree), vec!["src", "Cargo.toml"]);
+        press(&mut tree, KeyCode::End);
+        assert_eq!(tree.data(tree.cursor().unwrap()), Some(&"Cargo.toml"));
+    }
+
+    #[test]
+    fn renders_indentation_and_glyphs() {
+        let mut tree = sample();
+        tree.expand(tree.roots()[0]);
+        let lines = render(&mut tree, 20, 5);
+        assert_eq!(lines[0], "▾ src");
+        assert_eq!(lines[1], "    main.rs");
+        assert_eq!(lines[2], "  ▸ ui");
+        assert_eq!(lines[3], "  Cargo.toml");
+    }
+
+    #[test]
+    fn lazy_children_load_once_on_expand() {
+        let calls = Rc::new(Cell::new(0));
+        let counter = calls.clone();
+        let mut tree = TreeViewComponent::new(vec![TreeNode::lazy("/", 0u32)]).with_loader(
+            move |depth: &u32| {
+                counter.set(counter.get() + 1);
+                if *depth >= 1 {
+                    return Vec::new();
+                }
+                (0..3)
+                    .map(|i| TreeNode::lazy(format!("dir{i}"), depth + 1))
+                    .collect()
+            },
+        );
+        assert_eq!(calls.get(), 0);
+        let root = tree.roots()[0];
+        tree.expand(root);
+        assert_eq!(tree.visible_len(), 4);
+        tree.collapse(root);
+        tree.expand(root);
+        assert_eq!(calls.get(), 1);
+        // An empty load turns the node into a leaf.
+        let child = tree.children(root)[0];
+        tree.expand(child);
+        assert!(!tree.is_expanded(child));
+        assert_eq!(render(&mut tree, 20, 5)[1], "    dir0");
+    }
+
+    #[test]
+    fn typing_filters_to_matches_and_ancestors() {
+        let mut tree = sample();
+        for c in "tree".chars() {
+            press(&mut tree, KeyCode::Char(c));
+        }
+        assert_eq!(tree.filter(), "tree");
+        assert_eq!(labels(&tree), vec!["src", "src/ui", "src/ui/tree.rs"]);
+        assert_eq!(tree.data(tree.cursor().unwrap()), Some(&"src/ui/tree.rs"));
+        assert_eq!(render(&mut tree, 20, 5)[0], "/ tree");
+        press(&mut tree, KeyCode::Backspace);
+        assert_eq!(tree.filter(), "tre");
+        assert!(press(&mut tree, KeyCode::Esc).is_consumed());
+        // The node found through the filter stays under the cursor.
+        assert_eq!(
+            labels(&tree),
+            vec![
+                "src",
+                "src/main.rs",
+                "src/ui",
+                "src/ui/tree.rs",
+                "Cargo.toml"
+            ]
+        );
+        assert_eq!(tree.data(tree.cursor().unwrap()), Some(&"src/ui/tree.rs"));
+        assert!(!press(&mut tree, KeyCode::Esc).is_consumed());
+    }
+
+    #[test]
+    fn multi_select_with_space_and_mouse() {
+        let mut tree = sample().with_multi_select(true);
+        tree.expand(tree.roots()[0]);
+        press(&mut tree, KeyCode::Char(' '));
+        press(&mut tree, KeyCode::Down);
+        press(&mut tree, KeyCode::Char(' '));
+        assert_eq!(tree.selected().len(), 2);
+        assert_eq!(tree.filter(), "");
+        let ctx = ComponentContext::new(true);
+        let shift = KeyModifiers {
+            shift: true,
+            ..KeyModifiers::NONE
+        };
+        tree.on_mouse_press(6, 3, MouseButton::Left, shift, &ctx);
+        let selected: Vec<_> = tree
+            .selected()
+            .into_iter()
+            .map(|id| *tree.data(id).unwrap())
+            .collect();
+        assert_eq!(selected, vec!["src", "src/main.rs", "src/ui", "Cargo.toml"]);
+        assert_eq!(
+            tree.selection_text().as_deref(),
+            Some("src\nmain.rs\nui\nCargo.toml")
+        );
+    }
+
+    #[test]
+    fn clicking_glyph_toggles_node() {
+        let mut tree = sample();
+        let ctx = ComponentContext::new(true);
+        tree.on_mouse_press(0, 0, MouseButton::Left, KeyModifiers::NONE, &ctx);
+        assert!(tree.is_expanded(tree.roots()[0]));
+        // Clicking the label only moves the cursor.
+        tree.on_mouse_press(6, 2, MouseButton::Left, KeyModifiers::NONE, &ctx);
+        assert_eq!(tree.data(tree.cursor().unwrap()), Some(&"src/ui"));
+        assert_eq!(tree.visible_len(), 4);
+    }
+
+    #[test]
+    fn only_visible_rows_are_drawn_for_large_trees() {
+        let children = (0..50_000)
+            .map(|i| TreeNode::leaf(format!("node {i}"), i))
+            .collect();
+        let mut tree = TreeViewComponent::new(vec![TreeNode::branch("root", -1, children)]);
+        tree.set_cursor(TreeNodeId(40_001));
+        let handle = ScrollHandle {
+            scroll: Rc::new(RefCell::new(ScrollBounds::default())),
+        };
+        // The first pass scrolls to the cursor; the second draws there, as
+        // the scroll view's same-frame re-render does.
+        render_with(&mut tree, &handle, 30, 4);
+        let lines = render_with(&mut tree, &handle, 30, 4);
+        assert_eq!(lines[3], "    node 40000");
+        assert_eq!(tree.visible_len(), 50_001);
+    }
+}
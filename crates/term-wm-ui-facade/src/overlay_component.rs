@@ -4,6 +4,7 @@ use term_wm_sys_ui_components::wm_command_palette::WmCommandPaletteComponent;
 use term_wm_sys_ui_components::wm_help_overlay::WmHelpOverlayComponent;
 use term_wm_ui_components::confirm_overlay::ConfirmOverlayComponent;
 
+#[allow(clippy::large_enum_variant)]
 pub enum OverlayComponent {
     Help(WmHelpOverlayComponent),
     CommandPalette(WmCommandPaletteComponent),
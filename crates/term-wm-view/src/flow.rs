@@ -0,0 +1,212 @@
+//! Control-flow blocks: `{ for .. }`, `{ if .. }` and `{ match .. }` whose
+//! bodies contain markup.
+//!
+//! rstml only understands `{ }` blocks that are valid Rust, so
+//! [`parse_nodes`] rewrites control-flow child blocks into a
+//! `__view_flow! { .. }` marker statement first. `Generator::node` recognizes
+//! the marker and parses the original tokens with [`Flow`].
+
+use proc_macro2::{Delimiter, Group, TokenStream as TokenStream2, TokenTree};
+use quote::{ToTokens, format_ident, quote};
+use rstml::node::Node;
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::{Block, Expr, Pat, Stmt, Token, braced};
+
+const MARKER: &str = "__view_flow";
+
+/// Parse `view!` input, routing control-flow blocks through the marker.
+pub(crate) fn parse_nodes(tokens: TokenStream2) -> syn::Result<Vec<Node>> {
+    rstml::parse2(mark_flow(tokens))
+}
+
+/// Wrap the contents of control-flow child blocks in the marker macro.
+///
+/// Only top-level brace groups are child blocks — everything else is element
+/// syntax, and a group after `=` is an attribute value. Bodies are handled
+/// when [`Body`] parses them. `for` blocks are always markup loops; `if` and
+/// `match` blocks are only taken over when they are not plain Rust, so
+/// `{ if a { x } else { y } }` keeps meaning "an expression yielding a
+/// component". An armless `match` is taken over too, so it is reported
+/// rather than left to type inference.
+fn mark_flow(tokens: TokenStream2) -> TokenStream2 {
+    let mut after_eq = false;
+    tokens
+        .into_iter()
+        .map(|tree| {
+            let value = after_eq;
+            after_eq = matches!(&tree, TokenTree::Punct(p) if p.as_char() == '=');
+            match tree {
+                TokenTree::Group(group)
+                    if !value
+                        && group.delimiter() == Delimiter::Brace
+                        && is_flow(group.stream()) =>
+                {
+                    let marker = format_ident!("{MARKER}");
+                    let inner = group.stream();
+                    let mut marked = Group::new(Delimiter::Brace, quote!(#marker! { #inner }));
+                    marked.set_span(group.span());
+                    TokenTree::Group(marked)
+                }
+                other => other,
+            }
+        })
+        .collect()
+}
+
+fn is_flow(tokens: TokenStream2) -> bool {
+    let verdict = |input: ParseStream| {
+        let flow = if input.peek(Token![for]) {
+            true
+        } else if input.peek(Token![if]) || input.peek(Token![match]) {
+            let fork = input.fork();
+            match Block::parse_within(&fork) {
+                Ok(stmts) if fork.is_empty() => matches!(
+                    stmts.as_slice(),
+                    [Stmt::Expr(Expr::Match(m), None)] if m.arms.is_empty()
+                ),
+                _ => true,
+            }
+        } else {
+            false
+        };
+        // Only the verdict matters; skip the tokens.
+        input.parse::<TokenStream2>()?;
+        Ok(flow)
+    };
+    verdict.parse2(tokens).unwrap_or(false)
+}
+
+/// The original tokens of a block rewritten by [`mark_flow`].
+pub(crate) fn marked_tokens(block: &Block) -> Option<TokenStream2> {
+    let mac = match block.stmts.as_slice() {
+        [Stmt::Macro(m)] => &m.mac,
+        [Stmt::Expr(Expr::Macro(m), _)] => &m.mac,
+        _ => return None,
+    };
+    mac.path.is_ident(MARKER).then(|| mac.tokens.clone())
+}
+
+#[allow(clippy::large_enum_variant)]
+pub(crate) enum Flow {
+    /// `for pat in iter { <Tag/> }`
+    For { pat: Pat, iter: Expr, body: Body },
+    /// `if cond { .. } else if cond { .. } else { .. }`; conditions may be
+    /// `let` patterns.
+    If {
+        branches: Vec<(TokenStream2, Body)>,
+        otherwise: Option<Body>,
+    },
+    /// `match scrutinee { pat if guard => { .. } }`
+    Match { scrutinee: Expr, arms: Vec<Arm> },
+}
+
+pub(crate) struct Arm {
+    pub pat: Pat,
+    pub guard: Option<Expr>,
+    pub body: Body,
+}
+
+/// A braced branch body holding exactly one view node.
+pub(crate) struct Body {
+    pub node: Node,
+}
+
+impl Parse for Flow {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let flow = if input.peek(Token![for]) {
+            input.parse::<Token![for]>()?;
+            let pat = Pat::parse_multi_with_leading_vert(input)?;
+            input.parse::<Token![in]>()?;
+            let iter = Expr::parse_without_eager_brace(input)?;
+            let body = input.parse()?;
+            Self::For { pat, iter, body }
+        } else if input.peek(Token![if]) {
+            let mut branches = Vec::new();
+            let mut otherwise = None;
+            loop {
+                input.parse::<Token![if]>()?;
+                let condition = condition(input)?;
+                branches.push((condition, input.parse()?));
+                if input.parse::<Option<Token![else]>>()?.is_none() {
+                    break;
+                }
+                if !input.peek(Token![if]) {
+                    otherwise = Some(input.parse()?);
+                    break;
+                }
+            }
+            Self::If {
+                branches,
+                otherwise,
+            }
+        } else {
+            input.parse::<Token![match]>()?;
+            let scrutinee = Expr::parse_without_eager_brace(input)?;
+            let content;
+            let brace = braced!(content in input);
+            let mut arms = Vec::new();
+            while !content.is_empty() {
+                let pat = Pat::parse_multi_with_leading_vert(&content)?;
+                let guard = match content.parse::<Option<Token![if]>>()? {
+                    Some(_) => Some(content.parse()?),
+                    None => None,
+                };
+                content.parse::<Token![=>]>()?;
+                let body = content.parse()?;
+                content.parse::<Option<Token![,]>>()?;
+                arms.push(Arm { pat, guard, body });
+            }
+            if arms.is_empty() {
+                return Err(syn::Error::new(
+                    brace.span.join(),
+                    "`match` blocks in view! need at least one arm",
+                ));
+            }
+            Self::Match { scrutinee, arms }
+        };
+        if !input.is_empty() {
+            return Err(input.error("unexpected tokens after the control-flow block"));
+        }
+        Ok(flow)
+    }
+}
+
+fn condition(input: ParseStream) -> syn::Result<TokenStream2> {
+    if input.peek(Token![let]) {
+        let let_token: Token![let] = input.parse()?;
+        let pat = Pat::parse_multi_with_leading_vert(input)?;
+        let eq: Token![=] = input.parse()?;
+        let expr = Expr::parse_without_eager_brace(input)?;
+        Ok(quote!(#let_token #pat #eq #expr))
+    } else {
+        Ok(Expr::parse_without_eager_brace(input)?.into_token_stream())
+    }
+}
+
+impl Parse for Body {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if !input.peek(syn::token::Brace) {
+            return Err(
+                input.error("control-flow bodies must be braced, e.g. `{ <Label text=\"..\" /> }`")
+            );
+        }
+        let content;
+        let brace = braced!(content in input);
+        let span = brace.span.join();
+        let nodes = parse_nodes(content.parse()?)?;
+        let mut nodes: Vec<Node> = nodes
+            .into_iter()
+            .filter(|n| !crate::meaningful(std::slice::from_ref(n)).is_empty())
+            .collect();
+        if nodes.len() != 1 {
+            return Err(syn::Error::new(
+                span,
+                "each control-flow body must contain exactly one element — wrap \
+                 siblings in a container like <VStack>",
+            ));
+        }
+        Ok(Self {
+            node: nodes.remove(0),
+        })
+    }
+}
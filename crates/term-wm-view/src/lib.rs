@@ -4,7 +4,8 @@
 //! container constructors, leaf component constructors, and generated delegate
 //! enums for heterogeneous sibling sets. The result is a value implementing
 //! [`Component`](term_wm_core::components::Component) — there is no runtime tree, no reactivity, no reconciliation,
-//! no `Box`/`dyn`.
+//! no `Box`/`dyn`. Loops and conditionals expand to ordinary `for`/`if`/`match`
+//! code over those same concrete types.
 //!
 //! ```text
 //! view! {
@@ -20,10 +21,44 @@
 //! 1. **Layout-primitive tags**: `<VStack>`/`<Column>`, `<HStack>`/`<Row>`,
 //!    `<Center width height>`, `<Grid cols rows>` (constraint strings parsed at
 //!    compile time into `GridConstraint`s).
-//! 2. **Built-in component tags**: `<Label text>`, `<Button label action|onClick>`.
+//! 2. **Built-in component tags**: `<Label text>`, `<Button label action|onClick|msg>`
+//!    (`msg={Msg::Refresh}` emits a typed application message, see
+//!    `impl_view_component!`'s `msg =` option),
+//!    `<TextInput value placeholder history validate onSubmit>`,
+//!    `<TextArea value placeholder rows validate>` (all attributes optional)
+//!    and `<TreeView roots loader multiSelect onActivate>` (`roots` required).
+//!    `<Form parser onSubmit onCancel>` takes field children
+//!    (`<Input name label value placeholder validate required>`,
+//!    `<Toggle name label checked>`, `<Select name label options selected>`)
+//!    and optional `<Submit label>`/`<Cancel label>` buttons.
 //! 3. **Expression braces** `{ expr }`: any expression yielding a `Component`
 //!    (owned, or `&mut C` via the blanket impl) — no registry needed for
 //!    third-party or fallible components.
+//! 4. **Control flow** whose bodies hold markup, each body exactly one
+//!    element:
+//!    - `{ for item in iter { <Label text={item} /> } }` expands to a plain
+//!      `for` loop filling a stack of one concrete item type (an `HStack` inside
+//!      `<HStack>`/`<Row>` with the same `gap`, otherwise a `VStack`).
+//!    - `{ if cond { .. } else if let Some(x) = y { .. } else { .. } }` and
+//!      `{ match value { pat if guard => { .. } } }` wrap each branch in a
+//!      generated delegate enum. A missing `else` becomes `NoopComponent`,
+//!      which still takes a stretch row in a stack.
+//!
+//!    `if`/`match` blocks that are plain Rust (no markup) stay ordinary
+//!    expression braces.
+//! 5. **Component tags** from any crate: `#[view_tag(Table)]` on a
+//!    component's inherent `impl` generates a `Table` builder, and
+//!    `<Table columns={cols} rows={rows} />` expands to
+//!    `Table::__view().columns(cols).rows(rows).__build()`. Parameters of `new`
+//!    are required attributes; public `with_x(self, v) -> Self` methods are
+//!    optional attribute `x`, and argument-less `x(self) -> Self` methods are
+//!    flags (`<Table x />` or `x={bool}`). Unknown, mistyped or missing
+//!    attributes are compile errors. The in-tree `<Table>`, `<ProgressBar>`,
+//!    `<Spinner>`, `<Gauge>` and `<Sparkline>` tags resolve through the
+//!    component crate; any other tag resolves like a Rust path, so import it
+//!    (`use widgets::Badge;`) or write `<widgets::Badge ..>`.
+//!    Components without a plain `new` (such as `TerminalComponent`, built
+//!    from a pane) still go through `{ expr }`.
 //!
 //! # Path resolution
 //!
@@ -42,10 +77,20 @@
 use proc_macro::TokenStream;
 use proc_macro_crate::{FoundCrate, crate_name};
 use proc_macro2::TokenStream as TokenStream2;
-use quote::{format_ident, quote};
+use quote::{ToTokens, format_ident, quote};
 use rstml::node::{Infallible, Node, NodeAttribute, NodeElement};
+use syn::spanned::Spanned;
 use syn::{Expr, Ident, Lit};
 
+mod flow;
+mod tag;
+
+use flow::Flow;
+
+/// `#[view_tag]` tags declared in `term-wm-ui-components`; these resolve
+/// through the component crate path, so they need no import.
+const COMPONENT_TAGS: &[&str] = &["Table", "ProgressBar", "Spinner", "Gauge", "Sparkline"];
+
 /// rstml parses `<VStack>` elements as `NodeElement<Infallible>`.
 type Element = NodeElement<Infallible>;
 
@@ -105,12 +150,27 @@ pub fn view(input: TokenStream) -> TokenStream {
     }
 }
 
+/// Expose a component's `new` constructor and builder methods as a `view!`
+/// tag: `#[view_tag(Name)]` (or `#[view_tag(pub(crate) Name)]`) on an
+/// inherent `impl` block. See the crate docs.
+#[proc_macro_attribute]
+pub fn view_tag(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let item = TokenStream2::from(item);
+    match tag::expand(attr.into(), item.clone()) {
+        Ok(ts) => ts.into(),
+        Err(e) => tag::with_item(e, item).into(),
+    }
+}
+
 /// Accumulates block-scoped generated items (`enum __ViewN` + `impl`) and the
 /// unique-name counter.
 struct Generator {
     items: Vec<TokenStream2>,
     counter: usize,
     paths: Paths,
+    /// Direction and gap of the innermost enclosing stack; `{ for .. }`
+    /// loops collect their items into a stack of the same kind.
+    list: (ContainerKind, Option<Expr>),
 }
 
 impl Generator {
@@ -119,6 +179,7 @@ impl Generator {
             items: Vec::new(),
             counter: 0,
             paths,
+            list: (ContainerKind::Vertical, None),
         }
     }
 
@@ -157,7 +218,7 @@ impl Generator {
 
 fn expand(tokens: TokenStream2) -> syn::Result<TokenStream2> {
     let mut g = Generator::new(detect_paths());
-    let nodes = rstml::parse2(tokens.clone())?;
+    let nodes = flow::parse_nodes(tokens.clone())?;
     if nodes.len() != 1 {
         return Err(syn::Error::new_spanned(
             tokens,
@@ -248,6 +309,9 @@ impl Generator {
                         "invalid expression block in view!",
                     ));
                 };
+                if let Some(tokens) = flow::marked_tokens(block) {
+                    return self.flow(tokens);
+                }
                 // Emit a single-expression block without its braces to avoid
                 // the `unused_braces` lint at the construction site.
                 match block.stmts.as_slice() {
@@ -281,14 +345,17 @@ impl Generator {
             }
             "Button" => {
                 let label = required_attr_expr(el, "label")?;
-                let action = attr_expr(el, "action")
-                    .or_else(|| attr_expr(el, "onClick"))
-                    .cloned()
-                    .map(strip_block)
+                let message = optional_attr(el, &["msg"]).map(|msg| {
+                    let action = self.core_mod("actions", "TermWmAction");
+                    quote!(#action::message(#msg))
+                });
+                let action = optional_attr(el, &["action", "onClick"])
+                    .map(|action| quote!(#action))
+                    .or(message)
                     .ok_or_else(|| {
                         syn::Error::new_spanned(
                             el.name(),
-                            "<Button> requires an `action` (or `onClick`) attribute",
+                            "<Button> requires an `action` (or `onClick`), or a typed `msg` attribute",
                         )
                     })?;
                 let button = self.comp("ButtonComponent");
@@ -296,16 +363,71 @@ impl Generator {
                     #button::new(#label, #action)
                 })
             }
+            "TextInput" => Ok(self.text_input(el)),
+            "TextArea" => Ok(self.text_area(el)),
+            "TreeView" => self.tree_view(el),
+            "Form" => self.form(el),
+            other if other.starts_with(char::is_uppercase) => self.custom_tag(el),
             other => Err(syn::Error::new_spanned(
                 el.name(),
                 format!(
                     "unknown tag <{other}>; available tags: VStack, Column, HStack, Row, \
-                     Center, Grid, Label, Button. Use {{ expr }} to inject an arbitrary component."
+                     Center, Grid, Box, Div, Label, Button, TextInput, TextArea, TreeView, Form, \
+                     or a component tag declared with #[view_tag]. \
+                     Use {{ expr }} to inject an arbitrary component."
                 ),
             )),
         }
     }
 
+    /// A tag declared with `#[view_tag]`: chain one builder call per
+    /// attribute, so the compiler checks names, types and required ones.
+    fn custom_tag(&mut self, el: &Element) -> syn::Result<TokenStream2> {
+        let rstml::node::NodeName::Path(path) = el.name() else {
+            return Err(syn::Error::new_spanned(
+                el.name(),
+                "tag names must be plain identifiers (e.g. <VStack>)",
+            ));
+        };
+        if !meaningful(el.children()).is_empty() {
+            return Err(syn::Error::new_spanned(
+                el.name(),
+                "#[view_tag] component tags take no children",
+            ));
+        }
+        let view = Ident::new("__view", path.span());
+        let target = match path.path.get_ident() {
+            Some(ident) if COMPONENT_TAGS.contains(&ident.to_string().as_str()) => {
+                let prefix = &self.paths.comp;
+                quote!(#prefix #ident)
+            }
+            _ => path.to_token_stream(),
+        };
+        let mut chain = quote!(#target::#view());
+        for attr in el.attributes() {
+            let NodeAttribute::Attribute(kv) = attr else {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "attribute blocks are not supported on component tags — use name={value}",
+                ));
+            };
+            let key = match &kv.key {
+                rstml::node::NodeName::Path(p) => p.path.get_ident().cloned(),
+                _ => None,
+            }
+            .ok_or_else(|| {
+                syn::Error::new_spanned(&kv.key, "attribute names must be plain identifiers")
+            })?;
+            let value = kv
+                .value()
+                .cloned()
+                .map_or_else(|| quote!(true), |v| strip_block(v).into_token_stream());
+            chain = quote!(#chain.#key(#value));
+        }
+        let build = Ident::new("__build", path.span());
+        Ok(quote!(#chain.#build()))
+    }
+
     /// Expand a container's children into `.add()` / `vec!` entries, generating
     /// a delegate enum for heterogeneous sibling sets.
     ///
@@ -386,6 +508,9 @@ impl Generator {
         let take_alternate = variants
             .iter()
             .map(|v| quote!(Self::#v(x) => x.take_alternate_screen_transition()));
+        let host_cursor = variants
+            .iter()
+            .map(|v| quote!(Self::#v(x) => x.host_cursor()));
         let take_teardown = variants
             .iter()
             .map(|v| quote!(Self::#v(x) => x.take_teardown_parts()));
@@ -431,6 +556,7 @@ impl Generator {
         let event_result_ty = self.core_mod("actions", "EventResult");
         let term_wm_action_ty = self.core_mod("actions", "TermWmAction");
         let selection_status_ty = self.core_mod("components", "SelectionStatus");
+        let host_cursor_ty = self.core_mod("components", "HostCursor");
         let rect_ty = self.core_root("Rect");
         let backend_ty = &self.paths.backend;
 
@@ -510,6 +636,7 @@ impl Generator {
                 fn desired_height(&self, width: u16) -> u16 { match self { #(#desired_height),* } }
                 fn take_pending_title(&mut self) -> Option<String> { match self { #(#take_pending_title),* } }
                 fn take_alternate_screen_transition(&mut self) -> Option<bool> { match self { #(#take_alternate),* } }
+                fn host_cursor(&self) -> Option<#host_cursor_ty> { match self { #(#host_cursor),* } }
                 fn take_teardown_parts(
                     &mut self,
                 ) -> Option<(Box<dyn ::std::any::Any + Send + Sync>, ::std::thread::JoinHandle<()>)> {
@@ -534,7 +661,10 @@ impl Generator {
             ContainerKind::Horizontal => self.comp("HStackComponent"),
         };
         let gap = attr_expr(el, "gap").cloned();
-        let (items, adds, has_children) = self.children(el)?;
+        let outer = std::mem::replace(&mut self.list, (kind, gap.clone()));
+        let children = self.children(el);
+        self.list = outer;
+        let (items, adds, has_children) = children?;
         self.push_items(items);
         let noop = self.core_mod("components", "NoopComponent");
 
@@ -645,6 +775,260 @@ impl Generator {
     }
 }
 
+impl Generator {
+    /// Expand a `{ for .. }`, `{ if .. }` or `{ match .. }` block.
+    ///
+    /// Loops build a stack (the enclosing stack's direction and gap, vertical
+    /// by default) holding one concrete item type. Conditionals wrap each
+    /// branch in a generated delegate enum, with `NoopComponent` standing in
+    /// for a missing `else`.
+    fn flow(&mut self, tokens: TokenStream2) -> syn::Result<TokenStream2> {
+        match syn::parse2::<Flow>(tokens)? {
+            Flow::For { pat, iter, body } => {
+                let item = self.node(&body.node)?;
+                let (kind, gap) = self.list.clone();
+                let ctor = match kind {
+                    ContainerKind::Vertical => self.comp("VStackComponent"),
+                    ContainerKind::Horizontal => self.comp("HStackComponent"),
+                };
+                let init = match gap {
+                    Some(g) => quote!(#ctor::new().with_gap(#g)),
+                    None => quote!(#ctor::new()),
+                };
+                Ok(quote! {{
+                    let mut __list = #init;
+                    for #pat in #iter {
+                        __list.add(#item);
+                    }
+                    __list
+                }})
+            }
+            Flow::If {
+                branches,
+                otherwise,
+            } => {
+                let mut exprs = Vec::with_capacity(branches.len() + 1);
+                for (_, body) in &branches {
+                    exprs.push(self.node(&body.node)?);
+                }
+                exprs.push(match &otherwise {
+                    Some(body) => self.node(&body.node)?,
+                    None => self.core_mod("components", "NoopComponent"),
+                });
+                let (item, mut constructions) = self.sibling_enum(&exprs)?;
+                self.push_items(vec![item]);
+                let last = constructions.pop();
+                let conditions = branches.iter().map(|(c, _)| c);
+                Ok(quote! {
+                    #(if #conditions { #constructions } else)* { #last }
+                })
+            }
+            Flow::Match { scrutinee, arms } => {
+                let mut exprs = Vec::with_capacity(arms.len());
+                for arm in &arms {
+                    exprs.push(self.node(&arm.body.node)?);
+                }
+                let (item, constructions) = self.sibling_enum(&exprs)?;
+                self.push_items(vec![item]);
+                let pats = arms.iter().map(|arm| &arm.pat);
+                let guards = arms
+                    .iter()
+                    .map(|arm| arm.guard.as_ref().map(|g| quote!(if #g)));
+                Ok(quote! {
+                    match #scrutinee { #(#pats #guards => #constructions,)* }
+                })
+            }
+        }
+    }
+
+    /// `<TextInput value=.. placeholder=.. history=N validate={..} onSubmit={..} />`
+    /// — every attribute is optional and maps to the matching builder.
+    fn text_input(&mut self, el: &Element) -> TokenStream2 {
+        let input = self.comp("TextInputComponent");
+        let mut base = quote!(#input::new());
+        if let Some(v) = optional_attr(el, &["value"]) {
+            base = quote!(#base.with_value(#v));
+        }
+        if let Some(p) = optional_attr(el, &["placeholder"]) {
+            base = quote!(#base.with_placeholder(#p));
+        }
+        if let Some(h) = optional_attr(el, &["history"]) {
+            base = quote!(#base.with_history(#h));
+        }
+        if let Some(f) = optional_attr(el, &["validate", "validator"]) {
+            base = quote!(#base.with_validator(#f));
+        }
+        if let Some(a) = optional_attr(el, &["onSubmit", "action"]) {
+            base = quote!(#base.with_submit_action(#a));
+        }
+        base
+    }
+
+    /// `<TextArea value=.. placeholder=.. rows=N validate={..} />`.
+    fn text_area(&mut self, el: &Element) -> TokenStream2 {
+        let area = self.comp("TextAreaComponent");
+        let mut base = quote!(#area::new());
+        if let Some(v) = optional_attr(el, &["value"]) {
+            base = quote!(#base.with_value(#v));
+        }
+        if let Some(p) = optional_attr(el, &["placeholder"]) {
+            base = quote!(#base.with_placeholder(#p));
+        }
+        if let Some(r) = optional_attr(el, &["rows"]) {
+            base = quote!(#base.with_rows(#r));
+        }
+        if let Some(f) = optional_attr(el, &["validate", "validator"]) {
+            base = quote!(#base.with_validator(#f));
+        }
+        base
+    }
+
+    /// `<TreeView roots={..} loader={..} multiSelect={bool} onActivate={..} />`
+    /// — `roots` is the `Vec<TreeNode<T>>` of top-level nodes.
+    fn tree_view(&mut self, el: &Element) -> syn::Result<TokenStream2> {
+        let roots = optional_attr(el, &["roots"]).ok_or_else(|| {
+            syn::Error::new_spanned(el.name(), "<TreeView> requires a `roots` attribute")
+        })?;
+        let tree = self.comp("TreeViewComponent");
+        let mut base = quote!(#tree::new(#roots));
+        if let Some(l) = optional_attr(el, &["loader"]) {
+            base = quote!(#base.with_loader(#l));
+        }
+        if let Some(m) = optional_attr(el, &["multiSelect", "multi_select"]) {
+            base = quote!(#base.with_multi_select(#m));
+        }
+        if let Some(a) = optional_attr(el, &["onActivate", "action"]) {
+            base = quote!(#base.with_activate_action(#a));
+        }
+        Ok(base)
+    }
+
+    /// `<Form parser={..} onSubmit={..} onCancel={..}>` with field and button
+    /// children. The children are form parts, not components, so they are
+    /// expanded here rather than through [`Self::element`].
+    fn form(&mut self, el: &Element) -> syn::Result<TokenStream2> {
+        let form = self.comp("FormComponent");
+        let field = self.comp("FormField");
+        let button = self.comp("FormButton");
+        let mut base = quote!(#form::new());
+        let mut buttons = Vec::new();
+        for child in meaningful(el.children()) {
+            let Node::Element(child) = child else {
+                return Err(syn::Error::new(
+                    proc_macro2::Span::call_site(),
+                    "<Form> children must be <Input>, <Toggle>, <Select>, <Submit> or <Cancel>",
+                ));
+            };
+            let tag = tag_ident(child)?;
+            let part = match tag.to_string().as_str() {
+                "Input" => {
+                    let (name, label) = field_name_label(child)?;
+                    let mut f = quote!(#field::text(#name, #label));
+                    if let Some(v) = optional_attr(child, &["value"]) {
+                        f = quote!(#f.with_value(#v));
+                    }
+                    if let Some(p) = optional_attr(child, &["placeholder"]) {
+                        f = quote!(#f.with_placeholder(#p));
+                    }
+                    if let Some(v) = optional_attr(child, &["validate", "validator"]) {
+                        f = quote!(#f.with_validator(#v));
+                    }
+                    if let Some(required) = flag_attr(child, "required") {
+                        f = quote!({
+                            let field = #f;
+                            if #required { field.required() } else { field }
+                        });
+                    }
+                    f
+                }
+                "Toggle" => {
+                    let (name, label) = field_name_label(child)?;
+                    let checked = flag_attr(child, "checked").unwrap_or_else(|| quote!(false));
+                    quote!(#field::toggle(#name, #label, #checked))
+                }
+                "Select" => {
+                    let (name, label) = field_name_label(child)?;
+                    let options = required_attr_expr(child, "options")?;
+                    let mut f = quote!(#field::select(#name, #label, #options));
+                    if let Some(i) = optional_attr(child, &["selected"]) {
+                        f = quote!(#f.with_selected(#i));
+                    }
+                    f
+                }
+                "Submit" | "Cancel" => {
+                    let default = if tag == "Submit" { "OK" } else { "Cancel" };
+                    let label = optional_attr(child, &["label"])
+                        .map_or_else(|| quote!(#default), |l| quote!(#l));
+                    buttons.push(if tag == "Submit" {
+                        quote!(#button::submit(#label))
+                    } else {
+                        quote!(#button::cancel(#label))
+                    });
+                    continue;
+                }
+                other => {
+                    return Err(syn::Error::new_spanned(
+                        child.name(),
+                        format!(
+                            "unknown <Form> child <{other}>; available: Input, Toggle, \
+                             Select, Submit, Cancel"
+                        ),
+                    ));
+                }
+            };
+            base = quote!(#base.with_field(#part));
+        }
+        if !buttons.is_empty() {
+            base = quote!(#base.with_buttons(vec![#(#buttons),*]));
+        }
+        if let Some(a) = optional_attr(el, &["onSubmit"]) {
+            base = quote!(#base.with_submit_action(#a));
+        }
+        if let Some(a) = optional_attr(el, &["onCancel"]) {
+            base = quote!(#base.with_cancel_action(#a));
+        }
+        if let Some(p) = optional_attr(el, &["parser"]) {
+            base = quote!(#base.with_parser(#p));
+        }
+        Ok(base)
+    }
+}
+
+/// The required `name` and `label` of a `<Form>` field child.
+fn field_name_label(el: &Element) -> syn::Result<(Expr, Expr)> {
+    Ok((
+        required_attr_expr(el, "name")?,
+        required_attr_expr(el, "label")?,
+    ))
+}
+
+/// A boolean attribute: `true` when present without a value (`<Input
+/// required />`), otherwise its value.
+fn flag_attr(el: &Element, name: &str) -> Option<TokenStream2> {
+    el.attributes().iter().find_map(|a| match a {
+        NodeAttribute::Attribute(kv) if attr_key(&kv.key).as_deref() == Some(name) => {
+            Some(match kv.value() {
+                Some(v) => {
+                    let v = strip_block(v.clone());
+                    quote!(#v)
+                }
+                None => quote!(true),
+            })
+        }
+        _ => None,
+    })
+}
+
+/// The first of `names` present on `el`, unwrapped from `{ }`.
+fn optional_attr(el: &Element, names: &[&str]) -> Option<Expr> {
+    names
+        .iter()
+        .find_map(|name| attr_expr(el, name))
+        .cloned()
+        .map(strip_block)
+}
+
+#[derive(Clone, Copy)]
 enum ContainerKind {
     Vertical,
     Horizontal,
@@ -741,6 +1125,76 @@ mod tests {
         assert_eq!(toks.len(), 2);
     }
 
+    /// The `compile_error!` message `view!` would emit for `input`.
+    fn expand_error(input: &str) -> String {
+        match expand(input.parse().unwrap()) {
+            Ok(tokens) => panic!("expected an error, expanded to {tokens}"),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    #[test]
+    fn control_flow_bodies_need_exactly_one_braced_element() {
+        let err = expand_error(
+            "<VStack>{ for n in 0..3 { <Label text=\"a\" /> <Label text=\"b\" /> } }</VStack>",
+        );
+        assert!(err.contains("exactly one element"), "{err}");
+        let err = expand_error("<VStack>{ for n in 0..3 { } }</VStack>");
+        assert!(err.contains("exactly one element"), "{err}");
+        let err = expand_error("<VStack>{ match n { 0 => <Label text=\"a\" />, } }</VStack>");
+        assert!(err.contains("must be braced"), "{err}");
+    }
+
+    #[test]
+    fn control_flow_misuse_is_reported() {
+        let err = expand_error("<VStack>{ for n in { <Label text=\"a\" /> } }</VStack>");
+        assert!(!err.is_empty());
+        let err = expand_error("<VStack>{ if ready { <Label text=\"a\" /> } else }</VStack>");
+        assert!(err.contains("must be braced"), "{err}");
+        let err = expand_error("<VStack>{ if ready { oops <Label text=\"a\" /> } }</VStack>");
+        assert!(err.contains("exactly one element"), "{err}");
+        let err = expand_error(
+            "<VStack>{ if ready { \"text\" } else { <Label text=\"a\" /> } }</VStack>",
+        );
+        assert!(err.contains("text nodes are not allowed"), "{err}");
+        let err = expand_error("<VStack>{ match n {} }</VStack>");
+        assert!(err.contains("at least one arm"), "{err}");
+    }
+
+    #[test]
+    fn component_tags_chain_builder_calls() {
+        let tokens = expand(
+            "<my::Badge text=\"inbox\" count={n} hidden />"
+                .parse()
+                .unwrap(),
+        )
+        .unwrap()
+        .to_string();
+        assert!(
+            tokens.contains(
+                "my :: Badge :: __view () . text (\"inbox\") . count (n) . hidden (true) . __build ()"
+            ),
+            "{tokens}"
+        );
+        let err = expand_error("<Badge><Label text=\"a\" /></Badge>");
+        assert!(err.contains("take no children"), "{err}");
+        let err = expand_error("<badge />");
+        assert!(err.contains("unknown tag <badge>"), "{err}");
+    }
+
+    #[test]
+    fn plain_rust_conditionals_stay_expressions() {
+        let tokens = expand(
+            "<VStack>{ if a { x } else { y } }</VStack>"
+                .parse()
+                .unwrap(),
+        )
+        .unwrap()
+        .to_string();
+        assert!(!tokens.contains("__View"), "{tokens}");
+        assert!(tokens.contains("if a { x } else { y }"), "{tokens}");
+    }
+
     #[test]
     fn grid_constraints_reject_bad_token() {
         let paths = Paths {
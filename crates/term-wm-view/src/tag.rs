@@ -0,0 +1,432 @@
+//! `#[view_tag(Name)]` — expose a component's constructor and builder methods
+//! as a `view!` tag.
+//!
+//! The attribute goes on an inherent `impl` block and generates a builder
+//! struct called `Name` next to it:
+//! - parameters of `new` are required attributes, tracked in the builder's
+//!   type (a slot holds `MissingAttribute` until set), so a missing one is a
+//!   compile error;
+//! - `pub fn with_x(self, v: T) -> Self` / `pub fn x(self, v: T) -> Self`
+//!   become optional attribute `x`, and argument-less `pub fn x(self) -> Self`
+//!   becomes a flag taking a `bool`.
+//!
+//! `view!` expands `<Name a={..} flag />` to
+//! `Name::__view().a(..).flag(true).__build()`, so attribute values are
+//! checked against the method signatures.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, quote_spanned};
+use syn::parse::{Parse, ParseStream};
+use syn::{
+    FnArg, GenericParam, Ident, ImplItem, ImplItemFn, ItemImpl, Pat, ReturnType, Type, Visibility,
+};
+
+/// `#[view_tag(pub(crate) Name)]` — the visibility defaults to that of `new`.
+struct TagArgs {
+    vis: Option<Visibility>,
+    name: Ident,
+}
+
+impl Parse for TagArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let vis = match input.parse()? {
+            Visibility::Inherited => None,
+            vis => Some(vis),
+        };
+        Ok(Self {
+            vis,
+            name: input.parse()?,
+        })
+    }
+}
+
+/// A required attribute: one parameter of `new`.
+struct Required {
+    name: Ident,
+    ty: Type,
+}
+
+/// An optional attribute: a by-value builder method.
+struct Optional<'a> {
+    name: Ident,
+    method: &'a ImplItemFn,
+    /// `None` for argument-less flag methods.
+    arg: Option<Type>,
+}
+
+pub(crate) fn expand(attr: TokenStream2, item: TokenStream2) -> syn::Result<TokenStream2> {
+    let args: TagArgs = syn::parse2(attr)?;
+    let block: ItemImpl = syn::parse2(item)?;
+    if let Some((path, _)) = &block.trait_ {
+        return Err(syn::Error::new_spanned(
+            path,
+            "#[view_tag] goes on an inherent impl block, not a trait impl",
+        ));
+    }
+    let methods: Vec<&ImplItemFn> = block
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            ImplItem::Fn(f) => Some(f),
+            _ => None,
+        })
+        .collect();
+    let ctor = methods
+        .iter()
+        .find(|f| f.sig.ident == "new" && f.sig.receiver().is_none())
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                &block.self_ty,
+                "#[view_tag] needs a `new` constructor in the same impl block",
+            )
+        })?;
+
+    let mut required = Vec::new();
+    for input in &ctor.sig.inputs {
+        let FnArg::Typed(arg) = input else { continue };
+        let Pat::Ident(pat) = &*arg.pat else {
+            return Err(syn::Error::new_spanned(
+                &arg.pat,
+                "#[view_tag] constructor parameters must be plain names — they become attribute names",
+            ));
+        };
+        required.push(Required {
+            name: pat.ident.clone(),
+            ty: (*arg.ty).clone(),
+        });
+    }
+    let optional = builder_methods(&methods);
+    for opt in &optional {
+        if let Some(req) = required.iter().find(|r| r.name == opt.name) {
+            return Err(syn::Error::new_spanned(
+                &opt.method.sig.ident,
+                format!(
+                    "attribute `{}` is both a constructor parameter and a builder method",
+                    req.name
+                ),
+            ));
+        }
+    }
+
+    let paths = crate::detect_paths();
+    let core = &paths.core;
+    let missing = quote!(#core view_support::MissingAttribute);
+    let provided = quote!(#core view_support::Provided);
+    let name = &args.name;
+    let vis = args.vis.clone().unwrap_or_else(|| ctor.vis.clone());
+    let self_ty = &block.self_ty;
+    let (impl_generics, _, where_clause) = block.generics.split_for_impl();
+    // Type arguments of the generated struct for the impl's own parameters.
+    let own_params: Vec<&GenericParam> = block.generics.params.iter().collect();
+    let own_args: Vec<TokenStream2> = own_params
+        .iter()
+        .map(|p| match p {
+            GenericParam::Type(t) => {
+                let ident = &t.ident;
+                quote!(#ident)
+            }
+            GenericParam::Lifetime(l) => {
+                let lifetime = &l.lifetime;
+                quote!(#lifetime)
+            }
+            GenericParam::Const(c) => {
+                let ident = &c.ident;
+                quote!(#ident)
+            }
+        })
+        .collect();
+
+    let slots: Vec<Ident> = (0..required.len())
+        .map(|i| format_ident!("__P{i}"))
+        .collect();
+    let fields: Vec<Ident> = (0..required.len())
+        .map(|i| format_ident!("__p{i}"))
+        .collect();
+    let transform = quote!(fn(#self_ty) -> #self_ty);
+
+    // `Name<own.., slots.., F>` with one slot swapped out.
+    let builder_ty = |slot_args: &[TokenStream2], apply: &TokenStream2| quote!(#name<#(#own_args,)* #(#slot_args,)* #apply>);
+    let slot_args: Vec<TokenStream2> = slots.iter().map(|s| quote!(#s)).collect();
+    // Rebuild the struct moving every field across, optionally replacing one.
+    let rebuild = |replace: Option<(usize, TokenStream2)>, apply: TokenStream2| {
+        let moved = fields.iter().enumerate().map(|(i, f)| match &replace {
+            Some((j, value)) if *j == i => quote!(#f: #value),
+            _ => quote!(#f: self.#f),
+        });
+        quote! {
+            #name {
+                #(#moved,)*
+                __apply: #apply,
+                __component: ::core::marker::PhantomData,
+            }
+        }
+    };
+
+    let mut setters = Vec::new();
+    for (i, req) in required.iter().enumerate() {
+        let attr = &req.name;
+        let mut args = slot_args.clone();
+        let value = rebuild(Some((i, quote!(value))), quote!(self.__apply));
+        let setter = if let Type::ImplTrait(bounds) = &req.ty {
+            let bounds = &bounds.bounds;
+            args[i] = quote!(__V);
+            let ret = builder_ty(&args, &quote!(__F));
+            quote! {
+                pub fn #attr<__V: #bounds>(self, value: __V) -> #ret { #value }
+            }
+        } else {
+            let ty = &req.ty;
+            args[i] = quote!(#ty);
+            let ret = builder_ty(&args, &quote!(__F));
+            quote! {
+                pub fn #attr(self, value: #ty) -> #ret { #value }
+            }
+        };
+        setters.push(setter);
+    }
+    let composed = builder_ty(&slot_args, &quote!(impl FnOnce(#self_ty) -> #self_ty));
+    for opt in &optional {
+        let attr = &opt.name;
+        let method = &opt.method.sig.ident;
+        let (_, _, method_where) = opt.method.sig.generics.split_for_impl();
+        let method_params = &opt.method.sig.generics.params;
+        let setter = match &opt.arg {
+            Some(ty) => {
+                let apply = rebuild(
+                    None,
+                    quote!(move |c: #self_ty| <#self_ty>::#method(apply(c), value)),
+                );
+                quote! {
+                    pub fn #attr<#method_params>(self, value: #ty) -> #composed #method_where {
+                        let apply = self.__apply;
+                        #apply
+                    }
+                }
+            }
+            None => {
+                let apply = rebuild(
+                    None,
+                    quote!(move |c: #self_ty| {
+                        let c = apply(c);
+                        if on { <#self_ty>::#method(c) } else { c }
+                    }),
+                );
+                quote! {
+                    pub fn #attr(self, on: bool) -> #composed {
+                        let apply = self.__apply;
+                        #apply
+                    }
+                }
+            }
+        };
+        setters.push(setter);
+    }
+
+    let build_bounds = required
+        .iter()
+        .zip(&slots)
+        .map(|(req, slot)| match &req.ty {
+            Type::ImplTrait(bounds) => {
+                let bounds = &bounds.bounds;
+                quote!(#slot: #bounds)
+            }
+            ty => quote!(#slot: #provided<#ty>),
+        });
+    let ctor_args = required
+        .iter()
+        .zip(&fields)
+        .map(|(req, field)| match &req.ty {
+            Type::ImplTrait(_) => quote!(self.#field),
+            ty => quote!(<_ as #provided<#ty>>::provide(self.#field)),
+        });
+    let initial_slots: Vec<TokenStream2> = slots.iter().map(|_| missing.clone()).collect();
+    let initial_ty = builder_ty(&initial_slots, &transform);
+    let generic_ty = builder_ty(&slot_args, &quote!(__F));
+    let initial_fields = fields.iter().map(|f| quote!(#f: #missing));
+    let own_params_iter = own_params.iter();
+    let struct_slots = slots.iter().map(|s| quote!(#s = #missing));
+    let own_for_impl = own_params.iter();
+    let own_for_build = own_params.iter();
+
+    let self_name = match &**self_ty {
+        Type::Path(p) => p
+            .path
+            .segments
+            .last()
+            .map_or_else(|| quote!(#self_ty).to_string(), |s| s.ident.to_string()),
+        _ => quote!(#self_ty).to_string(),
+    };
+    let example = required
+        .iter()
+        .map(|r| format!(" {}={{..}}", r.name))
+        .collect::<String>();
+    let mut doc = format!("`view!` tag for [`{self_name}`]: `<{name}{example} />`.");
+    let list = |names: Vec<String>| {
+        names
+            .iter()
+            .map(|n| format!("`{n}`"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    if !required.is_empty() {
+        doc.push_str(&format!(
+            "\n\nRequired attributes: {}.",
+            list(required.iter().map(|r| r.name.to_string()).collect())
+        ));
+    }
+    if !optional.is_empty() {
+        doc.push_str(&format!(
+            "\n\nOptional attributes: {}.",
+            list(optional.iter().map(|o| o.name.to_string()).collect())
+        ));
+    }
+    let build = quote_spanned!(name.span()=> __build);
+
+    Ok(quote! {
+        #block
+
+        #[doc = #doc]
+        #vis struct #name<#(#own_params_iter,)* #(#struct_slots,)* __F = #transform> #where_clause {
+            #(#fields: #slots,)*
+            __apply: __F,
+            __component: ::core::marker::PhantomData<fn() -> #self_ty>,
+        }
+
+        impl #impl_generics #initial_ty #where_clause {
+            #[doc(hidden)]
+            pub fn __view() -> Self {
+                #name {
+                    #(#initial_fields,)*
+                    __apply: |c| c,
+                    __component: ::core::marker::PhantomData,
+                }
+            }
+        }
+
+        impl<#(#own_for_impl,)* #(#slots,)* __F: FnOnce(#self_ty) -> #self_ty> #generic_ty
+        #where_clause
+        {
+            #(#setters)*
+        }
+
+        impl<#(#own_for_build,)* #(#build_bounds,)* __F: FnOnce(#self_ty) -> #self_ty> #generic_ty
+        #where_clause
+        {
+            #[doc(hidden)]
+            pub fn #build(self) -> #self_ty {
+                (self.__apply)(<#self_ty>::new(#(#ctor_args),*))
+            }
+        }
+    })
+}
+
+/// Public by-value `fn(self[, arg]) -> Self` methods, named by their
+/// attribute (`with_` stripped).
+fn builder_methods<'a>(methods: &[&'a ImplItemFn]) -> Vec<Optional<'a>> {
+    methods
+        .iter()
+        .filter(|f| matches!(f.vis, Visibility::Public(_)))
+        .filter_map(|f| {
+            let receiver = f.sig.receiver()?;
+            if !matches!(receiver.kind, syn::ReceiverKind::Value) || !returns_self(&f.sig.output) {
+                return None;
+            }
+            let args: Vec<&Type> = f
+                .sig
+                .inputs
+                .iter()
+                .filter_map(|input| match input {
+                    FnArg::Typed(arg) => Some(&*arg.ty),
+                    FnArg::Receiver(_) => None,
+                })
+                .collect();
+            let arg = match args.as_slice() {
+                [] => None,
+                [ty] => Some((*ty).clone()),
+                _ => return None,
+            };
+            let method = f.sig.ident.to_string();
+            let attr = method.strip_prefix("with_").unwrap_or(&method);
+            Some(Optional {
+                name: Ident::new(attr, f.sig.ident.span()),
+                method: f,
+                arg,
+            })
+        })
+        .collect()
+}
+
+fn returns_self(output: &ReturnType) -> bool {
+    matches!(output, ReturnType::Type(_, ty) if matches!(&**ty, Type::Path(p) if p.path.is_ident("Self")))
+}
+
+/// Keep the annotated item compiling when the attribute is rejected, so the
+/// only error shown is ours.
+pub(crate) fn with_item(error: syn::Error, item: TokenStream2) -> TokenStream2 {
+    let error = error.to_compile_error();
+    quote!(#error #item)
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand_str(attr: &str, item: &str) -> syn::Result<String> {
+        expand(attr.parse().unwrap(), item.parse().unwrap()).map(|t| t.to_string())
+    }
+
+    #[test]
+    fn constructor_params_and_builders_become_attributes() {
+        let tokens = expand_str(
+            "Badge",
+            "impl<T: Clone> BadgeComponent<T> {
+                pub fn new(text: impl Into<String>, count: usize) -> Self { todo!() }
+                pub fn with_color(self, color: Color) -> Self { self }
+                pub fn bold(mut self) -> Self { self }
+                pub fn set_text(&mut self, text: String) {}
+                fn with_secret(self, v: u8) -> Self { self }
+                pub fn with_pair(self, a: u8, b: u8) -> Self { self }
+            }",
+        )
+        .unwrap();
+        assert!(tokens.contains("pub struct Badge < T : Clone"), "{tokens}");
+        for setter in [
+            "pub fn text < __V : Into < String > > (self , value : __V)",
+            "pub fn count (self , value : usize)",
+            "pub fn color < > (self , value : Color)",
+            "pub fn bold (self , on : bool)",
+        ] {
+            assert!(tokens.contains(setter), "missing {setter}: {tokens}");
+        }
+        for skipped in ["fn set_text (self", "fn secret", "fn pair"] {
+            assert!(!tokens.contains(skipped), "{skipped}: {tokens}");
+        }
+        assert!(tokens.contains("Required attributes: `text`, `count`."));
+        assert!(tokens.contains("Optional attributes: `color`, `bold`."));
+    }
+
+    #[test]
+    fn misuse_is_reported() {
+        let err = expand_str("Badge", "impl Component for Badge {}").unwrap_err();
+        assert!(err.to_string().contains("inherent impl block"), "{err}");
+        let err =
+            expand_str("Badge", "impl Badge { pub fn build() -> Self { todo!() } }").unwrap_err();
+        assert!(
+            err.to_string().contains("needs a `new` constructor"),
+            "{err}"
+        );
+        let err = expand_str(
+            "Badge",
+            "impl Badge {
+                pub fn new(text: String) -> Self { todo!() }
+                pub fn with_text(self, text: String) -> Self { self }
+            }",
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("both a constructor parameter"),
+            "{err}"
+        );
+    }
+}
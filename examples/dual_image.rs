@@ -1,9 +1,9 @@
 use std::io;
 
 use term_wm::AppContext;
-use term_wm::SvgImageComponent;
 use term_wm::components::AppRootComponent;
 use term_wm::term_wm_app::TermWmApp;
+use term_wm::{ImageFit, PixelImageComponent};
 
 /// Default demo image, resolved against the crate root so it loads regardless
 /// of the current working directory.
@@ -18,19 +18,15 @@ fn main() -> io::Result<()> {
         paths.push(paths[0].clone());
     }
 
-    let mut app: TermWmApp<SvgImageComponent> =
+    let mut app: TermWmApp<PixelImageComponent> =
         TermWmApp::new_custom(AppContext::new("example", "0.0.0"));
 
-    let mut left = SvgImageComponent::new();
-    left.set_keep_aspect(true);
-    left.set_colorize(true);
+    let mut left = PixelImageComponent::new().with_fit(ImageFit::Fit);
     left.load_from_path(&paths[0])?;
     let left_key = app.open_window(AppRootComponent::Custom(left));
     app.set_window_title(left_key, "Left Image");
 
-    let mut right = SvgImageComponent::new();
-    right.set_keep_aspect(true);
-    right.set_colorize(true);
+    let mut right = PixelImageComponent::new().with_fit(ImageFit::Fit);
     right.load_from_path(&paths[1])?;
     let right_key = app.open_window(AppRootComponent::Custom(right));
     app.set_window_title(right_key, "Right Image");
@@ -2,9 +2,9 @@ pub use term_wm_core::components::NoopComponent;
 
 use term_wm_core::actions::TermWmAction;
 // Re-exported (not just imported) so `::term_wm::components::Component` and
-// `::term_wm::components::SelectionStatus` resolve for the umbrella path style
+// `::term_wm::components::SelectionStatus` (and `HostCursor`) resolve for the umbrella path style
 // of the `view!` macro (the module shadows `term_wm_core::components`).
-pub use term_wm_core::components::{Component, SelectionStatus};
+pub use term_wm_core::components::{Component, HostCursor, MessageComponent, SelectionStatus};
 use term_wm_core::impl_component_delegate;
 use term_wm_ui_facade::core_component::CoreWmComponent;
 
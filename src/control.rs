@@ -0,0 +1,553 @@
+//! Control mode, window-manager side.
+//!
+//! The internal-session listener in `main.rs` owns the gateway connection: it
+//! queues incoming [`ControlCommand`]s for the [`ControlBridge`] and publishes
+//! the [`ControlEvent`]s the bridge produces. The bridge itself runs on the
+//! event-loop thread once per rendered frame: it applies queued commands, then
+//! diffs the window manager against the previous frame.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crossbeam_channel::{Receiver, Sender};
+use slotmap::{Key, KeyData};
+use term_session::protocol::{
+    ControlCommand, ControlEvent, ControlWindow, ControlWindowId, OnChannelRenamed,
+    OnControlCommand, PublishControlEvents, PublishControlEventsRequest, RpcMethodPrebuffered,
+};
+use term_session::rpc_client::RpcIpcClient;
+use term_wm_core::actions::TermWmAction;
+use term_wm_core::components::Component;
+use term_wm_core::window::WindowKey;
+use term_wm_ui_components::default_shell_command;
+use term_wm_ui_facade::core_component::CoreWmComponent;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+
+use crate::components::AppRootComponent;
+use crate::term_wm_app::TermWmApp;
+use crate::unified_event_source::UnifiedEvent;
+
+/// How often an idle publisher checks that the gateway is still there.
+const LINK_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// Wire id of a window. Slotmap keys carry a generation, so ids of closed
+/// windows are never reused.
+pub fn window_id(key: WindowKey) -> ControlWindowId {
+    key.data().as_ffi()
+}
+
+fn window_key(id: ControlWindowId) -> WindowKey {
+    KeyData::from_ffi(id).into()
+}
+
+/// Window-manager state as of the last frame, for diffing.
+#[derive(Debug, Default)]
+pub struct ControlObserver {
+    /// Listed windows and their display titles, in display order.
+    titles: Vec<(WindowKey, String)>,
+    focused: Option<WindowKey>,
+    direct_input: HashMap<WindowKey, (bool, bool)>,
+    floating: HashMap<WindowKey, bool>,
+    /// Lowest notification id not reported yet (ids count up from zero).
+    next_notification: u64,
+}
+
+impl ControlObserver {
+    /// Append the events that turn the previous snapshot into the current
+    /// state of `app`, then remember the current state.
+    pub fn observe<C>(&mut self, app: &mut TermWmApp<C>, out: &mut Vec<ControlEvent>)
+    where
+        C: Component<TermWmAction> + 'static,
+    {
+        let wm = app.wm();
+        let titles = wm.window_titles();
+
+        for (key, _) in &self.titles {
+            if !titles.iter().any(|(k, _)| k == key) {
+                out.push(ControlEvent::WindowClosed {
+                    window: window_id(*key),
+                });
+                self.direct_input.remove(key);
+                self.floating.remove(key);
+            }
+        }
+        for (key, title) in &titles {
+            match self.titles.iter().find(|(k, _)| k == key) {
+                None => out.push(ControlEvent::WindowOpened {
+                    window: window_id(*key),
+                    title: title.clone(),
+                }),
+                Some((_, old)) if old != title => out.push(ControlEvent::TitleChanged {
+                    window: window_id(*key),
+                    title: title.clone(),
+                }),
+                Some(_) => {}
+            }
+            let mode = wm.direct_input_mode(*key);
+            let mode = (mode.keyboard, mode.mouse);
+            // Windows start in normal mode; only report departures from it.
+            let old = self.direct_input.insert(*key, mode).unwrap_or_default();
+            if old != mode {
+                out.push(ControlEvent::DirectInputChanged {
+                    window: window_id(*key),
+                    keyboard: mode.0,
+                    mouse: mode.1,
+                });
+            }
+            // Likewise windows start tiled.
+            let floating = wm.is_window_floating(*key);
+            if self.floating.insert(*key, floating).unwrap_or_default() != floating {
+                out.push(ControlEvent::FloatingChanged {
+                    window: window_id(*key),
+                    floating,
+                });
+            }
+        }
+
+        let focused = Some(wm.focused_window()).filter(|f| titles.iter().any(|(k, _)| k == f));
+        if focused != self.focused {
+            out.push(ControlEvent::FocusChanged {
+                window: focused.map(window_id),
+            });
+            self.focused = focused;
+        }
+
+        for notification in wm.notifications().renderable() {
+            if notification.id >= self.next_notification {
+                self.next_notification = notification.id + 1;
+                out.push(ControlEvent::Notification {
+                    id: notification.id,
+                    message: notification.message.to_string(),
+                });
+            }
+        }
+
+        self.titles = titles;
+    }
+}
+
+/// Every listed window with its geometry, in display order.
+pub fn list_windows<C>(app: &mut TermWmApp<C>) -> Vec<ControlWindow>
+where
+    C: Component<TermWmAction> + 'static,
+{
+    let wm = app.wm();
+    let focused = wm.focused_window();
+    wm.window_titles()
+        .into_iter()
+        .enumerate()
+        .map(|(index, (key, title))| {
+            let rect = wm.region_or_fallback(key, index);
+            ControlWindow {
+                window: window_id(key),
+                title,
+                focused: key == focused,
+                floating: wm.is_window_floating(key),
+                x: rect.x,
+                y: rect.y,
+                width: rect.width,
+                height: rect.height,
+            }
+        })
+        .collect()
+}
+
+/// Apply one command. `Err` carries the message for a
+/// [`ControlEvent::Error`].
+pub fn apply_command<C>(
+    app: &mut TermWmApp<C>,
+    command: ControlCommand,
+    out: &mut Vec<ControlEvent>,
+) -> Result<(), String>
+where
+    C: Component<TermWmAction> + 'static,
+{
+    let known = |app: &mut TermWmApp<C>, id: ControlWindowId| -> Result<WindowKey, String> {
+        let key = window_key(id);
+        if app.wm().window_titles().iter().any(|(k, _)| *k == key) {
+            Ok(key)
+        } else {
+            Err(format!("no such window: {id}"))
+        }
+    };
+    match command {
+        ControlCommand::Open { run, title } => {
+            let title = title.unwrap_or_else(|| format!("Shell {}", app.wm().window_count() + 1));
+            app.spawn_terminal_window(default_shell_command(), run, title)
+                .map_err(|e| format!("open failed: {e}"))?;
+        }
+        ControlCommand::Close { window } => {
+            let key = known(app, window)?;
+            app.wm().close_window(key);
+        }
+        ControlCommand::Focus { window } => {
+            let key = known(app, window)?;
+            app.wm().focus_window_key(key);
+        }
+        ControlCommand::SendInput { window, data } => {
+            let key = known(app, window)?;
+            match app.wm().component_for_key_mut(key) {
+                Some(AppRootComponent::Core(CoreWmComponent::Terminal(scroll_view))) => {
+                    scroll_view
+                        .content
+                        .borrow_mut()
+                        .write_bytes(data.as_bytes())
+                        .map_err(|e| format!("send_input failed: {e}"))?;
+                }
+                _ => return Err(format!("window {window} is not a terminal")),
+            }
+        }
+        ControlCommand::Resize {
+            window,
+            width,
+            height,
+        } => {
+            let key = known(app, window)?;
+            let mut rect = app.wm().region_or_fallback(key, 0);
+            rect.width = width;
+            rect.height = height;
+            app.wm().place_floating(key, rect);
+        }
+        ControlCommand::Move { window, x, y } => {
+            let key = known(app, window)?;
+            let mut rect = app.wm().region_or_fallback(key, 0);
+            rect.x = x;
+            rect.y = y;
+            app.wm().place_floating(key, rect);
+        }
+        ControlCommand::ListWindows => out.push(ControlEvent::Windows {
+            windows: list_windows(app),
+        }),
+    }
+    Ok(())
+}
+
+/// The event-loop end of the control link.
+pub struct ControlBridge {
+    commands: Receiver<ControlCommand>,
+    exits: Receiver<WindowKey>,
+    events: UnboundedSender<Vec<ControlEvent>>,
+    observer: ControlObserver,
+}
+
+impl ControlBridge {
+    /// `commands` come from the gateway listener, `exits` from
+    /// [`UnifiedEventSource::set_exit_observer`], and each frame's events go
+    /// out as one batch on `events`.
+    ///
+    /// [`UnifiedEventSource::set_exit_observer`]: crate::unified_event_source::UnifiedEventSource::set_exit_observer
+    pub fn new(
+        commands: Receiver<ControlCommand>,
+        exits: Receiver<WindowKey>,
+        events: UnboundedSender<Vec<ControlEvent>>,
+    ) -> Self {
+        Self {
+            commands,
+            exits,
+            events,
+            observer: ControlObserver::default(),
+        }
+    }
+
+    /// Apply queued commands and publish what changed. Returns `true` when a
+    /// command ran, so the caller can schedule a redraw to show its effect.
+    pub fn pump<C>(&mut self, app: &mut TermWmApp<C>) -> bool
+    where
+        C: Component<TermWmAction> + 'static,
+    {
+        let mut out = Vec::new();
+        for key in self.exits.try_iter() {
+            out.push(ControlEvent::ProcessExited {
+                window: window_id(key),
+            });
+        }
+        let mut applied = false;
+        while let Ok(command) = self.commands.try_recv() {
+            applied = true;
+            if let Err(message) = apply_command(app, command, &mut out) {
+                out.push(ControlEvent::Error { message });
+            }
+        }
+        self.observer.observe(app, &mut out);
+        if !out.is_empty() {
+            let _ = self.events.send(out);
+        }
+        applied
+    }
+}
+
+/// Route the gateway's `OnControlCommand` pushes into `commands` and wake the
+/// event loop so the bridge applies them promptly.
+pub async fn register_command_handler(
+    client: &RpcIpcClient,
+    commands: Sender<ControlCommand>,
+    wake: Sender<UnifiedEvent>,
+) {
+    use muxio_rpc_service_endpoint::RpcServiceEndpointInterface;
+    let result = client
+        .get_endpoint()
+        .register_prebuffered(OnControlCommand::METHOD_ID, move |payload, _ctx| {
+            let commands = commands.clone();
+            let wake = wake.clone();
+            async move {
+                let command = OnControlCommand::decode_request(&payload)
+                    .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+                let _ = commands.send(command);
+                let _ = wake.try_send(UnifiedEvent::Tick);
+                OnControlCommand::encode_response(())
+                    .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+            }
+        })
+        .await;
+    if let Err(e) = result {
+        tracing::error!("register OnControlCommand: {e:?}");
+    }
+}
+
+/// Keep `channel` (the listener's channel name) in step with renames
+/// pushed by the gateway; the event loop picks the new name up on the
+/// `Tick` sent to `wake`.
+pub async fn register_rename_handler(
+    client: &RpcIpcClient,
+    channel: Arc<Mutex<String>>,
+    wake: Sender<UnifiedEvent>,
+) {
+    use muxio_rpc_service_endpoint::RpcServiceEndpointInterface;
+    let result = client
+        .get_endpoint()
+        .register_prebuffered(OnChannelRenamed::METHOD_ID, move |payload, _ctx| {
+            let channel = Arc::clone(&channel);
+            let wake = wake.clone();
+            async move {
+                let req = OnChannelRenamed::decode_request(&payload)
+                    .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+                *channel.lock().unwrap_or_else(|e| e.into_inner()) = req.channel;
+                let _ = wake.try_send(UnifiedEvent::Tick);
+                OnChannelRenamed::encode_response(())
+                    .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+            }
+        })
+        .await;
+    if let Err(e) = result {
+        tracing::error!("register OnChannelRenamed: {e:?}");
+    }
+}
+
+/// Publish the bridge's event batches for `channel` until `client`
+/// disconnects. Batches produced while disconnected wait in `events`.
+pub async fn publish_events(
+    client: &RpcIpcClient,
+    channel: &Mutex<String>,
+    events: &mut UnboundedReceiver<Vec<ControlEvent>>,
+) {
+    use muxio_rpc_service_caller::prebuffered::RpcCallPrebuffered as _;
+    use term_session::rpc_client::RpcServiceCallerInterface as _;
+    while client.is_connected() {
+        let batch = tokio::select! {
+            batch = events.recv() => batch,
+            _ = tokio::time::sleep(LINK_CHECK_INTERVAL) => continue,
+        };
+        let Some(batch) = batch else {
+            // The bridge is gone (the WM is shutting down); just hold the
+            // connection like the input listener does.
+            tokio::time::sleep(LINK_CHECK_INTERVAL).await;
+            continue;
+        };
+        let request = PublishControlEventsRequest {
+            channel: channel.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+            events: batch,
+        };
+        if let Err(e) = PublishControlEvents::call(client, request).await {
+            tracing::debug!("PublishControlEvents failed: {e:?}");
+        }
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::NoopComponent;
+    use term_wm_core::app_context::AppContext;
+
+    fn app() -> TermWmApp<NoopComponent> {
+        TermWmApp::<NoopComponent>::new_custom(AppContext::new("test", "0.0.0"))
+    }
+
+    #[test]
+    fn observer_reports_open_title_focus_and_close() {
+        let mut app = app();
+        let mut observer = ControlObserver::default();
+        let mut out = Vec::new();
+        observer.observe(&mut app, &mut out);
+        assert!(out.is_empty(), "system windows start hidden: {out:?}");
+
+        let key = app.open_window(AppRootComponent::Custom(NoopComponent));
+        app.wm().set_window_title(key, "one");
+        observer.observe(&mut app, &mut out);
+        assert_eq!(
+            out,
+            vec![
+                ControlEvent::WindowOpened {
+                    window: window_id(key),
+                    title: "one".into()
+                },
+                ControlEvent::FocusChanged {
+                    window: Some(window_id(key))
+                },
+            ]
+        );
+
+        out.clear();
+        observer.observe(&mut app, &mut out);
+        assert!(out.is_empty(), "no change, no events: {out:?}");
+
+        app.wm().set_window_title(key, "two");
+        app.wm().close_window(key);
+        observer.observe(&mut app, &mut out);
+        assert_eq!(
+            out,
+            vec![
+                ControlEvent::WindowClosed {
+                    window: window_id(key)
+                },
+                ControlEvent::FocusChanged { window: None },
+            ]
+        );
+    }
+
+    #[test]
+    fn observer_reports_each_notification_once() {
+        let mut app = app();
+        let mut observer = ControlObserver::default();
+        let mut out = Vec::new();
+        let id = app
+            .wm()
+            .push_notification("saved", std::time::Duration::from_secs(5));
+        observer.observe(&mut app, &mut out);
+        observer.observe(&mut app, &mut out);
+        assert_eq!(
+            out,
+            vec![ControlEvent::Notification {
+                id,
+                message: "saved".into()
+            }]
+        );
+    }
+
+    #[test]
+    fn commands_focus_move_and_close_windows() {
+        let mut app = app();
+        let first = app.open_window(AppRootComponent::Custom(NoopComponent));
+        let second = app.open_window(AppRootComponent::Custom(NoopComponent));
+        let mut out = Vec::new();
+
+        apply_command(
+            &mut app,
+            ControlCommand::Focus {
+                window: window_id(first),
+            },
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(app.wm().focused_window(), first);
+
+        apply_command(
+            &mut app,
+            ControlCommand::Move {
+                window: window_id(second),
+                x: 3,
+                y: 4,
+            },
+            &mut out,
+        )
+        .unwrap();
+        assert!(app.wm().is_window_floating(second));
+
+        apply_command(
+            &mut app,
+            ControlCommand::Close {
+                window: window_id(second),
+            },
+            &mut out,
+        )
+        .unwrap();
+        let err = apply_command(
+            &mut app,
+            ControlCommand::Focus {
+                window: window_id(second),
+            },
+            &mut out,
+        )
+        .unwrap_err();
+        assert!(err.contains("no such window"), "{err}");
+
+        apply_command(&mut app, ControlCommand::ListWindows, &mut out).unwrap();
+        match out.as_slice() {
+            [ControlEvent::Windows { windows }] => {
+                assert_eq!(windows.len(), 1);
+                assert_eq!(windows[0].window, window_id(first));
+                assert!(windows[0].focused);
+            }
+            other => panic!("expected one listing, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn resizing_a_tiled_window_reports_that_it_floated() {
+        let mut app = app();
+        let key = app.open_window(AppRootComponent::Custom(NoopComponent));
+        let mut observer = ControlObserver::default();
+        let mut out = Vec::new();
+        observer.observe(&mut app, &mut out);
+        assert!(!app.wm().is_window_floating(key));
+        out.clear();
+
+        apply_command(
+            &mut app,
+            ControlCommand::Resize {
+                window: window_id(key),
+                width: 20,
+                height: 8,
+            },
+            &mut out,
+        )
+        .unwrap();
+        observer.observe(&mut app, &mut out);
+        assert_eq!(
+            out,
+            vec![ControlEvent::FloatingChanged {
+                window: window_id(key),
+                floating: true
+            }]
+        );
+    }
+
+    #[test]
+    fn bridge_reports_exits_before_the_resulting_close() {
+        let mut app = app();
+        let key = app.open_window(AppRootComponent::Custom(NoopComponent));
+        let (_cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
+        let (exit_tx, exit_rx) = crossbeam_channel::unbounded();
+        let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut bridge = ControlBridge::new(cmd_rx, exit_rx, event_tx);
+        bridge.pump(&mut app);
+        event_rx.try_recv().unwrap();
+
+        exit_tx.send(key).unwrap();
+        app.wm().close_window(key);
+        assert!(!bridge.pump(&mut app));
+        let batch = event_rx.try_recv().unwrap();
+        assert_eq!(
+            &batch[..2],
+            &[
+                ControlEvent::ProcessExited {
+                    window: window_id(key)
+                },
+                ControlEvent::WindowClosed {
+                    window: window_id(key)
+                },
+            ]
+        );
+    }
+}
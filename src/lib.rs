@@ -8,8 +8,10 @@ pub use term_wm_core::events::Event;
 pub use term_wm_core::*;
 pub use term_wm_render::RenderBackend;
 pub use term_wm_ui_components::*;
-pub use term_wm_view::view;
+pub use term_wm_view::{view, view_tag};
 pub mod components;
+#[cfg(feature = "session-persistence")]
+pub mod control;
 pub mod logging;
 pub mod prelude;
 pub mod term_wm_app;
@@ -19,10 +21,11 @@ pub use term_wm_console::widget_adapter::{StatefulWidgetAdapter, WidgetAdapter};
 use ratatui::prelude::Widget;
 use term_wm_console::RatatuiBackend;
 use term_wm_console::draw_plan_renderer::{
-    ColorConvert, DrawPlanRenderer, composite_window, overlay_shadow_data, render_cursor_overlay,
-    render_drop_shadow, render_ghost_preview, render_handles_masked, render_overlays,
-    render_panels, render_resize_outline, row_has_content_in_range,
+    ColorConvert, DrawPlanRenderer, overlay_shadow_data, render_cursor_overlay, render_drop_shadow,
+    render_ghost_preview, render_handles_masked, render_overlays, render_panels,
+    render_resize_outline, render_window_surface, row_has_content_in_range,
 };
+use term_wm_console::window_damage::WindowSignature;
 pub use term_wm_core::actions::TermWmAction;
 pub use term_wm_core::components::{
     Component, NoopComponent, Overlay, SelectionStatus, WmComponent,
@@ -70,10 +73,16 @@ pub fn render_app<
     render_overlays(backend, wm);
     render_notification_hitboxes(backend, wm, draw_plan);
 
+    // Screenshots capture the finished frame minus the mouse cursor block.
+    if let Some(request) = wm.take_screenshot_request() {
+        export_screenshot(backend, wm, renderer, request);
+    }
+
     // Cursor overlay — MUST be last (highest Z-order) so it paints over
     // all previously rendered content including overlays and chrome.
     if let Some(rb) = backend.as_any_mut().downcast_mut::<RatatuiBackend>() {
         render_cursor_overlay(&mut rb.buffer, wm, &wm.config().theme);
+        rb.host_cursor = wm.focused_host_cursor();
     }
 }
 
@@ -157,10 +166,16 @@ fn render_window_regions<C, L, O>(
     L: WmComponent,
     O: Overlay<TermWmAction>,
 {
-    // Take the renderer's persistent scratch buffer — resized per window,
-    // returned to the renderer after the loop.  No Buffer::empty allocations
-    // in steady state.
-    let mut scratch_buf = renderer.take_scratch();
+    let Some(frame_area) = backend
+        .as_any_mut()
+        .downcast_mut::<RatatuiBackend>()
+        .map(|rb| rb.buffer.area)
+    else {
+        return;
+    };
+    // Windows are drawn into their own cached buffers and stacked into the
+    // renderer's window layer; clean windows are not drawn at all.
+    renderer.window_damage_mut().begin_frame(frame_area);
     let plan_regions = draw_plan.regions();
     for (i, region) in plan_regions.iter().enumerate() {
         // Skip hidden regions (used for monocle mode culling)
@@ -208,19 +223,35 @@ fn render_window_regions<C, L, O>(
                 let title = all_titles.get(key).map(String::as_str).unwrap_or("");
                 let borders_enabled = wm.window_borders_enabled(*key);
                 let header_enabled = wm.window_header_enabled(*key);
+                let label = wm.window_label(*key).map(str::to_string);
                 let win_ctx = term_wm_console::draw_plan_renderer::ChromeCtx {
                     title,
                     focused,
                     floating,
                     hover_pos: wm.hover_pos(),
-                    theme: wm.config().theme,
+                    theme: wm.config().theme.clone(),
                     wm_buttons: wm.window_management_buttons_for(*key),
                     borders_enabled,
                     header_enabled,
+                    border_style: wm.config().border_style,
+                    title_alignment: wm.config().title_alignment,
+                    accent: wm.window_accent(*key),
+                    label: label.as_deref(),
                 };
+                let signature =
+                    WindowSignature::new(&surface, &win_ctx, wm.window_damage_epoch(*key));
+                let needs_render = wm.component_for_key(*key).is_none_or(|c| c.needs_render());
+                if !needs_render
+                    && let Some(hitboxes) = renderer.window_damage_mut().reuse(*key, &signature)
+                {
+                    wm.hitbox_registry_mut().merge(hitboxes);
+                    continue;
+                }
+                let theme = win_ctx.theme.clone();
+                let mut window_buf = renderer.window_damage_mut().take_buffer(*key);
+                let mut content_hb = None;
                 let content_hitbox_id = HitboxId::new();
-                let (_chrome_return, chrome_hb) = composite_window(
-                    backend,
+                let (_chrome_return, chrome_hb) = render_window_surface(
                     &surface,
                     *key,
                     content_hitbox_id,
@@ -244,13 +275,19 @@ fn render_window_regions<C, L, O>(
                             let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
                                 component.render(backend, content_bounds, &ctx, &mut local_hb);
                             }));
-                            wm.hitbox_registry_mut().merge(local_hb);
+                            content_hb = Some(local_hb);
                         }
                     },
-                    &mut scratch_buf,
+                    &mut window_buf,
                 );
-                // Merge chrome hitboxes (including content hitbox) into main registry
-                wm.hitbox_registry_mut().merge(chrome_hb);
+                // Content hitboxes first, then chrome (including the content
+                // hitbox); cached with the frame for when it is reused.
+                let mut hitboxes = content_hb.unwrap_or_default();
+                hitboxes.merge(chrome_hb);
+                wm.hitbox_registry_mut().merge(hitboxes.clone());
+                renderer
+                    .window_damage_mut()
+                    .store(*key, signature, window_buf, hitboxes, &theme);
             }
             // Notification rendering deferred to after tiling handles
             term_wm_core::draw_plan::RegionType::Notification(_) => {}
@@ -272,7 +309,9 @@ fn render_window_regions<C, L, O>(
             }
         }
     }
-    renderer.put_scratch(scratch_buf);
+    if let Some(rb) = backend.as_any_mut().downcast_mut::<RatatuiBackend>() {
+        renderer.window_damage_mut().finish_frame(&mut rb.buffer);
+    }
 }
 
 #[inline]
@@ -635,6 +674,48 @@ fn render_notification_toasts(
     }
 }
 
+/// Save the composed frame, or the requested window's cached buffer, and
+/// report the outcome back to the window manager for a toast.
+fn export_screenshot<C, L, O>(
+    backend: &mut dyn term_wm_render::RenderBackend,
+    wm: &mut WindowManager<C, L, O>,
+    renderer: &DrawPlanRenderer,
+    request: term_wm_core::screenshot::ScreenshotRequest,
+) where
+    C: Component<TermWmAction> + 'static,
+    L: WmComponent,
+    O: Overlay<TermWmAction>,
+{
+    let Some(rb) = backend.as_any_mut().downcast_mut::<RatatuiBackend>() else {
+        return;
+    };
+    let buffer = match request.window {
+        None => Some(&rb.buffer),
+        Some(key) => renderer.window_damage().window_buffer(key),
+    };
+    let error = match (buffer, wm.screenshot_dir()) {
+        (None, _) => std::io::Error::new(std::io::ErrorKind::NotFound, "window is not on screen"),
+        (Some(_), None) => std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!(
+                "no screenshot directory; set {}",
+                term_wm_config::SCREENSHOT_DIR_ENV_VAR
+            ),
+        ),
+        (Some(buffer), Some(dir)) => {
+            // Only the copy happens here; encoding and writing run off the
+            // render thread.
+            let buffer = buffer.clone();
+            let theme = wm.config().theme.clone();
+            wm.export_screenshot_in_background(move || {
+                term_wm_console::screenshot::save(&buffer, &theme, request.format, &dir)
+            });
+            return;
+        }
+    };
+    wm.finish_screenshot(Err(error));
+}
+
 #[inline]
 fn render_overlay_drop_shadows<C, L, O>(
     backend: &mut dyn term_wm_render::RenderBackend,
@@ -650,7 +731,7 @@ fn render_overlay_drop_shadows<C, L, O>(
     // Render overlay drop shadows before overlays themselves
     use term_wm_console::RatatuiBackend;
     if let Some(rb) = backend.as_any_mut().downcast_mut::<RatatuiBackend>() {
-        let theme = wm.config().theme;
+        let theme = wm.config().theme.clone();
         let mut tmp_mask = std::mem::take(&mut rb.mask_buffer);
         let buf_len = rb.buffer.content.len();
         if tmp_mask.len() < buf_len {
@@ -13,7 +13,9 @@ use term_wm::io::RenderTarget;
 use term_wm::runner::WindowManagerHost;
 use term_wm::term_wm_app::TermWmApp;
 use term_wm::unified_event_source::{UnifiedEvent, UnifiedEventSource};
+use term_wm_console::TerminalCapabilities;
 use term_wm_console::console_render_target::ConsoleRenderTarget;
+use term_wm_core::chrome::{BorderStyle, TitleAlignment};
 use term_wm_core::components::Component;
 use term_wm_core::events::Event;
 use term_wm_core::wm_config::WmConfig;
@@ -36,6 +38,26 @@ struct Cli {
     #[arg(long = "scrollback", default_value_t = term_wm_core::constants::DEFAULT_SCROLLBACK_LEN)]
     scrollback: usize,
 
+    /// Directory "Export Screenshot" writes to (default: $TERM_WM_SCREENSHOT_DIR, else ~/.local/share/term-wm/screenshots).
+    #[arg(long = "screenshot-dir", value_name = "DIR")]
+    screenshot_dir: Option<std::path::PathBuf>,
+
+    /// Window border set: standard, plain, rounded, double, heavy, ascii or none. Only takes effect on new sessions.
+    #[arg(long = "border-style", value_name = "STYLE", default_value_t = BorderStyle::default())]
+    border_style: BorderStyle,
+
+    /// Title placement in window headers: left, center or right. Only takes effect on new sessions.
+    #[arg(long = "title-align", value_name = "ALIGN", default_value_t = TitleAlignment::default())]
+    title_align: TitleAlignment,
+
+    /// Blank cells between the tiled area and the screen edge (default 0). Only takes effect on new sessions.
+    #[arg(long = "outer-gap", value_name = "CELLS", default_value_t = 0)]
+    outer_gap: u16,
+
+    /// Cells between neighbouring tiles, at least 1 for the resize handle (default 1). Only takes effect on new sessions.
+    #[arg(long = "inner-gap", value_name = "CELLS", default_value_t = 1)]
+    inner_gap: u16,
+
     /// Command to run in a window; repeatable, one window per `--run`. Only takes effect on new sessions.
     #[arg(short = 'r', long = "run", value_name = "CMD", action = clap::ArgAction::Append)]
     run_cmds: Vec<String>,
@@ -69,6 +91,11 @@ struct Cli {
     #[arg(long = "list-channels")]
     list_channels: bool,
 
+    /// Control the workspace's running term-wm instead of attaching: print its
+    /// events as JSON lines and read JSON commands from stdin.
+    #[arg(long = "control")]
+    control: bool,
+
     /// Force stop daemon or kill channels even if sessions/participants are active
     #[arg(long = "force", short = 'f')]
     force: bool,
@@ -122,6 +149,22 @@ fn build_inner_command(exe: String, workspace: &str, cli: &Cli) -> Vec<String> {
         inner_cmd.push("--scrollback".to_string());
         inner_cmd.push(cli.scrollback.to_string());
     }
+    if cli.border_style != BorderStyle::default() {
+        inner_cmd.push("--border-style".to_string());
+        inner_cmd.push(cli.border_style.to_string());
+    }
+    if cli.title_align != TitleAlignment::default() {
+        inner_cmd.push("--title-align".to_string());
+        inner_cmd.push(cli.title_align.to_string());
+    }
+    if cli.outer_gap != 0 {
+        inner_cmd.push("--outer-gap".to_string());
+        inner_cmd.push(cli.outer_gap.to_string());
+    }
+    if cli.inner_gap != 1 {
+        inner_cmd.push("--inner-gap".to_string());
+        inner_cmd.push(cli.inner_gap.to_string());
+    }
     for run_cmd in &cli.run_cmds {
         inner_cmd.push("--run".to_string());
         inner_cmd.push(run_cmd.clone());
@@ -201,6 +244,13 @@ fn run() -> io::Result<()> {
         return term_session::print_list();
     }
 
+    // 0c. Control mode for the workspace's WM
+    #[cfg(feature = "session-persistence")]
+    if cli.control && term_wm_config::runtime::session_persistence_enabled() {
+        let channel = term_session::ChannelName::session(&workspace).to_string();
+        return term_session::control::run_control(&channel);
+    }
+
     // 1. Standalone daemon mode
     #[cfg(feature = "session-persistence")]
     if cli.daemon && term_wm_config::runtime::session_persistence_enabled() {
@@ -215,23 +265,35 @@ fn run() -> io::Result<()> {
     // 2. Headless client mode (no WM chrome)
     #[cfg(feature = "session-persistence")]
     if cli.no_wm && term_wm_config::runtime::session_persistence_enabled() {
-        let socket = term_session::auto_spawn::connect_or_spawn_server(None)?;
+        let mut socket = term_session::auto_spawn::connect_or_spawn_server(None)?;
         let channel = term_session::ChannelName::session(&workspace).to_string();
-        return term_session::client::run_session(
-            &socket,
-            &channel,
-            &cli.cmds,
-            cli.allow_nested,
-            "term-wm",
-        )
-        .map(|_| ());
+        loop {
+            match term_session::client::run_session(
+                &socket,
+                &channel,
+                &cli.cmds,
+                cli.allow_nested,
+                "term-wm",
+                None,
+            ) {
+                Err(e) if term_session::client::is_gateway_upgrade(&e) => {
+                    socket = term_session::auto_spawn::wait_for_gateway(
+                        term_session::auto_spawn::GATEWAY_UPGRADE_RECONNECT_TIMEOUT,
+                    )?;
+                }
+                result => return result.map(|_| ()),
+            }
+        }
     }
 
     // 3. Outer launcher with workspace rebind loop
     #[cfg(feature = "session-persistence")]
     if !cli.internal_session && term_wm_config::runtime::session_persistence_enabled() {
-        let socket_path = term_session::auto_spawn::connect_or_spawn_server(None)?;
+        let mut socket_path = term_session::auto_spawn::connect_or_spawn_server(None)?;
         let mut current_workspace = workspace.clone();
+        // Only this process sees the host terminal: probe it once and hand
+        // the result to the inner window manager on every attach.
+        let host_display = TerminalCapabilities::detect().host_display();
 
         loop {
             let clean_workspace =
@@ -249,12 +311,20 @@ fn run() -> io::Result<()> {
                 &inner_cmd,
                 cli.allow_nested,
                 "term-wm",
+                Some(host_display),
             ) {
                 Ok(Some(target_channel)) => {
                     current_workspace = target_channel;
                     continue;
                 }
                 Ok(None) => return Ok(()),
+                // Same workspace, new daemon: the inner WM kept running.
+                Err(e) if term_session::client::is_gateway_upgrade(&e) => {
+                    socket_path = term_session::auto_spawn::wait_for_gateway(
+                        term_session::auto_spawn::GATEWAY_UPGRADE_RECONNECT_TIMEOUT,
+                    )?;
+                    continue;
+                }
                 Err(e) => {
                     if term_session::client::is_nested_session_fatal(&e) {
                         return Err(e);
@@ -284,6 +354,14 @@ fn run() -> io::Result<()> {
     #[cfg(feature = "session-persistence")]
     let _rt_guard = rt.enter();
 
+    // Probe the host terminal before the console reader thread claims stdin.
+    // Inside the gateway our stdio is its PTY, not the host terminal: the
+    // outer launcher reports what it probed once we subscribe.
+    let capabilities = if cli.internal_session {
+        TerminalCapabilities::from_env()
+    } else {
+        TerminalCapabilities::detect()
+    };
     let (mut event_source, event_owner) = UnifiedEventSource::new(cli.internal_session)?;
     #[cfg(feature = "session-persistence")]
     let pty_wakeup_tx = event_source.pty_wakeup_tx();
@@ -291,63 +369,112 @@ fn run() -> io::Result<()> {
     // For internal sessions, spawn a Muxio listener that receives structured
     // events from the server and pipes them into the event source via pty_wakeup_tx.
     #[cfg(feature = "session-persistence")]
+    let mut control_bridge = None;
+    #[cfg(feature = "session-persistence")]
+    let mut channel_name = None;
+    #[cfg(feature = "session-persistence")]
     if cli.internal_session && term_wm_config::runtime::session_persistence_enabled() {
         let tx = pty_wakeup_tx.clone();
-        let channel = term_session::ChannelName::session(&workspace).to_string();
-        let socket_path = term_session::auto_spawn::connect_or_spawn_server(None)?;
+        // Shared with the app: the gateway may rename the channel under us.
+        let channel = Arc::new(std::sync::Mutex::new(
+            term_session::ChannelName::session(&workspace).to_string(),
+        ));
+        channel_name = Some(Arc::clone(&channel));
+        let mut socket_path = term_session::auto_spawn::connect_or_spawn_server(None)?;
+        // Control mode: commands from the gateway queue up for the bridge,
+        // which runs on the event loop and hands back event batches.
+        let (control_cmd_tx, control_cmd_rx) = crossbeam_channel::unbounded();
+        let (control_event_tx, mut control_event_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (exit_tx, exit_rx) = crossbeam_channel::unbounded();
+        event_source.set_exit_observer(exit_tx);
+        control_bridge = Some(term_wm::control::ControlBridge::new(
+            control_cmd_rx,
+            exit_rx,
+            control_event_tx,
+        ));
         rt.spawn(async move {
             use term_session::protocol::OnAttributedInput;
             use term_session::protocol::RpcMethodPrebuffered;
             use term_session::protocol::SubscribeInternalInputRequest;
 
-            let client = match term_session::rpc_client::RpcIpcClient::new(&socket_path).await {
-                Ok(c) => std::sync::Arc::new(c),
-                Err(e) => {
-                    tracing::error!("Failed to connect for attributed input: {e:?}");
+            // Reconnect after a gateway upgrade: the replacement daemon knows
+            // nothing about this listener until it subscribes again.
+            loop {
+                let client = match term_session::rpc_client::RpcIpcClient::new(&socket_path).await {
+                    Ok(c) => std::sync::Arc::new(c),
+                    Err(e) => {
+                        tracing::error!("Failed to connect for attributed input: {e:?}");
+                        return;
+                    }
+                };
+                // Register handler BEFORE subscribing to avoid race
+                {
+                    let tx = tx.clone();
+                    use muxio_rpc_service_endpoint::RpcServiceEndpointInterface;
+                    client
+                        .get_endpoint()
+                        .register_prebuffered(OnAttributedInput::METHOD_ID, move |payload, _ctx| {
+                            let tx = tx.clone();
+                            async move {
+                                let req =
+                                    OnAttributedInput::decode_request(&payload).map_err(|e| {
+                                        Box::new(e) as Box<dyn std::error::Error + Send + Sync>
+                                    })?;
+                                // Route through main channel — wakes poll() immediately
+                                let _ = tx.try_send(UnifiedEvent::Input {
+                                    conn_id: Some(req.conn_id),
+                                    event: req.event,
+                                });
+                                OnAttributedInput::encode_response(()).map_err(|e| {
+                                    Box::new(e) as Box<dyn std::error::Error + Send + Sync>
+                                })
+                            }
+                        })
+                        .await
+                        .expect("register OnAttributedInput");
+                }
+                term_wm::control::register_command_handler(
+                    &client,
+                    control_cmd_tx.clone(),
+                    tx.clone(),
+                )
+                .await;
+                term_wm::control::register_rename_handler(
+                    &client,
+                    Arc::clone(&channel),
+                    tx.clone(),
+                )
+                .await;
+                // Subscribe
+                use muxio_rpc_service_caller::prebuffered::RpcCallPrebuffered as _;
+                let client_ref: &term_session::rpc_client::RpcIpcClient = &client;
+                let current = channel.lock().unwrap_or_else(|e| e.into_inner()).clone();
+                if let Err(e) = term_session::protocol::SubscribeInternalInput::call(
+                    client_ref,
+                    SubscribeInternalInputRequest { channel: current },
+                )
+                .await
+                {
+                    tracing::error!("SubscribeInternalInput failed: {e:?}");
                     return;
                 }
-            };
-            // Register handler BEFORE subscribing to avoid race
-            {
-                let tx = tx.clone();
-                use muxio_rpc_service_endpoint::RpcServiceEndpointInterface;
-                client
-                    .get_endpoint()
-                    .register_prebuffered(OnAttributedInput::METHOD_ID, move |payload, _ctx| {
-                        let tx = tx.clone();
-                        async move {
-                            let req = OnAttributedInput::decode_request(&payload).map_err(|e| {
-                                Box::new(e) as Box<dyn std::error::Error + Send + Sync>
-                            })?;
-                            // Route through main channel — wakes poll() immediately
-                            let _ = tx.try_send(UnifiedEvent::Input {
-                                conn_id: Some(req.conn_id),
-                                event: req.event,
-                            });
-                            OnAttributedInput::encode_response(()).map_err(|e| {
-                                Box::new(e) as Box<dyn std::error::Error + Send + Sync>
-                            })
-                        }
-                    })
-                    .await
-                    .expect("register OnAttributedInput");
-            }
-            // Subscribe
-            use muxio_rpc_service_caller::prebuffered::RpcCallPrebuffered as _;
-            let client_ref: &term_session::rpc_client::RpcIpcClient = &client;
-            if let Err(e) = term_session::protocol::SubscribeInternalInput::call(
-                client_ref,
-                SubscribeInternalInputRequest { channel },
-            )
-            .await
-            {
-                tracing::error!("SubscribeInternalInput failed: {e:?}");
-                return;
-            }
-            tracing::info!("Attributed input listener subscribed");
-            // Keep the connection alive so the endpoint keeps processing RPCs.
-            loop {
-                tokio::time::sleep(std::time::Duration::from_secs(3600)).await;
+                tracing::info!("Attributed input listener subscribed");
+                // Keep the connection alive so the endpoint keeps processing
+                // RPCs, publishing control events, until the gateway goes away.
+                term_wm::control::publish_events(&client, &channel, &mut control_event_rx).await;
+                socket_path = match tokio::task::spawn_blocking(|| {
+                    term_session::auto_spawn::wait_for_gateway(
+                        term_session::auto_spawn::GATEWAY_UPGRADE_RECONNECT_TIMEOUT,
+                    )
+                })
+                .await
+                {
+                    Ok(Ok(socket)) => socket,
+                    _ => {
+                        tracing::error!("Gateway gone; attributed input listener stopped");
+                        return;
+                    }
+                };
             }
         });
     }
@@ -355,6 +482,11 @@ fn run() -> io::Result<()> {
 
     let config = WmConfig {
         scrollback_lines: cli.scrollback,
+        screenshot_dir: cli.screenshot_dir.clone(),
+        border_style: cli.border_style,
+        title_alignment: cli.title_align,
+        outer_gap: cli.outer_gap,
+        inner_gap: cli.inner_gap,
         ..Default::default()
     };
 
@@ -367,7 +499,20 @@ fn run() -> io::Result<()> {
         event_owner,
     )?;
 
-    let mut output = ConsoleRenderTarget::new()?;
+    #[cfg(feature = "session-persistence")]
+    {
+        app.control = control_bridge;
+        app.channel_name = channel_name;
+    }
+
+    app.inner.apply_terminal_capabilities(&capabilities);
+    let mut output =
+        ConsoleRenderTarget::with_writer(io::stdout())?.with_capabilities(capabilities);
+    if cli.internal_session {
+        // Nothing reads the daemon PTY's replies; the gateway forwards the
+        // host colors the outer client reports instead.
+        output = output.without_host_color_queries();
+    }
     output.enter()?;
     let result = app.run_with(&mut output, &mut event_source);
     output.exit()?;
@@ -378,7 +523,7 @@ fn run() -> io::Result<()> {
 /// management, debug window, and system overlays.
 struct App {
     inner: TermWmApp,
-    #[expect(dead_code)]
+    #[cfg_attr(not(feature = "session-persistence"), expect(dead_code))]
     pty_wakeup_tx: Sender<UnifiedEvent>,
     /// Current workspace name for IPC source_channel identification.
     #[allow(dead_code, reason = "used only with session-persistence")]
@@ -386,6 +531,13 @@ struct App {
     /// Shared event attribution — updated by UnifiedEventSource, read by handle_custom_action.
     #[cfg_attr(not(feature = "session-persistence"), allow(dead_code))]
     event_owner: std::sync::Arc<std::sync::Mutex<Option<usize>>>,
+    /// Control-mode link; `Some` only inside a gateway-managed session.
+    #[cfg(feature = "session-persistence")]
+    control: Option<term_wm::control::ControlBridge>,
+    /// Name of this session's channel as last reported by the gateway;
+    /// `Some` only inside a gateway-managed session.
+    #[cfg(feature = "session-persistence")]
+    channel_name: Option<Arc<std::sync::Mutex<String>>>,
 }
 
 /// Build the window manager the way the `term-wm` binary runs it: full system
@@ -447,6 +599,10 @@ impl App {
             pty_wakeup_tx,
             current_workspace: workspace,
             event_owner,
+            #[cfg(feature = "session-persistence")]
+            control: None,
+            #[cfg(feature = "session-persistence")]
+            channel_name: None,
         };
 
         // One window per command (shell + the command as input), then default
@@ -477,6 +633,32 @@ impl App {
         term_wm::runner::run_with_defaults(output, driver, self)
     }
 
+    /// Adopt `workspace` as this session's workspace name, e.g. after the
+    /// channel was renamed.
+    #[cfg(feature = "session-persistence")]
+    fn set_workspace(&mut self, workspace: String) {
+        if let Some(shared) = &self.channel_name {
+            *shared.lock().unwrap_or_else(|e| e.into_inner()) =
+                term_session::ChannelName::session(&workspace).to_string();
+        }
+        self.inner.set_current_workspace(workspace.clone());
+        self.current_workspace = workspace;
+    }
+
+    /// Pick up a rename of this session's channel made elsewhere (e.g.
+    /// `term-session rename`).
+    #[cfg(feature = "session-persistence")]
+    fn sync_renamed_workspace(&mut self) {
+        let Some(shared) = &self.channel_name else {
+            return;
+        };
+        let channel = shared.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        let workspace = term_session::ChannelName::parse_workspace(&channel);
+        if workspace != self.current_workspace {
+            self.set_workspace(workspace.to_string());
+        }
+    }
+
     fn spawn_terminal_with_command(
         &mut self,
         cmd: portable_pty::CommandBuilder,
@@ -550,6 +732,27 @@ impl WindowManagerHost<AppRootComponent, LayerComponent, OverlayComponent> for A
                 true
             }
             #[cfg(feature = "session-persistence")]
+            TermWmAction::RenameWorkspace(target_ws) => {
+                let source_channel =
+                    term_session::ChannelName::session(&self.current_workspace).to_string();
+                let target_channel = term_session::ChannelName::session(target_ws).to_string();
+                let message = match term_session::rename_channel(&source_channel, &target_channel) {
+                    Ok(()) => {
+                        self.set_workspace(target_ws.clone());
+                        self.inner.refresh_workspace_cache();
+                        format!("Renamed workspace to: {target_ws}")
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to rename workspace: {e}");
+                        format!("Rename failed: {e}")
+                    }
+                };
+                self.inner
+                    .wm()
+                    .push_notification(message, std::time::Duration::from_secs(3));
+                true
+            }
+            #[cfg(feature = "session-persistence")]
             TermWmAction::DetachCurrentClient => {
                 if let Some(conn_id) = *self
                     .event_owner
@@ -613,7 +816,17 @@ impl WindowManagerHost<AppRootComponent, LayerComponent, OverlayComponent> for A
     }
 
     fn render(&mut self, backend: &mut dyn term_wm_render::RenderBackend) {
+        #[cfg(feature = "session-persistence")]
+        self.sync_renamed_workspace();
         self.inner.render_app(backend);
+        // After drawing, so titles refreshed this frame are reported. A
+        // command that changed the layout needs another frame to show it.
+        #[cfg(feature = "session-persistence")]
+        if let Some(control) = &mut self.control
+            && control.pump(&mut self.inner)
+        {
+            let _ = self.pty_wakeup_tx.try_send(UnifiedEvent::Tick);
+        }
     }
 }
 
@@ -716,6 +929,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn build_inner_command_forwards_chrome_options() {
+        let cli = Cli::parse_from([
+            "term-wm",
+            "--border-style",
+            "ascii",
+            "--title-align",
+            "left",
+            "--inner-gap",
+            "2",
+        ]);
+        let cmd = build_inner_command("exe".to_string(), "dev", &cli);
+        assert_eq!(
+            cmd,
+            vec![
+                "exe",
+                "--internal-session",
+                "-w",
+                "dev",
+                "--border-style",
+                "ascii",
+                "--title-align",
+                "left",
+                "--inner-gap",
+                "2"
+            ]
+        );
+        assert!(Cli::try_parse_from(["term-wm", "--border-style", "dotted"]).is_err());
+    }
+
     #[test]
     fn build_inner_command_with_runs_and_positionals() {
         let cli = Cli::parse_from(["term-wm", "-r", "htop", "--", "vim", "file.txt"]);
@@ -824,9 +1067,28 @@ mod tests {
             pty_wakeup_tx,
             current_workspace: "dev".into(),
             event_owner,
+            #[cfg(feature = "session-persistence")]
+            control: None,
+            #[cfg(feature = "session-persistence")]
+            channel_name: None,
         }
     }
 
+    /// A rename pushed by the gateway updates the app's workspace name.
+    #[cfg(feature = "session-persistence")]
+    #[test]
+    fn renamed_channel_updates_current_workspace() {
+        let mut app = test_app();
+        let shared = Arc::new(std::sync::Mutex::new("dev/main".to_string()));
+        app.channel_name = Some(Arc::clone(&shared));
+        app.sync_renamed_workspace();
+        assert_eq!(app.current_workspace, "dev");
+
+        *shared.lock().unwrap() = "ops/main".to_string();
+        app.sync_renamed_workspace();
+        assert_eq!(app.current_workspace, "ops");
+    }
+
     /// With session persistence disabled at runtime, every workspace action
     /// must fall through as unhandled (`false`) — the runtime toggle's contract.
     #[cfg(feature = "session-persistence")]
@@ -845,6 +1107,7 @@ mod tests {
         for action in [
             TermWmAction::SwitchWorkspace("prod".into()),
             TermWmAction::NewWorkspace,
+            TermWmAction::RenameWorkspace("ops".into()),
             TermWmAction::DetachCurrentClient,
         ] {
             assert!(
@@ -856,8 +1119,8 @@ mod tests {
         init(prev);
     }
 
-    /// With session persistence enabled, `SwitchWorkspace` / `NewWorkspace`
-    /// are consumed by the app (`true`) even when no gateway is reachable —
+    /// With session persistence enabled, `SwitchWorkspace` / `NewWorkspace` /
+    /// `RenameWorkspace` are consumed by the app (`true`) even when no gateway is reachable —
     /// the IPC failure is logged, not bubbled up. Hermetic: a throwaway
     /// gateway name avoids colliding with a real daemon.
     #[cfg(feature = "session-persistence")]
@@ -879,6 +1142,10 @@ mod tests {
         let mut app = test_app();
         assert!(app.handle_custom_action(&TermWmAction::SwitchWorkspace("prod".into())));
         assert!(app.handle_custom_action(&TermWmAction::NewWorkspace));
+        // A failed rename keeps the current workspace name.
+        let before = app.current_workspace.clone();
+        assert!(app.handle_custom_action(&TermWmAction::RenameWorkspace("ops".into())));
+        assert_eq!(app.current_workspace, before);
 
         // Detach with no attributed conn id: no gateway call, still consumed.
         assert!(app.handle_custom_action(&TermWmAction::DetachCurrentClient));
@@ -898,4 +1165,14 @@ mod tests {
         let cli = Cli::try_parse_from(["term-wm", "--workspace", "test"]).unwrap();
         assert!(!cli.allow_nested, "default must be false");
     }
+
+    #[test]
+    fn cli_parses_control_flag() {
+        let cli = Cli::try_parse_from(["term-wm", "--control", "-w", "dev"]).unwrap();
+        assert!(cli.control);
+        assert_eq!(cli.workspace, "dev");
+
+        let cli = Cli::try_parse_from(["term-wm"]).unwrap();
+        assert!(!cli.control, "default must be false");
+    }
 }
@@ -5,6 +5,7 @@ use std::sync::Arc;
 
 use crossbeam_channel::{Sender, bounded};
 
+use term_wm_console::TerminalCapabilities;
 use term_wm_console::console_render_target::ConsoleRenderTarget;
 use term_wm_console::draw_plan_renderer::DrawPlanRenderer;
 use term_wm_core::actions::TermWmAction;
@@ -13,9 +14,11 @@ use term_wm_core::components::Component;
 use term_wm_core::config::AppBuilder;
 use term_wm_core::debug_log::set_global_debug_log;
 use term_wm_core::engine::CoreEngine;
-use term_wm_core::events::{Event, KeyEvent};
+use term_wm_core::events::{Event, HostColorKind, KeyEvent};
 use term_wm_core::io::{EventSource, RenderTarget};
 use term_wm_core::runner::{WindowManagerHost, run_with_defaults};
+use term_wm_core::screenshot::ScreenshotFormat;
+use term_wm_core::task_scheduler::{AppTask, TaskHandle};
 use term_wm_core::window::{ClosePolicy, WindowKey, WindowManager, WindowState};
 use term_wm_core::wm_config::WmConfig;
 
@@ -24,10 +27,10 @@ use term_wm_sys_ui_components::WmSystemPanelComponent;
 use term_wm_sys_ui_components::wm_command_palette::WmCommandPaletteComponent;
 use term_wm_sys_ui_components::wm_debug_log::{WmDebugLogComponent, install_panic_hook};
 use term_wm_sys_ui_components::wm_help_overlay::WmHelpOverlayComponent;
-use term_wm_ui_components::TerminalComponent;
 use term_wm_ui_components::confirm_overlay::ConfirmOverlayComponent;
 use term_wm_ui_components::default_shell_command;
 use term_wm_ui_components::scroll_view::{ScrollKeyMode, ScrollViewComponent};
+use term_wm_ui_components::{AnimationClock, TerminalComponent};
 use term_wm_ui_facade::core_component::CoreWmComponent;
 use term_wm_ui_facade::{LayerComponent, OverlayComponent};
 
@@ -50,6 +53,8 @@ const DEFAULT_STANDALONE_MENU_ACTIONS: &[TermWmAction] = &[
     TermWmAction::ToggleTiling,
     TermWmAction::NewTerminal,
     TermWmAction::ToggleDebugWindow,
+    TermWmAction::SwitchTheme(String::new()),
+    TermWmAction::ExportScreenshot(ScreenshotFormat::Html),
     #[cfg(feature = "session-persistence")]
     TermWmAction::NewWorkspace,
 ];
@@ -274,6 +279,7 @@ impl<C: Component<TermWmAction> + 'static> TermWmApp<C> {
         // system panel) exist from construction, without any app needing to call
         // init_system_windows() itself. Idempotent.
         app.init_system_windows();
+        app.wm.load_user_themes();
         app
     }
 
@@ -468,6 +474,22 @@ impl<C: Component<TermWmAction> + 'static> TermWmApp<C> {
         }
     }
 
+    /// Turn off what the host terminal cannot do: mouse capture without
+    /// xterm mouse reporting, OSC 52 copies without clipboard support. The
+    /// probed default colors pick the automatic light/dark theme.
+    pub fn apply_terminal_capabilities(&mut self, capabilities: &TerminalCapabilities) {
+        if !capabilities.mouse {
+            self.wm.set_mouse_capture_enabled(false);
+        }
+        self.wm.set_osc52_enabled(capabilities.osc52);
+        if let Some(fg) = capabilities.default_fg {
+            self.wm.set_host_color(HostColorKind::Foreground, fg);
+        }
+        if let Some(bg) = capabilities.default_bg {
+            self.wm.set_host_color(HostColorKind::Background, bg);
+        }
+    }
+
     /// Whether a quit has been requested.
     pub fn quit_requested(&self) -> bool {
         self.should_quit
@@ -479,6 +501,17 @@ impl<C: Component<TermWmAction> + 'static> TermWmApp<C> {
         self.wm.open_window(component)
     }
 
+    /// Deliver a typed application message to the window `key`, as if one of
+    /// its components had emitted `TermWmAction::message(msg)`. Useful for
+    /// feeding background results (or setup code) into a `view!` window's
+    /// `update(&mut self, msg)`.
+    pub fn send_message<M>(&mut self, key: WindowKey, msg: M)
+    where
+        M: std::fmt::Debug + Send + Sync + 'static,
+    {
+        self.wm.process_action(key, TermWmAction::message(msg));
+    }
+
     /// Borrow the WindowManager for configuration or direct access.
     pub fn wm(
         &mut self,
@@ -525,6 +558,7 @@ impl<C: Component<TermWmAction> + 'static> TermWmApp<C> {
         F: FnOnce(&mut TermWmApp<C>, &AppSetupContext<'_>),
     {
         let mut output = ConsoleRenderTarget::new()?;
+        self.apply_terminal_capabilities(output.capabilities());
         output.enter()?;
         // Drive the loop with the unified event source so terminal (PTY) output
         // wakes the loop — not just console input. The constructors hand the app
@@ -604,6 +638,12 @@ impl<C: Component<TermWmAction> + 'static>
         );
     }
 
+    fn on_app_scheduler_ready(&mut self, handle: TaskHandle<AppTask<Self>>) {
+        // Spinners and indeterminate bars tick on the app scheduler; the
+        // clock only keeps a task pending while one of them is on screen.
+        AnimationClock::global().attach(&handle);
+    }
+
     fn handle_app_event(&mut self, event: &Event) -> bool {
         if let Event::Key(key) = event {
             *self.last_key.borrow_mut() = Some(*key);
@@ -674,6 +714,8 @@ impl<C: Component<TermWmAction> + 'static>
                             | TermWmAction::MaximizeWindow(_)
                             | TermWmAction::MinimizeWindow(_)
                             | TermWmAction::CloseWindow(_)
+                            | TermWmAction::SetWindowAccent(..)
+                            | TermWmAction::SetWindowLabel(..)
                             | TermWmAction::SendSuperKeyToWindow(_)
                             | TermWmAction::SendSuperKeyToFocusedWindow
                     );
@@ -681,8 +723,21 @@ impl<C: Component<TermWmAction> + 'static>
                     let always_pass = always_pass
                         || matches!(
                             item.action,
-                            TermWmAction::SwitchWorkspace(_) | TermWmAction::NewWorkspace
+                            TermWmAction::SwitchWorkspace(_)
+                                | TermWmAction::NewWorkspace
+                                | TermWmAction::RenameWorkspace(_)
                         );
+                    let always_pass = always_pass
+                        || (matches!(item.action, TermWmAction::SwitchTheme(_))
+                            && supported.contains(&TermWmAction::SwitchTheme(String::new())));
+                    let always_pass = always_pass
+                        || (matches!(
+                            item.action,
+                            TermWmAction::ExportScreenshot(_)
+                                | TermWmAction::ExportWindowScreenshot(..)
+                        ) && supported
+                            .iter()
+                            .any(|a| matches!(a, TermWmAction::ExportScreenshot(_))));
                     supported.contains(&item.action) || always_pass
                 }
                 MenuDisplayItem::Separator => true,
@@ -695,7 +750,7 @@ impl<C: Component<TermWmAction> + 'static>
 
     fn open_help_overlay(&mut self) {
         let kb = self.wm.keybindings().clone();
-        let mut h = WmHelpOverlayComponent::new(self.wm.app_ctx(), kb);
+        let mut h = WmHelpOverlayComponent::new(self.wm.app_ctx(), kb, self.wm.theme());
         h.show();
         h.set_selection_enabled(self.wm.clipboard_enabled());
         self.wm.open_help_overlay(OverlayComponent::Help(h));
@@ -769,6 +824,8 @@ mod tests {
                 TermWmAction::ToggleTiling,
                 TermWmAction::NewTerminal,
                 TermWmAction::ToggleDebugWindow,
+                TermWmAction::SwitchTheme(String::new()),
+                TermWmAction::ExportScreenshot(ScreenshotFormat::Html),
                 TermWmAction::NewWorkspace,
             ],
             "new_custom must expose exactly its configured allow-list, not the full default set"
@@ -809,6 +866,45 @@ mod tests {
         let _ = core;
     }
 
+    #[test]
+    fn send_message_reaches_the_window_update() {
+        #[derive(Default)]
+        struct Inbox(Vec<String>);
+        impl Component<TermWmAction> for Inbox {
+            fn update(
+                &mut self,
+                action: TermWmAction,
+                _ctx: &term_wm_core::component_context::ComponentContext,
+                _actions: &mut std::collections::VecDeque<(WindowKey, TermWmAction)>,
+            ) {
+                if let Some(text) = action.as_message::<String>() {
+                    self.0.push(text.clone());
+                }
+            }
+            fn render(
+                &mut self,
+                _backend: &mut dyn term_wm_render::RenderBackend,
+                _area: term_wm_layout_engine::LayoutRect,
+                _ctx: &term_wm_core::component_context::ComponentContext,
+                _registry: &mut term_wm_core::hitbox_registry::HitboxRegistry,
+            ) {
+            }
+        }
+
+        let mut app = TermWmApp::<Inbox>::new_custom(AppContext::new("test", "0.0.0"));
+        let first = app.open_window(AppRootComponent::Custom(Inbox::default()));
+        let second = app.open_window(AppRootComponent::Custom(Inbox::default()));
+        app.send_message(first, "loaded".to_string());
+        app.send_message(first, 7_u32);
+
+        let inbox = |app: &mut TermWmApp<Inbox>, key| match app.wm().component_for_key_mut(key) {
+            Some(AppRootComponent::Custom(inbox)) => inbox.0.clone(),
+            _ => panic!("custom window"),
+        };
+        assert_eq!(inbox(&mut app, first), ["loaded"]);
+        assert!(inbox(&mut app, second).is_empty());
+    }
+
     #[test]
     fn new_with_actions_initializes_system_windows() {
         let mut app = TermWmApp::<NoopComponent>::new_with_actions(
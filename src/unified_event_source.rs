@@ -102,6 +102,9 @@ pub struct UnifiedEventSource {
     /// Shared event owner — updated when an event is popped from the buffer.
     /// Cloned into `App` for action attribution.
     event_owner: Arc<Mutex<Option<usize>>>,
+    /// Optional tap on window exits, fed as the runner drains them (control
+    /// mode reports `process_exited` before the window closes).
+    exit_observer: Option<Sender<WindowKey>>,
 }
 
 /// Outcome of processing one unified-channel event during `poll`.
@@ -154,11 +157,17 @@ impl UnifiedEventSource {
                 pending_work: false,
                 max_sleep_duration: None,
                 event_owner: event_owner.clone(),
+                exit_observer: None,
             },
             event_owner,
         ))
     }
 
+    /// Report every exited window to `tx` as well as to the runner.
+    pub fn set_exit_observer(&mut self, tx: Sender<WindowKey>) {
+        self.exit_observer = Some(tx);
+    }
+
     /// Return a sender that PTY reader threads can use to send wakeup pings.
     pub fn pty_wakeup_tx(&self) -> Sender<UnifiedEvent> {
         self.tx.clone()
@@ -824,6 +833,11 @@ impl EventSource for UnifiedEventSource {
     }
 
     fn take_exited_windows(&mut self) -> Vec<WindowKey> {
+        if let Some(observer) = &self.exit_observer {
+            for &key in &self.exited_windows {
+                let _ = observer.try_send(key);
+            }
+        }
         std::mem::take(&mut self.exited_windows)
     }
 
@@ -891,6 +905,7 @@ mod tests {
             direct_input_changed: Vec::new(),
             pending_redraw: false,
             event_owner: std::sync::Arc::new(std::sync::Mutex::new(None)),
+            exit_observer: None,
             pending_event: None,
             input_buffer: VecDeque::new(),
             signal_received: false,
@@ -982,6 +997,7 @@ mod tests {
             direct_input_changed: Vec::new(),
             pending_redraw: false,
             event_owner: std::sync::Arc::new(std::sync::Mutex::new(None)),
+            exit_observer: None,
             pending_event: None,
             input_buffer: VecDeque::new(),
             signal_received: false,
@@ -1065,6 +1081,7 @@ mod tests {
             direct_input_changed: Vec::new(),
             pending_redraw: false,
             event_owner: std::sync::Arc::new(std::sync::Mutex::new(None)),
+            exit_observer: None,
             pending_event: None,
             input_buffer: VecDeque::new(),
             signal_received: false,
@@ -1114,6 +1131,7 @@ mod tests {
             direct_input_changed: Vec::new(),
             pending_redraw: false,
             event_owner: std::sync::Arc::new(std::sync::Mutex::new(None)),
+            exit_observer: None,
             pending_event: None,
             input_buffer: VecDeque::new(),
             signal_received: false,
@@ -1173,6 +1191,7 @@ mod tests {
             direct_input_changed: Vec::new(),
             pending_redraw: false,
             event_owner: std::sync::Arc::new(std::sync::Mutex::new(None)),
+            exit_observer: None,
             pending_event: None,
             input_buffer: VecDeque::new(),
             signal_received: false,
@@ -1204,6 +1223,7 @@ mod tests {
             direct_input_changed: Vec::new(),
             pending_redraw: false,
             event_owner: std::sync::Arc::new(std::sync::Mutex::new(None)),
+            exit_observer: None,
             pending_event: None,
             input_buffer: VecDeque::new(),
             signal_received: false,
@@ -1233,6 +1253,7 @@ mod tests {
             direct_input_changed: Vec::new(),
             pending_redraw: false,
             event_owner: std::sync::Arc::new(std::sync::Mutex::new(None)),
+            exit_observer: None,
             pending_event: None,
             input_buffer: VecDeque::new(),
             signal_received: false,
@@ -1272,6 +1293,7 @@ mod tests {
             direct_input_changed: Vec::new(),
             pending_redraw: false,
             event_owner: std::sync::Arc::new(std::sync::Mutex::new(None)),
+            exit_observer: None,
             pending_event: None,
             input_buffer: VecDeque::new(),
             signal_received: false,
@@ -1291,6 +1313,41 @@ mod tests {
         assert!(again.is_empty(), "second call must drain");
     }
 
+    #[test]
+    fn exit_observer_sees_exits_as_the_runner_drains_them() {
+        use super::EventSource;
+        let (_tx, rx) = bounded(EVENT_CHANNEL_CAPACITY);
+        let (console, console_rx, _console_tx) = test_console();
+        let (observer_tx, observer_rx) = bounded(4);
+        let key = WindowKey::default();
+        let mut source = UnifiedEventSource {
+            rx,
+            tx: _tx,
+            console: Some(console),
+            console_rx: Some(console_rx),
+            console_alive: true,
+            dirty_windows: HashSet::new(),
+            exited_windows: vec![key],
+            direct_input_changed: Vec::new(),
+            pending_redraw: false,
+            event_owner: std::sync::Arc::new(std::sync::Mutex::new(None)),
+            exit_observer: None,
+            pending_event: None,
+            input_buffer: VecDeque::new(),
+            signal_received: false,
+            normalizer: KeyboardNormalizer::new(),
+            last_event_at: None,
+            frame_pacer: FramePacer::new(),
+            pending_work: false,
+            max_sleep_duration: None,
+        };
+        source.set_exit_observer(observer_tx);
+
+        assert_eq!(EventSource::take_exited_windows(&mut source), vec![key]);
+        assert_eq!(observer_rx.try_recv().ok(), Some(key));
+        assert!(observer_rx.try_recv().is_err());
+    }
+
     /// Regression: `take_dirty_windows` must be reachable through the
     /// `EventSource` trait so that generic runner code (`D: EventSource`)
     /// actually consumes accumulated dirty keys.  Without the trait
@@ -1315,6 +1372,7 @@ mod tests {
             direct_input_changed: Vec::new(),
             pending_redraw: false,
             event_owner: std::sync::Arc::new(std::sync::Mutex::new(None)),
+            exit_observer: None,
             pending_event: None,
             input_buffer: VecDeque::new(),
             signal_received: false,
@@ -1350,6 +1408,7 @@ mod tests {
             direct_input_changed: Vec::new(),
             pending_redraw: false,
             event_owner: std::sync::Arc::new(std::sync::Mutex::new(None)),
+            exit_observer: None,
             pending_event: None,
             input_buffer: VecDeque::new(),
             signal_received: false,
@@ -1403,6 +1462,7 @@ mod tests {
             direct_input_changed: Vec::new(),
             pending_redraw: false,
             event_owner: std::sync::Arc::new(std::sync::Mutex::new(None)),
+            exit_observer: None,
             pending_event: None,
             input_buffer: VecDeque::new(),
             signal_received: false,
@@ -1441,6 +1501,7 @@ mod tests {
             direct_input_changed: Vec::new(),
             pending_redraw: false,
             event_owner: std::sync::Arc::new(std::sync::Mutex::new(None)),
+            exit_observer: None,
             pending_event: None,
             input_buffer: VecDeque::new(),
             signal_received: false,
@@ -1489,6 +1550,7 @@ mod tests {
             direct_input_changed: Vec::new(),
             pending_redraw: false,
             event_owner: std::sync::Arc::new(std::sync::Mutex::new(None)),
+            exit_observer: None,
             pending_event: None,
             input_buffer: VecDeque::new(),
             signal_received: false,
@@ -1532,6 +1594,7 @@ mod tests {
             direct_input_changed: Vec::new(),
             pending_redraw: false,
             event_owner: std::sync::Arc::new(std::sync::Mutex::new(None)),
+            exit_observer: None,
             pending_event: None,
             input_buffer: VecDeque::new(),
             signal_received: false,
@@ -1581,6 +1644,7 @@ mod tests {
             direct_input_changed: Vec::new(),
             pending_redraw: false,
             event_owner: event_owner.clone(),
+            exit_observer: None,
             pending_event: None,
             input_buffer: VecDeque::new(),
             signal_received: false,
@@ -1682,6 +1746,7 @@ mod tests {
             direct_input_changed: Vec::new(),
             pending_redraw: false,
             event_owner: std::sync::Arc::new(std::sync::Mutex::new(None)),
+            exit_observer: None,
             pending_event: None,
             input_buffer: VecDeque::new(),
             signal_received: false,
@@ -1737,6 +1802,7 @@ mod tests {
             direct_input_changed: Vec::new(),
             pending_redraw: false,
             event_owner: event_owner.clone(),
+            exit_observer: None,
             pending_event: None,
             input_buffer: VecDeque::new(),
             signal_received: false,
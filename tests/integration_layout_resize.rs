@@ -3,7 +3,7 @@
 mod tests {
     use term_wm::layout::Direction;
     use term_wm::layout::tiling::{InsertPosition, LayoutNode};
-    use term_wm_layout_engine::LayoutRect;
+    use term_wm_layout_engine::{Gaps, LayoutRect};
 
     #[test]
     fn test_vertical_resize_precision() {
@@ -34,7 +34,7 @@ mod tests {
         // Perform 5 separate 1-pixel drag operations
         for _ in 0..5 {
             // Drag handle #0 (vertical) by +1
-            let success = node.apply_drag(area, &[], 0, Direction::Vertical, 1, 4);
+            let success = node.apply_drag(area, Gaps::default(), &[], 0, Direction::Vertical, 1, 4);
             assert!(success, "apply_drag returned false");
 
             let regions = node.layout_rects(area);
@@ -7,9 +7,9 @@ use serial_test::serial;
 use std::sync::Arc;
 use std::time::Duration;
 use term_session_muxio_service_definitions::{
-    Attach, AttachRequest, CloseSession, KillChannel, KillClient, ResizePty,
-    STREAM_INPUT_METHOD_ID, SUBSCRIBE_OUTPUT_METHOD_ID, ShutdownGateway, Spawn, SpawnRequest,
-    SpawnResponse,
+    Attach, AttachRequest, CloseSession, KillChannel, KillClient, NegotiateOutput, OutputCodec,
+    OutputDecoder, ResizePty, STREAM_INPUT_METHOD_ID, SUBSCRIBE_OUTPUT_METHOD_ID, ShutdownGateway,
+    Spawn, SpawnRequest, SpawnResponse,
 };
 
 mod common;
@@ -80,6 +80,63 @@ async fn session_input_output_roundtrip() {
     guard.shutdown().await;
 }
 
+#[tokio::test]
+async fn session_output_negotiated_lz4_roundtrip() {
+    let mock = get_mock_bin();
+    let (client, _conn_id, guard) = spawn_session(&test_channel("test/output_lz4")).await;
+    let codec = NegotiateOutput::call(&*client, vec![OutputCodec::Lz4])
+        .await
+        .unwrap();
+    assert_eq!(codec, OutputCodec::Lz4);
+    Spawn::call(
+        &*client,
+        SpawnRequest {
+            cmd: Some(vec![mock, "echo".into()]),
+            cols: TEST_COLS,
+            rows: TEST_ROWS,
+            cwd: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    let (_, mut frames) = client
+        .open_channel(SUBSCRIBE_OUTPUT_METHOD_ID, 0)
+        .await
+        .unwrap();
+    let (writer, _) = client
+        .open_channel(STREAM_INPUT_METHOD_ID, 0)
+        .await
+        .unwrap();
+    // Decode in front of the usual reader, remembering whether any frame
+    // actually arrived compressed.
+    let (tx, mut reader) = tokio::sync::mpsc::unbounded_channel();
+    let compressed = Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let saw_lz4 = Arc::clone(&compressed);
+    tokio::spawn(async move {
+        let mut decoder = OutputDecoder::new(OutputCodec::Lz4);
+        while let Some(Ok(frame)) = frames.recv().await {
+            if frame.first() == Some(&0x01) {
+                saw_lz4.store(true, std::sync::atomic::Ordering::SeqCst);
+            }
+            if tx.send(Ok(decoder.decode(&frame).unwrap())).is_err() {
+                break;
+            }
+        }
+    });
+    let line = format!("{}done\n", "abc".repeat(300));
+    writer.send(line.into_bytes()).unwrap();
+
+    let output = wait_for_output(&mut reader, b"abcabcdone", Duration::from_secs(3)).await;
+    assert!(
+        output.windows(10).any(|w| w == b"abcabcdone"),
+        "Expected the echoed line in output, got: {:?}",
+        String::from_utf8_lossy(&output)
+    );
+    assert!(compressed.load(std::sync::atomic::Ordering::SeqCst));
+    guard.shutdown().await;
+}
+
 /// Regression test for the input-reordering bug observed with IME voice typing
 /// over SSH (termux + Google voice typing): a burst of many stream input
 /// chunks must reach the PTY in the exact order they were sent.
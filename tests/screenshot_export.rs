@@ -0,0 +1,94 @@
+//! Screenshot export through the full `render_app` pipeline.
+
+use std::sync::Arc;
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use term_wm::AppContext;
+use term_wm::layout::tiling::{LayoutNode, TilingLayout};
+use term_wm::render_app;
+use term_wm::screenshot::{ScreenshotFormat, ScreenshotRequest};
+use term_wm::window::{WindowKey, WindowManager};
+use term_wm::wm_config::WmConfig;
+use term_wm_console::RatatuiBackend;
+use term_wm_console::draw_plan_renderer::DrawPlanRenderer;
+use term_wm_core::components::NoopComponent;
+use term_wm_core::engine::CoreEngine;
+
+const AREA: Rect = Rect {
+    x: 0,
+    y: 0,
+    width: 80,
+    height: 24,
+};
+
+fn wm_in(dir: &std::path::Path) -> (WindowManager<NoopComponent>, WindowKey) {
+    let config = WmConfig {
+        screenshot_dir: Some(dir.to_path_buf()),
+        ..Default::default()
+    };
+    let mut wm = WindowManager::<NoopComponent>::with_config(
+        config,
+        Arc::new(AppContext::new("test", "0.0.0")),
+        None,
+        term_wm_core::window::LayerManager::new(),
+        std::collections::HashMap::new(),
+    );
+    wm.set_panel_visible(false);
+    let key = wm.create_window(NoopComponent);
+    wm.transition_window(key, term_wm_core::window::WindowState::Mapped);
+    wm.set_window_title(key, "shot-me");
+    wm.set_managed_layout(TilingLayout::new(LayoutNode::leaf(key)));
+    (wm, key)
+}
+
+fn exported(dir: &std::path::Path) -> Vec<std::path::PathBuf> {
+    let mut files: Vec<_> = std::fs::read_dir(dir)
+        .map(|entries| entries.map(|e| e.expect("dir entry").path()).collect())
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+#[test]
+fn screen_and_window_exports_land_in_the_configured_dir() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let (mut wm, key) = wm_in(dir.path());
+    let mut engine = CoreEngine::new();
+    let mut renderer = DrawPlanRenderer::new();
+    let mut backend = RatatuiBackend::new_simple(Buffer::empty(AREA), AREA);
+
+    render_app(&mut backend, &mut wm, &mut engine, &mut renderer);
+    assert!(exported(dir.path()).is_empty(), "nothing requested yet");
+
+    wm.request_screenshot(ScreenshotRequest {
+        window: None,
+        format: ScreenshotFormat::Ansi,
+    });
+    render_app(&mut backend, &mut wm, &mut engine, &mut renderer);
+    wm.wait_for_screenshot_exports();
+    let files = exported(dir.path());
+    assert_eq!(files.len(), 1);
+    let ansi = std::fs::read_to_string(&files[0]).expect("read export");
+    assert_eq!(ansi.lines().count(), usize::from(AREA.height));
+    assert!(ansi.contains("shot-me"), "window title is in the frame");
+    let toasts = wm
+        .notifications()
+        .renderable()
+        .map(|n| n.message.to_string())
+        .collect::<Vec<_>>();
+    assert!(toasts.contains(&format!("Screenshot saved to {}", files[0].display())));
+
+    wm.request_screenshot(ScreenshotRequest {
+        window: Some(key),
+        format: ScreenshotFormat::Html,
+    });
+    render_app(&mut backend, &mut wm, &mut engine, &mut renderer);
+    wm.wait_for_screenshot_exports();
+    let html = exported(dir.path())
+        .into_iter()
+        .find(|path| path.extension().is_some_and(|ext| ext == "html"))
+        .expect("window export");
+    let html = std::fs::read_to_string(html).expect("read export");
+    assert!(html.contains("shot-me"));
+}
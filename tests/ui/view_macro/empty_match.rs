@@ -0,0 +1,10 @@
+use term_wm::view;
+
+fn main() {
+    let n = 1;
+    let _ = view! {
+        <VStack>
+            { match n {} }
+        </VStack>
+    };
+}
@@ -0,0 +1,9 @@
+use term_wm::view;
+
+fn main() {
+    let _ = view! {
+        <VStack>
+            { for n in 0..3 { } }
+        </VStack>
+    };
+}
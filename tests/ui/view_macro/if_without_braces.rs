@@ -0,0 +1,10 @@
+use term_wm::view;
+
+fn main() {
+    let ready = true;
+    let _ = view! {
+        <VStack>
+            { if ready <Label text="ready" /> }
+        </VStack>
+    };
+}
@@ -184,3 +184,454 @@ fn view_macro_box_border_false_renders_no_border() {
     );
     assert!(content.contains("hi"), "content should render: {content:?}");
 }
+
+#[test]
+fn view_macro_text_input_and_text_area_tags() {
+    let ctx = Arc::new(term_wm::AppContext::new("test", "0.0.0"));
+    let mut wm = AppBuilder::<LayerComponent>::new()
+        .app_ctx(ctx)
+        .build::<_, NoopOverlay>()
+        .expect("test build");
+
+    let key = wm.open_window(view! {
+        <VStack>
+            <TextInput placeholder="Name" history=10 validate={|v: &str| {
+                if v.len() <= 8 { Ok(()) } else { Err("too long".to_string()) }
+            }} />
+            <TextArea value="line one\nline two" rows=2 />
+        </VStack>
+    });
+
+    let comp = wm.component_for_key_mut(key).expect("window component");
+    let area = LayoutRect {
+        x: 0,
+        y: 0,
+        width: 20,
+        height: 6,
+    };
+    let ctx = term_wm::ComponentContext::new(true).with_screen_area(area);
+    let typed = term_wm::Event::Key(term_wm::events::KeyEvent::new(
+        term_wm::events::KeyCode::Char('x'),
+        term_wm::events::KeyModifiers::NONE,
+        term_wm::events::KeyKind::Press,
+    ));
+    assert!(comp.handle_events(&typed, &ctx).is_consumed());
+
+    let buffer = ratatui::buffer::Buffer::empty(ratatui::layout::Rect::new(0, 0, 20, 6));
+    let mut backend = term_wm_console::RatatuiBackend::new_simple(
+        buffer,
+        ratatui::layout::Rect::new(0, 0, 20, 6),
+    );
+    let mut registry = term_wm::hitbox_registry::HitboxRegistry::new();
+    comp.render(&mut backend, area, &ctx, &mut registry);
+
+    let content: String = backend
+        .buffer
+        .content()
+        .iter()
+        .map(|c| c.symbol())
+        .collect();
+    assert!(content.starts_with('x'), "typed text: {content:?}");
+    assert!(content.contains("line one"), "text area: {content:?}");
+    assert!(content.contains("line two"), "text area: {content:?}");
+}
+
+#[test]
+fn view_macro_tree_view_tag() {
+    use term_wm::TreeNode;
+
+    let ctx = Arc::new(term_wm::AppContext::new("test", "0.0.0"));
+    let mut wm = AppBuilder::<LayerComponent>::new()
+        .app_ctx(ctx)
+        .build::<_, NoopOverlay>()
+        .expect("test build");
+
+    let key = wm.open_window(view! {
+        <TreeView
+            roots={vec![TreeNode::lazy("root", 0u8)]}
+            loader={|_: &u8| vec![TreeNode::leaf("child", 1u8)]}
+            multiSelect={true}
+        />
+    });
+
+    let comp = wm.component_for_key_mut(key).expect("window component");
+    let area = LayoutRect {
+        x: 0,
+        y: 0,
+        width: 20,
+        height: 3,
+    };
+    let ctx = term_wm::ComponentContext::new(true).with_screen_area(area);
+    let expand = term_wm::Event::Key(term_wm::events::KeyEvent::new(
+        term_wm::events::KeyCode::Right,
+        term_wm::events::KeyModifiers::NONE,
+        term_wm::events::KeyKind::Press,
+    ));
+    assert!(comp.handle_events(&expand, &ctx).is_consumed());
+
+    let buffer = ratatui::buffer::Buffer::empty(ratatui::layout::Rect::new(0, 0, 20, 3));
+    let mut backend = term_wm_console::RatatuiBackend::new_simple(
+        buffer,
+        ratatui::layout::Rect::new(0, 0, 20, 3),
+    );
+    let mut registry = term_wm::hitbox_registry::HitboxRegistry::new();
+    comp.render(&mut backend, area, &ctx, &mut registry);
+
+    let content: String = backend
+        .buffer
+        .content()
+        .iter()
+        .map(|c| c.symbol())
+        .collect();
+    assert!(content.starts_with("▾ root"), "tree: {content:?}");
+    assert!(content.contains("child"), "tree: {content:?}");
+}
+
+#[test]
+fn view_macro_form_tag() {
+    use term_wm::TermWmAction;
+    use term_wm::events::{KeyCode, KeyEvent, KeyKind, KeyModifiers};
+
+    let mut form = view! {
+        <Form onSubmit={TermWmAction::Help} parser={|values: &term_wm::FormValues| {
+            Ok(values.text("name").unwrap_or_default().to_uppercase())
+        }}>
+            <Input name="name" label="Name" placeholder="layout" required />
+            <Toggle name="float" label="Floating" checked />
+            <Select name="mode" label="Mode" options={["Tiled", "Monocle"]} selected=1 />
+            <Submit label="Save" />
+        </Form>
+    };
+    let press = |code| term_wm::Event::Key(KeyEvent::new(code, KeyModifiers::NONE, KeyKind::Press));
+    let ctx = term_wm::ComponentContext::new(true);
+
+    form.handle_events(&press(KeyCode::Enter), &ctx);
+    assert_eq!(form.field("name").and_then(|f| f.error()), Some("Required"));
+    assert_eq!(form.values().flag("float"), Some(true));
+    assert_eq!(form.values().choice("mode"), Some(1));
+
+    form.handle_events(&press(KeyCode::Char('w')), &ctx);
+    let result = form.handle_events(&press(KeyCode::Enter), &ctx);
+    assert!(matches!(
+        result,
+        term_wm::EventResult::Action(TermWmAction::Help)
+    ));
+    assert_eq!(form.take_result().as_deref(), Some("W"));
+
+    let area = LayoutRect {
+        x: 0,
+        y: 0,
+        width: 30,
+        height: 5,
+    };
+    let rect = ratatui::layout::Rect::new(0, 0, 30, 5);
+    let mut backend =
+        term_wm_console::RatatuiBackend::new_simple(ratatui::buffer::Buffer::empty(rect), rect);
+    let mut registry = term_wm::hitbox_registry::HitboxRegistry::new();
+    form.render(&mut backend, area, &ctx, &mut registry);
+    let content: String = backend
+        .buffer
+        .content()
+        .iter()
+        .map(|c| c.symbol())
+        .collect();
+    assert!(content.contains("[ Save ]"), "form: {content:?}");
+    assert!(!content.contains("Cancel"), "form: {content:?}");
+}
+
+/// Render `comp` into a `width`×`height` buffer and return its rows.
+fn render_rows(
+    comp: &mut impl Component<term_wm::TermWmAction>,
+    width: u16,
+    height: u16,
+) -> Vec<String> {
+    let rect = ratatui::layout::Rect::new(0, 0, width, height);
+    let mut backend =
+        term_wm_console::RatatuiBackend::new_simple(ratatui::buffer::Buffer::empty(rect), rect);
+    let area = LayoutRect {
+        x: 0,
+        y: 0,
+        width,
+        height,
+    };
+    let ctx = term_wm::ComponentContext::new(true).with_screen_area(area);
+    let mut registry = term_wm::hitbox_registry::HitboxRegistry::new();
+    comp.render(&mut backend, area, &ctx, &mut registry);
+    (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| backend.buffer[(x, y)].symbol().to_string())
+                .collect::<String>()
+                .trim_end()
+                .to_string()
+        })
+        .collect()
+}
+
+#[test]
+fn view_macro_for_loop_builds_a_list_of_items() {
+    let names = ["alpha", "beta", "gamma"];
+    let mut comp = view! {
+        <VStack>
+            <Label text="Sessions" />
+            { for name in names.iter().copied() {
+                <Label text={name} />
+            } }
+        </VStack>
+    };
+    let rows = render_rows(&mut comp, 12, 4);
+    assert_eq!(rows, ["Sessions", "alpha", "beta", "gamma"]);
+
+    let mut row = view! {
+        <HStack gap=1>
+            { for n in 0..3 { <Label text={n.to_string()} /> } }
+        </HStack>
+    };
+    let rows = render_rows(&mut row, 5, 1);
+    assert_eq!(rows[0], "0 1 2");
+}
+
+#[test]
+fn view_macro_if_and_match_pick_a_branch() {
+    fn status(online: bool, count: usize) -> impl Component<term_wm::TermWmAction> {
+        view! {
+            <VStack>
+                { if online {
+                    <Label text="online" />
+                } else {
+                    <Button label="Reconnect" action={term_wm::TermWmAction::Help} />
+                } }
+                { if let 1 = count { <Label text="one" /> } }
+                { match count {
+                    0 => { <Label text="none" /> }
+                    n if n > 5 => { <Label text="many" /> }
+                    _ => { <HStack><Label text="some" /></HStack> }
+                } }
+            </VStack>
+        }
+    }
+    let rows = render_rows(&mut status(true, 1), 12, 3);
+    assert_eq!(rows, ["online", "one", "some"]);
+    // The missing `else` leaves a stretch placeholder row.
+    let rows = render_rows(&mut status(false, 9), 12, 5);
+    assert!(rows[1].contains("Reconnect"), "{rows:?}");
+    assert_eq!(rows[3], "");
+    assert_eq!(rows[4], "many", "{rows:?}");
+}
+
+/// A downstream component exposed as `<Badge>`: `text` and `count` are
+/// required, `prefix` is optional and `hidden` is a flag.
+struct BadgeComponent {
+    text: String,
+    visible: bool,
+}
+
+#[term_wm::view_tag(Badge)]
+impl BadgeComponent {
+    pub fn new(text: impl Into<String>, count: usize) -> Self {
+        Self {
+            text: format!("{} ({count})", text.into()),
+            visible: true,
+        }
+    }
+
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        self.text.insert_str(0, prefix);
+        self
+    }
+
+    pub fn hidden(mut self) -> Self {
+        self.visible = false;
+        self
+    }
+}
+
+impl Component<term_wm::TermWmAction> for BadgeComponent {
+    fn desired_height(&self, _width: u16) -> u16 {
+        1
+    }
+
+    fn render(
+        &mut self,
+        backend: &mut dyn term_wm::RenderBackend,
+        area: LayoutRect,
+        ctx: &term_wm::ComponentContext,
+        registry: &mut term_wm::hitbox_registry::HitboxRegistry,
+    ) {
+        if self.visible {
+            term_wm::LabelComponent::new(self.text.as_str()).render(backend, area, ctx, registry);
+        }
+    }
+}
+
+#[test]
+fn view_macro_view_tag_components() {
+    let names = vec!["alpha".to_string(), "beta".to_string()];
+    let column =
+        term_wm::TableColumn::new("Name", term_wm::GridConstraint::Fixed(8), |s: &String| {
+            s.clone()
+        });
+    let mut comp = view! {
+        <VStack>
+            <Badge text="inbox" count=3 />
+            <Badge count={2} text={String::from("sent")} prefix="> " />
+            <Badge text="drafts" count=0 hidden />
+            <Badge text="spam" count=1 hidden={false} />
+            <Table columns={vec![column]} rows={names} />
+        </VStack>
+    };
+    let rows = render_rows(&mut comp, 12, 8);
+    assert_eq!(rows[..4], ["inbox (3)", "> sent (2)", "", "spam (1)"]);
+    assert!(rows[4..].iter().any(|r| r.contains("alpha")), "{rows:?}");
+    assert!(rows[4..].iter().any(|r| r.contains("beta")), "{rows:?}");
+}
+
+#[test]
+fn view_macro_progress_and_meter_tags() {
+    let clock = term_wm::AnimationClock::new();
+    let mut comp = view! {
+        <VStack>
+            <ProgressBar label="copy" progress={0.5} />
+            <ProgressBar indeterminate clock={clock.clone()} />
+            <Spinner label="Syncing" frames={term_wm::LINE_FRAMES} clock={clock.clone()} />
+            <Gauge value=30 max=60 label="mem" warning=40 />
+            <Sparkline data={vec![1u64, 2, 4, 8]} />
+        </VStack>
+    };
+    let rows = render_rows(&mut comp, 14, 5);
+    assert_eq!(
+        rows,
+        [
+            "copy ██░░  50%",
+            "███░░░░░░░░░░░",
+            "- Syncing",
+            "mem ███░░  50%",
+            "          ▁▂▄█",
+        ]
+    );
+    clock.tick();
+    assert_eq!(render_rows(&mut comp, 14, 5)[2], "\\ Syncing");
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum CounterMsg {
+    Add(i64),
+    Reset,
+}
+
+struct Counter {
+    value: i64,
+}
+
+impl Counter {
+    fn view(&self) -> impl Component<term_wm::TermWmAction> + '_ {
+        view! {
+            <VStack>
+                <Label text={format!("count: {}", self.value)} />
+                <Button label="+5" msg={CounterMsg::Add(5)} />
+                { term_wm::components::MessageComponent::new(ResetKey) }
+            </VStack>
+        }
+    }
+
+    fn update(&mut self, msg: CounterMsg) {
+        match msg {
+            CounterMsg::Add(n) => self.value += n,
+            CounterMsg::Reset => self.value = 0,
+        }
+    }
+}
+
+term_wm_core::impl_view_component!(Counter, msg = CounterMsg);
+
+/// A leaf speaking `CounterMsg` directly: any key press asks for a reset.
+struct ResetKey;
+
+impl Component<CounterMsg> for ResetKey {
+    fn handle_events(
+        &mut self,
+        event: &term_wm::Event,
+        _ctx: &term_wm::ComponentContext,
+    ) -> term_wm::EventResult<CounterMsg> {
+        match event {
+            term_wm::Event::Key(_) => term_wm::EventResult::Action(CounterMsg::Reset),
+            _ => term_wm::EventResult::Ignored,
+        }
+    }
+
+    fn desired_height(&self, _width: u16) -> u16 {
+        1
+    }
+
+    fn render(
+        &mut self,
+        _backend: &mut dyn term_wm::RenderBackend,
+        _area: LayoutRect,
+        _ctx: &term_wm::ComponentContext,
+        _registry: &mut term_wm::hitbox_registry::HitboxRegistry,
+    ) {
+    }
+}
+
+#[test]
+fn view_macro_typed_messages_reach_the_window_update() {
+    use term_wm::TermWmAction;
+    use term_wm::events::{
+        KeyCode, KeyEvent, KeyKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    };
+
+    let mut counter = Counter { value: 1 };
+    assert_eq!(render_rows(&mut counter, 20, 5)[0], "count: 1");
+
+    // The button (rows 1..4) emits the typed message as an action...
+    let ctx = term_wm::ComponentContext::new(true).with_screen_area(LayoutRect {
+        x: 0,
+        y: 0,
+        width: 20,
+        height: 5,
+    });
+    let click = term_wm::Event::Mouse(MouseEvent {
+        kind: MouseEventKind::Press(MouseButton::Left),
+        modifiers: KeyModifiers::NONE,
+        column: 2,
+        row: 2,
+    });
+    let action = counter
+        .handle_events(&click, &ctx)
+        .into_action()
+        .expect("button click");
+    assert_eq!(action.as_message(), Some(&CounterMsg::Add(5)));
+
+    // ...which the window manager routes back to the owning window.
+    let ctx = Arc::new(term_wm::AppContext::new("test", "0.0.0"));
+    let mut wm = AppBuilder::<LayerComponent>::new()
+        .app_ctx(ctx)
+        .build::<_, NoopOverlay>()
+        .expect("test build");
+    let key = wm.open_window(counter);
+    wm.process_action(key, action.clone());
+    wm.process_action(key, action);
+    let counter = wm.component_for_key_mut(key).expect("window component");
+    assert_eq!(counter.value, 11);
+
+    // A `Component<CounterMsg>` child speaks the message type natively.
+    let key_press = term_wm::Event::Key(KeyEvent::new(
+        KeyCode::Char('r'),
+        KeyModifiers::NONE,
+        KeyKind::Press,
+    ));
+    let reset = counter
+        .handle_events(&key_press, &term_wm::ComponentContext::new(true))
+        .into_action()
+        .expect("reset message");
+    assert!(matches!(&reset, TermWmAction::Message(m) if m.is::<CounterMsg>()));
+    wm.process_action(key, reset);
+    assert_eq!(wm.component_for_key_mut(key).map(|c| c.value), Some(0));
+}
+
+/// Control-flow misuse is rejected by `view!` itself, with the messages
+/// snapshotted next to each case in `tests/ui/view_macro/`.
+#[test]
+fn view_macro_control_flow_misuse_fails_to_compile() {
+    trybuild::TestCases::new().compile_fail("tests/ui/view_macro/*.rs");
+}